@@ -0,0 +1,851 @@
+use crate::config::EditorGrid;
+use crate::{beautify, color};
+use ab_glyph::{point, Font, FontVec, PxScale, ScaleFont};
+use chrono::{DateTime, Local};
+use image::{Rgba, RgbaImage};
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+/// Families tried in order for burned-in text; the first installed wins.
+const LABEL_FAMILIES: [&str; 3] = ["Helvetica Neue", "Helvetica", "Arial"];
+
+/// Arrow heads are this many pixels long and wide, as in the editor.
+const ARROW_HEAD_SIZE: f32 = 15.0;
+
+/// How close, in image pixels, an edge must be to a guide to snap onto it;
+/// the editor uses the same distance.
+const SNAP_THRESHOLD: f32 = 6.0;
+
+/// The editor's annotation shadow: 35% black, 2 pixels down and right,
+/// blurred by 8 (a Gaussian of half that).
+const SHADOW_OPACITY: f32 = 0.35;
+const SHADOW_OFFSET: f32 = 2.0;
+const SHADOW_SIGMA: f32 = 4.0;
+
+/// Line spacing of multi-line text as a multiple of the font size; the
+/// editor's canvas library uses the same default.
+const TEXT_LINE_HEIGHT: f32 = 1.16;
+
+static FONT_DB: OnceLock<fontdb::Database> = OnceLock::new();
+static LABEL_FONT: OnceLock<Option<FontVec>> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// Date/time (and optional label) burned into saved images.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StampSettings {
+    pub enabled: bool,
+    pub corner: Corner,
+    /// chrono strftime pattern for the capture time.
+    pub format: String,
+    /// Extra text such as a ticket number; empty stamps the time only.
+    pub label: String,
+}
+
+impl Default for StampSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            corner: Corner::BottomRight,
+            format: "%Y-%m-%d %H:%M:%S".to_string(),
+            label: String::new(),
+        }
+    }
+}
+
+impl StampSettings {
+    /// The stamp line for a capture taken at `captured_at`, e.g.
+    /// "QA-123  2024-01-01 10:00:00".
+    pub fn text(&self, captured_at: DateTime<Local>) -> Result<String, String> {
+        use std::fmt::Write;
+
+        let mut time = String::new();
+        write!(time, "{}", captured_at.format(&self.format))
+            .map_err(|_| format!("'{}' is not a valid date format", self.format))?;
+        let label = self.label.trim();
+        Ok(if label.is_empty() {
+            time
+        } else {
+            format!("{}  {}", label, time)
+        })
+    }
+}
+
+/// Top-left position for a box of `box_size` in `corner` of an image,
+/// inset by `margin`.
+pub fn corner_origin(
+    corner: Corner,
+    image_size: (u32, u32),
+    box_size: (u32, u32),
+    margin: u32,
+) -> (i64, i64) {
+    let (image_width, image_height) = (image_size.0 as i64, image_size.1 as i64);
+    let (width, height) = (box_size.0 as i64, box_size.1 as i64);
+    let margin = margin as i64;
+    let left = margin;
+    let right = image_width - width - margin;
+    let top = margin;
+    let bottom = image_height - height - margin;
+    match corner {
+        Corner::TopLeft => (left, top),
+        Corner::TopRight => (right, top),
+        Corner::BottomLeft => (left, bottom),
+        Corner::BottomRight => (right, bottom),
+    }
+}
+
+/// The system font database, scanned once. Scanning reads every font file,
+/// so the first call should happen off the async runtime.
+fn font_db() -> &'static fontdb::Database {
+    FONT_DB.get_or_init(|| {
+        let mut db = fontdb::Database::new();
+        db.load_system_fonts();
+        db
+    })
+}
+
+fn load_face(db: &fontdb::Database, id: fontdb::ID) -> Option<FontVec> {
+    db.with_face_data(id, |data, index| FontVec::try_from_vec_and_index(data.to_vec(), index).ok())
+        .flatten()
+}
+
+/// Loads the label font from the system once.
+fn label_font() -> Option<&'static FontVec> {
+    LABEL_FONT
+        .get_or_init(|| {
+            let db = font_db();
+            let families: Vec<fontdb::Family> = LABEL_FAMILIES
+                .iter()
+                .map(|name| fontdb::Family::Name(name))
+                .chain(std::iter::once(fontdb::Family::SansSerif))
+                .collect();
+            let id = db
+                .query(&fontdb::Query {
+                    families: &families,
+                    ..Default::default()
+                })
+                .or_else(|| {
+                    // Fall back to any regular face rather than no stamp at all.
+                    db.faces()
+                        .find(|face| {
+                            face.style == fontdb::Style::Normal
+                                && face.weight == fontdb::Weight::NORMAL
+                                && !face.monospaced
+                        })
+                        .map(|face| face.id)
+                })?;
+            load_face(db, id)
+        })
+        .as_ref()
+}
+
+fn blend(pixel: &mut Rgba<u8>, color: [u8; 3], alpha: f32) {
+    let alpha = alpha.clamp(0.0, 1.0);
+    for (channel, value) in pixel.0.iter_mut().zip(color) {
+        *channel = (*channel as f32 * (1.0 - alpha) + value as f32 * alpha).round() as u8;
+    }
+    pixel.0[3] = pixel.0[3].max((alpha * 255.0).round() as u8);
+}
+
+/// Draws `text` in white on a translucent dark box in `corner`, sized
+/// relative to the image so it stays legible on Retina captures.
+pub fn draw_label(image: &mut RgbaImage, text: &str, corner: Corner, font: &FontVec) {
+    let (image_width, image_height) = image.dimensions();
+    let size = (image_height.min(image_width) as f32 * 0.03).clamp(12.0, 48.0);
+    let scaled = font.as_scaled(PxScale::from(size));
+    let padding = (size * 0.4).round() as u32;
+
+    let mut glyphs = Vec::new();
+    let mut x = 0.0;
+    let mut previous = None;
+    for c in text.chars() {
+        let id = font.glyph_id(c);
+        if let Some(previous) = previous {
+            x += scaled.kern(previous, id);
+        }
+        glyphs.push(id.with_scale_and_position(size, point(x, scaled.ascent())));
+        x += scaled.h_advance(id);
+        previous = Some(id);
+    }
+    let text_width = x.ceil() as u32;
+    let text_height = scaled.height().ceil() as u32;
+    let box_size = (text_width + padding * 2, text_height + padding * 2);
+    let (box_x, box_y) = corner_origin(corner, (image_width, image_height), box_size, padding);
+
+    let mut put = |x: i64, y: i64, color: [u8; 3], alpha: f32| {
+        if x >= 0 && y >= 0 && (x as u32) < image_width && (y as u32) < image_height {
+            blend(image.get_pixel_mut(x as u32, y as u32), color, alpha);
+        }
+    };
+    for y in 0..box_size.1 as i64 {
+        for x in 0..box_size.0 as i64 {
+            put(box_x + x, box_y + y, [0, 0, 0], 0.6);
+        }
+    }
+    let text_x = box_x + padding as i64;
+    let text_y = box_y + padding as i64;
+    for glyph in glyphs {
+        if let Some(outlined) = font.outline_glyph(glyph) {
+            let bounds = outlined.px_bounds();
+            outlined.draw(|gx, gy, coverage| {
+                put(
+                    text_x + bounds.min.x as i64 + gx as i64,
+                    text_y + bounds.min.y as i64 + gy as i64,
+                    [255, 255, 255],
+                    coverage,
+                );
+            });
+        }
+    }
+}
+
+/// Burns `text` into a PNG as a label in `corner` and returns the re-encoded image.
+pub fn apply_label(png: &[u8], text: &str, corner: Corner) -> Result<Vec<u8>, String> {
+    let font = label_font().ok_or("No system font is available for labels")?;
+    let mut image = image::load_from_memory(png)
+        .map_err(|e| format!("Failed to decode image: {}", e))?
+        .to_rgba8();
+
+    draw_label(&mut image, text, corner, font);
+
+    let mut labeled = Vec::new();
+    image::DynamicImage::ImageRgba8(image)
+        .write_to(&mut std::io::Cursor::new(&mut labeled), image::ImageOutputFormat::Png)
+        .map_err(|e| format!("Failed to encode labeled image: {}", e))?;
+    Ok(labeled)
+}
+
+/// Burns the stamp into a PNG and returns the re-encoded image.
+pub fn apply_stamp(png: &[u8], settings: &StampSettings, captured_at: DateTime<Local>) -> Result<Vec<u8>, String> {
+    let text = settings.text(captured_at)?;
+    apply_label(png, &text, settings.corner)
+}
+
+/// A point in image pixels.
+pub type Point = [f32; 2];
+
+/// An editor annotation in image pixels. Strokes are centered on the
+/// outline, as they are in the editor; `shadow` draws the editor's soft
+/// drop shadow under the shape.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Shape {
+    Rect {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        color: String,
+        stroke_width: f32,
+        /// Fill the rectangle instead of outlining it.
+        #[serde(default)]
+        filled: bool,
+        #[serde(default)]
+        shadow: bool,
+    },
+    Ellipse {
+        cx: f32,
+        cy: f32,
+        rx: f32,
+        ry: f32,
+        color: String,
+        stroke_width: f32,
+        #[serde(default)]
+        shadow: bool,
+    },
+    Line {
+        from: Point,
+        to: Point,
+        color: String,
+        stroke_width: f32,
+        #[serde(default)]
+        shadow: bool,
+    },
+    Arrow {
+        from: Point,
+        to: Point,
+        color: String,
+        stroke_width: f32,
+        #[serde(default)]
+        shadow: bool,
+    },
+    /// A freehand stroke through `points`.
+    Path {
+        points: Vec<Point>,
+        color: String,
+        stroke_width: f32,
+        #[serde(default)]
+        shadow: bool,
+    },
+    /// `(x, y)` is the top-left of the first line; `font_size` is in pixels
+    /// per em, like CSS. An empty family uses the label font.
+    Text {
+        x: f32,
+        y: f32,
+        text: String,
+        color: String,
+        font_size: f32,
+        #[serde(default)]
+        font_family: String,
+        #[serde(default)]
+        shadow: bool,
+    },
+}
+
+impl Shape {
+    fn has_shadow(&self) -> bool {
+        match self {
+            Shape::Rect { shadow, .. }
+            | Shape::Ellipse { shadow, .. }
+            | Shape::Line { shadow, .. }
+            | Shape::Arrow { shadow, .. }
+            | Shape::Path { shadow, .. }
+            | Shape::Text { shadow, .. } => *shadow,
+        }
+    }
+
+    /// The box (min, max) the shape's geometry spans, without its stroke.
+    /// Text only has its top-left corner, since its size needs the font.
+    fn bounds(&self) -> Option<(Point, Point)> {
+        match self {
+            Shape::Rect { x, y, width, height, .. } => Some((
+                [x.min(x + width), y.min(y + height)],
+                [x.max(x + width), y.max(y + height)],
+            )),
+            Shape::Ellipse { cx, cy, rx, ry, .. } => Some((
+                [cx - rx.abs(), cy - ry.abs()],
+                [cx + rx.abs(), cy + ry.abs()],
+            )),
+            Shape::Line { from, to, .. } | Shape::Arrow { from, to, .. } => points_bounds(&[*from, *to]),
+            Shape::Path { points, .. } => points_bounds(points),
+            Shape::Text { x, y, .. } => Some(([*x, *y], [*x, *y])),
+        }
+    }
+
+    fn translate(&mut self, dx: f32, dy: f32) {
+        match self {
+            Shape::Rect { x, y, .. } | Shape::Text { x, y, .. } => {
+                *x += dx;
+                *y += dy;
+            }
+            Shape::Ellipse { cx, cy, .. } => {
+                *cx += dx;
+                *cy += dy;
+            }
+            Shape::Line { from, to, .. } | Shape::Arrow { from, to, .. } => {
+                for p in [from, to] {
+                    *p = [p[0] + dx, p[1] + dy];
+                }
+            }
+            Shape::Path { points, .. } => {
+                for p in points {
+                    *p = [p[0] + dx, p[1] + dy];
+                }
+            }
+        }
+    }
+}
+
+fn points_bounds(points: &[Point]) -> Option<(Point, Point)> {
+    let first = *points.first()?;
+    Some(points.iter().fold((first, first), |(min, max), p| {
+        ([min[0].min(p[0]), min[1].min(p[1])], [max[0].max(p[0]), max[1].max(p[1])])
+    }))
+}
+
+/// Vertical (`x`) and horizontal (`y`) lines a shape can snap to.
+#[derive(Default)]
+struct Guides {
+    x: Vec<f32>,
+    y: Vec<f32>,
+}
+
+impl Guides {
+    /// Adds the edges and centre lines of a box.
+    fn add_box(&mut self, (min, max): (Point, Point)) {
+        self.x.extend([min[0], (min[0] + max[0]) / 2.0, max[0]]);
+        self.y.extend([min[1], (min[1] + max[1]) / 2.0, max[1]]);
+    }
+}
+
+/// The smallest shift that puts one of `values` on a guide, or 0 when none
+/// is within [`SNAP_THRESHOLD`].
+fn guide_offset(values: &[f32], guides: &[f32]) -> f32 {
+    let mut best = 0.0;
+    let mut closest = f32::INFINITY;
+    for value in values {
+        for guide in guides {
+            let distance = (guide - value).abs();
+            if distance <= SNAP_THRESHOLD && distance < closest {
+                best = guide - value;
+                closest = distance;
+            }
+        }
+    }
+    best
+}
+
+fn snap_to_grid(value: f32, size: f32) -> f32 {
+    (value / size).round() * size
+}
+
+/// Lines `shapes` up the way the editor does while drawing. With grid
+/// snapping each shape's top-left corner, and both ends of a line, move to
+/// the nearest grid line; with guide snapping, edges and centres within a
+/// few pixels of the image's or another shape's move onto them.
+pub fn snap_shapes(shapes: &mut [Shape], grid: &EditorGrid, image_size: (u32, u32)) {
+    if grid.snap_to_grid && grid.size > 0 {
+        let size = grid.size as f32;
+        for shape in shapes.iter_mut() {
+            match shape {
+                Shape::Line { from, to, .. } | Shape::Arrow { from, to, .. } => {
+                    for p in [from, to] {
+                        *p = p.map(|value| snap_to_grid(value, size));
+                    }
+                }
+                _ => {
+                    if let Some((min, _)) = shape.bounds() {
+                        shape.translate(snap_to_grid(min[0], size) - min[0], snap_to_grid(min[1], size) - min[1]);
+                    }
+                }
+            }
+        }
+    } else if grid.snap_to_guides {
+        let image = ([0.0, 0.0], [image_size.0 as f32, image_size.1 as f32]);
+        let others: Vec<Option<(Point, Point)>> = shapes.iter().map(Shape::bounds).collect();
+        for (index, shape) in shapes.iter_mut().enumerate() {
+            let mut guides = Guides::default();
+            guides.add_box(image);
+            for (other, bounds) in others.iter().enumerate() {
+                if let Some(bounds) = bounds.filter(|_| other != index) {
+                    guides.add_box(bounds);
+                }
+            }
+            match shape {
+                Shape::Line { from, to, .. } | Shape::Arrow { from, to, .. } => {
+                    for p in [from, to] {
+                        *p = [p[0] + guide_offset(&[p[0]], &guides.x), p[1] + guide_offset(&[p[1]], &guides.y)];
+                    }
+                }
+                _ => {
+                    if let Some(bounds) = shape.bounds() {
+                        let mut own = Guides::default();
+                        own.add_box(bounds);
+                        shape.translate(guide_offset(&own.x, &guides.x), guide_offset(&own.y, &guides.y));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Distance from `p` to the segment `a`-`b`.
+fn segment_distance(p: Point, a: Point, b: Point) -> f32 {
+    let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+    let length_sq = dx * dx + dy * dy;
+    let t = if length_sq == 0.0 {
+        0.0
+    } else {
+        (((p[0] - a[0]) * dx + (p[1] - a[1]) * dy) / length_sq).clamp(0.0, 1.0)
+    };
+    (p[0] - a[0] - t * dx).hypot(p[1] - a[1] - t * dy)
+}
+
+/// Blends `color` over every pixel in `bounds` (min, max; image pixels) by
+/// the coverage `coverage` returns for the pixel's center.
+fn fill_coverage(
+    image: &mut RgbaImage,
+    bounds: (Point, Point),
+    color: [u8; 3],
+    coverage: impl Fn(Point) -> f32,
+) {
+    let (width, height) = image.dimensions();
+    let x_range = (bounds.0[0].floor().max(0.0) as u32)..(bounds.1[0].ceil().max(0.0) as u32).min(width);
+    let y_range = (bounds.0[1].floor().max(0.0) as u32)..(bounds.1[1].ceil().max(0.0) as u32).min(height);
+    for y in y_range {
+        for x in x_range.clone() {
+            let alpha = coverage([x as f32 + 0.5, y as f32 + 0.5]);
+            if alpha > 0.0 {
+                blend(image.get_pixel_mut(x, y), color, alpha);
+            }
+        }
+    }
+}
+
+/// Strokes the polyline through `points` with round joins and caps.
+fn stroke_polyline(image: &mut RgbaImage, points: &[Point], color: [u8; 3], stroke_width: f32) {
+    let Some(first) = points.first() else {
+        return;
+    };
+    let reach = stroke_width / 2.0 + 1.0;
+    let mut min = *first;
+    let mut max = *first;
+    for p in points {
+        min = [min[0].min(p[0]), min[1].min(p[1])];
+        max = [max[0].max(p[0]), max[1].max(p[1])];
+    }
+    let bounds = ([min[0] - reach, min[1] - reach], [max[0] + reach, max[1] + reach]);
+    let segments: Vec<(Point, Point)> = if points.len() == 1 {
+        vec![(*first, *first)]
+    } else {
+        points.windows(2).map(|pair| (pair[0], pair[1])).collect()
+    };
+    fill_coverage(image, bounds, color, |p| {
+        let distance = segments
+            .iter()
+            .map(|&(a, b)| segment_distance(p, a, b))
+            .fold(f32::INFINITY, f32::min);
+        (stroke_width / 2.0 + 0.5 - distance).clamp(0.0, 1.0)
+    });
+}
+
+/// Fills a convex polygon whose corners run in either direction.
+fn fill_convex(image: &mut RgbaImage, corners: &[Point], color: [u8; 3]) {
+    let mut min = [f32::INFINITY; 2];
+    let mut max = [f32::NEG_INFINITY; 2];
+    for p in corners {
+        min = [min[0].min(p[0]), min[1].min(p[1])];
+        max = [max[0].max(p[0]), max[1].max(p[1])];
+    }
+    let area: f32 = (0..corners.len())
+        .map(|i| {
+            let (a, b) = (corners[i], corners[(i + 1) % corners.len()]);
+            a[0] * b[1] - b[0] * a[1]
+        })
+        .sum();
+    let orientation = area.signum();
+    fill_coverage(image, ([min[0] - 1.0, min[1] - 1.0], [max[0] + 1.0, max[1] + 1.0]), color, |p| {
+        // Signed distance to the nearest edge, positive inside.
+        let inside = (0..corners.len())
+            .map(|i| {
+                let (a, b) = (corners[i], corners[(i + 1) % corners.len()]);
+                let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+                let length = dx.hypot(dy).max(f32::EPSILON);
+                orientation * (dx * (p[1] - a[1]) - dy * (p[0] - a[0])) / length
+            })
+            .fold(f32::INFINITY, f32::min);
+        (inside + 0.5).clamp(0.0, 1.0)
+    });
+}
+
+fn draw_text(image: &mut RgbaImage, origin: Point, text: &str, color: [u8; 3], font_size: f32, font: &FontVec) {
+    let Some(units_per_em) = font.units_per_em() else {
+        return;
+    };
+    let scale = PxScale::from(font_size * font.height_unscaled() / units_per_em);
+    let scaled = font.as_scaled(scale);
+    let (width, height) = image.dimensions();
+    for (row, line) in text.lines().enumerate() {
+        let baseline = origin[1] + row as f32 * font_size * TEXT_LINE_HEIGHT + scaled.ascent();
+        let mut x = origin[0];
+        let mut previous = None;
+        for c in line.chars() {
+            let id = font.glyph_id(c);
+            if let Some(previous) = previous {
+                x += scaled.kern(previous, id);
+            }
+            let glyph = id.with_scale_and_position(scale, point(x, baseline));
+            x += scaled.h_advance(id);
+            previous = Some(id);
+            let Some(outlined) = font.outline_glyph(glyph) else {
+                continue;
+            };
+            let bounds = outlined.px_bounds();
+            outlined.draw(|gx, gy, coverage| {
+                let px = bounds.min.x as i64 + gx as i64;
+                let py = bounds.min.y as i64 + gy as i64;
+                if px >= 0 && py >= 0 && (px as u32) < width && (py as u32) < height {
+                    blend(image.get_pixel_mut(px as u32, py as u32), color, coverage);
+                }
+            });
+        }
+    }
+}
+
+/// An installed face of `family`, if there is one.
+fn family_font(family: &str) -> Option<FontVec> {
+    let family = family.trim();
+    if family.is_empty() {
+        return None;
+    }
+    let db = font_db();
+    let id = db.query(&fontdb::Query {
+        families: &[fontdb::Family::Name(family)],
+        ..Default::default()
+    })?;
+    load_face(db, id)
+}
+
+fn rgb(color: &str) -> Result<[u8; 3], String> {
+    let Rgba([r, g, b, _]) = beautify::parse_hex(color)?;
+    Ok([r, g, b])
+}
+
+/// Rasterizes one annotation, and its shadow if it has one, onto `image`.
+pub fn draw_shape(image: &mut RgbaImage, shape: &Shape) -> Result<(), String> {
+    if shape.has_shadow() {
+        draw_shadow(image, shape)?;
+    }
+    draw_without_shadow(image, shape)
+}
+
+/// Blends the shape's blurred silhouette, offset like the editor's shadow,
+/// under where it will be drawn.
+fn draw_shadow(image: &mut RgbaImage, shape: &Shape) -> Result<(), String> {
+    let (width, height) = image.dimensions();
+    let mut offset = shape.clone();
+    offset.translate(SHADOW_OFFSET, SHADOW_OFFSET);
+    // Drawn on a transparent layer, the alpha channel is the shape's coverage.
+    let mut silhouette = RgbaImage::new(width, height);
+    draw_without_shadow(&mut silhouette, &offset)?;
+
+    let covered = silhouette.enumerate_pixels().filter(|(_, _, pixel)| pixel.0[3] > 0);
+    let Some((min, max)) = covered.fold(None, |bounds: Option<([u32; 2], [u32; 2])>, (x, y, _)| {
+        Some(match bounds {
+            Some((min, max)) => ([min[0].min(x), min[1].min(y)], [max[0].max(x), max[1].max(y)]),
+            None => ([x, y], [x, y]),
+        })
+    }) else {
+        return Ok(());
+    };
+    // Only the covered area is blurred, with room for the blur to spread.
+    let margin = (SHADOW_SIGMA * 3.0).ceil() as u32;
+    let (left, top) = (min[0].saturating_sub(margin), min[1].saturating_sub(margin));
+    let (right, bottom) = ((max[0] + margin + 1).min(width), (max[1] + margin + 1).min(height));
+    let area = image::imageops::crop_imm(&silhouette, left, top, right - left, bottom - top).to_image();
+    let blurred = image::imageops::blur(&area, SHADOW_SIGMA);
+    for (x, y, pixel) in blurred.enumerate_pixels() {
+        let alpha = pixel.0[3] as f32 / 255.0 * SHADOW_OPACITY;
+        if alpha > 0.0 {
+            blend(image.get_pixel_mut(left + x, top + y), [0, 0, 0], alpha);
+        }
+    }
+    Ok(())
+}
+
+fn draw_without_shadow(image: &mut RgbaImage, shape: &Shape) -> Result<(), String> {
+    match shape {
+        Shape::Rect { x, y, width, height, color, stroke_width, filled, .. } => {
+            let color = rgb(color)?;
+            let (half_width, half_height) = (width.abs() / 2.0, height.abs() / 2.0);
+            let center = [x + width / 2.0, y + height / 2.0];
+            let reach = if *filled { 1.0 } else { stroke_width / 2.0 + 1.0 };
+            let bounds = (
+                [center[0] - half_width - reach, center[1] - half_height - reach],
+                [center[0] + half_width + reach, center[1] + half_height + reach],
+            );
+            fill_coverage(image, bounds, color, |p| {
+                // Box distance with square corners, negative inside.
+                let distance = ((p[0] - center[0]).abs() - half_width).max((p[1] - center[1]).abs() - half_height);
+                if *filled {
+                    (0.5 - distance).clamp(0.0, 1.0)
+                } else {
+                    (stroke_width / 2.0 + 0.5 - distance.abs()).clamp(0.0, 1.0)
+                }
+            });
+        }
+        Shape::Ellipse { cx, cy, rx, ry, color, stroke_width, .. } => {
+            let color = rgb(color)?;
+            let (rx, ry) = (rx.abs().max(f32::EPSILON), ry.abs().max(f32::EPSILON));
+            let reach = stroke_width / 2.0 + 1.0;
+            let bounds = ([cx - rx - reach, cy - ry - reach], [cx + rx + reach, cy + ry + reach]);
+            fill_coverage(image, bounds, color, |p| {
+                // First-order distance to the ellipse outline.
+                let (dx, dy) = (p[0] - cx, p[1] - cy);
+                let k0 = (dx / rx).hypot(dy / ry);
+                let k1 = (dx / (rx * rx)).hypot(dy / (ry * ry));
+                let distance = if k1 == 0.0 { rx.min(ry) } else { (k0 * (k0 - 1.0) / k1).abs() };
+                (stroke_width / 2.0 + 0.5 - distance).clamp(0.0, 1.0)
+            });
+        }
+        Shape::Line { from, to, color, stroke_width, .. } => {
+            stroke_polyline(image, &[*from, *to], rgb(color)?, *stroke_width);
+        }
+        Shape::Arrow { from, to, color, stroke_width, .. } => {
+            let color = rgb(color)?;
+            stroke_polyline(image, &[*from, *to], color, *stroke_width);
+            let (dx, dy) = (to[0] - from[0], to[1] - from[1]);
+            let length = dx.hypot(dy);
+            if length > 0.0 {
+                // The head is centered on the tip, pointing along the line.
+                let half = ARROW_HEAD_SIZE / 2.0;
+                let (ux, uy) = (dx / length, dy / length);
+                let tip = [to[0] + ux * half, to[1] + uy * half];
+                let back = [to[0] - ux * half, to[1] - uy * half];
+                let left = [back[0] - uy * half, back[1] + ux * half];
+                let right = [back[0] + uy * half, back[1] - ux * half];
+                fill_convex(image, &[tip, left, right], color);
+            }
+        }
+        Shape::Path { points, color, stroke_width, .. } => {
+            stroke_polyline(image, points, rgb(color)?, *stroke_width);
+        }
+        Shape::Text { x, y, text, color, font_size, font_family, .. } => {
+            let color = rgb(color)?;
+            let family = family_font(font_family);
+            let font = match &family {
+                Some(font) => font,
+                None => label_font().ok_or("No system font is available for text")?,
+            };
+            draw_text(image, [*x, *y], text, color, *font_size, font);
+        }
+    }
+    Ok(())
+}
+
+/// Snaps `shapes` per `grid`, draws them in order onto a PNG and returns
+/// the re-encoded image.
+pub fn render_annotations(png: &[u8], shapes: &[Shape], grid: &EditorGrid) -> Result<Vec<u8>, String> {
+    let mut image = image::load_from_memory(png)
+        .map_err(|e| format!("Failed to decode image: {}", e))?
+        .to_rgba8();
+
+    let mut shapes = shapes.to_vec();
+    snap_shapes(&mut shapes, grid, image.dimensions());
+    for shape in &shapes {
+        draw_shape(&mut image, shape)?;
+    }
+
+    let mut rendered = Vec::new();
+    image::DynamicImage::ImageRgba8(image)
+        .write_to(&mut std::io::Cursor::new(&mut rendered), image::ImageOutputFormat::Png)
+        .map_err(|e| format!("Failed to encode annotated image: {}", e))?;
+    let icc = color::icc_profile(png);
+    color::apply_profile(rendered, icc.as_deref(), color::ColorProfileMode::Preserve)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_stamp_text_includes_label() {
+        let captured_at = Local.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+        let mut settings = StampSettings::default();
+
+        assert_eq!(settings.text(captured_at).unwrap(), "2024-01-02 03:04:05");
+        settings.label = " QA-123 ".to_string();
+        settings.format = "%d/%m/%Y".to_string();
+        assert_eq!(settings.text(captured_at).unwrap(), "QA-123  02/01/2024");
+        settings.format = "%Q".to_string();
+        assert!(settings.text(captured_at).is_err());
+    }
+
+    #[test]
+    fn test_corner_origin() {
+        assert_eq!(corner_origin(Corner::TopLeft, (100, 50), (20, 10), 4), (4, 4));
+        assert_eq!(corner_origin(Corner::BottomRight, (100, 50), (20, 10), 4), (76, 36));
+        assert_eq!(corner_origin(Corner::TopRight, (100, 50), (20, 10), 4), (76, 4));
+    }
+
+    #[test]
+    fn test_draw_shapes() {
+        let mut image = RgbaImage::from_pixel(40, 40, Rgba([255, 255, 255, 255]));
+        let shapes: Vec<Shape> = serde_json::from_str(
+            r##"[
+                {"kind": "rect", "x": 5, "y": 5, "width": 20, "height": 10, "color": "#ff0000", "stroke_width": 2},
+                {"kind": "line", "from": [0, 30], "to": [40, 30], "color": "#0000ff", "stroke_width": 4}
+            ]"##,
+        )
+        .unwrap();
+
+        for shape in &shapes {
+            draw_shape(&mut image, shape).unwrap();
+        }
+
+        // On the rectangle's outline, but not inside it.
+        assert_eq!(image.get_pixel(15, 5).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(15, 10).0, [255, 255, 255, 255]);
+        assert_eq!(image.get_pixel(20, 30).0, [0, 0, 255, 255]);
+        assert_eq!(image.get_pixel(20, 35).0, [255, 255, 255, 255]);
+        let bad = Shape::Line {
+            from: [0.0, 0.0],
+            to: [1.0, 1.0],
+            color: "red".to_string(),
+            stroke_width: 1.0,
+            shadow: false,
+        };
+        assert!(draw_shape(&mut image, &bad).is_err());
+    }
+
+    #[test]
+    fn test_snap_shapes() {
+        let rect = |x: f32, y: f32| Shape::Rect {
+            x,
+            y,
+            width: 20.0,
+            height: 10.0,
+            color: "#ff0000".to_string(),
+            stroke_width: 2.0,
+            filled: false,
+            shadow: false,
+        };
+        let arrow = Shape::Arrow {
+            from: [3.0, 30.0],
+            to: [97.0, 42.0],
+            color: "#ff0000".to_string(),
+            stroke_width: 2.0,
+            shadow: false,
+        };
+
+        let mut shapes = vec![rect(7.0, 18.0), arrow];
+        let grid = EditorGrid {
+            snap_to_grid: true,
+            size: 16,
+            ..EditorGrid::default()
+        };
+        snap_shapes(&mut shapes, &grid, (100, 100));
+        assert_eq!(shapes[0], rect(0.0, 16.0));
+        assert!(matches!(shapes[1], Shape::Arrow { from: [0.0, 32.0], to: [96.0, 48.0], .. }));
+
+        // Guides: the image's and the other shapes' edges, within 6 pixels.
+        let arrow = Shape::Arrow {
+            from: [30.0, 70.0],
+            to: [97.0, 90.0],
+            color: "#ff0000".to_string(),
+            stroke_width: 2.0,
+            shadow: false,
+        };
+        let mut shapes = vec![rect(3.0, 20.0), rect(60.0, 26.0), arrow];
+        let guides = EditorGrid {
+            snap_to_guides: true,
+            ..EditorGrid::default()
+        };
+        snap_shapes(&mut shapes, &guides, (100, 100));
+        assert_eq!(shapes[0], rect(0.0, 21.0));
+        assert_eq!(shapes[1], rect(63.5, 25.0));
+        assert!(matches!(shapes[2], Shape::Arrow { from: [30.0, 70.0], to: [100.0, 90.0], .. }));
+
+        let mut untouched = vec![rect(7.0, 18.0)];
+        snap_shapes(&mut untouched, &EditorGrid::default(), (100, 100));
+        assert_eq!(untouched[0], rect(7.0, 18.0));
+    }
+
+    #[test]
+    fn test_shadow_falls_down_and_right() {
+        let mut image = RgbaImage::from_pixel(60, 60, Rgba([255, 255, 255, 255]));
+        let shape: Shape = serde_json::from_str(
+            r##"{"kind": "rect", "x": 20, "y": 20, "width": 20, "height": 20, "color": "#ff0000", "filled": true,
+                 "stroke_width": 0, "shadow": true}"##,
+        )
+        .unwrap();
+
+        draw_shape(&mut image, &shape).unwrap();
+
+        assert_eq!(image.get_pixel(30, 30).0, [255, 0, 0, 255]);
+        let right = image.get_pixel(41, 30).0[0];
+        let left = image.get_pixel(18, 30).0[0];
+        assert!(right < 220);
+        assert!(left > right);
+        assert_eq!(image.get_pixel(2, 2).0, [255, 255, 255, 255]);
+    }
+}
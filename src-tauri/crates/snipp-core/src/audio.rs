@@ -0,0 +1,129 @@
+use std::io::{self, Seek, SeekFrom, Write};
+
+const HEADER_LEN: u32 = 44;
+/// `WAVE_FORMAT_IEEE_FLOAT`; ScreenCaptureKit delivers 32-bit float samples.
+const FORMAT_FLOAT: u16 = 3;
+
+/// Streams 32-bit float samples into a WAV file. The sizes in the header
+/// aren't known until the end, so [`WavWriter::finish`] patches them in.
+pub struct WavWriter<W: Write + Seek> {
+    inner: W,
+    channels: u16,
+    data_len: u32,
+}
+
+impl<W: Write + Seek> WavWriter<W> {
+    pub fn new(mut inner: W, sample_rate: u32, channels: u16) -> io::Result<Self> {
+        inner.write_all(&header(sample_rate, channels, 0))?;
+        Ok(Self {
+            inner,
+            channels,
+            data_len: 0,
+        })
+    }
+
+    /// Writes one buffer given as a plane per channel, as ScreenCaptureKit
+    /// hands them over; a single plane is taken as already interleaved.
+    pub fn write_planes(&mut self, planes: &[Vec<f32>]) -> io::Result<()> {
+        let samples = if planes.len() == 1 {
+            planes[0].clone()
+        } else {
+            interleave(planes, self.channels as usize)
+        };
+        let bytes: Vec<u8> = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+        self.inner.write_all(&bytes)?;
+        self.data_len = self.data_len.saturating_add(bytes.len() as u32);
+        Ok(())
+    }
+
+    /// Bytes of sample data written so far.
+    pub fn data_len(&self) -> u32 {
+        self.data_len
+    }
+
+    pub fn finish(mut self) -> io::Result<W> {
+        self.inner.seek(SeekFrom::Start(4))?;
+        self.inner.write_all(&(HEADER_LEN - 8 + self.data_len).to_le_bytes())?;
+        self.inner.seek(SeekFrom::Start(40))?;
+        self.inner.write_all(&self.data_len.to_le_bytes())?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+fn header(sample_rate: u32, channels: u16, data_len: u32) -> [u8; HEADER_LEN as usize] {
+    let block_align = channels * 4;
+    let mut header = [0u8; HEADER_LEN as usize];
+    header[0..4].copy_from_slice(b"RIFF");
+    header[4..8].copy_from_slice(&(HEADER_LEN - 8 + data_len).to_le_bytes());
+    header[8..16].copy_from_slice(b"WAVEfmt ");
+    header[16..20].copy_from_slice(&16u32.to_le_bytes());
+    header[20..22].copy_from_slice(&FORMAT_FLOAT.to_le_bytes());
+    header[22..24].copy_from_slice(&channels.to_le_bytes());
+    header[24..28].copy_from_slice(&sample_rate.to_le_bytes());
+    header[28..32].copy_from_slice(&(sample_rate * block_align as u32).to_le_bytes());
+    header[32..34].copy_from_slice(&block_align.to_le_bytes());
+    header[34..36].copy_from_slice(&32u16.to_le_bytes());
+    header[36..40].copy_from_slice(b"data");
+    header[40..44].copy_from_slice(&data_len.to_le_bytes());
+    header
+}
+
+/// `[[l0, l1], [r0, r1]]` becomes `[l0, r0, l1, r1]`. Missing planes are
+/// filled with the last one so a mono source still fills every channel.
+fn interleave(planes: &[Vec<f32>], channels: usize) -> Vec<f32> {
+    let frames = planes.iter().map(Vec::len).min().unwrap_or(0);
+    let mut samples = Vec::with_capacity(frames * channels);
+    for frame in 0..frames {
+        for channel in 0..channels {
+            let plane = &planes[channel.min(planes.len() - 1)];
+            samples.push(plane[frame]);
+        }
+    }
+    samples
+}
+
+/// RMS level of `samples` in dBFS, matching what ffmpeg's astats reports;
+/// silence is -100.
+pub fn rms_db(samples: &[f32]) -> f64 {
+    if samples.is_empty() {
+        return -100.0;
+    }
+    let mean_square = samples.iter().map(|s| (*s as f64).powi(2)).sum::<f64>() / samples.len() as f64;
+    if mean_square <= 0.0 {
+        return -100.0;
+    }
+    (10.0 * mean_square.log10()).max(-100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_wav_writer_interleaves_and_patches_sizes() {
+        let mut writer = WavWriter::new(Cursor::new(Vec::new()), 48_000, 2).unwrap();
+        writer.write_planes(&[vec![0.5, 0.25], vec![-0.5, -0.25]]).unwrap();
+        let bytes = writer.finish().unwrap().into_inner();
+
+        assert_eq!(&bytes[0..4], b"RIFF");
+        assert_eq!(u32::from_le_bytes(bytes[4..8].try_into().unwrap()), 36 + 16);
+        assert_eq!(u16::from_le_bytes(bytes[20..22].try_into().unwrap()), FORMAT_FLOAT);
+        assert_eq!(u32::from_le_bytes(bytes[24..28].try_into().unwrap()), 48_000);
+        assert_eq!(u32::from_le_bytes(bytes[40..44].try_into().unwrap()), 16);
+        let samples: Vec<f32> = bytes[44..]
+            .chunks(4)
+            .map(|chunk| f32::from_le_bytes(chunk.try_into().unwrap()))
+            .collect();
+        assert_eq!(samples, [0.5, -0.5, 0.25, -0.25]);
+    }
+
+    #[test]
+    fn test_rms_db() {
+        assert_eq!(rms_db(&[]), -100.0);
+        assert_eq!(rms_db(&[0.0; 4]), -100.0);
+        assert!((rms_db(&[1.0, -1.0]) - 0.0).abs() < 1e-9);
+        assert!((rms_db(&[0.5; 8]) + 6.0206).abs() < 1e-3);
+    }
+}
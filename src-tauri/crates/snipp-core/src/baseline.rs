@@ -0,0 +1,169 @@
+use image::RgbaImage;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::thumbnail::fnv1a;
+
+/// Channel differences up to this much are treated as rendering noise
+/// (antialiasing, cursor blink fades) rather than a real change.
+pub const CHANNEL_TOLERANCE: u8 = 8;
+
+/// Outcome of diffing a preset capture against its baseline.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegressionResult {
+    pub preset: String,
+    pub baseline_path: String,
+    pub changed_pixels: u64,
+    pub total_pixels: u64,
+    pub changed_percent: f64,
+    pub threshold_percent: f64,
+    pub passed: bool,
+}
+
+/// Counts pixels where any channel differs by more than `tolerance`.
+/// Images of different sizes are compared over the larger canvas, so every
+/// pixel outside the overlap counts as changed.
+pub fn count_changed_pixels(baseline: &RgbaImage, current: &RgbaImage, tolerance: u8) -> (u64, u64) {
+    let width = baseline.width().max(current.width());
+    let height = baseline.height().max(current.height());
+    let total = width as u64 * height as u64;
+
+    let overlap_width = baseline.width().min(current.width());
+    let overlap_height = baseline.height().min(current.height());
+    let mut unchanged = 0u64;
+    for y in 0..overlap_height {
+        for x in 0..overlap_width {
+            let a = baseline.get_pixel(x, y);
+            let b = current.get_pixel(x, y);
+            if a.0.iter().zip(b.0.iter()).all(|(a, b)| a.abs_diff(*b) <= tolerance) {
+                unchanged += 1;
+            }
+        }
+    }
+
+    (total - unchanged, total)
+}
+
+/// Where the baseline for `preset` lives under the Snipp config directory.
+pub fn baseline_path(preset: &str) -> Result<PathBuf, String> {
+    let dir = dirs::config_dir()
+        .ok_or("Failed to get config directory")?
+        .join("snipp")
+        .join("baselines");
+    Ok(dir.join(format!("{}.png", preset_slug(preset))))
+}
+
+/// Lowercases the preset name and replaces anything that isn't safe in a
+/// file name with `-`, then appends a hash of the exact name so presets
+/// that read the same once simplified ("App!", "App?") keep apart.
+pub fn preset_slug(preset: &str) -> String {
+    let slug: String = preset
+        .trim()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+        .collect();
+    let slug = if slug.is_empty() { "preset" } else { slug.as_str() };
+    format!("{}-{:08x}", slug, fnv1a(preset.as_bytes()) as u32)
+}
+
+/// Diffs `current_png` against the baseline at `baseline_file`. Returns
+/// `None` when no baseline has been set. Decoding is blocking, so call this
+/// off the async runtime.
+pub fn check_against_baseline(
+    preset: &str,
+    baseline_file: &Path,
+    current_png: &[u8],
+    threshold_percent: f64,
+) -> Result<Option<RegressionResult>, String> {
+    if !baseline_file.exists() {
+        return Ok(None);
+    }
+
+    let baseline = image::open(baseline_file)
+        .map_err(|e| format!("Failed to open baseline: {}", e))?
+        .to_rgba8();
+    let current = image::load_from_memory(current_png)
+        .map_err(|e| format!("Failed to decode capture: {}", e))?
+        .to_rgba8();
+
+    let (changed_pixels, total_pixels) = count_changed_pixels(&baseline, &current, CHANNEL_TOLERANCE);
+    let changed_percent = if total_pixels == 0 {
+        0.0
+    } else {
+        changed_pixels as f64 * 100.0 / total_pixels as f64
+    };
+
+    Ok(Some(RegressionResult {
+        preset: preset.to_string(),
+        baseline_path: baseline_file.to_string_lossy().to_string(),
+        changed_pixels,
+        total_pixels,
+        changed_percent,
+        threshold_percent,
+        passed: changed_percent <= threshold_percent,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_changed_pixels_ignores_small_noise() {
+        let baseline = RgbaImage::from_pixel(4, 4, image::Rgba([100, 100, 100, 255]));
+        let mut current = RgbaImage::from_pixel(4, 4, image::Rgba([104, 96, 100, 255]));
+        current.put_pixel(0, 0, image::Rgba([0, 0, 0, 255]));
+
+        assert_eq!(count_changed_pixels(&baseline, &current, CHANNEL_TOLERANCE), (1, 16));
+    }
+
+    #[test]
+    fn test_count_changed_pixels_counts_size_mismatch_as_changed() {
+        let baseline = RgbaImage::from_pixel(2, 2, image::Rgba([0, 0, 0, 255]));
+        let current = RgbaImage::from_pixel(3, 2, image::Rgba([0, 0, 0, 255]));
+
+        assert_eq!(count_changed_pixels(&baseline, &current, 0), (2, 6));
+    }
+
+    #[test]
+    fn test_preset_slug_is_file_safe() {
+        assert!(preset_slug("Login Page/Header").starts_with("login-page-header-"));
+        assert!(preset_slug("  ").starts_with("preset-"));
+        assert_eq!(preset_slug("App!"), preset_slug("App!"));
+    }
+
+    #[test]
+    fn test_preset_slug_keeps_similar_names_apart() {
+        for (a, b) in [("App!", "App?"), ("App", "app"), ("\u{0413}\u{043b}", "\u{041f}\u{0440}"), ("\u{30ed}", "\u{8a2d}")] {
+            assert_ne!(preset_slug(a), preset_slug(b), "{} and {}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_check_against_baseline_applies_threshold() {
+        let dir = std::env::temp_dir().join(format!("snipp_baseline_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let baseline_file = dir.join("header.png");
+        RgbaImage::from_pixel(10, 10, image::Rgba([255, 255, 255, 255]))
+            .save(&baseline_file)
+            .unwrap();
+
+        let mut current = RgbaImage::from_pixel(10, 10, image::Rgba([255, 255, 255, 255]));
+        current.put_pixel(5, 5, image::Rgba([0, 0, 0, 255]));
+        let mut png = Vec::new();
+        image::DynamicImage::ImageRgba8(current)
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageOutputFormat::Png)
+            .unwrap();
+
+        let strict = check_against_baseline("Header", &baseline_file, &png, 0.5).unwrap().unwrap();
+        let loose = check_against_baseline("Header", &baseline_file, &png, 1.0).unwrap().unwrap();
+        let missing = check_against_baseline("Header", &dir.join("none.png"), &png, 1.0).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(strict.changed_pixels, 1);
+        assert_eq!(strict.changed_percent, 1.0);
+        assert!(!strict.passed);
+        assert!(loose.passed);
+        assert!(missing.is_none());
+    }
+}
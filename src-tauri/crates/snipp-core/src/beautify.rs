@@ -0,0 +1,312 @@
+use image::imageops::FilterType;
+use image::{GrayImage, Luma, Rgba, RgbaImage};
+use serde::{Deserialize, Serialize};
+
+use crate::color;
+
+/// The shadow is blurred at this fraction of full size; it's soft enough
+/// that nobody can tell, and blurring a full Retina canvas takes seconds.
+const SHADOW_DOWNSCALE: u32 = 4;
+
+/// What fills the padding around a beautified capture. Colors are
+/// `#rrggbb` hex.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Background {
+    Solid { color: String },
+    /// Runs from the top-left corner to the bottom-right one.
+    Gradient { from: String, to: String },
+}
+
+/// Frames a capture for sharing: padding, a background, rounded corners and
+/// a drop shadow. Sizes are in image pixels.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BeautifyOptions {
+    pub padding: u32,
+    pub background: Background,
+    pub corner_radius: u32,
+    /// Blur radius of the shadow; 0 leaves it out.
+    pub shadow: u32,
+}
+
+impl Default for BeautifyOptions {
+    fn default() -> Self {
+        Self {
+            padding: 64,
+            background: Background::Gradient {
+                from: "#667eea".to_string(),
+                to: "#764ba2".to_string(),
+            },
+            corner_radius: 12,
+            shadow: 32,
+        }
+    }
+}
+
+/// An outline drawn just inside a capture's rounded edge.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Border {
+    /// 1-4 pixels.
+    pub width: u32,
+    pub color: String,
+}
+
+impl Border {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=4).contains(&self.width) {
+            return Err("Border width must be between 1 and 4 pixels".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// The popup's Round Corners action.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CornerOptions {
+    pub radius: u32,
+    /// `None` leaves the edge as it is.
+    pub border: Option<Border>,
+}
+
+impl Default for CornerOptions {
+    fn default() -> Self {
+        Self { radius: 12, border: None }
+    }
+}
+
+/// Parses `#rrggbb` (or `rrggbb`) into an opaque color.
+pub fn parse_hex(color: &str) -> Result<Rgba<u8>, String> {
+    let hex = color.trim().trim_start_matches('#');
+    let channel = |range: std::ops::Range<usize>| {
+        hex.get(range)
+            .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+            .ok_or_else(|| format!("'{}' is not a #rrggbb color", color))
+    };
+    if hex.len() != 6 {
+        return Err(format!("'{}' is not a #rrggbb color", color));
+    }
+    Ok(Rgba([channel(0..2)?, channel(2..4)?, channel(4..6)?, 255]))
+}
+
+/// Makes everything outside a `radius` rounded rectangle transparent, with
+/// antialiased edges.
+pub fn round_corners(image: &mut RgbaImage, radius: u32) {
+    let (width, height) = image.dimensions();
+    let radius = radius.min(width / 2).min(height / 2) as f32;
+    if radius < 1.0 {
+        return;
+    }
+    let bounds = (0.0, 0.0, width as f32, height as f32);
+    for (x, y, pixel) in image.enumerate_pixels_mut() {
+        let inside = coverage(x, y, bounds, radius);
+        if inside < 1.0 {
+            pixel[3] = (pixel[3] as f32 * inside).round() as u8;
+        }
+    }
+}
+
+/// How much of pixel `(x, y)` lies inside the rounded rectangle spanning
+/// `(left, top, right, bottom)`.
+fn coverage(x: u32, y: u32, (left, top, right, bottom): (f32, f32, f32, f32), radius: f32) -> f32 {
+    let px = x as f32 + 0.5;
+    let py = y as f32 + 0.5;
+    if px < left || px > right || py < top || py > bottom {
+        return 0.0;
+    }
+    let radius = radius.max(0.0);
+    let cx = px.clamp(left + radius, (right - radius).max(left + radius));
+    let cy = py.clamp(top + radius, (bottom - radius).max(top + radius));
+    let distance = ((px - cx).powi(2) + (py - cy).powi(2)).sqrt();
+    (radius - distance + 0.5).clamp(0.0, 1.0)
+}
+
+/// Rounds the corners of a PNG capture, optionally outlining it with
+/// `border` drawn just inside the edge. Transparency is kept, as is the
+/// capture's color profile.
+pub fn apply_corner_radius(png: &[u8], radius: u32, border: Option<&Border>) -> Result<Vec<u8>, String> {
+    let mut image = image::load_from_memory(png)
+        .map_err(|e| format!("Failed to decode image: {}", e))?
+        .to_rgba8();
+    let (width, height) = image.dimensions();
+    let radius = radius.min(width / 2).min(height / 2) as f32;
+    let outer = (0.0, 0.0, width as f32, height as f32);
+
+    match border {
+        None => round_corners(&mut image, radius as u32),
+        Some(border) => {
+            border.validate()?;
+            let color = parse_hex(&border.color)?;
+            let inset = border.width as f32;
+            let inner = (inset, inset, width as f32 - inset, height as f32 - inset);
+            for (x, y, pixel) in image.enumerate_pixels_mut() {
+                let outside = coverage(x, y, outer, radius);
+                let inside = coverage(x, y, inner, radius - inset);
+                let ring = (outside - inside).max(0.0);
+                let content = pixel[3] as f32 / 255.0 * inside;
+                let alpha = ring + content * (1.0 - ring);
+                if alpha <= 0.0 {
+                    *pixel = Rgba([0, 0, 0, 0]);
+                    continue;
+                }
+                for channel in 0..3 {
+                    let mixed = color[channel] as f32 * ring + pixel[channel] as f32 * content * (1.0 - ring);
+                    pixel[channel] = (mixed / alpha).round() as u8;
+                }
+                pixel[3] = (alpha * 255.0).round() as u8;
+            }
+        }
+    }
+
+    let mut rounded = Vec::new();
+    image::DynamicImage::ImageRgba8(image)
+        .write_to(&mut std::io::Cursor::new(&mut rounded), image::ImageOutputFormat::Png)
+        .map_err(|e| format!("Failed to encode image: {}", e))?;
+    let icc = color::icc_profile(png);
+    color::apply_profile(rounded, icc.as_deref(), color::ColorProfileMode::Preserve)
+}
+
+/// Composites a PNG capture onto a padded background per `options`. The
+/// capture's color profile is kept.
+pub fn beautify(png: &[u8], options: &BeautifyOptions) -> Result<Vec<u8>, String> {
+    let mut capture = image::load_from_memory(png)
+        .map_err(|e| format!("Failed to decode image: {}", e))?
+        .to_rgba8();
+    round_corners(&mut capture, options.corner_radius);
+
+    let padding = options.padding;
+    let width = capture.width() + padding * 2;
+    let height = capture.height() + padding * 2;
+    let mut canvas = fill(&options.background, width, height)?;
+    if options.shadow > 0 {
+        draw_shadow(&mut canvas, &capture, padding, options.shadow);
+    }
+    image::imageops::overlay(&mut canvas, &capture, padding as i64, padding as i64);
+
+    let mut framed = Vec::new();
+    image::DynamicImage::ImageRgba8(canvas)
+        .write_to(&mut std::io::Cursor::new(&mut framed), image::ImageOutputFormat::Png)
+        .map_err(|e| format!("Failed to encode image: {}", e))?;
+    let icc = color::icc_profile(png);
+    color::apply_profile(framed, icc.as_deref(), color::ColorProfileMode::Preserve)
+}
+
+/// A `width` x `height` canvas painted with `background`.
+pub fn fill(background: &Background, width: u32, height: u32) -> Result<RgbaImage, String> {
+    Ok(match background {
+        Background::Solid { color } => RgbaImage::from_pixel(width, height, parse_hex(color)?),
+        Background::Gradient { from, to } => gradient(width, height, parse_hex(from)?, parse_hex(to)?),
+    })
+}
+
+fn gradient(width: u32, height: u32, from: Rgba<u8>, to: Rgba<u8>) -> RgbaImage {
+    let span = (width + height).saturating_sub(2).max(1) as f32;
+    RgbaImage::from_fn(width, height, |x, y| {
+        let t = (x + y) as f32 / span;
+        let mix = |channel: usize| (from[channel] as f32 + (to[channel] as f32 - from[channel] as f32) * t).round() as u8;
+        Rgba([mix(0), mix(1), mix(2), 255])
+    })
+}
+
+/// Darkens `canvas` under the capture's silhouette, blurred by `blur` and
+/// dropped a little below it.
+fn draw_shadow(canvas: &mut RgbaImage, capture: &RgbaImage, padding: u32, blur: u32) {
+    let (width, height) = canvas.dimensions();
+    let small_width = (width / SHADOW_DOWNSCALE).max(1);
+    let small_height = (height / SHADOW_DOWNSCALE).max(1);
+    let offset_y = blur / 3;
+
+    let mut mask = GrayImage::new(small_width, small_height);
+    let silhouette = image::imageops::resize(
+        capture,
+        (capture.width() / SHADOW_DOWNSCALE).max(1),
+        (capture.height() / SHADOW_DOWNSCALE).max(1),
+        FilterType::Triangle,
+    );
+    for (x, y, pixel) in silhouette.enumerate_pixels() {
+        let (mx, my) = (x + padding / SHADOW_DOWNSCALE, y + (padding + offset_y) / SHADOW_DOWNSCALE);
+        if mx < small_width && my < small_height {
+            mask.put_pixel(mx, my, Luma([pixel[3]]));
+        }
+    }
+    let mask = image::imageops::blur(&mask, (blur as f32 / SHADOW_DOWNSCALE as f32 / 2.0).max(0.5));
+    let mask = image::imageops::resize(&mask, width, height, FilterType::Triangle);
+
+    for (pixel, shadow) in canvas.pixels_mut().zip(mask.pixels()) {
+        // At most half black, so the shadow stays soft on dark backgrounds.
+        let alpha = shadow[0] as u32 / 2;
+        for channel in 0..3 {
+            pixel[channel] = (pixel[channel] as u32 * (255 - alpha) / 255) as u8;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capture(width: u32, height: u32) -> Vec<u8> {
+        let mut png = Vec::new();
+        image::DynamicImage::ImageRgba8(RgbaImage::from_pixel(width, height, Rgba([255, 255, 255, 255])))
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageOutputFormat::Png)
+            .unwrap();
+        png
+    }
+
+    #[test]
+    fn test_beautify_pads_and_rounds() {
+        let options = BeautifyOptions {
+            padding: 20,
+            background: Background::Solid { color: "#102030".to_string() },
+            corner_radius: 8,
+            shadow: 0,
+        };
+        let framed = image::load_from_memory(&beautify(&capture(60, 40), &options).unwrap()).unwrap().to_rgba8();
+
+        assert_eq!(framed.dimensions(), (100, 80));
+        assert_eq!(framed.get_pixel(0, 0).0, [0x10, 0x20, 0x30, 255]);
+        // The capture's own corner shows the background, its middle is untouched.
+        assert_eq!(framed.get_pixel(20, 20).0, [0x10, 0x20, 0x30, 255]);
+        assert_eq!(framed.get_pixel(50, 40).0, [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn test_shadow_and_gradient() {
+        let framed = image::load_from_memory(&beautify(&capture(40, 40), &BeautifyOptions::default()).unwrap())
+            .unwrap()
+            .to_rgba8();
+        let plain = gradient(168, 168, parse_hex("#667eea").unwrap(), parse_hex("#764ba2").unwrap());
+
+        assert_eq!(framed.dimensions(), (168, 168));
+        assert_eq!(framed.get_pixel(0, 0), plain.get_pixel(0, 0));
+        assert_eq!(framed.get_pixel(167, 167).0, [0x76, 0x4b, 0xa2, 255]);
+        // Just below the capture is in its shadow.
+        assert!(framed.get_pixel(84, 108)[0] < plain.get_pixel(84, 108)[0]);
+    }
+
+    #[test]
+    fn test_apply_corner_radius() {
+        let rounded = image::load_from_memory(&apply_corner_radius(&capture(40, 30), 10, None).unwrap())
+            .unwrap()
+            .to_rgba8();
+        assert_eq!(rounded.get_pixel(0, 0)[3], 0);
+        assert_eq!(rounded.get_pixel(20, 0).0, [255, 255, 255, 255]);
+
+        let border = Border { width: 2, color: "#ff0000".to_string() };
+        let outlined = image::load_from_memory(&apply_corner_radius(&capture(40, 30), 10, Some(&border)).unwrap())
+            .unwrap()
+            .to_rgba8();
+        assert_eq!(outlined.get_pixel(0, 0)[3], 0);
+        assert_eq!(outlined.get_pixel(20, 0).0, [255, 0, 0, 255]);
+        assert_eq!(outlined.get_pixel(20, 15).0, [255, 255, 255, 255]);
+        assert!(apply_corner_radius(&capture(40, 30), 10, Some(&Border { width: 6, ..border })).is_err());
+    }
+
+    #[test]
+    fn test_parse_hex() {
+        assert_eq!(parse_hex("#ff8000").unwrap().0, [255, 128, 0, 255]);
+        assert!(parse_hex("#fff").is_err());
+        assert!(parse_hex("zzzzzz").is_err());
+    }
+}
@@ -0,0 +1,251 @@
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, Frame, RgbaImage};
+use serde::{Deserialize, Serialize};
+
+use crate::color::{self, PNG_SIGNATURE};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BurstFormat {
+    Gif,
+    Apng,
+}
+
+impl BurstFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            BurstFormat::Gif => "gif",
+            BurstFormat::Apng => "png",
+        }
+    }
+}
+
+/// Settings for stitching still captures into an animation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BurstOptions {
+    /// How long each capture stays on screen.
+    pub delay_ms: u32,
+    /// Output width in pixels, keeping the aspect ratio; 0 keeps the first
+    /// capture's width.
+    pub max_width: u32,
+}
+
+impl Default for BurstOptions {
+    fn default() -> Self {
+        Self {
+            delay_ms: 500,
+            max_width: 800,
+        }
+    }
+}
+
+impl BurstOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(20..=10_000).contains(&self.delay_ms) {
+            return Err("Frame delay must be between 20 ms and 10 seconds".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Settings for capturing a screen region straight to a GIF.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GifCaptureOptions {
+    pub fps: u32,
+    /// Capture stops on its own after this long.
+    pub max_seconds: u32,
+    /// Output width in pixels, keeping the aspect ratio; 0 keeps the
+    /// region's captured width.
+    pub max_width: u32,
+}
+
+impl Default for GifCaptureOptions {
+    fn default() -> Self {
+        Self {
+            fps: 10,
+            max_seconds: 10,
+            max_width: 800,
+        }
+    }
+}
+
+impl GifCaptureOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=30).contains(&self.fps) {
+            return Err("GIF frame rate must be between 1 and 30 fps".to_string());
+        }
+        if !(1..=60).contains(&self.max_seconds) {
+            return Err("GIF length must be between 1 and 60 seconds".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn frame_delay_ms(&self) -> u32 {
+        1000 / self.fps.max(1)
+    }
+
+    pub fn max_frames(&self) -> usize {
+        (self.fps * self.max_seconds) as usize
+    }
+}
+
+/// Whether `data` is a GIF rather than a PNG.
+pub fn is_gif(data: &[u8]) -> bool {
+    data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a")
+}
+
+/// Decodes the captures and scales them all to the first one's size (capped
+/// at `max_width`), since every frame of an animation shares one canvas.
+pub fn frames(pngs: &[Vec<u8>], max_width: u32) -> Result<Vec<RgbaImage>, String> {
+    if pngs.len() < 2 {
+        return Err("Pick at least two captures to animate".to_string());
+    }
+    let mut frames = Vec::with_capacity(pngs.len());
+    let mut canvas = None;
+    for png in pngs {
+        let image = image::load_from_memory(png)
+            .map_err(|e| format!("Failed to decode image: {}", e))?
+            .to_rgba8();
+        let (width, height) = *canvas.get_or_insert_with(|| {
+            let (width, height) = image.dimensions();
+            if max_width > 0 && width > max_width {
+                let scaled = (height as u64 * max_width as u64 / width as u64).max(1) as u32;
+                (max_width, scaled)
+            } else {
+                (width, height)
+            }
+        });
+        frames.push(if image.dimensions() == (width, height) {
+            image
+        } else {
+            image::imageops::resize(&image, width, height, image::imageops::FilterType::Triangle)
+        });
+    }
+    Ok(frames)
+}
+
+/// A looping GIF showing each frame for `delay_ms`.
+pub fn encode_gif(frames: Vec<RgbaImage>, delay_ms: u32) -> Result<Vec<u8>, String> {
+    let mut gif = Vec::new();
+    {
+        let mut encoder = GifEncoder::new_with_speed(&mut gif, 10);
+        encoder
+            .set_repeat(Repeat::Infinite)
+            .map_err(|e| format!("Failed to encode GIF: {}", e))?;
+        encoder
+            .encode_frames(
+                frames
+                    .into_iter()
+                    .map(|frame| Frame::from_parts(frame, 0, 0, Delay::from_numer_denom_ms(delay_ms, 1))),
+            )
+            .map_err(|e| format!("Failed to encode GIF: {}", e))?;
+    }
+    Ok(gif)
+}
+
+/// A looping APNG showing each frame for `delay_ms`. Each frame is encoded
+/// as a PNG and its image data moved into `fdAT` chunks; viewers without
+/// APNG support show the first frame.
+pub fn encode_apng(frames: Vec<RgbaImage>, delay_ms: u32) -> Result<Vec<u8>, String> {
+    let frame_count = frames.len() as u32;
+    let delay = delay_ms.min(u16::MAX as u32) as u16;
+    let mut apng = PNG_SIGNATURE.to_vec();
+    let mut sequence = 0u32;
+
+    for (index, frame) in frames.into_iter().enumerate() {
+        let (width, height) = frame.dimensions();
+        let mut png = Vec::new();
+        image::DynamicImage::ImageRgba8(frame)
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageOutputFormat::Png)
+            .map_err(|e| format!("Failed to encode frame: {}", e))?;
+        let chunks = color::read_chunks(&png)?;
+
+        if index == 0 {
+            let ihdr = chunks.iter().find(|chunk| &chunk.kind == b"IHDR").ok_or("Frame has no header")?;
+            color::write_chunk(&mut apng, b"IHDR", ihdr.data);
+            let mut actl = frame_count.to_be_bytes().to_vec();
+            actl.extend_from_slice(&0u32.to_be_bytes());
+            color::write_chunk(&mut apng, b"acTL", &actl);
+        }
+
+        let mut fctl = Vec::with_capacity(26);
+        for value in [sequence, width, height, 0, 0] {
+            fctl.extend_from_slice(&value.to_be_bytes());
+        }
+        fctl.extend_from_slice(&delay.to_be_bytes());
+        fctl.extend_from_slice(&1000u16.to_be_bytes());
+        // Dispose: none; blend: source. Every frame covers the whole canvas.
+        fctl.extend_from_slice(&[0, 0]);
+        color::write_chunk(&mut apng, b"fcTL", &fctl);
+        sequence += 1;
+
+        for chunk in chunks.iter().filter(|chunk| &chunk.kind == b"IDAT") {
+            if index == 0 {
+                color::write_chunk(&mut apng, b"IDAT", chunk.data);
+            } else {
+                let mut fdat = sequence.to_be_bytes().to_vec();
+                fdat.extend_from_slice(chunk.data);
+                color::write_chunk(&mut apng, b"fdAT", &fdat);
+                sequence += 1;
+            }
+        }
+    }
+    color::write_chunk(&mut apng, b"IEND", &[]);
+    Ok(apng)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::AnimationDecoder;
+
+    fn png(width: u32, height: u32, shade: u8) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        image::DynamicImage::ImageRgba8(RgbaImage::from_pixel(width, height, image::Rgba([shade, 0, 0, 255])))
+            .write_to(&mut std::io::Cursor::new(&mut bytes), image::ImageOutputFormat::Png)
+            .unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_frames_share_the_first_capture_size() {
+        let frames = frames(&[png(40, 20, 0), png(10, 10, 255)], 20).unwrap();
+        assert!(frames.iter().all(|frame| frame.dimensions() == (20, 10)));
+        assert!(super::frames(&[png(4, 4, 0)], 0).is_err());
+    }
+
+    #[test]
+    fn test_gif_capture_options() {
+        let options = GifCaptureOptions::default();
+        assert!(options.validate().is_ok());
+        assert_eq!(options.frame_delay_ms(), 100);
+        assert_eq!(options.max_frames(), 100);
+        assert!(GifCaptureOptions { fps: 0, ..options.clone() }.validate().is_err());
+        assert!(GifCaptureOptions { max_seconds: 120, ..options }.validate().is_err());
+    }
+
+    #[test]
+    fn test_encoded_animations_keep_every_frame() {
+        let pngs = [png(8, 8, 0), png(8, 8, 128), png(8, 8, 255)];
+
+        let gif = encode_gif(frames(&pngs, 0).unwrap(), 200).unwrap();
+        assert!(is_gif(&gif));
+        assert!(!is_gif(&pngs[0]));
+        let decoded = image::codecs::gif::GifDecoder::new(std::io::Cursor::new(gif))
+            .unwrap()
+            .into_frames()
+            .collect_frames()
+            .unwrap();
+        assert_eq!(decoded.len(), 3);
+        assert_eq!(decoded[0].delay().numer_denom_ms(), (200, 1));
+
+        let apng = encode_apng(frames(&pngs, 0).unwrap(), 200).unwrap();
+        let decoder = image::codecs::png::PngDecoder::new(std::io::Cursor::new(apng)).unwrap();
+        assert!(decoder.is_apng());
+        let decoded = decoder.apng().into_frames().collect_frames().unwrap();
+        assert_eq!(decoded.len(), 3);
+        assert_eq!(decoded[2].buffer().get_pixel(0, 0)[0], 255);
+    }
+}
@@ -0,0 +1,303 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::spill::{self, SpillArea};
+
+/// Memory pending captures may take before the least recently used ones
+/// are moved to disk.
+pub const DEFAULT_MAX_BYTES: usize = 512 * 1024 * 1024;
+
+struct Entry {
+    bytes: Vec<u8>,
+    /// Value of the cache's clock when the capture was last stored or read.
+    last_used: u64,
+    /// Also written to a save folder, so memory pressure can drop it first.
+    saved: bool,
+}
+
+/// Pending captures' PNGs by timestamp. Past `max_bytes` the least recently
+/// used are moved to the spill area, where [`ScreenshotCache::get`] still
+/// finds them.
+pub struct ScreenshotCache {
+    entries: HashMap<u64, Entry>,
+    max_bytes: usize,
+    total_bytes: usize,
+    clock: u64,
+    spill: Option<SpillArea>,
+}
+
+impl ScreenshotCache {
+    pub fn new(max_bytes: usize, spill: Option<SpillArea>) -> Self {
+        Self {
+            entries: HashMap::new(),
+            max_bytes,
+            total_bytes: 0,
+            clock: 0,
+            spill,
+        }
+    }
+
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    /// Changes the limit, spilling captures right away if it shrank.
+    pub fn set_max_bytes(&mut self, max_bytes: usize) {
+        self.max_bytes = max_bytes;
+        self.evict_over_limit(None);
+    }
+
+    /// Stores (or replaces) a capture, then spills the least recently used
+    /// others while the cache is over its limit.
+    pub fn insert(&mut self, timestamp: u64, image_data: Vec<u8>) {
+        let last_used = self.tick();
+        self.total_bytes += image_data.len();
+        let previous = self.entries.insert(
+            timestamp,
+            Entry {
+                bytes: image_data,
+                last_used,
+                saved: false,
+            },
+        );
+        if let Some(previous) = previous {
+            self.total_bytes -= previous.bytes.len();
+        }
+        self.evict_over_limit(Some(timestamp));
+    }
+
+    /// A pending capture's PNG, from memory or, once evicted, from the
+    /// spill area on disk.
+    pub fn get(&mut self, timestamp: u64) -> Option<Vec<u8>> {
+        let now = self.tick();
+        if let Some(entry) = self.entries.get_mut(&timestamp) {
+            entry.last_used = now;
+            return Some(entry.bytes.clone());
+        }
+        self.spill.as_ref()?.read(timestamp)
+    }
+
+    /// Whether a capture is pending, without reading it back from disk.
+    pub fn contains(&self, timestamp: u64) -> bool {
+        self.entries.contains_key(&timestamp) || self.spill.as_ref().is_some_and(|area| area.contains(timestamp))
+    }
+
+    /// Notes that a capture still in memory has been written to a save folder.
+    pub fn mark_saved(&mut self, timestamp: u64) {
+        if let Some(entry) = self.entries.get_mut(&timestamp) {
+            entry.saved = true;
+        }
+    }
+
+    /// Removes a capture from memory and the spill area.
+    pub fn remove(&mut self, timestamp: u64) -> Option<Vec<u8>> {
+        if let Some(area) = &self.spill {
+            area.remove(timestamp);
+        }
+        let entry = self.entries.remove(&timestamp)?;
+        self.total_bytes -= entry.bytes.len();
+        Some(entry.bytes)
+    }
+
+    /// Forgets every pending capture, in memory and on disk.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.total_bytes = 0;
+        if let Some(area) = &self.spill {
+            area.clear();
+        }
+    }
+
+    /// Pending captures held in memory, and their total bytes.
+    pub fn memory_usage(&self) -> (usize, usize) {
+        (self.entries.len(), self.total_bytes)
+    }
+
+    /// Spills the least recently used captures, never `keep`, until the
+    /// cache fits its limit or nothing more can be spilled.
+    fn evict_over_limit(&mut self, keep: Option<u64>) {
+        if self.total_bytes <= self.max_bytes {
+            return;
+        }
+        let mut candidates: Vec<(u64, u64)> = self
+            .entries
+            .iter()
+            .filter(|(timestamp, _)| Some(**timestamp) != keep)
+            .map(|(timestamp, entry)| (entry.last_used, *timestamp))
+            .collect();
+        candidates.sort_unstable();
+        for (_, timestamp) in candidates {
+            if self.total_bytes <= self.max_bytes {
+                break;
+            }
+            if self.spill_out(timestamp) {
+                tracing::debug!("Cache over {} bytes, spilled capture {}", self.max_bytes, timestamp);
+            }
+        }
+    }
+
+    /// Moves a capture to the spill area. Saved captures that can't be
+    /// spilled are dropped; unsaved ones stay in memory rather than being
+    /// lost. Returns whether it left memory.
+    fn spill_out(&mut self, timestamp: u64) -> bool {
+        let Some(entry) = self.entries.get(&timestamp) else {
+            return false;
+        };
+        let written = match &self.spill {
+            Some(area) => area.write(timestamp, &entry.bytes),
+            None => Err(format!("Nowhere to spill capture {}", timestamp)),
+        };
+        match written {
+            Ok(()) => {}
+            Err(e) if entry.saved => tracing::warn!("{}; dropping the saved copy from memory", e),
+            Err(e) => {
+                tracing::error!("{}", e);
+                return false;
+            }
+        }
+        if let Some(entry) = self.entries.remove(&timestamp) {
+            self.total_bytes -= entry.bytes.len();
+        }
+        true
+    }
+
+    /// Moves cached captures to disk when macOS reports memory pressure,
+    /// saved ones first.
+    pub fn relieve_memory_pressure(&mut self, level: spill::PressureLevel) {
+        if self.spill.is_none() {
+            return;
+        }
+        let captures: Vec<spill::CachedCapture> = self
+            .entries
+            .iter()
+            .map(|(timestamp, entry)| spill::CachedCapture {
+                timestamp: *timestamp,
+                bytes: entry.bytes.len(),
+                saved: entry.saved,
+            })
+            .collect();
+        let order = spill::spill_order(&captures, level, spill::WARNING_KEEP_BYTES);
+        for timestamp in &order {
+            self.spill_out(*timestamp);
+        }
+        tracing::debug!("Memory pressure ({:?}): spilled {} cached captures", level, order.len());
+    }
+}
+
+/// The cache shared by every window, as Tauri managed state.
+pub struct ScreenshotStore {
+    cache: Mutex<ScreenshotCache>,
+}
+
+impl ScreenshotStore {
+    pub fn new(max_bytes: usize, spill: Option<SpillArea>) -> Self {
+        Self {
+            cache: Mutex::new(ScreenshotCache::new(max_bytes, spill)),
+        }
+    }
+
+    pub fn insert(&self, timestamp: u64, image_data: Vec<u8>) {
+        self.cache.lock().unwrap().insert(timestamp, image_data);
+        tracing::debug!("Stored image in memory cache with key: {}", timestamp);
+    }
+
+    pub fn get(&self, timestamp: u64) -> Option<Vec<u8>> {
+        self.cache.lock().unwrap().get(timestamp)
+    }
+
+    pub fn contains(&self, timestamp: u64) -> bool {
+        self.cache.lock().unwrap().contains(timestamp)
+    }
+
+    pub fn mark_saved(&self, timestamp: u64) {
+        self.cache.lock().unwrap().mark_saved(timestamp);
+    }
+
+    pub fn remove(&self, timestamp: u64) -> Option<Vec<u8>> {
+        self.cache.lock().unwrap().remove(timestamp)
+    }
+
+    pub fn clear(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+
+    pub fn memory_usage(&self) -> (usize, usize) {
+        self.cache.lock().unwrap().memory_usage()
+    }
+
+    /// Applies the `cache_limit_mb` setting.
+    pub fn set_limit_mb(&self, limit_mb: u32) {
+        self.cache.lock().unwrap().set_max_bytes(megabytes(limit_mb));
+    }
+
+    pub fn relieve_memory_pressure(&self, level: spill::PressureLevel) {
+        self.cache.lock().unwrap().relieve_memory_pressure(level);
+    }
+}
+
+/// The `cache_limit_mb` setting in bytes.
+pub fn megabytes(limit_mb: u32) -> usize {
+    limit_mb as usize * 1024 * 1024
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_get_and_remove() {
+        let store = ScreenshotStore::new(DEFAULT_MAX_BYTES, None);
+        let timestamp = 8_888_888_888;
+        let test_data = vec![1, 2, 3, 4, 5];
+
+        store.insert(timestamp, test_data.clone());
+        store.mark_saved(timestamp);
+        assert_eq!(store.get(timestamp), Some(test_data.clone()));
+        assert!(store.cache.lock().unwrap().entries[&timestamp].saved);
+
+        assert_eq!(store.remove(timestamp), Some(test_data));
+        assert_eq!(store.get(timestamp), None);
+        assert_eq!(store.memory_usage(), (0, 0));
+    }
+
+    #[test]
+    fn test_lru_eviction_spills_to_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut cache = ScreenshotCache::new(10, Some(SpillArea::with_dir(dir.path().to_path_buf()).unwrap()));
+
+        cache.insert(1, vec![1; 4]);
+        cache.insert(2, vec![2; 4]);
+        assert_eq!(cache.get(1), Some(vec![1; 4]));
+        cache.insert(3, vec![3; 4]);
+
+        // 2 was used least recently, so it went to disk and is still readable.
+        assert_eq!(cache.memory_usage(), (2, 8));
+        assert!(!cache.entries.contains_key(&2));
+        assert!(cache.contains(2));
+        assert_eq!(cache.get(2), Some(vec![2; 4]));
+
+        // Reading 2 back from disk doesn't count as use, so 1 is next.
+        cache.set_max_bytes(4);
+        assert_eq!(cache.memory_usage(), (1, 4));
+        assert!(cache.entries.contains_key(&3));
+
+        assert_eq!(cache.remove(2), None);
+        assert_eq!(cache.get(2), None);
+        assert!(!cache.contains(2));
+    }
+
+    #[test]
+    fn test_unsaved_captures_stay_without_spill_area() {
+        let mut cache = ScreenshotCache::new(4, None);
+
+        cache.insert(1, vec![1; 4]);
+        cache.mark_saved(1);
+        cache.insert(2, vec![2; 4]);
+        cache.insert(3, vec![3; 4]);
+
+        // The saved capture can be dropped; the unsaved one can't go anywhere.
+        assert!(!cache.entries.contains_key(&1));
+        assert_eq!(cache.memory_usage(), (2, 8));
+    }
+}
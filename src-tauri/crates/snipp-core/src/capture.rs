@@ -0,0 +1,209 @@
+use std::fmt;
+
+use crate::region::CaptureRegion;
+
+#[derive(Debug, Clone, Copy)]
+pub enum CaptureMode {
+    Interactive,
+    FullScreen,
+    Region(CaptureRegion),
+    /// Freezes the screen and lets the user pick a region in Snipp's own
+    /// overlay, which shows a pixel loupe.
+    Selector,
+    /// One window by its CoreGraphics window number, without its shadow.
+    Window(u32),
+    /// One screen by its `screencapture -D` display number.
+    Display(u32),
+}
+
+impl CaptureMode {
+    pub fn temp_prefix(&self) -> &'static str {
+        match self {
+            CaptureMode::Interactive => "snipp_capture",
+            CaptureMode::FullScreen => "snipp_fullscreen",
+            CaptureMode::Region(_) => "snipp_region",
+            CaptureMode::Selector => "snipp_selector",
+            CaptureMode::Window(_) => "snipp_window",
+            CaptureMode::Display(_) => "snipp_display",
+        }
+    }
+
+    /// Window grabs only see their own window; every other mode could
+    /// catch Snipp's windows in the shot.
+    pub fn hides_own_windows(&self) -> bool {
+        !matches!(self, CaptureMode::Window(_))
+    }
+
+    /// Arguments for macOS `screencapture`, writing a PNG to `temp_path`.
+    /// `window_shadow` keeps the shadow on windows picked with Space during
+    /// an interactive capture.
+    pub fn screencapture_args(&self, temp_path: &str, window_shadow: bool) -> Vec<String> {
+        let mut args = Vec::new();
+        match self {
+            CaptureMode::Interactive => {
+                args.push("-i".to_string());
+                if !window_shadow {
+                    args.push("-o".to_string());
+                }
+            }
+            CaptureMode::FullScreen => {}
+            CaptureMode::Region(region) => {
+                args.push("-R".to_string());
+                args.push(region.to_screencapture_arg());
+            }
+            // Silent full-screen grab; the selection happens on the frozen frame.
+            CaptureMode::Selector => args.push("-x".to_string()),
+            CaptureMode::Window(id) => {
+                args.extend(["-x".to_string(), "-o".to_string(), "-l".to_string(), id.to_string()]);
+            }
+            CaptureMode::Display(id) => {
+                args.extend(["-D".to_string(), id.to_string()]);
+            }
+        }
+        args.extend(["-t".to_string(), "png".to_string(), temp_path.to_string()]);
+        args
+    }
+}
+
+/// Why a capture produced no image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CaptureError {
+    /// The user dismissed the capture.
+    Cancelled,
+    /// Screen recording access hasn't been granted.
+    PermissionDenied,
+    /// The display or window asked for doesn't exist (any more).
+    NotFound(String),
+    Failed(String),
+}
+
+impl fmt::Display for CaptureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CaptureError::Cancelled => write!(f, "Screenshot capture was cancelled"),
+            CaptureError::PermissionDenied => {
+                write!(f, "Snipp needs screen recording permission to capture the screen")
+            }
+            CaptureError::NotFound(what) => write!(f, "{} is no longer available", what),
+            CaptureError::Failed(reason) => write!(f, "Screenshot capture failed: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for CaptureError {}
+
+impl From<CaptureError> for String {
+    fn from(e: CaptureError) -> Self {
+        e.to_string()
+    }
+}
+
+/// Grabs the screen in-process, without going through `screencapture` and
+/// a temp file.
+pub trait CaptureBackend: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Whether the backend handles `mode`; the rest go to `screencapture`.
+    fn supports(&self, mode: CaptureMode) -> bool;
+
+    /// Captures `mode` as PNG bytes.
+    fn capture(&self, mode: CaptureMode) -> Result<Vec<u8>, CaptureError>;
+
+    /// Where the pointer is on the desktop, in points, or `None` where the
+    /// platform won't say (Wayland keeps it from clients).
+    fn cursor_position(&self) -> Option<(f64, f64)> {
+        None
+    }
+
+    /// Whether the primary mouse button is held, for highlighting clicks;
+    /// `false` where the platform won't say.
+    fn primary_button_down(&self) -> bool {
+        false
+    }
+}
+
+/// Byte order of a 32-bit pixel in a native screen grab.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PixelLayout {
+    Bgra,
+    Argb,
+    Rgba,
+}
+
+/// Encodes raw 32-bit pixels, `bytes_per_row` apart, as an opaque PNG.
+pub fn encode_pixels(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    bytes_per_row: usize,
+    layout: PixelLayout,
+) -> Result<Vec<u8>, CaptureError> {
+    let fits = width > 0
+        && height > 0
+        && bytes_per_row >= width * 4
+        && data.len() >= bytes_per_row * (height - 1) + width * 4;
+    if !fits {
+        return Err(CaptureError::Failed(format!("Unexpected {}x{} pixel buffer", width, height)));
+    }
+    let mut rgba = Vec::with_capacity(width * height * 4);
+    for row in data.chunks(bytes_per_row).take(height) {
+        for pixel in row[..width * 4].chunks_exact(4) {
+            // Screens are opaque; skipped alpha bytes are often zero.
+            let [r, g, b] = match layout {
+                PixelLayout::Bgra => [pixel[2], pixel[1], pixel[0]],
+                PixelLayout::Argb => [pixel[1], pixel[2], pixel[3]],
+                PixelLayout::Rgba => [pixel[0], pixel[1], pixel[2]],
+            };
+            rgba.extend_from_slice(&[r, g, b, 255]);
+        }
+    }
+    let image = image::RgbaImage::from_raw(width as u32, height as u32, rgba)
+        .ok_or_else(|| CaptureError::Failed("Pixel buffer size mismatch".to_string()))?;
+
+    let mut png = Vec::new();
+    image
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageOutputFormat::Png)
+        .map_err(|e| CaptureError::Failed(format!("Failed to encode capture: {}", e)))?;
+    Ok(png)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_screencapture_args() {
+        assert_eq!(CaptureMode::FullScreen.screencapture_args("/tmp/a.png", true), ["-t", "png", "/tmp/a.png"]);
+        assert_eq!(
+            CaptureMode::Window(42).screencapture_args("/tmp/a.png", true),
+            ["-x", "-o", "-l", "42", "-t", "png", "/tmp/a.png"]
+        );
+        assert_eq!(
+            CaptureMode::Display(2).screencapture_args("/tmp/a.png", true),
+            ["-D", "2", "-t", "png", "/tmp/a.png"]
+        );
+        assert_eq!(CaptureMode::Interactive.screencapture_args("/tmp/a.png", true)[..2], ["-i", "-t"]);
+        assert_eq!(CaptureMode::Interactive.screencapture_args("/tmp/a.png", false)[..2], ["-i", "-o"]);
+        assert!(!CaptureMode::Window(42).hides_own_windows());
+        assert!(CaptureMode::Selector.hides_own_windows());
+    }
+
+    #[test]
+    fn test_encode_pixels_reorders_and_skips_row_padding() {
+        // 2x2 BGRA with 4 bytes of padding per row.
+        let data = [
+            0, 0, 255, 0, 0, 255, 0, 0, 9, 9, 9, 9, //
+            255, 0, 0, 0, 10, 20, 30, 0, 9, 9, 9, 9,
+        ];
+
+        let png = encode_pixels(&data, 2, 2, 12, PixelLayout::Bgra).unwrap();
+        let image = image::load_from_memory(&png).unwrap().to_rgba8();
+
+        assert_eq!(image.get_pixel(0, 0).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(1, 0).0, [0, 255, 0, 255]);
+        assert_eq!(image.get_pixel(0, 1).0, [0, 0, 255, 255]);
+        assert_eq!(image.get_pixel(1, 1).0, [30, 20, 10, 255]);
+        assert!(encode_pixels(&data, 2, 3, 12, PixelLayout::Bgra).is_err());
+        assert_eq!(CaptureError::Cancelled.to_string(), "Screenshot capture was cancelled");
+    }
+}
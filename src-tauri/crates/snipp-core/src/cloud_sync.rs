@@ -0,0 +1,295 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::error::SnippError;
+use crate::numbered_path;
+
+/// Sync engines (iCloud's bird in particular) briefly lock files they are
+/// uploading; a few spaced retries ride that out.
+const WRITE_ATTEMPTS: u32 = 3;
+const RETRY_DELAY: Duration = Duration::from_millis(250);
+
+/// `SF_DATALESS` from <sys/stat.h>: the file is a cloud placeholder whose
+/// contents have been evicted from disk.
+#[cfg(target_os = "macos")]
+const SF_DATALESS: u32 = 0x4000_0000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CloudProvider {
+    ICloud,
+    Dropbox,
+    OneDrive,
+    GoogleDrive,
+    /// Some other File Provider under ~/Library/CloudStorage.
+    Other,
+}
+
+impl CloudProvider {
+    pub fn label(self) -> &'static str {
+        match self {
+            CloudProvider::ICloud => "iCloud Drive",
+            CloudProvider::Dropbox => "Dropbox",
+            CloudProvider::OneDrive => "OneDrive",
+            CloudProvider::GoogleDrive => "Google Drive",
+            CloudProvider::Other => "The cloud storage provider",
+        }
+    }
+}
+
+/// Where a saved file stands with respect to cloud sync.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncStatus {
+    /// Not in a synced folder.
+    Local,
+    /// In a synced folder with its contents on disk.
+    Synced,
+    /// Evicted to the cloud; opening it triggers a download.
+    Placeholder,
+    /// The provider left a conflicting copy next to it.
+    Conflict,
+    Missing,
+}
+
+pub fn detect_provider(path: &Path) -> Option<CloudProvider> {
+    let path = path.to_string_lossy();
+    if path.contains("/Library/Mobile Documents/") || path.ends_with("/Library/Mobile Documents") {
+        return Some(CloudProvider::ICloud);
+    }
+    if let Some((_, rest)) = path.split_once("/Library/CloudStorage/") {
+        let provider = rest.split('/').next().unwrap_or_default();
+        return Some(if provider.starts_with("Dropbox") {
+            CloudProvider::Dropbox
+        } else if provider.starts_with("OneDrive") {
+            CloudProvider::OneDrive
+        } else if provider.starts_with("GoogleDrive") {
+            CloudProvider::GoogleDrive
+        } else {
+            CloudProvider::Other
+        });
+    }
+    // Legacy Dropbox installs sync ~/Dropbox directly.
+    if path.contains("/Dropbox/") || path.ends_with("/Dropbox") {
+        return Some(CloudProvider::Dropbox);
+    }
+    None
+}
+
+/// Matches the names sync engines give the losing side of a conflict:
+/// Dropbox's "(conflicted copy ...)" and iCloud's numbered duplicates
+/// ("Snipp ... 2.png").
+pub fn is_conflict_copy_of(candidate: &str, original: &Path) -> bool {
+    let (Some(stem), Some(ext)) = (
+        original.file_stem().and_then(|s| s.to_str()),
+        original.extension().and_then(|e| e.to_str()),
+    ) else {
+        return false;
+    };
+    let Some(middle) = candidate
+        .strip_prefix(stem)
+        .and_then(|rest| rest.strip_suffix(&format!(".{}", ext)))
+    else {
+        return false;
+    };
+
+    middle.contains("conflicted copy")
+        || middle
+            .strip_prefix(' ')
+            .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+}
+
+#[cfg(target_os = "macos")]
+fn is_dataless(metadata: &std::fs::Metadata) -> bool {
+    use std::os::macos::fs::MetadataExt;
+    metadata.st_flags() & SF_DATALESS != 0
+}
+
+#[cfg(not(target_os = "macos"))]
+fn is_dataless(_metadata: &std::fs::Metadata) -> bool {
+    false
+}
+
+/// Checks a saved file without reading it, so placeholders aren't pulled
+/// back down just to show history.
+pub fn status_of(path: &Path) -> SyncStatus {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return SyncStatus::Missing;
+    };
+    if detect_provider(path).is_none() {
+        return SyncStatus::Local;
+    }
+    if is_dataless(&metadata) {
+        return SyncStatus::Placeholder;
+    }
+
+    let has_conflict = path
+        .parent()
+        .and_then(|dir| std::fs::read_dir(dir).ok())
+        .is_some_and(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .any(|entry| is_conflict_copy_of(&entry.file_name().to_string_lossy(), path))
+        });
+    if has_conflict {
+        SyncStatus::Conflict
+    } else {
+        SyncStatus::Synced
+    }
+}
+
+/// Turns an IO failure into an error that names the sync provider, since
+/// a bare "Operation not permitted" says little when iCloud is the cause.
+/// The error keeps the failure's kind, so a full disk stays `disk_full`.
+pub fn describe_error(path: &Path, error: &std::io::Error) -> SnippError {
+    let message = match detect_provider(path) {
+        Some(provider) => format!(
+            "{} could not store {}: {}. Check that it is signed in and has free space.",
+            provider.label(),
+            path.display(),
+            error
+        ),
+        None => format!("Failed to save file: {}", error),
+    };
+    SnippError::from(std::io::Error::new(error.kind(), message))
+}
+
+fn partial_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{}.snipp-partial", name))
+}
+
+fn write_once(path: &Path, data: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let partial = partial_path(path);
+    let result = (|| {
+        let mut file = std::fs::File::create(&partial)?;
+        file.write_all(data)?;
+        file.sync_all()?;
+        std::fs::rename(&partial, path)
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&partial);
+    }
+    result
+}
+
+/// Writes to a hidden sibling and renames it into place, so sync engines
+/// never upload a half-written capture, then confirms the file on disk is
+/// complete. Retries briefly while a provider holds the folder busy.
+pub fn write_file(path: &Path, data: &[u8]) -> Result<SyncStatus, SnippError> {
+    let mut attempt = 1;
+    loop {
+        match write_once(path, data) {
+            Ok(()) => break,
+            Err(e) if attempt < WRITE_ATTEMPTS && detect_provider(path).is_some() => {
+                tracing::warn!("Retrying save to {} after: {}", path.display(), e);
+                std::thread::sleep(RETRY_DELAY * attempt);
+                attempt += 1;
+            }
+            Err(e) => return Err(describe_error(path, &e)),
+        }
+    }
+
+    let written = std::fs::metadata(path)
+        .map_err(|e| describe_error(path, &e))?
+        .len();
+    if written != data.len() as u64 {
+        return Err(SnippError::Io(format!(
+            "{} was only partly written ({} of {} bytes)",
+            path.display(),
+            written,
+            data.len()
+        )));
+    }
+    Ok(status_of(path))
+}
+
+/// Like [`write_file`], but never replaces an existing file: when `path` is
+/// taken the next free `-1`, `-2`, ... name is used. The name is claimed
+/// with an exclusive create before the contents are renamed over it, so two
+/// saves racing for the same name can't overwrite each other. Returns the
+/// path actually written.
+pub fn write_new_file(path: &Path, data: &[u8]) -> Result<(PathBuf, SyncStatus), SnippError> {
+    let mut n = 0;
+    loop {
+        let candidate = if n == 0 { path.to_path_buf() } else { numbered_path(path, n) };
+        match std::fs::OpenOptions::new().write(true).create_new(true).open(&candidate) {
+            Ok(_) => {
+                return match write_file(&candidate, data) {
+                    Ok(status) => Ok((candidate, status)),
+                    Err(e) => {
+                        let _ = std::fs::remove_file(&candidate);
+                        Err(e)
+                    }
+                };
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => n += 1,
+            Err(e) => return Err(describe_error(&candidate, &e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detect_provider() {
+        let icloud = Path::new("/Users/me/Library/Mobile Documents/com~apple~CloudDocs/Shots");
+        let dropbox = Path::new("/Users/me/Library/CloudStorage/Dropbox-Team/Shots");
+        let legacy = Path::new("/Users/me/Dropbox/Shots");
+
+        assert_eq!(detect_provider(icloud), Some(CloudProvider::ICloud));
+        assert_eq!(detect_provider(dropbox), Some(CloudProvider::Dropbox));
+        assert_eq!(detect_provider(legacy), Some(CloudProvider::Dropbox));
+        assert_eq!(detect_provider(Path::new("/Users/me/Desktop")), None);
+    }
+
+    #[test]
+    fn test_is_conflict_copy_of() {
+        let original = Path::new("/d/Snipp 24-01-01 at 10.00.00.png");
+
+        assert!(is_conflict_copy_of("Snipp 24-01-01 at 10.00.00 2.png", original));
+        assert!(is_conflict_copy_of(
+            "Snipp 24-01-01 at 10.00.00 (Mac's conflicted copy 2024-01-02).png",
+            original
+        ));
+        assert!(!is_conflict_copy_of("Snipp 24-01-01 at 10.00.00 (1).png", original));
+        assert!(!is_conflict_copy_of("Snipp 24-01-01 at 10.00.00.png", original));
+    }
+
+    #[test]
+    fn test_write_file_is_atomic_and_verified() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("Snipp test.png");
+
+        assert_eq!(write_file(&path, b"png data").unwrap(), SyncStatus::Local);
+        assert_eq!(std::fs::read(&path).unwrap(), b"png data");
+        assert!(!partial_path(&path).exists());
+        assert_eq!(status_of(&dir.path().join("gone.png")), SyncStatus::Missing);
+    }
+
+    #[test]
+    fn test_write_new_file_appends_counter_instead_of_overwriting() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("snipp-1700000000000.png");
+
+        let (first, _) = write_new_file(&path, b"first").unwrap();
+        let (second, _) = write_new_file(&path, b"second").unwrap();
+        let (third, _) = write_new_file(&path, b"third").unwrap();
+
+        assert_eq!(first, path);
+        assert_eq!(second, dir.path().join("snipp-1700000000000-1.png"));
+        assert_eq!(third, dir.path().join("snipp-1700000000000-2.png"));
+        assert_eq!(std::fs::read(&path).unwrap(), b"first");
+        assert_eq!(std::fs::read(&second).unwrap(), b"second");
+        assert!(!partial_path(&second).exists());
+    }
+}
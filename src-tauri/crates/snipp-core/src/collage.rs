@@ -0,0 +1,183 @@
+use image::RgbaImage;
+use serde::{Deserialize, Serialize};
+
+use crate::beautify::{self, Background};
+use crate::color;
+
+/// Most captures a collage can hold; past this the result is too large to
+/// be useful for sharing.
+pub const MAX_COLLAGE_IMAGES: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CollageArrangement {
+    /// Rows of `columns` captures, filled left to right.
+    Grid { columns: u32 },
+    /// Every capture in a single row.
+    Strip,
+}
+
+/// How the Collage action lays captures out. Sizes are in image pixels;
+/// captures keep their own size and are centered in their cell.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CollageLayout {
+    pub arrangement: CollageArrangement,
+    /// Space between captures and around the edge.
+    pub gutter: u32,
+    pub background: Background,
+}
+
+impl Default for CollageLayout {
+    fn default() -> Self {
+        Self {
+            arrangement: CollageArrangement::Grid { columns: 2 },
+            gutter: 32,
+            background: Background::Solid {
+                color: "#ffffff".to_string(),
+            },
+        }
+    }
+}
+
+impl CollageLayout {
+    pub fn validate(&self) -> Result<(), String> {
+        if let CollageArrangement::Grid { columns } = self.arrangement {
+            if !(1..=8).contains(&columns) {
+                return Err("A collage grid must have between 1 and 8 columns".to_string());
+            }
+        }
+        if self.gutter > 256 {
+            return Err("The collage gutter can be at most 256 pixels".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// The top-left corner of each image and the canvas size. Columns are as
+/// wide as their widest image and rows as tall as their tallest.
+pub fn arrange(sizes: &[(u32, u32)], layout: &CollageLayout) -> (Vec<(u32, u32)>, (u32, u32)) {
+    let columns = match layout.arrangement {
+        CollageArrangement::Grid { columns } => (columns as usize).clamp(1, sizes.len().max(1)),
+        CollageArrangement::Strip => sizes.len().max(1),
+    };
+    let rows = sizes.len().div_ceil(columns);
+    let mut column_widths = vec![0u32; columns];
+    let mut row_heights = vec![0u32; rows];
+    for (i, &(width, height)) in sizes.iter().enumerate() {
+        column_widths[i % columns] = column_widths[i % columns].max(width);
+        row_heights[i / columns] = row_heights[i / columns].max(height);
+    }
+
+    let gutter = layout.gutter;
+    let offsets = |lengths: &[u32]| {
+        lengths
+            .iter()
+            .scan(gutter, |start, length| {
+                let offset = *start;
+                *start += length + gutter;
+                Some(offset)
+            })
+            .collect::<Vec<_>>()
+    };
+    let column_x = offsets(&column_widths);
+    let row_y = offsets(&row_heights);
+
+    let positions = sizes
+        .iter()
+        .enumerate()
+        .map(|(i, &(width, height))| {
+            let (column, row) = (i % columns, i / columns);
+            (
+                column_x[column] + (column_widths[column] - width) / 2,
+                row_y[row] + (row_heights[row] - height) / 2,
+            )
+        })
+        .collect();
+    let width = column_widths.iter().sum::<u32>() + gutter * (columns as u32 + 1);
+    let height = row_heights.iter().sum::<u32>() + gutter * (rows as u32 + 1);
+    (positions, (width, height))
+}
+
+/// Combines PNG captures, in order, into one image per `layout`. The first
+/// capture's color profile is kept. Decoding is blocking, so call this off
+/// the async runtime.
+pub fn compose(pngs: &[Vec<u8>], layout: &CollageLayout) -> Result<Vec<u8>, String> {
+    layout.validate()?;
+    if pngs.is_empty() {
+        return Err("Pick at least one capture for the collage".to_string());
+    }
+    if pngs.len() > MAX_COLLAGE_IMAGES {
+        return Err(format!("A collage can hold at most {} captures", MAX_COLLAGE_IMAGES));
+    }
+
+    let images = pngs
+        .iter()
+        .map(|png| {
+            image::load_from_memory(png)
+                .map(|image| image.to_rgba8())
+                .map_err(|e| format!("Failed to decode image: {}", e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let sizes: Vec<_> = images.iter().map(RgbaImage::dimensions).collect();
+    let (positions, (width, height)) = arrange(&sizes, layout);
+
+    let mut canvas = beautify::fill(&layout.background, width, height)?;
+    for (image, (x, y)) in images.iter().zip(positions) {
+        image::imageops::overlay(&mut canvas, image, x as i64, y as i64);
+    }
+
+    let mut png = Vec::new();
+    image::DynamicImage::ImageRgba8(canvas)
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageOutputFormat::Png)
+        .map_err(|e| format!("Failed to encode image: {}", e))?;
+    let icc = color::icc_profile(&pngs[0]);
+    color::apply_profile(png, icc.as_deref(), color::ColorProfileMode::Preserve)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arrange_centers_captures_in_cells() {
+        let layout = CollageLayout {
+            arrangement: CollageArrangement::Grid { columns: 2 },
+            gutter: 10,
+            ..CollageLayout::default()
+        };
+
+        let (positions, size) = arrange(&[(100, 50), (60, 80), (40, 40)], &layout);
+
+        assert_eq!(size, (10 + 100 + 10 + 60 + 10, 10 + 80 + 10 + 40 + 10));
+        assert_eq!(positions, vec![(10, 25), (120, 10), (40, 100)]);
+
+        let strip = CollageLayout {
+            arrangement: CollageArrangement::Strip,
+            gutter: 0,
+            ..CollageLayout::default()
+        };
+        assert_eq!(arrange(&[(10, 10), (20, 10), (5, 10)], &strip).1, (35, 10));
+    }
+
+    #[test]
+    fn test_compose_fills_background() {
+        let mut png = Vec::new();
+        image::DynamicImage::ImageRgba8(RgbaImage::from_pixel(4, 4, image::Rgba([0, 0, 0, 255])))
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageOutputFormat::Png)
+            .unwrap();
+        let layout = CollageLayout {
+            arrangement: CollageArrangement::Strip,
+            gutter: 2,
+            ..CollageLayout::default()
+        };
+
+        let collage = image::load_from_memory(&compose(&[png.clone(), png], &layout).unwrap()).unwrap().to_rgba8();
+
+        assert_eq!(collage.dimensions(), (14, 8));
+        assert_eq!(collage.get_pixel(0, 0), &image::Rgba([255, 255, 255, 255]));
+        assert_eq!(collage.get_pixel(2, 2), &image::Rgba([0, 0, 0, 255]));
+        assert_eq!(collage.get_pixel(7, 2), &image::Rgba([255, 255, 255, 255]));
+        assert!(compose(&[], &layout).is_err());
+    }
+}
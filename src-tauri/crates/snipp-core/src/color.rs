@@ -0,0 +1,169 @@
+use flate2::{read::ZlibDecoder, write::ZlibEncoder, Compression};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+
+pub const PNG_SIGNATURE: [u8; 8] = [137, 80, 78, 71, 13, 10, 26, 10];
+
+/// Chunks that describe a PNG's color space; replaced together when retagging.
+const COLOR_CHUNKS: [&[u8; 4]; 4] = [b"iCCP", b"sRGB", b"gAMA", b"cHRM"];
+
+/// What happens to the display's color profile when a capture is re-encoded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorProfileMode {
+    /// Keep the pixels as captured and embed the source profile.
+    #[default]
+    Preserve,
+    /// Convert the pixels to sRGB so untagged viewers show the same colors.
+    ConvertToSrgb,
+}
+
+pub struct Chunk<'a> {
+    pub kind: [u8; 4],
+    pub data: &'a [u8],
+}
+
+pub fn read_chunks(png: &[u8]) -> Result<Vec<Chunk<'_>>, String> {
+    let mut rest = png.strip_prefix(&PNG_SIGNATURE[..]).ok_or("Not a PNG image")?;
+    let mut chunks = Vec::new();
+    while rest.len() >= 12 {
+        let len = u32::from_be_bytes([rest[0], rest[1], rest[2], rest[3]]) as usize;
+        if rest.len() < len + 12 {
+            return Err("Truncated PNG chunk".to_string());
+        }
+        chunks.push(Chunk {
+            kind: [rest[4], rest[5], rest[6], rest[7]],
+            data: &rest[8..8 + len],
+        });
+        rest = &rest[len + 12..];
+    }
+    Ok(chunks)
+}
+
+pub fn write_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    let mut crc = flate2::Crc::new();
+    crc.update(kind);
+    crc.update(data);
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+    out.extend_from_slice(&crc.sum().to_be_bytes());
+}
+
+/// The ICC profile embedded in a PNG's `iCCP` chunk, if any.
+pub fn icc_profile(png: &[u8]) -> Option<Vec<u8>> {
+    let chunks = read_chunks(png).ok()?;
+    let iccp = chunks.iter().find(|chunk| &chunk.kind == b"iCCP")?;
+    // Profile name, NUL, compression method (always zlib), compressed profile.
+    let name_end = iccp.data.iter().position(|&b| b == 0)?;
+    let compressed = iccp.data.get(name_end + 2..)?;
+    let mut profile = Vec::new();
+    ZlibDecoder::new(compressed).read_to_end(&mut profile).ok()?;
+    Some(profile)
+}
+
+/// Rewrites the color chunks of `png`: embeds `icc`, or marks the image as
+/// sRGB when there is none. Pixel data is copied untouched.
+fn retag(png: &[u8], icc: Option<&[u8]>) -> Result<Vec<u8>, String> {
+    let mut out = PNG_SIGNATURE.to_vec();
+    for chunk in read_chunks(png)? {
+        if COLOR_CHUNKS.contains(&&chunk.kind) {
+            continue;
+        }
+        write_chunk(&mut out, &chunk.kind, chunk.data);
+        // Color chunks must come before PLTE and IDAT; right after IHDR is safe.
+        if &chunk.kind == b"IHDR" {
+            match icc {
+                Some(icc) => {
+                    let mut data = b"ICC Profile\0\0".to_vec();
+                    let mut encoder = ZlibEncoder::new(&mut data, Compression::default());
+                    encoder
+                        .write_all(icc)
+                        .and_then(|_| encoder.finish().map(|_| ()))
+                        .map_err(|e| format!("Failed to compress color profile: {}", e))?;
+                    write_chunk(&mut out, b"iCCP", &data);
+                }
+                None => write_chunk(&mut out, b"sRGB", &[0]),
+            }
+        }
+    }
+    Ok(out)
+}
+
+fn convert_to_srgb(png: &[u8], icc: &[u8]) -> Result<Vec<u8>, String> {
+    let input = qcms::Profile::new_from_slice(icc, false).ok_or("Unsupported color profile")?;
+    let mut output = qcms::Profile::new_sRGB();
+    output.precache_output_transform();
+    let transform = qcms::Transform::new(&input, &output, qcms::DataType::RGBA8, qcms::Intent::Perceptual)
+        .ok_or("Unsupported color profile")?;
+
+    let mut image = image::load_from_memory(png)
+        .map_err(|e| format!("Failed to decode image: {}", e))?
+        .to_rgba8();
+    transform.apply(&mut image);
+
+    let mut converted = Vec::new();
+    image::DynamicImage::ImageRgba8(image)
+        .write_to(&mut std::io::Cursor::new(&mut converted), image::ImageOutputFormat::Png)
+        .map_err(|e| format!("Failed to encode image: {}", e))?;
+    retag(&converted, None)
+}
+
+/// Applies `mode` to a re-encoded PNG whose pixels came from a source tagged
+/// with `icc` (decoding and re-encoding drops the profile). Untagged sources
+/// are returned as is; a profile that can't be converted is kept instead.
+pub fn apply_profile(png: Vec<u8>, icc: Option<&[u8]>, mode: ColorProfileMode) -> Result<Vec<u8>, String> {
+    let Some(icc) = icc else {
+        return Ok(png);
+    };
+    if mode == ColorProfileMode::ConvertToSrgb {
+        match convert_to_srgb(&png, icc) {
+            Ok(converted) => return Ok(converted),
+            Err(e) => tracing::warn!("Keeping the capture's color profile: {}", e),
+        }
+    }
+    if icc_profile(&png).is_some() {
+        return Ok(png);
+    }
+    retag(&png, Some(icc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_png() -> Vec<u8> {
+        let mut png = Vec::new();
+        image::DynamicImage::ImageRgba8(image::RgbaImage::from_pixel(4, 2, image::Rgba([200, 40, 10, 255])))
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageOutputFormat::Png)
+            .unwrap();
+        png
+    }
+
+    #[test]
+    fn test_preserve_embeds_profile_and_keeps_pixels() {
+        let png = sample_png();
+        let icc = b"not a real profile, just bytes".to_vec();
+
+        let tagged = apply_profile(png.clone(), Some(&icc), ColorProfileMode::Preserve).unwrap();
+
+        assert_eq!(icc_profile(&tagged), Some(icc.clone()));
+        assert_eq!(
+            image::load_from_memory(&tagged).unwrap().to_rgba8(),
+            image::load_from_memory(&png).unwrap().to_rgba8()
+        );
+        // Already tagged images aren't rewritten again.
+        let again = apply_profile(tagged.clone(), Some(&icc), ColorProfileMode::Preserve).unwrap();
+        assert_eq!(again, tagged);
+    }
+
+    #[test]
+    fn test_convert_falls_back_to_preserve_for_unreadable_profile() {
+        let png = sample_png();
+        let icc = b"garbage".to_vec();
+
+        assert_eq!(apply_profile(png.clone(), None, ColorProfileMode::ConvertToSrgb).unwrap(), png);
+        let kept = apply_profile(png, Some(&icc), ColorProfileMode::ConvertToSrgb).unwrap();
+        assert_eq!(icc_profile(&kept), Some(icc));
+    }
+}
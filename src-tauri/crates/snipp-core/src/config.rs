@@ -0,0 +1,886 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::annotate::StampSettings;
+use crate::beautify::{BeautifyOptions, CornerOptions};
+use crate::burst::{BurstOptions, GifCaptureOptions};
+use crate::collage::CollageLayout;
+use crate::error::SnippError;
+use crate::highlight::ClickHighlight;
+use crate::logs::LogLevel;
+use crate::color::ColorProfileMode;
+use crate::metadata::MetadataMode;
+use crate::naming::DEFAULT_FILENAME_TEMPLATE;
+use crate::output::OutputFormat;
+use crate::recording::{AnimationOptions, RecordingLimits, RecordingQuality, WebcamOverlay};
+use crate::redact::{AutoRedactSettings, RedactStyle};
+use crate::region::{CaptureRegion, RegionPreset};
+use crate::watch::WatchFolder;
+
+// Missing fields fall back to `Default`, so configs written by older builds
+// keep loading as new settings are added.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppConfig {
+    pub default_save_location: String,
+    /// Extra folders (e.g. a mounted team share) that every save also goes to.
+    pub additional_save_locations: Vec<String>,
+    /// Folder on an SMB/NFS share that gets a copy of every save; empty
+    /// disables it. Copies made while it is unmounted wait in a queue.
+    pub mirror_location: String,
+    /// Rhai script whose `on_capture`, `on_save` and `on_upload` functions
+    /// run on those events; empty disables hooks.
+    pub hook_script: String,
+    /// Imports screenshots taken outside Snipp into history.
+    pub watch_folder: WatchFolder,
+    /// Write `name.png.json` metadata next to every saved capture.
+    pub write_sidecar: bool,
+    /// Whether re-encoded captures keep the display profile or become sRGB.
+    pub color_profile: ColorProfileMode,
+    /// Whether saved captures keep, lose, or get provenance metadata.
+    pub metadata: MetadataMode,
+    /// Overwrite deleted captures (their thumbnails and spilled cache
+    /// files too) before unlinking.
+    pub secure_delete: bool,
+    /// Megabytes of memory unsaved captures may use before the least
+    /// recently used are moved to disk.
+    pub cache_limit_mb: u32,
+    /// Least severe messages written to the log file.
+    pub log_level: LogLevel,
+    /// strftime pattern for saved files; `{seq}` is a counter that restarts daily.
+    pub filename_template: String,
+    /// Format captures are saved in; anything but PNG is saved as sRGB.
+    pub output_format: OutputFormat,
+    /// 1-100, used when `output_format` is JPEG.
+    pub jpeg_quality: u8,
+    /// 1-100, used when `output_format` is AVIF.
+    pub avif_quality: u8,
+    /// 1-100, used when `output_format` is HEIC.
+    pub heic_quality: u8,
+    /// Keep the drop shadow on windows picked during an interactive capture.
+    pub window_shadow: bool,
+    /// Resize Retina captures to their size in points before they're shown.
+    pub downscale_retina: bool,
+    /// Seconds counted down before a timed capture.
+    pub default_capture_delay: u32,
+    /// Select areas on a frozen frame in Snipp's own overlay instead of the
+    /// system picker.
+    pub selection_overlay: bool,
+    pub capture_hotkey: String,
+    pub import_clipboard_hotkey: String,
+    /// Re-captures the last selected region; empty disables the shortcut.
+    pub repeat_region_hotkey: String,
+    /// The last area captured, so Capture Last Region survives a restart.
+    /// Kept by the backend; settings updates don't overwrite it.
+    pub last_region: Option<CaptureRegion>,
+    /// Selects an area and copies its recognized text; empty disables it.
+    pub capture_text_hotkey: String,
+    /// Summons the quick search palette; empty disables it.
+    pub quick_search_hotkey: String,
+    /// Recognize text in saved captures so quick search can match it.
+    pub index_capture_text: bool,
+    pub auto_copy_after_capture: bool,
+    /// Off saves captures straight away instead of showing the preview popup.
+    pub show_capture_popup: bool,
+    /// Show the preview in a panel under the menu bar icon instead of the
+    /// corner popup.
+    pub tray_popover: bool,
+    /// Hand focus back to the app in front before the capture once the popup is up.
+    pub restore_focus_after_capture: bool,
+    /// Speak capture results through VoiceOver.
+    pub announce_actions: bool,
+    pub auto_copy_after_edit: bool,
+    /// Named rectangles shared by still captures and recordings.
+    pub region_presets: Vec<RegionPreset>,
+    /// Empty means auto-detect (Homebrew prefixes, then PATH).
+    pub ffmpeg_path: String,
+    pub record_microphone: bool,
+    /// Empty means the system default input.
+    pub microphone_device: String,
+    /// Captured through ScreenCaptureKit, so it needs no loopback driver.
+    pub record_system_audio: bool,
+    pub webcam_overlay: WebcamOverlay,
+    /// Presets can override this for their own captures.
+    pub click_highlight: ClickHighlight,
+    /// Default encoder settings; a recording can override them when started.
+    pub recording_quality: RecordingQuality,
+    pub recording_limits: RecordingLimits,
+    /// Defaults for GIF/WebP conversion of recordings.
+    pub animation_export: AnimationOptions,
+    /// Defaults for animating a set of still captures.
+    pub burst_animation: BurstOptions,
+    /// Frame rate, length and size for region GIF captures.
+    pub gif_capture: GifCaptureOptions,
+    pub editor_grid: EditorGrid,
+    pub text_annotation: TextDefaults,
+    /// Named annotation styles the editor can switch between.
+    pub annotation_styles: Vec<AnnotationStyle>,
+    /// A preset capture fails its baseline check when more than this
+    /// percentage of pixels changed.
+    pub regression_threshold_percent: f64,
+    pub retention: RetentionPolicy,
+    pub translation: TranslationSettings,
+    pub stamp: StampSettings,
+    /// Framing applied by the popup's Beautify action.
+    pub beautify: BeautifyOptions,
+    /// Radius and border used by the popup's Round Corners action.
+    pub corners: CornerOptions,
+    /// How the editor's Redact tool hides an area.
+    pub redaction: RedactStyle,
+    /// What the popup's Redact action looks for.
+    pub auto_redact: AutoRedactSettings,
+    /// How the Collage action lays out captures.
+    pub collage: CollageLayout,
+    pub upload: UploadSettings,
+    pub onboarding_completed: bool,
+}
+
+/// Opt-in cleanup of old Snipp files in the save folder.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionPolicy {
+    pub enabled: bool,
+    /// Files older than this many days are moved to the Trash.
+    pub days: u32,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            days: 30,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TranslationProvider {
+    /// Runs a Shortcuts shortcut built around Apple's Translate action.
+    Apple,
+    /// A LibreTranslate-compatible HTTP endpoint.
+    Api,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TranslationSettings {
+    pub provider: TranslationProvider,
+    /// Language code used when the popup asks for a translation.
+    pub target_language: String,
+    pub shortcut_name: String,
+    pub api_url: String,
+    /// Sent with API requests when non-empty.
+    pub api_key: String,
+}
+
+impl Default for TranslationSettings {
+    fn default() -> Self {
+        Self {
+            provider: TranslationProvider::Apple,
+            target_language: "en".to_string(),
+            shortcut_name: "Snipp Translate".to_string(),
+            api_url: String::new(),
+            api_key: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UploadProvider {
+    Zipline,
+    Chibisafe,
+    Lutim,
+    /// Any host described by `CustomUploader`.
+    Custom,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UploadHeader {
+    pub name: String,
+    pub value: String,
+}
+
+/// A multipart upload endpoint; the presets are expressed in these terms.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CustomUploader {
+    pub endpoint: String,
+    /// Form field that carries the image.
+    pub file_field: String,
+    pub headers: Vec<UploadHeader>,
+    /// Extra text fields sent with the file.
+    pub form: Vec<UploadHeader>,
+    /// Share link built from the JSON response, e.g. "{data.link}".
+    pub url_template: String,
+}
+
+impl Default for CustomUploader {
+    fn default() -> Self {
+        Self {
+            endpoint: String::new(),
+            file_field: "file".to_string(),
+            headers: Vec::new(),
+            form: Vec::new(),
+            url_template: "{url}".to_string(),
+        }
+    }
+}
+
+/// Optional step that swaps the upload link for a short one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShortenerSettings {
+    pub enabled: bool,
+    /// GET request with `{url}` standing in for the encoded link, e.g.
+    /// "https://is.gd/create.php?format=simple&url={url}".
+    pub request_url: String,
+    pub headers: Vec<UploadHeader>,
+    /// Short link built from a JSON response; empty takes the body as is.
+    pub url_template: String,
+}
+
+impl Default for ShortenerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            request_url: "https://is.gd/create.php?format=simple&url={url}".to_string(),
+            headers: Vec::new(),
+            url_template: String::new(),
+        }
+    }
+}
+
+/// Where captures are uploaded when the user asks for a share link.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UploadSettings {
+    pub enabled: bool,
+    pub provider: UploadProvider,
+    /// Base URL of the self-hosted instance, e.g. "https://img.example.com".
+    pub server_url: String,
+    /// API token; Lutim needs none.
+    pub token: String,
+    /// Upload every capture as soon as it is saved.
+    pub upload_after_save: bool,
+    /// Uploads allowed to run at the same time.
+    pub max_concurrent: u32,
+    /// Uploads started per minute against one host; 0 means no limit.
+    pub uploads_per_minute: u32,
+    /// Upload in whichever format is smallest without visibly changing the capture.
+    pub auto_format: bool,
+    pub custom: CustomUploader,
+    pub shortener: ShortenerSettings,
+}
+
+impl Default for UploadSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            provider: UploadProvider::Zipline,
+            server_url: String::new(),
+            token: String::new(),
+            upload_after_save: false,
+            max_concurrent: 2,
+            uploads_per_minute: 30,
+            auto_format: false,
+            custom: CustomUploader::default(),
+            shortener: ShortenerSettings::default(),
+        }
+    }
+}
+
+/// Starting style for new text annotations in the editor.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TextDefaults {
+    /// Installed family name; empty means the editor's built-in font.
+    pub font_family: String,
+    pub font_size: u32,
+    pub color: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnnotationStyle {
+    pub name: String,
+    /// Stroke colour for shapes, arrows and lines.
+    pub color: String,
+    pub stroke_width: u32,
+    pub font_family: String,
+    pub font_size: u32,
+    pub text_color: String,
+    pub shadow: bool,
+}
+
+impl Default for AnnotationStyle {
+    fn default() -> Self {
+        let text = TextDefaults::default();
+        Self {
+            name: String::new(),
+            color: "#ff0000".to_string(),
+            stroke_width: 3,
+            font_family: text.font_family,
+            font_size: text.font_size,
+            text_color: text.color,
+            shadow: false,
+        }
+    }
+}
+
+impl Default for TextDefaults {
+    fn default() -> Self {
+        Self {
+            font_family: String::new(),
+            font_size: 24,
+            color: "#ff0000".to_string(),
+        }
+    }
+}
+
+/// Grid overlay and snapping used while drawing annotations.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EditorGrid {
+    pub show_grid: bool,
+    /// Spacing in image pixels, measured from the image's top-left corner.
+    pub size: u32,
+    pub snap_to_grid: bool,
+    /// Snap to the image's and other shapes' edges and centres.
+    pub snap_to_guides: bool,
+}
+
+impl Default for EditorGrid {
+    fn default() -> Self {
+        Self {
+            show_grid: false,
+            size: 16,
+            snap_to_grid: false,
+            snap_to_guides: false,
+        }
+    }
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        let home_dir = std::env::var("HOME").unwrap_or_default();
+        Self {
+            default_save_location: format!("{}/Desktop", home_dir),
+            additional_save_locations: Vec::new(),
+            mirror_location: String::new(),
+            hook_script: String::new(),
+            watch_folder: WatchFolder::default(),
+            write_sidecar: false,
+            color_profile: ColorProfileMode::Preserve,
+            metadata: MetadataMode::Keep,
+            secure_delete: false,
+            cache_limit_mb: (crate::cache::DEFAULT_MAX_BYTES / (1024 * 1024)) as u32,
+            log_level: LogLevel::Info,
+            filename_template: DEFAULT_FILENAME_TEMPLATE.to_string(),
+            output_format: OutputFormat::Png,
+            jpeg_quality: 90,
+            avif_quality: 70,
+            heic_quality: 80,
+            window_shadow: true,
+            downscale_retina: false,
+            default_capture_delay: 5,
+            selection_overlay: true,
+            capture_hotkey: "Ctrl+Shift+S".to_string(),
+            import_clipboard_hotkey: "Ctrl+Shift+V".to_string(),
+            repeat_region_hotkey: "Ctrl+Shift+R".to_string(),
+            last_region: None,
+            capture_text_hotkey: "Ctrl+Shift+T".to_string(),
+            quick_search_hotkey: "Ctrl+Shift+F".to_string(),
+            index_capture_text: true,
+            auto_copy_after_capture: true,
+            show_capture_popup: true,
+            tray_popover: false,
+            restore_focus_after_capture: true,
+            announce_actions: true,
+            auto_copy_after_edit: false,
+            region_presets: Vec::new(),
+            ffmpeg_path: String::new(),
+            record_microphone: false,
+            microphone_device: String::new(),
+            record_system_audio: false,
+            webcam_overlay: WebcamOverlay::default(),
+            click_highlight: ClickHighlight::Off,
+            recording_quality: RecordingQuality::default(),
+            recording_limits: RecordingLimits::default(),
+            animation_export: AnimationOptions::default(),
+            burst_animation: BurstOptions::default(),
+            gif_capture: GifCaptureOptions::default(),
+            editor_grid: EditorGrid::default(),
+            text_annotation: TextDefaults::default(),
+            annotation_styles: Vec::new(),
+            regression_threshold_percent: 0.1,
+            retention: RetentionPolicy::default(),
+            translation: TranslationSettings::default(),
+            stamp: StampSettings::default(),
+            beautify: BeautifyOptions::default(),
+            corners: CornerOptions::default(),
+            redaction: RedactStyle::default(),
+            auto_redact: AutoRedactSettings::default(),
+            collage: CollageLayout::default(),
+            upload: UploadSettings::default(),
+            onboarding_completed: false,
+        }
+    }
+}
+
+impl AppConfig {
+    pub fn load() -> Result<Self, SnippError> {
+        let config_path = Self::get_config_path()?;
+        tracing::debug!("config path: {}", config_path.to_string_lossy());
+
+        if config_path.exists() {
+            let contents = std::fs::read_to_string(&config_path)?;
+            let mut config = AppConfig::from_saved_json(&contents)?;
+            if config.normalize_hotkeys() {
+                config.save()?;
+            }
+            Ok(config)
+        } else {
+            let mut config = AppConfig::default();
+            config.normalize_hotkeys();
+            config.save()?;
+            Ok(config)
+        }
+    }
+
+    /// Parses a config written by an earlier run. Configs that predate
+    /// onboarding belong to people already using Snipp, so they skip it.
+    pub fn from_saved_json(contents: &str) -> Result<Self, serde_json::Error> {
+        let raw: serde_json::Value = serde_json::from_str(contents)?;
+        let predates_onboarding = raw.get("onboarding_completed").is_none();
+        let mut config: AppConfig = serde_json::from_value(raw)?;
+        if predates_onboarding {
+            config.onboarding_completed = true;
+        }
+        Ok(config)
+    }
+
+    pub fn save(&self) -> Result<(), SnippError> {
+        let config_path = Self::get_config_path()?;
+
+        if let Some(parent) = config_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let contents = serde_json::to_string_pretty(self)?;
+        std::fs::write(&config_path, contents)?;
+        Ok(())
+    }
+
+    pub fn get_config_path() -> Result<PathBuf, SnippError> {
+        let config_dir = dirs::config_dir()
+            .ok_or("Failed to get config directory")?
+            .join("snipp");
+
+        Ok(config_dir.join("config.json"))
+    }
+
+    /// The quality setting for `output_format`; lossless formats ignore it.
+    pub fn output_quality(&self) -> u8 {
+        match self.output_format {
+            OutputFormat::Avif => self.avif_quality,
+            OutputFormat::Heic => self.heic_quality,
+            _ => self.jpeg_quality,
+        }
+    }
+
+    /// Every save folder, primary first, without blanks or repeats.
+    pub fn save_locations(&self) -> Vec<String> {
+        let mut locations: Vec<String> = Vec::new();
+        for location in std::iter::once(&self.default_save_location)
+            .chain(self.additional_save_locations.iter())
+        {
+            let location = location.trim();
+            if !location.is_empty() && !locations.iter().any(|l| l == location) {
+                locations.push(location.to_string());
+            }
+        }
+        locations
+    }
+
+    fn normalize_hotkeys(&mut self) -> bool {
+        let normalized_capture = normalize_hotkey(&self.capture_hotkey);
+        let normalized_import = normalize_hotkey(&self.import_clipboard_hotkey);
+        let normalized_repeat = normalize_hotkey(&self.repeat_region_hotkey);
+        let normalized_text = normalize_hotkey(&self.capture_text_hotkey);
+        let normalized_search = normalize_hotkey(&self.quick_search_hotkey);
+        let changed = normalized_capture != self.capture_hotkey
+            || normalized_import != self.import_clipboard_hotkey
+            || normalized_repeat != self.repeat_region_hotkey
+            || normalized_text != self.capture_text_hotkey
+            || normalized_search != self.quick_search_hotkey;
+
+        self.capture_hotkey = normalized_capture;
+        self.import_clipboard_hotkey = normalized_import;
+        self.repeat_region_hotkey = normalized_repeat;
+        self.capture_text_hotkey = normalized_text;
+        self.quick_search_hotkey = normalized_search;
+
+        changed
+    }
+}
+
+fn normalize_hotkey(raw_hotkey: &str) -> String {
+    let mut modifiers: Vec<String> = Vec::new();
+    let mut key_parts: Vec<String> = Vec::new();
+
+    for part in raw_hotkey
+        .split(|c| c == '+' || c == ' ')
+        .filter(|part| !part.is_empty())
+    {
+        let normalized = normalize_hotkey_part(part);
+        if is_modifier(&normalized) {
+            if !modifiers.contains(&normalized) {
+                modifiers.push(normalized);
+            }
+        } else {
+            key_parts.push(normalized);
+        }
+    }
+
+    let mut ordered_modifiers: Vec<String> = Vec::new();
+    for modifier in ["CommandOrControl", "Ctrl", "Alt", "Shift", "Super"] {
+        if modifiers.iter().any(|item| item == modifier) {
+            ordered_modifiers.push(modifier.to_string());
+        }
+    }
+
+    let mut parts: Vec<String> = Vec::new();
+    parts.extend(ordered_modifiers);
+    parts.extend(key_parts);
+
+    parts.join("+")
+}
+
+fn normalize_hotkey_part(part: &str) -> String {
+    let lower = part.to_ascii_lowercase();
+    match lower.as_str() {
+        "cmd" | "command" | "meta" | "super" => "Super".to_string(),
+        "commandorcontrol" | "commandorctrl" => "CommandOrControl".to_string(),
+        "ctrl" | "control" => "Ctrl".to_string(),
+        "alt" | "option" => "Alt".to_string(),
+        "shift" => "Shift".to_string(),
+        "esc" | "escape" => "Escape".to_string(),
+        "return" | "enter" => "Enter".to_string(),
+        "space" => "Space".to_string(),
+        "tab" => "Tab".to_string(),
+        "comma" => "Comma".to_string(),
+        "period" | "dot" => "Period".to_string(),
+        _ => normalize_key_name(part),
+    }
+}
+
+fn normalize_key_name(part: &str) -> String {
+    let trimmed = part.trim();
+    if trimmed.len() == 1 {
+        return trimmed.to_ascii_uppercase();
+    }
+
+    let lower = trimmed.to_ascii_lowercase();
+    if let Some(stripped) = lower.strip_prefix('f') {
+        if !stripped.is_empty() && stripped.chars().all(|c| c.is_ascii_digit()) {
+            return format!("F{}", stripped);
+        }
+    }
+
+    let mut chars = lower.chars();
+    match chars.next() {
+        Some(first) => {
+            let mut normalized = String::new();
+            normalized.push(first.to_ascii_uppercase());
+            normalized.extend(chars);
+            normalized
+        }
+        None => String::new(),
+    }
+}
+
+fn is_modifier(value: &str) -> bool {
+    matches!(value, "CommandOrControl" | "Super" | "Shift" | "Alt" | "Ctrl")
+}
+
+pub struct ConfigManager {
+    config: AppConfig,
+}
+
+impl ConfigManager {
+    pub fn new() -> Result<Self, SnippError> {
+        let config = AppConfig::load()?;
+        Ok(Self { config })
+    }
+
+    pub fn get_config(&self) -> &AppConfig {
+        &self.config
+    }
+
+    pub fn update_config(
+        &mut self,
+        new_config: AppConfig,
+    ) -> Result<(), SnippError> {
+        let mut normalized_config = new_config;
+        normalized_config.normalize_hotkeys();
+        normalized_config.last_region = self.config.last_region;
+        self.config = normalized_config;
+        self.config.save()?;
+        Ok(())
+    }
+
+    pub fn set_last_region(&mut self, region: CaptureRegion) -> Result<(), SnippError> {
+        if self.config.last_region == Some(region) {
+            return Ok(());
+        }
+        self.config.last_region = Some(region);
+        self.config.save()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::{WebcamPosition, WebcamShape};
+
+    #[test]
+    fn test_app_config_default() {
+        let original_home = std::env::var("HOME").ok();
+        std::env::set_var("HOME", "/test/home");
+
+        let config = AppConfig::default();
+
+        assert_eq!(config.default_save_location, "/test/home/Desktop");
+        assert_eq!(config.capture_hotkey, "Ctrl+Shift+S");
+        assert_eq!(config.import_clipboard_hotkey, "Ctrl+Shift+V");
+        assert_eq!(config.auto_copy_after_capture, true);
+        assert_eq!(config.auto_copy_after_edit, false);
+
+        match original_home {
+            Some(home) => std::env::set_var("HOME", home),
+            None => std::env::remove_var("HOME"),
+        }
+    }
+
+    #[test]
+    fn test_app_config_serialization() {
+        let config = AppConfig {
+            default_save_location: "/test/path".to_string(),
+            capture_hotkey: "Ctrl+S".to_string(),
+            import_clipboard_hotkey: "Ctrl+V".to_string(),
+            auto_copy_after_capture: true,
+            auto_copy_after_edit: true,
+            ..AppConfig::default()
+        };
+
+        let json = serde_json::to_string(&config).expect("Failed to serialize");
+        let deserialized: AppConfig = serde_json::from_str(&json).expect("Failed to deserialize");
+
+        assert_eq!(
+            config.default_save_location,
+            deserialized.default_save_location
+        );
+        assert_eq!(config.capture_hotkey, deserialized.capture_hotkey);
+        assert_eq!(
+            config.import_clipboard_hotkey,
+            deserialized.import_clipboard_hotkey
+        );
+        assert_eq!(
+            config.auto_copy_after_capture,
+            deserialized.auto_copy_after_capture
+        );
+        assert_eq!(
+            config.auto_copy_after_edit,
+            deserialized.auto_copy_after_edit
+        );
+    }
+
+    #[test]
+    fn test_config_manager_creation_with_default() {
+        let config = AppConfig::default();
+        let manager = ConfigManager {
+            config: config.clone(),
+        };
+
+        let retrieved_config = manager.get_config();
+        assert_eq!(
+            retrieved_config.default_save_location,
+            config.default_save_location
+        );
+        assert_eq!(retrieved_config.capture_hotkey, config.capture_hotkey);
+    }
+
+    #[test]
+    fn test_config_manager_update_in_memory() {
+        let initial_config = AppConfig::default();
+        let mut manager = ConfigManager {
+            config: initial_config,
+        };
+
+        let new_config = AppConfig {
+            default_save_location: "/new/path".to_string(),
+            capture_hotkey: "Alt+S".to_string(),
+            import_clipboard_hotkey: String::new(),
+            auto_copy_after_capture: false,
+            auto_copy_after_edit: false,
+            ..AppConfig::default()
+        };
+
+        manager.config = new_config.clone();
+
+        let updated_config = manager.get_config();
+        assert_eq!(
+            updated_config.default_save_location,
+            new_config.default_save_location
+        );
+        assert_eq!(updated_config.capture_hotkey, new_config.capture_hotkey);
+    }
+
+    #[test]
+    fn test_config_without_new_fields_uses_defaults() {
+        let json = r#"{
+            "default_save_location": "/old/path",
+            "capture_hotkey": "Ctrl+Shift+S",
+            "auto_copy_after_capture": false,
+            "auto_copy_after_edit": true
+        }"#;
+
+        let config: AppConfig = serde_json::from_str(json).expect("Failed to deserialize");
+
+        assert_eq!(config.default_save_location, "/old/path");
+        assert_eq!(config.filename_template, DEFAULT_FILENAME_TEMPLATE);
+        assert_eq!(config.output_format, OutputFormat::Png);
+        assert_eq!(config.jpeg_quality, 90);
+        assert_eq!(config.avif_quality, 70);
+        assert_eq!(config.heic_quality, 80);
+        assert!(!config.write_sidecar);
+        assert!(config.mirror_location.is_empty());
+        assert!(config.hook_script.is_empty());
+        assert!(!config.watch_folder.enabled);
+        assert!(config.window_shadow);
+        assert!(!config.downscale_retina);
+        assert_eq!(config.default_capture_delay, 5);
+        assert!(config.selection_overlay);
+        assert!(config.last_region.is_none());
+        assert!(!config.tray_popover);
+        assert!(config.watch_folder.folder.is_empty());
+        assert!(config.show_capture_popup);
+        assert!(config.restore_focus_after_capture);
+        assert!(config.announce_actions);
+        assert_eq!(config.color_profile, ColorProfileMode::Preserve);
+        assert_eq!(config.metadata, MetadataMode::Keep);
+        assert!(!config.secure_delete);
+        assert_eq!(config.cache_limit_mb, 512);
+        assert_eq!(config.log_level, LogLevel::Info);
+        assert_eq!(config.import_clipboard_hotkey, "Ctrl+Shift+V");
+        assert_eq!(config.repeat_region_hotkey, "Ctrl+Shift+R");
+        assert_eq!(config.capture_text_hotkey, "Ctrl+Shift+T");
+        assert_eq!(config.quick_search_hotkey, "Ctrl+Shift+F");
+        assert!(config.index_capture_text);
+        assert!(config.region_presets.is_empty());
+        assert!(!config.record_microphone);
+        assert!(!config.webcam_overlay.enabled);
+        assert_eq!(config.click_highlight, ClickHighlight::Off);
+        assert_eq!(config.recording_quality, RecordingQuality::default());
+        assert_eq!(config.recording_limits, RecordingLimits::default());
+        assert_eq!(config.animation_export, AnimationOptions::default());
+        assert_eq!(config.burst_animation, BurstOptions::default());
+        assert_eq!(config.gif_capture, GifCaptureOptions::default());
+        assert_eq!(config.editor_grid, EditorGrid::default());
+        assert_eq!(config.text_annotation, TextDefaults::default());
+        assert!(config.annotation_styles.is_empty());
+        assert_eq!(config.regression_threshold_percent, 0.1);
+        assert_eq!(config.retention, RetentionPolicy::default());
+        assert_eq!(config.translation, TranslationSettings::default());
+        assert!(!config.stamp.enabled);
+        assert_eq!(config.beautify, BeautifyOptions::default());
+        assert_eq!(config.corners, CornerOptions::default());
+        assert_eq!(config.redaction, RedactStyle::default());
+        assert_eq!(config.auto_redact, AutoRedactSettings::default());
+        assert_eq!(config.collage, CollageLayout::default());
+        assert_eq!(config.upload, UploadSettings::default());
+        assert!(!config.upload.shortener.enabled);
+        assert_eq!(config.upload.max_concurrent, 2);
+        assert!(!config.upload.auto_format);
+        assert!(config.additional_save_locations.is_empty());
+        assert!(!config.retention.enabled);
+        assert!(!config.auto_copy_after_capture);
+        assert!(config.auto_copy_after_edit);
+    }
+
+    #[test]
+    fn test_partial_webcam_overlay_fills_defaults() {
+        let json = r#"{ "webcam_overlay": { "enabled": true, "position": "top_left" } }"#;
+
+        let config: AppConfig = serde_json::from_str(json).expect("Failed to deserialize");
+
+        assert!(config.webcam_overlay.enabled);
+        assert_eq!(config.webcam_overlay.position, WebcamPosition::TopLeft);
+        assert_eq!(config.webcam_overlay.shape, WebcamShape::Circle);
+        assert_eq!(config.webcam_overlay.size, 200);
+    }
+
+    #[test]
+    fn test_partial_annotation_style_fills_defaults() {
+        let json = r##"{ "annotation_styles": [{ "name": "Review", "color": "#00aaff", "shadow": true }] }"##;
+
+        let config: AppConfig = serde_json::from_str(json).expect("Failed to deserialize");
+
+        let style = &config.annotation_styles[0];
+        assert_eq!(style.name, "Review");
+        assert_eq!(style.color, "#00aaff");
+        assert!(style.shadow);
+        assert_eq!(style.stroke_width, 3);
+        assert_eq!(style.font_size, 24);
+    }
+
+    #[test]
+    fn test_empty_hotkey_stays_empty() {
+        assert_eq!(normalize_hotkey(""), "");
+    }
+
+    #[test]
+    fn test_normalize_hotkey_formats() {
+        assert_eq!(normalize_hotkey("Cmd+Shift+2"), "Shift+Super+2");
+        assert_eq!(normalize_hotkey("command+shift+s"), "Shift+Super+S");
+        assert_eq!(
+            normalize_hotkey("Ctrl+Alt+Shift+Cmd+S"),
+            "Ctrl+Alt+Shift+Super+S"
+        );
+        assert_eq!(normalize_hotkey("ctrl alt p"), "Ctrl+Alt+P");
+        assert_eq!(
+            normalize_hotkey("CommandOrControl+Comma"),
+            "CommandOrControl+Comma"
+        );
+    }
+
+    #[test]
+    fn test_save_locations_puts_primary_first_without_repeats() {
+        let config = AppConfig {
+            default_save_location: "/Users/me/Desktop".to_string(),
+            additional_save_locations: vec![
+                "/Volumes/Team/Shots".to_string(),
+                " ".to_string(),
+                "/Users/me/Desktop".to_string(),
+            ],
+            ..AppConfig::default()
+        };
+
+        assert_eq!(
+            config.save_locations(),
+            vec!["/Users/me/Desktop".to_string(), "/Volumes/Team/Shots".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_saved_config_without_onboarding_flag_skips_onboarding() {
+        let existing = AppConfig::from_saved_json(r#"{ "default_save_location": "/old/path" }"#).unwrap();
+        let fresh = AppConfig::from_saved_json(r#"{ "onboarding_completed": false }"#).unwrap();
+
+        assert!(existing.onboarding_completed);
+        assert!(!fresh.onboarding_completed);
+        assert!(!AppConfig::default().onboarding_completed);
+    }
+}
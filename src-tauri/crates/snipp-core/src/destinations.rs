@@ -0,0 +1,122 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::cloud_sync::{self, SyncStatus};
+use crate::error::SnippError;
+
+/// Outcome of writing a capture into one save folder.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DestinationResult {
+    pub folder: String,
+    /// Where the file ended up; `None` if this folder failed.
+    pub file_path: Option<String>,
+    pub error: Option<String>,
+    #[serde(default)]
+    pub sync_status: Option<SyncStatus>,
+    /// `error` with its kind, for the save's own error; not kept in history.
+    #[serde(skip)]
+    pub failure: Option<SnippError>,
+}
+
+/// Writes `data` as `filename` into every folder, numbering it `-1`, `-2`,
+/// ... where the name is taken. A folder that fails is recorded and skipped
+/// so it doesn't stop the others.
+pub fn write_to_all(folders: &[String], filename: &str, data: &[u8]) -> Vec<DestinationResult> {
+    folders
+        .iter()
+        .map(|folder| {
+            let written = std::fs::create_dir_all(folder)
+                .map_err(|e| {
+                    let message = format!("Failed to create save directory: {}", e);
+                    SnippError::from(std::io::Error::new(e.kind(), message))
+                })
+                .and_then(|_| cloud_sync::write_new_file(&PathBuf::from(folder).join(filename), data))
+                .map(|(file_path, status)| (file_path.to_string_lossy().to_string(), status));
+            match written {
+                Ok((file_path, status)) => DestinationResult {
+                    folder: folder.clone(),
+                    file_path: Some(file_path),
+                    error: None,
+                    sync_status: Some(status),
+                    failure: None,
+                },
+                Err(error) => {
+                    tracing::warn!("Saving to {} failed: {}", folder, error);
+                    DestinationResult {
+                        folder: folder.clone(),
+                        file_path: None,
+                        error: Some(error.to_string()),
+                        sync_status: None,
+                        failure: Some(error),
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
+/// The first folder that worked, which is what history and the UI show as
+/// the capture's path. Errs with every failure when none worked, coded like
+/// the first one so a full disk still reads as `disk_full`.
+pub fn primary_path(results: &[DestinationResult]) -> Result<String, SnippError> {
+    results
+        .iter()
+        .find_map(|result| result.file_path.clone())
+        .ok_or_else(|| {
+            let errors: Vec<String> = results
+                .iter()
+                .filter_map(|result| result.error.as_ref().map(|e| format!("{}: {}", result.folder, e)))
+                .collect();
+            if errors.is_empty() {
+                return SnippError::Other("No save location is configured".to_string());
+            }
+            let message = errors.join("; ");
+            match results.iter().find_map(|result| result.failure.as_ref()) {
+                Some(failure) => failure.with_message(message),
+                None => SnippError::Other(message),
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_write_to_all_continues_past_failures() {
+        let dir = TempDir::new().unwrap();
+        // A regular file can't be used as a folder, so this destination fails.
+        let blocked = dir.path().join("blocked");
+        std::fs::write(&blocked, "").unwrap();
+        let folders = vec![
+            blocked.to_string_lossy().to_string(),
+            dir.path().join("a").to_string_lossy().to_string(),
+            dir.path().join("b").to_string_lossy().to_string(),
+        ];
+
+        let results = write_to_all(&folders, "Snipp test.png", b"png");
+
+        assert!(results[0].error.is_some());
+        assert!(results[1].error.is_none() && results[2].error.is_none());
+        assert!(dir.path().join("b/Snipp test.png").exists());
+        assert_eq!(primary_path(&results).unwrap(), results[1].file_path.clone().unwrap());
+    }
+
+    #[test]
+    fn test_primary_path_reports_all_failures() {
+        let results = vec![DestinationResult {
+            folder: "/x".to_string(),
+            file_path: None,
+            error: Some("denied".to_string()),
+            sync_status: None,
+            failure: Some(SnippError::DiskFull("denied".to_string())),
+        }];
+
+        assert_eq!(
+            primary_path(&results).unwrap_err(),
+            SnippError::DiskFull("/x: denied".to_string())
+        );
+        assert!(primary_path(&[]).is_err());
+    }
+}
@@ -0,0 +1,154 @@
+use serde::Serialize;
+
+use crate::color;
+use crate::region::CaptureRegion;
+
+/// A connected screen. `id` is the display number `screencapture -D`
+/// takes, starting at 1 for the main display.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DisplayInfo {
+    pub id: u32,
+    pub name: String,
+    /// Resolution in pixels.
+    pub width: u32,
+    pub height: u32,
+    pub scale_factor: f64,
+    /// Frame in global screen points, origin at the top-left of the main display.
+    pub bounds: CaptureRegion,
+    pub primary: bool,
+}
+
+/// Lays out a PNG capture of each display the way the screens are
+/// arranged, at the highest scale factor among them so no screen loses
+/// detail. Space not covered by any screen is left transparent.
+pub fn stitch(captures: &[(DisplayInfo, Vec<u8>)]) -> Result<Vec<u8>, String> {
+    if captures.is_empty() {
+        return Err("No displays were captured".to_string());
+    }
+    let scale = captures
+        .iter()
+        .map(|(display, _)| display.scale_factor)
+        .fold(1.0, f64::max);
+    let left = captures.iter().map(|(display, _)| display.bounds.x).min().unwrap_or(0);
+    let top = captures.iter().map(|(display, _)| display.bounds.y).min().unwrap_or(0);
+    let right = captures
+        .iter()
+        .map(|(display, _)| display.bounds.x + display.bounds.width as i32)
+        .max()
+        .unwrap_or(0);
+    let bottom = captures
+        .iter()
+        .map(|(display, _)| display.bounds.y + display.bounds.height as i32)
+        .max()
+        .unwrap_or(0);
+    let to_pixels = |points: i32| (points as f64 * scale).round() as u32;
+
+    let mut canvas = image::RgbaImage::new(to_pixels(right - left), to_pixels(bottom - top));
+    for (display, png) in captures {
+        let screen = image::load_from_memory(png)
+            .map_err(|e| format!("Failed to decode capture of {}: {}", display.name, e))?;
+        let width = to_pixels(display.bounds.width as i32);
+        let height = to_pixels(display.bounds.height as i32);
+        let screen = if (screen.width(), screen.height()) == (width, height) {
+            screen.to_rgba8()
+        } else {
+            screen.resize_exact(width, height, image::imageops::FilterType::Lanczos3).to_rgba8()
+        };
+        image::imageops::replace(
+            &mut canvas,
+            &screen,
+            to_pixels(display.bounds.x - left) as i64,
+            to_pixels(display.bounds.y - top) as i64,
+        );
+    }
+
+    let mut png_bytes = Vec::new();
+    canvas
+        .write_to(&mut std::io::Cursor::new(&mut png_bytes), image::ImageOutputFormat::Png)
+        .map_err(|e| format!("Failed to encode stitched capture: {}", e))?;
+    Ok(png_bytes)
+}
+
+/// The display under the middle of `region`, given in global points.
+pub fn display_at(displays: &[DisplayInfo], region: CaptureRegion) -> Option<&DisplayInfo> {
+    let x = region.x + region.width as i32 / 2;
+    let y = region.y + region.height as i32 / 2;
+    displays.iter().find(|display| {
+        let bounds = display.bounds;
+        (bounds.x..bounds.x + bounds.width as i32).contains(&x)
+            && (bounds.y..bounds.y + bounds.height as i32).contains(&y)
+    })
+}
+
+/// Resizes a capture taken at `scale_factor` pixels per point down to its
+/// size in points, keeping its color profile. 1x captures are returned as is.
+pub fn downscale_to_points(png: Vec<u8>, scale_factor: f64) -> Result<Vec<u8>, String> {
+    if !scale_factor.is_finite() || scale_factor <= 1.0 {
+        return Ok(png);
+    }
+    let image = image::load_from_memory(&png).map_err(|e| format!("Failed to decode capture: {}", e))?;
+    let width = ((image.width() as f64 / scale_factor).round() as u32).max(1);
+    let height = ((image.height() as f64 / scale_factor).round() as u32).max(1);
+    let resized = image.resize_exact(width, height, image::imageops::FilterType::Triangle);
+
+    let mut resized_png = Vec::new();
+    resized
+        .write_to(&mut std::io::Cursor::new(&mut resized_png), image::ImageOutputFormat::Png)
+        .map_err(|e| format!("Failed to encode capture: {}", e))?;
+    let icc = color::icc_profile(&png);
+    color::apply_profile(resized_png, icc.as_deref(), color::ColorProfileMode::Preserve)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn screen(id: u32, bounds: CaptureRegion, scale_factor: f64, color: [u8; 4]) -> (DisplayInfo, Vec<u8>) {
+        let width = (bounds.width as f64 * scale_factor) as u32;
+        let height = (bounds.height as f64 * scale_factor) as u32;
+        let mut png = Vec::new();
+        image::DynamicImage::ImageRgba8(image::RgbaImage::from_pixel(width, height, image::Rgba(color)))
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageOutputFormat::Png)
+            .unwrap();
+        let display = DisplayInfo {
+            id,
+            name: format!("Display {}", id),
+            width,
+            height,
+            scale_factor,
+            bounds,
+            primary: id == 1,
+        };
+        (display, png)
+    }
+
+    #[test]
+    fn test_stitch_follows_arrangement() {
+        // A Retina laptop with a 1x monitor to its left, lower down.
+        let captures = vec![
+            screen(1, CaptureRegion { x: 0, y: 0, width: 40, height: 30 }, 2.0, [255, 0, 0, 255]),
+            screen(2, CaptureRegion { x: -50, y: 10, width: 50, height: 20 }, 1.0, [0, 0, 255, 255]),
+        ];
+
+        let stitched = image::load_from_memory(&stitch(&captures).unwrap()).unwrap().to_rgba8();
+
+        assert_eq!(stitched.dimensions(), (180, 60));
+        assert_eq!(stitched.get_pixel(100, 0).0, [255, 0, 0, 255]);
+        assert_eq!(stitched.get_pixel(0, 20).0, [0, 0, 255, 255]);
+        assert_eq!(stitched.get_pixel(0, 0).0[3], 0);
+        assert!(stitch(&[]).is_err());
+    }
+
+    #[test]
+    fn test_downscale_to_points() {
+        let (display, png) = screen(1, CaptureRegion { x: 0, y: 0, width: 40, height: 30 }, 2.0, [0, 255, 0, 255]);
+        let downscaled = image::load_from_memory(&downscale_to_points(png.clone(), 2.0).unwrap()).unwrap();
+        assert_eq!((downscaled.width(), downscaled.height()), (40, 30));
+        assert_eq!(downscale_to_points(png.clone(), 1.0).unwrap(), png);
+
+        let displays = [display];
+        let inside = CaptureRegion { x: 30, y: 20, width: 4, height: 4 };
+        assert_eq!(display_at(&displays, inside).map(|display| display.id), Some(1));
+        assert!(display_at(&displays, CaptureRegion { x: 60, ..inside }).is_none());
+    }
+}
@@ -0,0 +1,156 @@
+use serde::Serialize;
+
+/// Why a command failed, sent to the frontend as `{ code, message }` so it
+/// can tell a dismissed selection from a missing permission or a full disk.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error, Serialize)]
+#[serde(tag = "code", content = "message", rename_all = "snake_case")]
+pub enum SnippError {
+    /// The user dismissed a selection, picker or dialog.
+    #[error("{0}")]
+    Cancelled(String),
+    /// macOS is withholding a permission such as Screen Recording.
+    #[error("{0}")]
+    PermissionDenied(String),
+    /// A file, capture or history entry that isn't there (any more).
+    #[error("{0}")]
+    NotFound(String),
+    #[error("{0}")]
+    DiskFull(String),
+    /// Any other file system failure.
+    #[error("{0}")]
+    Io(String),
+    /// A settings, history or sidecar file that isn't valid JSON.
+    #[error("{0}")]
+    InvalidData(String),
+    #[error("{0}")]
+    Other(String),
+}
+
+impl SnippError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            SnippError::Cancelled(_) => "cancelled",
+            SnippError::PermissionDenied(_) => "permission_denied",
+            SnippError::NotFound(_) => "not_found",
+            SnippError::DiskFull(_) => "disk_full",
+            SnippError::Io(_) => "io",
+            SnippError::InvalidData(_) => "invalid_data",
+            SnippError::Other(_) => "other",
+        }
+    }
+
+    /// The same kind of error with a different message, for callers that
+    /// add context to a failure without losing its code.
+    pub fn with_message(&self, message: String) -> Self {
+        match self {
+            SnippError::Cancelled(_) => SnippError::Cancelled(message),
+            SnippError::PermissionDenied(_) => SnippError::PermissionDenied(message),
+            SnippError::NotFound(_) => SnippError::NotFound(message),
+            SnippError::DiskFull(_) => SnippError::DiskFull(message),
+            SnippError::Io(_) => SnippError::Io(message),
+            SnippError::InvalidData(_) => SnippError::InvalidData(message),
+            SnippError::Other(_) => SnippError::Other(message),
+        }
+    }
+
+    /// A pending capture that has been saved, closed or deleted since.
+    pub fn not_cached() -> Self {
+        SnippError::NotFound("Screenshot data not found in memory cache".to_string())
+    }
+}
+
+impl From<std::io::Error> for SnippError {
+    fn from(e: std::io::Error) -> Self {
+        let message = e.to_string();
+        match e.kind() {
+            std::io::ErrorKind::NotFound => SnippError::NotFound(message),
+            std::io::ErrorKind::PermissionDenied => SnippError::PermissionDenied(message),
+            std::io::ErrorKind::StorageFull => SnippError::DiskFull(message),
+            _ => SnippError::Io(message),
+        }
+    }
+}
+
+impl From<crate::capture::CaptureError> for SnippError {
+    fn from(e: crate::capture::CaptureError) -> Self {
+        use crate::capture::CaptureError;
+        let message = e.to_string();
+        match e {
+            CaptureError::Cancelled => SnippError::Cancelled(message),
+            CaptureError::PermissionDenied => SnippError::PermissionDenied(message),
+            CaptureError::NotFound(_) => SnippError::NotFound(message),
+            CaptureError::Failed(_) => SnippError::Other(message),
+        }
+    }
+}
+
+impl From<serde_json::Error> for SnippError {
+    fn from(e: serde_json::Error) -> Self {
+        SnippError::InvalidData(e.to_string())
+    }
+}
+
+impl From<image::ImageError> for SnippError {
+    fn from(e: image::ImageError) -> Self {
+        match e {
+            image::ImageError::IoError(e) => e.into(),
+            e => SnippError::Other(e.to_string()),
+        }
+    }
+}
+
+/// Helpers still report failures as plain messages; those have no more
+/// specific code.
+impl From<String> for SnippError {
+    fn from(message: String) -> Self {
+        SnippError::Other(message)
+    }
+}
+
+impl From<&str> for SnippError {
+    fn from(message: &str) -> Self {
+        SnippError::Other(message.to_string())
+    }
+}
+
+impl From<SnippError> for String {
+    fn from(e: SnippError) -> Self {
+        e.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serializes_with_code() {
+        let error = SnippError::Cancelled("Screenshot capture was cancelled".to_string());
+
+        assert_eq!(
+            serde_json::to_value(&error).unwrap(),
+            serde_json::json!({ "code": "cancelled", "message": "Screenshot capture was cancelled" })
+        );
+        assert_eq!(error.to_string(), "Screenshot capture was cancelled");
+    }
+
+    #[test]
+    fn test_io_errors_keep_their_kind() {
+        let missing = std::io::Error::new(std::io::ErrorKind::NotFound, "gone");
+        let full = std::io::Error::new(std::io::ErrorKind::StorageFull, "full");
+
+        assert_eq!(SnippError::from(missing).code(), "not_found");
+        assert_eq!(SnippError::from(full), SnippError::DiskFull("full".to_string()));
+        assert_eq!(SnippError::from("oops").code(), "other");
+    }
+
+    #[test]
+    fn test_with_message_keeps_the_code() {
+        let full = SnippError::DiskFull("full".to_string());
+
+        assert_eq!(
+            full.with_message("/Shots: full".to_string()),
+            SnippError::DiskFull("/Shots: full".to_string())
+        );
+    }
+}
@@ -0,0 +1,285 @@
+use image::{Rgba, RgbaImage};
+use serde::{Deserialize, Serialize};
+
+/// How long each click stays highlighted.
+pub const HIGHLIGHT_MS: u64 = 500;
+
+/// Sizes a ripple grows through. Videos overlay one sprite per step, looped
+/// from inputs `1..=RIPPLE_STEPS`.
+pub const RIPPLE_STEPS: usize = 5;
+
+/// Radius of the widest ripple, in points.
+const RIPPLE_RADIUS: f64 = 28.0;
+const RIPPLE_WIDTH: f64 = 3.0;
+const RIPPLE_COLOR: [u8; 3] = [255, 196, 0];
+
+/// Radius left at full brightness around a spotlighted click, in points;
+/// the dimming reaches full strength at twice this.
+const SPOTLIGHT_RADIUS: f64 = 60.0;
+const SPOTLIGHT_DIM: f64 = 0.5;
+
+/// How clicks are drawn into recordings and GIF captures.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClickHighlight {
+    #[default]
+    Off,
+    /// A ring that grows and fades around each click.
+    Ripple,
+    /// Dims everything but the area around each click.
+    Spotlight,
+}
+
+/// A press of the primary button, `at_ms` after the capture started.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Click {
+    pub at_ms: u64,
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Click {
+    /// Moves a click from desktop points into the pixels of an output that
+    /// shows the area at `origin`, `scale` pixels to the point.
+    pub fn to_output(self, origin: (f64, f64), scale: f64) -> Click {
+        Click {
+            at_ms: self.at_ms,
+            x: (self.x - origin.0) * scale,
+            y: (self.y - origin.1) * scale,
+        }
+    }
+
+    fn showing_at(&self, at_ms: u64) -> Option<u64> {
+        at_ms.checked_sub(self.at_ms).filter(|elapsed| *elapsed < HIGHLIGHT_MS)
+    }
+}
+
+fn ripple_step(elapsed_ms: u64) -> usize {
+    (elapsed_ms as usize * RIPPLE_STEPS / HIGHLIGHT_MS as usize).min(RIPPLE_STEPS - 1)
+}
+
+/// Seconds into the capture during which `step` of the click's ripple shows.
+fn step_window(click: &Click, step: usize) -> (f64, f64) {
+    let length = HIGHLIGHT_MS as f64 / RIPPLE_STEPS as f64;
+    let start = click.at_ms as f64 + length * step as f64;
+    (start / 1000.0, (start + length) / 1000.0)
+}
+
+/// Straight-alpha "over", so sprites keep the ring colour at any opacity.
+fn blend(pixel: &mut Rgba<u8>, color: [u8; 3], alpha: f64) {
+    let below = pixel.0[3] as f64 / 255.0 * (1.0 - alpha);
+    let total = alpha + below;
+    if total <= 0.0 {
+        return;
+    }
+    for (channel, value) in pixel.0.iter_mut().zip(color) {
+        *channel = ((value as f64 * alpha + *channel as f64 * below) / total).round() as u8;
+    }
+    pixel.0[3] = (total * 255.0).round() as u8;
+}
+
+fn draw_ripple(image: &mut RgbaImage, center: (f64, f64), step: usize, scale: f64) {
+    let radius = RIPPLE_RADIUS * scale * (step + 1) as f64 / RIPPLE_STEPS as f64;
+    let half_width = RIPPLE_WIDTH * scale / 2.0;
+    let opacity = 1.0 - step as f64 / RIPPLE_STEPS as f64;
+    let reach = radius + half_width + 1.0;
+    let (width, height) = image.dimensions();
+
+    let left = (center.0 - reach).floor().max(0.0) as u32;
+    let top = (center.1 - reach).floor().max(0.0) as u32;
+    let right = ((center.0 + reach).ceil().max(0.0) as u32).min(width);
+    let bottom = ((center.1 + reach).ceil().max(0.0) as u32).min(height);
+    for y in top..bottom {
+        for x in left..right {
+            let distance = (x as f64 + 0.5 - center.0).hypot(y as f64 + 0.5 - center.1);
+            let coverage = (half_width + 0.5 - (distance - radius).abs()).clamp(0.0, 1.0);
+            if coverage > 0.0 {
+                blend(image.get_pixel_mut(x, y), RIPPLE_COLOR, opacity * coverage);
+            }
+        }
+    }
+}
+
+fn draw_spotlight(image: &mut RgbaImage, center: (f64, f64), scale: f64) {
+    let radius = SPOTLIGHT_RADIUS * scale;
+    for (x, y, pixel) in image.enumerate_pixels_mut() {
+        let distance = (x as f64 + 0.5 - center.0).hypot(y as f64 + 0.5 - center.1);
+        let factor = 1.0 - SPOTLIGHT_DIM * ((distance - radius) / radius).clamp(0.0, 1.0);
+        for channel in pixel.0.iter_mut().take(3) {
+            *channel = (*channel as f64 * factor).round() as u8;
+        }
+    }
+}
+
+/// Step `step` of the ripple centred on a transparent square, `scale`
+/// pixels to the point, for overlaying on video.
+pub fn ripple_sprite(step: usize, scale: f64) -> RgbaImage {
+    let size = (((RIPPLE_RADIUS + RIPPLE_WIDTH) * scale).ceil() as u32 + 1) * 2;
+    let mut sprite = RgbaImage::new(size, size);
+    let center = size as f64 / 2.0;
+    draw_ripple(&mut sprite, (center, center), step, scale);
+    sprite
+}
+
+/// Draws every click still showing `at_ms` into the capture onto `frame`.
+/// `clicks` are in the frame's pixels, `scale` of them to the point.
+pub fn draw(frame: &mut RgbaImage, style: ClickHighlight, clicks: &[Click], at_ms: u64, scale: f64) {
+    for click in clicks {
+        let Some(elapsed) = click.showing_at(at_ms) else {
+            continue;
+        };
+        match style {
+            ClickHighlight::Off => return,
+            ClickHighlight::Ripple => draw_ripple(frame, (click.x, click.y), ripple_step(elapsed), scale),
+            ClickHighlight::Spotlight => draw_spotlight(frame, (click.x, click.y), scale),
+        }
+    }
+}
+
+/// A filter graph drawing `clicks` (in video pixels) over input 0 and ending
+/// in `[vout]`, or `None` when there is nothing to draw. Ripples overlay the
+/// looped `ripple_sprite`s; spotlights dim the planes with geq, which only
+/// runs while a click shows.
+pub fn ffmpeg_filter(style: ClickHighlight, clicks: &[Click], scale: f64) -> Option<String> {
+    if clicks.is_empty() || style == ClickHighlight::Off {
+        return None;
+    }
+
+    let mut graph = Vec::new();
+    // Each stage takes the previous video and, for ripples, a sprite copy.
+    let mut stages: Vec<(String, String)> = Vec::new();
+    match style {
+        ClickHighlight::Off => {}
+        ClickHighlight::Ripple => {
+            for step in 0..RIPPLE_STEPS {
+                let copies: String = (0..clicks.len()).map(|index| format!("[r{}_{}]", step, index)).collect();
+                graph.push(format!("[{}:v]split={}{}", step + 1, clicks.len(), copies));
+            }
+            for (index, click) in clicks.iter().enumerate() {
+                for step in 0..RIPPLE_STEPS {
+                    let (start, end) = step_window(click, step);
+                    stages.push((
+                        format!("[r{}_{}]", step, index),
+                        format!(
+                            "overlay=x={:.0}-overlay_w/2:y={:.0}-overlay_h/2:shortest=1:enable='between(t,{:.3},{:.3})'",
+                            click.x, click.y, start, end
+                        ),
+                    ));
+                }
+            }
+        }
+        ClickHighlight::Spotlight => {
+            let radius = SPOTLIGHT_RADIUS * scale;
+            for click in clicks {
+                // SW/SH map chroma plane coordinates back to luma pixels.
+                let factor = format!(
+                    "(1-{}*clip((hypot(X/SW-{:.0},Y/SH-{:.0})-{r:.0})/{r:.0},0,1))",
+                    SPOTLIGHT_DIM,
+                    click.x,
+                    click.y,
+                    r = radius
+                );
+                let start = click.at_ms as f64 / 1000.0;
+                let end = (click.at_ms + HIGHLIGHT_MS) as f64 / 1000.0;
+                stages.push((
+                    String::new(),
+                    format!(
+                        "geq=lum='p(X,Y)*{f}':cb='128+(p(X,Y)-128)*{f}':cr='128+(p(X,Y)-128)*{f}':enable='between(t,{:.3},{:.3})'",
+                        start,
+                        end,
+                        f = factor
+                    ),
+                ));
+            }
+        }
+    }
+
+    let mut previous = "[0:v]".to_string();
+    let last = stages.len() - 1;
+    for (index, (extra, filter)) in stages.into_iter().enumerate() {
+        let output = if index == last { "[vout]".to_string() } else { format!("[h{}]", index) };
+        graph.push(format!("{}{}{}{}", previous, extra, filter, output));
+        previous = output;
+    }
+    Some(graph.join(";"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn click(at_ms: u64) -> Click {
+        Click { at_ms, x: 50.0, y: 40.0 }
+    }
+
+    #[test]
+    fn test_click_to_output() {
+        let click = Click { at_ms: 7, x: 110.0, y: 70.0 }.to_output((100.0, 50.0), 2.0);
+        assert_eq!(click, Click { at_ms: 7, x: 20.0, y: 40.0 });
+    }
+
+    #[test]
+    fn test_ripple_grows_and_fades() {
+        let mut frame = RgbaImage::from_pixel(100, 80, Rgba([0, 0, 0, 255]));
+        draw(&mut frame, ClickHighlight::Ripple, &[click(1000)], 1000, 1.0);
+        // The first step is a small ring: its edge is lit, the click point isn't.
+        let radius = (RIPPLE_RADIUS / RIPPLE_STEPS as f64) as u32;
+        assert!(frame.get_pixel(50 + radius, 40)[0] > 200);
+        assert_eq!(frame.get_pixel(50, 40)[0], 0);
+
+        let mut late = RgbaImage::from_pixel(100, 80, Rgba([0, 0, 0, 255]));
+        draw(&mut late, ClickHighlight::Ripple, &[click(1000)], 1000 + HIGHLIGHT_MS - 1, 1.0);
+        let edge = late.get_pixel(50 + RIPPLE_RADIUS as u32, 40)[0];
+        assert!(edge > 0 && edge < frame.get_pixel(50 + radius, 40)[0]);
+
+        let mut over = RgbaImage::from_pixel(100, 80, Rgba([0, 0, 0, 255]));
+        draw(&mut over, ClickHighlight::Ripple, &[click(1000)], 1000 + HIGHLIGHT_MS, 1.0);
+        draw(&mut over, ClickHighlight::Ripple, &[click(1000)], 999, 1.0);
+        assert!(over.pixels().all(|pixel| pixel[0] == 0));
+    }
+
+    #[test]
+    fn test_spotlight_dims_away_from_the_click() {
+        let mut frame = RgbaImage::from_pixel(400, 80, Rgba([200, 200, 200, 255]));
+        draw(&mut frame, ClickHighlight::Spotlight, &[click(0)], 100, 1.0);
+        assert_eq!(frame.get_pixel(50, 40)[0], 200);
+        assert_eq!(frame.get_pixel(399, 40)[0], 100);
+
+        let mut off = RgbaImage::from_pixel(400, 80, Rgba([200, 200, 200, 255]));
+        draw(&mut off, ClickHighlight::Off, &[click(0)], 100, 1.0);
+        assert!(off.pixels().all(|pixel| pixel[0] == 200));
+    }
+
+    #[test]
+    fn test_ripple_sprite_is_transparent_but_for_the_ring() {
+        let sprite = ripple_sprite(RIPPLE_STEPS - 1, 2.0);
+        let center = sprite.width() / 2;
+        assert_eq!(sprite.width() % 2, 0);
+        assert_eq!(sprite.get_pixel(center, center)[3], 0);
+        let ring = sprite.get_pixel(center + (RIPPLE_RADIUS * 2.0) as u32, center);
+        assert_eq!(&ring.0[..3], &RIPPLE_COLOR);
+        assert!(ring[3] > 0);
+    }
+
+    #[test]
+    fn test_ffmpeg_filter() {
+        assert_eq!(ffmpeg_filter(ClickHighlight::Ripple, &[], 1.0), None);
+        assert_eq!(ffmpeg_filter(ClickHighlight::Off, &[click(0)], 1.0), None);
+
+        let ripple = ffmpeg_filter(ClickHighlight::Ripple, &[click(1000), click(2500)], 1.0).unwrap();
+        assert!(ripple.starts_with("[1:v]split=2[r0_0][r0_1];"));
+        assert_eq!(ripple.matches("overlay=").count(), RIPPLE_STEPS * 2);
+        assert!(ripple.contains("[0:v][r0_0]overlay=x=50-overlay_w/2:y=40-overlay_h/2:shortest=1:enable='between(t,1.000,1.100)'[h0]"));
+        assert!(ripple.contains("enable='between(t,2.900,3.000)'[vout]"));
+
+        let spotlight = ffmpeg_filter(ClickHighlight::Spotlight, &[click(1000)], 2.0).unwrap();
+        assert!(spotlight.starts_with("[0:v]geq=lum='p(X,Y)*(1-0.5*clip((hypot(X/SW-50,Y/SH-40)-120)/120,0,1))'"));
+        assert!(spotlight.ends_with(":enable='between(t,1.000,1.500)'[vout]"));
+    }
+
+    #[test]
+    fn test_click_highlight_serializes_in_snake_case() {
+        assert_eq!(serde_json::to_string(&ClickHighlight::Spotlight).unwrap(), "\"spotlight\"");
+        assert_eq!(ClickHighlight::default(), ClickHighlight::Off);
+    }
+}
@@ -0,0 +1,463 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use chrono::{DateTime, Utc};
+
+use crate::baseline::RegressionResult;
+use crate::cloud_sync::SyncStatus;
+use crate::destinations::DestinationResult;
+use crate::error::SnippError;
+use crate::mirror::MirrorStatus;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreenshotHistory {
+    pub file_path: String,
+    pub timestamp: DateTime<Utc>,
+    pub filename: String,
+    pub thumbnail_path: Option<String>,
+    /// Set when the capture was diffed against a region preset baseline.
+    #[serde(default)]
+    pub regression: Option<RegressionResult>,
+    /// Per-folder results when the capture was saved to several locations.
+    #[serde(default)]
+    pub destinations: Vec<DestinationResult>,
+    /// Cloud sync state of `file_path` when it was saved.
+    #[serde(default)]
+    pub sync_status: Option<SyncStatus>,
+    /// Free-text context added by the user, e.g. "repro of crash #123".
+    #[serde(default)]
+    pub note: String,
+    /// Text recognized in the image, so captures can be found by what they show.
+    #[serde(default)]
+    pub text: String,
+    /// Link from the last upload of this capture.
+    #[serde(default)]
+    pub share_url: Option<String>,
+    /// Copy on the network share; `None` when mirroring was off.
+    #[serde(default)]
+    pub mirror: Option<MirrorStatus>,
+    /// Labels added by hook scripts, searchable like the note.
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl ScreenshotHistory {
+    pub fn new(file_path: String) -> Self {
+        let filename = PathBuf::from(&file_path)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("unknown.png")
+            .to_string();
+
+        Self {
+            file_path,
+            timestamp: Utc::now(),
+            filename,
+            thumbnail_path: None,
+            regression: None,
+            destinations: Vec::new(),
+            sync_status: None,
+            note: String::new(),
+            text: String::new(),
+            share_url: None,
+            mirror: None,
+            tags: Vec::new(),
+        }
+    }
+
+    /// Every file this capture was written to.
+    pub fn saved_paths(&self) -> Vec<&str> {
+        let copies: Vec<&str> = self
+            .destinations
+            .iter()
+            .filter_map(|destination| destination.file_path.as_deref())
+            .collect();
+        if copies.is_empty() {
+            vec![self.file_path.as_str()]
+        } else {
+            copies
+        }
+    }
+
+    /// Whether every whitespace-separated term of `query` appears in the
+    /// filename, note, tags or recognized text, ignoring case.
+    pub fn matches(&self, query: &str) -> bool {
+        let haystack =
+            format!("{}\n{}\n{}\n{}", self.filename, self.note, self.tags.join(" "), self.text).to_lowercase();
+        query
+            .to_lowercase()
+            .split_whitespace()
+            .all(|term| haystack.contains(term))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct HistoryData {
+    pub screenshots: Vec<ScreenshotHistory>,
+}
+
+impl HistoryData {
+    pub fn load() -> Result<Self, SnippError> {
+        let history_path = Self::get_history_path()?;
+        Self::load_from_path(history_path)
+    }
+    
+    pub fn load_from_path(history_path: PathBuf) -> Result<Self, SnippError> {
+        if history_path.exists() {
+            let contents = std::fs::read_to_string(&history_path)?;
+            let history: HistoryData = serde_json::from_str(&contents)?;
+            Ok(history)
+        } else {
+            let history = HistoryData::default();
+            history.save_to_path(history_path)?;
+            Ok(history)
+        }
+    }
+    
+    pub fn save(&self) -> Result<(), SnippError> {
+        let history_path = Self::get_history_path()?;
+        self.save_to_path(history_path)
+    }
+    
+    pub fn save_to_path(&self, history_path: PathBuf) -> Result<(), SnippError> {
+        if let Some(parent) = history_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        
+        let contents = serde_json::to_string_pretty(self)?;
+        std::fs::write(&history_path, contents)?;
+        Ok(())
+    }
+    
+    pub fn add_screenshot(&mut self, file_path: String) -> Result<(), SnippError> {
+        self.add_entry(ScreenshotHistory::new(file_path))
+    }
+
+    pub fn add_entry(&mut self, screenshot: ScreenshotHistory) -> Result<(), SnippError> {
+        self.screenshots.insert(0, screenshot);
+        
+        self.screenshots.truncate(50);
+        
+        self.save()?;
+        Ok(())
+    }
+    
+    pub fn remove_screenshot(&mut self, file_path: &str) -> Result<(), SnippError> {
+        self.screenshots.retain(|screenshot| screenshot.file_path != file_path);
+        self.save()?;
+        Ok(())
+    }
+    
+    pub fn get_recent_screenshots(&self, limit: usize) -> Vec<&ScreenshotHistory> {
+        self.screenshots.iter().take(limit).collect()
+    }
+
+    /// Replaces the note on an entry in memory. Returns false if no entry
+    /// has that path.
+    pub fn update_note(&mut self, file_path: &str, note: &str) -> bool {
+        match self.screenshots.iter_mut().find(|s| s.file_path == file_path) {
+            Some(screenshot) => {
+                screenshot.note = note.trim().to_string();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn set_note(&mut self, file_path: &str, note: &str) -> Result<(), SnippError> {
+        if !self.update_note(file_path, note) {
+            return Err(SnippError::NotFound(format!("{} is not in history", file_path)));
+        }
+        self.save()?;
+        Ok(())
+    }
+
+    /// Stores the recognized text of an entry. A capture deleted before
+    /// recognition finished is not an error.
+    pub fn set_text(&mut self, file_path: &str, text: &str) -> Result<(), SnippError> {
+        match self.screenshots.iter_mut().find(|s| s.file_path == file_path) {
+            Some(screenshot) => screenshot.text = text.trim().to_string(),
+            None => return Ok(()),
+        }
+        self.save()?;
+        Ok(())
+    }
+
+    pub fn set_share_url(&mut self, file_path: &str, url: &str) -> Result<(), SnippError> {
+        let screenshot = self
+            .screenshots
+            .iter_mut()
+            .find(|s| s.file_path == file_path)
+            .ok_or_else(|| SnippError::NotFound(format!("{} is not in history", file_path)))?;
+        screenshot.share_url = Some(url.to_string());
+        self.save()?;
+        Ok(())
+    }
+
+    /// Adds tags the entry doesn't have yet.
+    pub fn add_tags(&mut self, file_path: &str, tags: &[String]) -> Result<(), SnippError> {
+        let screenshot = self
+            .screenshots
+            .iter_mut()
+            .find(|s| s.file_path == file_path)
+            .ok_or_else(|| SnippError::NotFound(format!("{} is not in history", file_path)))?;
+        for tag in tags {
+            if !screenshot.tags.contains(tag) {
+                screenshot.tags.push(tag.clone());
+            }
+        }
+        self.save()?;
+        Ok(())
+    }
+
+    /// Records where the entry's network copy stands. A capture deleted
+    /// while its copy was queued is not an error.
+    pub fn set_mirror_status(&mut self, file_path: &str, status: MirrorStatus) -> Result<(), SnippError> {
+        match self.screenshots.iter_mut().find(|s| s.file_path == file_path) {
+            Some(screenshot) => screenshot.mirror = Some(status),
+            None => return Ok(()),
+        }
+        self.save()?;
+        Ok(())
+    }
+
+    /// Newest-first entries matching `query`; an empty query matches all.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<&ScreenshotHistory> {
+        self.screenshots
+            .iter()
+            .filter(|screenshot| screenshot.matches(query))
+            .take(limit)
+            .collect()
+    }
+    
+    fn get_history_path() -> Result<PathBuf, SnippError> {
+        let config_dir = dirs::config_dir()
+            .ok_or("Failed to get config directory")?
+            .join("snipp");
+        
+        Ok(config_dir.join("history.json"))
+    }
+}
+
+pub struct HistoryManager {
+    history: HistoryData,
+}
+
+impl HistoryManager {
+    pub fn new() -> Result<Self, SnippError> {
+        let history = HistoryData::load()?;
+        Ok(Self { history })
+    }
+    
+    pub fn get_history(&self) -> &HistoryData {
+        &self.history
+    }
+    
+    pub fn add_screenshot(&mut self, file_path: String) -> Result<(), SnippError> {
+        self.history.add_screenshot(file_path)?;
+        Ok(())
+    }
+    
+    pub fn add_entry(&mut self, screenshot: ScreenshotHistory) -> Result<(), SnippError> {
+        self.history.add_entry(screenshot)?;
+        Ok(())
+    }
+
+    pub fn remove_screenshot(&mut self, file_path: &str) -> Result<(), SnippError> {
+        self.history.remove_screenshot(file_path)?;
+        Ok(())
+    }
+    
+    pub fn get_recent_screenshots(&self, limit: usize) -> Vec<&ScreenshotHistory> {
+        self.history.get_recent_screenshots(limit)
+    }
+
+    pub fn set_note(&mut self, file_path: &str, note: &str) -> Result<(), SnippError> {
+        self.history.set_note(file_path, note)
+    }
+
+    pub fn set_text(&mut self, file_path: &str, text: &str) -> Result<(), SnippError> {
+        self.history.set_text(file_path, text)
+    }
+
+    pub fn set_share_url(&mut self, file_path: &str, url: &str) -> Result<(), SnippError> {
+        self.history.set_share_url(file_path, url)
+    }
+
+    pub fn set_mirror_status(&mut self, file_path: &str, status: MirrorStatus) -> Result<(), SnippError> {
+        self.history.set_mirror_status(file_path, status)
+    }
+
+    pub fn add_tags(&mut self, file_path: &str, tags: &[String]) -> Result<(), SnippError> {
+        self.history.add_tags(file_path, tags)
+    }
+
+    pub fn search(&self, query: &str, limit: usize) -> Vec<&ScreenshotHistory> {
+        self.history.search(query, limit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn test_history_data_default() {
+        let history = HistoryData::default();
+        assert_eq!(history.screenshots.len(), 0);
+    }
+
+    #[test]
+    fn test_add_screenshot() {
+        let mut history = HistoryData::default();
+        let file_path = "/test/path/screenshot.png".to_string();
+        
+        // Test in-memory addition without file system operations
+        let path = std::path::PathBuf::from(&file_path);
+        let filename = path.file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("unknown.png")
+            .to_string();
+        
+        let screenshot = ScreenshotHistory {
+            file_path: file_path.clone(),
+            timestamp: chrono::Utc::now(),
+            filename: filename.clone(),
+            thumbnail_path: None,
+            regression: None,
+            destinations: Vec::new(),
+            sync_status: None,
+            note: String::new(),
+            text: String::new(),
+            share_url: None,
+            mirror: None,
+            tags: Vec::new(),
+        };
+        
+        history.screenshots.insert(0, screenshot);
+        history.screenshots.truncate(50);
+        
+        assert_eq!(history.screenshots.len(), 1);
+        assert_eq!(history.screenshots[0].file_path, file_path);
+        assert_eq!(history.screenshots[0].filename, "screenshot.png");
+    }
+
+    #[test]
+    fn test_remove_screenshot() {
+        let mut history = HistoryData::default();
+        let file_path = "/test/path/screenshot.png".to_string();
+        
+        // Add a screenshot first
+        let screenshot = ScreenshotHistory {
+            file_path: file_path.clone(),
+            timestamp: chrono::Utc::now(),
+            filename: "screenshot.png".to_string(),
+            thumbnail_path: None,
+            regression: None,
+            destinations: Vec::new(),
+            sync_status: None,
+            note: String::new(),
+            text: String::new(),
+            share_url: None,
+            mirror: None,
+            tags: Vec::new(),
+        };
+        history.screenshots.push(screenshot);
+        assert_eq!(history.screenshots.len(), 1);
+        
+        // Remove it (in-memory only)
+        history.screenshots.retain(|screenshot| screenshot.file_path != file_path);
+        assert_eq!(history.screenshots.len(), 0);
+    }
+
+    #[test]
+    fn test_screenshot_limit() {
+        let mut history = HistoryData::default();
+        
+        for i in 0..60 {
+            let file_path = format!("/test/path/screenshot_{}.png", i);
+            let screenshot = ScreenshotHistory {
+                file_path,
+                timestamp: chrono::Utc::now(),
+                filename: format!("screenshot_{}.png", i),
+                thumbnail_path: None,
+                regression: None,
+                destinations: Vec::new(),
+                sync_status: None,
+                note: String::new(),
+                text: String::new(),
+                share_url: None,
+                mirror: None,
+                tags: Vec::new(),
+            };
+            history.screenshots.insert(0, screenshot);
+            history.screenshots.truncate(50); // Apply limit
+        }
+        
+        assert_eq!(history.screenshots.len(), 50);
+    }
+
+    #[test]
+    fn test_get_recent_screenshots() {
+        let mut history = HistoryData::default();
+        
+        for i in 0..10 {
+            let file_path = format!("/test/path/screenshot_{}.png", i);
+            let screenshot = ScreenshotHistory {
+                file_path,
+                timestamp: chrono::Utc::now(),
+                filename: format!("screenshot_{}.png", i),
+                thumbnail_path: None,
+                regression: None,
+                destinations: Vec::new(),
+                sync_status: None,
+                note: String::new(),
+                text: String::new(),
+                share_url: None,
+                mirror: None,
+                tags: Vec::new(),
+            };
+            history.screenshots.insert(0, screenshot);
+        }
+        
+        let recent = history.get_recent_screenshots(5);
+        assert_eq!(recent.len(), 5);
+        
+        // Most recent should be screenshot_9 (last added, inserted at front)
+        assert_eq!(recent[0].filename, "screenshot_9.png");
+        assert_eq!(recent[4].filename, "screenshot_5.png");
+    }
+
+    #[test]
+    fn test_entry_without_regression_deserializes() {
+        let json = r#"{"screenshots":[{"file_path":"/a.png","timestamp":"2024-01-01T00:00:00Z",
+            "filename":"a.png","thumbnail_path":null}]}"#;
+        let history: HistoryData = serde_json::from_str(json).unwrap();
+
+        assert!(history.screenshots[0].regression.is_none());
+        assert!(history.screenshots[0].note.is_empty());
+        assert!(history.screenshots[0].tags.is_empty());
+    }
+
+    #[test]
+    fn test_notes_are_searchable() {
+        let mut history = HistoryData::default();
+        history.screenshots.push(ScreenshotHistory::new("/s/Snipp 24-01-01 at 10.00.00.png".to_string()));
+        history.screenshots.push(ScreenshotHistory::new("/s/Snipp 24-01-02 at 11.00.00.png".to_string()));
+
+        assert!(history.update_note("/s/Snipp 24-01-02 at 11.00.00.png", "  Repro of crash #123 "));
+        assert!(!history.update_note("/s/missing.png", "x"));
+
+        let found = history.search("crash #123", 10);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].note, "Repro of crash #123");
+        assert_eq!(history.search("24-01-01", 10).len(), 1);
+        assert_eq!(history.search("", 10).len(), 2);
+
+        history.screenshots[0].text = "Invoice Total: $42".to_string();
+        assert_eq!(history.search("invoice", 10)[0].filename, "Snipp 24-01-01 at 10.00.00.png");
+
+        history.screenshots[1].tags = vec!["triage".to_string()];
+        assert_eq!(history.search("triage", 10)[0].filename, "Snipp 24-01-02 at 11.00.00.png");
+    }
+}
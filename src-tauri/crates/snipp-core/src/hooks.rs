@@ -0,0 +1,251 @@
+use rhai::{Array, Engine, EvalAltResult, Scope};
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+
+/// Hook scripts get this many Rhai operations per event before they are
+/// stopped, so a runaway loop can't stall a save.
+const MAX_OPERATIONS: u64 = 1_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookEvent {
+    /// A capture was taken and is waiting in the popup.
+    Capture,
+    /// A capture is about to be written to its save folders.
+    Save,
+    /// A saved capture was uploaded.
+    Upload,
+}
+
+impl HookEvent {
+    /// The script function called for this event.
+    pub fn function_name(self) -> &'static str {
+        match self {
+            HookEvent::Capture => "on_capture",
+            HookEvent::Save => "on_save",
+            HookEvent::Upload => "on_upload",
+        }
+    }
+}
+
+/// What a hook can read about the capture; empty where not known yet.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HookContext {
+    pub filename: String,
+    pub folder: String,
+    pub file_path: String,
+    pub source_app: String,
+    pub note: String,
+    pub url: String,
+    pub tags: Vec<String>,
+}
+
+/// Changes a hook asked for.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HookActions {
+    pub filename: Option<String>,
+    pub folder: Option<String>,
+    pub tags: Vec<String>,
+}
+
+impl HookActions {
+    /// Layers `later` over these actions: its rename and destination win,
+    /// tags add up.
+    pub fn merge(&mut self, later: HookActions) {
+        if later.filename.is_some() {
+            self.filename = later.filename;
+        }
+        if later.folder.is_some() {
+            self.folder = later.folder;
+        }
+        for tag in later.tags {
+            if !self.tags.contains(&tag) {
+                self.tags.push(tag);
+            }
+        }
+    }
+}
+
+/// The `capture` argument passed to hook functions. Clones share the
+/// actions, since Rhai passes arguments by value.
+#[derive(Debug, Clone)]
+struct Capture {
+    context: Rc<HookContext>,
+    actions: Rc<RefCell<HookActions>>,
+}
+
+impl Capture {
+    fn filename(&mut self) -> String {
+        self.actions
+            .borrow()
+            .filename
+            .clone()
+            .unwrap_or_else(|| self.context.filename.clone())
+    }
+
+    fn folder(&mut self) -> String {
+        self.actions.borrow().folder.clone().unwrap_or_else(|| self.context.folder.clone())
+    }
+
+    fn tags(&mut self) -> Array {
+        let actions = self.actions.borrow();
+        self.context
+            .tags
+            .iter()
+            .chain(actions.tags.iter())
+            .map(|tag| tag.clone().into())
+            .collect()
+    }
+
+    /// Keeps the original extension when the new name has none.
+    fn rename(&mut self, name: &str) -> Result<(), Box<EvalAltResult>> {
+        let name = name.trim();
+        if name.is_empty() || name.contains(['/', '\\']) || name == "." || name == ".." {
+            return Err(format!("'{}' is not a valid file name", name).into());
+        }
+        let name = match (Path::new(name).extension(), Path::new(&self.context.filename).extension()) {
+            (None, Some(ext)) => format!("{}.{}", name, ext.to_string_lossy()),
+            _ => name.to_string(),
+        };
+        self.actions.borrow_mut().filename = Some(name);
+        Ok(())
+    }
+
+    fn set_destination(&mut self, folder: &str) -> Result<(), Box<EvalAltResult>> {
+        if !Path::new(folder).is_absolute() {
+            return Err(format!("Destination '{}' must be an absolute path", folder).into());
+        }
+        self.actions.borrow_mut().folder = Some(folder.to_string());
+        Ok(())
+    }
+
+    fn add_tag(&mut self, tag: &str) {
+        let tag = tag.trim();
+        let mut actions = self.actions.borrow_mut();
+        if !tag.is_empty() && !self.context.tags.iter().chain(actions.tags.iter()).any(|t| t == tag) {
+            actions.tags.push(tag.to_string());
+        }
+    }
+}
+
+/// An engine with the capture API and nothing else: no file, process or
+/// network access, no `eval`, and bounded work.
+fn engine() -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_call_levels(32);
+    engine.set_max_string_size(64 * 1024);
+    engine.set_max_array_size(1024);
+    engine.disable_symbol("eval");
+    engine.on_print(|text| tracing::info!("Hook script: {}", text));
+    engine.on_debug(|text, _, _| tracing::debug!("Hook script: {}", text));
+
+    engine
+        .register_type_with_name::<Capture>("Capture")
+        .register_get("filename", Capture::filename)
+        .register_get("folder", Capture::folder)
+        .register_get("file_path", |c: &mut Capture| c.context.file_path.clone())
+        .register_get("source_app", |c: &mut Capture| c.context.source_app.clone())
+        .register_get("note", |c: &mut Capture| c.context.note.clone())
+        .register_get("url", |c: &mut Capture| c.context.url.clone())
+        .register_get("tags", Capture::tags)
+        .register_fn("rename", Capture::rename)
+        .register_fn("set_destination", Capture::set_destination)
+        .register_fn("add_tag", Capture::add_tag);
+    engine
+}
+
+/// Checks that a script parses, for settings to report mistakes early.
+pub fn validate(source: &str) -> Result<(), String> {
+    engine()
+        .compile(source)
+        .map(|_| ())
+        .map_err(|e| format!("Hook script error: {}", e))
+}
+
+/// Calls the script's handler for `event`, if it defines one, and returns
+/// what it asked for.
+pub fn run(source: &str, event: HookEvent, context: &HookContext) -> Result<HookActions, String> {
+    let engine = engine();
+    let ast = engine.compile(source).map_err(|e| format!("Hook script error: {}", e))?;
+    let name = event.function_name();
+    if !ast.iter_functions().any(|f| f.name == name && f.params.len() == 1) {
+        return Ok(HookActions::default());
+    }
+
+    let capture = Capture {
+        context: Rc::new(context.clone()),
+        actions: Rc::new(RefCell::new(HookActions::default())),
+    };
+    let _ = engine
+        .call_fn::<rhai::Dynamic>(&mut Scope::new(), &ast, name, (capture.clone(),))
+        .map_err(|e| format!("Hook {} failed: {}", name, e))?;
+    let actions = capture.actions.borrow().clone();
+    Ok(actions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> HookContext {
+        HookContext {
+            filename: "Snipp 24-05-01 at 10.00.00.png".to_string(),
+            folder: "/Users/me/Desktop".to_string(),
+            source_app: "Safari".to_string(),
+            tags: vec!["web".to_string()],
+            ..HookContext::default()
+        }
+    }
+
+    #[test]
+    fn test_save_hook_renames_redirects_and_tags() {
+        let script = r#"
+            fn on_save(capture) {
+                if capture.source_app == "Safari" {
+                    capture.rename("browser-" + capture.filename.sub_string(6, 8));
+                    capture.set_destination("/Users/me/Browser");
+                    capture.add_tag("web");
+                    capture.add_tag("triage");
+                }
+            }
+        "#;
+
+        let actions = run(script, HookEvent::Save, &context()).unwrap();
+
+        assert_eq!(actions.filename.as_deref(), Some("browser-24-05-01.png"));
+        assert_eq!(actions.folder.as_deref(), Some("/Users/me/Browser"));
+        assert_eq!(actions.tags, vec!["triage".to_string()]);
+    }
+
+    #[test]
+    fn test_missing_handlers_and_bad_scripts() {
+        let script = "fn on_upload(capture) { capture.add_tag(\"shared\"); }";
+        assert_eq!(run(script, HookEvent::Save, &context()).unwrap(), HookActions::default());
+        assert_eq!(run(script, HookEvent::Upload, &context()).unwrap().tags, vec!["shared".to_string()]);
+
+        assert!(run("fn on_save(capture) { capture.rename(\"../x\"); }", HookEvent::Save, &context()).is_err());
+        assert!(run("fn on_save(capture) { capture.set_destination(\"tmp\"); }", HookEvent::Save, &context()).is_err());
+        assert!(run("fn on_save(capture) { loop {} }", HookEvent::Save, &context()).is_err());
+        assert!(validate("fn on_save(capture) { eval(\"1\") }").is_err());
+        assert!(validate("fn on_save(capture) {").is_err());
+    }
+
+    #[test]
+    fn test_merge_keeps_later_changes_and_all_tags() {
+        let mut actions = HookActions {
+            filename: Some("a.png".to_string()),
+            folder: None,
+            tags: vec!["bug".to_string()],
+        };
+        actions.merge(HookActions {
+            filename: None,
+            folder: Some("/tmp".to_string()),
+            tags: vec!["bug".to_string(), "ui".to_string()],
+        });
+
+        assert_eq!(actions.filename.as_deref(), Some("a.png"));
+        assert_eq!(actions.folder.as_deref(), Some("/tmp"));
+        assert_eq!(actions.tags, vec!["bug".to_string(), "ui".to_string()]);
+    }
+}
@@ -0,0 +1,102 @@
+//! Capture, cache, history, thumbnail, config and destination logic for
+//! Snipp, with no dependency on Tauri. The app crate in `src-tauri` wires
+//! these into commands and windows.
+
+use std::path::{Path, PathBuf};
+
+pub mod annotate;
+pub mod audio;
+pub mod baseline;
+pub mod beautify;
+pub mod burst;
+pub mod cache;
+pub mod capture;
+pub mod cloud_sync;
+pub mod collage;
+pub mod color;
+pub mod config;
+pub mod destinations;
+pub mod diagnostics;
+pub mod display;
+pub mod error;
+pub mod highlight;
+pub mod history;
+pub mod hooks;
+pub mod logs;
+pub mod metadata;
+pub mod mirror;
+pub mod naming;
+pub mod output;
+pub mod palette;
+pub mod recording;
+pub mod redact;
+pub mod region;
+pub mod scrolling;
+pub mod shred;
+pub mod spill;
+pub mod thumbnail;
+pub mod transform;
+pub mod watch;
+
+/// Appends `-n` when the path is taken, so same-named captures don't overwrite.
+pub fn resolve_unique_path(path: PathBuf) -> PathBuf {
+    if !path.exists() {
+        return path;
+    }
+
+    let mut counter = 1;
+    loop {
+        let candidate = numbered_path(&path, counter);
+        if !candidate.exists() {
+            return candidate;
+        }
+        counter += 1;
+    }
+}
+
+/// `name.png` with `-n` before the extension: `name-1.png`, `name-2.png`.
+pub(crate) fn numbered_path(path: &Path, n: u32) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let name = match path.extension() {
+        Some(ext) => format!("{}-{}.{}", stem, n, ext.to_string_lossy()),
+        None => format!("{}-{}", stem, n),
+    };
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_unique_path_returns_original_when_free() {
+        let dir = std::env::temp_dir().join(format!("snipp_unique_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("Snipp test.png");
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(resolve_unique_path(path.clone()), path);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_resolve_unique_path_appends_counter_on_collision() {
+        let dir = std::env::temp_dir().join(format!("snipp_unique_collide_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("Snipp test.png");
+        std::fs::write(&path, b"x").unwrap();
+
+        let resolved = resolve_unique_path(path.clone());
+        assert_ne!(resolved, path);
+        assert_eq!(resolved, dir.join("Snipp test-1.png"));
+
+        std::fs::write(&resolved, b"x").unwrap();
+        assert_eq!(resolve_unique_path(path.clone()), dir.join("Snipp test-2.png"));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::error::SnippError;
+
+/// Log files are named `snipp.<date>.log`, one per day.
+pub const LOG_FILE_PREFIX: &str = "snipp";
+pub const LOG_FILE_SUFFIX: &str = "log";
+/// Days of log files kept before the oldest is deleted.
+pub const MAX_LOG_FILES: usize = 7;
+
+/// How much Snipp writes to its log.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogLevel {
+    Error,
+    Warn,
+    #[default]
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    /// The level as a `RUST_LOG`-style directive.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LogLevel::Error => "error",
+            LogLevel::Warn => "warn",
+            LogLevel::Info => "info",
+            LogLevel::Debug => "debug",
+            LogLevel::Trace => "trace",
+        }
+    }
+}
+
+/// Where the rolling log files are written.
+pub fn log_dir() -> Result<PathBuf, SnippError> {
+    Ok(dirs::data_dir()
+        .ok_or("Failed to get data directory")?
+        .join("snipp")
+        .join("logs"))
+}
+
+/// Log files in `dir`, oldest first. The date in their names sorts the
+/// same way as the days they cover.
+pub fn log_files(dir: &Path) -> Result<Vec<PathBuf>, SnippError> {
+    let mut files: Vec<PathBuf> = match std::fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| {
+                        name.starts_with(LOG_FILE_PREFIX) && name.ends_with(LOG_FILE_SUFFIX)
+                    })
+            })
+            .collect(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(e.into()),
+    };
+    files.sort();
+    Ok(files)
+}
+
+/// The last `lines` lines logged, reading back into older files when the
+/// newest one is shorter than that.
+pub fn recent_lines(dir: &Path, lines: usize) -> Result<Vec<String>, SnippError> {
+    let mut recent: Vec<String> = Vec::new();
+    for file in log_files(dir)?.iter().rev() {
+        if recent.len() >= lines {
+            break;
+        }
+        let contents = std::fs::read(file)?;
+        let contents = String::from_utf8_lossy(&contents);
+        let wanted = lines - recent.len();
+        let mut older: Vec<String> = contents.lines().rev().take(wanted).map(str::to_string).collect();
+        older.reverse();
+        older.append(&mut recent);
+        recent = older;
+    }
+    Ok(recent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_recent_lines_span_files() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("snipp.2024-05-01.log"), "one\ntwo\nthree\n").unwrap();
+        std::fs::write(dir.path().join("snipp.2024-05-02.log"), "four\nfive\n").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "not a log\n").unwrap();
+
+        assert_eq!(recent_lines(dir.path(), 3).unwrap(), ["three", "four", "five"]);
+        assert_eq!(recent_lines(dir.path(), 1).unwrap(), ["five"]);
+        assert_eq!(recent_lines(dir.path(), 10).unwrap().len(), 5);
+        assert!(recent_lines(&dir.path().join("missing"), 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_log_level_names() {
+        assert_eq!(serde_json::to_string(&LogLevel::Warn).unwrap(), "\"warn\"");
+        assert_eq!(LogLevel::default().as_str(), "info");
+    }
+}
@@ -0,0 +1,235 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::color::{self, PNG_SIGNATURE};
+
+/// PNG chunks that carry text or EXIF rather than pixels or color.
+const METADATA_CHUNKS: [&[u8; 4]; 5] = [b"tEXt", b"zTXt", b"iTXt", b"eXIf", b"tIME"];
+
+/// What happens to metadata in a saved capture.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetadataMode {
+    /// Save whatever the capture already carries.
+    #[default]
+    Keep,
+    /// Remove text and EXIF so nothing but the pixels is shared.
+    Strip,
+    /// Replace it with where and when the capture was taken.
+    Embed,
+}
+
+/// Provenance written into a capture by [`MetadataMode::Embed`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaptureMetadata {
+    pub captured_at: DateTime<Local>,
+    /// e.g. `Snipp 0.1.0`.
+    pub software: String,
+    pub source_app: Option<String>,
+    pub display: Option<String>,
+}
+
+impl CaptureMetadata {
+    /// Key/value pairs in the order they are written as PNG text.
+    fn text_entries(&self) -> Vec<(&'static str, String)> {
+        let mut entries = vec![
+            ("Creation Time", self.captured_at.to_rfc3339()),
+            ("Software", self.software.clone()),
+        ];
+        if let Some(display) = &self.display {
+            entries.push(("Source", display.clone()));
+        }
+        if let Some(app) = &self.source_app {
+            entries.push(("Source App", app.clone()));
+        }
+        entries
+    }
+
+    /// One line naming the app and display, for EXIF's ImageDescription.
+    fn description(&self) -> Option<String> {
+        match (&self.source_app, &self.display) {
+            (Some(app), Some(display)) => Some(format!("{} on {}", app, display)),
+            (Some(app), None) => Some(app.clone()),
+            (None, Some(display)) => Some(display.clone()),
+            (None, None) => None,
+        }
+    }
+}
+
+/// Applies `mode` to an encoded capture. PNGs lose their text and EXIF
+/// chunks, and with `Embed` get `metadata` as text plus EXIF; JPEGs get an
+/// EXIF segment. Other formats are re-encoded from pixels, so there is
+/// nothing to strip and they are returned as they are.
+pub fn apply(data: Vec<u8>, mode: MetadataMode, metadata: &CaptureMetadata) -> Result<Vec<u8>, String> {
+    match mode {
+        MetadataMode::Keep => Ok(data),
+        MetadataMode::Strip if data.starts_with(&PNG_SIGNATURE) => rewrite_png(&data, None),
+        MetadataMode::Embed if data.starts_with(&PNG_SIGNATURE) => rewrite_png(&data, Some(metadata)),
+        MetadataMode::Embed if data.starts_with(&[0xFF, 0xD8]) => Ok(embed_jpeg(&data, metadata)),
+        _ => Ok(data),
+    }
+}
+
+/// Drops the metadata chunks of a PNG and, given `metadata`, writes new
+/// ones just before the image data.
+fn rewrite_png(png: &[u8], metadata: Option<&CaptureMetadata>) -> Result<Vec<u8>, String> {
+    let mut out = PNG_SIGNATURE.to_vec();
+    let mut embedded = false;
+    for chunk in color::read_chunks(png)? {
+        if METADATA_CHUNKS.contains(&&chunk.kind) {
+            continue;
+        }
+        if let (Some(metadata), b"IDAT", false) = (metadata, &chunk.kind, embedded) {
+            for (keyword, text) in metadata.text_entries() {
+                write_text_chunk(&mut out, keyword, &text);
+            }
+            color::write_chunk(&mut out, b"eXIf", &exif(metadata));
+            embedded = true;
+        }
+        color::write_chunk(&mut out, &chunk.kind, chunk.data);
+    }
+    Ok(out)
+}
+
+/// `tEXt` only holds Latin-1, so anything else goes in an uncompressed `iTXt`.
+fn write_text_chunk(out: &mut Vec<u8>, keyword: &str, text: &str) {
+    let mut data = keyword.as_bytes().to_vec();
+    if text.is_ascii() {
+        data.push(0);
+        data.extend_from_slice(text.as_bytes());
+        color::write_chunk(out, b"tEXt", &data);
+    } else {
+        // Null separator, no compression, then empty language and translated keyword.
+        data.extend_from_slice(&[0, 0, 0, 0, 0]);
+        data.extend_from_slice(text.as_bytes());
+        color::write_chunk(out, b"iTXt", &data);
+    }
+}
+
+/// Inserts an APP1 EXIF segment after the JFIF header, or right after the
+/// start-of-image marker when there is none.
+fn embed_jpeg(jpeg: &[u8], metadata: &CaptureMetadata) -> Vec<u8> {
+    let mut at = 2;
+    if jpeg.get(2..4) == Some(&[0xFF, 0xE0]) {
+        if let Some(len) = jpeg.get(4..6) {
+            at = (4 + u16::from_be_bytes([len[0], len[1]]) as usize).min(jpeg.len());
+        }
+    }
+    let mut payload = b"Exif\0\0".to_vec();
+    payload.extend_from_slice(&exif(metadata));
+
+    let mut out = Vec::with_capacity(jpeg.len() + payload.len() + 4);
+    out.extend_from_slice(&jpeg[..at]);
+    out.extend_from_slice(&[0xFF, 0xE1]);
+    out.extend_from_slice(&((payload.len() + 2) as u16).to_be_bytes());
+    out.extend_from_slice(&payload);
+    out.extend_from_slice(&jpeg[at..]);
+    out
+}
+
+/// A big-endian TIFF header with one IFD of ASCII tags: ImageDescription,
+/// Software and DateTime.
+fn exif(metadata: &CaptureMetadata) -> Vec<u8> {
+    let mut tags: Vec<(u16, String)> = Vec::new();
+    if let Some(description) = metadata.description() {
+        tags.push((0x010E, description));
+    }
+    tags.push((0x0131, metadata.software.clone()));
+    tags.push((0x0132, metadata.captured_at.format("%Y:%m:%d %H:%M:%S").to_string()));
+
+    let mut out = b"MM\0\x2a".to_vec();
+    out.extend_from_slice(&8u32.to_be_bytes());
+    out.extend_from_slice(&(tags.len() as u16).to_be_bytes());
+    // Values too long for the entry follow the IFD and its next-IFD offset.
+    let mut data_offset = 8 + 2 + tags.len() * 12 + 4;
+    let mut data = Vec::new();
+    for (tag, value) in &tags {
+        // EXIF ASCII is 7-bit and null-terminated.
+        let mut bytes: Vec<u8> = value.chars().map(|c| if c.is_ascii() { c as u8 } else { b'?' }).collect();
+        bytes.push(0);
+        out.extend_from_slice(&tag.to_be_bytes());
+        out.extend_from_slice(&2u16.to_be_bytes());
+        out.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+        if bytes.len() <= 4 {
+            bytes.resize(4, 0);
+            out.extend_from_slice(&bytes);
+        } else {
+            out.extend_from_slice(&(data_offset as u32).to_be_bytes());
+            data_offset += bytes.len();
+            data.extend_from_slice(&bytes);
+        }
+    }
+    out.extend_from_slice(&0u32.to_be_bytes());
+    out.extend_from_slice(&data);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn metadata() -> CaptureMetadata {
+        CaptureMetadata {
+            captured_at: Local.with_ymd_and_hms(2024, 5, 1, 9, 30, 0).unwrap(),
+            software: "Snipp 0.1.0".to_string(),
+            source_app: Some("Safari".to_string()),
+            display: None,
+        }
+    }
+
+    fn png_with_comment() -> Vec<u8> {
+        let mut png = Vec::new();
+        image::DynamicImage::ImageRgba8(image::RgbaImage::from_pixel(2, 2, image::Rgba([9, 9, 9, 255])))
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageOutputFormat::Png)
+            .unwrap();
+        let mut out = PNG_SIGNATURE.to_vec();
+        for chunk in color::read_chunks(&png).unwrap() {
+            color::write_chunk(&mut out, &chunk.kind, chunk.data);
+            if &chunk.kind == b"IHDR" {
+                color::write_chunk(&mut out, b"tEXt", b"Comment\0/Users/me/secret");
+            }
+        }
+        out
+    }
+
+    fn kinds(png: &[u8]) -> Vec<[u8; 4]> {
+        color::read_chunks(png).unwrap().iter().map(|chunk| chunk.kind).collect()
+    }
+
+    #[test]
+    fn test_strip_and_embed_png() {
+        let png = png_with_comment();
+
+        let stripped = apply(png.clone(), MetadataMode::Strip, &metadata()).unwrap();
+        assert!(!kinds(&stripped).contains(b"tEXt"));
+        assert_eq!(
+            image::load_from_memory(&stripped).unwrap().to_rgba8(),
+            image::load_from_memory(&png).unwrap().to_rgba8()
+        );
+
+        let embedded = apply(png.clone(), MetadataMode::Embed, &metadata()).unwrap();
+        let chunks = color::read_chunks(&embedded).unwrap();
+        let texts: Vec<_> = chunks.iter().filter(|chunk| &chunk.kind == b"tEXt").map(|chunk| chunk.data).collect();
+        assert!(texts.contains(&&b"Software\0Snipp 0.1.0"[..]));
+        assert!(texts.contains(&&b"Source App\0Safari"[..]));
+        assert!(!texts.iter().any(|text| text.starts_with(b"Comment")));
+        let exif = chunks.iter().find(|chunk| &chunk.kind == b"eXIf").unwrap().data;
+        assert!(exif.starts_with(b"MM\0\x2a"));
+        assert!(exif.windows(20).any(|w| w == b"2024:05:01 09:30:00\0"));
+
+        assert_eq!(apply(png.clone(), MetadataMode::Keep, &metadata()).unwrap(), png);
+    }
+
+    #[test]
+    fn test_embed_jpeg_adds_exif_segment() {
+        let jpeg = [0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x04, 0x4A, 0x46, 0xFF, 0xD9].to_vec();
+
+        let embedded = apply(jpeg, MetadataMode::Embed, &metadata()).unwrap();
+
+        // After the 6-byte APP0 segment that follows the start-of-image marker
+        assert_eq!(&embedded[8..10], &[0xFF, 0xE1]);
+        assert_eq!(&embedded[12..18], b"Exif\0\0");
+        assert!(embedded.ends_with(&[0xFF, 0xD9]));
+    }
+}
@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Where a capture's copy on the network share stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MirrorStatus {
+    /// Waiting for the share to be mounted again.
+    Queued,
+    Mirrored,
+}
+
+/// A copy that couldn't be made yet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MirrorJob {
+    /// The saved capture, which is also its history key.
+    pub source: String,
+    pub folder: String,
+}
+
+/// Whether the share is mounted. Only an existing folder counts: creating it
+/// while the share is away would write into the local mount point instead.
+pub fn is_reachable(folder: &str) -> bool {
+    !folder.is_empty() && Path::new(folder).is_dir()
+}
+
+/// Copies `source` into `folder` under its own file name.
+pub fn copy_to(source: &Path, folder: &str) -> Result<PathBuf, String> {
+    if !is_reachable(folder) {
+        return Err(format!("{} is not mounted", folder));
+    }
+    let name = source.file_name().ok_or("Capture has no file name")?;
+    let target = crate::resolve_unique_path(Path::new(folder).join(name));
+    std::fs::copy(source, &target).map_err(|e| format!("Failed to copy to {}: {}", folder, e))?;
+    Ok(target)
+}
+
+/// Copies waiting for their share, kept on disk so they survive a restart.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct MirrorQueue {
+    jobs: Vec<MirrorJob>,
+}
+
+impl MirrorQueue {
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = Self::path().ok_or("Failed to get config directory")?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create config directory: {}", e))?;
+        }
+        let contents = serde_json::to_string(self).map_err(|e| format!("Failed to serialize mirror queue: {}", e))?;
+        std::fs::write(path, contents).map_err(|e| format!("Failed to save mirror queue: {}", e))
+    }
+
+    fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("snipp").join("mirror_queue.json"))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+
+    pub fn push(&mut self, job: MirrorJob) {
+        if !self.jobs.contains(&job) {
+            self.jobs.push(job);
+        }
+    }
+
+    /// Removes and returns the jobs whose share is reachable now.
+    pub fn take_ready(&mut self) -> Vec<MirrorJob> {
+        let (ready, waiting) = std::mem::take(&mut self.jobs)
+            .into_iter()
+            .partition(|job| is_reachable(&job.folder));
+        self.jobs = waiting;
+        ready
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queue_releases_jobs_once_share_is_mounted() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("Snipp.png");
+        std::fs::write(&source, b"png").unwrap();
+        let share = dir.path().join("share");
+        let folder = share.to_string_lossy().to_string();
+
+        assert!(copy_to(&source, &folder).is_err());
+        let mut queue = MirrorQueue::default();
+        let job = MirrorJob {
+            source: source.to_string_lossy().to_string(),
+            folder: folder.clone(),
+        };
+        queue.push(job.clone());
+        queue.push(job.clone());
+        assert!(queue.take_ready().is_empty());
+
+        std::fs::create_dir(&share).unwrap();
+        assert_eq!(queue.take_ready(), vec![job]);
+        assert!(queue.is_empty());
+        let copied = copy_to(&source, &folder).unwrap();
+        assert_eq!(std::fs::read(copied).unwrap(), b"png");
+    }
+}
@@ -0,0 +1,171 @@
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Reproduces the original "Snipp 24-05-01 at 10.00.00" names.
+pub const DEFAULT_FILENAME_TEMPLATE: &str = "Snipp %y-%m-%d at %H.%M.%S";
+
+/// Names a saved capture from a strftime `template`, where `{seq}` becomes
+/// the zero-padded daily counter. `suffix` goes before the extension.
+pub fn render_filename(
+    template: &str,
+    captured_at: DateTime<Local>,
+    seq: Option<u32>,
+    suffix: Option<&str>,
+) -> Result<String, String> {
+    use std::fmt::Write;
+
+    let template = match seq {
+        Some(seq) => template.replace("{seq}", &format!("{:03}", seq)),
+        None => template.to_string(),
+    };
+    let mut name = String::new();
+    write!(name, "{}", captured_at.format(&template))
+        .map_err(|_| format!("'{}' is not a valid filename pattern", template))?;
+    // Path separators would write outside the save folder; Finder shows ':' as '/'.
+    let name = name.trim().replace(['/', ':'], "-");
+    if name.is_empty() {
+        return Err("The filename pattern produced an empty name".to_string());
+    }
+    Ok(format!("{}{}.png", name, suffix.unwrap_or("")))
+}
+
+/// Whether `name` could have been produced by `template`: its literal text
+/// must appear in order, with each placeholder standing for at least one
+/// character. Anything may follow, such as a suffix or collision number.
+pub fn matches_template(template: &str, name: &str) -> bool {
+    let literals = template_literals(template);
+    if literals.iter().all(|literal| literal.trim().is_empty()) {
+        // A pattern of bare placeholders would match any file.
+        return false;
+    }
+    let Some(mut rest) = name.strip_prefix(literals[0].trim_start()) else {
+        return false;
+    };
+    for literal in &literals[1..] {
+        let mut chars = rest.chars();
+        if chars.next().is_none() {
+            return false;
+        }
+        match chars.as_str().find(literal.as_str()) {
+            Some(at) => rest = &chars.as_str()[at + literal.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+/// Splits a filename template into the literal text around its
+/// placeholders, cleaned up the way `render_filename` cleans names.
+fn template_literals(template: &str) -> Vec<String> {
+    let template = template.replace("{seq}", "%");
+    let mut literals = vec![String::new()];
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            literals.last_mut().unwrap().push(c);
+            continue;
+        }
+        match chars.peek() {
+            Some('%') => {
+                chars.next();
+                literals.last_mut().unwrap().push('%');
+            }
+            None => literals.push(String::new()),
+            Some(_) => {
+                // Padding flags, widths and `%:z`-style modifiers.
+                while chars.next_if(|c| "-_0^#.:123456789".contains(*c)).is_some() {}
+                chars.next();
+                literals.push(String::new());
+            }
+        }
+    }
+    literals
+        .into_iter()
+        .map(|literal| literal.replace(['/', ':'], "-"))
+        .collect()
+}
+
+/// Counter behind `{seq}`; starts again at 1 each day.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DailySequence {
+    pub date: Option<NaiveDate>,
+    pub last: u32,
+}
+
+impl DailySequence {
+    pub fn next(&mut self, today: NaiveDate) -> u32 {
+        if self.date != Some(today) {
+            self.date = Some(today);
+            self.last = 0;
+        }
+        self.last += 1;
+        self.last
+    }
+
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = Self::path().ok_or("Failed to get config directory")?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create config directory: {}", e))?;
+        }
+        let contents = serde_json::to_string(self).map_err(|e| format!("Failed to serialize sequence: {}", e))?;
+        std::fs::write(path, contents).map_err(|e| format!("Failed to save sequence: {}", e))
+    }
+
+    fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("snipp").join("sequence.json"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_render_filename() {
+        let captured_at = Local.with_ymd_and_hms(2024, 5, 1, 10, 0, 0).unwrap();
+
+        assert_eq!(
+            render_filename(DEFAULT_FILENAME_TEMPLATE, captured_at, None, None).unwrap(),
+            "Snipp 24-05-01 at 10.00.00.png"
+        );
+        assert_eq!(
+            render_filename("snipp-%Y-%m-%d-{seq}", captured_at, Some(3), Some("-edited")).unwrap(),
+            "snipp-2024-05-01-003-edited.png"
+        );
+        assert_eq!(render_filename("a/b %H:%M", captured_at, None, None).unwrap(), "a-b 10-00.png");
+        assert!(render_filename("%Q", captured_at, None, None).is_err());
+    }
+
+    #[test]
+    fn test_matches_template() {
+        let captured_at = Local.with_ymd_and_hms(2024, 5, 1, 10, 0, 0).unwrap();
+        let rendered = render_filename("shot_%Y%m%d_{seq}", captured_at, Some(7), None).unwrap();
+
+        assert!(matches_template("shot_%Y%m%d_{seq}", &rendered));
+        assert!(matches_template("shot_%Y%m%d_{seq}", "shot_20240501_007-2.jpg"));
+        assert!(matches_template(DEFAULT_FILENAME_TEMPLATE, "Snipp 24-05-01 at 10.00.00.png"));
+        assert!(!matches_template(DEFAULT_FILENAME_TEMPLATE, "Snipp notes.png"));
+        assert!(!matches_template("shot_%Y%m%d_{seq}", "shot__.png"));
+        assert!(!matches_template("shot_%Y%m%d_{seq}", "Screenshot 2024.png"));
+        assert!(!matches_template("%Y%m%d", "20240501.png"));
+    }
+
+    #[test]
+    fn test_daily_sequence_resets_each_day() {
+        let mut sequence = DailySequence::default();
+        let day = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+
+        assert_eq!(sequence.next(day), 1);
+        assert_eq!(sequence.next(day), 2);
+        assert_eq!(sequence.next(day.succ_opt().unwrap()), 1);
+    }
+}
@@ -0,0 +1,161 @@
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::tiff::TiffEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::{ImageEncoder, RgbImage, RgbaImage};
+use serde::{Deserialize, Serialize};
+
+/// ravif speed (1-10). Screenshots are mostly flat colour, so the slower
+/// settings cost seconds without shrinking them much further.
+const AVIF_SPEED: u8 = 8;
+
+/// File format saved captures are written in. Captures stay PNG in the
+/// cache and are only re-encoded on their way to disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    #[default]
+    Png,
+    Jpeg,
+    Webp,
+    Tiff,
+    Avif,
+    /// Encoded by the app through ImageIO; only available on macOS.
+    Heic,
+}
+
+impl OutputFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            OutputFormat::Png => "png",
+            OutputFormat::Jpeg => "jpg",
+            OutputFormat::Webp => "webp",
+            OutputFormat::Tiff => "tiff",
+            OutputFormat::Avif => "avif",
+            OutputFormat::Heic => "heic",
+        }
+    }
+
+    /// The format for a file extension or macOS screenshot `type`, e.g. "jpg".
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "png" => Some(OutputFormat::Png),
+            "jpg" | "jpeg" => Some(OutputFormat::Jpeg),
+            "webp" => Some(OutputFormat::Webp),
+            "tif" | "tiff" => Some(OutputFormat::Tiff),
+            "avif" => Some(OutputFormat::Avif),
+            "heic" | "heif" => Some(OutputFormat::Heic),
+            _ => None,
+        }
+    }
+}
+
+/// Composites `rgba` over white, for formats without transparency.
+pub fn flatten_onto_white(rgba: &RgbaImage) -> RgbImage {
+    let mut rgb = RgbImage::new(rgba.width(), rgba.height());
+    for (target, pixel) in rgb.pixels_mut().zip(rgba.pixels()) {
+        let alpha = pixel[3] as u32;
+        for channel in 0..3 {
+            target[channel] = ((pixel[channel] as u32 * alpha + 255 * (255 - alpha)) / 255) as u8;
+        }
+    }
+    rgb
+}
+
+/// Re-encodes a PNG capture as `format`. PNGs are returned as they are;
+/// `quality` (1-100) applies to JPEG and AVIF, and WebP is lossless.
+/// HEIC isn't handled here since it needs the platform encoder.
+pub fn encode(png: &[u8], format: OutputFormat, quality: u8) -> Result<Vec<u8>, String> {
+    let decode = || {
+        image::load_from_memory(png)
+            .map(|image| image.to_rgba8())
+            .map_err(|e| format!("Failed to decode image: {}", e))
+    };
+    let mut encoded = Vec::new();
+    let result = match format {
+        OutputFormat::Png => return Ok(png.to_vec()),
+        OutputFormat::Heic => return Err("HEIC can only be encoded on macOS".to_string()),
+        OutputFormat::Avif => return encode_avif(&decode()?, quality),
+        OutputFormat::Jpeg => {
+            let rgba = decode()?;
+            JpegEncoder::new_with_quality(&mut encoded, quality.clamp(1, 100)).write_image(
+                &flatten_onto_white(&rgba),
+                rgba.width(),
+                rgba.height(),
+                image::ColorType::Rgb8,
+            )
+        }
+        OutputFormat::Webp => {
+            let rgba = decode()?;
+            WebPEncoder::new_lossless(&mut encoded).write_image(
+                &rgba,
+                rgba.width(),
+                rgba.height(),
+                image::ColorType::Rgba8,
+            )
+        }
+        OutputFormat::Tiff => {
+            let rgba = decode()?;
+            TiffEncoder::new(std::io::Cursor::new(&mut encoded)).write_image(
+                &rgba,
+                rgba.width(),
+                rgba.height(),
+                image::ColorType::Rgba8,
+            )
+        }
+    };
+    result.map_err(|e| format!("Failed to encode {}: {}", format.extension(), e))?;
+    Ok(encoded)
+}
+
+fn encode_avif(rgba: &RgbaImage, quality: u8) -> Result<Vec<u8>, String> {
+    let pixels: Vec<ravif::RGBA8> = rgba
+        .pixels()
+        .map(|pixel| ravif::RGBA8::new(pixel[0], pixel[1], pixel[2], pixel[3]))
+        .collect();
+    let image = ravif::Img::new(&pixels[..], rgba.width() as usize, rgba.height() as usize);
+    ravif::Encoder::new()
+        .with_quality(quality.clamp(1, 100) as f32)
+        .with_speed(AVIF_SPEED)
+        .encode_rgba(image)
+        .map(|encoded| encoded.avif_file)
+        .map_err(|e| format!("Failed to encode avif: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_every_format() {
+        let mut png = Vec::new();
+        image::DynamicImage::ImageRgba8(RgbaImage::from_pixel(12, 8, image::Rgba([200, 40, 40, 0])))
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageOutputFormat::Png)
+            .unwrap();
+
+        assert_eq!(encode(&png, OutputFormat::Png, 90).unwrap(), png);
+        assert!(encode(&png, OutputFormat::Heic, 90).is_err());
+        let avif = encode(&png, OutputFormat::Avif, 70).unwrap();
+        assert_eq!(&avif[4..12], b"ftypavif");
+        for (format, expected) in [
+            (OutputFormat::Jpeg, image::ImageFormat::Jpeg),
+            (OutputFormat::Webp, image::ImageFormat::WebP),
+            (OutputFormat::Tiff, image::ImageFormat::Tiff),
+        ] {
+            let encoded = encode(&png, format, 90).unwrap();
+            assert_eq!(image::guess_format(&encoded).unwrap(), expected);
+            assert_eq!(image::load_from_memory(&encoded).unwrap().width(), 12);
+        }
+        // Fully transparent pixels come out white in JPEG.
+        let jpeg = image::load_from_memory(&encode(&png, OutputFormat::Jpeg, 90).unwrap()).unwrap().to_rgb8();
+        assert!(jpeg.get_pixel(0, 0)[1] > 240);
+    }
+
+    #[test]
+    fn test_from_extension() {
+        assert_eq!(OutputFormat::from_extension("JPG"), Some(OutputFormat::Jpeg));
+        assert_eq!(OutputFormat::from_extension("tif"), Some(OutputFormat::Tiff));
+        assert_eq!(OutputFormat::from_extension("heif"), Some(OutputFormat::Heic));
+        assert_eq!(OutputFormat::from_extension("pdf"), None);
+        assert_eq!(OutputFormat::Jpeg.extension(), "jpg");
+    }
+}
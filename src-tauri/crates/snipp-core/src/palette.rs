@@ -0,0 +1,197 @@
+use serde::Serialize;
+
+/// Captures are shrunk to fit this before finding their palette; the
+/// dominant colors survive and it keeps large captures quick.
+const PALETTE_SAMPLE_SIZE: u32 = 128;
+
+pub const MAX_PALETTE_COLORS: usize = 16;
+
+/// A color in the forms designers paste elsewhere.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ColorSample {
+    /// `#rrggbb`
+    pub hex: String,
+    pub rgb: [u8; 3],
+    /// Hue in degrees, saturation and lightness in percent.
+    pub hsl: [u16; 3],
+}
+
+impl ColorSample {
+    pub fn from_rgb([r, g, b]: [u8; 3]) -> Self {
+        let (r1, g1, b1) = (r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0);
+        let max = r1.max(g1).max(b1);
+        let min = r1.min(g1).min(b1);
+        let lightness = (max + min) / 2.0;
+        let delta = max - min;
+        let (hue, saturation) = if delta == 0.0 {
+            (0.0, 0.0)
+        } else {
+            let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+            let hue = if max == r1 {
+                60.0 * ((g1 - b1) / delta).rem_euclid(6.0)
+            } else if max == g1 {
+                60.0 * ((b1 - r1) / delta + 2.0)
+            } else {
+                60.0 * ((r1 - g1) / delta + 4.0)
+            };
+            (hue, saturation)
+        };
+        Self {
+            hex: format!("#{:02x}{:02x}{:02x}", r, g, b),
+            rgb: [r, g, b],
+            hsl: [
+                hue.round() as u16 % 360,
+                (saturation * 100.0).round() as u16,
+                (lightness * 100.0).round() as u16,
+            ],
+        }
+    }
+}
+
+/// One of an image's dominant colors.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PaletteColor {
+    #[serde(flatten)]
+    pub color: ColorSample,
+    /// Fraction of the image's opaque pixels closest to this color.
+    pub share: f64,
+}
+
+/// The color of pixel `(x, y)` in a PNG, ignoring its transparency.
+pub fn pick(png: &[u8], x: u32, y: u32) -> Result<ColorSample, String> {
+    let image = image::load_from_memory(png)
+        .map_err(|e| format!("Failed to decode image: {}", e))?
+        .to_rgba8();
+    if x >= image.width() || y >= image.height() {
+        return Err(format!("({}, {}) is outside the {}x{} image", x, y, image.width(), image.height()));
+    }
+    let pixel = image.get_pixel(x, y);
+    Ok(ColorSample::from_rgb([pixel[0], pixel[1], pixel[2]]))
+}
+
+/// Up to `count` dominant colors of a PNG, most common first, found by
+/// median cut over its opaque pixels.
+pub fn extract(png: &[u8], count: usize) -> Result<Vec<PaletteColor>, String> {
+    if !(1..=MAX_PALETTE_COLORS).contains(&count) {
+        return Err(format!("A palette has between 1 and {} colors", MAX_PALETTE_COLORS));
+    }
+    let image = image::load_from_memory(png).map_err(|e| format!("Failed to decode image: {}", e))?;
+    let image = if image.width() > PALETTE_SAMPLE_SIZE || image.height() > PALETTE_SAMPLE_SIZE {
+        image.thumbnail(PALETTE_SAMPLE_SIZE, PALETTE_SAMPLE_SIZE).to_rgba8()
+    } else {
+        image.to_rgba8()
+    };
+    let pixels: Vec<[u8; 3]> = image
+        .pixels()
+        .filter(|pixel| pixel[3] >= 128)
+        .map(|pixel| [pixel[0], pixel[1], pixel[2]])
+        .collect();
+    if pixels.is_empty() {
+        return Ok(Vec::new());
+    }
+    let total = pixels.len() as f64;
+
+    let mut boxes = vec![pixels];
+    while boxes.len() < count {
+        let Some((index, channel, range)) = boxes
+            .iter()
+            .enumerate()
+            .map(|(index, pixels)| {
+                let (channel, range) = widest_channel(pixels);
+                (index, channel, range)
+            })
+            .max_by_key(|&(_, _, range)| range)
+        else {
+            break;
+        };
+        if range == 0 {
+            break;
+        }
+        let mut pixels = boxes.swap_remove(index);
+        pixels.sort_unstable_by_key(|pixel| pixel[channel]);
+        // Split at the median, but never through a run of one value, so a
+        // flat color stays in one box.
+        let median = pixels[pixels.len() / 2][channel];
+        let split = match pixels.partition_point(|pixel| pixel[channel] < median) {
+            0 => pixels.partition_point(|pixel| pixel[channel] <= median),
+            split => split,
+        };
+        let upper = pixels.split_off(split);
+        boxes.push(pixels);
+        boxes.push(upper);
+    }
+
+    let mut palette: Vec<PaletteColor> = boxes
+        .iter()
+        .map(|pixels| {
+            let mut sum = [0u64; 3];
+            for pixel in pixels {
+                for channel in 0..3 {
+                    sum[channel] += pixel[channel] as u64;
+                }
+            }
+            let len = pixels.len() as u64;
+            let average = sum.map(|total| ((total + len / 2) / len) as u8);
+            PaletteColor {
+                color: ColorSample::from_rgb(average),
+                share: pixels.len() as f64 / total,
+            }
+        })
+        .collect();
+    palette.sort_by(|a, b| b.share.total_cmp(&a.share));
+    Ok(palette)
+}
+
+/// The channel the pixels spread furthest along, and how far.
+fn widest_channel(pixels: &[[u8; 3]]) -> (usize, u8) {
+    (0..3)
+        .map(|channel| {
+            let min = pixels.iter().map(|pixel| pixel[channel]).min().unwrap_or(0);
+            let max = pixels.iter().map(|pixel| pixel[channel]).max().unwrap_or(0);
+            (channel, max - min)
+        })
+        .max_by_key(|&(_, range)| range)
+        .unwrap_or((0, 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Three quarters red on the left, the rest blue.
+    fn sample_png() -> Vec<u8> {
+        let image = image::RgbaImage::from_fn(40, 10, |x, _| {
+            if x < 30 {
+                image::Rgba([255, 0, 0, 255])
+            } else {
+                image::Rgba([0, 0, 255, 255])
+            }
+        });
+        let mut png = Vec::new();
+        image::DynamicImage::ImageRgba8(image)
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageOutputFormat::Png)
+            .unwrap();
+        png
+    }
+
+    #[test]
+    fn test_pick() {
+        let red = pick(&sample_png(), 0, 0).unwrap();
+        assert_eq!(red.hex, "#ff0000");
+        assert_eq!(red.hsl, [0, 100, 50]);
+        assert_eq!(pick(&sample_png(), 35, 5).unwrap().hsl, [240, 100, 50]);
+        assert!(pick(&sample_png(), 40, 0).is_err());
+        assert_eq!(ColorSample::from_rgb([128, 128, 128]).hsl, [0, 0, 50]);
+    }
+
+    #[test]
+    fn test_extract() {
+        let palette = extract(&sample_png(), 4).unwrap();
+
+        assert_eq!(palette.len(), 2);
+        assert_eq!(palette[0].color.hex, "#ff0000");
+        assert!((palette[0].share - 0.75).abs() < 0.05);
+        assert_eq!(palette[1].color.hex, "#0000ff");
+        assert!(extract(&sample_png(), 0).is_err());
+    }
+}
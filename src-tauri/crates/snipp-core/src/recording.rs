@@ -0,0 +1,251 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Gap between the webcam bubble and the edge of the video, in points.
+const WEBCAM_MARGIN: f64 = 24.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebcamPosition {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl WebcamPosition {
+    pub fn overlay_expr(self, scale_factor: f64) -> String {
+        let margin = (WEBCAM_MARGIN * scale_factor.max(1.0)).round() as u32;
+        match self {
+            WebcamPosition::TopLeft => format!("{m}:{m}", m = margin),
+            WebcamPosition::TopRight => format!("main_w-overlay_w-{m}:{m}", m = margin),
+            WebcamPosition::BottomLeft => format!("{m}:main_h-overlay_h-{m}", m = margin),
+            WebcamPosition::BottomRight => {
+                format!("main_w-overlay_w-{m}:main_h-overlay_h-{m}", m = margin)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebcamShape {
+    Circle,
+    Square,
+}
+
+/// Camera bubble composited over screen recordings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebcamOverlay {
+    pub enabled: bool,
+    /// avfoundation video device name; empty uses the default camera.
+    pub device: String,
+    pub position: WebcamPosition,
+    pub shape: WebcamShape,
+    /// Bubble width and height in points.
+    pub size: u32,
+}
+
+impl Default for WebcamOverlay {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            device: String::new(),
+            position: WebcamPosition::BottomRight,
+            shape: WebcamShape::Circle,
+            size: 200,
+        }
+    }
+}
+
+impl WebcamOverlay {
+    pub fn device_arg(&self) -> &str {
+        if self.device.trim().is_empty() {
+            "default"
+        } else {
+            self.device.trim()
+        }
+    }
+
+    /// Crops the camera to a square of the configured size and, for circles,
+    /// masks the corners through the alpha channel before overlaying.
+    pub fn filter(&self, scale_factor: f64) -> String {
+        let size = (((self.size.max(16) as f64) * scale_factor.max(1.0)).round() as u32) / 2 * 2;
+        let square = format!(
+            "scale={s}:{s}:force_original_aspect_ratio=increase,crop={s}:{s}",
+            s = size
+        );
+        match self.shape {
+            WebcamShape::Square => square,
+            WebcamShape::Circle => format!(
+                "{},format=yuva444p,geq=lum='p(X,Y)':cb='p(X,Y)':cr='p(X,Y)':a='if(lte(hypot(X-W/2,Y-H/2),W/2),255,0)'",
+                square
+            ),
+        }
+    }
+}
+
+/// Encoder settings that trade quality for file size.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RecordingQuality {
+    pub frame_rate: u32,
+    /// Output size relative to the captured area, in (0, 1].
+    pub scale: f64,
+    /// x264 constant rate factor; lower is better quality. Ignored when a
+    /// bitrate is set.
+    pub crf: u8,
+    /// Target video bitrate in kbit/s; 0 uses `crf` instead.
+    pub bitrate_kbps: u32,
+}
+
+impl Default for RecordingQuality {
+    fn default() -> Self {
+        Self {
+            frame_rate: 30,
+            scale: 1.0,
+            crf: 23,
+            bitrate_kbps: 0,
+        }
+    }
+}
+
+impl RecordingQuality {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=120).contains(&self.frame_rate) {
+            return Err("Frame rate must be between 1 and 120".to_string());
+        }
+        if !(self.scale > 0.0 && self.scale <= 1.0) {
+            return Err("Resolution scale must be greater than 0 and at most 1".to_string());
+        }
+        if self.crf > 51 {
+            return Err("CRF must be between 0 and 51".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn scale_filter(&self) -> Option<String> {
+        (self.scale < 1.0)
+            .then(|| format!("scale=trunc(iw*{s}/2)*2:trunc(ih*{s}/2)*2", s = self.scale))
+    }
+
+    pub fn rate_control_args(&self) -> Vec<String> {
+        if self.bitrate_kbps > 0 {
+            vec![
+                "-b:v".into(),
+                format!("{}k", self.bitrate_kbps),
+                "-maxrate".into(),
+                format!("{}k", self.bitrate_kbps),
+                "-bufsize".into(),
+                format!("{}k", self.bitrate_kbps * 2),
+            ]
+        } else {
+            vec!["-crf".into(), self.crf.to_string()]
+        }
+    }
+}
+
+/// Safety limits that stop a forgotten recording; 0 disables a limit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RecordingLimits {
+    pub max_duration_secs: u64,
+    pub max_file_size_mb: u64,
+}
+
+impl Default for RecordingLimits {
+    fn default() -> Self {
+        Self {
+            max_duration_secs: 60 * 60,
+            max_file_size_mb: 4096,
+        }
+    }
+}
+
+/// `2 minute`, `45 second` or `1m 30s`: exact, unlike rounding to minutes.
+fn format_duration_limit(secs: u64) -> String {
+    match (secs / 60, secs % 60) {
+        (0, seconds) => format!("{} second", seconds),
+        (minutes, 0) => format!("{} minute", minutes),
+        (minutes, seconds) => format!("{}m {}s", minutes, seconds),
+    }
+}
+
+impl RecordingLimits {
+    /// Returns why the recording should stop, if a limit has been reached.
+    pub fn exceeded(&self, recorded: Duration, bytes_written: u64) -> Option<String> {
+        if self.max_duration_secs > 0 && recorded.as_secs() >= self.max_duration_secs {
+            return Some(format!(
+                "Recording reached the {} limit",
+                format_duration_limit(self.max_duration_secs)
+            ));
+        }
+        if self.max_file_size_mb > 0 && bytes_written >= self.max_file_size_mb * 1024 * 1024 {
+            return Some(format!(
+                "Recording reached the {} MB size limit",
+                self.max_file_size_mb
+            ));
+        }
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnimationFormat {
+    Gif,
+    Webp,
+}
+
+impl AnimationFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            AnimationFormat::Gif => "gif",
+            AnimationFormat::Webp => "webp",
+        }
+    }
+}
+
+/// Settings for turning a recording into an animated image.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnimationOptions {
+    pub fps: u32,
+    /// Output width in pixels, keeping the aspect ratio; 0 keeps the
+    /// recording's width.
+    pub max_width: u32,
+    /// GIF palette size.
+    pub max_colors: u32,
+    /// Dither the GIF palette; smoother gradients but larger files.
+    pub dither: bool,
+    /// WebP quality, 0-100.
+    pub quality: u8,
+}
+
+impl Default for AnimationOptions {
+    fn default() -> Self {
+        Self {
+            fps: 12,
+            max_width: 800,
+            max_colors: 256,
+            dither: true,
+            quality: 75,
+        }
+    }
+}
+
+impl AnimationOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=50).contains(&self.fps) {
+            return Err("Animation frame rate must be between 1 and 50".to_string());
+        }
+        if !(2..=256).contains(&self.max_colors) {
+            return Err("GIF palette must have between 2 and 256 colors".to_string());
+        }
+        if self.quality > 100 {
+            return Err("WebP quality must be between 0 and 100".to_string());
+        }
+        Ok(())
+    }
+}
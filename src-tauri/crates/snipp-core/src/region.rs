@@ -0,0 +1,209 @@
+use serde::{Deserialize, Serialize};
+
+use crate::highlight::ClickHighlight;
+
+/// A screen rectangle in logical points, matching what `screencapture -R` expects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CaptureRegion {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl CaptureRegion {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.width == 0 || self.height == 0 {
+            return Err("Capture region must have a non-zero size".to_string());
+        }
+        Ok(())
+    }
+
+    /// Whether the point `(x, y)`, in points, lies inside.
+    pub fn contains(self, x: f64, y: f64) -> bool {
+        x >= self.x as f64
+            && y >= self.y as f64
+            && x < self.x as f64 + self.width as f64
+            && y < self.y as f64 + self.height as f64
+    }
+
+    /// Formats the region as the `x,y,w,h` argument taken by `screencapture -R`.
+    pub fn to_screencapture_arg(self) -> String {
+        format!("{},{},{},{}", self.x, self.y, self.width, self.height)
+    }
+
+    /// Converts to physical pixels. Sizes are rounded down to even numbers since
+    /// most video encoders reject odd dimensions.
+    pub fn to_physical(self, scale_factor: f64) -> (u32, u32, u32, u32) {
+        let scale = if scale_factor > 0.0 { scale_factor } else { 1.0 };
+        let x = (self.x.max(0) as f64 * scale).round() as u32;
+        let y = (self.y.max(0) as f64 * scale).round() as u32;
+        let width = ((self.width as f64 * scale).round() as u32 / 2 * 2).max(2);
+        let height = ((self.height as f64 * scale).round() as u32 / 2 * 2).max(2);
+        (x, y, width, height)
+    }
+
+    /// Converts to physical pixels clipped to a `bounds_width` x
+    /// `bounds_height` image, without the even-size rounding video needs.
+    /// Returns `None` if nothing of the region is left.
+    pub fn to_pixels(
+        self,
+        scale_factor: f64,
+        bounds_width: u32,
+        bounds_height: u32,
+    ) -> Option<(u32, u32, u32, u32)> {
+        let scale = if scale_factor > 0.0 { scale_factor } else { 1.0 };
+        let x = ((self.x.max(0) as f64 * scale).round() as u32).min(bounds_width);
+        let y = ((self.y.max(0) as f64 * scale).round() as u32).min(bounds_height);
+        let width = ((self.width as f64 * scale).round() as u32).min(bounds_width - x);
+        let height = ((self.height as f64 * scale).round() as u32).min(bounds_height - y);
+        (width > 0 && height > 0).then_some((x, y, width, height))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegionPreset {
+    pub name: String,
+    pub region: CaptureRegion,
+    /// Replaces the configured click highlight for recordings and GIF
+    /// captures of this preset.
+    #[serde(default)]
+    pub click_highlight: Option<ClickHighlight>,
+}
+
+/// Picks the region for a capture or recording: an explicit rectangle wins,
+/// otherwise the named preset is looked up. `None` means the whole screen.
+pub fn resolve_region(
+    explicit: Option<CaptureRegion>,
+    preset_name: Option<&str>,
+    presets: &[RegionPreset],
+) -> Result<Option<CaptureRegion>, String> {
+    let region = match (explicit, preset_name) {
+        (Some(region), _) => Some(region),
+        (None, Some(name)) => {
+            let preset = presets
+                .iter()
+                .find(|preset| preset.name == name)
+                .ok_or_else(|| format!("No region preset named '{}'", name))?;
+            Some(preset.region)
+        }
+        (None, None) => None,
+    };
+
+    if let Some(region) = &region {
+        region.validate()?;
+    }
+
+    Ok(region)
+}
+
+/// The click highlight for a capture: the named preset's own setting when it
+/// has one, otherwise `default`.
+pub fn resolve_click_highlight(
+    preset_name: Option<&str>,
+    presets: &[RegionPreset],
+    default: ClickHighlight,
+) -> ClickHighlight {
+    preset_name
+        .and_then(|name| presets.iter().find(|preset| preset.name == name))
+        .and_then(|preset| preset.click_highlight)
+        .unwrap_or(default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_presets() -> Vec<RegionPreset> {
+        vec![RegionPreset {
+            name: "Browser".to_string(),
+            region: CaptureRegion {
+                x: 100,
+                y: 50,
+                width: 1280,
+                height: 720,
+            },
+            click_highlight: Some(ClickHighlight::Spotlight),
+        }]
+    }
+
+    #[test]
+    fn test_screencapture_arg_format() {
+        let region = CaptureRegion {
+            x: 10,
+            y: 20,
+            width: 300,
+            height: 400,
+        };
+        assert_eq!(region.to_screencapture_arg(), "10,20,300,400");
+    }
+
+    #[test]
+    fn test_to_physical_scales_and_evens_sizes() {
+        let region = CaptureRegion {
+            x: 10,
+            y: 5,
+            width: 101,
+            height: 51,
+        };
+        assert_eq!(region.to_physical(2.0), (20, 10, 202, 102));
+        assert_eq!(region.to_physical(1.0), (10, 5, 100, 50));
+    }
+
+    #[test]
+    fn test_to_pixels_clips_to_bounds() {
+        let region = CaptureRegion {
+            x: 90,
+            y: 10,
+            width: 50,
+            height: 20,
+        };
+        assert_eq!(region.to_pixels(1.0, 100, 100), Some((90, 10, 10, 20)));
+        assert_eq!(region.to_pixels(2.0, 100, 100), None);
+    }
+
+    #[test]
+    fn test_resolve_region_prefers_explicit_rect() {
+        let explicit = CaptureRegion {
+            x: 0,
+            y: 0,
+            width: 10,
+            height: 10,
+        };
+        let resolved = resolve_region(Some(explicit), Some("Browser"), &sample_presets()).unwrap();
+        assert_eq!(resolved, Some(explicit));
+    }
+
+    #[test]
+    fn test_resolve_region_looks_up_preset() {
+        let resolved = resolve_region(None, Some("Browser"), &sample_presets()).unwrap();
+        assert_eq!(resolved.map(|r| r.width), Some(1280));
+    }
+
+    #[test]
+    fn test_resolve_region_rejects_unknown_preset_and_empty_rect() {
+        assert!(resolve_region(None, Some("Missing"), &sample_presets()).is_err());
+
+        let empty = CaptureRegion {
+            x: 0,
+            y: 0,
+            width: 0,
+            height: 10,
+        };
+        assert!(resolve_region(Some(empty), None, &[]).is_err());
+        assert_eq!(resolve_region(None, None, &[]).unwrap(), None);
+    }
+
+    #[test]
+    fn test_resolve_click_highlight_prefers_the_preset() {
+        let presets = sample_presets();
+        let resolve = |name| resolve_click_highlight(name, &presets, ClickHighlight::Ripple);
+        assert_eq!(resolve(Some("Browser")), ClickHighlight::Spotlight);
+        assert_eq!(resolve(Some("Missing")), ClickHighlight::Ripple);
+        assert_eq!(resolve(None), ClickHighlight::Ripple);
+
+        let preset: RegionPreset =
+            serde_json::from_str(r#"{"name":"Old","region":{"x":0,"y":0,"width":10,"height":10}}"#).unwrap();
+        assert_eq!(preset.click_highlight, None);
+    }
+}
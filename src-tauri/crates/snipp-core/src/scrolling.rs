@@ -0,0 +1,145 @@
+use image::RgbaImage;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Shifts that leave fewer matching rows than this aren't trusted as an
+/// overlap between two frames.
+const MIN_OVERLAP_ROWS: usize = 16;
+
+/// Joins successive captures of a window scrolled downwards into one tall
+/// PNG. Rows that stay put in every frame (toolbars, status bars) are kept
+/// once at the top and bottom; the scrolling part between them is lined up
+/// by finding where each frame overlaps the one before. Frames that didn't
+/// move are skipped.
+pub fn stitch(pngs: &[Vec<u8>]) -> Result<Vec<u8>, String> {
+    let frames = pngs
+        .iter()
+        .map(|png| {
+            image::load_from_memory(png)
+                .map(|image| image.to_rgba8())
+                .map_err(|e| format!("Failed to decode frame: {}", e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let first = frames.first().ok_or("No frames were captured")?;
+    let (width, height) = first.dimensions();
+    if frames.iter().any(|frame| frame.dimensions() != (width, height)) {
+        return Err("The window changed size during the scrolling capture".to_string());
+    }
+
+    let hashes: Vec<Vec<u64>> = frames.iter().map(row_hashes).collect();
+    let mut kept: Vec<usize> = vec![0];
+    for index in 1..frames.len() {
+        if hashes[index] != hashes[*kept.last().unwrap()] {
+            kept.push(index);
+        }
+    }
+
+    let (header, footer) = kept
+        .windows(2)
+        .map(|pair| fixed_rows(&hashes[pair[0]], &hashes[pair[1]]))
+        .fold((usize::MAX, usize::MAX), |(header, footer), (h, f)| (header.min(h), footer.min(f)));
+    let header = header.min(height as usize);
+    let band_end = height as usize - footer.min(height as usize - header);
+
+    // (frame, row) for every row of the output, top to bottom.
+    let mut rows: Vec<(usize, usize)> = (0..band_end).map(|row| (kept[0], row)).collect();
+    for pair in kept.windows(2) {
+        let prev = &hashes[pair[0]][header..band_end];
+        let next = &hashes[pair[1]][header..band_end];
+        let new_rows = scroll_offset(prev, next).unwrap_or(next.len());
+        rows.extend((band_end - new_rows..band_end).map(|row| (pair[1], row)));
+    }
+    let last = *kept.last().unwrap();
+    rows.extend((band_end..height as usize).map(|row| (last, row)));
+
+    let stride = width as usize * 4;
+    let mut pixels = Vec::with_capacity(rows.len() * stride);
+    for (frame, row) in &rows {
+        pixels.extend_from_slice(&frames[*frame].as_raw()[row * stride..(row + 1) * stride]);
+    }
+    let stitched = RgbaImage::from_raw(width, rows.len() as u32, pixels).ok_or("Failed to assemble the capture")?;
+
+    let mut png = Vec::new();
+    image::DynamicImage::ImageRgba8(stitched)
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageOutputFormat::Png)
+        .map_err(|e| format!("Failed to encode image: {}", e))?;
+    Ok(png)
+}
+
+fn row_hashes(image: &RgbaImage) -> Vec<u64> {
+    image
+        .as_raw()
+        .chunks_exact(image.width() as usize * 4)
+        .map(|row| {
+            let mut hasher = DefaultHasher::new();
+            row.hash(&mut hasher);
+            hasher.finish()
+        })
+        .collect()
+}
+
+/// Rows matching at the top and bottom of two frames.
+fn fixed_rows(a: &[u64], b: &[u64]) -> (usize, usize) {
+    let header = a.iter().zip(b).take_while(|(a, b)| a == b).count();
+    let footer = a
+        .iter()
+        .rev()
+        .zip(b.iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count()
+        .min(a.len() - header);
+    (header, footer)
+}
+
+/// How many rows `next` scrolled past `prev`: the smallest shift where the
+/// rest of `prev` lines up with the top of `next`.
+fn scroll_offset(prev: &[u64], next: &[u64]) -> Option<usize> {
+    let len = prev.len();
+    (1..=len.saturating_sub(MIN_OVERLAP_ROWS)).find(|&shift| prev[shift..] == next[..len - shift])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A window `height` rows tall with a fixed toolbar and status bar,
+    /// showing a page scrolled down by `scrolled` rows.
+    fn frame(scrolled: u32, height: u32) -> Vec<u8> {
+        let image = RgbaImage::from_fn(4, height, |x, y| match y {
+            0..=9 => image::Rgba([200, 200, 200, 255]),
+            y if y >= height - 5 => image::Rgba([50, 50, 50, 255]),
+            y => {
+                let line = y - 10 + scrolled;
+                image::Rgba([(line % 251) as u8, (line / 251) as u8, x as u8, 255])
+            }
+        });
+        let mut png = Vec::new();
+        image::DynamicImage::ImageRgba8(image)
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageOutputFormat::Png)
+            .unwrap();
+        png
+    }
+
+    #[test]
+    fn test_stitch_lines_up_overlapping_frames() {
+        let frames = [frame(0, 100), frame(40, 100), frame(80, 100), frame(80, 100)];
+        let stitched = image::load_from_memory(&stitch(&frames).unwrap()).unwrap().to_rgba8();
+
+        // Toolbar, 85 page rows from the first frame plus 40 from each later one, status bar.
+        assert_eq!(stitched.dimensions(), (4, 10 + 85 + 80 + 5));
+        assert_eq!(stitched.get_pixel(0, 5)[0], 200);
+        assert_eq!(stitched.get_pixel(0, 10 + 150)[0], 150);
+        assert_eq!(stitched.get_pixel(0, 10 + 164)[0], 164);
+        assert_eq!(stitched.get_pixel(0, 179)[0], 50);
+    }
+
+    #[test]
+    fn test_stitch_without_overlap_or_movement() {
+        let stitched = stitch(&[frame(0, 60), frame(500, 60)]).unwrap();
+        assert_eq!(image::load_from_memory(&stitched).unwrap().height(), 10 + 45 * 2 + 5);
+
+        let still = stitch(&[frame(0, 60), frame(0, 60)]).unwrap();
+        assert_eq!(image::load_from_memory(&still).unwrap().height(), 60);
+        assert!(stitch(&[frame(0, 60), frame(0, 80)]).is_err());
+    }
+}
@@ -0,0 +1,63 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+
+const CHUNK: usize = 64 * 1024;
+
+/// Overwrites a file with zeros and flushes it to disk before unlinking.
+/// A missing file is not an error. On copy-on-write filesystems (APFS) and
+/// SSDs the old blocks may survive; this removes the easy recovery paths.
+pub fn shred_file(path: &Path) -> Result<(), String> {
+    if !path.exists() {
+        return Ok(());
+    }
+    let mut file = OpenOptions::new()
+        .write(true)
+        .open(path)
+        .map_err(|e| format!("Failed to open {} for overwriting: {}", path.display(), e))?;
+    let mut remaining = file
+        .metadata()
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?
+        .len();
+    let zeros = vec![0u8; CHUNK];
+    while remaining > 0 {
+        let len = remaining.min(CHUNK as u64) as usize;
+        file.write_all(&zeros[..len])
+            .map_err(|e| format!("Failed to overwrite {}: {}", path.display(), e))?;
+        remaining -= len as u64;
+    }
+    file.sync_all()
+        .map_err(|e| format!("Failed to flush {}: {}", path.display(), e))?;
+    drop(file);
+    std::fs::remove_file(path).map_err(|e| format!("Failed to delete {}: {}", path.display(), e))
+}
+
+/// Zeros an in-memory capture before it is dropped. Volatile writes keep
+/// the compiler from skipping stores to memory that is about to be freed.
+pub fn wipe(bytes: &mut [u8]) {
+    for byte in bytes.iter_mut() {
+        // SAFETY: `byte` is a valid, exclusive reference into the slice.
+        unsafe { std::ptr::write_volatile(byte, 0) };
+    }
+    std::sync::atomic::compiler_fence(std::sync::atomic::Ordering::SeqCst);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shred_file_removes_file_and_ignores_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("secret.png");
+        std::fs::write(&path, vec![7u8; CHUNK + 10]).unwrap();
+
+        shred_file(&path).unwrap();
+        assert!(!path.exists());
+        shred_file(&path).unwrap();
+
+        let mut bytes = vec![1u8, 2, 3];
+        wipe(&mut bytes);
+        assert_eq!(bytes, [0, 0, 0]);
+    }
+}
@@ -0,0 +1,289 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+
+/// Under a memory warning, cached captures beyond this many bytes go to disk.
+pub const WARNING_KEEP_BYTES: usize = 64 * 1024 * 1024;
+
+static AREA: OnceLock<Option<SpillArea>> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub enum PressureLevel {
+    Normal,
+    Warning,
+    Critical,
+}
+
+impl PressureLevel {
+    /// From libdispatch's `DISPATCH_MEMORYPRESSURE_*` flags.
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    pub fn from_flags(flags: usize) -> Self {
+        if flags & 0x4 != 0 {
+            PressureLevel::Critical
+        } else if flags & 0x2 != 0 {
+            PressureLevel::Warning
+        } else {
+            PressureLevel::Normal
+        }
+    }
+}
+
+/// Called with each memory pressure change.
+pub type PressureHandler = Box<dyn Fn(PressureLevel) + Send + Sync>;
+
+/// A cached capture as seen by the spill policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CachedCapture {
+    pub timestamp: u64,
+    pub bytes: usize,
+    /// Already written to a save folder, so the least likely to be needed again.
+    pub saved: bool,
+}
+
+/// Which captures to move to disk: saved ones first, then the largest.
+/// A warning spills until `keep_bytes` remain in memory; critical spills all.
+pub fn spill_order(captures: &[CachedCapture], level: PressureLevel, keep_bytes: usize) -> Vec<u64> {
+    let mut ordered = captures.to_vec();
+    ordered.sort_by(|a, b| b.saved.cmp(&a.saved).then(b.bytes.cmp(&a.bytes)));
+    let mut remaining: usize = captures.iter().map(|capture| capture.bytes).sum();
+    let limit = match level {
+        PressureLevel::Normal => return Vec::new(),
+        PressureLevel::Warning => keep_bytes,
+        PressureLevel::Critical => 0,
+    };
+    ordered
+        .into_iter()
+        .take_while(|capture| {
+            let over = remaining > limit;
+            remaining -= capture.bytes;
+            over
+        })
+        .map(|capture| capture.timestamp)
+        .collect()
+}
+
+/// Disk-backed overflow for the capture cache, one PNG per timestamp.
+/// Clones share the `secure_delete` setting.
+#[derive(Debug, Clone)]
+pub struct SpillArea {
+    dir: PathBuf,
+    secure_delete: Arc<AtomicBool>,
+}
+
+impl SpillArea {
+    pub fn new() -> Result<Self, String> {
+        let dir = dirs::cache_dir()
+            .ok_or("Failed to get cache directory")?
+            .join("snipp")
+            .join("spill");
+        Self::with_dir(dir)
+    }
+
+    pub fn with_dir(dir: PathBuf) -> Result<Self, String> {
+        std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create spill directory: {}", e))?;
+        Ok(Self {
+            dir,
+            secure_delete: Arc::new(AtomicBool::new(false)),
+        })
+    }
+
+    /// Applies the `secure_delete` setting: spilled captures are then
+    /// overwritten before they are unlinked.
+    pub fn set_secure_delete(&self, enabled: bool) {
+        self.secure_delete.store(enabled, Ordering::Relaxed);
+    }
+
+    fn delete(&self, path: &Path) {
+        let result = if self.secure_delete.load(Ordering::Relaxed) {
+            crate::shred::shred_file(path)
+        } else {
+            std::fs::remove_file(path).map_err(|e| e.to_string())
+        };
+        if let Err(e) = result {
+            tracing::warn!("Failed to delete spilled capture {}: {}", path.display(), e);
+        }
+    }
+
+    fn path(&self, timestamp: u64) -> PathBuf {
+        self.dir.join(format!("{}.png", timestamp))
+    }
+
+    pub fn write(&self, timestamp: u64, image_data: &[u8]) -> Result<(), String> {
+        std::fs::write(self.path(timestamp), image_data)
+            .map_err(|e| format!("Failed to spill capture {}: {}", timestamp, e))
+    }
+
+    pub fn read(&self, timestamp: u64) -> Option<Vec<u8>> {
+        std::fs::read(self.path(timestamp)).ok()
+    }
+
+    pub fn contains(&self, timestamp: u64) -> bool {
+        self.path(timestamp).is_file()
+    }
+
+    pub fn remove(&self, timestamp: u64) {
+        let path = self.path(timestamp);
+        if path.exists() {
+            self.delete(&path);
+        }
+    }
+
+    /// Spilled captures on disk, and their total bytes.
+    pub fn usage(&self) -> (usize, u64) {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return (0, 0);
+        };
+        entries
+            .flatten()
+            .filter_map(|entry| entry.metadata().ok())
+            .fold((0, 0), |(count, bytes), meta| (count + 1, bytes + meta.len()))
+    }
+
+    /// Drops every spilled capture, e.g. leftovers from a previous run.
+    pub fn clear(&self) {
+        if let Ok(entries) = std::fs::read_dir(&self.dir) {
+            for entry in entries.flatten() {
+                self.delete(&entry.path());
+            }
+        }
+    }
+}
+
+/// The shared spill area, or `None` when the cache directory is unusable.
+pub fn area() -> Option<&'static SpillArea> {
+    AREA.get_or_init(|| {
+        SpillArea::new()
+            .map_err(|e| tracing::error!("Cache spilling unavailable: {}", e))
+            .ok()
+    })
+    .as_ref()
+}
+
+#[cfg(target_os = "macos")]
+mod ffi {
+    use std::ffi::c_void;
+
+    #[repr(C)]
+    pub struct DispatchSourceType {
+        _private: [u8; 0],
+    }
+
+    extern "C" {
+        pub static _dispatch_source_type_memorypressure: DispatchSourceType;
+        pub fn dispatch_get_global_queue(identifier: isize, flags: usize) -> *mut c_void;
+        pub fn dispatch_source_create(
+            kind: *const DispatchSourceType,
+            handle: usize,
+            mask: usize,
+            queue: *mut c_void,
+        ) -> *mut c_void;
+        pub fn dispatch_source_set_event_handler_f(source: *mut c_void, handler: extern "C" fn(*mut c_void));
+        pub fn dispatch_source_get_data(source: *mut c_void) -> usize;
+        pub fn dispatch_resume(object: *mut c_void);
+    }
+}
+
+#[cfg(target_os = "macos")]
+static PRESSURE_SOURCE: std::sync::atomic::AtomicPtr<std::ffi::c_void> =
+    std::sync::atomic::AtomicPtr::new(std::ptr::null_mut());
+#[cfg(target_os = "macos")]
+static ON_PRESSURE: OnceLock<PressureHandler> = OnceLock::new();
+
+#[cfg(target_os = "macos")]
+extern "C" fn pressure_event(_context: *mut std::ffi::c_void) {
+    let source = PRESSURE_SOURCE.load(std::sync::atomic::Ordering::Acquire);
+    // SAFETY: the handler only runs once the source below has been created.
+    let level = PressureLevel::from_flags(unsafe { ffi::dispatch_source_get_data(source) });
+    if let Some(on_pressure) = ON_PRESSURE.get() {
+        on_pressure(level);
+    }
+}
+
+/// Calls `on_pressure` (on a background queue) whenever macOS reports a
+/// memory pressure change. Only the first call installs a handler.
+#[cfg(target_os = "macos")]
+pub fn watch_memory_pressure(on_pressure: PressureHandler) {
+    if ON_PRESSURE.set(on_pressure).is_err() {
+        return;
+    }
+    // SAFETY: a process-lifetime dispatch source on a global queue; it is
+    // never cancelled or released.
+    unsafe {
+        let queue = ffi::dispatch_get_global_queue(0, 0);
+        let source = ffi::dispatch_source_create(
+            &ffi::_dispatch_source_type_memorypressure,
+            0,
+            0x2 | 0x4,
+            queue,
+        );
+        if source.is_null() {
+            tracing::warn!("Memory pressure notifications unavailable");
+            return;
+        }
+        PRESSURE_SOURCE.store(source, std::sync::atomic::Ordering::Release);
+        ffi::dispatch_source_set_event_handler_f(source, pressure_event);
+        ffi::dispatch_resume(source);
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn watch_memory_pressure(_on_pressure: PressureHandler) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capture(timestamp: u64, bytes: usize, saved: bool) -> CachedCapture {
+        CachedCapture { timestamp, bytes, saved }
+    }
+
+    #[test]
+    fn test_spill_order_prefers_saved_then_largest() {
+        let captures = [capture(1, 10, false), capture(2, 30, false), capture(3, 5, true)];
+
+        assert!(spill_order(&captures, PressureLevel::Normal, 0).is_empty());
+        assert_eq!(spill_order(&captures, PressureLevel::Critical, 0), vec![3, 2, 1]);
+        // 45 bytes cached, keep 20: the saved one alone isn't enough, the largest is.
+        assert_eq!(spill_order(&captures, PressureLevel::Warning, 20), vec![3, 2]);
+        assert!(spill_order(&captures, PressureLevel::Warning, 45).is_empty());
+    }
+
+    #[test]
+    fn test_pressure_level_from_flags() {
+        assert_eq!(PressureLevel::from_flags(0x1), PressureLevel::Normal);
+        assert_eq!(PressureLevel::from_flags(0x2), PressureLevel::Warning);
+        assert_eq!(PressureLevel::from_flags(0x4), PressureLevel::Critical);
+    }
+
+    #[test]
+    fn test_spill_area_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let area = SpillArea::with_dir(dir.path().join("spill")).unwrap();
+
+        area.write(42, b"png").unwrap();
+        assert_eq!(area.read(42).as_deref(), Some(&b"png"[..]));
+        area.remove(42);
+        assert!(area.read(42).is_none());
+
+        area.write(43, b"png").unwrap();
+        area.clear();
+        assert!(area.read(43).is_none());
+    }
+
+    #[test]
+    fn test_spill_area_secure_delete_is_shared_by_clones() {
+        let dir = tempfile::tempdir().unwrap();
+        let area = SpillArea::with_dir(dir.path().join("spill")).unwrap();
+        let store_copy = area.clone();
+
+        area.set_secure_delete(true);
+        assert!(store_copy.secure_delete.load(Ordering::Relaxed));
+        store_copy.write(44, b"png").unwrap();
+        store_copy.remove(44);
+        assert!(!store_copy.contains(44));
+        store_copy.write(45, b"png").unwrap();
+        store_copy.clear();
+        assert_eq!(store_copy.usage(), (0, 0));
+    }
+}
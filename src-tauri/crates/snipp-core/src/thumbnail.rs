@@ -0,0 +1,238 @@
+use image::{ImageFormat, DynamicImage, GenericImageView};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::fs;
+use std::time::UNIX_EPOCH;
+use base64::Engine;
+
+use crate::error::SnippError;
+
+#[derive(Clone)]
+pub struct ThumbnailGenerator {
+    cache_dir: PathBuf,
+}
+
+impl ThumbnailGenerator {
+    pub fn new() -> Result<Self, SnippError> {
+        let cache_dir = dirs::cache_dir()
+            .ok_or("Failed to get cache directory")?
+            .join("snipp")
+            .join("thumbnails");
+        
+        Self::with_cache_dir(cache_dir)
+    }
+    
+    pub fn with_cache_dir(cache_dir: PathBuf) -> Result<Self, SnippError> {
+        fs::create_dir_all(&cache_dir)?;
+        Ok(Self { cache_dir })
+    }
+    
+    pub fn generate_thumbnail(&self, image_path: &str, max_size: u32) -> Result<String, SnippError> {
+        let source_path = Path::new(image_path);
+        let thumbnail_path = self.thumbnail_path(image_path, max_size)?;
+        
+        if thumbnail_path.exists() {
+            return Ok(thumbnail_path.to_string_lossy().to_string());
+        }
+        
+        let img = image::open(source_path)?;
+        let thumbnail = self.resize_image(img, max_size);
+        
+        // The source changed since older thumbnails of it were made.
+        let prefix = format!("{:016x}_{}_", path_key(image_path), max_size);
+        for stale in self.cached_files(&prefix) {
+            let _ = fs::remove_file(stale);
+        }
+        thumbnail.save_with_format(&thumbnail_path, ImageFormat::Jpeg)?;
+        
+        Ok(thumbnail_path.to_string_lossy().to_string())
+    }
+    
+    
+    pub fn get_thumbnail_base64(&self, image_path: &str, max_size: u32) -> Result<String, SnippError> {
+        let thumbnail_path = self.generate_thumbnail(image_path, max_size)?;
+        let thumbnail_data = fs::read(&thumbnail_path)?;
+        let base64_data = base64::prelude::BASE64_STANDARD.encode(&thumbnail_data);
+        Ok(format!("data:image/jpeg;base64,{}", base64_data))
+    }
+    
+    
+    fn resize_image(&self, img: DynamicImage, max_size: u32) -> DynamicImage {
+        let (width, height) = img.dimensions();
+        
+        if width <= max_size && height <= max_size {
+            return img;
+        }
+        
+        let aspect_ratio = width as f32 / height as f32;
+        let (new_width, new_height) = if width > height {
+            (max_size, (max_size as f32 / aspect_ratio) as u32)
+        } else {
+            ((max_size as f32 * aspect_ratio) as u32, max_size)
+        };
+        
+        img.resize(new_width, new_height, image::imageops::FilterType::Triangle)
+    }
+    
+    
+    /// Where the cached thumbnail for the current version of `image_path`
+    /// lives, whether or not it exists yet. Names hash the full path and the
+    /// file's modification time, so same-named captures in different folders
+    /// get their own thumbnails and edited files get fresh ones.
+    pub fn thumbnail_path(&self, image_path: &str, max_size: u32) -> Result<PathBuf, SnippError> {
+        let modified = fs::metadata(image_path)?
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        Ok(self.cache_dir.join(format!(
+            "{:016x}_{}_{:016x}.jpg",
+            path_key(image_path),
+            max_size,
+            fnv1a(&modified.to_le_bytes())
+        )))
+    }
+    
+    /// Every cached thumbnail of `image_path`, at any size or version. Works
+    /// after the source is gone.
+    pub fn thumbnails_of(&self, image_path: &str) -> Vec<PathBuf> {
+        self.cached_files(&format!("{:016x}_", path_key(image_path)))
+    }
+    
+    pub fn remove_thumbnails(&self, image_path: &str) -> Result<(), SnippError> {
+        for thumbnail_path in self.thumbnails_of(image_path) {
+            fs::remove_file(&thumbnail_path)?;
+        }
+        
+        Ok(())
+    }
+    
+    pub fn usage(&self) -> CacheUsage {
+        self.cached_files("")
+            .iter()
+            .fold(CacheUsage::default(), |usage, path| CacheUsage {
+                files: usage.files + 1,
+                bytes: usage.bytes + fs::metadata(path).map(|meta| meta.len()).unwrap_or(0),
+            })
+    }
+    
+    /// Deletes every cached thumbnail; they are rebuilt on demand. Returns
+    /// what was freed.
+    pub fn purge(&self) -> Result<CacheUsage, SnippError> {
+        let usage = self.usage();
+        for path in self.cached_files("") {
+            fs::remove_file(path)?;
+        }
+        Ok(usage)
+    }
+    
+    fn cached_files(&self, prefix: &str) -> Vec<PathBuf> {
+        let Ok(entries) = fs::read_dir(&self.cache_dir) else {
+            return Vec::new();
+        };
+        entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with(prefix) && name.ends_with(".jpg"))
+            })
+            .collect()
+    }
+}
+
+/// Files and bytes in the thumbnail cache.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct CacheUsage {
+    pub files: usize,
+    pub bytes: u64,
+}
+
+fn path_key(image_path: &str) -> u64 {
+    fnv1a(image_path.as_bytes())
+}
+
+/// FNV-1a, which unlike `DefaultHasher` is stable across Rust releases, so
+/// cached names stay valid after an update.
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+
+    fn create_test_thumbnail_generator() -> (ThumbnailGenerator, TempDir) {
+        let temp_dir = TempDir::new().expect("Failed to create temp directory");
+        let cache_dir = temp_dir.path().join("thumbnails");
+        let generator = ThumbnailGenerator::with_cache_dir(cache_dir).expect("Failed to create thumbnail generator");
+        (generator, temp_dir)
+    }
+
+    #[test]
+    fn test_thumbnail_generator_creation() {
+        let temp_dir = TempDir::new().expect("Failed to create temp directory");
+        let cache_dir = temp_dir.path().join("thumbnails");
+        let generator = ThumbnailGenerator::with_cache_dir(cache_dir);
+        assert!(generator.is_ok());
+    }
+
+    #[test]
+    fn test_resize_image() {
+        let (generator, _temp_dir) = create_test_thumbnail_generator();
+        let img = DynamicImage::new_rgb8(200, 100);
+        
+        let resized = generator.resize_image(img, 50);
+        let (width, height) = resized.dimensions();
+        
+        assert!(width <= 50 && height <= 50);
+        assert!(width == 50 || height == 50);
+    }
+
+    #[test]
+    fn test_same_filename_in_different_folders_gets_separate_thumbnails() {
+        let (generator, temp_dir) = create_test_thumbnail_generator();
+        let mut paths = Vec::new();
+        for (folder, width) in [("a", 40), ("b", 80)] {
+            let dir = temp_dir.path().join(folder);
+            fs::create_dir_all(&dir).unwrap();
+            let path = dir.join("Snipp.png");
+            DynamicImage::new_rgb8(width, 20).save(&path).unwrap();
+            paths.push(path.to_string_lossy().to_string());
+        }
+
+        let first = generator.generate_thumbnail(&paths[0], 64).unwrap();
+        let second = generator.generate_thumbnail(&paths[1], 64).unwrap();
+
+        assert_ne!(first, second);
+        assert_eq!(image::open(&first).unwrap().width(), 40);
+        assert_eq!(image::open(&second).unwrap().width(), 64);
+        assert_eq!(generator.usage().files, 2);
+    }
+
+    #[test]
+    fn test_changed_source_replaces_thumbnail() {
+        let (generator, temp_dir) = create_test_thumbnail_generator();
+        let path = temp_dir.path().join("Snipp.png");
+        DynamicImage::new_rgb8(40, 20).save(&path).unwrap();
+        let path = path.to_string_lossy().to_string();
+        let old = generator.generate_thumbnail(&path, 64).unwrap();
+
+        let file = fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(5)).unwrap();
+        let new = generator.generate_thumbnail(&path, 64).unwrap();
+
+        assert_ne!(old, new);
+        assert_eq!(generator.thumbnails_of(&path), vec![PathBuf::from(&new)]);
+
+        let freed = generator.purge().unwrap();
+        assert_eq!(freed.files, 1);
+        assert!(freed.bytes > 0);
+        assert_eq!(generator.usage(), CacheUsage::default());
+    }
+}
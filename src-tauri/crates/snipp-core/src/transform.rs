@@ -0,0 +1,110 @@
+use serde::{Deserialize, Serialize};
+
+use crate::color;
+
+/// A rectangle of image pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PixelRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FlipAxis {
+    /// Mirror left to right.
+    Horizontal,
+    /// Mirror top to bottom.
+    Vertical,
+}
+
+fn decode(png: &[u8]) -> Result<image::DynamicImage, String> {
+    image::load_from_memory(png).map_err(|e| format!("Failed to decode image: {}", e))
+}
+
+/// Re-encodes a transformed image with the color profile of `source`.
+fn encode(image: image::DynamicImage, source: &[u8]) -> Result<Vec<u8>, String> {
+    let mut png = Vec::new();
+    image
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageOutputFormat::Png)
+        .map_err(|e| format!("Failed to encode image: {}", e))?;
+    let icc = color::icc_profile(source);
+    color::apply_profile(png, icc.as_deref(), color::ColorProfileMode::Preserve)
+}
+
+/// Cuts `rect` out of a PNG; parts of it outside the image are dropped.
+pub fn crop(png: &[u8], rect: PixelRect) -> Result<Vec<u8>, String> {
+    let image = decode(png)?;
+    let x = rect.x.min(image.width());
+    let y = rect.y.min(image.height());
+    let width = rect.width.min(image.width() - x);
+    let height = rect.height.min(image.height() - y);
+    if width == 0 || height == 0 {
+        return Err("The crop doesn't overlap the image".to_string());
+    }
+    encode(image.crop_imm(x, y, width, height), png)
+}
+
+/// Rotates a PNG clockwise by a multiple of 90 degrees; negative turns
+/// counter-clockwise.
+pub fn rotate(png: &[u8], degrees: i32) -> Result<Vec<u8>, String> {
+    if degrees % 90 != 0 {
+        return Err(format!("Captures can only be rotated in quarter turns, not {} degrees", degrees));
+    }
+    let image = decode(png)?;
+    let rotated = match degrees.rem_euclid(360) {
+        90 => image.rotate90(),
+        180 => image.rotate180(),
+        270 => image.rotate270(),
+        _ => image,
+    };
+    encode(rotated, png)
+}
+
+pub fn flip(png: &[u8], axis: FlipAxis) -> Result<Vec<u8>, String> {
+    let image = decode(png)?;
+    let flipped = match axis {
+        FlipAxis::Horizontal => image.fliph(),
+        FlipAxis::Vertical => image.flipv(),
+    };
+    encode(flipped, png)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 3x2 with a red top-left pixel on white.
+    fn sample_png() -> Vec<u8> {
+        let mut image = image::RgbaImage::from_pixel(3, 2, image::Rgba([255, 255, 255, 255]));
+        image.put_pixel(0, 0, image::Rgba([255, 0, 0, 255]));
+        let mut png = Vec::new();
+        image::DynamicImage::ImageRgba8(image)
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageOutputFormat::Png)
+            .unwrap();
+        png
+    }
+
+    fn red_at(png: &[u8]) -> (u32, u32, (u32, u32)) {
+        let image = image::load_from_memory(png).unwrap().to_rgba8();
+        let (x, y, _) = image.enumerate_pixels().find(|(_, _, pixel)| pixel.0 == [255, 0, 0, 255]).unwrap();
+        (x, y, image.dimensions())
+    }
+
+    #[test]
+    fn test_transforms() {
+        let png = sample_png();
+
+        assert_eq!(red_at(&rotate(&png, 90).unwrap()), (1, 0, (2, 3)));
+        assert_eq!(red_at(&rotate(&png, -90).unwrap()), (0, 2, (2, 3)));
+        assert_eq!(red_at(&rotate(&png, 360).unwrap()), (0, 0, (3, 2)));
+        assert!(rotate(&png, 45).is_err());
+        assert_eq!(red_at(&flip(&png, FlipAxis::Horizontal).unwrap()), (2, 0, (3, 2)));
+        assert_eq!(red_at(&flip(&png, FlipAxis::Vertical).unwrap()), (0, 1, (3, 2)));
+        let cropped = crop(&png, PixelRect { x: 0, y: 0, width: 2, height: 10 }).unwrap();
+        assert_eq!(red_at(&cropped), (0, 0, (2, 2)));
+        assert!(crop(&png, PixelRect { x: 3, y: 0, width: 2, height: 2 }).is_err());
+    }
+}
@@ -0,0 +1,147 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// File types picked up from a watched folder or opened with Snipp.
+pub const IMAGE_EXTENSIONS: [&str; 4] = ["png", "jpg", "jpeg", "tiff"];
+
+/// Importing screenshots taken outside Snipp.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct WatchFolder {
+    pub enabled: bool,
+    /// Folder to watch; empty uses the folder macOS saves screenshots to.
+    pub folder: String,
+}
+
+/// Finds images that appear in a folder after watching started. A file is
+/// reported once its size is the same on two scans in a row, so images
+/// still being written are left for the next scan.
+#[derive(Debug)]
+pub struct FolderScanner {
+    folder: PathBuf,
+    since: SystemTime,
+    seen: HashSet<PathBuf>,
+    pending: HashMap<PathBuf, u64>,
+}
+
+impl FolderScanner {
+    pub fn new(folder: PathBuf, since: SystemTime) -> Self {
+        Self {
+            folder,
+            since,
+            seen: HashSet::new(),
+            pending: HashMap::new(),
+        }
+    }
+
+    pub fn folder(&self) -> &Path {
+        &self.folder
+    }
+
+    pub fn scan(&mut self) -> Vec<PathBuf> {
+        let Ok(entries) = std::fs::read_dir(&self.folder) else {
+            return Vec::new();
+        };
+        let mut ready = Vec::new();
+        let mut still_pending = HashMap::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if self.seen.contains(&path) || !is_image(&path) {
+                continue;
+            }
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            if !metadata.is_file() || modified < self.since {
+                continue;
+            }
+            let size = metadata.len();
+            if size > 0 && self.pending.get(&path) == Some(&size) {
+                self.seen.insert(path.clone());
+                ready.push(path);
+            } else {
+                still_pending.insert(path, size);
+            }
+        }
+        self.pending = still_pending;
+        ready.sort();
+        ready
+    }
+}
+
+/// Visible image files; macOS writes a hidden `.Screenshot ...` file first
+/// and renames it when done.
+fn is_image(path: &Path) -> bool {
+    let visible = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| !name.starts_with('.'));
+    visible && has_image_extension(path)
+}
+
+pub fn has_image_extension(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| IMAGE_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+/// The image as a PNG, for the capture cache. PNGs are passed through so
+/// their color profile survives; other formats are decoded and re-encoded.
+pub fn to_png(data: Vec<u8>) -> Result<Vec<u8>, String> {
+    if data.starts_with(&crate::color::PNG_SIGNATURE) {
+        return Ok(data);
+    }
+    let image = image::load_from_memory(&data).map_err(|e| format!("Not a supported image: {}", e))?;
+    let mut png = Vec::new();
+    image
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageOutputFormat::Png)
+        .map_err(|e| format!("Failed to encode image: {}", e))?;
+    Ok(png)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_scan_reports_new_images_once_they_settle() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("old.png"), b"old").unwrap();
+        let since = SystemTime::now() + std::time::Duration::from_millis(10);
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        let mut scanner = FolderScanner::new(dir.path().to_path_buf(), since);
+
+        let shot = dir.path().join("Screenshot 2024-05-01 at 10.00.00.png");
+        std::fs::write(&shot, b"part").unwrap();
+        std::fs::write(dir.path().join(".Screenshot 2024-05-01 at 10.00.01.png"), b"tmp").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), b"text").unwrap();
+        assert!(scanner.scan().is_empty());
+
+        std::fs::write(&shot, b"partial png").unwrap();
+        assert!(scanner.scan().is_empty());
+
+        assert_eq!(scanner.scan(), vec![shot]);
+        assert!(scanner.scan().is_empty());
+    }
+
+    #[test]
+    fn test_to_png() {
+        let image = image::DynamicImage::ImageRgb8(image::RgbImage::from_pixel(6, 4, image::Rgb([10, 20, 30])));
+        let mut jpeg = Vec::new();
+        image
+            .write_to(&mut std::io::Cursor::new(&mut jpeg), image::ImageOutputFormat::Jpeg(90))
+            .unwrap();
+
+        let png = to_png(jpeg).unwrap();
+        assert!(png.starts_with(&crate::color::PNG_SIGNATURE));
+        assert_eq!(image::load_from_memory(&png).unwrap().width(), 6);
+        assert_eq!(to_png(png.clone()).unwrap(), png);
+        assert!(to_png(b"not an image".to_vec()).is_err());
+        assert!(has_image_extension(Path::new("/tmp/Shot.JPG")));
+        assert!(!has_image_extension(Path::new("/tmp/notes.txt")));
+    }
+}
@@ -0,0 +1,134 @@
+use tauri::AppHandle;
+
+/// What a capture announcement says, e.g. "Screenshot captured, copied to clipboard".
+pub fn capture_message(saved: bool, copied: bool) -> String {
+    let mut message = if saved { "Screenshot saved" } else { "Screenshot captured" }.to_string();
+    if copied {
+        message.push_str(", copied to clipboard");
+    }
+    message
+}
+
+#[cfg(target_os = "macos")]
+mod ffi {
+    use std::ffi::{c_char, c_void};
+
+    pub type Id = *mut c_void;
+
+    #[repr(C)]
+    pub struct CallBacks {
+        _private: [u8; 0],
+    }
+
+    pub const UTF8_ENCODING: u32 = 0x0800_0100;
+    pub const SINT64_NUMBER: isize = 4;
+    /// `NSAccessibilityPriorityHigh`: interrupts other speech.
+    pub const PRIORITY_HIGH: i64 = 90;
+
+    #[link(name = "AppKit", kind = "framework")]
+    extern "C" {
+        pub static NSAccessibilityAnnouncementRequestedNotification: Id;
+        pub static NSAccessibilityAnnouncementKey: Id;
+        pub static NSAccessibilityPriorityKey: Id;
+        pub fn NSAccessibilityPostNotificationWithUserInfo(element: Id, notification: Id, user_info: Id);
+    }
+
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        pub static kCFTypeDictionaryKeyCallBacks: CallBacks;
+        pub static kCFTypeDictionaryValueCallBacks: CallBacks;
+        pub fn CFStringCreateWithBytes(
+            allocator: Id,
+            bytes: *const u8,
+            len: isize,
+            encoding: u32,
+            external: bool,
+        ) -> Id;
+        pub fn CFNumberCreate(allocator: Id, kind: isize, value: *const c_void) -> Id;
+        pub fn CFDictionaryCreate(
+            allocator: Id,
+            keys: *const Id,
+            values: *const Id,
+            count: isize,
+            key_callbacks: *const CallBacks,
+            value_callbacks: *const CallBacks,
+        ) -> Id;
+        pub fn CFRelease(object: Id);
+    }
+
+    #[link(name = "objc")]
+    extern "C" {
+        pub fn objc_getClass(name: *const c_char) -> Id;
+        pub fn sel_registerName(name: *const c_char) -> Id;
+        pub fn objc_msgSend();
+    }
+}
+
+/// Has VoiceOver (or another screen reader) speak `message` without moving
+/// focus. Silent when no screen reader is running; a no-op off macOS.
+#[cfg(target_os = "macos")]
+pub fn announce(app: &AppHandle, message: &str) {
+    let message = message.to_string();
+    let posted = app.run_on_main_thread(move || {
+        // SAFETY: AppKit and CoreFoundation calls on the main thread; every
+        // object created here is released before returning.
+        unsafe {
+            let send: extern "C" fn(ffi::Id, ffi::Id) -> ffi::Id =
+                std::mem::transmute(ffi::objc_msgSend as unsafe extern "C" fn());
+            let application = send(
+                ffi::objc_getClass(b"NSApplication\0".as_ptr().cast()),
+                ffi::sel_registerName(b"sharedApplication\0".as_ptr().cast()),
+            );
+            if application.is_null() {
+                return;
+            }
+            let text = ffi::CFStringCreateWithBytes(
+                std::ptr::null_mut(),
+                message.as_ptr(),
+                message.len() as isize,
+                ffi::UTF8_ENCODING,
+                false,
+            );
+            let priority = ffi::CFNumberCreate(
+                std::ptr::null_mut(),
+                ffi::SINT64_NUMBER,
+                (&ffi::PRIORITY_HIGH as *const i64).cast(),
+            );
+            let keys = [ffi::NSAccessibilityAnnouncementKey, ffi::NSAccessibilityPriorityKey];
+            let values = [text, priority];
+            let user_info = ffi::CFDictionaryCreate(
+                std::ptr::null_mut(),
+                keys.as_ptr(),
+                values.as_ptr(),
+                2,
+                &ffi::kCFTypeDictionaryKeyCallBacks,
+                &ffi::kCFTypeDictionaryValueCallBacks,
+            );
+            ffi::NSAccessibilityPostNotificationWithUserInfo(
+                application,
+                ffi::NSAccessibilityAnnouncementRequestedNotification,
+                user_info,
+            );
+            ffi::CFRelease(user_info);
+            ffi::CFRelease(priority);
+            ffi::CFRelease(text);
+        }
+    });
+    if let Err(e) = posted {
+        tracing::error!("Failed to post accessibility announcement: {}", e);
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn announce(_app: &AppHandle, _message: &str) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture_message() {
+        assert_eq!(capture_message(false, true), "Screenshot captured, copied to clipboard");
+        assert_eq!(capture_message(true, false), "Screenshot saved");
+    }
+}
@@ -0,0 +1,60 @@
+use image::imageops::FilterType;
+use std::path::{Path, PathBuf};
+
+/// The `name.png` / `name@2x.png` pair for a path the user picked. A chosen
+/// name that already ends in "@2x" is treated as the base name.
+pub fn asset_pair_paths(chosen: &Path) -> (PathBuf, PathBuf) {
+    let stem = chosen
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("Snipp");
+    let stem = stem.strip_suffix("@2x").unwrap_or(stem);
+    let parent = chosen.parent().map(Path::to_path_buf).unwrap_or_default();
+    (
+        parent.join(format!("{}.png", stem)),
+        parent.join(format!("{}@2x.png", stem)),
+    )
+}
+
+/// Halves a @2x PNG with a Lanczos filter for the @1x variant. Odd sizes
+/// round up so no edge pixels are lost.
+pub fn downscale_half(png: &[u8]) -> Result<Vec<u8>, String> {
+    let image = image::load_from_memory(png).map_err(|e| format!("Failed to decode image: {}", e))?;
+    let width = image.width().div_ceil(2).max(1);
+    let height = image.height().div_ceil(2).max(1);
+    let resized = image.resize_exact(width, height, FilterType::Lanczos3);
+
+    let mut bytes = Vec::new();
+    resized
+        .write_to(&mut std::io::Cursor::new(&mut bytes), image::ImageOutputFormat::Png)
+        .map_err(|e| format!("Failed to encode image: {}", e))?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_asset_pair_paths() {
+        let (one, two) = asset_pair_paths(Path::new("/docs/img/button.png"));
+        assert_eq!(one, PathBuf::from("/docs/img/button.png"));
+        assert_eq!(two, PathBuf::from("/docs/img/button@2x.png"));
+
+        let (one, two) = asset_pair_paths(Path::new("/docs/img/button@2x.png"));
+        assert_eq!(one, PathBuf::from("/docs/img/button.png"));
+        assert_eq!(two, PathBuf::from("/docs/img/button@2x.png"));
+    }
+
+    #[test]
+    fn test_downscale_half_rounds_up() {
+        let mut png = Vec::new();
+        image::DynamicImage::ImageRgba8(image::RgbaImage::new(101, 40))
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageOutputFormat::Png)
+            .unwrap();
+
+        let half = image::load_from_memory(&downscale_half(&png).unwrap()).unwrap();
+
+        assert_eq!((half.width(), half.height()), (51, 20));
+    }
+}
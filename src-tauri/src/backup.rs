@@ -0,0 +1,427 @@
+use serde::{Deserialize, Serialize};
+use snipp_core::resolve_unique_path;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+const MANIFEST_NAME: &str = "manifest.json";
+/// 2 stores screenshots under numbered folders, listed in the manifest.
+const BACKUP_VERSION: u32 = 2;
+const CONFIG_PREFIX: &str = "config/";
+const THUMBNAILS_PREFIX: &str = "thumbnails/";
+const SCREENSHOTS_PREFIX: &str = "screenshots/";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub version: u32,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub includes_screenshots: bool,
+    /// Which saved file each screenshot entry came from. Older backups
+    /// named entries after the file alone and have no list.
+    #[serde(default)]
+    pub screenshots: Vec<BackedUpScreenshot>,
+}
+
+/// A saved screenshot in the archive, stored as `screenshots/<n>/<name>`
+/// so files with the same name from different folders don't collide.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackedUpScreenshot {
+    pub entry: String,
+    pub original_path: String,
+}
+
+/// What went into an archive, or came back out of one.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BackupSummary {
+    pub path: String,
+    pub config_files: usize,
+    pub thumbnails: usize,
+    pub screenshots: usize,
+}
+
+/// Where Snipp keeps the data a backup covers.
+pub struct DataDirs {
+    /// Config, history, baselines and anything else under the config folder.
+    pub config_dir: PathBuf,
+    pub thumbnail_dir: PathBuf,
+}
+
+impl DataDirs {
+    pub fn locate() -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Self {
+            config_dir: dirs::config_dir()
+                .ok_or("Failed to get config directory")?
+                .join("snipp"),
+            thumbnail_dir: dirs::cache_dir()
+                .ok_or("Failed to get cache directory")?
+                .join("snipp")
+                .join("thumbnails"),
+        })
+    }
+}
+
+/// Files below `dir`, relative to it, with `/` separators. A missing
+/// directory has no files.
+fn relative_files(dir: &Path) -> Vec<(PathBuf, String)> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&current) else {
+            continue;
+        };
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if let Ok(relative) = path.strip_prefix(dir) {
+                let name = relative
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                files.push((path.clone(), name));
+            }
+        }
+    }
+    files.sort_by(|a, b| a.1.cmp(&b.1));
+    files
+}
+
+fn add_file(
+    zip: &mut ZipWriter<File>,
+    source: &Path,
+    name: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    // Images are already compressed; deflating them again only costs time.
+    let method = if name.ends_with(".json") {
+        CompressionMethod::Deflated
+    } else {
+        CompressionMethod::Stored
+    };
+    zip.start_file(name, SimpleFileOptions::default().compression_method(method))?;
+    zip.write_all(&std::fs::read(source)?)?;
+    Ok(())
+}
+
+/// Writes config, history and thumbnails (and optionally the given saved
+/// screenshots) into a zip archive at `archive_path`.
+pub fn create_backup(
+    dirs: &DataDirs,
+    archive_path: &Path,
+    screenshots: &[PathBuf],
+) -> Result<BackupSummary, Box<dyn std::error::Error>> {
+    let mut zip = ZipWriter::new(File::create(archive_path)?);
+    let mut summary = BackupSummary {
+        path: archive_path.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+
+    let mut backed_up: Vec<BackedUpScreenshot> = Vec::new();
+    for path in screenshots.iter().filter(|path| path.is_file()) {
+        let original_path = path.to_string_lossy().to_string();
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if backed_up.iter().any(|screenshot| screenshot.original_path == original_path) {
+            continue;
+        }
+        let entry = format!("{}{}/{}", SCREENSHOTS_PREFIX, backed_up.len(), name);
+        backed_up.push(BackedUpScreenshot { entry, original_path });
+    }
+
+    let manifest = BackupManifest {
+        version: BACKUP_VERSION,
+        created_at: chrono::Utc::now(),
+        includes_screenshots: !backed_up.is_empty(),
+        screenshots: backed_up,
+    };
+    zip.start_file(MANIFEST_NAME, SimpleFileOptions::default())?;
+    zip.write_all(serde_json::to_string_pretty(&manifest)?.as_bytes())?;
+
+    for (path, name) in relative_files(&dirs.config_dir) {
+        add_file(&mut zip, &path, &format!("{}{}", CONFIG_PREFIX, name))?;
+        summary.config_files += 1;
+    }
+    for (path, name) in relative_files(&dirs.thumbnail_dir) {
+        add_file(&mut zip, &path, &format!("{}{}", THUMBNAILS_PREFIX, name))?;
+        summary.thumbnails += 1;
+    }
+    for screenshot in &manifest.screenshots {
+        add_file(&mut zip, Path::new(&screenshot.original_path), &screenshot.entry)?;
+        summary.screenshots += 1;
+    }
+
+    zip.finish()?;
+    Ok(summary)
+}
+
+/// Unpacks an archive made by `create_backup`, overwriting the current
+/// config and history. Screenshots go into `screenshots_dir`, numbered
+/// rather than overwriting files already there, and history entries are
+/// repointed at them; every other history field is kept as is.
+pub fn restore_backup(
+    dirs: &DataDirs,
+    archive_path: &Path,
+    screenshots_dir: &Path,
+) -> Result<BackupSummary, Box<dyn std::error::Error>> {
+    let mut archive = ZipArchive::new(File::open(archive_path)?)?;
+
+    let manifest: BackupManifest = {
+        let mut contents = String::new();
+        archive
+            .by_name(MANIFEST_NAME)
+            .map_err(|_| "Not a Snipp backup: manifest is missing")?
+            .read_to_string(&mut contents)?;
+        serde_json::from_str(&contents)?
+    };
+    if manifest.version > BACKUP_VERSION {
+        return Err(format!("Backup version {} is newer than this Snipp supports", manifest.version).into());
+    }
+
+    let mut summary = BackupSummary {
+        path: archive_path.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+    // Entry name to the path its screenshot was restored at.
+    let mut restored_screenshots: HashMap<String, PathBuf> = HashMap::new();
+
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        if entry.is_dir() {
+            continue;
+        }
+        // enclosed_name rejects absolute paths and `..`, so entries can't
+        // escape the target folders.
+        let Some(name) = entry.enclosed_name() else {
+            continue;
+        };
+        let name = name.to_string_lossy().replace('\\', "/");
+
+        let target = if let Some(rest) = name.strip_prefix(CONFIG_PREFIX) {
+            summary.config_files += 1;
+            dirs.config_dir.join(rest)
+        } else if let Some(rest) = name.strip_prefix(THUMBNAILS_PREFIX) {
+            summary.thumbnails += 1;
+            dirs.thumbnail_dir.join(rest)
+        } else if name.starts_with(SCREENSHOTS_PREFIX) {
+            let Some(file_name) = Path::new(&name).file_name() else {
+                continue;
+            };
+            summary.screenshots += 1;
+            let target = resolve_unique_path(screenshots_dir.join(file_name));
+            restored_screenshots.insert(name.clone(), target.clone());
+            target
+        } else {
+            continue;
+        };
+
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut contents = Vec::new();
+        entry.read_to_end(&mut contents)?;
+        std::fs::write(&target, contents)?;
+    }
+
+    let history_path = dirs.config_dir.join("history.json");
+    if !restored_screenshots.is_empty() && history_path.exists() {
+        let history = std::fs::read_to_string(&history_path)?;
+        let history = repoint_history(&history, &manifest, &restored_screenshots)?;
+        std::fs::write(&history_path, history)?;
+    }
+
+    Ok(summary)
+}
+
+/// Rewrites `file_path` (and `filename`, if it was numbered) of history
+/// entries whose file was restored. `restored` maps archive entries to where
+/// they were written. Works on raw JSON so fields this version doesn't know
+/// about survive the trip.
+pub fn repoint_history(
+    history_json: &str,
+    manifest: &BackupManifest,
+    restored: &HashMap<String, PathBuf>,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let mut history: serde_json::Value = serde_json::from_str(history_json)?;
+    if let Some(entries) = history
+        .get_mut("screenshots")
+        .and_then(|screenshots| screenshots.as_array_mut())
+    {
+        for entry in entries {
+            let archive_entry = if manifest.screenshots.is_empty() {
+                // Older backups: entries are named after the file alone.
+                entry
+                    .get("filename")
+                    .and_then(|f| f.as_str())
+                    .map(|filename| format!("{}{}", SCREENSHOTS_PREFIX, filename))
+            } else {
+                entry.get("file_path").and_then(|f| f.as_str()).and_then(|file_path| {
+                    manifest
+                        .screenshots
+                        .iter()
+                        .find(|screenshot| screenshot.original_path == file_path)
+                        .map(|screenshot| screenshot.entry.clone())
+                })
+            };
+            let Some(new_path) = archive_entry.and_then(|name| restored.get(&name)) else {
+                continue;
+            };
+            if let Some(filename) = new_path.file_name() {
+                entry["filename"] = serde_json::Value::String(filename.to_string_lossy().to_string());
+            }
+            entry["file_path"] = serde_json::Value::String(new_path.to_string_lossy().to_string());
+            // Thumbnail paths point into the old machine's cache.
+            entry["thumbnail_path"] = serde_json::Value::Null;
+        }
+    }
+    Ok(serde_json::to_string_pretty(&history)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn data_dirs(root: &Path) -> DataDirs {
+        DataDirs {
+            config_dir: root.join("config"),
+            thumbnail_dir: root.join("thumbs"),
+        }
+    }
+
+    #[test]
+    fn test_backup_round_trip_repoints_screenshots() {
+        let old = TempDir::new().unwrap();
+        let old_dirs = data_dirs(old.path());
+        std::fs::create_dir_all(old_dirs.config_dir.join("baselines")).unwrap();
+        std::fs::create_dir_all(&old_dirs.thumbnail_dir).unwrap();
+        std::fs::write(old_dirs.config_dir.join("config.json"), "{}").unwrap();
+        std::fs::write(old_dirs.config_dir.join("baselines/header.png"), "b").unwrap();
+        std::fs::write(old_dirs.thumbnail_dir.join("thumb_64_a.png.jpg"), "t").unwrap();
+        let shot = old.path().join("a.png");
+        std::fs::write(&shot, "png").unwrap();
+        std::fs::write(
+            old_dirs.config_dir.join("history.json"),
+            format!(
+                r#"{{"screenshots":[{{"file_path":"{}","filename":"a.png","favorite":true,"thumbnail_path":"/old/t.jpg"}}]}}"#,
+                shot.display()
+            ),
+        )
+        .unwrap();
+
+        let archive = old.path().join("backup.zip");
+        let created = create_backup(&old_dirs, &archive, &[shot]).unwrap();
+        assert_eq!((created.config_files, created.thumbnails, created.screenshots), (3, 1, 1));
+
+        let new = TempDir::new().unwrap();
+        let new_dirs = data_dirs(new.path());
+        let shots_dir = new.path().join("Desktop");
+        let restored = restore_backup(&new_dirs, &archive, &shots_dir).unwrap();
+
+        assert_eq!((restored.config_files, restored.thumbnails, restored.screenshots), (3, 1, 1));
+        assert!(new_dirs.config_dir.join("baselines/header.png").exists());
+        assert_eq!(std::fs::read_to_string(shots_dir.join("a.png")).unwrap(), "png");
+
+        let history: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(new_dirs.config_dir.join("history.json")).unwrap())
+                .unwrap();
+        let entry = &history["screenshots"][0];
+        assert_eq!(entry["file_path"], shots_dir.join("a.png").to_string_lossy().as_ref());
+        assert_eq!(entry["favorite"], true);
+        assert!(entry["thumbnail_path"].is_null());
+    }
+
+    fn write_history(dirs: &DataDirs, shots: &[&Path]) {
+        let entries: Vec<serde_json::Value> = shots
+            .iter()
+            .map(|shot| {
+                serde_json::json!({
+                    "file_path": shot.to_string_lossy(),
+                    "filename": shot.file_name().unwrap().to_string_lossy(),
+                })
+            })
+            .collect();
+        std::fs::create_dir_all(&dirs.config_dir).unwrap();
+        std::fs::write(
+            dirs.config_dir.join("history.json"),
+            serde_json::json!({ "screenshots": entries }).to_string(),
+        )
+        .unwrap();
+    }
+
+    fn restored_paths(dirs: &DataDirs) -> Vec<String> {
+        let history: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(dirs.config_dir.join("history.json")).unwrap()).unwrap();
+        history["screenshots"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entry| entry["file_path"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_backup_keeps_same_named_screenshots_apart() {
+        let old = TempDir::new().unwrap();
+        let old_dirs = data_dirs(old.path());
+        let first = old.path().join("Desktop/a.png");
+        let second = old.path().join("Pictures/a.png");
+        for (shot, contents) in [(&first, "one"), (&second, "two")] {
+            std::fs::create_dir_all(shot.parent().unwrap()).unwrap();
+            std::fs::write(shot, contents).unwrap();
+        }
+        write_history(&old_dirs, &[&first, &second]);
+
+        let archive = old.path().join("backup.zip");
+        let created = create_backup(&old_dirs, &archive, &[first, second]).unwrap();
+        assert_eq!(created.screenshots, 2);
+
+        let new = TempDir::new().unwrap();
+        let new_dirs = data_dirs(new.path());
+        let shots_dir = new.path().join("Desktop");
+        let restored = restore_backup(&new_dirs, &archive, &shots_dir).unwrap();
+
+        assert_eq!(restored.screenshots, 2);
+        let paths = restored_paths(&new_dirs);
+        assert_eq!(std::fs::read_to_string(&paths[0]).unwrap(), "one");
+        assert_eq!(std::fs::read_to_string(&paths[1]).unwrap(), "two");
+        assert_ne!(paths[0], paths[1]);
+    }
+
+    #[test]
+    fn test_restore_does_not_overwrite_existing_screenshots() {
+        let old = TempDir::new().unwrap();
+        let old_dirs = data_dirs(old.path());
+        let shot = old.path().join("a.png");
+        std::fs::write(&shot, "backed up").unwrap();
+        write_history(&old_dirs, &[&shot]);
+        let archive = old.path().join("backup.zip");
+        create_backup(&old_dirs, &archive, &[shot]).unwrap();
+
+        let new = TempDir::new().unwrap();
+        let new_dirs = data_dirs(new.path());
+        let shots_dir = new.path().join("Desktop");
+        std::fs::create_dir_all(&shots_dir).unwrap();
+        std::fs::write(shots_dir.join("a.png"), "already here").unwrap();
+        restore_backup(&new_dirs, &archive, &shots_dir).unwrap();
+
+        assert_eq!(std::fs::read_to_string(shots_dir.join("a.png")).unwrap(), "already here");
+        assert_eq!(std::fs::read_to_string(shots_dir.join("a-1.png")).unwrap(), "backed up");
+        assert_eq!(restored_paths(&new_dirs), [shots_dir.join("a-1.png").to_string_lossy()]);
+    }
+
+    #[test]
+    fn test_restore_rejects_archive_without_manifest() {
+        let dir = TempDir::new().unwrap();
+        let archive = dir.path().join("other.zip");
+        let mut zip = ZipWriter::new(File::create(&archive).unwrap());
+        zip.start_file("readme.txt", SimpleFileOptions::default()).unwrap();
+        zip.write_all(b"hi").unwrap();
+        zip.finish().unwrap();
+
+        assert!(restore_backup(&data_dirs(dir.path()), &archive, dir.path()).is_err());
+    }
+}
@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use tauri_plugin_shell::ShellExt;
+
+/// JXA bridge to Vision's `VNDetectBarcodesRequest`; takes an image path and
+/// prints every decoded code as JSON. Boxes are normalized with the origin at
+/// the bottom-left, like text recognition's.
+const BARCODE_SCRIPT: &str = "ObjC.import('Vision'); \
+function run(argv) { \
+const url = $.NSURL.fileURLWithPath(argv[0]); \
+const handler = $.VNImageRequestHandler.alloc.initWithURLOptions(url, $({})); \
+const request = $.VNDetectBarcodesRequest.alloc.init; \
+if (!handler.performRequestsError($([request]), null)) { throw new Error('Code detection failed'); } \
+const results = request.results; \
+const codes = []; \
+for (let i = 0; i < results.count; i++) { \
+const observation = results.objectAtIndex(i); \
+if (observation.payloadStringValue.isNil()) continue; \
+const box = observation.boundingBox; \
+codes.push({ payload: observation.payloadStringValue.js, symbology: observation.symbology.js, \
+x: box.origin.x, y: box.origin.y, width: box.size.width, height: box.size.height }); \
+} \
+return JSON.stringify(codes); }";
+
+/// A decoded QR code or barcode with its box as fractions of the image,
+/// origin top-left.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DetectedCode {
+    pub payload: String,
+    /// e.g. "QR", "EAN13", "Code128".
+    pub symbology: String,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    /// Whether `payload` is a web link the popup can offer to copy.
+    #[serde(default)]
+    pub is_url: bool,
+}
+
+/// Flips Vision's boxes to top-left, drops repeats of the same payload and
+/// orders codes top to bottom.
+pub fn parse_barcode_output(json: &str) -> Result<Vec<DetectedCode>, String> {
+    let detected: Vec<DetectedCode> = serde_json::from_str(json.trim())
+        .map_err(|e| format!("Failed to parse code detection output: {}", e))?;
+
+    let mut codes: Vec<DetectedCode> = Vec::new();
+    for mut code in detected {
+        if codes.iter().any(|seen| seen.payload == code.payload) {
+            continue;
+        }
+        code.y = 1.0 - code.y - code.height;
+        code.symbology = code.symbology.trim_start_matches("VNBarcodeSymbology").to_string();
+        let payload = code.payload.trim().to_ascii_lowercase();
+        code.is_url = payload.starts_with("https://") || payload.starts_with("http://");
+        codes.push(code);
+    }
+    codes.sort_by(|a, b| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+    Ok(codes)
+}
+
+/// Runs Vision barcode detection over a PNG.
+pub async fn detect_codes(app_handle: &AppHandle, png: &[u8]) -> Result<Vec<DetectedCode>, String> {
+    let temp_path = std::env::temp_dir().join(format!(
+        "snipp_codes_{}_{}.png",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default()
+    ));
+    std::fs::write(&temp_path, png).map_err(|e| format!("Failed to stage image for code detection: {}", e))?;
+
+    let output = app_handle
+        .shell()
+        .command("osascript")
+        .args([
+            "-l",
+            "JavaScript",
+            "-e",
+            BARCODE_SCRIPT,
+            temp_path.to_string_lossy().as_ref(),
+        ])
+        .output()
+        .await;
+    let _ = std::fs::remove_file(&temp_path);
+    let output = output.map_err(|e| format!("Failed to run osascript: {}", e))?;
+
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Code detection failed: {}", error.trim()));
+    }
+
+    parse_barcode_output(&String::from_utf8_lossy(&output.stdout))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_barcode_output() {
+        let json = r#"[
+            {"payload": "4006381333931", "symbology": "VNBarcodeSymbologyEAN13", "x": 0.1, "y": 0.1, "width": 0.3, "height": 0.1},
+            {"payload": "HTTPS://example.com/a", "symbology": "VNBarcodeSymbologyQR", "x": 0.5, "y": 0.6, "width": 0.2, "height": 0.2},
+            {"payload": "HTTPS://example.com/a", "symbology": "VNBarcodeSymbologyQR", "x": 0.5, "y": 0.6, "width": 0.2, "height": 0.2}
+        ]"#;
+
+        let codes = parse_barcode_output(json).unwrap();
+
+        assert_eq!(codes.len(), 2);
+        assert_eq!(codes[0].symbology, "QR");
+        assert!(codes[0].is_url);
+        assert!((codes[0].y - 0.2).abs() < 1e-9);
+        assert_eq!(codes[1].symbology, "EAN13");
+        assert!(!codes[1].is_url);
+        assert!(parse_barcode_output("[]\n").unwrap().is_empty());
+        assert!(parse_barcode_output("oops").is_err());
+    }
+}
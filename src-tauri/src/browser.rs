@@ -0,0 +1,230 @@
+use serde::Deserialize;
+use tauri::AppHandle;
+use tauri_plugin_shell::ShellExt;
+
+/// Pages taller than this (in CSS pixels) are cut off, which keeps
+/// infinite-scroll feeds from producing a gigantic image.
+pub const MAX_PAGE_HEIGHT: u32 = 20_000;
+
+/// Reports the page size, scroll offset and where the viewport sits on
+/// screen, in points.
+const METRICS_JS: &str = "JSON.stringify({\
+scroll_height: Math.max(document.documentElement.scrollHeight, document.body ? document.body.scrollHeight : 0),\
+viewport_width: window.innerWidth,\
+viewport_height: window.innerHeight,\
+scroll_y: window.scrollY,\
+screen_x: window.screenX + (window.outerWidth - window.innerWidth) / 2,\
+screen_y: window.screenY + window.outerHeight - window.innerHeight})";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Browser {
+    Safari,
+    Chrome,
+}
+
+impl Browser {
+    pub fn from_app_name(name: &str) -> Option<Self> {
+        match name.trim() {
+            "Safari" => Some(Browser::Safari),
+            "Google Chrome" => Some(Browser::Chrome),
+            _ => None,
+        }
+    }
+
+    /// AppleScript that runs `js` in the front tab. Chrome refuses unless
+    /// View > Developer > Allow JavaScript from Apple Events is on.
+    fn script_for(self, js: &str) -> String {
+        let js = applescript_string(js);
+        match self {
+            Browser::Safari => format!(
+                "tell application \"Safari\" to do JavaScript {} in current tab of front window",
+                js
+            ),
+            Browser::Chrome => format!(
+                "tell application \"Google Chrome\" to execute active tab of front window javascript {}",
+                js
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct PageMetrics {
+    pub scroll_height: f64,
+    pub viewport_width: f64,
+    pub viewport_height: f64,
+    pub scroll_y: f64,
+    pub screen_x: f64,
+    pub screen_y: f64,
+}
+
+/// Quotes `text` as an AppleScript string literal.
+pub fn applescript_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Offsets to scroll to so consecutive viewports cover the page; the last
+/// one is pulled back so it ends exactly at the bottom.
+pub fn scroll_positions(page_height: u32, viewport_height: u32) -> Vec<u32> {
+    if viewport_height == 0 || page_height <= viewport_height {
+        return vec![0];
+    }
+    let last = page_height - viewport_height;
+    let mut positions: Vec<u32> = (0..last).step_by(viewport_height as usize).collect();
+    positions.push(last);
+    positions
+}
+
+/// Stacks viewport frames (with the scroll offset each was taken at) into
+/// one page image. The frames' width against `viewport_width` gives the
+/// display scale, so Retina and non-Retina screens both line up.
+pub fn stitch_frames(frames: &[(u32, Vec<u8>)], page_height: u32, viewport_width: u32) -> Result<Vec<u8>, String> {
+    let decoded = frames
+        .iter()
+        .map(|(offset, png)| {
+            image::load_from_memory(png)
+                .map(|image| (*offset, image.to_rgba8()))
+                .map_err(|e| format!("Failed to decode page frame: {}", e))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    let width = decoded
+        .iter()
+        .map(|(_, frame)| frame.width())
+        .max()
+        .ok_or("No page frames were captured")?;
+    let scale = width as f64 / viewport_width.max(1) as f64;
+    let height = (page_height as f64 * scale).round() as u32;
+
+    let mut page = image::RgbaImage::new(width, height.max(1));
+    for (offset, frame) in &decoded {
+        let y = (*offset as f64 * scale).round() as i64;
+        image::imageops::replace(&mut page, frame, 0, y);
+    }
+
+    let mut png = Vec::new();
+    image::DynamicImage::ImageRgba8(page)
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageOutputFormat::Png)
+        .map_err(|e| format!("Failed to encode page image: {}", e))?;
+    Ok(png)
+}
+
+async fn osascript(app_handle: &AppHandle, script: &str) -> Result<String, String> {
+    let output = app_handle
+        .shell()
+        .command("osascript")
+        .args(["-e", script])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run osascript: {}", e))?;
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        return Err(error.trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Name of the app in front, e.g. "Safari".
+pub async fn frontmost_app(app_handle: &AppHandle) -> Result<String, String> {
+    osascript(
+        app_handle,
+        "tell application \"System Events\" to get name of first application process whose frontmost is true",
+    )
+    .await
+    .map_err(|e| format!("Failed to find the frontmost app: {}", e))
+}
+
+/// Bundle identifier of the app in front and the title of its front
+/// window. The title needs the Accessibility permission and is empty
+/// without it or when the app has no windows.
+pub async fn frontmost_window(app_handle: &AppHandle) -> Result<(String, String), String> {
+    let output = osascript(
+        app_handle,
+        "tell application \"System Events\"
+set frontApp to first application process whose frontmost is true
+set windowTitle to \"\"
+try
+set windowTitle to name of front window of frontApp
+end try
+return (bundle identifier of frontApp) & linefeed & windowTitle
+end tell",
+    )
+    .await
+    .map_err(|e| format!("Failed to find the frontmost window: {}", e))?;
+    let (bundle_id, title) = output.split_once('\n').unwrap_or((&output, ""));
+    Ok((bundle_id.trim().to_string(), title.trim().to_string()))
+}
+
+/// Brings an app named by `frontmost_app` back to the front.
+pub async fn activate_app(app_handle: &AppHandle, name: &str) -> Result<(), String> {
+    osascript(
+        app_handle,
+        &format!(
+            "tell application \"System Events\" to set frontmost of first application process whose name is {} to true",
+            applescript_string(name)
+        ),
+    )
+    .await
+    .map(|_| ())
+    .map_err(|e| format!("Failed to activate {}: {}", name, e))
+}
+
+/// The browser in front, or an error naming the app that is.
+pub async fn frontmost_browser(app_handle: &AppHandle) -> Result<Browser, String> {
+    let name = frontmost_app(app_handle).await?;
+    Browser::from_app_name(&name)
+        .ok_or_else(|| format!("{} is not a supported browser; bring Safari or Chrome to the front", name))
+}
+
+pub async fn run_javascript(app_handle: &AppHandle, browser: Browser, js: &str) -> Result<String, String> {
+    osascript(app_handle, &browser.script_for(js))
+        .await
+        .map_err(|e| format!("Failed to run script in the browser: {}", e))
+}
+
+pub async fn page_metrics(app_handle: &AppHandle, browser: Browser) -> Result<PageMetrics, String> {
+    let json = run_javascript(app_handle, browser, METRICS_JS).await?;
+    serde_json::from_str(&json).map_err(|e| format!("Unexpected page metrics: {}", e))
+}
+
+pub async fn scroll_to(app_handle: &AppHandle, browser: Browser, y: u32) -> Result<(), String> {
+    run_javascript(app_handle, browser, &format!("window.scrollTo(0, {}); 0", y))
+        .await
+        .map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scroll_positions_end_at_bottom() {
+        assert_eq!(scroll_positions(500, 800), vec![0]);
+        assert_eq!(scroll_positions(2000, 800), vec![0, 800, 1200]);
+        assert_eq!(scroll_positions(1600, 800), vec![0, 800]);
+    }
+
+    #[test]
+    fn test_applescript_string_escapes_quotes() {
+        assert_eq!(applescript_string(r#"a "b" \c"#), r#""a \"b\" \\c""#);
+        assert_eq!(Browser::from_app_name("Google Chrome\n"), Some(Browser::Chrome));
+        assert_eq!(Browser::from_app_name("Finder"), None);
+    }
+
+    #[test]
+    fn test_stitch_frames_places_frames_at_offsets() {
+        let frame = |value: u8| {
+            let mut png = Vec::new();
+            image::DynamicImage::ImageRgba8(image::RgbaImage::from_pixel(2, 2, image::Rgba([value, 0, 0, 255])))
+                .write_to(&mut std::io::Cursor::new(&mut png), image::ImageOutputFormat::Png)
+                .unwrap();
+            png
+        };
+
+        let png = stitch_frames(&[(0, frame(10)), (1, frame(20))], 3, 2).unwrap();
+        let page = image::load_from_memory(&png).unwrap().to_rgba8();
+
+        assert_eq!(page.dimensions(), (2, 3));
+        assert_eq!(page.get_pixel(0, 0)[0], 10);
+        assert_eq!(page.get_pixel(0, 2)[0], 20);
+    }
+}
@@ -0,0 +1,29 @@
+use snipp_core::capture::CaptureBackend;
+
+#[cfg(target_os = "linux")]
+mod linux;
+#[cfg(target_os = "macos")]
+mod macos;
+#[cfg(target_os = "windows")]
+mod windows;
+
+/// The in-process capture backend for this platform, if there is one.
+#[cfg(target_os = "macos")]
+pub fn native() -> Option<&'static dyn CaptureBackend> {
+    Some(&macos::CoreGraphicsBackend)
+}
+
+#[cfg(target_os = "linux")]
+pub fn native() -> Option<&'static dyn CaptureBackend> {
+    Some(&linux::CommandLineBackend)
+}
+
+#[cfg(target_os = "windows")]
+pub fn native() -> Option<&'static dyn CaptureBackend> {
+    Some(&windows::GdiBackend)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+pub fn native() -> Option<&'static dyn CaptureBackend> {
+    None
+}
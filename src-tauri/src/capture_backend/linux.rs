@@ -0,0 +1,191 @@
+use snipp_core::capture::{CaptureBackend, CaptureError, CaptureMode};
+use snipp_core::region::CaptureRegion;
+use std::process::Command;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Session {
+    Wayland,
+    X11,
+}
+
+impl Session {
+    fn detect() -> Result<Self, CaptureError> {
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            Ok(Session::Wayland)
+        } else if std::env::var_os("DISPLAY").is_some() {
+            Ok(Session::X11)
+        } else {
+            Err(CaptureError::Failed("No Wayland or X11 display to capture".to_string()))
+        }
+    }
+}
+
+/// Captures with `grim` (and `slurp` to select) on wlroots-based Wayland
+/// compositors, and with `maim` on X11.
+pub struct CommandLineBackend;
+
+impl CaptureBackend for CommandLineBackend {
+    fn name(&self) -> &'static str {
+        match Session::detect() {
+            Ok(Session::Wayland) => "grim",
+            _ => "maim",
+        }
+    }
+
+    /// `screencapture` doesn't exist here, so every mode is handled, if only
+    /// to say it can't be done.
+    fn supports(&self, _mode: CaptureMode) -> bool {
+        true
+    }
+
+    fn capture(&self, mode: CaptureMode) -> Result<Vec<u8>, CaptureError> {
+        match Session::detect()? {
+            Session::Wayland => capture_wayland(mode),
+            Session::X11 => capture_x11(mode),
+        }
+    }
+
+    /// Asked of `xdotool` on X11; X11 has no scaling, so pixels are points.
+    fn cursor_position(&self) -> Option<(f64, f64)> {
+        if Session::detect().ok()? != Session::X11 {
+            return None;
+        }
+        let output = run("xdotool", &["getmouselocation", "--shell"]).ok()?;
+        parse_mouse_location(&String::from_utf8_lossy(&output))
+    }
+}
+
+fn capture_wayland(mode: CaptureMode) -> Result<Vec<u8>, CaptureError> {
+    match mode {
+        CaptureMode::FullScreen | CaptureMode::Selector => run("grim", &["-"]),
+        CaptureMode::Region(region) => run("grim", &["-g", &grim_geometry(region), "-"]),
+        CaptureMode::Interactive => {
+            let selection = run("slurp", &[])?;
+            let geometry = String::from_utf8_lossy(&selection).trim().to_string();
+            run("grim", &["-g", &geometry, "-"])
+        }
+        CaptureMode::Window(_) => Err(CaptureError::Failed(
+            "Capturing a single window isn't supported on Wayland".to_string(),
+        )),
+        CaptureMode::Display(_) => Err(CaptureError::Failed(
+            "Capturing a single display isn't supported on Wayland".to_string(),
+        )),
+    }
+}
+
+fn capture_x11(mode: CaptureMode) -> Result<Vec<u8>, CaptureError> {
+    match mode {
+        CaptureMode::FullScreen | CaptureMode::Selector => run("maim", &["--hidecursor"]),
+        CaptureMode::Region(region) => run("maim", &["--hidecursor", "-g", &maim_geometry(region)]),
+        CaptureMode::Interactive => run("maim", &["--hidecursor", "-s"]),
+        CaptureMode::Window(id) => run("maim", &["--hidecursor", "-i", &id.to_string()]),
+        CaptureMode::Display(number) => {
+            let output = run("xrandr", &["--listactivemonitors"])?;
+            let monitors = parse_active_monitors(&String::from_utf8_lossy(&output));
+            let region = (number as usize)
+                .checked_sub(1)
+                .and_then(|index| monitors.get(index))
+                .ok_or_else(|| CaptureError::NotFound(format!("Display {}", number)))?;
+            run("maim", &["--hidecursor", "-g", &maim_geometry(*region)])
+        }
+    }
+}
+
+/// Runs `program` and returns its standard output. A tool that exits with
+/// an error after writing nothing was cancelled (slurp and `maim -s` do
+/// this on Escape).
+fn run(program: &str, args: &[&str]) -> Result<Vec<u8>, CaptureError> {
+    let output = Command::new(program).args(args).output().map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            CaptureError::Failed(format!("{} is not installed", program))
+        } else {
+            CaptureError::Failed(format!("Failed to run {}: {}", program, e))
+        }
+    })?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(if stderr.is_empty() || stderr.contains("cancel") {
+            CaptureError::Cancelled
+        } else {
+            CaptureError::Failed(format!("{}: {}", program, stderr))
+        });
+    }
+    if output.stdout.is_empty() {
+        return Err(CaptureError::Failed(format!("{} produced no output", program)));
+    }
+    Ok(output.stdout)
+}
+
+/// `x,y wxh`, as taken by `grim -g`.
+fn grim_geometry(region: CaptureRegion) -> String {
+    format!("{},{} {}x{}", region.x, region.y, region.width, region.height)
+}
+
+/// `wxh+x+y`, as taken by `maim -g`.
+fn maim_geometry(region: CaptureRegion) -> String {
+    format!("{}x{}{:+}{:+}", region.width, region.height, region.x, region.y)
+}
+
+/// `X=..` and `Y=..` from `xdotool getmouselocation --shell`.
+fn parse_mouse_location(output: &str) -> Option<(f64, f64)> {
+    let value = |key: &str| {
+        output
+            .lines()
+            .find_map(|line| line.strip_prefix(key)?.strip_prefix('=')?.trim().parse::<f64>().ok())
+    };
+    Some((value("X")?, value("Y")?))
+}
+
+/// Monitor rectangles from `xrandr --listactivemonitors`, primary first like
+/// `screencapture -D` numbering.
+fn parse_active_monitors(output: &str) -> Vec<CaptureRegion> {
+    let mut monitors: Vec<(bool, CaptureRegion)> = output
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace().skip(1);
+            let primary = fields.next()?.contains('*');
+            // e.g. 1920/344x1080/194+0+0: sizes carry millimetres after a slash.
+            let geometry = fields.next()?;
+            let (width, rest) = geometry.split_once('x')?;
+            let (height, offsets) = rest.split_at(rest.find(['+', '-'])?);
+            let offsets = &offsets[1..];
+            let (x, y) = offsets.split_at(offsets.get(1..)?.find(['+', '-'])? + 1);
+            let region = CaptureRegion {
+                x: x.parse().ok()?,
+                y: y.parse().ok()?,
+                width: width.split('/').next()?.parse().ok()?,
+                height: height.split('/').next()?.parse().ok()?,
+            };
+            Some((primary, region))
+        })
+        .collect();
+    monitors.sort_by_key(|(primary, _)| !primary);
+    monitors.into_iter().map(|(_, region)| region).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_active_monitors_puts_primary_first() {
+        let output = "Monitors: 2\n \
+                      0: +HDMI-1 2560/597x1440/336+1920+0  HDMI-1\n \
+                      1: +*eDP-1 1920/344x1080/194+0+0  eDP-1\n";
+
+        let monitors = parse_active_monitors(output);
+
+        assert_eq!(monitors.len(), 2);
+        assert_eq!(monitors[0], CaptureRegion { x: 0, y: 0, width: 1920, height: 1080 });
+        assert_eq!(monitors[1], CaptureRegion { x: 1920, y: 0, width: 2560, height: 1440 });
+        assert_eq!(maim_geometry(monitors[1]), "2560x1440+1920+0");
+        assert_eq!(grim_geometry(monitors[1]), "1920,0 2560x1440");
+    }
+
+    #[test]
+    fn test_parse_mouse_location() {
+        assert_eq!(parse_mouse_location("X=1204\nY=387\nSCREEN=0\nWINDOW=65011718\n"), Some((1204.0, 387.0)));
+        assert_eq!(parse_mouse_location("SCREEN=0\n"), None);
+    }
+}
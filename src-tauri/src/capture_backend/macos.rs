@@ -0,0 +1,235 @@
+use snipp_core::capture::{encode_pixels, CaptureBackend, CaptureError, CaptureMode, PixelLayout};
+use snipp_core::color;
+
+mod ffi {
+    use std::ffi::c_void;
+
+    pub type CGImageRef = *const c_void;
+    pub type CFDataRef = *const c_void;
+    pub type CGDirectDisplayID = u32;
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    pub struct CGPoint {
+        pub x: f64,
+        pub y: f64,
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    pub struct CGSize {
+        pub width: f64,
+        pub height: f64,
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    pub struct CGRect {
+        pub origin: CGPoint,
+        pub size: CGSize,
+    }
+
+    pub const LIST_ON_SCREEN_ONLY: u32 = 1 << 0;
+    pub const LIST_INCLUDING_WINDOW: u32 = 1 << 3;
+    pub const IMAGE_BOUNDS_IGNORE_FRAMING: u32 = 1 << 0;
+    pub const IMAGE_BEST_RESOLUTION: u32 = 1 << 3;
+
+    pub const ALPHA_INFO_MASK: u32 = 0x1F;
+    pub const BYTE_ORDER_MASK: u32 = 0x7000;
+    pub const BYTE_ORDER_32_LITTLE: u32 = 2 << 12;
+    /// `kCGImageAlphaPremultipliedLast`, `kCGImageAlphaLast` and
+    /// `kCGImageAlphaNoneSkipLast`: the alpha byte comes after the colors.
+    pub const ALPHA_LAST: [u32; 3] = [1, 3, 5];
+
+    /// `kCGEventSourceStateCombinedSessionState` and `kCGMouseButtonLeft`.
+    pub const COMBINED_SESSION_STATE: i32 = 0;
+    pub const MOUSE_BUTTON_LEFT: u32 = 0;
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        pub static CGRectNull: CGRect;
+
+        pub fn CGPreflightScreenCaptureAccess() -> bool;
+        pub fn CGGetActiveDisplayList(
+            max_displays: u32,
+            active_displays: *mut CGDirectDisplayID,
+            display_count: *mut u32,
+        ) -> i32;
+        pub fn CGDisplayCreateImage(display: CGDirectDisplayID) -> CGImageRef;
+        pub fn CGWindowListCreateImage(
+            screen_bounds: CGRect,
+            list_option: u32,
+            window_id: u32,
+            image_option: u32,
+        ) -> CGImageRef;
+        pub fn CGImageGetWidth(image: CGImageRef) -> usize;
+        pub fn CGImageGetHeight(image: CGImageRef) -> usize;
+        pub fn CGImageGetBitsPerPixel(image: CGImageRef) -> usize;
+        pub fn CGImageGetBytesPerRow(image: CGImageRef) -> usize;
+        pub fn CGImageGetBitmapInfo(image: CGImageRef) -> u32;
+        pub fn CGImageGetDataProvider(image: CGImageRef) -> *const c_void;
+        pub fn CGImageGetColorSpace(image: CGImageRef) -> *const c_void;
+        pub fn CGDataProviderCopyData(provider: *const c_void) -> CFDataRef;
+        pub fn CGColorSpaceCopyICCData(space: *const c_void) -> CFDataRef;
+        pub fn CGImageRelease(image: CGImageRef);
+        pub fn CGEventCreate(source: *const c_void) -> *const c_void;
+        pub fn CGEventGetLocation(event: *const c_void) -> CGPoint;
+        pub fn CGEventSourceButtonState(state: i32, button: u32) -> bool;
+    }
+
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        pub fn CFDataGetBytePtr(data: CFDataRef) -> *const u8;
+        pub fn CFDataGetLength(data: CFDataRef) -> isize;
+        pub fn CFRelease(object: *const c_void);
+    }
+}
+
+/// Most displays a Mac can drive at once, with room to spare.
+const MAX_DISPLAYS: u32 = 16;
+
+/// Captures through CoreGraphics. Interactive captures still need the
+/// `screencapture` UI.
+pub struct CoreGraphicsBackend;
+
+impl CaptureBackend for CoreGraphicsBackend {
+    fn name(&self) -> &'static str {
+        "CoreGraphics"
+    }
+
+    fn supports(&self, mode: CaptureMode) -> bool {
+        !matches!(mode, CaptureMode::Interactive)
+    }
+
+    fn capture(&self, mode: CaptureMode) -> Result<Vec<u8>, CaptureError> {
+        // SAFETY: argument-less status query.
+        if !unsafe { ffi::CGPreflightScreenCaptureAccess() } {
+            return Err(CaptureError::PermissionDenied);
+        }
+
+        // SAFETY: each call returns an owned CGImage (or null), which
+        // `encode_image` releases.
+        let image = match mode {
+            CaptureMode::FullScreen | CaptureMode::Selector => unsafe {
+                ffi::CGDisplayCreateImage(display_id(1)?)
+            },
+            CaptureMode::Display(number) => unsafe { ffi::CGDisplayCreateImage(display_id(number)?) },
+            CaptureMode::Region(region) => {
+                let bounds = ffi::CGRect {
+                    origin: ffi::CGPoint {
+                        x: region.x as f64,
+                        y: region.y as f64,
+                    },
+                    size: ffi::CGSize {
+                        width: region.width as f64,
+                        height: region.height as f64,
+                    },
+                };
+                unsafe {
+                    ffi::CGWindowListCreateImage(bounds, ffi::LIST_ON_SCREEN_ONLY, 0, ffi::IMAGE_BEST_RESOLUTION)
+                }
+            }
+            CaptureMode::Window(id) => unsafe {
+                ffi::CGWindowListCreateImage(
+                    ffi::CGRectNull,
+                    ffi::LIST_INCLUDING_WINDOW,
+                    id,
+                    ffi::IMAGE_BOUNDS_IGNORE_FRAMING | ffi::IMAGE_BEST_RESOLUTION,
+                )
+            },
+            CaptureMode::Interactive => {
+                return Err(CaptureError::Failed("Interactive capture needs screencapture".to_string()))
+            }
+        };
+        if image.is_null() {
+            return Err(match mode {
+                CaptureMode::Window(id) => CaptureError::NotFound(format!("Window {}", id)),
+                _ => CaptureError::Failed("CoreGraphics returned no image".to_string()),
+            });
+        }
+        encode_image(image)
+    }
+
+    fn cursor_position(&self) -> Option<(f64, f64)> {
+        // SAFETY: a null source makes a fresh event for the current state,
+        // released once its location is read.
+        unsafe {
+            let event = ffi::CGEventCreate(std::ptr::null());
+            if event.is_null() {
+                return None;
+            }
+            let location = ffi::CGEventGetLocation(event);
+            ffi::CFRelease(event);
+            Some((location.x, location.y))
+        }
+    }
+
+    fn primary_button_down(&self) -> bool {
+        // SAFETY: plain status query.
+        unsafe { ffi::CGEventSourceButtonState(ffi::COMBINED_SESSION_STATE, ffi::MOUSE_BUTTON_LEFT) }
+    }
+}
+
+/// The CoreGraphics id of display `number`, counted from 1 for the main
+/// display like `screencapture -D`.
+fn display_id(number: u32) -> Result<ffi::CGDirectDisplayID, CaptureError> {
+    let mut displays = [0; MAX_DISPLAYS as usize];
+    let mut count = 0;
+    // SAFETY: the buffer holds MAX_DISPLAYS ids.
+    let status = unsafe { ffi::CGGetActiveDisplayList(MAX_DISPLAYS, displays.as_mut_ptr(), &mut count) };
+    if status != 0 {
+        return Err(CaptureError::Failed(format!("Failed to list displays (error {})", status)));
+    }
+    displays[..count as usize]
+        .get((number as usize).wrapping_sub(1))
+        .copied()
+        .ok_or_else(|| CaptureError::NotFound(format!("Display {}", number)))
+}
+
+/// Copies the pixels out of `image` into a PNG that keeps the display's
+/// color profile, and releases the image.
+fn encode_image(image: ffi::CGImageRef) -> Result<Vec<u8>, CaptureError> {
+    // SAFETY: `image` is a valid CGImage owned by this function; the copied
+    // CFData objects are released before returning.
+    unsafe {
+        let width = ffi::CGImageGetWidth(image);
+        let height = ffi::CGImageGetHeight(image);
+        let bytes_per_row = ffi::CGImageGetBytesPerRow(image);
+        let bitmap_info = ffi::CGImageGetBitmapInfo(image);
+        let bits_per_pixel = ffi::CGImageGetBitsPerPixel(image);
+        let pixels = ffi::CGDataProviderCopyData(ffi::CGImageGetDataProvider(image));
+        let icc = ffi::CGColorSpaceCopyICCData(ffi::CGImageGetColorSpace(image));
+        ffi::CGImageRelease(image);
+
+        let icc_bytes = take_bytes(icc);
+        if bits_per_pixel != 32 {
+            let _ = take_bytes(pixels);
+            return Err(CaptureError::Failed(format!("Unsupported {}-bit pixels", bits_per_pixel)));
+        }
+        let layout = if bitmap_info & ffi::BYTE_ORDER_MASK == ffi::BYTE_ORDER_32_LITTLE {
+            PixelLayout::Bgra
+        } else if ffi::ALPHA_LAST.contains(&(bitmap_info & ffi::ALPHA_INFO_MASK)) {
+            PixelLayout::Rgba
+        } else {
+            PixelLayout::Argb
+        };
+        let data = take_bytes(pixels).ok_or_else(|| CaptureError::Failed("No pixel data".to_string()))?;
+
+        let png = encode_pixels(&data, width, height, bytes_per_row, layout)?;
+        color::apply_profile(png, icc_bytes.as_deref(), color::ColorProfileMode::Preserve).map_err(CaptureError::Failed)
+    }
+}
+
+/// Copies and releases a CFData; `None` for null.
+///
+/// # Safety
+/// `data` must be null or a CFData the caller owns.
+unsafe fn take_bytes(data: ffi::CFDataRef) -> Option<Vec<u8>> {
+    if data.is_null() {
+        return None;
+    }
+    let length = ffi::CFDataGetLength(data).max(0) as usize;
+    let bytes = std::slice::from_raw_parts(ffi::CFDataGetBytePtr(data), length).to_vec();
+    ffi::CFRelease(data);
+    Some(bytes)
+}
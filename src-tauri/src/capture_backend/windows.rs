@@ -0,0 +1,283 @@
+use snipp_core::capture::{encode_pixels, CaptureBackend, CaptureError, CaptureMode, PixelLayout};
+use snipp_core::region::CaptureRegion;
+use std::ffi::c_void;
+use std::ptr::null_mut;
+
+mod ffi {
+    use std::ffi::c_void;
+
+    pub type Handle = *mut c_void;
+
+    #[repr(C)]
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct Rect {
+        pub left: i32,
+        pub top: i32,
+        pub right: i32,
+        pub bottom: i32,
+    }
+
+    #[repr(C)]
+    #[derive(Default)]
+    pub struct BitmapInfoHeader {
+        pub size: u32,
+        pub width: i32,
+        /// Negative for rows stored top to bottom.
+        pub height: i32,
+        pub planes: u16,
+        pub bit_count: u16,
+        pub compression: u32,
+        pub size_image: u32,
+        pub x_pels_per_meter: i32,
+        pub y_pels_per_meter: i32,
+        pub clr_used: u32,
+        pub clr_important: u32,
+    }
+
+    #[repr(C)]
+    pub struct BitmapInfo {
+        pub header: BitmapInfoHeader,
+        pub colors: [u32; 1],
+    }
+
+    #[repr(C)]
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct Point {
+        pub x: i32,
+        pub y: i32,
+    }
+
+    pub type MonitorEnumProc = unsafe extern "system" fn(Handle, Handle, *mut Rect, isize) -> i32;
+
+    pub const SM_CXSCREEN: i32 = 0;
+    pub const SM_CYSCREEN: i32 = 1;
+    pub const VK_LBUTTON: i32 = 0x01;
+    pub const SRCCOPY: u32 = 0x00CC_0020;
+    /// Includes layered windows, which are most windows since Windows 8.
+    pub const CAPTUREBLT: u32 = 0x4000_0000;
+    pub const BI_RGB: u32 = 0;
+    pub const DIB_RGB_COLORS: u32 = 0;
+
+    #[link(name = "user32")]
+    extern "system" {
+        pub fn GetDC(window: Handle) -> Handle;
+        pub fn ReleaseDC(window: Handle, dc: Handle) -> i32;
+        pub fn GetSystemMetrics(index: i32) -> i32;
+        pub fn GetDpiForSystem() -> u32;
+        pub fn GetCursorPos(point: *mut Point) -> i32;
+        pub fn GetAsyncKeyState(key: i32) -> i16;
+        pub fn EnumDisplayMonitors(dc: Handle, clip: *const Rect, callback: MonitorEnumProc, data: isize) -> i32;
+    }
+
+    #[link(name = "gdi32")]
+    extern "system" {
+        pub fn CreateCompatibleDC(dc: Handle) -> Handle;
+        pub fn CreateCompatibleBitmap(dc: Handle, width: i32, height: i32) -> Handle;
+        pub fn SelectObject(dc: Handle, object: Handle) -> Handle;
+        #[allow(clippy::too_many_arguments)]
+        pub fn BitBlt(
+            dest: Handle,
+            x: i32,
+            y: i32,
+            width: i32,
+            height: i32,
+            source: Handle,
+            source_x: i32,
+            source_y: i32,
+            operation: u32,
+        ) -> i32;
+        pub fn GetDIBits(
+            dc: Handle,
+            bitmap: Handle,
+            start: u32,
+            lines: u32,
+            bits: *mut c_void,
+            info: *mut BitmapInfo,
+            usage: u32,
+        ) -> i32;
+        pub fn DeleteObject(object: Handle) -> i32;
+        pub fn DeleteDC(dc: Handle) -> i32;
+    }
+}
+
+/// Copies the screen with GDI. There is no system selection UI, so area
+/// captures go through Snipp's selector overlay instead.
+pub struct GdiBackend;
+
+impl CaptureBackend for GdiBackend {
+    fn name(&self) -> &'static str {
+        "GDI"
+    }
+
+    /// `screencapture` doesn't exist here, so every mode is handled, if only
+    /// to say it can't be done.
+    fn supports(&self, _mode: CaptureMode) -> bool {
+        true
+    }
+
+    fn capture(&self, mode: CaptureMode) -> Result<Vec<u8>, CaptureError> {
+        let rect = match mode {
+            CaptureMode::FullScreen | CaptureMode::Selector => {
+                // SAFETY: plain metric queries.
+                let (width, height) =
+                    unsafe { (ffi::GetSystemMetrics(ffi::SM_CXSCREEN), ffi::GetSystemMetrics(ffi::SM_CYSCREEN)) };
+                ffi::Rect {
+                    left: 0,
+                    top: 0,
+                    right: width,
+                    bottom: height,
+                }
+            }
+            CaptureMode::Region(region) => region_to_pixels(region, system_scale()),
+            CaptureMode::Display(number) => monitors()
+                .get((number as usize).wrapping_sub(1))
+                .copied()
+                .ok_or_else(|| CaptureError::NotFound(format!("Display {}", number)))?,
+            CaptureMode::Interactive => {
+                return Err(CaptureError::Failed("Area capture needs the selector overlay".to_string()))
+            }
+            CaptureMode::Window(_) => {
+                return Err(CaptureError::Failed(
+                    "Capturing a single window isn't supported on Windows".to_string(),
+                ))
+            }
+        };
+        capture_rect(rect)
+    }
+
+    fn cursor_position(&self) -> Option<(f64, f64)> {
+        let mut point = ffi::Point::default();
+        // SAFETY: `point` outlives the call.
+        if unsafe { ffi::GetCursorPos(&mut point) } == 0 {
+            return None;
+        }
+        let scale = system_scale();
+        Some((point.x as f64 / scale, point.y as f64 / scale))
+    }
+
+    fn primary_button_down(&self) -> bool {
+        // SAFETY: plain status query; the high bit means held.
+        unsafe { ffi::GetAsyncKeyState(ffi::VK_LBUTTON) < 0 }
+    }
+}
+
+/// The app is per-monitor DPI aware, so GDI works in pixels while regions
+/// come in points.
+fn system_scale() -> f64 {
+    // SAFETY: argument-less query.
+    let dpi = unsafe { ffi::GetDpiForSystem() };
+    if dpi == 0 {
+        1.0
+    } else {
+        dpi as f64 / 96.0
+    }
+}
+
+fn region_to_pixels(region: CaptureRegion, scale: f64) -> ffi::Rect {
+    let left = (region.x as f64 * scale).round() as i32;
+    let top = (region.y as f64 * scale).round() as i32;
+    ffi::Rect {
+        left,
+        top,
+        right: left + (region.width as f64 * scale).round() as i32,
+        bottom: top + (region.height as f64 * scale).round() as i32,
+    }
+}
+
+/// Monitor rectangles in desktop pixels, in the order `EnumDisplayMonitors`
+/// reports them, which is also the order of `list_displays`.
+fn monitors() -> Vec<ffi::Rect> {
+    unsafe extern "system" fn collect(_monitor: ffi::Handle, _dc: ffi::Handle, rect: *mut ffi::Rect, data: isize) -> i32 {
+        // SAFETY: `data` is the Vec passed below and `rect` is valid for the call.
+        let monitors = &mut *(data as *mut Vec<ffi::Rect>);
+        monitors.push(*rect);
+        1
+    }
+
+    let mut monitors: Vec<ffi::Rect> = Vec::new();
+    // SAFETY: the callback only runs during this call, while `monitors` is alive.
+    unsafe {
+        ffi::EnumDisplayMonitors(null_mut(), std::ptr::null(), collect, &mut monitors as *mut _ as isize);
+    }
+    monitors
+}
+
+fn capture_rect(rect: ffi::Rect) -> Result<Vec<u8>, CaptureError> {
+    let width = rect.right - rect.left;
+    let height = rect.bottom - rect.top;
+    if width <= 0 || height <= 0 {
+        return Err(CaptureError::Failed("Capture region must have a non-zero size".to_string()));
+    }
+
+    let mut pixels = vec![0u8; width as usize * height as usize * 4];
+    // SAFETY: every handle created here is released before returning, and
+    // `pixels` holds `height` top-down rows of 32-bit pixels.
+    let copied_lines = unsafe {
+        let screen = ffi::GetDC(null_mut());
+        if screen.is_null() {
+            return Err(CaptureError::Failed("Failed to access the screen".to_string()));
+        }
+        let memory = ffi::CreateCompatibleDC(screen);
+        let bitmap = ffi::CreateCompatibleBitmap(screen, width, height);
+        let previous = ffi::SelectObject(memory, bitmap);
+        let copied = ffi::BitBlt(
+            memory,
+            0,
+            0,
+            width,
+            height,
+            screen,
+            rect.left,
+            rect.top,
+            ffi::SRCCOPY | ffi::CAPTUREBLT,
+        ) != 0;
+        ffi::SelectObject(memory, previous);
+
+        let mut info = ffi::BitmapInfo {
+            header: ffi::BitmapInfoHeader {
+                size: std::mem::size_of::<ffi::BitmapInfoHeader>() as u32,
+                width,
+                height: -height,
+                planes: 1,
+                bit_count: 32,
+                compression: ffi::BI_RGB,
+                ..ffi::BitmapInfoHeader::default()
+            },
+            colors: [0],
+        };
+        let lines = if copied {
+            ffi::GetDIBits(
+                memory,
+                bitmap,
+                0,
+                height as u32,
+                pixels.as_mut_ptr() as *mut c_void,
+                &mut info,
+                ffi::DIB_RGB_COLORS,
+            )
+        } else {
+            0
+        };
+
+        ffi::DeleteObject(bitmap);
+        ffi::DeleteDC(memory);
+        ffi::ReleaseDC(null_mut(), screen);
+        lines
+    };
+    if copied_lines != height {
+        return Err(CaptureError::Failed("Failed to copy the screen".to_string()));
+    }
+
+    encode_pixels(&pixels, width as usize, height as usize, width as usize * 4, PixelLayout::Bgra)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_region_to_pixels_scales_points() {
+        let rect = region_to_pixels(CaptureRegion { x: 10, y: 20, width: 100, height: 50 }, 1.5);
+        assert_eq!((rect.left, rect.top, rect.right, rect.bottom), (15, 30, 165, 105));
+    }
+}
@@ -0,0 +1,48 @@
+use snipp_core::highlight::Click;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Short enough not to miss a quick click, which is held for 50-100 ms.
+const POLL_INTERVAL: Duration = Duration::from_millis(15);
+
+/// Polls the pointer while a recording segment or GIF capture runs and notes
+/// each press of the primary button, in desktop points.
+pub struct ClickTracker {
+    stop: Arc<AtomicBool>,
+    task: tauri::async_runtime::JoinHandle<Vec<Click>>,
+}
+
+impl ClickTracker {
+    /// `None` where there is no native backend to ask about the pointer.
+    pub fn start() -> Option<Self> {
+        let backend = crate::capture_backend::native()?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = stop.clone();
+        let task = tauri::async_runtime::spawn(async move {
+            let started_at = Instant::now();
+            let mut interval = tokio::time::interval(POLL_INTERVAL);
+            let mut was_down = false;
+            let mut clicks = Vec::new();
+            while !stopped.load(Ordering::SeqCst) {
+                interval.tick().await;
+                let down = backend.primary_button_down();
+                if down && !was_down {
+                    if let Some((x, y)) = backend.cursor_position() {
+                        let at_ms = started_at.elapsed().as_millis() as u64;
+                        clicks.push(Click { at_ms, x, y });
+                    }
+                }
+                was_down = down;
+            }
+            clicks
+        });
+        Some(Self { stop, task })
+    }
+
+    /// Stops polling and returns the clicks, oldest first.
+    pub async fn finish(self) -> Vec<Click> {
+        self.stop.store(true, Ordering::SeqCst);
+        self.task.await.unwrap_or_default()
+    }
+}
@@ -0,0 +1,189 @@
+use base64::prelude::*;
+use image::{imageops, DynamicImage, RgbaImage};
+use serde::{Deserialize, Serialize};
+use snipp_core::baseline;
+
+/// Both captures on a shared canvas plus their 50/50 blend, as base64 PNGs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComparisonData {
+    pub before_path: String,
+    pub after_path: String,
+    pub before_image: String,
+    pub after_image: String,
+    pub blend_image: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Places both images top-left on transparent canvases of the same size so
+/// their pixels line up even when the captures differ in size.
+pub fn align_pair(before: &RgbaImage, after: &RgbaImage) -> (RgbaImage, RgbaImage) {
+    let width = before.width().max(after.width());
+    let height = before.height().max(after.height());
+
+    let pad = |image: &RgbaImage| {
+        if image.dimensions() == (width, height) {
+            return image.clone();
+        }
+        let mut canvas = RgbaImage::new(width, height);
+        imageops::replace(&mut canvas, image, 0, 0);
+        canvas
+    };
+
+    (pad(before), pad(after))
+}
+
+/// Averages two equally sized images channel by channel.
+pub fn blend(before: &RgbaImage, after: &RgbaImage) -> RgbaImage {
+    RgbaImage::from_fn(before.width(), before.height(), |x, y| {
+        let a = before.get_pixel(x, y);
+        let b = after.get_pixel(x, y);
+        image::Rgba(std::array::from_fn(|i| {
+            (a[i] as u16 + b[i] as u16).div_ceil(2) as u8
+        }))
+    })
+}
+
+/// How two cached captures differ, with the changes highlighted in red over
+/// a faded copy of the second capture.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreenshotDiff {
+    pub diff_image: String,
+    pub width: u32,
+    pub height: u32,
+    pub changed_pixels: u64,
+    pub total_pixels: u64,
+    pub changed_percent: f64,
+}
+
+/// Marks pixels of two equally sized images where any channel differs by
+/// more than `tolerance`. Unchanged pixels are kept from `after` but faded
+/// towards white so the changes stand out. Returns the image and how many
+/// pixels changed.
+pub fn highlight_changes(before: &RgbaImage, after: &RgbaImage, tolerance: u8) -> (RgbaImage, u64) {
+    let mut changed = 0u64;
+    let image = RgbaImage::from_fn(after.width(), after.height(), |x, y| {
+        let a = before.get_pixel(x, y);
+        let b = after.get_pixel(x, y);
+        if a.0.iter().zip(b.0.iter()).any(|(a, b)| a.abs_diff(*b) > tolerance) {
+            changed += 1;
+            return image::Rgba([255, 0, 0, 255]);
+        }
+        let alpha = b[3] as u16;
+        image::Rgba(std::array::from_fn(|i| {
+            if i == 3 {
+                255
+            } else {
+                // Composite over white, then fade to a quarter strength.
+                let over_white = (b[i] as u16 * alpha + 255 * (255 - alpha)) / 255;
+                (255 - (255 - over_white) / 4) as u8
+            }
+        }))
+    });
+    (image, changed)
+}
+
+fn encode_png(image: RgbaImage) -> Result<String, String> {
+    let mut png_bytes = Vec::new();
+    DynamicImage::ImageRgba8(image)
+        .write_to(
+            &mut std::io::Cursor::new(&mut png_bytes),
+            image::ImageOutputFormat::Png,
+        )
+        .map_err(|e| format!("Failed to encode comparison image: {}", e))?;
+    Ok(BASE64_STANDARD.encode(png_bytes))
+}
+
+/// Loads two saved captures and builds the comparison. Decoding and
+/// encoding are blocking, so call this off the async runtime.
+pub fn build_comparison(before_path: &str, after_path: &str) -> Result<ComparisonData, String> {
+    let load = |path: &str| {
+        image::open(path)
+            .map(|image| image.to_rgba8())
+            .map_err(|e| format!("Failed to open {}: {}", path, e))
+    };
+    let (before, after) = align_pair(&load(before_path)?, &load(after_path)?);
+    let blended = blend(&before, &after);
+    let (width, height) = before.dimensions();
+
+    Ok(ComparisonData {
+        before_path: before_path.to_string(),
+        after_path: after_path.to_string(),
+        before_image: encode_png(before)?,
+        after_image: encode_png(after)?,
+        blend_image: encode_png(blended)?,
+        width,
+        height,
+    })
+}
+
+/// Diffs two captures aligned top-left. Decoding and encoding are blocking,
+/// so call this off the async runtime.
+pub fn diff_captures(before_png: &[u8], after_png: &[u8]) -> Result<ScreenshotDiff, String> {
+    let load = |png: &[u8]| {
+        image::load_from_memory(png)
+            .map(|image| image.to_rgba8())
+            .map_err(|e| format!("Failed to decode capture: {}", e))
+    };
+    let (before, after) = align_pair(&load(before_png)?, &load(after_png)?);
+    let (highlighted, changed_pixels) = highlight_changes(&before, &after, baseline::CHANNEL_TOLERANCE);
+    let (width, height) = highlighted.dimensions();
+    let total_pixels = width as u64 * height as u64;
+    let changed_percent = if total_pixels == 0 {
+        0.0
+    } else {
+        changed_pixels as f64 * 100.0 / total_pixels as f64
+    };
+
+    Ok(ScreenshotDiff {
+        diff_image: encode_png(highlighted)?,
+        width,
+        height,
+        changed_pixels,
+        total_pixels,
+        changed_percent,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_align_pair_pads_to_largest_size() {
+        let before = RgbaImage::from_pixel(4, 2, image::Rgba([255, 0, 0, 255]));
+        let after = RgbaImage::from_pixel(2, 3, image::Rgba([0, 0, 255, 255]));
+
+        let (before, after) = align_pair(&before, &after);
+
+        assert_eq!(before.dimensions(), (4, 3));
+        assert_eq!(after.dimensions(), (4, 3));
+        assert_eq!(before.get_pixel(3, 2)[3], 0);
+        assert_eq!(after.get_pixel(1, 2), &image::Rgba([0, 0, 255, 255]));
+        assert_eq!(after.get_pixel(3, 0)[3], 0);
+    }
+
+    #[test]
+    fn test_blend_averages_channels() {
+        let before = RgbaImage::from_pixel(1, 1, image::Rgba([200, 0, 100, 255]));
+        let after = RgbaImage::from_pixel(1, 1, image::Rgba([0, 100, 100, 255]));
+
+        let blended = blend(&before, &after);
+
+        assert_eq!(blended.get_pixel(0, 0), &image::Rgba([100, 50, 100, 255]));
+    }
+
+    #[test]
+    fn test_highlight_changes_marks_changed_pixels() {
+        let before = RgbaImage::from_pixel(3, 1, image::Rgba([0, 0, 0, 255]));
+        let mut after = before.clone();
+        after.put_pixel(0, 0, image::Rgba([4, 0, 0, 255]));
+        after.put_pixel(2, 0, image::Rgba([0, 0, 200, 255]));
+
+        let (highlighted, changed) = highlight_changes(&before, &after, baseline::CHANNEL_TOLERANCE);
+
+        assert_eq!(changed, 1);
+        assert_eq!(highlighted.get_pixel(2, 0), &image::Rgba([255, 0, 0, 255]));
+        assert_eq!(highlighted.get_pixel(1, 0), &image::Rgba([192, 192, 192, 255]));
+    }
+}
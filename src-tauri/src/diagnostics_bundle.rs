@@ -0,0 +1,106 @@
+use serde::Serialize;
+use serde_json::Value;
+use snipp_core::diagnostics::{DiagnosticsReport, HistoryStats};
+use snipp_core::error::SnippError;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use zip::result::ZipError;
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+/// Lines of log included in a bundle, about a day of normal use at `info`.
+pub const BUNDLE_LOG_LINES: usize = 2000;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Permissions {
+    pub screen_recording: bool,
+    pub accessibility: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SystemInfo {
+    pub version: String,
+    pub os: String,
+    pub arch: String,
+    /// e.g. `14.5` on macOS; `None` where it couldn't be read.
+    pub os_version: Option<String>,
+    pub permissions: Permissions,
+    pub exported_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Everything that goes into a diagnostics bundle, already redacted.
+pub struct Bundle {
+    pub system: SystemInfo,
+    pub report: DiagnosticsReport,
+    pub config: Value,
+    pub history: HistoryStats,
+    pub logs: Vec<String>,
+}
+
+fn zip_error(e: ZipError) -> SnippError {
+    match e {
+        ZipError::Io(e) => e.into(),
+        e => SnippError::Other(format!("Failed to write diagnostics bundle: {}", e)),
+    }
+}
+
+fn add_json(zip: &mut ZipWriter<File>, name: &str, value: &impl Serialize) -> Result<(), SnippError> {
+    zip.start_file(name, SimpleFileOptions::default()).map_err(zip_error)?;
+    zip.write_all(serde_json::to_string_pretty(value)?.as_bytes())?;
+    Ok(())
+}
+
+/// Writes `bundle` to a zip at `path`, one file per part.
+pub fn write_bundle(path: &Path, bundle: &Bundle) -> Result<(), SnippError> {
+    let mut zip = ZipWriter::new(File::create(path)?);
+    add_json(&mut zip, "system.json", &bundle.system)?;
+    add_json(&mut zip, "diagnostics.json", &bundle.report)?;
+    add_json(&mut zip, "config.json", &bundle.config)?;
+    add_json(&mut zip, "history.json", &bundle.history)?;
+    zip.start_file("snipp.log", SimpleFileOptions::default()).map_err(zip_error)?;
+    for line in &bundle.logs {
+        writeln!(zip, "{}", line)?;
+    }
+    zip.finish().map_err(zip_error)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn test_write_bundle() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("diagnostics.zip");
+        let bundle = Bundle {
+            system: SystemInfo {
+                version: "0.1.0".to_string(),
+                os: "macos".to_string(),
+                arch: "aarch64".to_string(),
+                os_version: Some("14.5".to_string()),
+                permissions: Permissions {
+                    screen_recording: true,
+                    accessibility: false,
+                },
+                exported_at: chrono::Utc::now(),
+            },
+            report: DiagnosticsReport::new("0.1.0", Vec::new()),
+            config: serde_json::json!({ "token": "[redacted]" }),
+            history: HistoryStats::default(),
+            logs: vec!["first".to_string(), "second".to_string()],
+        };
+
+        write_bundle(&path, &bundle).unwrap();
+
+        let mut archive = zip::ZipArchive::new(File::open(&path).unwrap()).unwrap();
+        let mut names: Vec<&str> = archive.file_names().collect();
+        names.sort();
+        assert_eq!(names, ["config.json", "diagnostics.json", "history.json", "snipp.log", "system.json"]);
+        let mut log = String::new();
+        archive.by_name("snipp.log").unwrap().read_to_string(&mut log).unwrap();
+        assert_eq!(log, "first\nsecond\n");
+    }
+}
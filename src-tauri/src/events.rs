@@ -0,0 +1,195 @@
+use serde::Serialize;
+use snipp_core::capture::CaptureMode;
+use snipp_core::history::ScreenshotHistory;
+use tauri::{AppHandle, Emitter};
+
+/// Sent as `version` in every lifecycle event; bumped when a payload
+/// changes in a way listeners would notice.
+pub const EVENT_VERSION: u32 = 1;
+
+/// A payload with a fixed event name, emitted through [`emit`].
+pub trait LifecycleEvent: Serialize + Clone {
+    const NAME: &'static str;
+}
+
+#[derive(Clone, Serialize)]
+struct Versioned<'a, E> {
+    version: u32,
+    #[serde(flatten)]
+    payload: &'a E,
+}
+
+/// Emits `event` to every window. Nobody listening is not an error, so
+/// failures are only logged.
+pub fn emit<E: LifecycleEvent>(app_handle: &AppHandle, event: &E) {
+    let versioned = Versioned {
+        version: EVENT_VERSION,
+        payload: event,
+    };
+    if let Err(e) = app_handle.emit(E::NAME, versioned) {
+        tracing::warn!("Failed to emit {}: {}", E::NAME, e);
+    }
+}
+
+/// What kind of capture an event is about, without the mode's details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptureKind {
+    Area,
+    FullScreen,
+    Region,
+    Window,
+    Display,
+}
+
+impl From<CaptureMode> for CaptureKind {
+    fn from(mode: CaptureMode) -> Self {
+        match mode {
+            CaptureMode::Interactive | CaptureMode::Selector => CaptureKind::Area,
+            CaptureMode::FullScreen => CaptureKind::FullScreen,
+            CaptureMode::Region(_) => CaptureKind::Region,
+            CaptureMode::Window(_) => CaptureKind::Window,
+            CaptureMode::Display(_) => CaptureKind::Display,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CaptureStarted {
+    pub timestamp: u64,
+    pub kind: CaptureKind,
+}
+
+impl LifecycleEvent for CaptureStarted {
+    const NAME: &'static str = "capture-started";
+}
+
+/// The capture is cached and about to be shown; it isn't saved yet.
+#[derive(Debug, Clone, Serialize)]
+pub struct CaptureCompleted {
+    pub timestamp: u64,
+    pub kind: CaptureKind,
+    pub filename: String,
+    pub bytes: usize,
+}
+
+impl LifecycleEvent for CaptureCompleted {
+    const NAME: &'static str = "capture-completed";
+}
+
+/// The selection was dismissed or the screen couldn't be grabbed.
+#[derive(Debug, Clone, Serialize)]
+pub struct CaptureCancelled {
+    pub timestamp: u64,
+    pub kind: CaptureKind,
+    pub reason: String,
+}
+
+impl LifecycleEvent for CaptureCancelled {
+    const NAME: &'static str = "capture-cancelled";
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SaveCompleted {
+    /// The cached capture that was saved, if it came from the cache.
+    pub timestamp: Option<u64>,
+    pub file_path: String,
+    /// Every copy written, one per save folder.
+    pub saved_paths: Vec<String>,
+}
+
+impl SaveCompleted {
+    pub fn new(timestamp: Option<u64>, entry: &ScreenshotHistory) -> Self {
+        Self {
+            timestamp,
+            file_path: entry.file_path.clone(),
+            saved_paths: entry.saved_paths().into_iter().map(str::to_string).collect(),
+        }
+    }
+}
+
+impl LifecycleEvent for SaveCompleted {
+    const NAME: &'static str = "save-completed";
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CopiedContent {
+    Image,
+    Path,
+    File,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CopyCompleted {
+    pub timestamp: Option<u64>,
+    pub file_path: Option<String>,
+    pub content: CopiedContent,
+}
+
+impl CopyCompleted {
+    pub fn image(timestamp: Option<u64>, file_path: Option<String>) -> Self {
+        Self {
+            timestamp,
+            file_path,
+            content: CopiedContent::Image,
+        }
+    }
+}
+
+impl LifecycleEvent for CopyCompleted {
+    const NAME: &'static str = "copy-completed";
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "stage", rename_all = "snake_case")]
+pub enum UploadStage {
+    Started,
+    /// Waiting for a slot in the upload queue, then sending.
+    Uploading,
+    Shortening,
+    Completed { url: String },
+    Failed { error: String },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UploadProgress {
+    pub file_path: String,
+    #[serde(flatten)]
+    pub stage: UploadStage,
+}
+
+impl LifecycleEvent for UploadProgress {
+    const NAME: &'static str = "upload-progress";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payloads_are_versioned_and_flat() {
+        let progress = UploadProgress {
+            file_path: "/tmp/shot.png".to_string(),
+            stage: UploadStage::Completed {
+                url: "https://example.com/a".to_string(),
+            },
+        };
+        let json = serde_json::to_value(Versioned {
+            version: EVENT_VERSION,
+            payload: &progress,
+        })
+        .unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "version": 1,
+                "file_path": "/tmp/shot.png",
+                "stage": "completed",
+                "url": "https://example.com/a",
+            })
+        );
+        assert_eq!(CaptureKind::from(CaptureMode::Selector), CaptureKind::Area);
+    }
+}
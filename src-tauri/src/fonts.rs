@@ -0,0 +1,44 @@
+/// Sorted, de-duplicated family names. Names starting with '.' are private
+/// macOS system faces that can't be selected by name, so they're dropped.
+pub fn family_names<'a>(families: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut names: Vec<String> = families
+        .into_iter()
+        .map(str::trim)
+        .filter(|name| !name.is_empty() && !name.starts_with('.'))
+        .map(str::to_string)
+        .collect();
+    names.sort_by_key(|name| name.to_lowercase());
+    names.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+    names
+}
+
+/// Scans the system font directories; this reads every font file, so call it
+/// off the async runtime.
+pub fn list_installed_families() -> Vec<String> {
+    let mut db = fontdb::Database::new();
+    db.load_system_fonts();
+    family_names(
+        db.faces()
+            .filter_map(|face| face.families.first().map(|(name, _)| name.as_str())),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_family_names_sorts_and_dedups() {
+        let names = family_names([
+            "Menlo",
+            "Helvetica Neue",
+            "menlo",
+            "Menlo",
+            ".SF NS Rounded",
+            "  ",
+            "Avenir",
+        ]);
+
+        assert_eq!(names, vec!["Avenir", "Helvetica Neue", "Menlo"]);
+    }
+}
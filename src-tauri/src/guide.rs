@@ -0,0 +1,155 @@
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::annotate::{self, Corner};
+
+/// One capture in a guide, in the order it was taken.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GuideStep {
+    pub file_path: String,
+    pub caption: String,
+}
+
+/// A step-by-step guide being collected: while one is open, every saved
+/// capture is appended as the next step.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GuideSession {
+    pub title: String,
+    pub steps: Vec<GuideStep>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GuideFormat {
+    Markdown,
+    Pdf,
+}
+
+impl GuideFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            GuideFormat::Markdown => "md",
+            GuideFormat::Pdf => "pdf",
+        }
+    }
+}
+
+impl GuideSession {
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.trim().to_string(),
+            steps: Vec::new(),
+        }
+    }
+
+    pub fn add_step(&mut self, file_path: &str, caption: &str) {
+        self.steps.push(GuideStep {
+            file_path: file_path.to_string(),
+            caption: caption.trim().to_string(),
+        });
+    }
+
+    pub fn set_caption(&mut self, index: usize, caption: &str) -> Result<(), String> {
+        let step = self.steps.get_mut(index).ok_or("No such guide step")?;
+        step.caption = caption.trim().to_string();
+        Ok(())
+    }
+
+    pub fn remove_step(&mut self, index: usize) -> Result<(), String> {
+        if index >= self.steps.len() {
+            return Err("No such guide step".to_string());
+        }
+        self.steps.remove(index);
+        Ok(())
+    }
+
+    /// The open session from an earlier run, if any.
+    pub fn load() -> Option<Self> {
+        Self::path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = Self::path().ok_or("Failed to get config directory")?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create config directory: {}", e))?;
+        }
+        let contents = serde_json::to_string(self).map_err(|e| format!("Failed to serialize guide: {}", e))?;
+        std::fs::write(path, contents).map_err(|e| format!("Failed to save guide: {}", e))
+    }
+
+    /// Forgets the stored session once it is ended.
+    pub fn discard() {
+        if let Some(path) = Self::path() {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+
+    fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("snipp").join("guide.json"))
+    }
+}
+
+/// Burns the step number into the top-left corner of a capture.
+pub fn badge(png: &[u8], number: usize) -> Result<Vec<u8>, String> {
+    annotate::apply_label(png, &number.to_string(), Corner::TopLeft)
+}
+
+/// The guide as a single Markdown file. Images are inlined as data URLs so
+/// the file can be moved around on its own.
+pub fn markdown(title: &str, steps: &[(Vec<u8>, String)]) -> String {
+    let mut doc = String::new();
+    if !title.trim().is_empty() {
+        doc.push_str(&format!("# {}\n\n", title.trim()));
+    }
+    for (index, (png, caption)) in steps.iter().enumerate() {
+        let number = index + 1;
+        let caption = caption.trim();
+        if caption.is_empty() {
+            doc.push_str(&format!("## Step {}\n\n", number));
+        } else {
+            doc.push_str(&format!("## {}. {}\n\n", number, caption));
+        }
+        doc.push_str(&format!(
+            "![Step {}](data:image/png;base64,{})\n\n",
+            number,
+            base64::prelude::BASE64_STANDARD.encode(png)
+        ));
+    }
+    doc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_steps() {
+        let mut session = GuideSession::new(" Reset a password ");
+        session.add_step("/tmp/a.png", "");
+        session.add_step("/tmp/b.png", "Click Save ");
+
+        session.set_caption(0, "Open Settings").unwrap();
+        assert_eq!(session.steps[0].caption, "Open Settings");
+        assert_eq!(session.steps[1].caption, "Click Save");
+        assert!(session.set_caption(2, "x").is_err());
+
+        session.remove_step(0).unwrap();
+        assert_eq!(session.steps.len(), 1);
+        assert_eq!(session.steps[0].file_path, "/tmp/b.png");
+        assert_eq!(session.title, "Reset a password");
+    }
+
+    #[test]
+    fn test_markdown_numbers_steps_and_inlines_images() {
+        let steps = vec![(b"png".to_vec(), "Open Settings".to_string()), (b"png".to_vec(), String::new())];
+
+        let doc = markdown("Reset a password", &steps);
+
+        assert!(doc.starts_with("# Reset a password\n\n## 1. Open Settings\n\n"));
+        assert!(doc.contains("![Step 1](data:image/png;base64,cG5n)"));
+        assert!(doc.contains("## Step 2\n\n"));
+    }
+}
@@ -0,0 +1,128 @@
+#[cfg(target_os = "macos")]
+mod ffi {
+    use std::ffi::{c_char, c_void};
+
+    pub type CFTypeRef = *const c_void;
+
+    pub const UTF8_ENCODING: u32 = 0x0800_0100;
+    pub const NUMBER_DOUBLE_TYPE: isize = 13;
+
+    #[link(name = "ImageIO", kind = "framework")]
+    extern "C" {
+        pub static kCGImageDestinationLossyCompressionQuality: CFTypeRef;
+        pub fn CGImageSourceCreateWithData(data: CFTypeRef, options: CFTypeRef) -> CFTypeRef;
+        pub fn CGImageSourceCreateImageAtIndex(source: CFTypeRef, index: usize, options: CFTypeRef) -> CFTypeRef;
+        pub fn CGImageDestinationCreateWithData(
+            data: CFTypeRef,
+            kind: CFTypeRef,
+            count: usize,
+            options: CFTypeRef,
+        ) -> CFTypeRef;
+        pub fn CGImageDestinationAddImage(destination: CFTypeRef, image: CFTypeRef, properties: CFTypeRef);
+        pub fn CGImageDestinationFinalize(destination: CFTypeRef) -> bool;
+    }
+
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        pub static kCFTypeDictionaryKeyCallBacks: c_void;
+        pub static kCFTypeDictionaryValueCallBacks: c_void;
+        pub fn CFDataCreate(allocator: CFTypeRef, bytes: *const u8, length: isize) -> CFTypeRef;
+        pub fn CFDataCreateMutable(allocator: CFTypeRef, capacity: isize) -> CFTypeRef;
+        pub fn CFDataGetBytePtr(data: CFTypeRef) -> *const u8;
+        pub fn CFDataGetLength(data: CFTypeRef) -> isize;
+        pub fn CFStringCreateWithCString(allocator: CFTypeRef, string: *const c_char, encoding: u32) -> CFTypeRef;
+        pub fn CFNumberCreate(allocator: CFTypeRef, kind: isize, value: *const c_void) -> CFTypeRef;
+        pub fn CFDictionaryCreate(
+            allocator: CFTypeRef,
+            keys: *const CFTypeRef,
+            values: *const CFTypeRef,
+            count: isize,
+            key_callbacks: *const c_void,
+            value_callbacks: *const c_void,
+        ) -> CFTypeRef;
+        pub fn CFRelease(object: CFTypeRef);
+    }
+}
+
+/// Re-encodes a PNG capture as HEIC through ImageIO. `quality` is 1-100.
+#[cfg(target_os = "macos")]
+pub fn encode(png: &[u8], quality: u8) -> Result<Vec<u8>, String> {
+    let quality = quality.clamp(1, 100) as f64 / 100.0;
+    // SAFETY: every CF object created here is released before returning,
+    // and nothing is used after a null check fails.
+    unsafe {
+        let input = ffi::CFDataCreate(std::ptr::null(), png.as_ptr(), png.len() as isize);
+        let source = release_after(input, |input| ffi::CGImageSourceCreateWithData(input, std::ptr::null()));
+        if source.is_null() {
+            return Err("Failed to read image".to_string());
+        }
+        let image = release_after(source, |source| {
+            ffi::CGImageSourceCreateImageAtIndex(source, 0, std::ptr::null())
+        });
+        if image.is_null() {
+            return Err("Failed to decode image".to_string());
+        }
+
+        let output = ffi::CFDataCreateMutable(std::ptr::null(), 0);
+        let kind = ffi::CFStringCreateWithCString(std::ptr::null(), c"public.heic".as_ptr(), ffi::UTF8_ENCODING);
+        let destination = ffi::CGImageDestinationCreateWithData(output, kind, 1, std::ptr::null());
+        ffi::CFRelease(kind);
+        if destination.is_null() {
+            ffi::CFRelease(image);
+            ffi::CFRelease(output);
+            return Err("HEIC encoding isn't supported on this Mac".to_string());
+        }
+
+        let value = ffi::CFNumberCreate(
+            std::ptr::null(),
+            ffi::NUMBER_DOUBLE_TYPE,
+            &quality as *const f64 as *const std::ffi::c_void,
+        );
+        let keys = [ffi::kCGImageDestinationLossyCompressionQuality];
+        let values = [value];
+        let properties = ffi::CFDictionaryCreate(
+            std::ptr::null(),
+            keys.as_ptr(),
+            values.as_ptr(),
+            1,
+            &ffi::kCFTypeDictionaryKeyCallBacks,
+            &ffi::kCFTypeDictionaryValueCallBacks,
+        );
+        ffi::CFRelease(value);
+        ffi::CGImageDestinationAddImage(destination, image, properties);
+        ffi::CFRelease(properties);
+        ffi::CFRelease(image);
+        let finalized = ffi::CGImageDestinationFinalize(destination);
+        ffi::CFRelease(destination);
+
+        let bytes = std::slice::from_raw_parts(
+            ffi::CFDataGetBytePtr(output),
+            ffi::CFDataGetLength(output).max(0) as usize,
+        )
+        .to_vec();
+        ffi::CFRelease(output);
+        if !finalized || bytes.is_empty() {
+            return Err("Failed to encode heic".to_string());
+        }
+        Ok(bytes)
+    }
+}
+
+/// Runs `f` on `object` and releases it, passing on whatever `f` made.
+///
+/// # Safety
+/// `object` must be null or a CF object the caller owns.
+#[cfg(target_os = "macos")]
+unsafe fn release_after(object: ffi::CFTypeRef, f: impl FnOnce(ffi::CFTypeRef) -> ffi::CFTypeRef) -> ffi::CFTypeRef {
+    if object.is_null() {
+        return object;
+    }
+    let result = f(object);
+    ffi::CFRelease(object);
+    result
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn encode(_png: &[u8], _quality: u8) -> Result<Vec<u8>, String> {
+    Err("HEIC can only be encoded on macOS".to_string())
+}
@@ -1,33 +1,123 @@
 use tauri::{App, AppHandle, Emitter, Listener, Manager, State, WebviewUrl, WebviewWindowBuilder, WindowEvent};
 use tauri_plugin_clipboard_manager::ClipboardExt;
+use tauri_plugin_shell::process::{CommandChild, CommandEvent};
 use tauri_plugin_shell::ShellExt;
 use tauri_plugin_dialog::DialogExt;
-use std::path::PathBuf;
+use tauri_plugin_notification::NotificationExt;
+use tauri_plugin_opener::OpenerExt;
+use tauri_plugin_positioner::{Position, WindowExt};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
 use std::collections::HashMap;
 use base64::prelude::*;
 use chrono::{Local, TimeZone};
 
 
-type ScreenshotCache = Mutex<HashMap<String, Vec<u8>>>;
-static SCREENSHOT_CACHE: std::sync::OnceLock<ScreenshotCache> = std::sync::OnceLock::new();
-
-mod config;
-mod history;
-mod thumbnail;
+/// Frontmost app when each pending capture was taken, by capture timestamp.
+static CAPTURE_SOURCE_APPS: std::sync::OnceLock<Mutex<HashMap<u64, String>>> = std::sync::OnceLock::new();
+/// Region, display and window each pending capture came from, kept only
+/// when it ends up in a sidecar or embedded metadata.
+static CAPTURE_CONTEXTS: std::sync::OnceLock<Mutex<HashMap<u64, sidecar::CaptureContext>>> = std::sync::OnceLock::new();
+/// What `on_capture` hooks asked for, applied when the capture is saved.
+static CAPTURE_HOOK_ACTIONS: std::sync::OnceLock<Mutex<HashMap<u64, HookActions>>> = std::sync::OnceLock::new();
+
+mod accessibility;
+mod assets;
+mod backup;
+mod barcode;
+mod browser;
+mod capture_backend;
+mod click_tracker;
+mod compare;
+mod diagnostics_bundle;
+mod events;
+mod fonts;
+mod guide;
+mod heic;
+mod logging;
+mod markdown;
+mod measure;
+mod ocr;
+mod optimize;
+mod pasteboard;
+mod recording;
+mod pdf;
+mod permissions;
+mod plugins;
+mod protocol;
+mod retention;
+mod screencapture_prefs;
+mod scroll_input;
+mod selector;
+mod sidecar;
+mod system_audio;
+mod translate;
 mod tray;
-
+mod uploader;
+mod window_list;
+
+use snipp_core::capture::{CaptureError, CaptureMode};
+use snipp_core::display::{self, DisplayInfo};
+use snipp_core::error::SnippError;
+use snipp_core::highlight::{self, Click, ClickHighlight};
+use snipp_core::hooks::{self, HookActions, HookContext, HookEvent};
+use snipp_core::output::{self, OutputFormat};
+use snipp_core::watch::{self, FolderScanner, WatchFolder};
+use snipp_core::{
+    annotate, baseline, beautify, burst, cache, cloud_sync, collage, color, config, destinations, diagnostics, history, logs,
+    metadata, mirror, naming, palette, redact, region, resolve_unique_path, scrolling, shred, spill, thumbnail,
+    transform,
+};
+use cloud_sync::SyncStatus;
+use diagnostics::DiagnosticsReport;
 use config::{AppConfig, ConfigManager};
-use history::HistoryManager;
-use thumbnail::ThumbnailGenerator;
+use history::{HistoryManager, ScreenshotHistory};
+use backup::BackupSummary;
+use barcode::DetectedCode;
+use measure::{MeasurePoint, Measurement};
+use naming::DailySequence;
+use palette::{ColorSample, PaletteColor};
+use recording::{
+    AnimationFormat, AnimationOptions, RecordingInfo, RecordingLimits, RecordingManager,
+    RecordingQuality, RecordingStatus,
+};
+use cache::ScreenshotStore;
+use beautify::{BeautifyOptions, Border};
+use burst::{BurstFormat, BurstOptions, GifCaptureOptions};
+use click_tracker::ClickTracker;
+use guide::{GuideFormat, GuideSession};
+use mirror::{MirrorJob, MirrorQueue, MirrorStatus};
+use permissions::PermissionKind;
+use plugins::{PluginContext, PluginMetadata, PluginOutput};
+use region::CaptureRegion;
+use retention::ExpiredFile;
+use screencapture_prefs::ScreencapturePrefs;
+use thumbnail::{CacheUsage, ThumbnailGenerator};
+use uploader::{UploadOutcome, UploadQueue};
 
 type ConfigState = Mutex<ConfigManager>;
 type HistoryState = Mutex<HistoryManager>;
 type ThumbnailState = Mutex<ThumbnailGenerator>;
+type RecordingState = Mutex<RecordingManager>;
+/// The most recent rectangle captured by region or selector capture.
+type LastRegionState = Mutex<Option<CaptureRegion>>;
+/// Set while a region is being captured to a GIF; clearing it ends the
+/// capture early.
+type GifCaptureState = AtomicBool;
+/// Counter behind the `{seq}` filename token.
+type SequenceState = Mutex<DailySequence>;
+/// The open Quick Look preview, as the previewed path and its `qlmanage`.
+type QuickLookState = Mutex<Option<(String, CommandChild)>>;
+/// Copies waiting for the network share to be mounted again.
+type MirrorQueueState = Mutex<MirrorQueue>;
+/// The guide being collected, if one is open.
+type GuideState = Mutex<Option<GuideSession>>;
 
 #[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
 pub struct ScreenshotData {
-    pub base64_image: String,
+    /// Where the webview loads the capture from; see [`protocol`].
+    pub image_url: String,
     pub filename: String,
     pub timestamp: u64,
     pub file_path: Option<String>, // Only set when saved to disk
@@ -46,721 +136,4363 @@ fn build_screenshot_filename(timestamp: u64, suffix: Option<&str>) -> String {
     }
 }
 
-/// Appends ` (n)` when the path is taken, so same-named captures don't overwrite.
-fn resolve_unique_path(path: PathBuf) -> PathBuf {
-    if !path.exists() {
-        return path;
-    }
-
-    let parent = path.parent().map(|p| p.to_path_buf()).unwrap_or_default();
-    let stem = path
-        .file_stem()
-        .and_then(|s| s.to_str())
-        .unwrap_or("Snipp")
-        .to_string();
-    let ext = path
-        .extension()
-        .and_then(|s| s.to_str())
-        .map(|e| format!(".{}", e))
-        .unwrap_or_default();
-
-    let mut counter = 1;
-    loop {
-        let candidate = parent.join(format!("{} ({}){}", stem, counter, ext));
-        if !candidate.exists() {
-            return candidate;
+/// Name for a capture being saved, from the configured filename pattern.
+/// Only patterns that use `{seq}` advance the daily counter.
+fn saved_filename(app_handle: &AppHandle, timestamp: u64, suffix: Option<&str>) -> String {
+    let template = {
+        let config_state = app_handle.state::<ConfigState>();
+        let config = config_state.lock().unwrap();
+        config.get_config().filename_template.clone()
+    };
+    let captured_at = Local
+        .timestamp_millis_opt(timestamp as i64)
+        .single()
+        .unwrap_or_else(Local::now);
+    let seq = template.contains("{seq}").then(|| {
+        let sequence_state = app_handle.state::<SequenceState>();
+        let mut sequence = sequence_state.lock().unwrap();
+        let seq = sequence.next(captured_at.date_naive());
+        if let Err(e) = sequence.save() {
+            tracing::error!("Failed to persist filename sequence: {}", e);
         }
-        counter += 1;
-    }
+        seq
+    });
+    naming::render_filename(&template, captured_at, seq, suffix).unwrap_or_else(|e| {
+        tracing::warn!("Falling back to the default filename: {}", e);
+        build_screenshot_filename(timestamp, suffix)
+    })
 }
 
 #[tauri::command]
 async fn capture_screenshot(
     app_handle: AppHandle,
     config_state: State<'_, ConfigState>,
-) -> Result<ScreenshotData, String> {
+) -> Result<ScreenshotData, SnippError> {
     let auto_copy = config_state.lock().unwrap().get_config().auto_copy_after_capture;
-    capture(app_handle, true, auto_copy).await
+    capture(app_handle, CaptureMode::Interactive, auto_copy).await
 }
 
 #[tauri::command]
 async fn capture_full_screen(
     app_handle: AppHandle,
     config_state: State<'_, ConfigState>,
-) -> Result<ScreenshotData, String> {
+) -> Result<ScreenshotData, SnippError> {
     let auto_copy = config_state.lock().unwrap().get_config().auto_copy_after_capture;
-    capture(app_handle, false, auto_copy).await
+    capture(app_handle, CaptureMode::FullScreen, auto_copy).await
 }
 
-/// Captures the screen (interactive area selection when `interactive`, else full
-/// screen), caches the PNG, optionally copies it, and shows the preview popup.
-async fn capture(
-    app_handle: AppHandle,
-    interactive: bool,
-    auto_copy: bool,
-) -> Result<ScreenshotData, String> {
-    log::debug!("Starting screen capture (interactive={}, auto_copy={})...", interactive, auto_copy);
-
-    let timestamp = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .map(|d| d.as_millis() as u64)
-        .unwrap_or_default();
+/// Longest countdown a timed capture accepts.
+const MAX_CAPTURE_DELAY: u32 = 60;
 
-    let filename = build_screenshot_filename(timestamp, None);
-
-    let prefix = if interactive { "snipp_capture" } else { "snipp_fullscreen" };
-    let temp_path = std::env::temp_dir().join(format!("{}_{}.png", prefix, timestamp));
-    let temp_path_str = temp_path.to_string_lossy().to_string();
-
-    let mut args = vec!["-t", "png", temp_path_str.as_str()];
-    if interactive {
-        args.insert(0, "-i");
+/// Counts down `seconds` (the configured delay when omitted), emitting
+/// `capture-countdown` each second and showing it next to the menu bar
+/// icon, then captures the full screen.
+#[tauri::command]
+async fn capture_with_delay(
+    app_handle: AppHandle,
+    config_state: State<'_, ConfigState>,
+    seconds: Option<u32>,
+) -> Result<ScreenshotData, SnippError> {
+    let (auto_copy, seconds) = {
+        let config = config_state.lock().unwrap();
+        let config = config.get_config();
+        (config.auto_copy_after_capture, seconds.unwrap_or(config.default_capture_delay))
+    };
+    if seconds > MAX_CAPTURE_DELAY {
+        return Err(format!("Capture delay can be at most {} seconds", MAX_CAPTURE_DELAY).into());
     }
 
-    let shell = app_handle.shell();
-    let output = shell
-        .command("screencapture")
-        .args(args)
-        .output()
-        .await
-        .map_err(|e| format!("Failed to execute screencapture: {}", e))?;
-
-    if !output.status.success() {
-        let _ = std::fs::remove_file(&temp_path);
-        return Err("Screenshot capture was cancelled or failed".to_string());
+    let tray = app_handle.tray_by_id("main");
+    for remaining in (1..=seconds).rev() {
+        let _ = app_handle.emit("capture-countdown", remaining);
+        if let Some(tray) = &tray {
+            let _ = tray.set_title(Some(remaining.to_string()));
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
     }
-
-    let image_data = std::fs::read(&temp_path)
-        .map_err(|e| format!("Failed to read captured screenshot: {}", e))?;
-
-    let _ = std::fs::remove_file(&temp_path);
-
-    if image_data.is_empty() {
-        return Err("No image data captured".to_string());
+    let _ = app_handle.emit("capture-countdown", 0);
+    if let Some(tray) = &tray {
+        let _ = tray.set_title(None::<&str>);
     }
 
-    log::debug!("Captured {} bytes of image data", image_data.len());
-
-    let base64_image = base64::prelude::BASE64_STANDARD.encode(&image_data);
+    capture(app_handle, CaptureMode::FullScreen, auto_copy).await
+}
 
-    let cache_key = timestamp.to_string();
-    let cache = SCREENSHOT_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
-    {
-        const MAX_CACHE_ENTRIES: usize = 50;
-        let mut cache_guard = cache.lock().unwrap();
-        // Cap the cache: when full, evict the oldest entry (smallest timestamp
-        // key) before inserting the new one (FIFO).
-        if cache_guard.len() >= MAX_CACHE_ENTRIES {
-            if let Some(oldest_key) = cache_guard
-                .keys()
-                .min_by_key(|k| k.parse::<u64>().unwrap_or(u64::MAX))
-                .cloned()
-            {
-                cache_guard.remove(&oldest_key);
-                log::debug!("Cache at capacity, evicted oldest entry: {}", oldest_key);
-            }
-        }
-        cache_guard.insert(cache_key.clone(), image_data.clone());
-        log::debug!("Stored image in memory cache with key: {}", cache_key);
-    }
+#[tauri::command]
+async fn capture_with_selector(
+    app_handle: AppHandle,
+    config_state: State<'_, ConfigState>,
+) -> Result<ScreenshotData, SnippError> {
+    let auto_copy = config_state.lock().unwrap().get_config().auto_copy_after_capture;
+    capture(app_handle, CaptureMode::Selector, auto_copy).await
+}
 
-    if auto_copy {
-        if let Err(e) = write_png_bytes_to_clipboard(&app_handle, &image_data) {
-            log::error!("Auto-copy failed: {}", e);
-        } else {
-            log::debug!("Auto-copied screenshot to clipboard after capture");
+/// Captures a fixed rectangle, given directly or by the name of a region preset.
+#[tauri::command]
+async fn capture_region(
+    app_handle: AppHandle,
+    config_state: State<'_, ConfigState>,
+    region: Option<CaptureRegion>,
+    preset: Option<String>,
+) -> Result<ScreenshotData, SnippError> {
+    let explicit = region.is_some();
+    let (auto_copy, resolved, threshold_percent) = {
+        let config = config_state.lock().unwrap();
+        let config = config.get_config();
+        let resolved = region::resolve_region(region, preset.as_deref(), &config.region_presets)?;
+        (config.auto_copy_after_capture, resolved, config.regression_threshold_percent)
+    };
+    let region = resolved.ok_or("A region or region preset is required")?;
+    let mut screenshot_data =
+        capture(app_handle.clone(), CaptureMode::Region(region), auto_copy).await?;
+
+    // Only preset captures have a baseline; an explicit region overrides the preset.
+    if let (false, Some(preset)) = (explicit, preset) {
+        if let Some(file_path) =
+            check_preset_baseline(&app_handle, &preset, screenshot_data.timestamp, threshold_percent)
+                .await?
+        {
+            screenshot_data.file_path = Some(file_path);
         }
     }
 
-    let screenshot_data = ScreenshotData {
-        base64_image,
-        filename,
-        timestamp,
-        file_path: None,
-    };
-
-    show_popup_window(&app_handle, &screenshot_data).await?;
-
     Ok(screenshot_data)
 }
 
-/// Waits (up to 3s) for a freshly built window's "ready" handshake.
-async fn wait_for_window_ready(window: &tauri::WebviewWindow, ready_event: &str) {
-    let (ready_tx, ready_rx) = tokio::sync::oneshot::channel::<()>();
-    let ready_tx = std::sync::Arc::new(std::sync::Mutex::new(Some(ready_tx)));
-    let ready_tx_clone = ready_tx.clone();
+/// Diffs a cached preset capture against the preset's baseline, saves it and
+/// records the pass/fail result in history. Returns the saved path, or
+/// `None` when the preset has no baseline.
+async fn check_preset_baseline(
+    app_handle: &AppHandle,
+    preset: &str,
+    timestamp: u64,
+    threshold_percent: f64,
+) -> Result<Option<String>, String> {
+    let baseline_file = baseline::baseline_path(preset)?;
+    if !baseline_file.exists() {
+        return Ok(None);
+    }
 
-    let unlisten_id = window.listen(ready_event.to_string(), move |_event| {
-        if let Ok(mut guard) = ready_tx_clone.lock() {
-            if let Some(tx) = guard.take() {
-                let _ = tx.send(());
-            }
-        }
-    });
+    let image_data = cached_capture(app_handle, timestamp).await?;
 
-    let _ = tokio::time::timeout(tokio::time::Duration::from_secs(3), ready_rx).await;
-    window.unlisten(unlisten_id);
-}
+    let preset_name = preset.to_string();
+    let (result, image_data) = tokio::task::spawn_blocking(move || {
+        baseline::check_against_baseline(&preset_name, &baseline_file, &image_data, threshold_percent)
+            .map(|result| (result, image_data))
+    })
+    .await
+    .map_err(|e| format!("Baseline check failed: {}", e))??;
+    let Some(result) = result else {
+        return Ok(None);
+    };
 
-async fn show_popup_window(app_handle: &AppHandle, screenshot_data: &ScreenshotData) -> Result<(), String> {
-    log::debug!("Showing popup window for screenshot: {}", screenshot_data.filename);
+    let mut entry =
+        save_to_locations(app_handle, timestamp, &saved_filename(app_handle, timestamp, None), &image_data, "")
+            .await?;
+    entry.regression = Some(result.clone());
+    let file_path_str = entry.file_path.clone();
 
-    let popup_width = 320.0;
-    let popup_height = 220.0;
-    let padding = 20.0;
+    record_in_history(app_handle, timestamp, entry, image_data, &["baseline_check"]).await;
 
-    let (x_position, y_position) = if let Some(monitor) = app_handle.primary_monitor().ok().flatten() {
-        let screen_size = monitor.size();
-        let scale_factor = monitor.scale_factor();
-        let screen_height = screen_size.height as f64 / scale_factor;
-        (padding, screen_height - popup_height - padding - 50.0)
+    let title = if result.passed {
+        format!("Baseline check passed: {}", preset)
     } else {
-        (padding, 600.0)
-    };
-
-    // Reuse the live popup window; only build (and await ready) when it's missing.
-    let popup_window = match app_handle.get_webview_window("popup") {
-        Some(window) => window,
-        None => {
-            let window = WebviewWindowBuilder::new(
-                app_handle,
-                "popup",
-                WebviewUrl::App("popup.html".into())
-            )
-            .title("Screenshot Captured")
-            .inner_size(popup_width, popup_height)
-            .position(x_position, y_position)
-            .decorations(false)
-            .transparent(true)
-            .always_on_top(true)
-            .skip_taskbar(true)
-            .resizable(false)
-            .minimizable(false)
-            .maximizable(false)
-            .visible(false)
-            .disable_drag_drop_handler()
-            .build()
-            .map_err(|e| format!("Failed to create popup window: {}", e))?;
-
-            wait_for_window_ready(&window, "popup-ready").await;
-            window
-        }
+        format!("Baseline check failed: {}", preset)
     };
+    let body = format!(
+        "{:.2}% of pixels changed (threshold {:.2}%)",
+        result.changed_percent, result.threshold_percent
+    );
+    if let Err(e) = app_handle.notification().builder().title(&title).body(&body).show() {
+        tracing::error!("Failed to show notification: {}", e);
+    }
+    let _ = app_handle.emit("regression-checked", &result);
 
-    popup_window
-        .set_position(tauri::LogicalPosition::new(x_position, y_position))
-        .map_err(|e| format!("Failed to position popup: {}", e))?;
+    Ok(Some(file_path_str))
+}
 
-    // Emit before showing so the reused webview doesn't flash the previous shot.
-    popup_window.emit("screenshot-data", screenshot_data)
-        .map_err(|e| format!("Failed to emit screenshot data: {}", e))?;
+/// Captures a region preset silently and stores it as that preset's baseline.
+#[tauri::command]
+async fn set_region_baseline(
+    app_handle: AppHandle,
+    config_state: State<'_, ConfigState>,
+    preset: String,
+) -> Result<String, SnippError> {
+    let region = {
+        let config = config_state.lock().unwrap();
+        region::resolve_region(None, Some(&preset), &config.get_config().region_presets)?
+    }
+    .ok_or("A region preset is required")?;
 
-    popup_window.show()
-        .map_err(|e| format!("Failed to show popup: {}", e))?;
-    let _ = popup_window.set_focus();
+    let image_data = grab_screen(&app_handle, CaptureMode::Region(region)).await?;
 
-    log::debug!("Screenshot data emitted successfully");
+    let baseline_file = baseline::baseline_path(&preset)?;
+    if let Some(parent) = baseline_file.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| format!("Failed to create baseline directory: {}", e))?;
+    }
+    tokio::fs::write(&baseline_file, &image_data)
+        .await
+        .map_err(|e| format!("Failed to save baseline: {}", e))?;
 
-    Ok(())
+    Ok(baseline_file.to_string_lossy().to_string())
 }
 
-fn write_png_bytes_to_clipboard(app_handle: &AppHandle, png_bytes: &[u8]) -> Result<(), String> {
-    let decoded = image::load_from_memory(png_bytes)
-        .map_err(|e| format!("Failed to decode image data: {}", e))?;
-    let rgba = decoded.to_rgba8();
-    let (width, height) = rgba.dimensions();
-    let clipboard_image = tauri::image::Image::new_owned(rgba.into_raw(), width, height);
-
-    app_handle
-        .clipboard()
-        .write_image(&clipboard_image)
-        .map_err(|e| format!("Failed to write image to clipboard: {}", e))?;
-
+#[tauri::command]
+async fn clear_region_baseline(preset: String) -> Result<(), SnippError> {
+    let baseline_file = baseline::baseline_path(&preset)?;
+    if baseline_file.exists() {
+        tokio::fs::remove_file(&baseline_file)
+            .await
+            .map_err(|e| format!("Failed to remove baseline: {}", e))?;
+    }
     Ok(())
 }
 
+/// Names of the region presets that currently have a baseline.
+#[tauri::command]
+async fn list_region_baselines(config_state: State<'_, ConfigState>) -> Result<Vec<String>, SnippError> {
+    let config = config_state.lock().unwrap();
+    let mut names = Vec::new();
+    for preset in &config.get_config().region_presets {
+        if baseline::baseline_path(&preset.name)?.exists() {
+            names.push(preset.name.clone());
+        }
+    }
+    Ok(names)
+}
 
+/// Re-captures the rectangle used by the last region or selector capture.
 #[tauri::command]
-async fn copy_to_clipboard(
+async fn capture_last_region(
     app_handle: AppHandle,
-    timestamp: u64,
-) -> Result<(), String> {
-    log::debug!("Copying screenshot to clipboard from memory cache: {}", timestamp);
-    
-    let cache_key = timestamp.to_string();
-    let image_data = {
-        let cache = SCREENSHOT_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
-        let cache_guard = cache.lock().unwrap();
-        cache_guard.get(&cache_key).cloned()
-    };
-    
-    let image_data = image_data.ok_or("Screenshot data not found in memory cache")?;
-
-    write_png_bytes_to_clipboard(&app_handle, &image_data)?;
-    log::debug!("Successfully copied screenshot to clipboard");
-    Ok(())
+    config_state: State<'_, ConfigState>,
+    last_region_state: State<'_, LastRegionState>,
+) -> Result<ScreenshotData, SnippError> {
+    let auto_copy = config_state.lock().unwrap().get_config().auto_copy_after_capture;
+    let region = (*last_region_state.lock().unwrap()).ok_or("No region has been captured yet")?;
+    capture(app_handle, CaptureMode::Region(region), auto_copy).await
 }
 
+/// Captures a region to an animated GIF at the configured frame rate until
+/// `stop_gif_capture` is called or the length limit is reached. Without a
+/// region or preset the area is picked on a frozen frame first.
 #[tauri::command]
-async fn save_to_disk(
-    timestamp: u64,
+async fn capture_gif(
+    app_handle: AppHandle,
     config_state: State<'_, ConfigState>,
-    history_state: State<'_, HistoryState>,
-) -> Result<String, String> {
-    log::debug!("Saving screenshot to disk from memory cache: {}", timestamp);
-    
-    let save_location = {
+    gif_state: State<'_, GifCaptureState>,
+    region: Option<CaptureRegion>,
+    preset: Option<String>,
+) -> Result<ScreenshotData, SnippError> {
+    let (resolved, options, highlight) = {
         let config = config_state.lock().unwrap();
-        config.get_config().default_save_location.clone()
-    };
-    
-    let cache_key = timestamp.to_string();
-    let image_data = {
-        let cache = SCREENSHOT_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
-        let cache_guard = cache.lock().unwrap();
-        cache_guard.get(&cache_key).cloned()
+        let config = config.get_config();
+        let resolved = region::resolve_region(region, preset.as_deref(), &config.region_presets)?;
+        let highlight =
+            region::resolve_click_highlight(preset.as_deref(), &config.region_presets, config.click_highlight);
+        (resolved, config.gif_capture.clone(), highlight)
     };
-    
-    let image_data = image_data.ok_or("Screenshot data not found in memory cache")?;
-    
-    let filename = build_screenshot_filename(timestamp, None);
-    let file_path = resolve_unique_path(PathBuf::from(&save_location).join(&filename));
-
-    if let Some(parent) = file_path.parent() {
-        std::fs::create_dir_all(parent)
-            .map_err(|e| format!("Failed to create save directory: {}", e))?;
+    options.validate()?;
+    if gif_state.swap(true, Ordering::SeqCst) {
+        return Err("A GIF capture is already running".into());
     }
-    
-    std::fs::write(&file_path, &image_data)
-        .map_err(|e| format!("Failed to save file: {}", e))?;
-    
-    let file_path_str = file_path.to_string_lossy().to_string();
-    log::debug!("Successfully saved screenshot to: {}", file_path_str);
+    let result = record_gif(&app_handle, resolved, options, highlight).await;
+    gif_state.store(false, Ordering::SeqCst);
+    result
+}
 
-    {
-        let cache = SCREENSHOT_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
-        let mut cache_guard = cache.lock().unwrap();
-        cache_guard.remove(&cache_key);
-        log::debug!("Evicted screenshot from memory cache after save");
-    }
-    
-    {
-        let mut history = history_state.lock().unwrap();
-        if let Err(e) = history.add_screenshot(file_path_str.clone()) {
-            log::error!("Failed to add screenshot to history: {}", e);
+async fn record_gif(
+    app_handle: &AppHandle,
+    region: Option<CaptureRegion>,
+    options: GifCaptureOptions,
+    highlight: ClickHighlight,
+) -> Result<ScreenshotData, SnippError> {
+    let region = match region {
+        Some(region) => region,
+        None => {
+            let frame = grab_screen(app_handle, CaptureMode::Selector).await?;
+            select_on_frozen_frame(app_handle, &frame)
+                .await?
+                .map(|(region, _)| region)
+                .ok_or_else(|| selection_cancelled(app_handle, "GIF capture was cancelled"))?
         }
+    };
+    let timestamp = current_timestamp_millis();
+    let _ = app_handle.emit("gif-capture-started", region);
+    tracing::debug!("Capturing {:?} to a GIF at {} fps", region, options.fps);
+
+    // Snipp's windows stay up so the capture can be stopped from them.
+    let mut interval = tokio::time::interval(tokio::time::Duration::from_millis(options.frame_delay_ms() as u64));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    let gif_state = app_handle.state::<GifCaptureState>();
+    let tracker = match highlight {
+        ClickHighlight::Off => None,
+        _ => ClickTracker::start(),
+    };
+    let mut pngs = Vec::new();
+    // Two frames are the least that animate, so an early stop still waits for them.
+    while pngs.len() < options.max_frames() && (pngs.len() < 2 || gif_state.load(Ordering::SeqCst)) {
+        interval.tick().await;
+        pngs.push(grab_pixels(app_handle, CaptureMode::Region(region)).await?);
     }
-    
-    Ok(file_path_str)
+    let _ = app_handle.emit("gif-capture-stopped", pngs.len());
+    let clicks = match tracker {
+        Some(tracker) => tracker.finish().await,
+        None => Vec::new(),
+    };
+
+    let gif = tokio::task::spawn_blocking(move || {
+        let mut frames = burst::frames(&pngs, options.max_width)?;
+        // Frames come in pixels and may have been scaled down since.
+        let scale = frames[0].width() as f64 / region.width as f64;
+        let clicks: Vec<Click> = clicks
+            .into_iter()
+            .filter(|click| region.contains(click.x, click.y))
+            .map(|click| click.to_output((region.x as f64, region.y as f64), scale))
+            .collect();
+        let delay_ms = options.frame_delay_ms() as u64;
+        for (index, frame) in frames.iter_mut().enumerate() {
+            highlight::draw(frame, highlight, &clicks, index as u64 * delay_ms, scale);
+        }
+        burst::encode_gif(frames, options.frame_delay_ms())
+    })
+    .await
+    .map_err(|e| format!("GIF encoding task failed: {}", e))??;
+
+    let filename = PathBuf::from(build_screenshot_filename(timestamp, None))
+        .with_extension("gif")
+        .to_string_lossy()
+        .to_string();
+    // The clipboard only holds still images, so a GIF is never auto-copied.
+    Ok(present_capture(app_handle, timestamp, filename, gif, false).await?)
 }
 
 #[tauri::command]
-async fn delete_from_memory(timestamp: u64) -> Result<(), String> {
-    log::debug!("Deleting screenshot from memory cache: {}", timestamp);
-    
-    let cache_key = timestamp.to_string();
-    let cache = SCREENSHOT_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
-    {
-        let mut cache_guard = cache.lock().unwrap();
-        cache_guard.remove(&cache_key);
-        log::debug!("Removed screenshot from memory cache");
+async fn stop_gif_capture(gif_state: State<'_, GifCaptureState>) -> Result<(), SnippError> {
+    if !gif_state.swap(false, Ordering::SeqCst) {
+        return Err("No GIF capture is running".into());
     }
-    
     Ok(())
 }
 
+/// Shows a frozen frame in the selector overlay and returns the chosen
+/// region (in points) with the display scale, or `None` if cancelled.
+async fn select_on_frozen_frame(
+    app_handle: &AppHandle,
+    frame_png: &[u8],
+) -> Result<Option<(CaptureRegion, f64)>, String> {
+    let scale_factor = app_handle
+        .primary_monitor()
+        .ok()
+        .flatten()
+        .map(|monitor| monitor.scale_factor())
+        .unwrap_or(1.0);
+    let windows = window_list::list_windows(app_handle)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!("Window snapping unavailable: {}", e);
+            Vec::new()
+        });
+    let region = selector::select_region(app_handle, frame_png, scale_factor, windows).await?;
+    Ok(region.map(|region| (region, scale_factor)))
+}
+
+/// Visible application windows, front to back, each with a small preview
+/// unless `thumbnails` is false. A window that can't be grabbed (e.g. it
+/// closed meanwhile) is listed without one.
 #[tauri::command]
-async fn close_popup_window(app_handle: AppHandle) -> Result<(), String> {
-    // Evict any cached screenshots: callers that still need the data (editor,
-    // save, delete) have already read or removed their specific entry by now.
-    if let Some(cache) = SCREENSHOT_CACHE.get() {
-        cache.lock().unwrap().clear();
-    }
-    // Hide rather than close so the WebView stays warm for the next capture.
-    if let Some(popup_window) = app_handle.get_webview_window("popup") {
-        popup_window.hide()
-            .map_err(|e| format!("Failed to hide popup: {}", e))?;
+async fn list_windows(
+    app_handle: AppHandle,
+    thumbnails: Option<bool>,
+) -> Result<Vec<window_list::WindowThumbnail>, SnippError> {
+    let windows = window_list::list_windows(&app_handle).await?;
+    let mut listed = Vec::with_capacity(windows.len());
+    for window in windows {
+        let thumbnail = if thumbnails.unwrap_or(true) {
+            match grab_screen(&app_handle, CaptureMode::Window(window.id)).await {
+                Ok(png) => tokio::task::spawn_blocking(move || {
+                    window_list::thumbnail_data_url(&png, window_list::THUMBNAIL_SIZE)
+                })
+                .await
+                .map_err(|e| format!("Thumbnail task failed: {}", e))?
+                .map_err(|e| tracing::debug!("No thumbnail for window {}: {}", window.id, e))
+                .ok(),
+                Err(e) => {
+                    tracing::debug!("No thumbnail for window {}: {}", window.id, e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        listed.push(window_list::WindowThumbnail { window, thumbnail });
     }
-    Ok(())
+    Ok(listed)
 }
 
+/// The window picker's name for `list_windows`.
 #[tauri::command]
-async fn get_config(config_state: State<'_, ConfigState>) -> Result<AppConfig, String> {
-    let config = config_state.lock().unwrap();
-    Ok(config.get_config().clone())
+async fn list_capture_windows(
+    app_handle: AppHandle,
+    thumbnails: Option<bool>,
+) -> Result<Vec<window_list::WindowThumbnail>, SnippError> {
+    list_windows(app_handle, thumbnails).await
 }
 
+/// Captures one window from `list_capture_windows` by its id, without
+/// whatever overlaps it.
 #[tauri::command]
-async fn update_config(
+async fn capture_window(
     app_handle: AppHandle,
     config_state: State<'_, ConfigState>,
-    new_config: AppConfig,
-) -> Result<(), String> {
-    let updated_config = {
-        let mut config = config_state.lock().unwrap();
-        config.update_config(new_config)
-            .map_err(|e| format!("Failed to update config: {}", e))?;
-        config.get_config().clone()
-    };
-
-    apply_global_shortcuts(&app_handle, &updated_config)?;
-    tray::update_tray_menu(&app_handle, &updated_config)
-        .map_err(|e| format!("Failed to update tray menu: {}", e))?;
-    Ok(())
+    window_id: u32,
+) -> Result<ScreenshotData, SnippError> {
+    let auto_copy = config_state.lock().unwrap().get_config().auto_copy_after_capture;
+    capture(app_handle, CaptureMode::Window(window_id), auto_copy).await
 }
 
+/// Most frames a scrolling capture takes, in case the window never stops.
+const MAX_SCROLL_FRAMES: usize = 30;
+/// How long a window gets to redraw after each scroll.
+const SCROLL_SETTLE_MS: u64 = 300;
 
-
+/// Captures a window, scrolls it down and captures again until it stops
+/// moving, then stitches the frames into one tall image.
 #[tauri::command]
-async fn hide_window(app_handle: AppHandle) -> Result<(), String> {
-    if let Some(main_window) = app_handle.get_webview_window("main") {
-        main_window
-            .hide()
-            .map_err(|e| format!("Failed to hide main window: {}", e))?;
+async fn capture_scrolling(
+    app_handle: AppHandle,
+    config_state: State<'_, ConfigState>,
+    window_id: u32,
+) -> Result<ScreenshotData, SnippError> {
+    let auto_copy = config_state.lock().unwrap().get_config().auto_copy_after_capture;
+    let window = window_list::list_windows(&app_handle)
+        .await?
+        .into_iter()
+        .find(|window| window.id == window_id)
+        .ok_or("That window is no longer open")?;
+    let bounds = window.bounds;
+    let center_x = bounds.x as f64 + bounds.width as f64 / 2.0;
+    let center_y = bounds.y as f64 + bounds.height as f64 / 2.0;
+    // Two thirds of a window per step leaves plenty of overlap to line up.
+    let step = (bounds.height as i32 * 2 / 3).max(1);
+    tracing::debug!("Starting scrolling capture of window {} ({})", window_id, window.app_name);
+
+    let mut frames = vec![grab_screen(&app_handle, CaptureMode::Window(window_id)).await?];
+    while frames.len() < MAX_SCROLL_FRAMES {
+        scroll_input::scroll_down(center_x, center_y, step)?;
+        tokio::time::sleep(tokio::time::Duration::from_millis(SCROLL_SETTLE_MS)).await;
+        let frame = grab_screen(&app_handle, CaptureMode::Window(window_id)).await?;
+        if frames.last() == Some(&frame) {
+            break;
+        }
+        frames.push(frame);
     }
-    Ok(())
+    tracing::debug!("Stitching {} frames", frames.len());
+
+    let image_data = tokio::task::spawn_blocking(move || {
+        let icc = color::icc_profile(&frames[0]);
+        let stitched = scrolling::stitch(&frames)?;
+        color::apply_profile(stitched, icc.as_deref(), color::ColorProfileMode::Preserve)
+    })
+    .await
+    .map_err(|e| format!("Stitch task failed: {}", e))??;
+    let scale_factor = capture_scale_factor(&app_handle, CaptureMode::Window(window_id), Some(bounds));
+    let image_data = downscale_retina(&app_handle, image_data, scale_factor).await?;
+
+    let timestamp = current_timestamp_millis();
+    let filename = build_screenshot_filename(timestamp, None);
+    Ok(present_capture(&app_handle, timestamp, filename, image_data, auto_copy).await?)
 }
 
+/// Connected screens in `screencapture -D` order, main display first.
 #[tauri::command]
-async fn show_window(app_handle: AppHandle) -> Result<(), String> {
-    if let Some(main_window) = app_handle.get_webview_window("main") {
-        main_window
-            .show()
+async fn list_displays(app_handle: AppHandle) -> Result<Vec<DisplayInfo>, SnippError> {
+    Ok(connected_displays(&app_handle)?)
+}
+
+fn connected_displays(app_handle: &AppHandle) -> Result<Vec<DisplayInfo>, String> {
+    let primary = app_handle
+        .primary_monitor()
+        .map_err(|e| format!("Failed to find the main display: {}", e))?;
+    let monitors = app_handle
+        .available_monitors()
+        .map_err(|e| format!("Failed to list displays: {}", e))?;
+
+    Ok(monitors
+        .iter()
+        .enumerate()
+        .map(|(index, monitor)| {
+            let scale_factor = monitor.scale_factor();
+            let size = monitor.size();
+            let position = monitor.position();
+            DisplayInfo {
+                id: index as u32 + 1,
+                name: monitor
+                    .name()
+                    .cloned()
+                    .unwrap_or_else(|| format!("Display {}", index + 1)),
+                width: size.width,
+                height: size.height,
+                scale_factor,
+                bounds: CaptureRegion {
+                    x: (position.x as f64 / scale_factor).round() as i32,
+                    y: (position.y as f64 / scale_factor).round() as i32,
+                    width: (size.width as f64 / scale_factor).round() as u32,
+                    height: (size.height as f64 / scale_factor).round() as u32,
+                },
+                primary: primary.as_ref().is_some_and(|primary| {
+                    primary.name() == monitor.name() && primary.position() == position
+                }),
+            }
+        })
+        .collect())
+}
+
+/// Captures one screen from `list_displays` by its id.
+#[tauri::command]
+async fn capture_display(
+    app_handle: AppHandle,
+    config_state: State<'_, ConfigState>,
+    display_id: u32,
+) -> Result<ScreenshotData, SnippError> {
+    let auto_copy = config_state.lock().unwrap().get_config().auto_copy_after_capture;
+    if !connected_displays(&app_handle)?.iter().any(|display| display.id == display_id) {
+        return Err(format!("Display {} is not connected", display_id).into());
+    }
+    capture(app_handle, CaptureMode::Display(display_id), auto_copy).await
+}
+
+/// Captures every display and joins them into one image laid out like the
+/// screens are arranged.
+#[tauri::command]
+async fn capture_all_displays(
+    app_handle: AppHandle,
+    config_state: State<'_, ConfigState>,
+) -> Result<ScreenshotData, SnippError> {
+    let auto_copy = config_state.lock().unwrap().get_config().auto_copy_after_capture;
+    let timestamp = current_timestamp_millis();
+
+    let displays = connected_displays(&app_handle)?;
+    let hidden = hide_own_windows(&app_handle).await;
+    let mut captures = Vec::new();
+    let mut result = Ok(());
+    for display in displays {
+        match grab_pixels(&app_handle, CaptureMode::Display(display.id)).await {
+            Ok(png) => captures.push((display, png)),
+            Err(e) => {
+                result = Err(e);
+                break;
+            }
+        }
+    }
+    show_own_windows(hidden);
+    result?;
+    // The stitched image uses the highest scale among the screens.
+    let scale_factor = captures.iter().map(|(display, _)| display.scale_factor).fold(1.0, f64::max);
+    let image_data = tokio::task::spawn_blocking(move || {
+        let stitched = display::stitch(&captures)?;
+        let icc = color::icc_profile(&captures[0].1);
+        color::apply_profile(stitched, icc.as_deref(), color::ColorProfileMode::Preserve)
+    })
+    .await
+    .map_err(|e| format!("Stitch task failed: {}", e))??;
+    let image_data = downscale_retina(&app_handle, image_data, scale_factor).await?;
+
+    let filename = build_screenshot_filename(timestamp, None);
+    Ok(present_capture(&app_handle, timestamp, filename, image_data, auto_copy).await?)
+}
+
+/// Measures between two points on a capture, given in image pixels.
+#[tauri::command]
+async fn measure_distance(
+    start: MeasurePoint,
+    end: MeasurePoint,
+    scale_factor: Option<f64>,
+) -> Result<Measurement, SnippError> {
+    Ok(measure::measure(start, end, scale_factor.unwrap_or(1.0))?)
+}
+
+/// Freezes the screen and measures the rectangle the user drags out.
+/// Resolves to `None` if the selection is cancelled.
+#[tauri::command]
+async fn measure_on_screen(app_handle: AppHandle) -> Result<Option<Measurement>, SnippError> {
+    let frame = grab_screen(&app_handle, CaptureMode::Selector).await?;
+
+    match select_on_frozen_frame(&app_handle, &frame).await? {
+        Some((region, scale_factor)) => Ok(Some(measure::measure_region(region, scale_factor)?)),
+        None => Ok(None),
+    }
+}
+
+/// Emits `capture-cancelled` for a selection dismissed on the frozen frame,
+/// as `capture` does, and returns the matching error.
+fn selection_cancelled(app_handle: &AppHandle, message: &str) -> SnippError {
+    events::emit(
+        app_handle,
+        &events::CaptureCancelled {
+            timestamp: current_timestamp_millis(),
+            kind: events::CaptureKind::Area,
+            reason: message.to_string(),
+        },
+    );
+    SnippError::Cancelled(message.to_string())
+}
+
+/// Lets the user select an area, recognizes its text and copies it to the
+/// clipboard. No image is saved and no popup is shown.
+#[tauri::command]
+async fn capture_text(app_handle: AppHandle) -> Result<String, SnippError> {
+    let frame = grab_screen(&app_handle, CaptureMode::Selector).await?;
+    let (region, scale_factor) = select_on_frozen_frame(&app_handle, &frame)
+        .await?
+        .ok_or_else(|| selection_cancelled(&app_handle, "Text capture was cancelled"))?;
+    let cropped = tokio::task::spawn_blocking(move || selector::crop_frame(&frame, region, scale_factor))
+        .await
+        .map_err(|e| format!("Crop task failed: {}", e))??;
+
+    let result = ocr::recognize_text(&app_handle, &cropped).await?;
+
+    let (title, body) = if result.text.is_empty() {
+        ("No text found".to_string(), "Nothing was copied".to_string())
+    } else {
+        app_handle
+            .clipboard()
+            .write_text(result.text.clone())
+            .map_err(|e| format!("Failed to copy text to clipboard: {}", e))?;
+        let body = match result.lines.len() {
+            1 => "1 line copied to the clipboard".to_string(),
+            n => format!("{} lines copied to the clipboard", n),
+        };
+        ("Text copied".to_string(), body)
+    };
+    announce(&app_handle, &title);
+    if let Err(e) = app_handle.notification().builder().title(&title).body(&body).show() {
+        tracing::error!("Failed to show notification: {}", e);
+    }
+
+    Ok(result.text)
+}
+
+/// Recognizes the text in a cached capture and translates it with the
+/// configured provider.
+#[tauri::command]
+async fn translate_text(
+    app_handle: AppHandle,
+    config_state: State<'_, ConfigState>,
+    timestamp: u64,
+    target_lang: String,
+) -> Result<String, SnippError> {
+    let image_data = cached_capture(&app_handle, timestamp).await?;
+    let settings = config_state.lock().unwrap().get_config().translation.clone();
+
+    let recognized = ocr::recognize_text(&app_handle, &image_data).await?;
+    if recognized.text.is_empty() {
+        return Err("No text found in the screenshot".into());
+    }
+    Ok(translate::translate(&app_handle, &settings, &recognized.text, &target_lang).await?)
+}
+
+/// Writes the (edited) capture to a PDF with its recognized text as an
+/// invisible layer, so the file can be searched and copied from. Returns
+/// `None` if the save dialog is cancelled.
+#[tauri::command]
+async fn export_searchable_pdf(
+    app_handle: AppHandle,
+    config_state: State<'_, ConfigState>,
+    base64_image: String,
+    timestamp: u64,
+) -> Result<Option<String>, SnippError> {
+    let image_data = base64::prelude::BASE64_STANDARD
+        .decode(&base64_image)
+        .map_err(|e| format!("Failed to decode image: {}", e))?;
+    let save_location = config_state.lock().unwrap().get_config().default_save_location.clone();
+    let filename = build_screenshot_filename(timestamp, None).replace(".png", ".pdf");
+
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    app_handle
+        .dialog()
+        .file()
+        .set_title("Export Searchable PDF")
+        .set_directory(&save_location)
+        .set_file_name(filename)
+        .add_filter("PDF", &["pdf"])
+        .save_file(move |path| {
+            let _ = sender.send(path.map(|p| p.to_string()));
+        });
+    let Some(pdf_path) = receiver
+        .await
+        .map_err(|e| format!("Dialog receiver error: {}", e))?
+    else {
+        return Ok(None);
+    };
+
+    let recognized = ocr::recognize_text(&app_handle, &image_data).await?;
+    let path = PathBuf::from(&pdf_path);
+    tokio::task::spawn_blocking(move || {
+        let pdf = pdf::build_searchable_pdf(&image_data, &recognized.lines)?;
+        cloud_sync::write_file(&path, &pdf).map(|_| ())
+    })
+    .await
+    .map_err(|e| format!("PDF export task failed: {}", e))??;
+
+    tracing::debug!("Exported searchable PDF to {}", pdf_path);
+    Ok(Some(pdf_path))
+}
+
+/// Captures the whole scrollable page of the front Safari or Chrome tab by
+/// scrolling through it and stitching the viewports, then shows it like any
+/// other capture. Fixed headers repeat once per viewport.
+#[tauri::command]
+async fn capture_browser_page(
+    app_handle: AppHandle,
+    config_state: State<'_, ConfigState>,
+) -> Result<ScreenshotData, SnippError> {
+    let auto_copy = config_state.lock().unwrap().get_config().auto_copy_after_capture;
+
+    // Clicking the dashboard button puts Snipp in front; step aside so the
+    // browser is frontmost again.
+    if let Some(main_window) = app_handle.get_webview_window("main") {
+        if main_window.is_visible().unwrap_or(false) {
+            let _ = main_window.hide();
+            #[cfg(target_os = "macos")]
+            let _ = app_handle.hide();
+            tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+        }
+    }
+
+    let browser = browser::frontmost_browser(&app_handle).await?;
+    let metrics = browser::page_metrics(&app_handle, browser).await?;
+    let viewport = CaptureRegion {
+        x: metrics.screen_x.round() as i32,
+        y: metrics.screen_y.round() as i32,
+        width: metrics.viewport_width.round() as u32,
+        height: metrics.viewport_height.round() as u32,
+    };
+    viewport.validate()?;
+    let page_height = (metrics.scroll_height.round() as u32).min(browser::MAX_PAGE_HEIGHT);
+
+    let hidden = hide_own_windows(&app_handle).await;
+    let mut frames = Vec::new();
+    let mut result = Ok(());
+    for offset in browser::scroll_positions(page_height, viewport.height) {
+        result = browser::scroll_to(&app_handle, browser, offset).await;
+        if result.is_err() {
+            break;
+        }
+        // Give lazy-loaded content and smooth scrolling a moment to settle.
+        tokio::time::sleep(std::time::Duration::from_millis(250)).await;
+        match grab_pixels(&app_handle, CaptureMode::Region(viewport)).await {
+            Ok(frame) => frames.push((offset, frame)),
+            Err(e) => {
+                result = Err(e.into());
+                break;
+            }
+        }
+    }
+    show_own_windows(hidden);
+    let _ = browser::scroll_to(&app_handle, browser, metrics.scroll_y.round() as u32).await;
+    result?;
+
+    let viewport_width = viewport.width;
+    let image_data =
+        tokio::task::spawn_blocking(move || browser::stitch_frames(&frames, page_height, viewport_width))
+            .await
+            .map_err(|e| format!("Stitch task failed: {}", e))??;
+    let scale_factor = capture_scale_factor(&app_handle, CaptureMode::Region(viewport), Some(viewport));
+    let image_data = downscale_retina(&app_handle, image_data, scale_factor).await?;
+
+    let timestamp = current_timestamp_millis();
+    let filename = build_screenshot_filename(timestamp, None);
+    Ok(present_capture(&app_handle, timestamp, filename, image_data, auto_copy).await?)
+}
+
+/// Exports the (edited) capture as `name@2x.png` plus a downscaled
+/// `name.png`, for dropping into app resources or docs. Returns the written
+/// paths, or `None` if the save dialog is cancelled.
+#[tauri::command]
+async fn export_asset_pair(
+    app_handle: AppHandle,
+    config_state: State<'_, ConfigState>,
+    base64_image: String,
+    timestamp: u64,
+) -> Result<Option<Vec<String>>, SnippError> {
+    let image_data = base64::prelude::BASE64_STANDARD
+        .decode(&base64_image)
+        .map_err(|e| format!("Failed to decode image: {}", e))?;
+    let (save_location, color_profile) = {
+        let config = config_state.lock().unwrap();
+        let config = config.get_config();
+        (config.default_save_location.clone(), config.color_profile)
+    };
+
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    app_handle
+        .dialog()
+        .file()
+        .set_title("Export @1x and @2x")
+        .set_directory(&save_location)
+        .set_file_name(build_screenshot_filename(timestamp, None))
+        .add_filter("PNG", &["png"])
+        .save_file(move |path| {
+            let _ = sender.send(path.map(|p| p.to_string()));
+        });
+    let Some(chosen) = receiver
+        .await
+        .map_err(|e| format!("Dialog receiver error: {}", e))?
+    else {
+        return Ok(None);
+    };
+
+    let (one_x, two_x) = assets::asset_pair_paths(std::path::Path::new(&chosen));
+    let written = tokio::task::spawn_blocking(move || {
+        let icc = color::icc_profile(&image_data);
+        let image_data = color::apply_profile(image_data, icc.as_deref(), color_profile)?;
+        let icc = color::icc_profile(&image_data);
+        let half = color::apply_profile(
+            assets::downscale_half(&image_data)?,
+            icc.as_deref(),
+            color::ColorProfileMode::Preserve,
+        )?;
+        cloud_sync::write_file(&two_x, &image_data)?;
+        cloud_sync::write_file(&one_x, &half)?;
+        Ok::<_, String>(vec![
+            one_x.to_string_lossy().to_string(),
+            two_x.to_string_lossy().to_string(),
+        ])
+    })
+    .await
+    .map_err(|e| format!("Export task failed: {}", e))??;
+
+    tracing::debug!("Exported asset pair: {:?}", written);
+    Ok(Some(written))
+}
+
+/// Changes the open guide, persists it and tells the UI. Does nothing when
+/// no guide is open.
+fn update_guide(
+    app_handle: &AppHandle,
+    change: impl FnOnce(&mut GuideSession) -> Result<(), String>,
+) -> Result<(), String> {
+    let guide_state = app_handle.state::<GuideState>();
+    let mut guide = guide_state.lock().unwrap();
+    let Some(session) = guide.as_mut() else {
+        return Ok(());
+    };
+    change(session)?;
+    if let Err(e) = session.save() {
+        tracing::error!("Failed to persist guide: {}", e);
+    }
+    let _ = app_handle.emit("guide-updated", &*guide);
+    Ok(())
+}
+
+/// Opens a guide session: captures saved from now on become its steps.
+#[tauri::command]
+async fn start_guide(
+    app_handle: AppHandle,
+    guide_state: State<'_, GuideState>,
+    title: String,
+) -> Result<GuideSession, SnippError> {
+    let session = GuideSession::new(&title);
+    session.save()?;
+    *guide_state.lock().unwrap() = Some(session.clone());
+    let _ = app_handle.emit("guide-updated", Some(&session));
+    Ok(session)
+}
+
+#[tauri::command]
+async fn get_guide(guide_state: State<'_, GuideState>) -> Result<Option<GuideSession>, SnippError> {
+    Ok(guide_state.lock().unwrap().clone())
+}
+
+#[tauri::command]
+async fn set_guide_caption(app_handle: AppHandle, index: usize, caption: String) -> Result<(), SnippError> {
+    Ok(update_guide(&app_handle, |guide| guide.set_caption(index, &caption))?)
+}
+
+#[tauri::command]
+async fn remove_guide_step(app_handle: AppHandle, index: usize) -> Result<(), SnippError> {
+    Ok(update_guide(&app_handle, |guide| guide.remove_step(index))?)
+}
+
+/// Closes the guide session. Its captures stay in history.
+#[tauri::command]
+async fn end_guide(app_handle: AppHandle, guide_state: State<'_, GuideState>) -> Result<(), SnippError> {
+    *guide_state.lock().unwrap() = None;
+    GuideSession::discard();
+    let _ = app_handle.emit("guide-updated", None::<GuideSession>);
+    Ok(())
+}
+
+/// Writes the open guide as one Markdown or PDF document, each capture
+/// badged with its step number. Returns `None` if the save dialog is
+/// cancelled.
+#[tauri::command]
+async fn export_guide(
+    app_handle: AppHandle,
+    config_state: State<'_, ConfigState>,
+    guide_state: State<'_, GuideState>,
+    format: GuideFormat,
+) -> Result<Option<String>, SnippError> {
+    let session = guide_state.lock().unwrap().clone().ok_or("No guide is open")?;
+    if session.steps.is_empty() {
+        return Err("The guide has no steps yet".into());
+    }
+    let save_location = config_state.lock().unwrap().get_config().default_save_location.clone();
+    let name = if session.title.is_empty() { "Guide" } else { session.title.as_str() };
+
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    let dialog = app_handle
+        .dialog()
+        .file()
+        .set_title("Export Guide")
+        .set_directory(&save_location)
+        .set_file_name(format!("{}.{}", name.replace('/', "-"), format.extension()));
+    let dialog = match format {
+        GuideFormat::Markdown => dialog.add_filter("Markdown", &["md"]),
+        GuideFormat::Pdf => dialog.add_filter("PDF", &["pdf"]),
+    };
+    dialog.save_file(move |path| {
+        let _ = sender.send(path.map(|p| p.to_string()));
+    });
+    let Some(guide_path) = receiver
+        .await
+        .map_err(|e| format!("Dialog receiver error: {}", e))?
+    else {
+        return Ok(None);
+    };
+
+    let path = PathBuf::from(&guide_path);
+    tokio::task::spawn_blocking(move || {
+        let mut steps = Vec::with_capacity(session.steps.len());
+        for (index, step) in session.steps.iter().enumerate() {
+            let image_data = std::fs::read(&step.file_path)
+                .map_err(|e| format!("Failed to read step {} ({}): {}", index + 1, step.file_path, e))?;
+            steps.push((guide::badge(&image_data, index + 1)?, step.caption.clone()));
+        }
+        let document = match format {
+            GuideFormat::Markdown => guide::markdown(&session.title, &steps).into_bytes(),
+            GuideFormat::Pdf => pdf::build_guide_pdf(&session.title, &steps)?,
+        };
+        cloud_sync::write_file(&path, &document).map(|_| ())
+    })
+    .await
+    .map_err(|e| format!("Guide export task failed: {}", e))??;
+
+    tracing::debug!("Exported guide to {}", guide_path);
+    Ok(Some(guide_path))
+}
+
+/// Writes a Markdown document for the given captures: each one is copied
+/// into an assets folder beside it and captioned with its capture time and
+/// note. Returns `None` if the save dialog is cancelled.
+#[tauri::command]
+async fn export_markdown(
+    app_handle: AppHandle,
+    config_state: State<'_, ConfigState>,
+    history_state: State<'_, HistoryState>,
+    file_paths: Vec<String>,
+) -> Result<Option<String>, SnippError> {
+    if file_paths.is_empty() {
+        return Err("No screenshots to export".into());
+    }
+    let save_location = config_state.lock().unwrap().get_config().default_save_location.clone();
+    let sources: Vec<(String, Option<ScreenshotHistory>)> = {
+        let history = history_state.lock().unwrap();
+        file_paths
+            .into_iter()
+            .map(|file_path| {
+                let entry = history
+                    .get_history()
+                    .screenshots
+                    .iter()
+                    .find(|entry| entry.file_path == file_path)
+                    .cloned();
+                (file_path, entry)
+            })
+            .collect()
+    };
+
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    app_handle
+        .dialog()
+        .file()
+        .set_title("Export Markdown")
+        .set_directory(&save_location)
+        .set_file_name("Screenshots.md")
+        .add_filter("Markdown", &["md"])
+        .save_file(move |path| {
+            let _ = sender.send(path.map(|p| p.to_string()));
+        });
+    let Some(document_path) = receiver
+        .await
+        .map_err(|e| format!("Dialog receiver error: {}", e))?
+    else {
+        return Ok(None);
+    };
+
+    let path = PathBuf::from(&document_path);
+    tokio::task::spawn_blocking(move || {
+        let assets_dir = markdown::assets_dir(&path);
+        std::fs::create_dir_all(&assets_dir).map_err(|e| format!("Failed to create assets folder: {}", e))?;
+        let mut images = Vec::with_capacity(sources.len());
+        for (file_path, entry) in sources {
+            let source = PathBuf::from(&file_path);
+            let name = source.file_name().ok_or("Screenshot has no file name")?;
+            let target = resolve_unique_path(assets_dir.join(name));
+            std::fs::copy(&source, &target).map_err(|e| format!("Failed to copy {}: {}", file_path, e))?;
+            images.push(markdown::MarkdownImage {
+                filename: target
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                captured_at: entry.as_ref().map(|entry| entry.timestamp.with_timezone(&Local)),
+                note: entry.map(|entry| entry.note).unwrap_or_default(),
+            });
+        }
+        let title = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let assets_name = assets_dir
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let document = markdown::document(&title, &assets_name, &images);
+        cloud_sync::write_file(&path, document.as_bytes()).map(|_| ())
+    })
+    .await
+    .map_err(|e| format!("Markdown export task failed: {}", e))??;
+
+    tracing::debug!("Exported Markdown to {}", document_path);
+    Ok(Some(document_path))
+}
+
+/// Hides Snipp's visible windows (except the region selector) so they
+/// don't end up in a capture, and returns them to show again afterwards.
+/// Multi-frame captures call this once around their `grab_pixels` loop.
+async fn hide_own_windows(app_handle: &AppHandle) -> Vec<tauri::WebviewWindow> {
+    let hidden: Vec<tauri::WebviewWindow> = app_handle
+        .webview_windows()
+        .into_values()
+        .filter(|window| window.label() != "selector" && window.is_visible().unwrap_or(false))
+        .filter(|window| window.hide().is_ok())
+        .collect();
+    if !hidden.is_empty() {
+        // Give the window server a moment to take them off screen.
+        tokio::time::sleep(std::time::Duration::from_millis(150)).await;
+    }
+    hidden
+}
+
+/// Grabs the screen for `mode` as a PNG, in-process when the platform's
+/// native backend handles the mode and through `screencapture` otherwise.
+async fn grab_screen(app_handle: &AppHandle, mode: CaptureMode) -> Result<Vec<u8>, CaptureError> {
+    let hidden = if mode.hides_own_windows() {
+        hide_own_windows(app_handle).await
+    } else {
+        Vec::new()
+    };
+    let result = grab_pixels(app_handle, mode).await;
+    show_own_windows(hidden);
+    result
+}
+
+/// Shows the windows `hide_own_windows` took off screen.
+fn show_own_windows(hidden: Vec<tauri::WebviewWindow>) {
+    for window in hidden {
+        let _ = window.show();
+    }
+}
+
+/// Captures `mode` with the native backend when it can, otherwise with
+/// `screencapture`, leaving Snipp's windows as they are.
+async fn grab_pixels(app_handle: &AppHandle, mode: CaptureMode) -> Result<Vec<u8>, CaptureError> {
+    match capture_backend::native().filter(|backend| backend.supports(mode)) {
+        Some(backend) => {
+            tracing::debug!("Capturing {:?} with {}", mode, backend.name());
+            tokio::task::spawn_blocking(move || backend.capture(mode))
+                .await
+                .map_err(|e| CaptureError::Failed(format!("Capture task failed: {}", e)))?
+        }
+        None => run_screencapture(app_handle, mode).await,
+    }
+}
+
+/// Runs `screencapture` for `mode` and returns the PNG it wrote.
+async fn run_screencapture(app_handle: &AppHandle, mode: CaptureMode) -> Result<Vec<u8>, CaptureError> {
+    let temp_path = std::env::temp_dir().join(format!(
+        "{}_{}.png",
+        mode.temp_prefix(),
+        current_timestamp_millis()
+    ));
+    let temp_path_str = temp_path.to_string_lossy().to_string();
+
+    let window_shadow = app_handle.state::<ConfigState>().lock().unwrap().get_config().window_shadow;
+    let args = mode.screencapture_args(&temp_path_str, window_shadow);
+
+    let shell = app_handle.shell();
+    let output = shell
+        .command("screencapture")
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| CaptureError::Failed(format!("Failed to execute screencapture: {}", e)))?;
+
+    // screencapture ends without a file both when the user dismisses it and
+    // when it isn't allowed to record; only the permission tells them apart.
+    let dismissed = || {
+        if permissions::is_granted(PermissionKind::ScreenRecording) {
+            CaptureError::Cancelled
+        } else {
+            CaptureError::PermissionDenied
+        }
+    };
+    if !output.status.success() {
+        let _ = tokio::fs::remove_file(&temp_path).await;
+        return Err(dismissed());
+    }
+
+    let image_data = match tokio::fs::read(&temp_path).await {
+        Ok(image_data) => image_data,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Err(dismissed()),
+        Err(e) => return Err(CaptureError::Failed(format!("Failed to read captured screenshot: {}", e))),
+    };
+
+    let _ = tokio::fs::remove_file(&temp_path).await;
+
+    if image_data.is_empty() {
+        return Err(CaptureError::Failed("No image data captured".to_string()));
+    }
+
+    Ok(image_data)
+}
+
+/// Captures the screen per `mode`, caches the PNG, optionally copies it, and
+/// shows the preview popup.
+async fn capture(
+    app_handle: AppHandle,
+    mode: CaptureMode,
+    auto_copy: bool,
+) -> Result<ScreenshotData, SnippError> {
+    // Windows has no system area picker, so it always gets Snipp's overlay.
+    let selection_overlay = app_handle.state::<ConfigState>().lock().unwrap().get_config().selection_overlay;
+    let mode = match mode {
+        CaptureMode::Interactive if selection_overlay || cfg!(target_os = "windows") => CaptureMode::Selector,
+        mode => mode,
+    };
+    tracing::debug!("Starting screen capture (mode={:?}, auto_copy={})...", mode, auto_copy);
+
+    let timestamp = current_timestamp_millis();
+    let kind = events::CaptureKind::from(mode);
+    events::emit(&app_handle, &events::CaptureStarted { timestamp, kind });
+    let failed = |error: SnippError| {
+        events::emit(&app_handle, &events::CaptureCancelled { timestamp, kind, reason: error.to_string() });
+        error
+    };
+
+    let filename = build_screenshot_filename(timestamp, None);
+
+    let (wants_source_app, write_sidecar, embed_metadata) = {
+        let config_state = app_handle.state::<ConfigState>();
+        let config = config_state.lock().unwrap();
+        let config = config.get_config();
+        let embed_metadata = config.metadata == metadata::MetadataMode::Embed;
+        (
+            config.write_sidecar || config.restore_focus_after_capture || !config.hook_script.is_empty() || embed_metadata,
+            config.write_sidecar,
+            embed_metadata,
+        )
+    };
+    let mut context = sidecar::CaptureContext::default();
+    if wants_source_app {
+        // Asked before capturing, while the app being captured is still in front.
+        match browser::frontmost_app(&app_handle).await {
+            Ok(name) => {
+                CAPTURE_SOURCE_APPS
+                    .get_or_init(|| Mutex::new(HashMap::new()))
+                    .lock()
+                    .unwrap()
+                    .insert(timestamp, name);
+            }
+            Err(e) => tracing::warn!("Failed to find the frontmost app: {}", e),
+        }
+    }
+    if write_sidecar {
+        match browser::frontmost_window(&app_handle).await {
+            Ok((bundle_id, title)) => {
+                context.bundle_id = Some(bundle_id).filter(|id| !id.is_empty());
+                context.window_title = Some(title).filter(|title| !title.is_empty());
+            }
+            Err(e) => tracing::warn!("{}", e),
+        }
+    }
+
+    let image_data = grab_screen(&app_handle, mode)
+        .await
+        .map_err(|e| failed(e.into()))?;
+
+    let (image_data, captured_region) = match mode {
+        CaptureMode::Selector => {
+            let (region, scale_factor) = select_on_frozen_frame(&app_handle, &image_data)
+                .await
+                .map_err(|e| failed(e.into()))?
+                .ok_or_else(|| failed(CaptureError::Cancelled.into()))?;
+            let cropped = tokio::task::spawn_blocking(move || {
+                // Keep the display profile until save decides what to do with it.
+                let cropped = selector::crop_frame(&image_data, region, scale_factor)?;
+                let icc = color::icc_profile(&image_data);
+                color::apply_profile(cropped, icc.as_deref(), color::ColorProfileMode::Preserve)
+            })
+            .await
+            .map_err(|e| format!("Crop task failed: {}", e))??;
+            (cropped, Some(region))
+        }
+        CaptureMode::Region(region) => (image_data, Some(region)),
+        _ => (image_data, None),
+    };
+
+    if let Some(region) = captured_region {
+        *app_handle.state::<LastRegionState>().lock().unwrap() = Some(region);
+        if let Err(e) = app_handle.state::<ConfigState>().lock().unwrap().set_last_region(region) {
+            tracing::warn!("Failed to remember the last region: {}", e);
+        }
+    }
+
+    let display = source_display(&app_handle, mode, captured_region);
+    if write_sidecar || embed_metadata {
+        context.region = captured_region;
+        context.display_id = display.as_ref().map(|display| display.id);
+        context.display_name = display.as_ref().map(|display| display.name.clone());
+        CAPTURE_CONTEXTS
+            .get_or_init(|| Mutex::new(HashMap::new()))
+            .lock()
+            .unwrap()
+            .insert(timestamp, context);
+    }
+    let scale_factor = display.map_or(1.0, |display| display.scale_factor);
+    let image_data = downscale_retina(&app_handle, image_data, scale_factor).await?;
+
+    tracing::debug!("Captured {} bytes of image data", image_data.len());
+    events::emit(
+        &app_handle,
+        &events::CaptureCompleted {
+            timestamp,
+            kind,
+            filename: filename.clone(),
+            bytes: image_data.len(),
+        },
+    );
+
+    Ok(present_capture(&app_handle, timestamp, filename, image_data, auto_copy).await?)
+}
+
+/// Pixels per point of the screen a capture came from.
+fn capture_scale_factor(app_handle: &AppHandle, mode: CaptureMode, region: Option<CaptureRegion>) -> f64 {
+    source_display(app_handle, mode, region).map_or(1.0, |display| display.scale_factor)
+}
+
+/// The screen a capture came from: the display it names, else the one
+/// under `region`, else the main display.
+fn source_display(app_handle: &AppHandle, mode: CaptureMode, region: Option<CaptureRegion>) -> Option<DisplayInfo> {
+    let displays = connected_displays(app_handle).unwrap_or_default();
+    let display = match (mode, region) {
+        (CaptureMode::Display(id), _) => displays.iter().find(|display| display.id == id),
+        (_, Some(region)) => display::display_at(&displays, region),
+        _ => None,
+    };
+    display
+        .or_else(|| displays.iter().find(|display| display.primary))
+        .cloned()
+}
+
+/// Shrinks a Retina capture to 1x when `downscale_retina` is on.
+async fn downscale_retina(app_handle: &AppHandle, image_data: Vec<u8>, scale_factor: f64) -> Result<Vec<u8>, String> {
+    if !app_handle.state::<ConfigState>().lock().unwrap().get_config().downscale_retina {
+        return Ok(image_data);
+    }
+    tokio::task::spawn_blocking(move || display::downscale_to_points(image_data, scale_factor))
+        .await
+        .map_err(|e| format!("Downscale task failed: {}", e))?
+}
+
+/// Runs `f` on the capture store off the async runtime, since reads and
+/// writes may go to spilled captures on disk.
+async fn with_store<T: Send + 'static>(
+    app_handle: &AppHandle,
+    f: impl FnOnce(&ScreenshotStore) -> T + Send + 'static,
+) -> Result<T, String> {
+    let app_handle = app_handle.clone();
+    tokio::task::spawn_blocking(move || f(&app_handle.state::<ScreenshotStore>()))
+        .await
+        .map_err(|e| format!("Cache task failed: {}", e))
+}
+
+/// A pending capture from the store.
+async fn cached_capture(app_handle: &AppHandle, timestamp: u64) -> Result<Vec<u8>, SnippError> {
+    with_store(app_handle, move |store| store.get(timestamp))
+        .await?
+        .ok_or_else(SnippError::not_cached)
+}
+
+/// Caches a capture; going over the memory limit spills older captures to
+/// disk.
+async fn cache_capture(app_handle: &AppHandle, timestamp: u64, image_data: Vec<u8>) -> Result<(), String> {
+    with_store(app_handle, move |store| store.insert(timestamp, image_data)).await
+}
+
+/// Caches a freshly captured PNG, optionally copies it, and shows the
+/// preview popup.
+async fn present_capture(
+    app_handle: &AppHandle,
+    timestamp: u64,
+    filename: String,
+    image_data: Vec<u8>,
+    auto_copy: bool,
+) -> Result<ScreenshotData, String> {
+    cache_capture(app_handle, timestamp, image_data.clone()).await?;
+
+    let source_app = CAPTURE_SOURCE_APPS
+        .get()
+        .and_then(|source_apps| source_apps.lock().unwrap().get(&timestamp).cloned());
+    let context = HookContext {
+        filename: filename.clone(),
+        source_app: source_app.unwrap_or_default(),
+        ..HookContext::default()
+    };
+    let actions = run_hook(app_handle, HookEvent::Capture, context).await;
+    if actions != HookActions::default() {
+        CAPTURE_HOOK_ACTIONS
+            .get_or_init(|| Mutex::new(HashMap::new()))
+            .lock()
+            .unwrap()
+            .insert(timestamp, actions);
+    }
+
+    if auto_copy {
+        if let Err(e) = write_png_bytes_to_clipboard(app_handle, image_data).await {
+            tracing::error!("Auto-copy failed: {}", e);
+        } else {
+            tracing::debug!("Auto-copied screenshot to clipboard after capture");
+            events::emit(app_handle, &events::CopyCompleted::image(Some(timestamp), None));
+        }
+    }
+
+    let mut screenshot_data = ScreenshotData {
+        image_url: protocol::screenshot_url(timestamp),
+        filename,
+        timestamp,
+        file_path: None,
+    };
+
+    let (show_popup, tray_popover, restore_focus) = {
+        let config_state = app_handle.state::<ConfigState>();
+        let config = config_state.lock().unwrap();
+        let config = config.get_config();
+        (config.show_capture_popup, config.tray_popover, config.restore_focus_after_capture)
+    };
+    if show_popup {
+        if tray_popover {
+            show_tray_popover(app_handle, &screenshot_data).await?;
+        } else {
+            show_popup_window(app_handle, &screenshot_data).await?;
+        }
+        if restore_focus {
+            restore_source_app_focus(app_handle, timestamp).await;
+        }
+    } else {
+        screenshot_data.file_path = Some(save_cached(app_handle, timestamp, None).await?);
+    }
+    announce(app_handle, &accessibility::capture_message(!show_popup, auto_copy));
+
+    Ok(screenshot_data)
+}
+
+/// Re-activates the app that was in front when the capture was taken, so
+/// the popup doesn't interrupt typing. Captures without one are left alone.
+async fn restore_source_app_focus(app_handle: &AppHandle, timestamp: u64) {
+    let source_app = CAPTURE_SOURCE_APPS
+        .get()
+        .and_then(|source_apps| source_apps.lock().unwrap().get(&timestamp).cloned());
+    let Some(source_app) = source_app else {
+        return;
+    };
+    if let Err(e) = browser::activate_app(app_handle, &source_app).await {
+        tracing::warn!("{}", e);
+    }
+}
+
+/// Posts a screen reader announcement unless turned off in settings.
+fn announce(app_handle: &AppHandle, message: &str) {
+    if app_handle.state::<ConfigState>().lock().unwrap().get_config().announce_actions {
+        accessibility::announce(app_handle, message);
+    }
+}
+
+/// Runs the configured hook script's handler for `event`. A broken script
+/// is logged and ignored so it never blocks a capture or save.
+async fn run_hook(app_handle: &AppHandle, event: HookEvent, context: HookContext) -> HookActions {
+    let script_path = app_handle.state::<ConfigState>().lock().unwrap().get_config().hook_script.clone();
+    if script_path.trim().is_empty() {
+        return HookActions::default();
+    }
+    let result = tokio::task::spawn_blocking(move || {
+        let source =
+            std::fs::read_to_string(&script_path).map_err(|e| format!("Failed to read hook script: {}", e))?;
+        hooks::run(&source, event, &context)
+    })
+    .await
+    .map_err(|e| format!("Hook task failed: {}", e))
+    .and_then(|result| result);
+    result.unwrap_or_else(|e| {
+        tracing::error!("{}", e);
+        HookActions::default()
+    })
+}
+
+/// Parses the hook script at `path`, so settings can flag mistakes before
+/// the next capture.
+#[tauri::command]
+async fn check_hook_script(path: String) -> Result<(), SnippError> {
+    Ok(tokio::task::spawn_blocking(move || {
+        let source = std::fs::read_to_string(&path).map_err(|e| format!("Failed to read hook script: {}", e))?;
+        hooks::validate(&source)
+    })
+    .await
+    .map_err(|e| format!("Hook task failed: {}", e))??)
+}
+
+fn current_timestamp_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// Waits (up to 3s) for a freshly built window's "ready" handshake.
+async fn wait_for_window_ready(window: &tauri::WebviewWindow, ready_event: &str) {
+    let (ready_tx, ready_rx) = tokio::sync::oneshot::channel::<()>();
+    let ready_tx = std::sync::Arc::new(std::sync::Mutex::new(Some(ready_tx)));
+    let ready_tx_clone = ready_tx.clone();
+
+    let unlisten_id = window.listen(ready_event.to_string(), move |_event| {
+        if let Ok(mut guard) = ready_tx_clone.lock() {
+            if let Some(tx) = guard.take() {
+                let _ = tx.send(());
+            }
+        }
+    });
+
+    let _ = tokio::time::timeout(tokio::time::Duration::from_secs(3), ready_rx).await;
+    window.unlisten(unlisten_id);
+}
+
+/// The monitor the pointer is on, so the popup shows up where the user is
+/// looking; the primary monitor when the pointer can't be found.
+fn monitor_at_cursor(app_handle: &AppHandle) -> Option<tauri::Monitor> {
+    let cursor = capture_backend::native().and_then(|backend| backend.cursor_position());
+    let under_cursor = cursor.and_then(|(x, y)| {
+        app_handle.available_monitors().ok()?.into_iter().find(|monitor| {
+            let scale_factor = monitor.scale_factor();
+            let origin = monitor.position().to_logical::<f64>(scale_factor);
+            let size = monitor.size().to_logical::<f64>(scale_factor);
+            x >= origin.x && x < origin.x + size.width && y >= origin.y && y < origin.y + size.height
+        })
+    });
+    under_cursor.or_else(|| app_handle.primary_monitor().ok().flatten())
+}
+
+async fn show_popup_window(app_handle: &AppHandle, screenshot_data: &ScreenshotData) -> Result<(), String> {
+    tracing::debug!("Showing popup window for screenshot: {}", screenshot_data.filename);
+
+    let popup_width = 320.0;
+    let popup_height = 220.0;
+    let padding = 20.0;
+
+    let (x_position, y_position) = if let Some(monitor) = monitor_at_cursor(app_handle) {
+        let scale_factor = monitor.scale_factor();
+        let origin = monitor.position().to_logical::<f64>(scale_factor);
+        let screen_height = monitor.size().height as f64 / scale_factor;
+        (origin.x + padding, origin.y + screen_height - popup_height - padding - 50.0)
+    } else {
+        (padding, 600.0)
+    };
+
+    // Reuse the live popup window; only build (and await ready) when it's missing.
+    let popup_window = match app_handle.get_webview_window("popup") {
+        Some(window) => window,
+        None => {
+            let window = WebviewWindowBuilder::new(
+                app_handle,
+                "popup",
+                WebviewUrl::App("popup.html".into())
+            )
+            .title("Screenshot Captured")
+            .inner_size(popup_width, popup_height)
+            .position(x_position, y_position)
+            .decorations(false)
+            .transparent(true)
+            .always_on_top(true)
+            .skip_taskbar(true)
+            .resizable(false)
+            .minimizable(false)
+            .maximizable(false)
+            .visible(false)
+            .disable_drag_drop_handler()
+            .build()
+            .map_err(|e| format!("Failed to create popup window: {}", e))?;
+
+            wait_for_window_ready(&window, "popup-ready").await;
+            window
+        }
+    };
+
+    popup_window
+        .set_position(tauri::LogicalPosition::new(x_position, y_position))
+        .map_err(|e| format!("Failed to position popup: {}", e))?;
+
+    // Emit before showing so the reused webview doesn't flash the previous shot.
+    popup_window.emit("screenshot-data", screenshot_data)
+        .map_err(|e| format!("Failed to emit screenshot data: {}", e))?;
+
+    popup_window.show()
+        .map_err(|e| format!("Failed to show popup: {}", e))?;
+    let _ = popup_window.set_focus();
+
+    tracing::debug!("Screenshot data emitted successfully");
+
+    Ok(())
+}
+
+/// Shows the capture in a small panel hanging from the menu bar icon, or
+/// in the top-right corner until the icon's position is known.
+async fn show_tray_popover(app_handle: &AppHandle, screenshot_data: &ScreenshotData) -> Result<(), String> {
+    let popover_width = 280.0;
+    let popover_height = 210.0;
+
+    let popover = match app_handle.get_webview_window("tray_popover") {
+        Some(window) => window,
+        None => {
+            let window = WebviewWindowBuilder::new(
+                app_handle,
+                "tray_popover",
+                WebviewUrl::App("popover.html".into())
+            )
+            .title("Latest Capture")
+            .inner_size(popover_width, popover_height)
+            .decorations(false)
+            .transparent(true)
+            .always_on_top(true)
+            .skip_taskbar(true)
+            .resizable(false)
+            .minimizable(false)
+            .maximizable(false)
+            .visible(false)
+            .disable_drag_drop_handler()
+            .build()
+            .map_err(|e| format!("Failed to create tray popover: {}", e))?;
+
+            wait_for_window_ready(&window, "popup-ready").await;
+            window
+        }
+    };
+
+    if popover.move_window(Position::TrayBottomCenter).is_err() {
+        popover
+            .move_window(Position::TopRight)
+            .map_err(|e| format!("Failed to position tray popover: {}", e))?;
+    }
+
+    popover.emit("screenshot-data", screenshot_data)
+        .map_err(|e| format!("Failed to emit screenshot data: {}", e))?;
+    popover.show()
+        .map_err(|e| format!("Failed to show tray popover: {}", e))?;
+    let _ = popover.set_focus();
+    Ok(())
+}
+
+/// Puts a capture on the clipboard as image data through the clipboard
+/// plugin, keeping its transparency. Decoding runs off the async runtime.
+async fn write_png_bytes_to_clipboard(app_handle: &AppHandle, png_bytes: Vec<u8>) -> Result<(), String> {
+    let (rgba, width, height) = tokio::task::spawn_blocking(move || {
+        let rgba = image::load_from_memory(&png_bytes)
+            .map_err(|e| format!("Failed to decode image data: {}", e))?
+            .to_rgba8();
+        let (width, height) = rgba.dimensions();
+        Ok::<_, String>((rgba.into_raw(), width, height))
+    })
+    .await
+    .map_err(|e| format!("Clipboard task failed: {}", e))??;
+    let clipboard_image = tauri::image::Image::new_owned(rgba, width, height);
+
+    app_handle
+        .clipboard()
+        .write_image(&clipboard_image)
+        .map_err(|e| format!("Failed to write image to clipboard: {}", e))?;
+
+    Ok(())
+}
+
+
+fn encode_rgba_as_png(rgba: Vec<u8>, width: u32, height: u32) -> Result<Vec<u8>, String> {
+    let buffer = image::RgbaImage::from_raw(width, height, rgba)
+        .ok_or("Clipboard image has an unexpected pixel layout")?;
+
+    let mut png_bytes = Vec::new();
+    image::DynamicImage::ImageRgba8(buffer)
+        .write_to(&mut std::io::Cursor::new(&mut png_bytes), image::ImageOutputFormat::Png)
+        .map_err(|e| format!("Failed to encode clipboard image: {}", e))?;
+
+    Ok(png_bytes)
+}
+
+/// Pulls an image off the system clipboard into the cache and shows it in
+/// the capture popup, so it can be annotated and saved like a capture.
+#[tauri::command]
+async fn import_from_clipboard(app_handle: AppHandle) -> Result<ScreenshotData, SnippError> {
+    tracing::debug!("Importing image from clipboard");
+
+    let (rgba, width, height) = {
+        let clipboard_image = app_handle
+            .clipboard()
+            .read_image()
+            .map_err(|e| format!("No image found on the clipboard: {}", e))?;
+        (
+            clipboard_image.rgba().to_vec(),
+            clipboard_image.width(),
+            clipboard_image.height(),
+        )
+    };
+
+    if width == 0 || height == 0 {
+        return Err("No image found on the clipboard".into());
+    }
+
+    let png_bytes = tokio::task::spawn_blocking(move || encode_rgba_as_png(rgba, width, height))
+        .await
+        .map_err(|e| format!("Clipboard import task failed: {}", e))??;
+
+    let timestamp = current_timestamp_millis();
+    let filename = build_screenshot_filename(timestamp, None);
+    // The image came from the clipboard, so copying it back would be a no-op.
+    Ok(present_capture(&app_handle, timestamp, filename, png_bytes, false).await?)
+}
+
+/// Opens an existing PNG, JPEG or TIFF file in the capture popup and adds
+/// it to history, so it can be edited and shared like a capture. Without a
+/// path the user picks one; `None` means the dialog was cancelled.
+#[tauri::command]
+async fn import_image_file(app_handle: AppHandle, path: Option<String>) -> Result<Option<ScreenshotData>, SnippError> {
+    let path = match path {
+        Some(path) => path,
+        None => {
+            let (sender, receiver) = tokio::sync::oneshot::channel();
+            app_handle
+                .dialog()
+                .file()
+                .set_title("Open Image")
+                .add_filter("Images", &watch::IMAGE_EXTENSIONS)
+                .pick_file(move |path| {
+                    let _ = sender.send(path.map(|p| p.to_string()));
+                });
+            let Some(path) = receiver.await.map_err(|e| format!("Dialog receiver error: {}", e))? else {
+                return Ok(None);
+            };
+            path
+        }
+    };
+    let path = PathBuf::from(path);
+    if !watch::has_image_extension(&path) {
+        return Err(format!("{} is not a PNG, JPEG or TIFF image", path.display()).into());
+    }
+    tracing::debug!("Importing image file {}", path.display());
+
+    let data = tokio::fs::read(&path)
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let png_bytes = tokio::task::spawn_blocking(move || watch::to_png(data))
+        .await
+        .map_err(|e| format!("Import task failed: {}", e))??;
+
+    import_watched_file(&app_handle, path.clone()).await;
+
+    let timestamp = current_timestamp_millis();
+    let filename = path
+        .with_extension("png")
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| build_screenshot_filename(timestamp, None));
+    Ok(present_capture(&app_handle, timestamp, filename, png_bytes, false).await.map(Some)?)
+}
+
+/// Imports files handed to Snipp by the OS: opened with Snipp from Finder on
+/// macOS, or passed on the command line elsewhere.
+fn import_opened_files(app_handle: &AppHandle, paths: Vec<PathBuf>) {
+    for path in paths.into_iter().filter(|path| watch::has_image_extension(path)) {
+        let app_handle = app_handle.clone();
+        tauri::async_runtime::spawn(async move {
+            let file_path = path.to_string_lossy().to_string();
+            if let Err(e) = import_image_file(app_handle, Some(file_path)).await {
+                tracing::error!("Failed to open {}: {}", path.display(), e);
+            }
+        });
+    }
+}
+
+#[tauri::command]
+async fn copy_to_clipboard(app_handle: AppHandle, timestamp: u64) -> Result<(), SnippError> {
+    tracing::debug!("Copying screenshot to clipboard from memory cache: {}", timestamp);
+
+    let image_data = cached_capture(&app_handle, timestamp).await?;
+
+    write_png_bytes_to_clipboard(&app_handle, image_data).await?;
+    tracing::debug!("Successfully copied screenshot to clipboard");
+    events::emit(&app_handle, &events::CopyCompleted::image(Some(timestamp), None));
+    announce(&app_handle, "Copied to clipboard");
+    Ok(())
+}
+
+/// Returns the path actually written, which gains a `-1`, `-2`, ... counter
+/// when a file with the same name is already there.
+#[tauri::command]
+async fn save_to_disk(
+    app_handle: AppHandle,
+    timestamp: u64,
+    note: Option<String>,
+) -> Result<String, SnippError> {
+    let file_path = save_cached(&app_handle, timestamp, note).await?;
+    announce(&app_handle, "Screenshot saved");
+    Ok(file_path)
+}
+
+/// Saves a cached capture to the configured folders, records it in history
+/// and drops it from the cache.
+async fn save_cached(app_handle: &AppHandle, timestamp: u64, note: Option<String>) -> Result<String, SnippError> {
+    tracing::debug!("Saving screenshot to disk from memory cache: {}", timestamp);
+
+    let image_data = cached_capture(app_handle, timestamp).await?;
+
+    let filename = saved_filename(app_handle, timestamp, None);
+    let entry = save_to_locations(app_handle, timestamp, &filename, &image_data, note.as_deref().unwrap_or("")).await?;
+    let file_path_str = entry.file_path.clone();
+    tracing::debug!("Successfully saved screenshot to: {}", file_path_str);
+
+    with_store(app_handle, move |store| store.remove(timestamp)).await?;
+    tracing::debug!("Evicted screenshot from memory cache after save");
+
+    events::emit(app_handle, &events::SaveCompleted::new(Some(timestamp), &entry));
+    record_in_history(app_handle, timestamp, entry, image_data, &[]).await;
+
+    Ok(file_path_str)
+}
+
+/// Adds a saved capture to history, then recognizes its text in the
+/// background so quick search can find it by what it shows.
+async fn record_in_history(
+    app_handle: &AppHandle,
+    timestamp: u64,
+    entry: ScreenshotHistory,
+    image_data: Vec<u8>,
+    operations: &[&str],
+) {
+    let file_path = entry.file_path.clone();
+    let saved_paths: Vec<PathBuf> = entry.saved_paths().into_iter().map(PathBuf::from).collect();
+    let note = entry.note.clone();
+    let _ = update_guide(app_handle, |guide| {
+        guide.add_step(&file_path, &note);
+        Ok(())
+    });
+    // Adding rewrites the whole history file.
+    let history_handle = app_handle.clone();
+    let added = tokio::task::spawn_blocking(move || {
+        history_handle.state::<HistoryState>().lock().unwrap().add_entry(entry)
+    })
+    .await
+    .map_err(|e| format!("History task failed: {}", e))
+    .and_then(|result| result.map_err(|e| e.to_string()));
+    if let Err(e) = added {
+        tracing::error!("Failed to add screenshot to history: {}", e);
+        return;
+    }
+    app_handle.state::<ScreenshotStore>().mark_saved(timestamp);
+
+    let (index_text, upload, write_sidecar, stamped, mirror_location) = {
+        let config_state = app_handle.state::<ConfigState>();
+        let config = config_state.lock().unwrap();
+        let config = config.get_config();
+        (
+            config.index_capture_text,
+            config.upload.enabled && config.upload.upload_after_save,
+            config.write_sidecar,
+            config.stamp.enabled,
+            config.mirror_location.clone(),
+        )
+    };
+
+    let source_app = CAPTURE_SOURCE_APPS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap()
+        .remove(&timestamp);
+    let context = CAPTURE_CONTEXTS
+        .get()
+        .and_then(|contexts| contexts.lock().unwrap().remove(&timestamp))
+        .unwrap_or_default();
+    if write_sidecar {
+        // Written before text recognition starts, which fills in `text` later.
+        match sidecar::png_dimensions(&image_data) {
+            Ok((width, height)) => {
+                let mut operations: Vec<String> = operations.iter().map(|op| op.to_string()).collect();
+                if stamped {
+                    operations.push("stamp".to_string());
+                }
+                let metadata = sidecar::Sidecar {
+                    timestamp: Local
+                        .timestamp_millis_opt(timestamp as i64)
+                        .single()
+                        .unwrap_or_else(Local::now),
+                    width,
+                    height,
+                    source_app,
+                    note,
+                    text: String::new(),
+                    operations,
+                    context,
+                };
+                let paths = saved_paths.clone();
+                let written = tokio::task::spawn_blocking(move || {
+                    for path in &paths {
+                        if let Err(e) = sidecar::write_sidecar(path, &metadata) {
+                            tracing::error!("Failed to write sidecar for {}: {}", path.display(), e);
+                        }
+                    }
+                })
+                .await;
+                if let Err(e) = written {
+                    tracing::error!("Sidecar task failed: {}", e);
+                }
+            }
+            Err(e) => tracing::error!("Skipping sidecar: {}", e),
+        }
+    }
+
+    if !mirror_location.is_empty() {
+        let app_handle = app_handle.clone();
+        let file_path = file_path.clone();
+        tauri::async_runtime::spawn(async move {
+            mirror_capture(&app_handle, file_path, mirror_location).await;
+        });
+    }
+
+    if upload {
+        let app_handle = app_handle.clone();
+        let file_path = file_path.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = share_screenshot(&app_handle, &file_path).await {
+                tracing::error!("Failed to upload screenshot: {}", e);
+                let _ = app_handle.notification().builder().title("Upload failed").body(&e).show();
+            }
+        });
+    }
+
+    if index_text {
+        index_capture_text(app_handle, file_path, saved_paths, image_data, write_sidecar);
+    }
+}
+
+/// Recognizes a saved capture's text in the background and stores it in
+/// history, and in its sidecars when `write_sidecar` is set.
+fn index_capture_text(
+    app_handle: &AppHandle,
+    file_path: String,
+    saved_paths: Vec<PathBuf>,
+    image_data: Vec<u8>,
+    write_sidecar: bool,
+) {
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let text = match ocr::recognize_text(&app_handle, &image_data).await {
+            Ok(result) => result.text,
+            Err(e) => {
+                tracing::warn!("Failed to recognize text for search: {}", e);
+                return;
+            }
+        };
+        if write_sidecar {
+            for path in &saved_paths {
+                if let Err(e) = sidecar::update_text(path, &text) {
+                    tracing::error!("Failed to add text to sidecar for {}: {}", path.display(), e);
+                }
+            }
+        }
+        let history_state = app_handle.state::<HistoryState>();
+        let mut history = history_state.lock().unwrap();
+        if let Err(e) = history.set_text(&file_path, &text) {
+            tracing::error!("Failed to store recognized text: {}", e);
+        }
+    });
+}
+
+fn set_mirror_status(app_handle: &AppHandle, file_path: &str, status: MirrorStatus) {
+    let history_state = app_handle.state::<HistoryState>();
+    let mut history = history_state.lock().unwrap();
+    if let Err(e) = history.set_mirror_status(file_path, status) {
+        tracing::error!("Failed to store mirror status: {}", e);
+    }
+}
+
+fn queue_mirror_jobs(app_handle: &AppHandle, jobs: Vec<MirrorJob>) {
+    let queue_state = app_handle.state::<MirrorQueueState>();
+    let mut queue = queue_state.lock().unwrap();
+    for job in jobs {
+        queue.push(job);
+    }
+    if let Err(e) = queue.save() {
+        tracing::error!("Failed to persist mirror queue: {}", e);
+    }
+}
+
+/// Copies a saved capture to the network share, or queues the copy while
+/// the share is unreachable.
+async fn mirror_capture(app_handle: &AppHandle, file_path: String, folder: String) {
+    let source = PathBuf::from(&file_path);
+    let target = folder.clone();
+    let copied = tokio::task::spawn_blocking(move || mirror::copy_to(&source, &target))
+        .await
+        .unwrap_or_else(|e| Err(format!("Mirror task failed: {}", e)));
+    let status = match copied {
+        Ok(_) => MirrorStatus::Mirrored,
+        Err(e) => {
+            tracing::info!("Queueing network copy of {}: {}", file_path, e);
+            queue_mirror_jobs(app_handle, vec![MirrorJob { source: file_path.clone(), folder }]);
+            MirrorStatus::Queued
+        }
+    };
+    set_mirror_status(app_handle, &file_path, status);
+}
+
+/// Retries queued copies whose share is mounted again. Captures deleted in
+/// the meantime are dropped from the queue.
+async fn flush_mirror_queue(app_handle: &AppHandle) {
+    let ready = {
+        let queue_state = app_handle.state::<MirrorQueueState>();
+        let mut queue = queue_state.lock().unwrap();
+        let ready = queue.take_ready();
+        if !ready.is_empty() {
+            if let Err(e) = queue.save() {
+                tracing::error!("Failed to persist mirror queue: {}", e);
+            }
+        }
+        ready
+    };
+    if ready.is_empty() {
+        return;
+    }
+
+    let results = tokio::task::spawn_blocking(move || {
+        ready
+            .into_iter()
+            .filter(|job| PathBuf::from(&job.source).exists())
+            .map(|job| {
+                let copied = mirror::copy_to(std::path::Path::new(&job.source), &job.folder);
+                (job, copied)
+            })
+            .collect::<Vec<_>>()
+    })
+    .await;
+    let results = match results {
+        Ok(results) => results,
+        Err(e) => {
+            tracing::error!("Mirror task failed: {}", e);
+            return;
+        }
+    };
+
+    let mut failed = Vec::new();
+    for (job, copied) in results {
+        match copied {
+            Ok(path) => {
+                tracing::info!("Copied {} to {}", job.source, path.display());
+                set_mirror_status(app_handle, &job.source, MirrorStatus::Mirrored);
+            }
+            Err(e) => {
+                tracing::warn!("Network copy of {} failed again: {}", job.source, e);
+                failed.push(job);
+            }
+        }
+    }
+    if !failed.is_empty() {
+        queue_mirror_jobs(app_handle, failed);
+    }
+}
+
+/// Checks the mirror queue periodically so copies go out once the share
+/// is back, including ones queued before a restart.
+fn spawn_mirror_task(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let pending = !app_handle.state::<MirrorQueueState>().lock().unwrap().is_empty();
+            if pending {
+                flush_mirror_queue(&app_handle).await;
+            }
+            tokio::time::sleep(MIRROR_RETRY_INTERVAL).await;
+        }
+    });
+}
+
+/// How often the watched folder is checked for new screenshots.
+const WATCH_FOLDER_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Imports screenshots that appear in the watched folder, such as ones
+/// taken with Cmd+Shift+3, while watching is enabled. Only files added
+/// after watching started are picked up.
+fn spawn_watch_folder_task(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut watching: Option<(WatchFolder, FolderScanner)> = None;
+        loop {
+            tokio::time::sleep(WATCH_FOLDER_INTERVAL).await;
+            let settings = app_handle.state::<ConfigState>().lock().unwrap().get_config().watch_folder.clone();
+            if !settings.enabled {
+                watching = None;
+                continue;
+            }
+
+            let scanner = match watching.take() {
+                Some((watched, scanner)) if watched == settings => scanner,
+                _ => {
+                    let folder = match settings.folder.trim() {
+                        "" => screencapture_prefs::screenshot_folder(&app_handle).await,
+                        folder => Some(PathBuf::from(folder)),
+                    };
+                    let Some(folder) = folder else {
+                        continue;
+                    };
+                    tracing::info!("Watching {} for screenshots", folder.display());
+                    FolderScanner::new(folder, std::time::SystemTime::now())
+                }
+            };
+            let scanned = tokio::task::spawn_blocking(move || {
+                let mut scanner = scanner;
+                let found = scanner.scan();
+                (scanner, found)
+            })
+            .await;
+            let (scanner, found) = match scanned {
+                Ok(scanned) => scanned,
+                Err(e) => {
+                    tracing::error!("Watch folder scan failed: {}", e);
+                    continue;
+                }
+            };
+            watching = Some((settings, scanner));
+            for path in found {
+                import_watched_file(&app_handle, path).await;
+            }
+        }
+    });
+}
+
+/// Adds an image from outside Snipp (the watched folder or a file opened
+/// with Snipp) to history and indexes its text, unless Snipp saved it itself.
+async fn import_watched_file(app_handle: &AppHandle, path: PathBuf) {
+    let file_path = path.to_string_lossy().to_string();
+    let known = app_handle
+        .state::<HistoryState>()
+        .lock()
+        .unwrap()
+        .get_history()
+        .screenshots
+        .iter()
+        .any(|entry| entry.saved_paths().contains(&file_path.as_str()));
+    if known {
+        return;
+    }
+
+    let (image_data, modified) = match tokio::fs::read(&path).await {
+        Ok(data) => (data, tokio::fs::metadata(&path).await.and_then(|meta| meta.modified())),
+        Err(e) => {
+            tracing::warn!("Failed to import {}: {}", file_path, e);
+            return;
+        }
+    };
+    let mut entry = ScreenshotHistory::new(file_path.clone());
+    if let Ok(modified) = modified {
+        entry.timestamp = modified.into();
+    }
+    {
+        let history_state = app_handle.state::<HistoryState>();
+        let mut history = history_state.lock().unwrap();
+        if let Err(e) = history.add_entry(entry) {
+            tracing::error!("Failed to add imported screenshot to history: {}", e);
+            return;
+        }
+    }
+    tracing::info!("Imported {} into history", file_path);
+    let _ = app_handle.emit("screenshot-imported", &serde_json::json!({ "path": file_path }));
+
+    let index_text = app_handle.state::<ConfigState>().lock().unwrap().get_config().index_capture_text;
+    if index_text {
+        index_capture_text(app_handle, file_path, vec![path], image_data, false);
+    }
+}
+
+/// Writes a capture into every configured save folder (stamped first when
+/// enabled, color profile kept or converted per config) and builds its
+/// history entry, which points at the first folder that worked. Fails only
+/// when all folders did. Hook scripts may rename the file, send it to a
+/// single other folder, and tag it.
+async fn save_to_locations(
+    app_handle: &AppHandle,
+    timestamp: u64,
+    filename: &str,
+    image_data: &[u8],
+    note: &str,
+) -> Result<ScreenshotHistory, SnippError> {
+    let (mut locations, stamp, color_profile, metadata_mode, output_format, quality) = {
+        let config_state = app_handle.state::<ConfigState>();
+        let config = config_state.lock().unwrap();
+        let config = config.get_config();
+        (
+            config.save_locations(),
+            config.stamp.clone(),
+            config.color_profile,
+            config.metadata,
+            config.output_format,
+            config.output_quality(),
+        )
+    };
+
+    let mut actions = CAPTURE_HOOK_ACTIONS
+        .get()
+        .and_then(|pending| pending.lock().unwrap().remove(&timestamp))
+        .unwrap_or_default();
+    let context = HookContext {
+        filename: actions.filename.clone().unwrap_or_else(|| filename.to_string()),
+        folder: actions
+            .folder
+            .clone()
+            .or_else(|| locations.first().cloned())
+            .unwrap_or_default(),
+        source_app: CAPTURE_SOURCE_APPS
+            .get()
+            .and_then(|source_apps| source_apps.lock().unwrap().get(&timestamp).cloned())
+            .unwrap_or_default(),
+        note: note.trim().to_string(),
+        tags: actions.tags.clone(),
+        ..HookContext::default()
+    };
+    actions.merge(run_hook(app_handle, HookEvent::Save, context).await);
+    if let Some(folder) = actions.folder {
+        locations = vec![folder];
+    }
+    let filename = actions.filename.unwrap_or_else(|| filename.to_string());
+    // GIF captures keep their extension and skip the PNG-only stamp.
+    let animated = burst::is_gif(image_data);
+    let output_format = if animated { OutputFormat::Png } else { output_format };
+    let extension = if animated { "gif" } else { output_format.extension() };
+    let filename = PathBuf::from(filename).with_extension(extension).to_string_lossy().to_string();
+    // Only PNGs carry the display profile, so other formats are saved as sRGB.
+    let color_profile = if output_format == OutputFormat::Png {
+        color_profile
+    } else {
+        color::ColorProfileMode::ConvertToSrgb
+    };
+    let captured_at = Local
+        .timestamp_millis_opt(timestamp as i64)
+        .single()
+        .unwrap_or_else(Local::now);
+    let provenance = metadata::CaptureMetadata {
+        captured_at,
+        software: format!("Snipp {}", app_handle.package_info().version),
+        source_app: CAPTURE_SOURCE_APPS
+            .get()
+            .and_then(|source_apps| source_apps.lock().unwrap().get(&timestamp).cloned()),
+        display: CAPTURE_CONTEXTS
+            .get()
+            .and_then(|contexts| contexts.lock().unwrap().get(&timestamp)?.display_name.clone()),
+    };
+    let image_data = image_data.to_vec();
+    let results = tokio::task::spawn_blocking(move || {
+        let icc = color::icc_profile(&image_data);
+        let image_data = if stamp.enabled && !animated {
+            annotate::apply_stamp(&image_data, &stamp, captured_at)?
+        } else {
+            image_data
+        };
+        let image_data = color::apply_profile(image_data, icc.as_deref(), color_profile)?;
+        let image_data = encode_output(&image_data, output_format, quality)?;
+        let image_data = metadata::apply(image_data, metadata_mode, &provenance)?;
+        Ok::<_, String>(destinations::write_to_all(&locations, &filename, &image_data))
+    })
+    .await
+    .map_err(|e| format!("Save task failed: {}", e))??;
+    let file_path = destinations::primary_path(&results)?;
+
+    let mut entry = ScreenshotHistory::new(file_path);
+    entry.note = note.trim().to_string();
+    entry.tags = actions.tags;
+    entry.sync_status = results.iter().find_map(|result| result.sync_status);
+    if results.len() > 1 {
+        entry.destinations = results;
+    }
+    Ok(entry)
+}
+
+#[tauri::command]
+async fn delete_from_memory(app_handle: AppHandle, timestamp: u64) -> Result<(), SnippError> {
+    tracing::debug!("Deleting screenshot from memory cache: {}", timestamp);
+    
+    with_store(&app_handle, move |store| {
+        if let Some(mut bytes) = store.remove(timestamp) {
+            shred::wipe(&mut bytes);
+        }
+    })
+    .await?;
+    tracing::debug!("Removed screenshot from memory cache");
+    if let Some(source_apps) = CAPTURE_SOURCE_APPS.get() {
+        source_apps.lock().unwrap().remove(&timestamp);
+    }
+    if let Some(contexts) = CAPTURE_CONTEXTS.get() {
+        contexts.lock().unwrap().remove(&timestamp);
+    }
+    if let Some(pending) = CAPTURE_HOOK_ACTIONS.get() {
+        pending.lock().unwrap().remove(&timestamp);
+    }
+    
+    Ok(())
+}
+
+#[tauri::command]
+async fn close_popup_window(app_handle: AppHandle) -> Result<(), SnippError> {
+    // Evict any cached screenshots: callers that still need the data (editor,
+    // save, delete) have already read or removed their specific entry by now.
+    with_store(&app_handle, |store| store.clear()).await?;
+    // Hide rather than close so the WebView stays warm for the next capture.
+    for label in ["popup", "tray_popover"] {
+        if let Some(popup_window) = app_handle.get_webview_window(label) {
+            popup_window.hide()
+                .map_err(|e| format!("Failed to hide popup: {}", e))?;
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_config(config_state: State<'_, ConfigState>) -> Result<AppConfig, SnippError> {
+    let config = config_state.lock().unwrap();
+    Ok(config.get_config().clone())
+}
+
+#[tauri::command]
+async fn update_config(
+    app_handle: AppHandle,
+    config_state: State<'_, ConfigState>,
+    new_config: AppConfig,
+) -> Result<(), SnippError> {
+    let updated_config = {
+        let mut config = config_state.lock().unwrap();
+        config.update_config(new_config)
+            .map_err(|e| format!("Failed to update config: {}", e))?;
+        config.get_config().clone()
+    };
+
+    let cache_limit_mb = updated_config.cache_limit_mb;
+    with_store(&app_handle, move |store| store.set_limit_mb(cache_limit_mb)).await?;
+    if let Some(area) = spill::area() {
+        area.set_secure_delete(updated_config.secure_delete);
+    }
+    logging::set_level(updated_config.log_level);
+    apply_global_shortcuts(&app_handle, &updated_config)?;
+    tray::update_tray_menu(&app_handle, &updated_config)
+        .map_err(|e| format!("Failed to update tray menu: {}", e))?;
+    Ok(())
+}
+
+
+
+#[tauri::command]
+async fn hide_window(app_handle: AppHandle) -> Result<(), SnippError> {
+    if let Some(main_window) = app_handle.get_webview_window("main") {
+        main_window
+            .hide()
+            .map_err(|e| format!("Failed to hide main window: {}", e))?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+async fn show_window(app_handle: AppHandle) -> Result<(), SnippError> {
+    if let Some(main_window) = app_handle.get_webview_window("main") {
+        main_window
+            .show()
             .map_err(|e| format!("Failed to show main window: {}", e))?;
         let _ = main_window.set_focus();
     }
     Ok(())
 }
 
+/// What the first-run wizard needs to know before the first capture.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct OnboardingStatus {
+    pub completed: bool,
+    pub screen_recording: bool,
+    pub accessibility: bool,
+    pub save_location: String,
+    pub save_location_writable: bool,
+    pub capture_hotkey: String,
+}
+
+#[tauri::command]
+async fn get_onboarding_status(config_state: State<'_, ConfigState>) -> Result<OnboardingStatus, SnippError> {
+    let config = config_state.lock().unwrap().get_config().clone();
+    let save_location_writable = tokio::fs::metadata(&config.default_save_location)
+        .await
+        .map(|metadata| metadata.is_dir() && !metadata.permissions().readonly())
+        .unwrap_or(false);
+
+    Ok(OnboardingStatus {
+        completed: config.onboarding_completed,
+        screen_recording: permissions::is_granted(PermissionKind::ScreenRecording),
+        accessibility: permissions::is_granted(PermissionKind::Accessibility),
+        save_location: config.default_save_location,
+        save_location_writable,
+        capture_hotkey: config.capture_hotkey,
+    })
+}
+
+/// Screenshot settings changed in macOS, which onboarding offers to adopt.
+#[tauri::command]
+async fn get_macos_screenshot_prefs(app_handle: AppHandle) -> Result<ScreencapturePrefs, SnippError> {
+    Ok(screencapture_prefs::load(&app_handle).await)
+}
+
+/// Asks for a permission: the system prompt where macOS offers one, otherwise
+/// the matching System Settings pane. Returns whether it is granted now.
+#[tauri::command]
+async fn request_permission(app_handle: AppHandle, kind: PermissionKind) -> Result<bool, SnippError> {
+    if kind == PermissionKind::ScreenRecording && permissions::request_screen_recording() {
+        return Ok(true);
+    }
+    if permissions::is_granted(kind) {
+        return Ok(true);
+    }
+
+    app_handle
+        .opener()
+        .open_url(kind.settings_url(), None::<&str>)
+        .map_err(|e| format!("Failed to open System Settings: {}", e))?;
+    Ok(false)
+}
+
+/// Checks permissions, shortcuts, config, save folders, caches and disk
+/// space, for the preferences window and bug reports.
+#[tauri::command]
+async fn run_diagnostics(
+    app_handle: AppHandle,
+    store: State<'_, ScreenshotStore>,
+    config_state: State<'_, ConfigState>,
+    thumbnail_state: State<'_, ThumbnailState>,
+) -> Result<DiagnosticsReport, SnippError> {
+    use diagnostics::{CheckStatus, DiagnosticCheck};
+    use tauri_plugin_global_shortcut::GlobalShortcutExt;
+
+    let config = config_state.lock().unwrap().get_config().clone();
+    let mut checks = Vec::new();
+
+    for (kind, name) in [
+        (PermissionKind::ScreenRecording, "Screen Recording permission"),
+        (PermissionKind::Accessibility, "Accessibility permission"),
+    ] {
+        checks.push(if permissions::is_granted(kind) {
+            DiagnosticCheck::new(name, CheckStatus::Pass, "Granted")
+        } else {
+            DiagnosticCheck::new(name, CheckStatus::Fail, "Not granted")
+        });
+    }
+
+    let global_shortcut = app_handle.global_shortcut();
+    for (name, hotkey) in [
+        ("Capture hotkey", &config.capture_hotkey),
+        ("Clipboard import hotkey", &config.import_clipboard_hotkey),
+        ("Repeat region hotkey", &config.repeat_region_hotkey),
+        ("Capture text hotkey", &config.capture_text_hotkey),
+        ("Quick search hotkey", &config.quick_search_hotkey),
+    ] {
+        if hotkey.is_empty() {
+            continue;
+        }
+        checks.push(if global_shortcut.is_registered(hotkey.as_str()) {
+            DiagnosticCheck::new(name, CheckStatus::Pass, format!("{} is registered", hotkey))
+        } else {
+            DiagnosticCheck::new(name, CheckStatus::Fail, format!("{} is not registered", hotkey))
+        });
+    }
+
+    let mut available = Vec::new();
+    for folder in config.save_locations() {
+        let output = app_handle.shell().command("df").args(["-Pk", folder.as_str()]).output().await;
+        let bytes = output
+            .ok()
+            .and_then(|output| diagnostics::parse_df_available(&String::from_utf8_lossy(&output.stdout)));
+        available.push((folder, bytes));
+    }
+
+    let (memory_count, memory_bytes) = store.memory_usage();
+    let (spilled_count, spilled_bytes) = spill::area().map(|area| area.usage()).unwrap_or_default();
+    let thumbnail_gen = thumbnail_state.lock().unwrap().clone();
+    let file_checks = tokio::task::spawn_blocking(move || {
+        let mut checks = Vec::new();
+        checks.push(match AppConfig::get_config_path() {
+            Ok(path) => diagnostics::check_config_file(&path),
+            Err(e) => DiagnosticCheck::new("Config file", CheckStatus::Fail, e.to_string()),
+        });
+        for (folder, bytes) in available {
+            checks.push(diagnostics::check_save_location(&folder));
+            checks.push(diagnostics::check_disk_space(&folder, bytes));
+        }
+        let thumbnails = thumbnail_gen.usage();
+        checks.push(DiagnosticCheck::new(
+            "Caches",
+            CheckStatus::Pass,
+            format!(
+                "{} pending capture(s) in memory ({}), {} spilled to disk ({}), {} thumbnail(s) ({})",
+                memory_count,
+                diagnostics::format_bytes(memory_bytes as u64),
+                spilled_count,
+                diagnostics::format_bytes(spilled_bytes),
+                thumbnails.files,
+                diagnostics::format_bytes(thumbnails.bytes),
+            ),
+        ));
+        checks
+    })
+    .await
+    .map_err(|e| format!("Diagnostics failed: {}", e))?;
+    checks.extend(file_checks);
+
+    Ok(DiagnosticsReport::new(app_handle.package_info().version.to_string(), checks))
+}
+
+/// The last `lines` lines of Snipp's log, oldest first.
+#[tauri::command]
+async fn get_recent_logs(lines: usize) -> Result<Vec<String>, SnippError> {
+    tokio::task::spawn_blocking(move || logs::recent_lines(&logs::log_dir()?, lines))
+        .await
+        .map_err(|e| format!("Log read task failed: {}", e))?
+}
+
+/// Zips the diagnostics report, redacted config, history stats, permission
+/// states and recent logs into one file for a bug report. Resolves to the
+/// saved path, or `None` if the save dialog is cancelled.
+#[tauri::command]
+async fn export_diagnostics(
+    app_handle: AppHandle,
+    history_state: State<'_, HistoryState>,
+) -> Result<Option<String>, SnippError> {
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    app_handle
+        .dialog()
+        .file()
+        .set_title("Export Snipp Diagnostics")
+        .set_file_name(format!("Snipp Diagnostics {}.zip", Local::now().format("%Y-%m-%d")))
+        .add_filter("Zip archive", &["zip"])
+        .save_file(move |path| {
+            let _ = sender.send(path.map(|p| p.to_string()));
+        });
+    let Some(bundle_path) = receiver
+        .await
+        .map_err(|e| format!("Dialog receiver error: {}", e))?
+    else {
+        return Ok(None);
+    };
+
+    let report = run_diagnostics(
+        app_handle.clone(),
+        app_handle.state(),
+        app_handle.state(),
+        app_handle.state(),
+    )
+    .await?;
+    let home = dirs::home_dir();
+    let config = diagnostics::redact_config(
+        app_handle.state::<ConfigState>().lock().unwrap().get_config(),
+        home.as_deref(),
+    );
+    let history = diagnostics::HistoryStats::new(&history_state.lock().unwrap().get_history().screenshots);
+    let os_version = Some(tauri_plugin_os::version().to_string()).filter(|version| version != "Unknown");
+    let system = diagnostics_bundle::SystemInfo {
+        version: app_handle.package_info().version.to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        os_version,
+        permissions: diagnostics_bundle::Permissions {
+            screen_recording: permissions::is_granted(PermissionKind::ScreenRecording),
+            accessibility: permissions::is_granted(PermissionKind::Accessibility),
+        },
+        exported_at: chrono::Utc::now(),
+    };
+
+    let saved_path = bundle_path.clone();
+    tokio::task::spawn_blocking(move || {
+        let home = home.as_deref().and_then(|home| home.to_str()).filter(|home| !home.is_empty());
+        let logs = logs::recent_lines(&logs::log_dir()?, diagnostics_bundle::BUNDLE_LOG_LINES)?
+            .into_iter()
+            .map(|line| match home {
+                Some(home) => diagnostics::redact_home(&line, home),
+                None => line,
+            })
+            .collect();
+        let bundle = diagnostics_bundle::Bundle { system, report, config, history, logs };
+        diagnostics_bundle::write_bundle(std::path::Path::new(&bundle_path), &bundle)
+    })
+    .await
+    .map_err(|e| format!("Diagnostics export task failed: {}", e))??;
+    tracing::info!("Exported diagnostics to {}", saved_path);
+    Ok(Some(saved_path))
+}
+
+/// Applies the choices made in the wizard and marks onboarding done.
+#[tauri::command]
+async fn complete_onboarding(
+    app_handle: AppHandle,
+    config_state: State<'_, ConfigState>,
+    save_location: Option<String>,
+    capture_hotkey: Option<String>,
+    window_shadow: Option<bool>,
+    output_format: Option<OutputFormat>,
+) -> Result<(), SnippError> {
+    let updated_config = {
+        let mut config = config_state.lock().unwrap();
+        let mut new_config = config.get_config().clone();
+        if let Some(save_location) = save_location {
+            new_config.default_save_location = save_location;
+        }
+        if let Some(window_shadow) = window_shadow {
+            new_config.window_shadow = window_shadow;
+        }
+        if let Some(output_format) = output_format {
+            new_config.output_format = output_format;
+        }
+        if let Some(capture_hotkey) = capture_hotkey {
+            new_config.capture_hotkey = capture_hotkey;
+        }
+        new_config.onboarding_completed = true;
+        config
+            .update_config(new_config)
+            .map_err(|e| format!("Failed to update config: {}", e))?;
+        config.get_config().clone()
+    };
+
+    apply_global_shortcuts(&app_handle, &updated_config)?;
+    tray::update_tray_menu(&app_handle, &updated_config)
+        .map_err(|e| format!("Failed to update tray menu: {}", e))?;
+    Ok(())
+}
+
+#[tauri::command]
+async fn choose_save_location(app_handle: AppHandle) -> Result<Option<String>, SnippError> {
+    use tokio::sync::oneshot;
+    
+    let (sender, receiver) = oneshot::channel();
+    
+    app_handle.dialog().file()
+        .set_title("Choose Save Location")
+        .pick_folder(move |folder_path| {
+            let result = folder_path.map(|p| p.to_string());
+            let _ = sender.send(result);
+        });
+    
+    let folder = receiver.await
+        .map_err(|e| format!("Dialog receiver error: {}", e))?;
+    
+    Ok(folder)
+}
+
+/// Exports config, history and thumbnails (plus saved screenshots when asked)
+/// to a zip chosen by the user. Returns `None` if the dialog is cancelled.
+#[tauri::command]
+async fn export_backup(
+    app_handle: AppHandle,
+    history_state: State<'_, HistoryState>,
+    include_screenshots: bool,
+) -> Result<Option<BackupSummary>, SnippError> {
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    app_handle
+        .dialog()
+        .file()
+        .set_title("Export Snipp Backup")
+        .set_file_name(format!("Snipp Backup {}.zip", Local::now().format("%Y-%m-%d")))
+        .add_filter("Snipp Backup", &["zip"])
+        .save_file(move |path| {
+            let _ = sender.send(path.map(|p| p.to_string()));
+        });
+    let Some(archive_path) = receiver
+        .await
+        .map_err(|e| format!("Dialog receiver error: {}", e))?
+    else {
+        return Ok(None);
+    };
+
+    let screenshots: Vec<PathBuf> = if include_screenshots {
+        let history = history_state.lock().unwrap();
+        history
+            .get_history()
+            .screenshots
+            .iter()
+            .map(|s| PathBuf::from(&s.file_path))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let summary = tokio::task::spawn_blocking(move || {
+        backup::DataDirs::locate()
+            .and_then(|dirs| backup::create_backup(&dirs, std::path::Path::new(&archive_path), &screenshots))
+            .map_err(|e| format!("Failed to export backup: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Backup task failed: {}", e))??;
+
+    Ok(Some(summary))
+}
+
+/// Restores a backup picked by the user over the current data and reloads
+/// it. Screenshots land in the current save folder.
+#[tauri::command]
+async fn restore_backup(
+    app_handle: AppHandle,
+    config_state: State<'_, ConfigState>,
+    history_state: State<'_, HistoryState>,
+) -> Result<Option<BackupSummary>, SnippError> {
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    app_handle
+        .dialog()
+        .file()
+        .set_title("Restore Snipp Backup")
+        .add_filter("Snipp Backup", &["zip"])
+        .pick_file(move |path| {
+            let _ = sender.send(path.map(|p| p.to_string()));
+        });
+    let Some(archive_path) = receiver
+        .await
+        .map_err(|e| format!("Dialog receiver error: {}", e))?
+    else {
+        return Ok(None);
+    };
+
+    let screenshots_dir = PathBuf::from(&config_state.lock().unwrap().get_config().default_save_location);
+    let restore_dir = screenshots_dir.clone();
+    let summary = tokio::task::spawn_blocking(move || {
+        backup::DataDirs::locate()
+            .and_then(|dirs| backup::restore_backup(&dirs, std::path::Path::new(&archive_path), &restore_dir))
+            .map_err(|e| format!("Failed to restore backup: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Restore task failed: {}", e))??;
+
+    let mut restored_config = ConfigManager::new()
+        .map_err(|e| format!("Failed to load restored config: {}", e))?;
+    // The backup may come from a machine whose save folder doesn't exist here.
+    if !PathBuf::from(&restored_config.get_config().default_save_location).exists() {
+        let mut config = restored_config.get_config().clone();
+        config.default_save_location = screenshots_dir.to_string_lossy().to_string();
+        restored_config
+            .update_config(config)
+            .map_err(|e| format!("Failed to update restored config: {}", e))?;
+    }
+    let updated_config = restored_config.get_config().clone();
+    *config_state.lock().unwrap() = restored_config;
+    *history_state.lock().unwrap() = HistoryManager::new()
+        .map_err(|e| format!("Failed to load restored history: {}", e))?;
+    if let Some(area) = spill::area() {
+        area.set_secure_delete(updated_config.secure_delete);
+    }
+
+    apply_global_shortcuts(&app_handle, &updated_config)?;
+    tray::update_tray_menu(&app_handle, &updated_config)
+        .map_err(|e| format!("Failed to update tray menu: {}", e))?;
+
+    Ok(Some(summary))
+}
+
+#[tauri::command]
+async fn get_recent_screenshots(
+    history_state: State<'_, HistoryState>,
+    thumbnail_state: State<'_, ThumbnailState>,
+) -> Result<Vec<serde_json::Value>, SnippError> {
+    // Snapshot under the lock, then release it before any thumbnail IO/CPU.
+    let recent: Vec<ScreenshotHistory> = {
+        let history = history_state
+            .lock()
+            .map_err(|e| format!("History lock poisoned: {}", e))?;
+        history
+            .get_recent_screenshots(10)
+            .into_iter()
+            .cloned()
+            .collect()
+    };
+
+    Ok(describe_screenshots(recent, &thumbnail_state).await?)
+}
+
+/// History entries whose filename or note contain every term in `query`.
+#[tauri::command]
+async fn search_screenshots(
+    query: String,
+    history_state: State<'_, HistoryState>,
+    thumbnail_state: State<'_, ThumbnailState>,
+) -> Result<Vec<serde_json::Value>, SnippError> {
+    let found: Vec<ScreenshotHistory> = {
+        let history = history_state
+            .lock()
+            .map_err(|e| format!("History lock poisoned: {}", e))?;
+        history.search(&query, 10).into_iter().cloned().collect()
+    };
+
+    Ok(describe_screenshots(found, &thumbnail_state).await?)
+}
+
+#[tauri::command]
+async fn set_screenshot_note(
+    file_path: String,
+    note: String,
+    history_state: State<'_, HistoryState>,
+) -> Result<(), SnippError> {
+    let mut history = history_state.lock().unwrap();
+    history.set_note(&file_path, &note)
+}
+
+/// Turns history entries into the JSON the UI lists, with a small thumbnail
+/// and the file's current sync status.
+async fn describe_screenshots(
+    entries: Vec<ScreenshotHistory>,
+    thumbnail_state: &ThumbnailState,
+) -> Result<Vec<serde_json::Value>, String> {
+    let thumbnail_gen = {
+        let guard = thumbnail_state
+            .lock()
+            .map_err(|e| format!("Thumbnail lock poisoned: {}", e))?;
+        guard.clone()
+    };
+
+    // Decode/resize/encode is blocking work; keep it off the async runtime.
+    let screenshots = tokio::task::spawn_blocking(move || {
+        entries
+            .into_iter()
+            .map(|entry| {
+                let ScreenshotHistory {
+                    file_path,
+                    timestamp,
+                    filename,
+                    regression,
+                    destinations,
+                    note,
+                    share_url,
+                    mirror,
+                    tags,
+                    ..
+                } = entry;
+                // Reading a cloud placeholder would download it just for a thumbnail.
+                let sync_status = cloud_sync::status_of(std::path::Path::new(&file_path));
+                let thumbnail = if !matches!(sync_status, SyncStatus::Missing | SyncStatus::Placeholder) {
+                    thumbnail_gen
+                        .get_thumbnail_base64(&file_path, 64)
+                        .unwrap_or_default()
+                } else {
+                    String::new()
+                };
+
+                serde_json::json!({
+                    "file_path": file_path,
+                    "timestamp": timestamp,
+                    "filename": filename,
+                    "thumbnail": thumbnail,
+                    "regression": regression,
+                    "destinations": destinations,
+                    "sync_status": sync_status,
+                    "note": note,
+                    "share_url": share_url,
+                    "mirror": mirror,
+                    "tags": tags,
+                })
+            })
+            .collect::<Vec<_>>()
+    })
+    .await
+    .map_err(|e| format!("Thumbnail generation task failed: {}", e))?;
+
+    Ok(screenshots)
+}
+
+/// Copies where a saved capture lives as text.
+#[tauri::command]
+async fn copy_path_to_clipboard(app_handle: AppHandle, file_path: String) -> Result<(), SnippError> {
+    let path = pasteboard::existing_capture(&file_path)?;
+    app_handle
+        .clipboard()
+        .write_text(path.to_string_lossy().to_string())
+        .map_err(|e| format!("Failed to copy path to clipboard: {}", e))?;
+    events::emit(
+        &app_handle,
+        &events::CopyCompleted {
+            timestamp: None,
+            file_path: Some(file_path),
+            content: events::CopiedContent::Path,
+        },
+    );
+    announce(&app_handle, "Path copied to clipboard");
+    Ok(())
+}
+
+/// Copies a saved capture as a file, so it pastes as an attachment rather
+/// than as image data.
+#[tauri::command]
+async fn copy_file_to_clipboard(app_handle: AppHandle, file_path: String) -> Result<(), SnippError> {
+    let path = pasteboard::existing_capture(&file_path)?;
+    pasteboard::write_file_url(&app_handle, &path).await?;
+    events::emit(
+        &app_handle,
+        &events::CopyCompleted {
+            timestamp: None,
+            file_path: Some(file_path),
+            content: events::CopiedContent::File,
+        },
+    );
+    announce(&app_handle, "File copied to clipboard");
+    Ok(())
+}
+
+#[tauri::command]
+async fn copy_screenshot_from_path(
+    app_handle: AppHandle,
+    file_path: String,
+) -> Result<(), SnippError> {
+    let image_data = tokio::fs::read(&file_path)
+        .await
+        .map_err(|e| format!("Failed to read image file: {}", e))?;
+
+    write_png_bytes_to_clipboard(&app_handle, image_data).await?;
+    tracing::debug!("Successfully copied screenshot to clipboard");
+    events::emit(&app_handle, &events::CopyCompleted::image(None, Some(file_path)));
+    Ok(())
+}
+
+#[tauri::command]
+async fn open_in_finder(app_handle: AppHandle, file_path: String) -> Result<(), SnippError> {
+    let output = app_handle
+        .shell()
+        .command("open")
+        .args(["-R", &file_path])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to open in Finder: {}", e))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        let error = String::from_utf8_lossy(&output.stderr);
+        Err(format!("Failed to reveal in Finder: {}", error).into())
+    }
+}
+
+/// Toggles a full-size Quick Look preview of a saved capture. Asking again
+/// for the file already shown closes it; another file replaces it.
+#[tauri::command]
+async fn quick_look(
+    app_handle: AppHandle,
+    quick_look_state: State<'_, QuickLookState>,
+    file_path: String,
+) -> Result<(), SnippError> {
+    if let Some((shown, child)) = quick_look_state.lock().unwrap().take() {
+        let _ = child.kill();
+        if shown == file_path {
+            return Ok(());
+        }
+    }
+
+    if !std::path::Path::new(&file_path).exists() {
+        return Err("The screenshot file no longer exists".into());
+    }
+
+    let (mut events, child) = app_handle
+        .shell()
+        .command("qlmanage")
+        .args(["-p", &file_path])
+        .spawn()
+        .map_err(|e| format!("Failed to start Quick Look: {}", e))?;
+    *quick_look_state.lock().unwrap() = Some((file_path.clone(), child));
+
+    // Forget the preview once the user closes the panel themselves.
+    let handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        while let Some(event) = events.recv().await {
+            if let CommandEvent::Terminated(_) = event {
+                let state = handle.state::<QuickLookState>();
+                let mut open = state.lock().unwrap();
+                if open.as_ref().is_some_and(|(shown, _)| *shown == file_path) {
+                    *open = None;
+                }
+                break;
+            }
+        }
+    });
+    Ok(())
+}
+
+#[tauri::command]
+async fn delete_screenshot(
+    file_path: String,
+    config_state: State<'_, ConfigState>,
+    history_state: State<'_, HistoryState>,
+    thumbnail_state: State<'_, ThumbnailState>,
+) -> Result<(), SnippError> {
+    let secure_delete = config_state.lock().unwrap().get_config().secure_delete;
+    if secure_delete {
+        let thumbnails = thumbnail_state.lock().unwrap().thumbnails_of(&file_path);
+        let image = std::path::PathBuf::from(&file_path);
+        tauri::async_runtime::spawn_blocking(move || {
+            shred::shred_file(&image)?;
+            sidecar::existing_sidecars(&image)
+                .iter()
+                .try_for_each(|sidecar| shred::shred_file(sidecar))?;
+            thumbnails.iter().try_for_each(|thumbnail| shred::shred_file(thumbnail))
+        })
+        .await
+        .map_err(|e| format!("Secure delete task failed: {}", e))??;
+    } else if std::path::Path::new(&file_path).exists() {
+        tokio::fs::remove_file(&file_path)
+            .await
+            .map_err(|e| format!("Failed to delete file: {}", e))?;
+    }
+    if !secure_delete {
+        for sidecar in sidecar::existing_sidecars(std::path::Path::new(&file_path)) {
+            if let Err(e) = tokio::fs::remove_file(&sidecar).await {
+                tracing::error!("Failed to delete sidecar: {}", e);
+            }
+        }
+    }
+    
+    {
+        let mut history = history_state.lock().unwrap();
+        if let Err(e) = history.remove_screenshot(&file_path) {
+            tracing::error!("Failed to remove screenshot from history: {}", e);
+        }
+    }
+    
+    {
+        let thumbnail_gen = thumbnail_state.lock().unwrap();
+        if let Err(e) = thumbnail_gen.remove_thumbnails(&file_path) {
+            tracing::error!("Failed to remove thumbnail: {}", e);
+        }
+    }
+    
+    Ok(())
+}
+
+/// How much disk the thumbnail cache takes.
+#[tauri::command]
+async fn get_thumbnail_cache_usage(thumbnail_state: State<'_, ThumbnailState>) -> Result<CacheUsage, SnippError> {
+    let thumbnail_gen = thumbnail_state.lock().unwrap().clone();
+    Ok(tokio::task::spawn_blocking(move || thumbnail_gen.usage())
+        .await
+        .map_err(|e| format!("Thumbnail cache scan failed: {}", e))?)
+}
+
+/// Empties the thumbnail cache and reports what was freed.
+#[tauri::command]
+async fn purge_thumbnail_cache(thumbnail_state: State<'_, ThumbnailState>) -> Result<CacheUsage, SnippError> {
+    let thumbnail_gen = thumbnail_state.lock().unwrap().clone();
+    Ok(tokio::task::spawn_blocking(move || thumbnail_gen.purge().map_err(|e| format!("Failed to clear thumbnails: {}", e)))
+        .await
+        .map_err(|e| format!("Thumbnail cache purge failed: {}", e))??)
+}
+
+#[tauri::command]
+async fn close_recent_window(app_handle: AppHandle) -> Result<(), SnippError> {
+    if let Some(recent_window) = app_handle.get_webview_window("recent_screenshots") {
+        recent_window.close()
+            .map_err(|e| format!("Failed to close recent window: {}", e))?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+async fn open_editor_window(
+    app_handle: AppHandle,
+    timestamp: u64,
+    file_path: Option<String>,
+) -> Result<(), SnippError> {
+    tracing::debug!("Opening editor window for screenshot: {}", timestamp);
+
+    // The editor loads the pixels itself over the protocol; this only checks
+    // the capture is still there to load.
+    if !app_handle.state::<ScreenshotStore>().contains(timestamp) {
+        return Err(SnippError::not_cached());
+    }
+
+    if let Some(existing_editor) = app_handle.get_webview_window("editor") {
+        existing_editor.close().map_err(|e| format!("Failed to close existing editor: {}", e))?;
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+    }
+
+    let editor_window = WebviewWindowBuilder::new(
+        &app_handle,
+        "editor",
+        WebviewUrl::App("editor.html".into())
+    )
+    .title("Snipp Editor")
+    .inner_size(1200.0, 800.0)
+    .min_inner_size(800.0, 600.0)
+    .center()
+    .resizable(true)
+    .build()
+    .map_err(|e| format!("Failed to create editor window: {}", e))?;
+
+    editor_window.show().map_err(|e| format!("Failed to show editor: {}", e))?;
+
+    let (ready_tx, ready_rx) = tokio::sync::oneshot::channel::<()>();
+    let ready_tx = std::sync::Arc::new(std::sync::Mutex::new(Some(ready_tx)));
+    let ready_tx_clone = ready_tx.clone();
+
+    let unlisten_id = editor_window.listen("editor-ready", move |_event| {
+        if let Some(tx) = ready_tx_clone.lock().unwrap().take() {
+            let _ = tx.send(());
+        }
+    });
+
+    tracing::debug!("Waiting for editor-ready signal (3s timeout)...");
+    let result = tokio::time::timeout(
+        tokio::time::Duration::from_secs(3),
+        ready_rx
+    ).await;
+
+    let _ = editor_window.unlisten(unlisten_id);
+
+    match result {
+        Ok(Ok(_)) => tracing::debug!("Received editor-ready signal"),
+        Ok(Err(_)) => tracing::debug!("Ready channel closed unexpectedly"),
+        Err(_) => tracing::debug!("Timeout waiting for editor-ready, emitting anyway"),
+    }
+
+    let editor_data = serde_json::json!({
+        "image_url": protocol::screenshot_url(timestamp),
+        "timestamp": timestamp,
+        "file_path": file_path,
+    });
+
+    editor_window.emit("editor-data", &editor_data)
+        .map_err(|e| format!("Failed to emit editor data: {}", e))?;
+
+    tracing::debug!("Editor data emitted successfully");
+
+    Ok(())
+}
+
+#[tauri::command]
+async fn close_editor_window(app_handle: AppHandle) -> Result<(), SnippError> {
+    if let Some(editor_window) = app_handle.get_webview_window("editor") {
+        editor_window.close()
+            .map_err(|e| format!("Failed to close editor: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Opens a window showing two saved captures side by side, with a slider
+/// and a blended overlay for spotting visual changes.
+#[tauri::command]
+async fn compare_screenshots(app_handle: AppHandle, a: String, b: String) -> Result<(), SnippError> {
+    let comparison = tokio::task::spawn_blocking(move || compare::build_comparison(&a, &b))
+        .await
+        .map_err(|e| format!("Comparison task failed: {}", e))??;
+
+    if let Some(existing) = app_handle.get_webview_window("compare") {
+        let _ = existing.close();
+    }
+
+    let window = WebviewWindowBuilder::new(
+        &app_handle,
+        "compare",
+        WebviewUrl::App("compare.html".into()),
+    )
+    .title("Compare Screenshots")
+    .inner_size(1200.0, 800.0)
+    .min_inner_size(600.0, 400.0)
+    .center()
+    .resizable(true)
+    .build()
+    .map_err(|e| format!("Failed to create compare window: {}", e))?;
+
+    wait_for_window_ready(&window, "compare-ready").await;
+
+    window
+        .emit("compare-data", &comparison)
+        .map_err(|e| format!("Failed to send comparison: {}", e))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+async fn close_compare_window(app_handle: AppHandle) -> Result<(), SnippError> {
+    if let Some(window) = app_handle.get_webview_window("compare") {
+        window
+            .close()
+            .map_err(|e| format!("Failed to close compare window: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Diffs two cached captures, aligned top-left, and highlights what changed.
+#[tauri::command]
+async fn diff_screenshots(
+    app_handle: AppHandle,
+    timestamp_a: u64,
+    timestamp_b: u64,
+) -> Result<compare::ScreenshotDiff, SnippError> {
+    let before = cached_capture(&app_handle, timestamp_a).await?;
+    let after = cached_capture(&app_handle, timestamp_b).await?;
+    Ok(tokio::task::spawn_blocking(move || compare::diff_captures(&before, &after))
+        .await
+        .map_err(|e| format!("Diff task failed: {}", e))??)
+}
+
+/// Combines cached captures and then saved ones, in order, into one image
+/// and opens it in the capture popup.
+#[tauri::command]
+async fn compose_collage(
+    app_handle: AppHandle,
+    timestamps: Vec<u64>,
+    file_paths: Option<Vec<String>>,
+    layout: collage::CollageLayout,
+) -> Result<ScreenshotData, SnippError> {
+    let mut images = Vec::new();
+    for timestamp in timestamps {
+        images.push(cached_capture(&app_handle, timestamp).await?);
+    }
+    for path in file_paths.unwrap_or_default() {
+        images.push(
+            tokio::fs::read(&path)
+                .await
+                .map_err(|e| format!("Failed to read {}: {}", path, e))?,
+        );
+    }
+
+    let png_bytes = tokio::task::spawn_blocking(move || collage::compose(&images, &layout))
+        .await
+        .map_err(|e| format!("Collage task failed: {}", e))??;
+
+    let timestamp = current_timestamp_millis();
+    let filename = build_screenshot_filename(timestamp, None);
+    Ok(present_capture(&app_handle, timestamp, filename, png_bytes, false).await?)
+}
+
+/// Uploads a saved capture to the configured host and copies its link.
+#[tauri::command]
+async fn upload_screenshot(app_handle: AppHandle, file_path: String) -> Result<String, SnippError> {
+    Ok(share_screenshot(&app_handle, &file_path).await?)
+}
+
+/// Uploads several captures through the upload queue and copies their
+/// links, one per line. Files that fail are reported, not fatal.
+#[tauri::command]
+async fn upload_screenshots(
+    app_handle: AppHandle,
+    file_paths: Vec<String>,
+) -> Result<Vec<UploadOutcome>, SnippError> {
+    let uploads: Vec<_> = file_paths
+        .into_iter()
+        .map(|file_path| {
+            let app_handle = app_handle.clone();
+            tauri::async_runtime::spawn(async move {
+                let result = upload_with_progress(&app_handle, &file_path).await;
+                UploadOutcome {
+                    file_path,
+                    url: result.as_ref().ok().cloned(),
+                    error: result.err(),
+                }
+            })
+        })
+        .collect();
+
+    let mut outcomes = Vec::with_capacity(uploads.len());
+    for upload in uploads {
+        outcomes.push(upload.await.map_err(|e| format!("Upload task failed: {}", e))?);
+    }
+
+    let links: Vec<&str> = outcomes.iter().filter_map(|o| o.url.as_deref()).collect();
+    if !links.is_empty() {
+        app_handle
+            .clipboard()
+            .write_text(links.join("\n"))
+            .map_err(|e| format!("Failed to copy links: {}", e))?;
+    }
+    let body = format!("{} of {} uploaded, links copied", links.len(), outcomes.len());
+    if let Err(e) = app_handle.notification().builder().title("Upload finished").body(&body).show() {
+        tracing::error!("Failed to show notification: {}", e);
+    }
+    Ok(outcomes)
+}
+
+async fn share_screenshot(app_handle: &AppHandle, file_path: &str) -> Result<String, String> {
+    let url = upload_with_progress(app_handle, file_path).await?;
+    app_handle
+        .clipboard()
+        .write_text(url.clone())
+        .map_err(|e| format!("Failed to copy link: {}", e))?;
+    if let Err(e) = app_handle.notification().builder().title("Link copied").body(&url).show() {
+        tracing::error!("Failed to show notification: {}", e);
+    }
+    announce(app_handle, "Link copied to clipboard");
+    Ok(url)
+}
+
+/// [`upload_and_record`], reporting each stage as `upload-progress`.
+async fn upload_with_progress(app_handle: &AppHandle, file_path: &str) -> Result<String, String> {
+    let progress = |stage| {
+        events::emit(
+            app_handle,
+            &events::UploadProgress {
+                file_path: file_path.to_string(),
+                stage,
+            },
+        )
+    };
+    progress(events::UploadStage::Started);
+    let result = upload_and_record(app_handle, file_path, &progress).await;
+    progress(match &result {
+        Ok(url) => events::UploadStage::Completed { url: url.clone() },
+        Err(error) => events::UploadStage::Failed { error: error.clone() },
+    });
+    result
+}
+
+/// Uploads (and optionally shortens) one capture and stores the link in
+/// its history entry.
+async fn upload_and_record(
+    app_handle: &AppHandle,
+    file_path: &str,
+    progress: &impl Fn(events::UploadStage),
+) -> Result<String, String> {
+    let settings = {
+        let config_state = app_handle.state::<ConfigState>();
+        let config = config_state.lock().unwrap();
+        config.get_config().upload.clone()
+    };
+    if !settings.enabled {
+        return Err("Uploading is turned off in settings".into());
+    }
+
+    let png = tokio::fs::read(file_path)
+        .await
+        .map_err(|e| format!("Failed to read image file: {}", e))?;
+    let mut filename = std::path::Path::new(file_path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("Snipp.png")
+        .to_string();
+    let image = if settings.auto_format {
+        let (format, image) = tokio::task::spawn_blocking(move || optimize::smallest(&png))
+            .await
+            .map_err(|e| format!("Optimize task failed: {}", e))??;
+        filename = std::path::Path::new(&filename)
+            .with_extension(format.extension())
+            .to_string_lossy()
+            .to_string();
+        image
+    } else {
+        png
+    };
+    let queue = app_handle.state::<UploadQueue>();
+    progress(events::UploadStage::Uploading);
+    let mut url = uploader::upload(&queue, &settings, &filename, image).await?;
+    if settings.shortener.enabled {
+        progress(events::UploadStage::Shortening);
+        // A shortener outage shouldn't cost the user the upload itself.
+        match uploader::shorten(&settings.shortener, &url).await {
+            Ok(short) => url = short,
+            Err(e) => tracing::warn!("Keeping the full link: {}", e),
+        }
+    }
+
+    let entry = {
+        let history_state = app_handle.state::<HistoryState>();
+        let mut history = history_state.lock().unwrap();
+        if let Err(e) = history.set_share_url(file_path, &url) {
+            tracing::error!("Failed to record share link: {}", e);
+        }
+        history
+            .get_history()
+            .screenshots
+            .iter()
+            .find(|entry| entry.file_path == file_path)
+            .cloned()
+    };
+
+    // The file is already saved and shared, so upload hooks can only tag it.
+    if let Some(entry) = entry {
+        let context = HookContext {
+            folder: std::path::Path::new(file_path)
+                .parent()
+                .map(|parent| parent.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            file_path: file_path.to_string(),
+            url: url.clone(),
+            filename: entry.filename,
+            note: entry.note,
+            tags: entry.tags,
+            ..HookContext::default()
+        };
+        let actions = run_hook(app_handle, HookEvent::Upload, context).await;
+        if actions.filename.is_some() || actions.folder.is_some() {
+            tracing::warn!("on_upload can't rename or move a capture; only its tags were applied");
+        }
+        if !actions.tags.is_empty() {
+            let history_state = app_handle.state::<HistoryState>();
+            let mut history = history_state.lock().unwrap();
+            if let Err(e) = history.add_tags(file_path, &actions.tags) {
+                tracing::error!("Failed to tag screenshot: {}", e);
+            }
+        }
+    }
+    Ok(url)
+}
+
+/// Shows the quick search palette, or hides it when it is already up.
+#[tauri::command]
+async fn toggle_quick_search(app_handle: AppHandle) -> Result<(), SnippError> {
+    if let Some(window) = app_handle.get_webview_window("quick_search") {
+        if window.is_visible().unwrap_or(false) {
+            window.hide().map_err(|e| format!("Failed to hide quick search: {}", e))?;
+            return Ok(());
+        }
+        // Clear the last query before the window reappears.
+        let _ = window.emit("quick-search-opened", ());
+        window.show().map_err(|e| format!("Failed to show quick search: {}", e))?;
+        let _ = window.set_focus();
+        return Ok(());
+    }
+
+    let window = WebviewWindowBuilder::new(
+        &app_handle,
+        "quick_search",
+        WebviewUrl::App("search.html".into()),
+    )
+    .title("Quick Search")
+    .inner_size(640.0, 420.0)
+    .center()
+    .decorations(false)
+    .transparent(true)
+    .always_on_top(true)
+    .skip_taskbar(true)
+    .resizable(false)
+    .visible(false)
+    .build()
+    .map_err(|e| format!("Failed to create quick search window: {}", e))?;
+
+    wait_for_window_ready(&window, "quick-search-ready").await;
+    window.show().map_err(|e| format!("Failed to show quick search: {}", e))?;
+    let _ = window.set_focus();
+    Ok(())
+}
+
 #[tauri::command]
-async fn choose_save_location(app_handle: AppHandle) -> Result<Option<String>, String> {
-    use tokio::sync::oneshot;
-    
-    let (sender, receiver) = oneshot::channel();
-    
-    app_handle.dialog().file()
-        .set_title("Choose Save Location")
-        .pick_folder(move |folder_path| {
-            let result = folder_path.map(|p| p.to_string());
-            let _ = sender.send(result);
-        });
-    
-    let folder = receiver.await
-        .map_err(|e| format!("Dialog receiver error: {}", e))?;
-    
-    Ok(folder)
+async fn close_quick_search(app_handle: AppHandle) -> Result<(), SnippError> {
+    if let Some(window) = app_handle.get_webview_window("quick_search") {
+        window
+            .hide()
+            .map_err(|e| format!("Failed to hide quick search: {}", e))?;
+    }
+    Ok(())
 }
 
+/// Past captures for the palette: the most recent ones for an empty query,
+/// otherwise those whose filename, note or recognized text match.
 #[tauri::command]
-async fn get_recent_screenshots(
+async fn quick_search(
+    query: String,
     history_state: State<'_, HistoryState>,
     thumbnail_state: State<'_, ThumbnailState>,
-) -> Result<Vec<serde_json::Value>, String> {
-    // Snapshot under the locks, then release them before any thumbnail IO/CPU.
-    let recent: Vec<(String, chrono::DateTime<chrono::Utc>, String)> = {
+) -> Result<Vec<serde_json::Value>, SnippError> {
+    let found: Vec<ScreenshotHistory> = {
         let history = history_state
             .lock()
             .map_err(|e| format!("History lock poisoned: {}", e))?;
-        history
-            .get_recent_screenshots(10)
-            .into_iter()
-            .map(|s| (s.file_path.clone(), s.timestamp, s.filename.clone()))
-            .collect()
+        history.search(&query, 8).into_iter().cloned().collect()
     };
 
-    let thumbnail_gen = {
-        let guard = thumbnail_state
-            .lock()
-            .map_err(|e| format!("Thumbnail lock poisoned: {}", e))?;
-        guard.clone()
+    Ok(describe_screenshots(found, &thumbnail_state).await?)
+}
+
+/// Opens a saved capture in its default app.
+#[tauri::command]
+async fn open_screenshot(app_handle: AppHandle, file_path: String) -> Result<(), SnippError> {
+    Ok(app_handle
+        .opener()
+        .open_path(&file_path, None::<&str>)
+        .map_err(|e| format!("Failed to open screenshot: {}", e))?)
+}
+
+#[tauri::command]
+async fn save_edited_screenshot(
+    app_handle: AppHandle,
+    base64_image: String,
+    timestamp: u64,
+    config_state: State<'_, ConfigState>,
+) -> Result<String, SnippError> {
+    tracing::debug!("Saving edited screenshot: {}", timestamp);
+
+    let image_data = tokio::task::spawn_blocking(move || base64::prelude::BASE64_STANDARD.decode(&base64_image))
+        .await
+        .map_err(|e| format!("Decode task failed: {}", e))?
+        .map_err(|e| format!("Failed to decode image: {}", e))?;
+
+    let filename = saved_filename(&app_handle, timestamp, Some("-edited"));
+    let entry = save_to_locations(&app_handle, timestamp, &filename, &image_data, "").await?;
+    let file_path_str = entry.file_path.clone();
+    tracing::debug!("Successfully saved edited screenshot to: {}", file_path_str);
+
+    events::emit(&app_handle, &events::SaveCompleted::new(Some(timestamp), &entry));
+    record_in_history(&app_handle, timestamp, entry, image_data.clone(), &["edited"]).await;
+
+    // Auto-copy edited screenshot to clipboard if enabled
+    let should_auto_copy_edited = {
+        let config = config_state.lock().unwrap();
+        config.get_config().auto_copy_after_edit
     };
 
-    // Decode/resize/encode is blocking work; keep it off the async runtime.
-    let screenshots = tokio::task::spawn_blocking(move || {
-        recent
-            .into_iter()
-            .map(|(file_path, timestamp, filename)| {
-                let thumbnail = if std::path::Path::new(&file_path).exists() {
-                    thumbnail_gen
-                        .get_thumbnail_base64(&file_path, 64)
-                        .unwrap_or_default()
-                } else {
-                    String::new()
-                };
+    if should_auto_copy_edited {
+        if let Err(e) = write_png_bytes_to_clipboard(&app_handle, image_data).await {
+            tracing::error!("Auto-copy edited screenshot failed: {}", e);
+        } else {
+            tracing::debug!("Auto-copied edited screenshot to clipboard after save");
+            events::emit(&app_handle, &events::CopyCompleted::image(Some(timestamp), Some(file_path_str.clone())));
+        }
+    }
 
-                serde_json::json!({
-                    "file_path": file_path,
-                    "timestamp": timestamp,
-                    "filename": filename,
-                    "thumbnail": thumbnail,
-                })
-            })
-            .collect::<Vec<_>>()
-    })
-    .await
-    .map_err(|e| format!("Thumbnail generation task failed: {}", e))?;
+    // Close editor after save
+    if let Some(editor_window) = app_handle.get_webview_window("editor") {
+        let _ = editor_window.close();
+    }
 
-    Ok(screenshots)
+    Ok(file_path_str)
 }
 
 #[tauri::command]
-async fn copy_screenshot_from_path(
+async fn copy_edited_screenshot(
     app_handle: AppHandle,
-    file_path: String,
-) -> Result<(), String> {
-    let image_data = std::fs::read(&file_path)
-        .map_err(|e| format!("Failed to read image file: {}", e))?;
+    base64_image: String,
+    timestamp: u64,
+) -> Result<(), SnippError> {
+    tracing::debug!("Copying edited screenshot to clipboard");
+
+    let image_data = base64::prelude::BASE64_STANDARD
+        .decode(&base64_image)
+        .map_err(|e| format!("Failed to decode image: {}", e))?;
 
-    write_png_bytes_to_clipboard(&app_handle, &image_data)?;
-    log::debug!("Successfully copied screenshot to clipboard");
+    write_png_bytes_to_clipboard(&app_handle, image_data).await?;
+    tracing::debug!("Successfully copied edited screenshot to clipboard");
+    events::emit(&app_handle, &events::CopyCompleted::image(Some(timestamp), None));
     Ok(())
 }
 
 #[tauri::command]
-async fn open_in_finder(app_handle: AppHandle, file_path: String) -> Result<(), String> {
-    let output = app_handle
-        .shell()
-        .command("open")
-        .args(["-R", &file_path])
-        .output()
+async fn prepare_drag_file(
+    app_handle: AppHandle,
+    config_state: State<'_, ConfigState>,
+    timestamp: u64,
+) -> Result<String, SnippError> {
+    let image_data = cached_capture(&app_handle, timestamp).await?;
+
+    let (output_format, quality) = {
+        let config = config_state.lock().unwrap();
+        let config = config.get_config();
+        (config.output_format, config.output_quality())
+    };
+    let output_format = if burst::is_gif(&image_data) { OutputFormat::Png } else { output_format };
+    let temp_path = drag_file_path(timestamp, output_format);
+    let image_data = tokio::task::spawn_blocking(move || encode_output(&image_data, output_format, quality))
         .await
-        .map_err(|e| format!("Failed to open in Finder: {}", e))?;
+        .map_err(|e| format!("Encode task failed: {}", e))??;
 
-    if output.status.success() {
-        Ok(())
-    } else {
-        let error = String::from_utf8_lossy(&output.stderr);
-        Err(format!("Failed to reveal in Finder: {}", error))
+    tokio::fs::write(&temp_path, &image_data)
+        .await
+        .map_err(|e| format!("Failed to write drag temp file: {}", e))?;
+
+    Ok(temp_path.to_string_lossy().to_string())
+}
+
+/// Re-encodes a PNG capture in the save format; HEIC goes through ImageIO.
+fn encode_output(png: &[u8], format: OutputFormat, quality: u8) -> Result<Vec<u8>, String> {
+    match format {
+        OutputFormat::Heic => heic::encode(png, quality),
+        _ => output::encode(png, format, quality),
     }
 }
 
+/// Temp file a capture is dragged out as, in the configured save format.
+fn drag_file_path(timestamp: u64, output_format: OutputFormat) -> PathBuf {
+    std::env::temp_dir()
+        .join(build_screenshot_filename(timestamp, None))
+        .with_extension(output_format.extension())
+}
+
 #[tauri::command]
-async fn delete_screenshot(
-    file_path: String,
-    history_state: State<'_, HistoryState>,
-    thumbnail_state: State<'_, ThumbnailState>,
-) -> Result<(), String> {
-    if std::path::Path::new(&file_path).exists() {
-        std::fs::remove_file(&file_path)
-            .map_err(|e| format!("Failed to delete file: {}", e))?;
+async fn cleanup_drag_file(config_state: State<'_, ConfigState>, timestamp: u64) -> Result<(), SnippError> {
+    let output_format = config_state.lock().unwrap().get_config().output_format;
+    let temp_path = drag_file_path(timestamp, output_format);
+    if temp_path.exists() {
+        let _ = tokio::fs::remove_file(&temp_path).await;
     }
-    
+    Ok(())
+}
+
+/// Starts an ffmpeg screen recording of the whole screen, an explicit region,
+/// or a named region preset (the same presets `capture_region` uses).
+/// `system_audio` and `quality` override the configured defaults for this run.
+#[tauri::command]
+async fn start_recording(
+    app_handle: AppHandle,
+    config_state: State<'_, ConfigState>,
+    recording_state: State<'_, RecordingState>,
+    region: Option<CaptureRegion>,
+    preset: Option<String>,
+    system_audio: Option<bool>,
+    quality: Option<RecordingQuality>,
+) -> Result<RecordingInfo, SnippError> {
+    if recording_state.lock().unwrap().is_recording() {
+        return Err("A recording is already in progress".into());
+    }
+
+    let scale_factor = app_handle
+        .primary_monitor()
+        .ok()
+        .flatten()
+        .map(|monitor| monitor.scale_factor())
+        .unwrap_or(1.0);
+
+    // The output path is filled in once the save location is known.
+    let (save_location, ffmpeg_path, limits, mut options) = {
+        let config = config_state.lock().unwrap();
+        let config = config.get_config();
+        let region = region::resolve_region(region, preset.as_deref(), &config.region_presets)?;
+        let quality = quality.unwrap_or_else(|| config.recording_quality.clone());
+        quality.validate()?;
+
+        let mut options = recording::RecordingOptions::new(PathBuf::new(), region, scale_factor);
+        options.quality = quality;
+        options.audio_device = config.record_microphone.then(|| {
+            if config.microphone_device.is_empty() {
+                "default".to_string()
+            } else {
+                config.microphone_device.clone()
+            }
+        });
+        // The per-recording toggle wins over the configured default.
+        options.system_audio = system_audio.unwrap_or(config.record_system_audio);
+        options.webcam = config
+            .webcam_overlay
+            .enabled
+            .then(|| config.webcam_overlay.clone());
+        options.click_highlight =
+            region::resolve_click_highlight(preset.as_deref(), &config.region_presets, config.click_highlight);
+        (
+            config.default_save_location.clone(),
+            config.ffmpeg_path.clone(),
+            config.recording_limits.clone(),
+            options,
+        )
+    };
+
+    let ffmpeg = recording::find_ffmpeg(&ffmpeg_path)
+        .ok_or("ffmpeg was not found; install it (e.g. `brew install ffmpeg`) or set its path in preferences")?;
+
+    let id = current_timestamp_millis();
+    let output_path = resolve_unique_path(
+        PathBuf::from(&save_location).join(recording::build_recording_filename(id)),
+    );
+    if let Some(parent) = output_path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| format!("Failed to create save directory: {}", e))?;
+    }
+    options.output_path = output_path;
+
+    let active = recording::ActiveRecording::start(&app_handle, id, ffmpeg, options)?;
+    let info = active.info();
+    recording_state.lock().unwrap().begin(active);
+
+    tracing::debug!("Started recording {} -> {}", id, info.file_path);
+    let _ = app_handle.emit("recording-started", &info);
+    spawn_recording_ticker(app_handle.clone(), id, limits);
+    refresh_tray_menu(&app_handle);
+
+    Ok(info)
+}
+
+/// Emits `recording-progress` once a second and stops the recording when it
+/// runs past the configured duration or size limit.
+fn spawn_recording_ticker(app_handle: AppHandle, id: u64, limits: RecordingLimits) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(1));
+        loop {
+            interval.tick().await;
+            let (status, segments) = {
+                let state = app_handle.state::<RecordingState>();
+                let manager = state.lock().unwrap();
+                (manager.status(), manager.segment_paths())
+            };
+            if status.id != Some(id) {
+                break;
+            }
+            let _ = app_handle.emit("recording-progress", &status);
+
+            let mut bytes_written = 0;
+            for segment in &segments {
+                if let Ok(metadata) = tokio::fs::metadata(segment).await {
+                    bytes_written += metadata.len();
+                }
+            }
+            let recorded = std::time::Duration::from_millis(status.recorded_ms);
+            if let Some(reason) = limits.exceeded(recorded, bytes_written) {
+                stop_recording_at_limit(&app_handle, reason).await;
+                break;
+            }
+        }
+    });
+}
+
+async fn stop_recording_at_limit(app_handle: &AppHandle, reason: String) {
+    tracing::warn!("{}; stopping", reason);
+    if let Err(e) = stop_recording(
+        app_handle.clone(),
+        app_handle.state::<RecordingState>(),
+        app_handle.state::<HistoryState>(),
+    )
+    .await
     {
-        let mut history = history_state.lock().unwrap();
-        if let Err(e) = history.remove_screenshot(&file_path) {
-            log::error!("Failed to remove screenshot from history: {}", e);
+        tracing::error!("Failed to stop recording at limit: {}", e);
+    }
+
+    let _ = app_handle.emit("recording-limit-reached", &reason);
+    if let Err(e) = app_handle
+        .notification()
+        .builder()
+        .title("Recording stopped")
+        .body(&reason)
+        .show()
+    {
+        tracing::error!("Failed to show notification: {}", e);
+    }
+}
+
+fn refresh_tray_menu(app_handle: &AppHandle) {
+    let config = app_handle.state::<ConfigState>().lock().unwrap().get_config().clone();
+    if let Err(e) = tray::update_tray_menu(app_handle, &config) {
+        tracing::error!("Failed to update tray menu: {}", e);
+    }
+}
+
+#[tauri::command]
+async fn pause_recording(
+    app_handle: AppHandle,
+    recording_state: State<'_, RecordingState>,
+) -> Result<RecordingStatus, SnippError> {
+    let (segment, done) = {
+        let mut manager = recording_state.lock().unwrap();
+        let active = manager.active_mut().ok_or("No recording in progress")?;
+        active.pause()?
+    };
+
+    // A stop that arrives meanwhile takes the recording and waits on `done`
+    // before joining segments.
+    let recorded = segment.finish().await;
+    let _ = done.send(Some(recorded));
+
+    let status = {
+        let mut manager = recording_state.lock().unwrap();
+        if let Some(active) = manager.active_mut() {
+            active.settle_pause();
         }
+        manager.status()
+    };
+
+    if status.active {
+        let _ = app_handle.emit("recording-paused", &status);
     }
-    
+    refresh_tray_menu(&app_handle);
+    Ok(status)
+}
+
+#[tauri::command]
+async fn resume_recording(
+    app_handle: AppHandle,
+    recording_state: State<'_, RecordingState>,
+) -> Result<RecordingStatus, SnippError> {
+    let status = {
+        let mut manager = recording_state.lock().unwrap();
+        let active = manager.active_mut().ok_or("No recording in progress")?;
+        active.resume(&app_handle)?;
+        manager.status()
+    };
+
+    let _ = app_handle.emit("recording-resumed", &status);
+    refresh_tray_menu(&app_handle);
+    Ok(status)
+}
+
+/// Finalizes the recording (joining paused segments) and records the result
+/// in history.
+#[tauri::command]
+async fn stop_recording(
+    app_handle: AppHandle,
+    recording_state: State<'_, RecordingState>,
+    history_state: State<'_, HistoryState>,
+) -> Result<RecordingInfo, SnippError> {
+    let active = recording_state
+        .lock()
+        .unwrap()
+        .take_active()
+        .ok_or("No recording in progress")?;
+
+    let finished = active.finalize(&app_handle).await;
+    refresh_tray_menu(&app_handle);
+    let info = finished?;
+
+    recording_state.lock().unwrap().record_completed(info.clone());
     {
-        let thumbnail_gen = thumbnail_state.lock().unwrap();
-        if let Err(e) = thumbnail_gen.remove_thumbnail(&file_path, 64) {
-            log::error!("Failed to remove thumbnail: {}", e);
+        let mut history = history_state.lock().unwrap();
+        if let Err(e) = history.add_screenshot(info.file_path.clone()) {
+            tracing::error!("Failed to add recording to history: {}", e);
         }
     }
-    
+
+    tracing::debug!("Recording saved to: {}", info.file_path);
+    let _ = app_handle.emit("recording-stopped", &info);
+
+    Ok(info)
+}
+
+/// A finished recording by the id this session gave it or, for recordings
+/// from earlier sessions, by its file in history. Those carry no region
+/// and an unknown (0) duration.
+fn find_recording(
+    recording_state: &RecordingState,
+    history_state: &HistoryState,
+    id: u64,
+    file_path: Option<&str>,
+) -> Result<RecordingInfo, String> {
+    if let Some(info) = recording_state.lock().unwrap().find_completed(id) {
+        return Ok(info.clone());
+    }
+    let file_path = file_path.ok_or("Recording not found")?;
+    let in_history = history_state
+        .lock()
+        .unwrap()
+        .get_history()
+        .screenshots
+        .iter()
+        .any(|entry| entry.saved_paths().contains(&file_path));
+    if !in_history || !Path::new(file_path).is_file() {
+        return Err("Recording not found".to_string());
+    }
+    Ok(RecordingInfo {
+        id,
+        file_path: file_path.to_string(),
+        region: None,
+        duration_ms: 0,
+    })
+}
+
+/// Writes a trimmed copy of a finished recording next to the original and
+/// adds it to history. `start_ms`/`end_ms` are offsets into the recording.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn trim_recording(
+    app_handle: AppHandle,
+    config_state: State<'_, ConfigState>,
+    recording_state: State<'_, RecordingState>,
+    history_state: State<'_, HistoryState>,
+    id: u64,
+    file_path: Option<String>,
+    start_ms: u64,
+    end_ms: u64,
+) -> Result<RecordingInfo, SnippError> {
+    let original = find_recording(&recording_state, &history_state, id, file_path.as_deref())?;
+    recording::validate_trim(start_ms, end_ms, original.duration_ms)?;
+    let end_ms = if original.duration_ms > 0 {
+        end_ms.min(original.duration_ms)
+    } else {
+        end_ms
+    };
+
+    let ffmpeg_path = config_state.lock().unwrap().get_config().ffmpeg_path.clone();
+    let ffmpeg = recording::find_ffmpeg(&ffmpeg_path).ok_or("ffmpeg was not found")?;
+
+    let input = PathBuf::from(&original.file_path);
+    let output = resolve_unique_path(recording::trimmed_output_path(&input));
+    recording::trim_file(&app_handle, &ffmpeg, &input, &output, start_ms, end_ms).await?;
+
+    let info = RecordingInfo {
+        id: current_timestamp_millis(),
+        file_path: output.to_string_lossy().to_string(),
+        region: original.region,
+        duration_ms: end_ms - start_ms,
+    };
+    recording_state.lock().unwrap().record_completed(info.clone());
+    if let Err(e) = history_state.lock().unwrap().add_screenshot(info.file_path.clone()) {
+        tracing::error!("Failed to add trimmed recording to history: {}", e);
+    }
+
+    tracing::debug!("Trimmed recording {} -> {}", id, info.file_path);
+    Ok(info)
+}
+
+/// Converts a finished recording into an animated GIF or WebP saved next to
+/// it. `options` overrides the configured animation settings.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn convert_recording(
+    app_handle: AppHandle,
+    config_state: State<'_, ConfigState>,
+    recording_state: State<'_, RecordingState>,
+    history_state: State<'_, HistoryState>,
+    id: u64,
+    file_path: Option<String>,
+    format: AnimationFormat,
+    options: Option<AnimationOptions>,
+) -> Result<String, SnippError> {
+    let recording = find_recording(&recording_state, &history_state, id, file_path.as_deref())?;
+
+    let (ffmpeg_path, options) = {
+        let config = config_state.lock().unwrap();
+        let config = config.get_config();
+        let options = options.unwrap_or_else(|| config.animation_export.clone());
+        (config.ffmpeg_path.clone(), options)
+    };
+    options.validate()?;
+    let ffmpeg = recording::find_ffmpeg(&ffmpeg_path).ok_or("ffmpeg was not found")?;
+
+    let input = PathBuf::from(&recording.file_path);
+    let output = resolve_unique_path(input.with_extension(format.extension()));
+    recording::convert_to_animation(&app_handle, &ffmpeg, &input, &output, format, &options)
+        .await?;
+
+    let file_path = output.to_string_lossy().to_string();
+    if let Err(e) = history_state.lock().unwrap().add_screenshot(file_path.clone()) {
+        tracing::error!("Failed to add animation to history: {}", e);
+    }
+
+    tracing::debug!("Converted recording {} -> {}", id, file_path);
+    Ok(file_path)
+}
+
+/// Reports what a cached capture would weigh as optimized PNG, JPEG and
+/// WebP, and which of them is smallest without visible loss.
+#[tauri::command]
+async fn analyze_capture(
+    app_handle: AppHandle,
+    timestamp: u64,
+) -> Result<optimize::SizeReport, SnippError> {
+    let image_data = cached_capture(&app_handle, timestamp).await?;
+    Ok(tokio::task::spawn_blocking(move || optimize::analyze(&image_data))
+        .await
+        .map_err(|e| format!("Analyze task failed: {}", e))??)
+}
+
+/// The color of pixel `(x, y)` of a cached capture, in image pixels.
+#[tauri::command]
+async fn pick_color(
+    app_handle: AppHandle,
+    timestamp: u64,
+    x: u32,
+    y: u32,
+) -> Result<ColorSample, SnippError> {
+    let image_data = cached_capture(&app_handle, timestamp).await?;
+    Ok(tokio::task::spawn_blocking(move || palette::pick(&image_data, x, y))
+        .await
+        .map_err(|e| format!("Color task failed: {}", e))??)
+}
+
+/// The `n` dominant colors of a cached capture, most common first.
+#[tauri::command]
+async fn extract_palette(
+    app_handle: AppHandle,
+    timestamp: u64,
+    n: usize,
+) -> Result<Vec<PaletteColor>, SnippError> {
+    let image_data = cached_capture(&app_handle, timestamp).await?;
+    Ok(tokio::task::spawn_blocking(move || palette::extract(&image_data, n))
+        .await
+        .map_err(|e| format!("Palette task failed: {}", e))??)
+}
+
+/// Decodes the QR codes and barcodes in a cached capture.
+#[tauri::command]
+async fn detect_codes(
+    app_handle: AppHandle,
+    timestamp: u64,
+) -> Result<Vec<DetectedCode>, SnippError> {
+    let image_data = cached_capture(&app_handle, timestamp).await?;
+    Ok(barcode::detect_codes(&app_handle, &image_data).await?)
+}
+
+/// Frames a cached capture per `options` and keeps the result in its
+/// place; the preview reloads it over the protocol.
+#[tauri::command]
+async fn beautify_screenshot(
+    app_handle: AppHandle,
+    timestamp: u64,
+    options: BeautifyOptions,
+) -> Result<(), SnippError> {
+    let image_data = cached_capture(&app_handle, timestamp).await?;
+    if burst::is_gif(&image_data) {
+        return Err("GIF captures can't be beautified".into());
+    }
+    let framed = tokio::task::spawn_blocking(move || beautify::beautify(&image_data, &options))
+        .await
+        .map_err(|e| format!("Beautify task failed: {}", e))??;
+    cache_capture(&app_handle, timestamp, framed).await?;
     Ok(())
 }
 
+/// Rounds the corners of a cached capture, with an optional 1-4px border,
+/// and keeps the result in its place.
 #[tauri::command]
-async fn close_recent_window(app_handle: AppHandle) -> Result<(), String> {
-    if let Some(recent_window) = app_handle.get_webview_window("recent_screenshots") {
-        recent_window.close()
-            .map_err(|e| format!("Failed to close recent window: {}", e))?;
+async fn apply_corner_radius(
+    app_handle: AppHandle,
+    timestamp: u64,
+    radius: u32,
+    border: Option<Border>,
+) -> Result<(), SnippError> {
+    let image_data = cached_capture(&app_handle, timestamp).await?;
+    if burst::is_gif(&image_data) {
+        return Err("GIF captures can't be rounded".into());
+    }
+    let rounded = tokio::task::spawn_blocking(move || beautify::apply_corner_radius(&image_data, radius, border.as_ref()))
+        .await
+        .map_err(|e| format!("Rounding task failed: {}", e))??;
+    cache_capture(&app_handle, timestamp, rounded).await?;
+    Ok(())
+}
+
+/// Runs a pixel edit on a cached capture off the async runtime and keeps the
+/// result in its place.
+async fn edit_cached_capture(
+    app_handle: &AppHandle,
+    timestamp: u64,
+    edit: impl FnOnce(&[u8]) -> Result<Vec<u8>, String> + Send + 'static,
+) -> Result<(), SnippError> {
+    let image_data = cached_capture(app_handle, timestamp).await?;
+    if burst::is_gif(&image_data) {
+        return Err("GIF captures can't be edited".into());
     }
+    let edited = tokio::task::spawn_blocking(move || edit(&image_data))
+        .await
+        .map_err(|e| format!("Edit task failed: {}", e))??;
+    cache_capture(app_handle, timestamp, edited).await?;
     Ok(())
 }
 
+/// Crops a cached capture to `rect`, in image pixels.
 #[tauri::command]
-async fn open_editor_window(
+async fn crop_screenshot(
     app_handle: AppHandle,
     timestamp: u64,
-) -> Result<(), String> {
-    log::debug!("Opening editor window for screenshot: {}", timestamp);
-
-    let cache_key = timestamp.to_string();
-    let image_data = {
-        let cache = SCREENSHOT_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
-        let cache_guard = cache.lock().unwrap();
-        cache_guard.get(&cache_key).cloned()
-    };
-
-    let image_data = image_data.ok_or("Screenshot data not found in memory cache")?;
-    let base64_image = base64::prelude::BASE64_STANDARD.encode(&image_data);
-
-    if let Some(existing_editor) = app_handle.get_webview_window("editor") {
-        existing_editor.close().map_err(|e| format!("Failed to close existing editor: {}", e))?;
-        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
-    }
+    rect: transform::PixelRect,
+) -> Result<(), SnippError> {
+    edit_cached_capture(&app_handle, timestamp, move |png| transform::crop(png, rect)).await
+}
 
-    let editor_window = WebviewWindowBuilder::new(
-        &app_handle,
-        "editor",
-        WebviewUrl::App("editor.html".into())
-    )
-    .title("Snipp Editor")
-    .inner_size(1200.0, 800.0)
-    .min_inner_size(800.0, 600.0)
-    .center()
-    .resizable(true)
-    .build()
-    .map_err(|e| format!("Failed to create editor window: {}", e))?;
+/// Rotates a cached capture clockwise by a multiple of 90 degrees.
+#[tauri::command]
+async fn rotate_screenshot(app_handle: AppHandle, timestamp: u64, degrees: i32) -> Result<(), SnippError> {
+    edit_cached_capture(&app_handle, timestamp, move |png| transform::rotate(png, degrees)).await
+}
 
-    editor_window.show().map_err(|e| format!("Failed to show editor: {}", e))?;
+#[tauri::command]
+async fn flip_screenshot(
+    app_handle: AppHandle,
+    timestamp: u64,
+    axis: transform::FlipAxis,
+) -> Result<(), SnippError> {
+    edit_cached_capture(&app_handle, timestamp, move |png| transform::flip(png, axis)).await
+}
 
-    let (ready_tx, ready_rx) = tokio::sync::oneshot::channel::<()>();
-    let ready_tx = std::sync::Arc::new(std::sync::Mutex::new(Some(ready_tx)));
-    let ready_tx_clone = ready_tx.clone();
+/// Hides `rect` of a cached capture for sharing, with `style` or the
+/// configured redaction style.
+#[tauri::command]
+async fn redact_region(
+    app_handle: AppHandle,
+    timestamp: u64,
+    rect: transform::PixelRect,
+    style: Option<redact::RedactStyle>,
+    config_state: State<'_, ConfigState>,
+) -> Result<(), SnippError> {
+    let style = style.unwrap_or_else(|| config_state.lock().unwrap().get_config().redaction);
+    edit_cached_capture(&app_handle, timestamp, move |png| redact::redact_region(png, rect, style)).await
+}
 
-    let unlisten_id = editor_window.listen("editor-ready", move |_event| {
-        if let Some(tx) = ready_tx_clone.lock().unwrap().take() {
-            let _ = tx.send(());
-        }
-    });
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct AutoRedaction {
+    /// How many areas were hidden; 0 leaves the capture as it was.
+    pub count: usize,
+}
 
-    log::debug!("Waiting for editor-ready signal (3s timeout)...");
-    let result = tokio::time::timeout(
-        tokio::time::Duration::from_secs(3),
-        ready_rx
-    ).await;
+/// Recognizes the text of a cached capture and hides emails, keys, card
+/// numbers and configured patterns in it, in the configured style.
+#[tauri::command]
+async fn auto_redact(
+    app_handle: AppHandle,
+    timestamp: u64,
+    config_state: State<'_, ConfigState>,
+) -> Result<AutoRedaction, SnippError> {
+    let image_data = cached_capture(&app_handle, timestamp).await?;
+    if burst::is_gif(&image_data) {
+        return Err("GIF captures can't be redacted".into());
+    }
+    let (settings, style) = {
+        let config = config_state.lock().unwrap();
+        (config.get_config().auto_redact.clone(), config.get_config().redaction)
+    };
 
-    let _ = editor_window.unlisten(unlisten_id);
+    let recognized = ocr::recognize_text(&app_handle, &image_data).await?;
+    let (redacted, count) = tokio::task::spawn_blocking(move || {
+        let lines: Vec<redact::TextLine> = recognized
+            .lines
+            .iter()
+            .map(|line| redact::TextLine {
+                text: &line.text,
+                x: line.x,
+                y: line.y,
+                width: line.width,
+                height: line.height,
+            })
+            .collect();
+        redact::auto_redact(&image_data, &lines, &settings, style)
+    })
+    .await
+    .map_err(|e| format!("Redaction task failed: {}", e))??;
 
-    match result {
-        Ok(Ok(_)) => log::debug!("Received editor-ready signal"),
-        Ok(Err(_)) => log::debug!("Ready channel closed unexpectedly"),
-        Err(_) => log::debug!("Timeout waiting for editor-ready, emitting anyway"),
+    if count > 0 {
+        cache_capture(&app_handle, timestamp, redacted).await?;
     }
+    Ok(AutoRedaction { count })
+}
 
-    let editor_data = serde_json::json!({
-        "base64_image": base64_image,
-        "timestamp": timestamp,
-    });
-
-    editor_window.emit("editor-data", &editor_data)
-        .map_err(|e| format!("Failed to emit editor data: {}", e))?;
+/// Saves the editor's shapes next to a saved capture so they stay editable.
+#[tauri::command]
+async fn save_annotations(file_path: String, shapes: Vec<annotate::Shape>) -> Result<(), SnippError> {
+    Ok(tokio::task::spawn_blocking(move || sidecar::write_annotations(std::path::Path::new(&file_path), &shapes))
+        .await
+        .map_err(|e| format!("Annotation save task failed: {}", e))??)
+}
 
-    log::debug!("Editor data emitted successfully");
+/// The editable shapes saved next to a capture, if any.
+#[tauri::command]
+async fn load_annotations(file_path: String) -> Result<Vec<annotate::Shape>, SnippError> {
+    Ok(tokio::task::spawn_blocking(move || sidecar::read_annotations(std::path::Path::new(&file_path)))
+        .await
+        .map_err(|e| format!("Annotation load task failed: {}", e))??)
+}
 
-    Ok(())
+/// What the metadata sidecar of a saved capture records about where it was
+/// taken; `None` when it was saved without one.
+#[tauri::command]
+async fn get_screenshot_metadata(file_path: String) -> Result<Option<sidecar::Sidecar>, SnippError> {
+    Ok(tokio::task::spawn_blocking(move || sidecar::read_sidecar(std::path::Path::new(&file_path)))
+        .await
+        .map_err(|e| format!("Metadata load task failed: {}", e))??)
 }
 
+/// Draws editor annotations onto a cached capture at full resolution,
+/// snapped per the editor grid settings, and keeps the result in its place.
 #[tauri::command]
-async fn close_editor_window(app_handle: AppHandle) -> Result<(), String> {
-    if let Some(editor_window) = app_handle.get_webview_window("editor") {
-        editor_window.close()
-            .map_err(|e| format!("Failed to close editor: {}", e))?;
+async fn render_annotations(
+    app_handle: AppHandle,
+    config_state: State<'_, ConfigState>,
+    timestamp: u64,
+    shapes: Vec<annotate::Shape>,
+) -> Result<(), SnippError> {
+    let image_data = cached_capture(&app_handle, timestamp).await?;
+    if burst::is_gif(&image_data) {
+        return Err("GIF captures can't be annotated".into());
     }
+    let grid = config_state.lock().unwrap().get_config().editor_grid.clone();
+    let rendered = tokio::task::spawn_blocking(move || annotate::render_annotations(&image_data, &shapes, &grid))
+        .await
+        .map_err(|e| format!("Annotation task failed: {}", e))??;
+    cache_capture(&app_handle, timestamp, rendered).await?;
     Ok(())
 }
 
+/// Plugins from the plugins folder, optionally only those offered in `context`.
 #[tauri::command]
-async fn save_edited_screenshot(
+async fn list_plugins(context: Option<PluginContext>) -> Result<Vec<plugins::Plugin>, SnippError> {
+    let Some(dir) = plugins::plugins_dir() else {
+        return Ok(Vec::new());
+    };
+    let found = tokio::task::spawn_blocking(move || plugins::discover(&dir))
+        .await
+        .map_err(|e| format!("Plugin discovery failed: {}", e))?;
+    Ok(found
+        .into_iter()
+        .filter(|plugin| context.is_none_or(|context| plugin.contexts.contains(&context)))
+        .collect())
+}
+
+/// Runs a plugin on a saved capture (`file_path`) or, from the popup, on the
+/// pending capture `timestamp`, which is staged like a drag file.
+#[tauri::command]
+async fn run_plugin(
     app_handle: AppHandle,
-    base64_image: String,
-    timestamp: u64,
-    config_state: State<'_, ConfigState>,
     history_state: State<'_, HistoryState>,
-) -> Result<String, String> {
-    log::debug!("Saving edited screenshot: {}", timestamp);
-
-    let save_location = {
-        let config = config_state.lock().unwrap();
-        config.get_config().default_save_location.clone()
+    plugin_id: String,
+    file_path: Option<String>,
+    timestamp: Option<u64>,
+) -> Result<PluginOutput, SnippError> {
+    let dir = plugins::plugins_dir().ok_or("Failed to get config directory")?;
+    let plugin = plugins::discover(&dir)
+        .into_iter()
+        .find(|plugin| plugin.id == plugin_id)
+        .ok_or_else(|| format!("Plugin '{}' is not installed", plugin_id))?;
+
+    let mut metadata = match (file_path, timestamp) {
+        (Some(file_path), _) => {
+            let entry = history_state
+                .lock()
+                .unwrap()
+                .get_history()
+                .screenshots
+                .iter()
+                .find(|entry| entry.file_path == file_path)
+                .cloned();
+            PluginMetadata {
+                filename: entry.as_ref().map(|entry| entry.filename.clone()).unwrap_or_else(|| {
+                    PathBuf::from(&file_path)
+                        .file_name()
+                        .map(|name| name.to_string_lossy().to_string())
+                        .unwrap_or_default()
+                }),
+                timestamp: entry
+                    .as_ref()
+                    .map(|entry| entry.timestamp.timestamp_millis() as u64)
+                    .unwrap_or_default(),
+                context: PluginContext::History,
+                width: None,
+                height: None,
+                note: entry.as_ref().map(|entry| entry.note.clone()).unwrap_or_default(),
+                share_url: entry.and_then(|entry| entry.share_url),
+                file_path,
+            }
+        }
+        (None, Some(timestamp)) => {
+            let image_data = cached_capture(&app_handle, timestamp).await?;
+            let filename = build_screenshot_filename(timestamp, None);
+            let temp_path = std::env::temp_dir().join(&filename);
+            tokio::fs::write(&temp_path, &image_data)
+                .await
+                .map_err(|e| format!("Failed to stage capture for plugin: {}", e))?;
+            PluginMetadata {
+                file_path: temp_path.to_string_lossy().to_string(),
+                filename,
+                timestamp,
+                context: PluginContext::Popup,
+                width: None,
+                height: None,
+                note: String::new(),
+                share_url: None,
+            }
+        }
+        (None, None) => return Err("No capture given to the plugin".into()),
     };
-
-    // Decode base64 image
-    let image_data = base64::prelude::BASE64_STANDARD
-        .decode(&base64_image)
-        .map_err(|e| format!("Failed to decode image: {}", e))?;
-
-    let filename = build_screenshot_filename(timestamp, Some("-edited"));
-    let file_path = resolve_unique_path(std::path::PathBuf::from(&save_location).join(&filename));
-
-    if let Some(parent) = file_path.parent() {
-        std::fs::create_dir_all(parent)
-            .map_err(|e| format!("Failed to create save directory: {}", e))?;
+    if let Ok((width, height)) = image::image_dimensions(&metadata.file_path) {
+        metadata.width = Some(width);
+        metadata.height = Some(height);
     }
 
-    std::fs::write(&file_path, &image_data)
-        .map_err(|e| format!("Failed to save file: {}", e))?;
+    Ok(plugins::run(&app_handle, &plugin, &metadata).await?)
+}
 
-    let file_path_str = file_path.to_string_lossy().to_string();
-    log::debug!("Successfully saved edited screenshot to: {}", file_path_str);
+/// Stitches saved captures, in the given order, into a looping GIF or APNG
+/// saved next to the first one. `options` overrides the configured frame
+/// delay and width.
+#[tauri::command]
+async fn animate_captures(
+    config_state: State<'_, ConfigState>,
+    history_state: State<'_, HistoryState>,
+    file_paths: Vec<String>,
+    format: BurstFormat,
+    options: Option<BurstOptions>,
+) -> Result<String, SnippError> {
+    let options = options.unwrap_or_else(|| config_state.lock().unwrap().get_config().burst_animation.clone());
+    options.validate()?;
+    let first = file_paths.first().ok_or("Pick at least two captures to animate")?;
+    let output = PathBuf::from(first);
+    let stem = output
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Snipp".to_string());
+    let output = resolve_unique_path(output.with_file_name(format!("{} animated.{}", stem, format.extension())));
+
+    let target = output.clone();
+    tokio::task::spawn_blocking(move || {
+        let pngs = file_paths
+            .iter()
+            .map(|path| std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path, e)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let frames = burst::frames(&pngs, options.max_width)?;
+        let animation = match format {
+            BurstFormat::Gif => burst::encode_gif(frames, options.delay_ms)?,
+            BurstFormat::Apng => burst::encode_apng(frames, options.delay_ms)?,
+        };
+        cloud_sync::write_file(&target, &animation).map(|_| ())
+    })
+    .await
+    .map_err(|e| format!("Animation task failed: {}", e))??;
 
-    // Add to history
-    {
-        let mut history = history_state.lock().unwrap();
-        if let Err(e) = history.add_screenshot(file_path_str.clone()) {
-            log::error!("Failed to add screenshot to history: {}", e);
-        }
+    let file_path = output.to_string_lossy().to_string();
+    if let Err(e) = history_state.lock().unwrap().add_screenshot(file_path.clone()) {
+        tracing::error!("Failed to add animation to history: {}", e);
     }
+    tracing::debug!("Animated captures into {}", file_path);
+    Ok(file_path)
+}
 
-    // Auto-copy edited screenshot to clipboard if enabled
-    let should_auto_copy_edited = {
-        let config = config_state.lock().unwrap();
-        config.get_config().auto_copy_after_edit
-    };
-
-    if should_auto_copy_edited {
-        if let Err(e) = write_png_bytes_to_clipboard(&app_handle, &image_data) {
-            log::error!("Auto-copy edited screenshot failed: {}", e);
-        } else {
-            log::debug!("Auto-copied edited screenshot to clipboard after save");
-        }
-    }
+/// Runs ffmpeg's avfoundation device listing. ffmpeg exits non-zero here by
+/// design (there is no real input) and prints the devices to stderr.
+async fn list_avfoundation_devices(
+    app_handle: &AppHandle,
+    config_state: &State<'_, ConfigState>,
+) -> Result<String, String> {
+    let ffmpeg_path = config_state.lock().unwrap().get_config().ffmpeg_path.clone();
+    let ffmpeg = recording::find_ffmpeg(&ffmpeg_path).ok_or("ffmpeg was not found")?;
 
-    // Close editor after save
-    if let Some(editor_window) = app_handle.get_webview_window("editor") {
-        let _ = editor_window.close();
-    }
+    let output = app_handle
+        .shell()
+        .command(ffmpeg)
+        .args(["-hide_banner", "-f", "avfoundation", "-list_devices", "true", "-i", ""])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run ffmpeg: {}", e))?;
 
-    Ok(file_path_str)
+    Ok(String::from_utf8_lossy(&output.stderr).to_string())
 }
 
+/// Lists microphones ffmpeg can record from, for the device picker.
 #[tauri::command]
-async fn copy_edited_screenshot(
+async fn list_audio_devices(
     app_handle: AppHandle,
-    base64_image: String,
-    _timestamp: u64,
-) -> Result<(), String> {
-    log::debug!("Copying edited screenshot to clipboard");
+    config_state: State<'_, ConfigState>,
+) -> Result<Vec<String>, SnippError> {
+    let listing = list_avfoundation_devices(&app_handle, &config_state).await?;
+    Ok(recording::parse_audio_devices(&listing))
+}
 
-    let image_data = base64::prelude::BASE64_STANDARD
-        .decode(&base64_image)
-        .map_err(|e| format!("Failed to decode image: {}", e))?;
+/// Lists cameras available for the webcam overlay.
+#[tauri::command]
+async fn list_camera_devices(
+    app_handle: AppHandle,
+    config_state: State<'_, ConfigState>,
+) -> Result<Vec<String>, SnippError> {
+    let listing = list_avfoundation_devices(&app_handle, &config_state).await?;
+    Ok(recording::parse_camera_devices(&listing))
+}
 
-    write_png_bytes_to_clipboard(&app_handle, &image_data)?;
-    log::debug!("Successfully copied edited screenshot to clipboard");
-    Ok(())
+/// Family names of installed fonts, for text annotations.
+#[tauri::command]
+async fn list_fonts() -> Result<Vec<String>, SnippError> {
+    Ok(tokio::task::spawn_blocking(fonts::list_installed_families)
+        .await
+        .map_err(|e| format!("Font listing failed: {}", e))?)
 }
 
+/// How often the background task applies an enabled retention policy.
+const MIRROR_RETRY_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+const RETENTION_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(6 * 60 * 60);
+
+/// Lists the Snipp files in the save folders that a retention period of
+/// `days` (or the configured period) would remove.
 #[tauri::command]
-async fn prepare_drag_file(timestamp: u64) -> Result<String, String> {
-    let cache_key = timestamp.to_string();
-    let image_data = {
-        let cache = SCREENSHOT_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
-        let cache_guard = cache.lock().map_err(|e| format!("Cache lock error: {}", e))?;
-        cache_guard.get(&cache_key).cloned()
+async fn preview_retention(
+    config_state: State<'_, ConfigState>,
+    days: Option<u32>,
+) -> Result<Vec<ExpiredFile>, SnippError> {
+    let (save_locations, template, configured_days) = {
+        let config = config_state.lock().unwrap();
+        let config = config.get_config();
+        (config.save_locations(), config.filename_template.clone(), config.retention.days)
     };
+    let days = days.unwrap_or(configured_days).max(1);
+
+    Ok(tokio::task::spawn_blocking(move || {
+        retention::find_expired_in(&save_locations, &template, days, std::time::SystemTime::now())
+    })
+    .await
+    .map_err(|e| format!("Retention scan failed: {}", e))??)
+}
+
+/// Moves expired files to the Trash now, even if the policy is disabled.
+#[tauri::command]
+async fn apply_retention(app_handle: AppHandle) -> Result<Vec<String>, SnippError> {
+    Ok(run_retention(&app_handle).await?)
+}
 
-    let image_data = image_data.ok_or("Screenshot data not found in memory cache")?;
+async fn run_retention(app_handle: &AppHandle) -> Result<Vec<String>, String> {
+    let (save_locations, template, days) = {
+        let config_state = app_handle.state::<ConfigState>();
+        let config = config_state.lock().unwrap();
+        let config = config.get_config();
+        (config.save_locations(), config.filename_template.clone(), config.retention.days.max(1))
+    };
 
-    let temp_path = std::env::temp_dir().join(build_screenshot_filename(timestamp, None));
+    let removed = tokio::task::spawn_blocking(move || {
+        let expired =
+            retention::find_expired_in(&save_locations, &template, days, std::time::SystemTime::now())?;
+        retention::move_to_trash(&expired)
+    })
+    .await
+    .map_err(|e| format!("Retention cleanup failed: {}", e))??;
 
-    std::fs::write(&temp_path, &image_data)
-        .map_err(|e| format!("Failed to write drag temp file: {}", e))?;
+    if !removed.is_empty() {
+        tracing::info!("Retention policy moved {} file(s) to the Trash", removed.len());
+        let history_state = app_handle.state::<HistoryState>();
+        let mut history = history_state.lock().unwrap();
+        for path in &removed {
+            if let Err(e) = history.remove_screenshot(path) {
+                tracing::error!("Failed to remove expired screenshot from history: {}", e);
+            }
+        }
+    }
+    Ok(removed)
+}
 
-    Ok(temp_path.to_string_lossy().to_string())
+/// Applies the retention policy at startup and then periodically, while enabled.
+fn spawn_retention_task(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let enabled = app_handle
+                .state::<ConfigState>()
+                .lock()
+                .unwrap()
+                .get_config()
+                .retention
+                .enabled;
+            if enabled {
+                if let Err(e) = run_retention(&app_handle).await {
+                    tracing::error!("Retention policy failed: {}", e);
+                }
+            }
+            tokio::time::sleep(RETENTION_CHECK_INTERVAL).await;
+        }
+    });
 }
 
 #[tauri::command]
-async fn cleanup_drag_file(timestamp: u64) -> Result<(), String> {
-    let temp_path = std::env::temp_dir().join(build_screenshot_filename(timestamp, None));
-    if temp_path.exists() {
-        let _ = std::fs::remove_file(&temp_path);
-    }
-    Ok(())
+async fn get_recording_status(
+    recording_state: State<'_, RecordingState>,
+) -> Result<RecordingStatus, SnippError> {
+    Ok(recording_state.lock().unwrap().status())
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -797,25 +4529,90 @@ fn apply_global_shortcuts(app_handle: &AppHandle, config: &AppConfig) -> Result<
                     let auto_copy = config_state.lock().unwrap().get_config().auto_copy_after_capture;
                     drop(config_state);
                     
-                    if let Err(e) = capture(app_handle, true, auto_copy).await {
-                        log::error!("Failed to capture screenshot: {}", e);
+                    if let Err(e) = capture(app_handle, CaptureMode::Interactive, auto_copy).await {
+                        tracing::error!("Failed to capture screenshot: {}", e);
                     }
                 });
             }
         })
         .map_err(|e| format!("Failed to register capture hotkey: {}", e))?;
 
+    // An empty hotkey leaves clipboard import reachable from the tray only.
+    if !config.import_clipboard_hotkey.is_empty() {
+        global_shortcut
+            .on_shortcut(config.import_clipboard_hotkey.as_str(), move |app, _shortcut, event| {
+                if event.state() == ShortcutState::Pressed {
+                    let app_handle = app.clone();
+                    tauri::async_runtime::spawn(async move {
+                        if let Err(e) = import_from_clipboard(app_handle).await {
+                            tracing::error!("Failed to import from clipboard: {}", e);
+                        }
+                    });
+                }
+            })
+            .map_err(|e| format!("Failed to register clipboard import hotkey: {}", e))?;
+    }
+
+    if !config.repeat_region_hotkey.is_empty() {
+        global_shortcut
+            .on_shortcut(config.repeat_region_hotkey.as_str(), move |app, _shortcut, event| {
+                if event.state() == ShortcutState::Pressed {
+                    let app_handle = app.clone();
+                    tauri::async_runtime::spawn(async move {
+                        let config_state = app_handle.state::<ConfigState>();
+                        let last_region_state = app_handle.state::<LastRegionState>();
+                        if let Err(e) = capture_last_region(app_handle.clone(), config_state, last_region_state).await {
+                            tracing::error!("Failed to capture last region: {}", e);
+                        }
+                    });
+                }
+            })
+            .map_err(|e| format!("Failed to register repeat region hotkey: {}", e))?;
+    }
+
+    if !config.capture_text_hotkey.is_empty() {
+        global_shortcut
+            .on_shortcut(config.capture_text_hotkey.as_str(), move |app, _shortcut, event| {
+                if event.state() == ShortcutState::Pressed {
+                    let app_handle = app.clone();
+                    tauri::async_runtime::spawn(async move {
+                        if let Err(e) = capture_text(app_handle).await {
+                            tracing::error!("Failed to capture text: {}", e);
+                        }
+                    });
+                }
+            })
+            .map_err(|e| format!("Failed to register capture text hotkey: {}", e))?;
+    }
+
+    if !config.quick_search_hotkey.is_empty() {
+        global_shortcut
+            .on_shortcut(config.quick_search_hotkey.as_str(), move |app, _shortcut, event| {
+                if event.state() == ShortcutState::Pressed {
+                    let app_handle = app.clone();
+                    tauri::async_runtime::spawn(async move {
+                        if let Err(e) = toggle_quick_search(app_handle).await {
+                            tracing::error!("Failed to toggle quick search: {}", e);
+                        }
+                    });
+                }
+            })
+            .map_err(|e| format!("Failed to register quick search hotkey: {}", e))?;
+    }
+
     Ok(())
 }
 
 pub fn run() {
-    // Verbose in debug builds, warnings-and-above in release; override with RUST_LOG.
-    let default_level = if cfg!(debug_assertions) { "debug" } else { "warn" };
-    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(default_level)).init();
-
     let config_manager = ConfigManager::new().expect("Failed to initialize config manager");
+    logging::init(config_manager.get_config().log_level);
+    let last_region = config_manager.get_config().last_region;
     let history_manager = HistoryManager::new().expect("Failed to initialize history manager");
     let thumbnail_generator = ThumbnailGenerator::new().expect("Failed to initialize thumbnail generator");
+    let screenshot_store = ScreenshotStore::new(
+        cache::megabytes(config_manager.get_config().cache_limit_mb),
+        spill::area().cloned(),
+    );
     
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
@@ -823,21 +4620,68 @@ pub fn run() {
         .plugin(tauri_plugin_clipboard_manager::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
+        .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_positioner::init())
         .plugin(tauri_plugin_drag::init())
+        .register_asynchronous_uri_scheme_protocol(protocol::SCHEME, |ctx, request, responder| {
+            let app_handle = ctx.app_handle().clone();
+            tauri::async_runtime::spawn_blocking(move || {
+                responder.respond(protocol::screenshot_response(&app_handle, &request))
+            });
+        })
         .manage(ConfigState::new(config_manager))
         .manage(HistoryState::new(history_manager))
         .manage(ThumbnailState::new(thumbnail_generator))
+        .manage(screenshot_store)
+        .manage(RecordingState::new(RecordingManager::new()))
+        .manage(LastRegionState::new(last_region))
+        .manage(GifCaptureState::new(false))
+        .manage(QuickLookState::new(None))
+        .manage(UploadQueue::default())
+        .manage(SequenceState::new(DailySequence::load()))
+        .manage(MirrorQueueState::new(MirrorQueue::load()))
+        .manage(GuideState::new(GuideSession::load()))
         .setup(|app| {
             #[cfg(target_os = "macos")]
             {
                 app.set_activation_policy(tauri::ActivationPolicy::Accessory);
             }
             tray::setup_system_tray(app.handle())?;
+
+            // Spilled captures from an earlier run can't be reached any more.
+            if let Some(area) = spill::area() {
+                area.set_secure_delete(app.state::<ConfigState>().lock().unwrap().get_config().secure_delete);
+                area.clear();
+            }
+            let app_handle = app.handle().clone();
+            spill::watch_memory_pressure(Box::new(move |level| {
+                app_handle.state::<ScreenshotStore>().relieve_memory_pressure(level)
+            }));
             
-            app.get_webview_window("main").unwrap().hide().unwrap();
+            // First run opens the preferences window so the onboarding
+            // wizard can ask for permissions before the first capture.
+            let onboarding_completed = app
+                .state::<ConfigState>()
+                .lock()
+                .unwrap()
+                .get_config()
+                .onboarding_completed;
+            let main_window = app.get_webview_window("main").unwrap();
+            if onboarding_completed {
+                main_window.hide().unwrap();
+            } else {
+                main_window.show()?;
+                let _ = main_window.set_focus();
+            }
             
             setup_global_shortcuts(app)?;
+
+            spawn_retention_task(app.handle().clone());
+            spawn_mirror_task(app.handle().clone());
+            spawn_watch_folder_task(app.handle().clone());
+
+            #[cfg(not(target_os = "macos"))]
+            import_opened_files(app.handle(), std::env::args_os().skip(1).map(PathBuf::from).collect());
             
             Ok(())
         })
@@ -852,20 +4696,87 @@ pub fn run() {
                     let _ = window.hide();
                 }
             }
+            // The palette is transient, like Spotlight: clicking away dismisses it.
+            if window.label() == "quick_search" {
+                if let WindowEvent::Focused(false) = event {
+                    let _ = window.hide();
+                }
+            }
         })
         .invoke_handler(tauri::generate_handler![
             capture_screenshot,
             capture_full_screen,
+            capture_with_delay,
+            capture_region,
+            capture_with_selector,
+            capture_last_region,
+            capture_gif,
+            stop_gif_capture,
+            list_windows,
+            list_capture_windows,
+            capture_window,
+            capture_scrolling,
+            list_displays,
+            capture_display,
+            capture_all_displays,
+            set_region_baseline,
+            clear_region_baseline,
+            list_region_baselines,
+            measure_distance,
+            measure_on_screen,
+            capture_text,
+            capture_browser_page,
+            translate_text,
+            export_searchable_pdf,
+            export_asset_pair,
+            start_guide,
+            get_guide,
+            set_guide_caption,
+            remove_guide_step,
+            end_guide,
+            export_guide,
+            export_markdown,
+            list_fonts,
+            preview_retention,
+            apply_retention,
+            export_backup,
+            restore_backup,
+            compare_screenshots,
+            close_compare_window,
+            diff_screenshots,
+            compose_collage,
+            import_from_clipboard,
+            import_image_file,
             copy_to_clipboard,
             save_to_disk,
             delete_from_memory,
             close_popup_window,
             get_config,
             update_config,
+            get_onboarding_status,
+            get_macos_screenshot_prefs,
+            run_diagnostics,
+            get_recent_logs,
+            export_diagnostics,
+            request_permission,
+            complete_onboarding,
             choose_save_location,
             get_recent_screenshots,
+            get_thumbnail_cache_usage,
+            purge_thumbnail_cache,
+            search_screenshots,
+            set_screenshot_note,
+            toggle_quick_search,
+            close_quick_search,
+            quick_search,
+            open_screenshot,
+            upload_screenshot,
+            upload_screenshots,
             copy_screenshot_from_path,
+            copy_path_to_clipboard,
+            copy_file_to_clipboard,
             open_in_finder,
+            quick_look,
             delete_screenshot,
             close_recent_window,
             open_editor_window,
@@ -875,10 +4786,48 @@ pub fn run() {
             prepare_drag_file,
             cleanup_drag_file,
             hide_window,
-            show_window
+            show_window,
+            start_recording,
+            pause_recording,
+            resume_recording,
+            stop_recording,
+            get_recording_status,
+            trim_recording,
+            convert_recording,
+            animate_captures,
+            analyze_capture,
+            pick_color,
+            extract_palette,
+            detect_codes,
+            beautify_screenshot,
+            apply_corner_radius,
+            render_annotations,
+            crop_screenshot,
+            rotate_screenshot,
+            flip_screenshot,
+            redact_region,
+            auto_redact,
+            save_annotations,
+            load_annotations,
+            get_screenshot_metadata,
+            list_plugins,
+            check_hook_script,
+            run_plugin,
+            list_audio_devices,
+            list_camera_devices
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|_app_handle, event| {
+            if let tauri::RunEvent::Exit = event {
+                logging::shutdown();
+            }
+            #[cfg(target_os = "macos")]
+            if let tauri::RunEvent::Opened { urls } = event {
+                let paths = urls.into_iter().filter_map(|url| url.to_file_path().ok()).collect();
+                import_opened_files(_app_handle, paths);
+            }
+        });
 }
 
 #[cfg(test)]
@@ -888,7 +4837,7 @@ mod tests {
     #[test]
     fn test_screenshot_data_creation() {
         let data = ScreenshotData {
-            base64_image: "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNkYPhfDwAChAGAWA".to_string(),
+            image_url: protocol::screenshot_url(1234567890),
             filename: "test-1234567890.png".to_string(),
             timestamp: 1234567890,
             file_path: None,
@@ -897,13 +4846,13 @@ mod tests {
         assert_eq!(data.filename, "test-1234567890.png");
         assert_eq!(data.timestamp, 1234567890);
         assert!(data.file_path.is_none());
-        assert!(!data.base64_image.is_empty());
+        assert!(data.image_url.ends_with("/screenshot/1234567890"));
     }
 
     #[test]
     fn test_screenshot_data_with_file_path() {
         let mut data = ScreenshotData {
-            base64_image: "test_data".to_string(),
+            image_url: protocol::screenshot_url(9876543210),
             filename: "screenshot.png".to_string(),
             timestamp: 9876543210,
             file_path: None,
@@ -914,64 +4863,6 @@ mod tests {
         assert_eq!(data.file_path, Some("/home/user/Desktop/screenshot.png".to_string()));
     }
 
-    #[test]
-    fn test_screenshot_cache_operations() {
-        let cache = SCREENSHOT_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
-        let test_key = "test_timestamp".to_string();
-        let test_data = vec![1, 2, 3, 4, 5];
-
-        // Hold the lock for the whole insert/read/clear sequence so this test
-        // can't interleave with other tests that share the global cache (the
-        // clear() below would otherwise wipe their entries mid-run).
-        let mut cache_guard = cache.lock().unwrap();
-
-        cache_guard.insert(test_key.clone(), test_data.clone());
-        assert_eq!(cache_guard.get(&test_key).cloned(), Some(test_data));
-
-        // close_popup_window clears the whole cache; verify that empties it.
-        cache_guard.clear();
-        assert!(cache_guard.is_empty());
-        assert!(cache_guard.get(&test_key).is_none());
-    }
-
-    #[test]
-    fn test_cache_fifo_eviction_at_capacity() {
-        const MAX_CACHE_ENTRIES: usize = 50;
-
-        // Use a private map so this test is independent of the shared global
-        // cache state, then exercise the same FIFO logic capture() uses.
-        let mut map: HashMap<String, Vec<u8>> = HashMap::new();
-
-        // Fill to capacity with sequential timestamp keys.
-        let base: u64 = 1_700_000_000_000;
-        for i in 0..MAX_CACHE_ENTRIES as u64 {
-            map.insert((base + i).to_string(), vec![i as u8]);
-        }
-        assert_eq!(map.len(), MAX_CACHE_ENTRIES);
-
-        let oldest_key = base.to_string();
-        assert!(map.contains_key(&oldest_key));
-
-        // Insert beyond capacity, evicting the oldest (smallest timestamp) first.
-        for i in MAX_CACHE_ENTRIES as u64..(MAX_CACHE_ENTRIES as u64 + 5) {
-            if map.len() >= MAX_CACHE_ENTRIES {
-                let oldest = map
-                    .keys()
-                    .min_by_key(|k| k.parse::<u64>().unwrap_or(u64::MAX))
-                    .cloned()
-                    .unwrap();
-                map.remove(&oldest);
-            }
-            map.insert((base + i).to_string(), vec![i as u8]);
-        }
-
-        // Never exceeds the cap, and the original oldest entry is gone.
-        assert_eq!(map.len(), MAX_CACHE_ENTRIES);
-        assert!(!map.contains_key(&oldest_key));
-        // Newest entry is present.
-        assert!(map.contains_key(&(base + MAX_CACHE_ENTRIES as u64 + 4).to_string()));
-    }
-
     #[test]
     fn test_filename_generation() {
         let timestamp = 1234567890u64;
@@ -996,17 +4887,13 @@ mod tests {
 
     #[test]
     fn test_prepare_drag_file_writes_and_returns_path() {
-        let cache = SCREENSHOT_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
         let timestamp = 8888888888u64;
         let test_png = vec![0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A];
-
-        // Hold the lock across insert + read so a concurrent cache clear (from
-        // test_screenshot_cache_operations) can't evict our entry mid-run.
-        let mut guard = cache.lock().unwrap();
-        guard.insert(timestamp.to_string(), test_png.clone());
+        let store = ScreenshotStore::new(cache::DEFAULT_MAX_BYTES, None);
+        store.insert(timestamp, test_png.clone());
 
         let temp_path = std::env::temp_dir().join(build_screenshot_filename(timestamp, None));
-        let data = guard.get(&timestamp.to_string()).cloned().unwrap();
+        let data = store.get(timestamp).unwrap();
         std::fs::write(&temp_path, &data).unwrap();
 
         assert!(temp_path.exists());
@@ -1021,33 +4908,7 @@ mod tests {
 
         // Cleanup
         std::fs::remove_file(&temp_path).unwrap();
-        guard.remove(&timestamp.to_string());
-    }
-
-    #[test]
-    fn test_resolve_unique_path_returns_original_when_free() {
-        let dir = std::env::temp_dir().join(format!("snipp_unique_{}", std::process::id()));
-        std::fs::create_dir_all(&dir).unwrap();
-        let path = dir.join("Snipp test.png");
-        let _ = std::fs::remove_file(&path);
-
-        assert_eq!(resolve_unique_path(path.clone()), path);
-
-        std::fs::remove_dir_all(&dir).ok();
-    }
-
-    #[test]
-    fn test_resolve_unique_path_appends_counter_on_collision() {
-        let dir = std::env::temp_dir().join(format!("snipp_unique_collide_{}", std::process::id()));
-        std::fs::create_dir_all(&dir).unwrap();
-        let path = dir.join("Snipp test.png");
-        std::fs::write(&path, b"x").unwrap();
-
-        let resolved = resolve_unique_path(path.clone());
-        assert_ne!(resolved, path);
-        assert_eq!(resolved, dir.join("Snipp test (1).png"));
-
-        std::fs::remove_dir_all(&dir).ok();
+        store.remove(timestamp);
     }
 
     #[test]
@@ -0,0 +1,66 @@
+use snipp_core::logs::{self, LogLevel};
+use std::sync::{Mutex, OnceLock};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::{fmt, reload, EnvFilter, Registry};
+
+/// Swaps the filter when the level changes in settings.
+static FILTER: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+/// Keeps the file writer running until `shutdown` drops it.
+static FILE_GUARD: Mutex<Option<WorkerGuard>> = Mutex::new(None);
+
+/// `RUST_LOG` wins over the configured level, for debugging a build.
+fn filter(level: LogLevel) -> EnvFilter {
+    EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(level.as_str()))
+}
+
+fn file_appender() -> Result<RollingFileAppender, String> {
+    let dir = logs::log_dir()?;
+    RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(logs::LOG_FILE_PREFIX)
+        .filename_suffix(logs::LOG_FILE_SUFFIX)
+        .max_log_files(logs::MAX_LOG_FILES)
+        .build(&dir)
+        .map_err(|e| format!("Failed to open log folder {}: {}", dir.display(), e))
+}
+
+/// Logs to stderr and to a daily file under the data folder, at `level`.
+/// Messages from the `log` crate, which plugins use, are forwarded too.
+pub fn init(level: LogLevel) {
+    let (filter, handle) = reload::Layer::new(filter(level));
+    let (file_layer, file_error) = match file_appender() {
+        Ok(appender) => {
+            let (writer, guard) = tracing_appender::non_blocking(appender);
+            *FILE_GUARD.lock().unwrap() = Some(guard);
+            (Some(fmt::layer().with_ansi(false).with_writer(writer)), None)
+        }
+        Err(e) => (None, Some(e)),
+    };
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt::layer())
+        .with(file_layer)
+        .init();
+    let _ = FILTER.set(handle);
+
+    if let Some(e) = file_error {
+        tracing::warn!("{}", e);
+    }
+}
+
+/// Applies a level changed in settings without a restart.
+pub fn set_level(level: LogLevel) {
+    if let Some(handle) = FILTER.get() {
+        if let Err(e) = handle.reload(filter(level)) {
+            tracing::warn!("Failed to change the log level: {}", e);
+        }
+    }
+}
+
+/// Flushes what the file writer still has buffered. Called on exit, since
+/// statics are never dropped; later messages only reach stderr.
+pub fn shutdown() {
+    drop(FILE_GUARD.lock().unwrap().take());
+}
@@ -0,0 +1,102 @@
+use chrono::{DateTime, Local};
+use std::path::{Path, PathBuf};
+
+/// One capture in an exported document, already copied next to it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkdownImage {
+    /// File name inside the assets folder.
+    pub filename: String,
+    pub captured_at: Option<DateTime<Local>>,
+    pub note: String,
+}
+
+/// Folder the images of `document` are copied into, e.g. `Notes.md` ->
+/// `Notes assets/`.
+pub fn assets_dir(document: &Path) -> PathBuf {
+    let stem = document
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("Screenshots");
+    document.with_file_name(format!("{} assets", stem))
+}
+
+/// Percent-encodes the characters that would end or break a Markdown link.
+pub fn encode_link(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for c in path.chars() {
+        match c {
+            ' ' => encoded.push_str("%20"),
+            '(' => encoded.push_str("%28"),
+            ')' => encoded.push_str("%29"),
+            '<' => encoded.push_str("%3C"),
+            '>' => encoded.push_str("%3E"),
+            '#' => encoded.push_str("%23"),
+            _ => encoded.push(c),
+        }
+    }
+    encoded
+}
+
+/// A document with each image followed by its capture time and note, with
+/// links relative to the document so the two can be moved together.
+pub fn document(title: &str, assets_dir: &str, images: &[MarkdownImage]) -> String {
+    let mut doc = format!("# {}\n\n", title.trim());
+    for image in images {
+        let note = image.note.trim();
+        let alt = if note.is_empty() { image.filename.as_str() } else { note };
+        doc.push_str(&format!(
+            "![{}]({}/{})\n\n",
+            alt.replace(['[', ']'], ""),
+            encode_link(assets_dir),
+            encode_link(&image.filename)
+        ));
+        let time = image
+            .captured_at
+            .map(|time| format!("*{}*", time.format("%Y-%m-%d %H:%M:%S")));
+        let caption = match (time, note.is_empty()) {
+            (Some(time), true) => time,
+            (Some(time), false) => format!("{} - {}", time, note),
+            (None, false) => note.to_string(),
+            (None, true) => continue,
+        };
+        doc.push_str(&caption);
+        doc.push_str("\n\n");
+    }
+    doc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_document_links_assets_and_captions() {
+        let images = vec![
+            MarkdownImage {
+                filename: "Snipp 24-05-01 at 10.00.00.png".to_string(),
+                captured_at: Some(Local.with_ymd_and_hms(2024, 5, 1, 10, 0, 0).unwrap()),
+                note: "Crash dialog [prod]".to_string(),
+            },
+            MarkdownImage {
+                filename: "b.png".to_string(),
+                captured_at: None,
+                note: String::new(),
+            },
+        ];
+
+        let doc = document("Bug report", "Bug report assets", &images);
+
+        assert_eq!(
+            doc,
+            "# Bug report\n\n\
+             ![Crash dialog prod](Bug%20report%20assets/Snipp%2024-05-01%20at%2010.00.00.png)\n\n\
+             *2024-05-01 10:00:00* - Crash dialog [prod]\n\n\
+             ![b.png](Bug%20report%20assets/b.png)\n\n"
+        );
+        assert_eq!(
+            assets_dir(Path::new("/tmp/Bug report.md")),
+            PathBuf::from("/tmp/Bug report assets")
+        );
+    }
+}
@@ -0,0 +1,128 @@
+use serde::{Deserialize, Serialize};
+
+use crate::region::CaptureRegion;
+
+/// A position on a capture, in image pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MeasurePoint {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// Distance and extent between two points, in pixels and in points.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Measurement {
+    pub width: f64,
+    pub height: f64,
+    pub distance: f64,
+    /// Direction from start to end in degrees, clockwise from the x axis
+    /// since screen y grows downwards.
+    pub angle: f64,
+    pub scale_factor: f64,
+    pub width_points: f64,
+    pub height_points: f64,
+    pub distance_points: f64,
+}
+
+pub fn measure(
+    start: MeasurePoint,
+    end: MeasurePoint,
+    scale_factor: f64,
+) -> Result<Measurement, String> {
+    let coords = [start.x, start.y, end.x, end.y];
+    if coords.iter().any(|value| !value.is_finite()) {
+        return Err("Measurement points must be finite".to_string());
+    }
+    if !scale_factor.is_finite() || scale_factor <= 0.0 {
+        return Err("Scale factor must be greater than 0".to_string());
+    }
+
+    let dx = end.x - start.x;
+    let dy = end.y - start.y;
+    let distance = dx.hypot(dy);
+
+    Ok(Measurement {
+        width: dx.abs(),
+        height: dy.abs(),
+        distance,
+        angle: dy.atan2(dx).to_degrees(),
+        scale_factor,
+        width_points: dx.abs() / scale_factor,
+        height_points: dy.abs() / scale_factor,
+        distance_points: distance / scale_factor,
+    })
+}
+
+/// Measures a rectangle given in points, corner to corner.
+pub fn measure_region(region: CaptureRegion, scale_factor: f64) -> Result<Measurement, String> {
+    let start = MeasurePoint {
+        x: region.x as f64 * scale_factor,
+        y: region.y as f64 * scale_factor,
+    };
+    let end = MeasurePoint {
+        x: (region.x as f64 + region.width as f64) * scale_factor,
+        y: (region.y as f64 + region.height as f64) * scale_factor,
+    };
+    measure(start, end, scale_factor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measure_reports_pixels_and_points() {
+        let measurement = measure(
+            MeasurePoint { x: 10.0, y: 20.0 },
+            MeasurePoint { x: 70.0, y: 100.0 },
+            2.0,
+        )
+        .unwrap();
+
+        assert_eq!(measurement.width, 60.0);
+        assert_eq!(measurement.height, 80.0);
+        assert_eq!(measurement.distance, 100.0);
+        assert_eq!(measurement.width_points, 30.0);
+        assert_eq!(measurement.height_points, 40.0);
+        assert_eq!(measurement.distance_points, 50.0);
+    }
+
+    #[test]
+    fn test_measure_angle_follows_screen_axes() {
+        let origin = MeasurePoint { x: 0.0, y: 0.0 };
+
+        let right = measure(origin, MeasurePoint { x: 5.0, y: 0.0 }, 1.0).unwrap();
+        let down = measure(origin, MeasurePoint { x: 0.0, y: 5.0 }, 1.0).unwrap();
+        let left = measure(origin, MeasurePoint { x: -5.0, y: 0.0 }, 1.0).unwrap();
+
+        assert_eq!(right.angle, 0.0);
+        assert_eq!(down.angle, 90.0);
+        assert_eq!(left.angle, 180.0);
+        assert_eq!(left.width, 5.0);
+    }
+
+    #[test]
+    fn test_measure_rejects_invalid_input() {
+        let origin = MeasurePoint { x: 0.0, y: 0.0 };
+        let nan = MeasurePoint { x: f64::NAN, y: 0.0 };
+
+        assert!(measure(origin, nan, 1.0).is_err());
+        assert!(measure(origin, origin, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_measure_region_converts_points_to_pixels() {
+        let region = CaptureRegion {
+            x: 10,
+            y: 10,
+            width: 30,
+            height: 40,
+        };
+
+        let measurement = measure_region(region, 2.0).unwrap();
+
+        assert_eq!(measurement.width, 60.0);
+        assert_eq!(measurement.height, 80.0);
+        assert_eq!(measurement.distance_points, 50.0);
+    }
+}
@@ -0,0 +1,124 @@
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use tauri_plugin_shell::ShellExt;
+
+/// JXA bridge to Vision's `VNRecognizeTextRequest`; takes an image path and
+/// prints the recognized lines as JSON. Vision reports boxes normalized with
+/// the origin at the bottom-left.
+const OCR_SCRIPT: &str = "ObjC.import('Vision'); \
+function run(argv) { \
+const url = $.NSURL.fileURLWithPath(argv[0]); \
+const handler = $.VNImageRequestHandler.alloc.initWithURLOptions(url, $({})); \
+const request = $.VNRecognizeTextRequest.alloc.init; \
+request.recognitionLevel = 0; \
+request.usesLanguageCorrection = true; \
+if (!handler.performRequestsError($([request]), null)) { throw new Error('Text recognition failed'); } \
+const results = request.results; \
+const lines = []; \
+for (let i = 0; i < results.count; i++) { \
+const observation = results.objectAtIndex(i); \
+const candidate = observation.topCandidates(1).firstObject; \
+if (!candidate) continue; \
+const box = observation.boundingBox; \
+lines.push({ text: candidate.string.js, confidence: candidate.confidence, \
+x: box.origin.x, y: box.origin.y, width: box.size.width, height: box.size.height }); \
+} \
+return JSON.stringify(lines); }";
+
+/// A recognized line with its box as fractions of the image, origin top-left.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OcrLine {
+    pub text: String,
+    pub confidence: f64,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OcrResult {
+    /// All lines joined with newlines, in reading order.
+    pub text: String,
+    pub lines: Vec<OcrLine>,
+}
+
+/// Flips Vision's bottom-left boxes to top-left and orders lines top to
+/// bottom, then left to right.
+pub fn parse_ocr_output(json: &str) -> Result<OcrResult, String> {
+    let mut lines: Vec<OcrLine> = serde_json::from_str(json.trim())
+        .map_err(|e| format!("Failed to parse text recognition output: {}", e))?;
+
+    for line in &mut lines {
+        line.y = 1.0 - line.y - line.height;
+    }
+    lines.sort_by(|a, b| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+
+    let text = lines
+        .iter()
+        .map(|line| line.text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n");
+    Ok(OcrResult { text, lines })
+}
+
+/// Runs Vision text recognition over a PNG.
+pub async fn recognize_text(app_handle: &AppHandle, png: &[u8]) -> Result<OcrResult, String> {
+    let temp_path = std::env::temp_dir().join(format!(
+        "snipp_ocr_{}_{}.png",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default()
+    ));
+    std::fs::write(&temp_path, png).map_err(|e| format!("Failed to stage image for OCR: {}", e))?;
+
+    let output = app_handle
+        .shell()
+        .command("osascript")
+        .args([
+            "-l",
+            "JavaScript",
+            "-e",
+            OCR_SCRIPT,
+            temp_path.to_string_lossy().as_ref(),
+        ])
+        .output()
+        .await;
+    let _ = std::fs::remove_file(&temp_path);
+    let output = output.map_err(|e| format!("Failed to run osascript: {}", e))?;
+
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Text recognition failed: {}", error.trim()));
+    }
+
+    parse_ocr_output(&String::from_utf8_lossy(&output.stdout))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ocr_output_orders_lines_top_down() {
+        let json = r#"[
+            {"text": "second", "confidence": 0.9, "x": 0.1, "y": 0.2, "width": 0.5, "height": 0.1},
+            {"text": "first", "confidence": 1.0, "x": 0.1, "y": 0.7, "width": 0.5, "height": 0.1}
+        ]"#;
+
+        let result = parse_ocr_output(json).unwrap();
+
+        assert_eq!(result.text, "first\nsecond");
+        assert!((result.lines[0].y - 0.2).abs() < 1e-9);
+        assert!((result.lines[1].y - 0.7).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_parse_ocr_output_handles_no_text() {
+        let result = parse_ocr_output("[]\n").unwrap();
+        assert!(result.text.is_empty());
+        assert!(parse_ocr_output("oops").is_err());
+    }
+}
@@ -0,0 +1,182 @@
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::{CompressionType, FilterType, PngEncoder};
+use image::codecs::webp::WebPEncoder;
+use image::{DynamicImage, ImageEncoder};
+use serde::{Deserialize, Serialize};
+use snipp_core::output;
+
+/// JPEG quality used for size estimates and auto-picked uploads.
+pub const JPEG_QUALITY: u8 = 85;
+
+/// Auto-pick only accepts JPEG when it is at most this fraction of the best
+/// lossless size: photos shrink a lot, while text and UI mostly gain artifacts.
+const JPEG_WORTHWHILE_RATIO: f64 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ShareFormat {
+    Png,
+    Jpeg,
+    Webp,
+}
+
+impl ShareFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ShareFormat::Png => "png",
+            ShareFormat::Jpeg => "jpg",
+            ShareFormat::Webp => "webp",
+        }
+    }
+
+    pub fn is_lossless(self) -> bool {
+        self != ShareFormat::Jpeg
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FormatSize {
+    pub format: ShareFormat,
+    pub bytes: usize,
+    pub lossless: bool,
+}
+
+/// What a capture would weigh in each format, smallest first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SizeReport {
+    pub original_bytes: usize,
+    pub width: u32,
+    pub height: u32,
+    /// JPEG would flatten transparent areas onto white.
+    pub has_transparency: bool,
+    pub sizes: Vec<FormatSize>,
+    /// The smallest format that keeps the capture looking the same.
+    pub recommended: ShareFormat,
+}
+
+/// Encodes `image` as `format`: PNG at maximum compression, JPEG at
+/// [`JPEG_QUALITY`] over white, WebP lossless.
+pub fn encode(image: &DynamicImage, format: ShareFormat) -> Result<Vec<u8>, String> {
+    let mut encoded = Vec::new();
+    let rgba = image.to_rgba8();
+    let (width, height) = rgba.dimensions();
+    let result = match format {
+        ShareFormat::Png => PngEncoder::new_with_quality(&mut encoded, CompressionType::Best, FilterType::Adaptive)
+            .write_image(&rgba, width, height, image::ColorType::Rgba8),
+        ShareFormat::Jpeg => {
+            JpegEncoder::new_with_quality(&mut encoded, JPEG_QUALITY).write_image(
+                &output::flatten_onto_white(&rgba),
+                width,
+                height,
+                image::ColorType::Rgb8,
+            )
+        }
+        ShareFormat::Webp => {
+            WebPEncoder::new_lossless(&mut encoded).write_image(&rgba, width, height, image::ColorType::Rgba8)
+        }
+    };
+    result.map_err(|e| format!("Failed to encode {}: {}", format.extension(), e))?;
+    Ok(encoded)
+}
+
+fn recommend(sizes: &[FormatSize], has_transparency: bool) -> ShareFormat {
+    let best_lossless = sizes
+        .iter()
+        .filter(|size| size.lossless)
+        .min_by_key(|size| size.bytes)
+        .map(|size| (size.format, size.bytes))
+        .unwrap_or((ShareFormat::Png, usize::MAX));
+    let jpeg = sizes.iter().find(|size| size.format == ShareFormat::Jpeg);
+    match jpeg {
+        Some(jpeg)
+            if !has_transparency && (jpeg.bytes as f64) <= best_lossless.1 as f64 * JPEG_WORTHWHILE_RATIO =>
+        {
+            ShareFormat::Jpeg
+        }
+        _ => best_lossless.0,
+    }
+}
+
+/// Encodes the capture in every format and reports the sizes.
+pub fn analyze(png: &[u8]) -> Result<SizeReport, String> {
+    let image = image::load_from_memory(png).map_err(|e| format!("Failed to decode image: {}", e))?;
+    let has_transparency = image.to_rgba8().pixels().any(|pixel| pixel[3] < 255);
+    let mut sizes = [ShareFormat::Png, ShareFormat::Jpeg, ShareFormat::Webp]
+        .into_iter()
+        .map(|format| {
+            encode(&image, format).map(|encoded| FormatSize {
+                format,
+                bytes: encoded.len(),
+                lossless: format.is_lossless(),
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    sizes.sort_by_key(|size| size.bytes);
+    let recommended = recommend(&sizes, has_transparency);
+    Ok(SizeReport {
+        original_bytes: png.len(),
+        width: image.width(),
+        height: image.height(),
+        has_transparency,
+        sizes,
+        recommended,
+    })
+}
+
+/// The capture re-encoded in its recommended format. PNGs that don't get
+/// smaller are returned untouched.
+pub fn smallest(png: &[u8]) -> Result<(ShareFormat, Vec<u8>), String> {
+    let report = analyze(png)?;
+    let recommended = report
+        .sizes
+        .iter()
+        .find(|size| size.format == report.recommended)
+        .map(|size| size.bytes)
+        .unwrap_or(usize::MAX);
+    if recommended >= png.len() {
+        return Ok((ShareFormat::Png, png.to_vec()));
+    }
+    let image = image::load_from_memory(png).map_err(|e| format!("Failed to decode image: {}", e))?;
+    Ok((report.recommended, encode(&image, report.recommended)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn size(format: ShareFormat, bytes: usize) -> FormatSize {
+        FormatSize {
+            format,
+            bytes,
+            lossless: format.is_lossless(),
+        }
+    }
+
+    #[test]
+    fn test_recommend_only_takes_jpeg_when_much_smaller_and_opaque() {
+        let sizes = [size(ShareFormat::Jpeg, 40), size(ShareFormat::Webp, 100), size(ShareFormat::Png, 120)];
+        assert_eq!(recommend(&sizes, false), ShareFormat::Jpeg);
+        assert_eq!(recommend(&sizes, true), ShareFormat::Webp);
+
+        let sizes = [size(ShareFormat::Jpeg, 80), size(ShareFormat::Png, 100), size(ShareFormat::Webp, 110)];
+        assert_eq!(recommend(&sizes, false), ShareFormat::Png);
+    }
+
+    #[test]
+    fn test_analyze_reports_every_format() {
+        let mut png = Vec::new();
+        DynamicImage::ImageRgba8(image::RgbaImage::from_pixel(32, 16, image::Rgba([10, 20, 30, 255])))
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageOutputFormat::Png)
+            .unwrap();
+
+        let report = analyze(&png).unwrap();
+
+        assert_eq!((report.width, report.height), (32, 16));
+        assert!(!report.has_transparency);
+        assert_eq!(report.sizes.len(), 3);
+        assert!(report.sizes.windows(2).all(|pair| pair[0].bytes <= pair[1].bytes));
+        let (_, bytes) = smallest(&png).unwrap();
+        assert!(bytes.len() <= png.len());
+        assert!(image::load_from_memory(&bytes).is_ok());
+    }
+}
@@ -0,0 +1,113 @@
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+#[cfg(target_os = "macos")]
+mod ffi {
+    use std::ffi::{c_char, c_void};
+
+    pub type Id = *mut c_void;
+
+    #[repr(C)]
+    pub struct CallBacks {
+        _private: [u8; 0],
+    }
+
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        pub static kCFTypeArrayCallBacks: CallBacks;
+        pub fn CFURLCreateFromFileSystemRepresentation(
+            allocator: Id,
+            path: *const u8,
+            len: isize,
+            is_directory: bool,
+        ) -> Id;
+        pub fn CFArrayCreate(allocator: Id, values: *const Id, count: isize, callbacks: *const CallBacks) -> Id;
+        pub fn CFRelease(object: Id);
+    }
+
+    #[link(name = "objc")]
+    extern "C" {
+        pub fn objc_getClass(name: *const c_char) -> Id;
+        pub fn sel_registerName(name: *const c_char) -> Id;
+        pub fn objc_msgSend();
+    }
+}
+
+/// Puts `path` on the general pasteboard as a file URL, so pasting into
+/// Finder, Mail or a chat app attaches the file itself.
+#[cfg(target_os = "macos")]
+pub async fn write_file_url(app: &AppHandle, path: &Path) -> Result<(), String> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = path.as_os_str().as_bytes().to_vec();
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    app.run_on_main_thread(move || {
+        // SAFETY: AppKit and CoreFoundation calls on the main thread; the
+        // URL and array created here are released before returning, and
+        // the pasteboard retains what it is given.
+        let written = unsafe {
+            let send: extern "C" fn(ffi::Id, ffi::Id) -> ffi::Id =
+                std::mem::transmute(ffi::objc_msgSend as unsafe extern "C" fn());
+            let send_with: extern "C" fn(ffi::Id, ffi::Id, ffi::Id) -> i8 =
+                std::mem::transmute(ffi::objc_msgSend as unsafe extern "C" fn());
+            let pasteboard = send(
+                ffi::objc_getClass(b"NSPasteboard\0".as_ptr().cast()),
+                ffi::sel_registerName(b"generalPasteboard\0".as_ptr().cast()),
+            );
+            if pasteboard.is_null() {
+                false
+            } else {
+                let url = ffi::CFURLCreateFromFileSystemRepresentation(
+                    std::ptr::null_mut(),
+                    path.as_ptr(),
+                    path.len() as isize,
+                    false,
+                );
+                let items = ffi::CFArrayCreate(std::ptr::null_mut(), &url, 1, &ffi::kCFTypeArrayCallBacks);
+                send(pasteboard, ffi::sel_registerName(b"clearContents\0".as_ptr().cast()));
+                let written = send_with(pasteboard, ffi::sel_registerName(b"writeObjects:\0".as_ptr().cast()), items);
+                ffi::CFRelease(items);
+                ffi::CFRelease(url);
+                written != 0
+            }
+        };
+        let _ = sender.send(written);
+    })
+    .map_err(|e| format!("Failed to reach the main thread: {}", e))?;
+
+    match receiver.await {
+        Ok(true) => Ok(()),
+        _ => Err("Failed to put the file on the clipboard".to_string()),
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+pub async fn write_file_url(_app: &AppHandle, _path: &Path) -> Result<(), String> {
+    Err("Copying a file to the clipboard is only available on macOS".to_string())
+}
+
+/// The saved capture at `file_path`, or an error if it has been moved or
+/// deleted since.
+pub fn existing_capture(file_path: &str) -> Result<PathBuf, String> {
+    let path = PathBuf::from(file_path);
+    if !path.is_file() {
+        return Err(format!("{} no longer exists", path.display()));
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_existing_capture() {
+        let dir = tempfile::tempdir().unwrap();
+        let image = dir.path().join("shot.png");
+        std::fs::write(&image, b"png").unwrap();
+
+        assert_eq!(existing_capture(image.to_str().unwrap()).unwrap(), image);
+        assert!(existing_capture(dir.path().join("gone.png").to_str().unwrap()).is_err());
+        assert!(existing_capture(dir.path().to_str().unwrap()).is_err());
+    }
+}
@@ -0,0 +1,343 @@
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use std::io::Write;
+
+use crate::ocr::OcrLine;
+
+/// Rough Helvetica advance width as a fraction of the font size; the text
+/// layer is stretched to each box with `Tz`, so this only sets the start.
+const AVG_GLYPH_WIDTH: f64 = 0.5;
+
+/// Encodes text for a WinAnsi `( )` string. Latin-1 maps straight across;
+/// anything outside it becomes '?'.
+pub fn escape_pdf_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            ' '..='~' => escaped.push(c),
+            '\u{a0}'..='\u{ff}' => escaped.push_str(&format!("\\{:03o}", c as u32)),
+            _ => escaped.push('?'),
+        }
+    }
+    escaped
+}
+
+/// Draws the image full-page, then each OCR line in render mode 3
+/// (invisible) over the spot it was read from.
+fn page_content(width: f64, height: f64, lines: &[OcrLine]) -> String {
+    let mut content = format!("q {} 0 0 {} 0 0 cm /Im0 Do Q\nBT 3 Tr\n", width, height);
+    for line in lines {
+        let chars = line.text.chars().count();
+        if chars == 0 {
+            continue;
+        }
+        let size = (line.height * height).max(1.0);
+        let natural_width = chars as f64 * AVG_GLYPH_WIDTH * size;
+        let scale = 100.0 * line.width * width / natural_width;
+        let x = line.x * width;
+        // PDF's origin is bottom-left; sit the baseline a little above the box bottom.
+        let y = height * (1.0 - line.y - line.height) + size * 0.2;
+        content.push_str(&format!(
+            "/F0 {:.2} Tf {:.2} Tz 1 0 0 1 {:.2} {:.2} Tm ({}) Tj\n",
+            size,
+            scale,
+            x,
+            y,
+            escape_pdf_text(&line.text)
+        ));
+    }
+    content.push_str("ET\n");
+    content
+}
+
+/// Decodes `png` into a Flate-compressed RGB stream. PDF images carry no
+/// alpha here, so it is flattened onto white.
+fn image_stream(png: &[u8]) -> Result<(u32, u32, Vec<u8>), String> {
+    let image = image::load_from_memory(png)
+        .map_err(|e| format!("Failed to decode image: {}", e))?
+        .to_rgba8();
+    let (width, height) = image.dimensions();
+
+    let mut rgb = Vec::with_capacity((width * height * 3) as usize);
+    for pixel in image.pixels() {
+        let alpha = pixel[3] as u32;
+        for channel in &pixel.0[..3] {
+            rgb.push(((*channel as u32 * alpha + 255 * (255 - alpha)) / 255) as u8);
+        }
+    }
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(&rgb)
+        .map_err(|e| format!("Failed to compress image: {}", e))?;
+    let stream = encoder
+        .finish()
+        .map_err(|e| format!("Failed to compress image: {}", e))?;
+    Ok((width, height, stream))
+}
+
+fn image_object(width: u32, height: u32, stream: &[u8]) -> Vec<u8> {
+    let mut object = format!(
+        "<< /Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace /DeviceRGB \
+         /BitsPerComponent 8 /Filter /FlateDecode /Length {} >>\nstream\n",
+        width,
+        height,
+        stream.len()
+    )
+    .into_bytes();
+    object.extend_from_slice(stream);
+    object.extend_from_slice(b"\nendstream");
+    object
+}
+
+fn content_object(content: &str) -> Vec<u8> {
+    format!("<< /Length {} >>\nstream\n{}endstream", content.len(), content).into_bytes()
+}
+
+/// Serializes `objects` (numbered from 1, the catalog first) with their
+/// cross-reference table. `trailer` is appended to the trailer dictionary.
+fn assemble(objects: &[Vec<u8>], trailer: &str) -> Vec<u8> {
+    let mut pdf = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n", index + 1).as_bytes());
+        pdf.extend_from_slice(object);
+        pdf.extend_from_slice(b"\nendobj\n");
+    }
+
+    let xref_offset = pdf.len();
+    pdf.extend_from_slice(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes());
+    for offset in offsets {
+        pdf.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+    }
+    pdf.extend_from_slice(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R{} >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            trailer,
+            xref_offset
+        )
+        .as_bytes(),
+    );
+    pdf
+}
+
+/// Builds a one-page PDF of `png` with `lines` as a selectable, searchable
+/// text layer. The page is sized one point per pixel.
+pub fn build_searchable_pdf(png: &[u8], lines: &[OcrLine]) -> Result<Vec<u8>, String> {
+    let (width, height, stream) = image_stream(png)?;
+    let content = page_content(width as f64, height as f64, lines);
+
+    let objects: Vec<Vec<u8>> = vec![
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        b"<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_vec(),
+        format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+             /Resources << /XObject << /Im0 4 0 R >> /Font << /F0 5 0 R >> >> /Contents 6 0 R >>",
+            width, height
+        )
+        .into_bytes(),
+        image_object(width, height, &stream),
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>".to_vec(),
+        content_object(&content),
+    ];
+    Ok(assemble(&objects, ""))
+}
+
+/// Greedy word wrap to at most `max_chars` per line; longer words are split.
+pub fn wrap_text(text: &str, max_chars: usize) -> Vec<String> {
+    let max_chars = max_chars.max(1);
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let mut word: Vec<char> = word.chars().collect();
+            while word.len() > max_chars {
+                if !line.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                }
+                lines.push(word.drain(..max_chars).collect());
+            }
+            let word: String = word.into_iter().collect();
+            if word.is_empty() {
+                continue;
+            }
+            if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > max_chars {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(&word);
+        }
+        if !line.is_empty() {
+            lines.push(line);
+        }
+    }
+    lines
+}
+
+/// Builds a PDF with one page per `(png, caption)` step: the image on top
+/// and its caption below, sized one point per pixel. `title` heads the
+/// first page and becomes the document title.
+pub fn build_guide_pdf(title: &str, steps: &[(Vec<u8>, String)]) -> Result<Vec<u8>, String> {
+    if steps.is_empty() {
+        return Err("The guide has no steps".to_string());
+    }
+    const FIRST_STEP_OBJECT: usize = 5;
+
+    let kids: Vec<String> = (0..steps.len())
+        .map(|index| format!("{} 0 R", FIRST_STEP_OBJECT + index * 3))
+        .collect();
+    let mut objects: Vec<Vec<u8>> = vec![
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids.join(" "), steps.len()).into_bytes(),
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>".to_vec(),
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>".to_vec(),
+    ];
+
+    for (index, (png, caption)) in steps.iter().enumerate() {
+        let (width, height, stream) = image_stream(png)?;
+        let (page_width, image_height) = (width as f64, height as f64);
+        // Scale the caption with the image so Retina captures stay readable.
+        let size = (page_width / 50.0).clamp(12.0, 36.0);
+        let leading = size * 1.3;
+        let margin = size;
+        let max_chars = ((page_width - margin * 2.0) / (size * AVG_GLYPH_WIDTH)).floor() as usize;
+
+        let mut lines: Vec<(&str, String)> = Vec::new();
+        if index == 0 && !title.trim().is_empty() {
+            lines.extend(wrap_text(title.trim(), max_chars).into_iter().map(|line| ("/F1", line)));
+        }
+        let caption = caption.trim();
+        let caption = if caption.is_empty() {
+            format!("Step {}", index + 1)
+        } else {
+            format!("{}. {}", index + 1, caption)
+        };
+        lines.extend(wrap_text(&caption, max_chars).into_iter().map(|line| ("/F0", line)));
+
+        let caption_height = margin * 2.0 + leading * lines.len() as f64;
+        let mut content = format!(
+            "q {} 0 0 {} 0 {:.2} cm /Im0 Do Q\nBT\n",
+            page_width, image_height, caption_height
+        );
+        let mut y = caption_height - margin - size;
+        for (font, line) in &lines {
+            content.push_str(&format!(
+                "{} {:.2} Tf 1 0 0 1 {:.2} {:.2} Tm ({}) Tj\n",
+                font,
+                size,
+                margin,
+                y,
+                escape_pdf_text(line)
+            ));
+            y -= leading;
+        }
+        content.push_str("ET\n");
+
+        let page = FIRST_STEP_OBJECT + index * 3;
+        objects.push(
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {:.2}] \
+                 /Resources << /XObject << /Im0 {} 0 R >> /Font << /F0 3 0 R /F1 4 0 R >> >> \
+                 /Contents {} 0 R >>",
+                width,
+                image_height + caption_height,
+                page + 1,
+                page + 2
+            )
+            .into_bytes(),
+        );
+        objects.push(image_object(width, height, &stream));
+        objects.push(content_object(&content));
+    }
+
+    let info = objects.len() + 1;
+    objects.push(format!("<< /Title ({}) /Producer (Snipp) >>", escape_pdf_text(title.trim())).into_bytes());
+    Ok(assemble(&objects, &format!(" /Info {} 0 R", info)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        image::DynamicImage::ImageRgba8(image::RgbaImage::new(width, height))
+            .write_to(&mut std::io::Cursor::new(&mut bytes), image::ImageOutputFormat::Png)
+            .unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_escape_pdf_text() {
+        assert_eq!(escape_pdf_text("a (b) \\"), "a \\(b\\) \\\\");
+        assert_eq!(escape_pdf_text("caf\u{e9}"), "caf\\351");
+        assert_eq!(escape_pdf_text("\u{65e5}"), "?");
+    }
+
+    #[test]
+    fn test_build_searchable_pdf_has_text_layer_and_valid_xref() {
+        let lines = vec![OcrLine {
+            text: "Total (USD)".to_string(),
+            confidence: 1.0,
+            x: 0.1,
+            y: 0.5,
+            width: 0.8,
+            height: 0.1,
+        }];
+
+        let pdf = build_searchable_pdf(&png(40, 20), &lines).unwrap();
+        let text = String::from_utf8_lossy(&pdf);
+
+        assert!(text.starts_with("%PDF-1.4"));
+        assert!(text.contains("/MediaBox [0 0 40 20]"));
+        assert!(text.contains("BT 3 Tr"));
+        assert!(text.contains("(Total \\(USD\\)) Tj"));
+
+        let startxref = pdf.windows(9).rposition(|w| w == b"startxref").unwrap();
+        let trailer = std::str::from_utf8(&pdf[startxref..]).unwrap();
+        let xref: usize = trailer.lines().nth(1).unwrap().parse().unwrap();
+        let table = std::str::from_utf8(&pdf[xref..]).unwrap();
+        assert!(table.starts_with("xref"));
+        let offsets: Vec<usize> = table
+            .lines()
+            .skip(3)
+            .take(6)
+            .map(|entry| entry[..10].parse().unwrap())
+            .collect();
+        for (index, offset) in offsets.iter().enumerate() {
+            assert!(pdf[*offset..].starts_with(format!("{} 0 obj", index + 1).as_bytes()));
+        }
+    }
+
+    #[test]
+    fn test_wrap_text() {
+        assert_eq!(wrap_text("open the settings menu", 10), vec!["open the", "settings", "menu"]);
+        assert_eq!(wrap_text("abcdefghij", 4), vec!["abcd", "efgh", "ij"]);
+        assert_eq!(wrap_text("one\ntwo", 20), vec!["one", "two"]);
+    }
+
+    #[test]
+    fn test_build_guide_pdf_has_a_page_per_step() {
+        let steps = vec![
+            (png(400, 200), "Open (File) menu".to_string()),
+            (png(300, 300), String::new()),
+        ];
+
+        let pdf = build_guide_pdf("Export a report", &steps).unwrap();
+        let text = String::from_utf8_lossy(&pdf);
+
+        assert!(text.contains("/Kids [5 0 R 8 0 R] /Count 2"));
+        assert!(text.contains("(Export a report) Tj"));
+        assert!(text.contains("(1. Open \\(File\\) menu) Tj"));
+        assert!(text.contains("(Step 2) Tj"));
+        assert!(text.contains("/Info 11 0 R"));
+        assert!(build_guide_pdf("Empty", &[]).is_err());
+    }
+}
@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionKind {
+    /// Without it, macOS hands `screencapture` a wallpaper-only (black) image.
+    ScreenRecording,
+    /// Needed for global shortcuts to fire while other apps are focused.
+    Accessibility,
+}
+
+impl PermissionKind {
+    /// Deep link into the matching Privacy & Security pane.
+    pub fn settings_url(self) -> &'static str {
+        match self {
+            PermissionKind::ScreenRecording => {
+                "x-apple.systempreferences:com.apple.preference.security?Privacy_ScreenCapture"
+            }
+            PermissionKind::Accessibility => {
+                "x-apple.systempreferences:com.apple.preference.security?Privacy_Accessibility"
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod ffi {
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        pub fn CGPreflightScreenCaptureAccess() -> bool;
+        pub fn CGRequestScreenCaptureAccess() -> bool;
+    }
+
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        pub fn AXIsProcessTrusted() -> bool;
+    }
+}
+
+/// Whether the permission is currently granted. Other platforms have no
+/// equivalent prompts, so they always report granted.
+#[cfg(target_os = "macos")]
+pub fn is_granted(kind: PermissionKind) -> bool {
+    // SAFETY: both are argument-less status queries.
+    unsafe {
+        match kind {
+            PermissionKind::ScreenRecording => ffi::CGPreflightScreenCaptureAccess(),
+            PermissionKind::Accessibility => ffi::AXIsProcessTrusted(),
+        }
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn is_granted(_kind: PermissionKind) -> bool {
+    true
+}
+
+/// Shows the system prompt for screen recording the first time it is asked
+/// for. Returns whether access is granted; macOS only prompts once, so a
+/// `false` here means the user has to flip the switch in System Settings.
+#[cfg(target_os = "macos")]
+pub fn request_screen_recording() -> bool {
+    // SAFETY: argument-less call that may show a system prompt.
+    unsafe { ffi::CGRequestScreenCaptureAccess() }
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn request_screen_recording() -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_permission_kind_uses_snake_case() {
+        let kind: PermissionKind = serde_json::from_str("\"screen_recording\"").unwrap();
+        assert_eq!(kind, PermissionKind::ScreenRecording);
+        assert!(PermissionKind::Accessibility.settings_url().ends_with("Privacy_Accessibility"));
+    }
+}
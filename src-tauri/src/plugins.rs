@@ -0,0 +1,224 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+use tauri_plugin_shell::ShellExt;
+
+/// Where an action is offered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginContext {
+    /// The capture popup, before the capture is saved.
+    Popup,
+    /// Saved captures in quick search.
+    History,
+}
+
+/// `<id>.json` in the plugins folder.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+struct PluginManifest {
+    name: String,
+    /// Program to run; relative paths are resolved against the plugins folder.
+    command: String,
+    #[serde(default)]
+    args: Vec<String>,
+    #[serde(default)]
+    description: String,
+    #[serde(default = "all_contexts")]
+    contexts: Vec<PluginContext>,
+}
+
+fn all_contexts() -> Vec<PluginContext> {
+    vec![PluginContext::Popup, PluginContext::History]
+}
+
+/// A discovered action, as listed in the UI.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Plugin {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub contexts: Vec<PluginContext>,
+    #[serde(skip)]
+    command: PathBuf,
+    #[serde(skip)]
+    args: Vec<String>,
+}
+
+/// Passed to the plugin as JSON in `SNIPP_METADATA`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PluginMetadata {
+    pub file_path: String,
+    pub filename: String,
+    /// Capture time in milliseconds since the epoch.
+    pub timestamp: u64,
+    pub context: PluginContext,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub note: String,
+    pub share_url: Option<String>,
+}
+
+/// What the plugin printed. Plugins may print `{"message": ..., "url": ...}`;
+/// any other output is shown as the message.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct PluginOutput {
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+pub fn plugins_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("snipp").join("plugins"))
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path).is_ok_and(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("exe"))
+}
+
+/// Reads the plugins in `dir`: every `<id>.json` manifest, plus executables
+/// with no manifest, which are offered everywhere under their file name.
+/// Broken manifests are logged and skipped.
+pub fn discover(dir: &Path) -> Vec<Plugin> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries.filter_map(|entry| entry.ok().map(|entry| entry.path())).collect();
+    paths.sort();
+
+    let mut plugins: Vec<Plugin> = Vec::new();
+    for path in paths.iter().filter(|path| path.extension().is_some_and(|ext| ext == "json")) {
+        let Some(id) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        let manifest = std::fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|contents| serde_json::from_str::<PluginManifest>(&contents).map_err(|e| e.to_string()));
+        match manifest {
+            Ok(manifest) => plugins.push(Plugin {
+                id: id.to_string(),
+                name: manifest.name,
+                description: manifest.description,
+                contexts: manifest.contexts,
+                command: dir.join(manifest.command),
+                args: manifest.args,
+            }),
+            Err(e) => tracing::warn!("Skipping plugin manifest {}: {}", path.display(), e),
+        }
+    }
+
+    let claimed: Vec<PathBuf> = plugins.iter().map(|plugin| plugin.command.clone()).collect();
+    for path in paths {
+        let Some(id) = path.file_stem().and_then(|stem| stem.to_str()).map(str::to_string) else {
+            continue;
+        };
+        if id.starts_with('.') || !is_executable(&path) || claimed.contains(&path) {
+            continue;
+        }
+        if plugins.iter().any(|plugin| plugin.id == id) {
+            continue;
+        }
+        plugins.push(Plugin {
+            name: id.clone(),
+            id,
+            description: String::new(),
+            contexts: all_contexts(),
+            command: path,
+            args: Vec::new(),
+        });
+    }
+    plugins
+}
+
+pub fn parse_output(stdout: &str) -> PluginOutput {
+    let stdout = stdout.trim();
+    if let Ok(output) = serde_json::from_str::<PluginOutput>(stdout) {
+        return output;
+    }
+    PluginOutput {
+        message: (!stdout.is_empty()).then(|| stdout.to_string()),
+        url: None,
+    }
+}
+
+/// Runs `plugin` with the capture path as its last argument and the
+/// metadata in `SNIPP_METADATA`.
+pub async fn run(app_handle: &AppHandle, plugin: &Plugin, metadata: &PluginMetadata) -> Result<PluginOutput, String> {
+    let metadata_json =
+        serde_json::to_string(metadata).map_err(|e| format!("Failed to serialize capture metadata: {}", e))?;
+    let output = app_handle
+        .shell()
+        .command(plugin.command.to_string_lossy().as_ref())
+        .args(plugin.args.iter().map(String::as_str).chain([metadata.file_path.as_str()]))
+        .env("SNIPP_METADATA", metadata_json)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run plugin '{}': {}", plugin.name, e))?;
+
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Plugin '{}' failed: {}", plugin.name, error.trim()));
+    }
+    Ok(parse_output(&String::from_utf8_lossy(&output.stdout)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_discover_reads_manifests_and_bare_executables() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("imgur.json"),
+            r#"{"name": "Upload to Imgur", "command": "imgur.sh", "contexts": ["history"]}"#,
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("imgur.sh"), "#!/bin/sh\n").unwrap();
+        std::fs::write(dir.path().join("broken.json"), "{").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::write(dir.path().join("ocr-to-jira"), "#!/bin/sh\n").unwrap();
+            for name in ["imgur.sh", "ocr-to-jira"] {
+                std::fs::set_permissions(dir.path().join(name), std::fs::Permissions::from_mode(0o755)).unwrap();
+            }
+        }
+
+        let plugins = discover(dir.path());
+
+        assert_eq!(plugins[0].id, "imgur");
+        assert_eq!(plugins[0].name, "Upload to Imgur");
+        assert_eq!(plugins[0].contexts, vec![PluginContext::History]);
+        assert_eq!(plugins[0].command, dir.path().join("imgur.sh"));
+        #[cfg(unix)]
+        {
+            assert_eq!(plugins.len(), 2);
+            assert_eq!(plugins[1].id, "ocr-to-jira");
+            assert_eq!(plugins[1].contexts, all_contexts());
+        }
+        assert!(discover(&dir.path().join("missing")).is_empty());
+    }
+
+    #[test]
+    fn test_parse_output() {
+        assert_eq!(
+            parse_output(r#"{"message": "Uploaded", "url": "https://example.com/a"}"#),
+            PluginOutput {
+                message: Some("Uploaded".to_string()),
+                url: Some("https://example.com/a".to_string()),
+            }
+        );
+        assert_eq!(parse_output(" Done\n").message.as_deref(), Some("Done"));
+        assert_eq!(parse_output(""), PluginOutput::default());
+    }
+}
@@ -0,0 +1,68 @@
+use snipp_core::burst;
+use snipp_core::cache::ScreenshotStore;
+use tauri::http::{header, Request, Response, StatusCode};
+use tauri::{AppHandle, Manager};
+
+/// Custom URI scheme the webviews load pending captures from, so events
+/// only need to carry metadata.
+pub const SCHEME: &str = "snipp";
+
+/// Where a webview loads the pending capture `timestamp` from.
+pub fn screenshot_url(timestamp: u64) -> String {
+    // WebView2 only serves custom schemes as http://<scheme>.localhost.
+    if cfg!(windows) {
+        format!("http://{}.localhost/screenshot/{}", SCHEME, timestamp)
+    } else {
+        format!("{}://screenshot/{}", SCHEME, timestamp)
+    }
+}
+
+/// The timestamp in `snipp://screenshot/<timestamp>`, or in
+/// `http://snipp.localhost/screenshot/<timestamp>` on Windows.
+fn requested_timestamp(host: Option<&str>, path: &str) -> Option<u64> {
+    let path = path.trim_matches('/');
+    let timestamp = match host {
+        Some("screenshot") => path,
+        _ => path.strip_prefix("screenshot/")?,
+    };
+    timestamp.parse().ok()
+}
+
+/// Answers a request for a pending capture from the store. Reading a
+/// spilled capture touches the disk, so call this off the main thread.
+pub fn screenshot_response(app_handle: &AppHandle, request: &Request<Vec<u8>>) -> Response<Vec<u8>> {
+    let uri = request.uri();
+    let image_data = requested_timestamp(uri.host(), uri.path())
+        .and_then(|timestamp| app_handle.state::<ScreenshotStore>().get(timestamp));
+    let response = match image_data {
+        Some(image_data) => Response::builder()
+            .header(
+                header::CONTENT_TYPE,
+                if burst::is_gif(&image_data) { "image/gif" } else { "image/png" },
+            )
+            // Edits replace the capture under the same URL.
+            .header(header::CACHE_CONTROL, "no-store")
+            .header(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*")
+            .body(image_data),
+        None => Response::builder().status(StatusCode::NOT_FOUND).body(Vec::new()),
+    };
+    response.unwrap_or_else(|e| {
+        tracing::error!("Failed to build screenshot response: {}", e);
+        let mut response = Response::new(Vec::new());
+        *response.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+        response
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requested_timestamp() {
+        assert_eq!(requested_timestamp(Some("screenshot"), "/1700000000000"), Some(1_700_000_000_000));
+        assert_eq!(requested_timestamp(Some("snipp.localhost"), "/screenshot/42"), Some(42));
+        assert_eq!(requested_timestamp(Some("localhost"), "/other/42"), None);
+        assert_eq!(requested_timestamp(Some("screenshot"), "/../config.json"), None);
+    }
+}
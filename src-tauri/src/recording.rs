@@ -0,0 +1,1475 @@
+use chrono::{Local, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_shell::process::{CommandChild, CommandEvent};
+use tauri_plugin_shell::ShellExt;
+use tokio::sync::watch;
+
+use crate::click_tracker::ClickTracker;
+use crate::region::CaptureRegion;
+use crate::system_audio::SystemAudioCapture;
+use snipp_core::highlight::{self, Click, ClickHighlight};
+
+pub use snipp_core::recording::{AnimationFormat, AnimationOptions, RecordingLimits, RecordingQuality, WebcamOverlay};
+
+
+/// Locations checked for ffmpeg when no explicit path is configured. GUI apps
+/// on macOS don't inherit the shell's PATH, so Homebrew prefixes are listed.
+const FFMPEG_CANDIDATES: [&str; 3] = [
+    "/opt/homebrew/bin/ffmpeg",
+    "/usr/local/bin/ffmpeg",
+    "/usr/bin/ffmpeg",
+];
+
+/// Minimum gap between `recording-audio-level` events; astats reports per
+/// audio frame, which is far more often than a meter needs.
+pub(crate) const AUDIO_LEVEL_INTERVAL: Duration = Duration::from_millis(100);
+
+const AUDIO_LEVEL_KEY: &str = "lavfi.astats.Overall.RMS_level=";
+
+/// Most built-in and USB cameras only offer 30 fps through avfoundation,
+/// regardless of the screen frame rate.
+const WEBCAM_FRAME_RATE: u32 = 30;
+
+#[derive(Debug, Clone)]
+pub struct RecordingOptions {
+    pub output_path: PathBuf,
+    pub region: Option<CaptureRegion>,
+    pub scale_factor: f64,
+    pub quality: RecordingQuality,
+    /// avfoundation audio device name; `None` records video only.
+    pub audio_device: Option<String>,
+    /// Captures what the Mac plays through ScreenCaptureKit alongside each
+    /// segment, mixed with the microphone when both are enabled.
+    pub system_audio: bool,
+    pub webcam: Option<WebcamOverlay>,
+    /// Drawn into each segment once it has finished recording.
+    pub click_highlight: ClickHighlight,
+}
+
+impl RecordingOptions {
+    pub fn new(output_path: PathBuf, region: Option<CaptureRegion>, scale_factor: f64) -> Self {
+        Self {
+            output_path,
+            region,
+            scale_factor,
+            quality: RecordingQuality::default(),
+            audio_device: None,
+            system_audio: false,
+            webcam: None,
+            click_highlight: ClickHighlight::Off,
+        }
+    }
+
+    fn for_segment(&self, output_path: PathBuf) -> Self {
+        Self {
+            output_path,
+            ..self.clone()
+        }
+    }
+
+    /// Video pixels to the point, after the quality downscale.
+    fn pixels_per_point(&self) -> f64 {
+        self.scale_factor * self.quality.scale.min(1.0)
+    }
+
+    /// Maps clicks from desktop points into the video's pixels, dropping
+    /// those outside the recorded area.
+    fn clicks_in_video(&self, clicks: Vec<Click>) -> Vec<Click> {
+        let origin = self
+            .region
+            .map(|region| (region.x.max(0) as f64, region.y.max(0) as f64))
+            .unwrap_or((0.0, 0.0));
+        clicks
+            .into_iter()
+            .filter(|click| match self.region {
+                Some(region) => region.contains(click.x, click.y),
+                None => click.x >= 0.0 && click.y >= 0.0,
+            })
+            .map(|click| click.to_output(origin, self.pixels_per_point()))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AudioLevel {
+    pub recording_id: u64,
+    /// RMS level in dBFS; silence is reported as -100.
+    pub level_db: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingInfo {
+    pub id: u64,
+    pub file_path: String,
+    pub region: Option<CaptureRegion>,
+    pub duration_ms: u64,
+}
+
+/// `elapsed_ms` is wall-clock time since the recording started; `recorded_ms`
+/// excludes time spent paused and matches the length of the output.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingStatus {
+    pub active: bool,
+    pub paused: bool,
+    pub id: Option<u64>,
+    pub elapsed_ms: u64,
+    pub recorded_ms: u64,
+}
+
+impl RecordingStatus {
+    fn idle() -> Self {
+        Self {
+            active: false,
+            paused: false,
+            id: None,
+            elapsed_ms: 0,
+            recorded_ms: 0,
+        }
+    }
+}
+
+/// One ffmpeg process writing one segment. Pausing finishes the current
+/// segment; resuming starts a new one, so paused time never reaches the output.
+pub struct SegmentProcess {
+    child: CommandChild,
+    finished: tokio::sync::oneshot::Receiver<Option<i32>>,
+    started_at: Instant,
+    app_handle: AppHandle,
+    ffmpeg: PathBuf,
+    options: RecordingOptions,
+    system_audio: Option<SystemAudioCapture>,
+    click_tracker: Option<ClickTracker>,
+}
+
+impl SegmentProcess {
+    pub fn spawn(
+        app_handle: &AppHandle,
+        recording_id: u64,
+        ffmpeg: &Path,
+        options: &RecordingOptions,
+    ) -> Result<Self, String> {
+        let (mut events, child) = app_handle
+            .shell()
+            .command(ffmpeg)
+            .args(build_ffmpeg_args(options))
+            .spawn()
+            .map_err(|e| format!("Failed to start ffmpeg: {}", e))?;
+
+        let (finished_tx, finished) = tokio::sync::oneshot::channel::<Option<i32>>();
+        let level_handle = app_handle.clone();
+        tauri::async_runtime::spawn(async move {
+            let mut finished_tx = Some(finished_tx);
+            let mut last_level_at: Option<Instant> = None;
+            while let Some(event) = events.recv().await {
+                match event {
+                    CommandEvent::Stderr(line) => {
+                        let line = String::from_utf8_lossy(&line);
+                        if let Some(level_db) = parse_audio_level(&line) {
+                            let due = last_level_at
+                                .map(|at| at.elapsed() >= AUDIO_LEVEL_INTERVAL)
+                                .unwrap_or(true);
+                            if due {
+                                last_level_at = Some(Instant::now());
+                                let _ = level_handle.emit(
+                                    "recording-audio-level",
+                                    AudioLevel { recording_id, level_db },
+                                );
+                            }
+                        } else {
+                            tracing::trace!("ffmpeg: {}", line);
+                        }
+                    }
+                    CommandEvent::Error(e) => tracing::error!("ffmpeg error: {}", e),
+                    CommandEvent::Terminated(payload) => {
+                        if let Some(tx) = finished_tx.take() {
+                            let _ = tx.send(payload.code);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        });
+
+        let system_audio = if options.system_audio {
+            let level_for = options.audio_device.is_none().then_some(recording_id);
+            match SystemAudioCapture::start(system_audio_path(&options.output_path), app_handle, level_for) {
+                Ok(capture) => Some(capture),
+                Err(e) => {
+                    let _ = child.kill();
+                    return Err(e);
+                }
+            }
+        } else {
+            None
+        };
+
+        let click_tracker = match options.click_highlight {
+            ClickHighlight::Off => None,
+            _ => ClickTracker::start(),
+        };
+
+        Ok(Self {
+            child,
+            finished,
+            started_at: Instant::now(),
+            app_handle: app_handle.clone(),
+            ffmpeg: ffmpeg.to_path_buf(),
+            options: options.clone(),
+            system_audio,
+            click_tracker,
+        })
+    }
+
+    fn elapsed(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+
+    /// Asks ffmpeg to finish (by sending `q`) so the segment is finalized,
+    /// killing it if it doesn't exit in time, then mixes in system audio and
+    /// draws the clicks. Returns how long it recorded.
+    pub async fn finish(mut self) -> Duration {
+        let recorded = self.elapsed();
+
+        if let Err(e) = self.child.write(b"q") {
+            tracing::error!("Failed to signal ffmpeg to stop: {}", e);
+        }
+        let system_audio = self.system_audio.take().and_then(SystemAudioCapture::stop);
+        let clicks = match self.click_tracker.take() {
+            Some(tracker) => self.options.clicks_in_video(tracker.finish().await),
+            None => Vec::new(),
+        };
+
+        match tokio::time::timeout(Duration::from_secs(10), self.finished).await {
+            Ok(Ok(code)) => tracing::debug!("ffmpeg exited with code {:?}", code),
+            _ => {
+                tracing::error!("ffmpeg did not stop in time; killing it");
+                let _ = self.child.kill();
+            }
+        }
+
+        if let Some(audio_path) = system_audio {
+            if let Err(e) = mix_system_audio(
+                &self.app_handle,
+                &self.ffmpeg,
+                &self.options.output_path,
+                &audio_path,
+                self.options.audio_device.is_some(),
+            )
+            .await
+            {
+                tracing::error!("Keeping the segment without system audio: {}", e);
+            }
+            let _ = std::fs::remove_file(&audio_path);
+        }
+
+        if let Err(e) = highlight_clicks(&self.app_handle, &self.ffmpeg, &self.options, &clicks).await {
+            tracing::error!("Keeping the segment without click highlights: {}", e);
+        }
+
+        recorded
+    }
+}
+
+/// Where a segment's system audio is written while it records.
+fn system_audio_path(segment: &Path) -> PathBuf {
+    segment.with_extension("wav")
+}
+
+/// Copies the video as is and adds the system audio, mixed with the
+/// microphone track when there is one.
+fn build_mix_args(video: &Path, audio: &Path, output: &Path, has_microphone: bool) -> Vec<String> {
+    let mut args = vec![
+        "-y".to_string(),
+        "-i".to_string(),
+        path_arg(video),
+        "-i".to_string(),
+        path_arg(audio),
+    ];
+    let audio_map = if has_microphone {
+        args.push("-filter_complex".into());
+        args.push("[0:a][1:a]amix=inputs=2:duration=first[aout]".into());
+        "[aout]"
+    } else {
+        "1:a"
+    };
+    args.extend(
+        ["-map", "0:v", "-map", audio_map, "-c:v", "copy", "-c:a", "aac", "-b:a", "128k", "-movflags", "+faststart"]
+            .iter()
+            .map(|arg| arg.to_string()),
+    );
+    args.push(path_arg(output));
+    args
+}
+
+async fn mix_system_audio(
+    app_handle: &AppHandle,
+    ffmpeg: &Path,
+    segment: &Path,
+    audio: &Path,
+    has_microphone: bool,
+) -> Result<(), String> {
+    let mixed = segment.with_extension("mixed.mp4");
+    let output = app_handle
+        .shell()
+        .command(ffmpeg)
+        .args(build_mix_args(segment, audio, &mixed, has_microphone))
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run ffmpeg: {}", e))?;
+
+    if !output.status.success() {
+        let _ = std::fs::remove_file(&mixed);
+        let error = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to add system audio: {}", error));
+    }
+    std::fs::rename(&mixed, segment).map_err(|e| format!("Failed to add system audio: {}", e))
+}
+
+/// Re-encodes the video with the ripple sprites, if any, looped as inputs
+/// `1..` for the highlight filter graph. The audio is copied as is.
+fn build_highlight_args(
+    video: &Path,
+    sprites: &[PathBuf],
+    filter: &str,
+    output: &Path,
+    quality: &RecordingQuality,
+) -> Vec<String> {
+    let mut args = vec!["-y".to_string(), "-i".to_string(), path_arg(video)];
+    for sprite in sprites {
+        args.extend(["-loop", "1", "-i"].iter().map(|arg| arg.to_string()));
+        args.push(path_arg(sprite));
+    }
+    args.extend(
+        [
+            "-filter_complex",
+            filter,
+            "-map",
+            "[vout]",
+            "-map",
+            "0:a?",
+            "-c:a",
+            "copy",
+            "-c:v",
+            "libx264",
+            "-preset",
+            "veryfast",
+            "-pix_fmt",
+            "yuv420p",
+            "-movflags",
+            "+faststart",
+        ]
+        .iter()
+        .map(|arg| arg.to_string()),
+    );
+    args.extend(quality.rate_control_args());
+    args.push(path_arg(output));
+    args
+}
+
+/// Draws `clicks` (in video pixels) into a finished segment.
+async fn highlight_clicks(
+    app_handle: &AppHandle,
+    ffmpeg: &Path,
+    options: &RecordingOptions,
+    clicks: &[Click],
+) -> Result<(), String> {
+    let scale = options.pixels_per_point();
+    let Some(filter) = highlight::ffmpeg_filter(options.click_highlight, clicks, scale) else {
+        return Ok(());
+    };
+    let segment = &options.output_path;
+
+    let sprites: Vec<PathBuf> = match options.click_highlight {
+        ClickHighlight::Ripple => (0..highlight::RIPPLE_STEPS)
+            .map(|step| segment.with_extension(format!("ripple{}.png", step)))
+            .collect(),
+        _ => Vec::new(),
+    };
+    let paths = sprites.clone();
+    tokio::task::spawn_blocking(move || {
+        paths.iter().enumerate().try_for_each(|(step, path)| {
+            highlight::ripple_sprite(step, scale)
+                .save(path)
+                .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+        })
+    })
+    .await
+    .map_err(|e| format!("Sprite task failed: {}", e))??;
+
+    let highlighted = segment.with_extension("clicks.mp4");
+    let output = app_handle
+        .shell()
+        .command(ffmpeg)
+        .args(build_highlight_args(segment, &sprites, &filter, &highlighted, &options.quality))
+        .output()
+        .await;
+    for sprite in &sprites {
+        let _ = std::fs::remove_file(sprite);
+    }
+    let output = output.map_err(|e| format!("Failed to run ffmpeg: {}", e))?;
+
+    if !output.status.success() {
+        let _ = std::fs::remove_file(&highlighted);
+        let error = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to draw clicks: {}", error));
+    }
+    std::fs::rename(&highlighted, segment).map_err(|e| format!("Failed to draw clicks: {}", e))
+}
+
+pub struct ActiveRecording {
+    pub id: u64,
+    pub output_path: PathBuf,
+    ffmpeg: PathBuf,
+    /// Settings shared by every segment; only the output path differs.
+    template: RecordingOptions,
+    started_at: Instant,
+    segments: Vec<PathBuf>,
+    recorded: Duration,
+    current: Option<SegmentProcess>,
+    /// Set from `pause` until the detached segment has finished; carries its
+    /// length once ffmpeg is done with it.
+    pausing: Option<watch::Receiver<Option<Duration>>>,
+}
+
+impl ActiveRecording {
+    pub fn start(
+        app_handle: &AppHandle,
+        id: u64,
+        ffmpeg: PathBuf,
+        options: RecordingOptions,
+    ) -> Result<Self, String> {
+        let mut recording = Self {
+            id,
+            output_path: options.output_path.clone(),
+            ffmpeg,
+            template: options,
+            started_at: Instant::now(),
+            segments: Vec::new(),
+            recorded: Duration::ZERO,
+            current: None,
+            pausing: None,
+        };
+        recording.resume(app_handle)?;
+        Ok(recording)
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.current.is_none()
+    }
+
+    pub fn recorded(&self) -> Duration {
+        self.recorded + self.current.as_ref().map(|s| s.elapsed()).unwrap_or_default()
+    }
+
+    pub fn status(&self) -> RecordingStatus {
+        RecordingStatus {
+            active: true,
+            paused: self.is_paused(),
+            id: Some(self.id),
+            elapsed_ms: self.started_at.elapsed().as_millis() as u64,
+            recorded_ms: self.recorded().as_millis() as u64,
+        }
+    }
+
+    pub fn segment_paths(&self) -> &[PathBuf] {
+        &self.segments
+    }
+
+    pub fn info(&self) -> RecordingInfo {
+        RecordingInfo {
+            id: self.id,
+            file_path: self.output_path.to_string_lossy().to_string(),
+            region: self.template.region,
+            duration_ms: self.recorded().as_millis() as u64,
+        }
+    }
+
+    /// Detaches the running segment; the caller finishes it outside the state
+    /// lock and sends its length through the returned sender. Until then the
+    /// recording is pausing: `resume` refuses and `finalize` waits for it.
+    pub fn pause(&mut self) -> Result<(SegmentProcess, watch::Sender<Option<Duration>>), String> {
+        let segment = self
+            .current
+            .take()
+            .ok_or_else(|| "Recording is already paused".to_string())?;
+        let (done, pausing) = watch::channel(None);
+        self.pausing = Some(pausing);
+        Ok((segment, done))
+    }
+
+    /// Counts a finished pause's segment, if it has finished. Returns false
+    /// while ffmpeg is still writing it.
+    pub fn settle_pause(&mut self) -> bool {
+        let Some(pausing) = &self.pausing else {
+            return true;
+        };
+        let recorded = *pausing.borrow();
+        match recorded {
+            Some(recorded) => {
+                self.recorded += recorded;
+                self.pausing = None;
+                true
+            }
+            None if pausing.has_changed().is_err() => {
+                // The pause was dropped before reporting; its length is lost.
+                self.pausing = None;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn resume(&mut self, app_handle: &AppHandle) -> Result<(), String> {
+        if self.current.is_some() {
+            return Err("Recording is not paused".to_string());
+        }
+        if !self.settle_pause() {
+            return Err("Recording is still pausing".to_string());
+        }
+
+        let segment_path = segment_path(self.id, self.segments.len());
+        let options = self.template.for_segment(segment_path.clone());
+        let segment = SegmentProcess::spawn(app_handle, self.id, &self.ffmpeg, &options)?;
+
+        self.segments.push(segment_path);
+        self.current = Some(segment);
+        Ok(())
+    }
+
+    /// Stops the running segment (if any) and joins all segments into the
+    /// final output file.
+    pub async fn finalize(mut self, app_handle: &AppHandle) -> Result<RecordingInfo, String> {
+        if let Some(mut pausing) = self.pausing.take() {
+            // A pause still finishing its segment; joining before ffmpeg is
+            // done would cut the file short.
+            if let Ok(recorded) = pausing.wait_for(Option::is_some).await {
+                self.recorded += recorded.unwrap_or_default();
+            }
+        }
+        if let Some(segment) = self.current.take() {
+            let recorded = segment.finish().await;
+            self.recorded += recorded;
+        }
+
+        let segments: Vec<PathBuf> = self
+            .segments
+            .iter()
+            .filter(|path| std::fs::metadata(path).map(|m| m.len() > 0).unwrap_or(false))
+            .cloned()
+            .collect();
+
+        let result = match segments.as_slice() {
+            [] => Err("Recording failed: no video was written".to_string()),
+            [single] => move_file(single, &self.output_path),
+            _ => concat_segments(app_handle, &self.ffmpeg, &segments, &self.output_path).await,
+        };
+
+        for segment in &self.segments {
+            let _ = std::fs::remove_file(segment);
+        }
+
+        result.map(|_| self.info())
+    }
+}
+
+#[derive(Default)]
+pub struct RecordingManager {
+    active: Option<ActiveRecording>,
+    completed: Vec<RecordingInfo>,
+}
+
+impl RecordingManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.active.is_some()
+    }
+
+    pub fn begin(&mut self, recording: ActiveRecording) {
+        self.active = Some(recording);
+    }
+
+    pub fn active_mut(&mut self) -> Option<&mut ActiveRecording> {
+        self.active.as_mut()
+    }
+
+    pub fn take_active(&mut self) -> Option<ActiveRecording> {
+        self.active.take()
+    }
+
+    pub fn status(&self) -> RecordingStatus {
+        self.active
+            .as_ref()
+            .map(|active| active.status())
+            .unwrap_or_else(RecordingStatus::idle)
+    }
+
+    /// Files written so far, so the caller can total their size without
+    /// holding the lock.
+    pub fn segment_paths(&self) -> Vec<PathBuf> {
+        self.active
+            .as_ref()
+            .map(|active| active.segment_paths().to_vec())
+            .unwrap_or_default()
+    }
+
+    pub fn record_completed(&mut self, info: RecordingInfo) {
+        self.completed.insert(0, info);
+    }
+
+    pub fn find_completed(&self, id: u64) -> Option<&RecordingInfo> {
+        self.completed.iter().find(|info| info.id == id)
+    }
+}
+
+fn segment_path(id: u64, index: usize) -> PathBuf {
+    std::env::temp_dir().join(format!("snipp_recording_{}_{}.mp4", id, index))
+}
+
+fn move_file(from: &Path, to: &Path) -> Result<(), String> {
+    // rename fails across volumes (temp dir vs. an external save location).
+    if std::fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    std::fs::copy(from, to).map_err(|e| format!("Failed to save recording: {}", e))?;
+    let _ = std::fs::remove_file(from);
+    Ok(())
+}
+
+/// Checks a trim range against the clip length; `duration_ms` of 0 means
+/// the length is unknown and only the ordering is checked.
+pub fn validate_trim(start_ms: u64, end_ms: u64, duration_ms: u64) -> Result<(), String> {
+    if end_ms <= start_ms {
+        return Err("Trim end must be after the start".to_string());
+    }
+    if duration_ms > 0 && start_ms >= duration_ms {
+        return Err("Trim start is past the end of the recording".to_string());
+    }
+    Ok(())
+}
+
+/// `Snipp Recording ... .mp4` becomes `Snipp Recording ... (trimmed).mp4`
+/// next to the original.
+pub fn trimmed_output_path(original: &Path) -> PathBuf {
+    let stem = original
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("Snipp Recording");
+    original.with_file_name(format!("{} (trimmed).mp4", stem))
+}
+
+/// Seeking before `-i` with stream copy cuts on the nearest keyframe without
+/// re-encoding; `reencode` gives frame-accurate cuts at the cost of time.
+fn build_trim_args(
+    input: &Path,
+    output: &Path,
+    start_ms: u64,
+    end_ms: u64,
+    reencode: bool,
+) -> Vec<String> {
+    let seconds = |ms: u64| format!("{:.3}", ms as f64 / 1000.0);
+    let mut args = vec![
+        "-y".to_string(),
+        "-ss".to_string(),
+        seconds(start_ms),
+        "-i".to_string(),
+        path_arg(input),
+        "-t".to_string(),
+        seconds(end_ms - start_ms),
+    ];
+    if reencode {
+        args.extend(
+            ["-c:v", "libx264", "-preset", "veryfast", "-pix_fmt", "yuv420p", "-c:a", "aac"]
+                .iter()
+                .map(|arg| arg.to_string()),
+        );
+    } else {
+        args.extend(
+            ["-c", "copy", "-avoid_negative_ts", "make_zero"]
+                .iter()
+                .map(|arg| arg.to_string()),
+        );
+    }
+    args.extend(["-movflags", "+faststart"].iter().map(|arg| arg.to_string()));
+    args.push(path_arg(output));
+    args
+}
+
+/// Trims `input` into `output`, trying a lossless stream copy first and
+/// re-encoding if ffmpeg rejects it.
+pub async fn trim_file(
+    app_handle: &AppHandle,
+    ffmpeg: &Path,
+    input: &Path,
+    output: &Path,
+    start_ms: u64,
+    end_ms: u64,
+) -> Result<(), String> {
+    let mut last_error = String::new();
+    for reencode in [false, true] {
+        let result = app_handle
+            .shell()
+            .command(ffmpeg)
+            .args(build_trim_args(input, output, start_ms, end_ms, reencode))
+            .output()
+            .await
+            .map_err(|e| format!("Failed to run ffmpeg: {}", e))?;
+
+        let written = std::fs::metadata(output).map(|m| m.len() > 0).unwrap_or(false);
+        if result.status.success() && written {
+            return Ok(());
+        }
+        last_error = String::from_utf8_lossy(&result.stderr).to_string();
+        tracing::debug!("Trim (reencode: {}) failed: {}", reencode, last_error);
+    }
+
+    let _ = std::fs::remove_file(output);
+    Err(format!("Failed to trim recording: {}", last_error))
+}
+
+/// GIFs get a palette generated from the clip itself (palettegen +
+/// paletteuse), which looks far better than ffmpeg's default 256-color one.
+pub fn build_animation_args(
+    input: &Path,
+    output: &Path,
+    format: AnimationFormat,
+    options: &AnimationOptions,
+) -> Vec<String> {
+    let mut filters = format!("fps={}", options.fps);
+    if options.max_width > 0 {
+        filters.push_str(&format!(
+            ",scale='min({},iw)':-2:flags=lanczos",
+            options.max_width
+        ));
+    }
+
+    let mut args = vec!["-y".to_string(), "-i".to_string(), path_arg(input)];
+    match format {
+        AnimationFormat::Gif => {
+            let dither = if options.dither { "sierra2_4a" } else { "none" };
+            args.push("-filter_complex".into());
+            args.push(format!(
+                "{},split[a][b];[a]palettegen=max_colors={}:stats_mode=diff[p];[b][p]paletteuse=dither={}",
+                filters, options.max_colors, dither
+            ));
+        }
+        AnimationFormat::Webp => {
+            args.push("-vf".into());
+            args.push(filters);
+            args.extend(
+                ["-c:v", "libwebp", "-lossless", "0", "-q:v"]
+                    .iter()
+                    .map(|arg| arg.to_string()),
+            );
+            args.push(options.quality.to_string());
+        }
+    }
+    args.extend(["-loop", "0", "-an"].iter().map(|arg| arg.to_string()));
+    args.push(path_arg(output));
+    args
+}
+
+pub async fn convert_to_animation(
+    app_handle: &AppHandle,
+    ffmpeg: &Path,
+    input: &Path,
+    output: &Path,
+    format: AnimationFormat,
+    options: &AnimationOptions,
+) -> Result<(), String> {
+    let result = app_handle
+        .shell()
+        .command(ffmpeg)
+        .args(build_animation_args(input, output, format, options))
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run ffmpeg: {}", e))?;
+
+    if !result.status.success() {
+        let _ = std::fs::remove_file(output);
+        let error = String::from_utf8_lossy(&result.stderr);
+        return Err(format!("Failed to convert recording: {}", error));
+    }
+    Ok(())
+}
+
+/// Builds an ffmpeg concat-demuxer list; single quotes are escaped the way
+/// the demuxer expects (`'\''`).
+fn build_concat_list(segments: &[PathBuf]) -> String {
+    segments
+        .iter()
+        .map(|path| format!("file '{}'\n", path.to_string_lossy().replace('\'', "'\\''")))
+        .collect()
+}
+
+async fn concat_segments(
+    app_handle: &AppHandle,
+    ffmpeg: &Path,
+    segments: &[PathBuf],
+    output_path: &Path,
+) -> Result<(), String> {
+    let list_path = segments[0].with_extension("txt");
+    std::fs::write(&list_path, build_concat_list(segments))
+        .map_err(|e| format!("Failed to write segment list: {}", e))?;
+
+    let output = app_handle
+        .shell()
+        .command(ffmpeg)
+        .args([
+            "-y".to_string(),
+            "-f".to_string(),
+            "concat".to_string(),
+            "-safe".to_string(),
+            "0".to_string(),
+            "-i".to_string(),
+            path_arg(&list_path),
+            "-c".to_string(),
+            "copy".to_string(),
+            "-movflags".to_string(),
+            "+faststart".to_string(),
+            path_arg(output_path),
+        ])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run ffmpeg: {}", e));
+
+    let _ = std::fs::remove_file(&list_path);
+    let output = output?;
+
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to join recording segments: {}", error));
+    }
+    Ok(())
+}
+
+/// `timestamp` is milliseconds, mirroring `build_screenshot_filename`.
+pub fn build_recording_filename(timestamp: u64) -> String {
+    let formatted = Local
+        .timestamp_opt((timestamp / 1000) as i64, 0)
+        .single()
+        .map(|dt| dt.format("%y-%m-%d at %H.%M.%S").to_string())
+        .unwrap_or_else(|| timestamp.to_string());
+    format!("Snipp Recording {}.mp4", formatted)
+}
+
+/// Resolves the ffmpeg binary: the configured path when set, otherwise the
+/// first well-known install location, otherwise whatever is on PATH.
+pub fn find_ffmpeg(configured_path: &str) -> Option<PathBuf> {
+    if !configured_path.is_empty() {
+        let path = PathBuf::from(configured_path);
+        return path.is_file().then_some(path);
+    }
+
+    let from_candidates = FFMPEG_CANDIDATES
+        .iter()
+        .map(PathBuf::from)
+        .find(|path| path.is_file());
+
+    from_candidates.or_else(|| {
+        std::env::var_os("PATH").and_then(|paths| {
+            std::env::split_paths(&paths)
+                .map(|dir| dir.join("ffmpeg"))
+                .find(|path| path.is_file())
+        })
+    })
+}
+
+/// Builds the ffmpeg arguments for an avfoundation screen recording. The
+/// region is given in points, so it is scaled to the pixels avfoundation
+/// delivers before cropping.
+pub fn build_ffmpeg_args(options: &RecordingOptions) -> Vec<String> {
+    let mut args: Vec<String> = vec![
+        "-y".into(),
+        "-f".into(),
+        "avfoundation".into(),
+        "-capture_cursor".into(),
+        "1".into(),
+        "-framerate".into(),
+        options.quality.frame_rate.to_string(),
+        "-i".into(),
+        format!(
+            "Capture screen 0:{}",
+            options.audio_device.as_deref().unwrap_or("none")
+        ),
+    ];
+
+    let webcam_input = options.webcam.as_ref().map(|webcam| {
+        args.extend(["-f", "avfoundation", "-framerate"].iter().map(|arg| arg.to_string()));
+        args.push(WEBCAM_FRAME_RATE.to_string());
+        args.push("-i".into());
+        args.push(format!("{}:none", webcam.device_arg()));
+        (1, webcam)
+    });
+
+    let crop = options.region.map(|region| {
+        let (x, y, width, height) = region.to_physical(options.scale_factor);
+        format!("crop={}:{}:{}:{}", width, height, x, y)
+    });
+    let scale = options.quality.scale_filter();
+
+    let mut complex: Vec<String> = Vec::new();
+    let has_webcam = webcam_input.is_some();
+    let video_map = match webcam_input {
+        Some((index, webcam)) => {
+            complex.push(format!("[0:v]{}[screen]", crop.as_deref().unwrap_or("null")));
+            complex.push(format!(
+                "[{}:v]{}[cam]",
+                index,
+                webcam.filter(options.scale_factor)
+            ));
+            // Downscaling happens after the overlay so the bubble keeps its
+            // configured size relative to the screen.
+            let scale = scale.map(|filter| format!(",{}", filter));
+            complex.push(format!(
+                "[screen][cam]overlay={}{}[vout]",
+                webcam.position.overlay_expr(options.scale_factor),
+                scale.as_deref().unwrap_or("")
+            ));
+            "[vout]".to_string()
+        }
+        None => {
+            let filters: Vec<String> = crop.into_iter().chain(scale).collect();
+            if !filters.is_empty() {
+                args.push("-vf".into());
+                args.push(filters.join(","));
+            }
+            "0:v".to_string()
+        }
+    };
+
+    // astats + ametadata log the RMS level to stderr for the level meter.
+    let level_filter =
+        "astats=metadata=1:reset=1,ametadata=print:key=lavfi.astats.Overall.RMS_level";
+    let audio_filter = options.audio_device.is_some().then_some(level_filter);
+
+    if !complex.is_empty() {
+        args.push("-filter_complex".into());
+        args.push(complex.join(";"));
+    }
+    if let Some(filter) = audio_filter {
+        args.push("-af".into());
+        args.push(filter.into());
+    }
+    // With a single input ffmpeg picks the streams itself; the camera needs
+    // explicit maps so it isn't taken as the main stream.
+    if has_webcam {
+        args.push("-map".into());
+        args.push(video_map);
+        if options.audio_device.is_some() {
+            args.push("-map".into());
+            args.push("0:a".into());
+        }
+    }
+    if options.audio_device.is_some() {
+        args.extend(["-c:a", "aac", "-b:a", "128k"].iter().map(|arg| arg.to_string()));
+    }
+
+    args.extend(
+        [
+            "-c:v",
+            "libx264",
+            "-preset",
+            "veryfast",
+            "-pix_fmt",
+            "yuv420p",
+            "-movflags",
+            "+faststart",
+        ]
+        .iter()
+        .map(|arg| arg.to_string()),
+    );
+    args.extend(options.quality.rate_control_args());
+    args.push(path_arg(&options.output_path));
+
+    args
+}
+
+/// Extracts the dBFS value from an ametadata log line; `-inf` (silence)
+/// becomes -100 so it serializes as a number.
+fn parse_audio_level(line: &str) -> Option<f64> {
+    let value = line.split(AUDIO_LEVEL_KEY).nth(1)?.trim();
+    if value.starts_with("-inf") {
+        return Some(-100.0);
+    }
+    value.parse::<f64>().ok().map(|level| level.max(-100.0))
+}
+
+/// Parses the audio section of `ffmpeg -f avfoundation -list_devices true`,
+/// whose lines look like `[AVFoundation indev @ 0x..] [0] MacBook Pro Microphone`.
+pub fn parse_audio_devices(listing: &str) -> Vec<String> {
+    parse_device_section(listing, "AVFoundation audio devices")
+}
+
+/// Parses cameras from the same listing, leaving out the screen inputs that
+/// avfoundation reports as video devices.
+pub fn parse_camera_devices(listing: &str) -> Vec<String> {
+    parse_device_section(listing, "AVFoundation video devices")
+        .into_iter()
+        .filter(|name| !name.starts_with("Capture screen"))
+        .collect()
+}
+
+fn parse_device_section(listing: &str, header: &str) -> Vec<String> {
+    let mut devices = Vec::new();
+    let mut in_section = false;
+
+    for line in listing.lines() {
+        if line.contains("AVFoundation") && line.contains("devices:") {
+            in_section = line.contains(header);
+            continue;
+        }
+        if !in_section {
+            continue;
+        }
+
+        let after_prefix = line.split("] ").skip(1).collect::<Vec<_>>().join("] ");
+        if let Some(name) = after_prefix
+            .strip_prefix('[')
+            .and_then(|rest| rest.split_once("] "))
+            .map(|(_, name)| name.trim())
+        {
+            if !name.is_empty() {
+                devices.push(name.to_string());
+            }
+        }
+    }
+
+    devices
+}
+
+fn path_arg(path: &Path) -> String {
+    path.to_string_lossy().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snipp_core::recording::{WebcamPosition, WebcamShape};
+
+    #[test]
+    fn test_recording_filename() {
+        let filename = build_recording_filename(1234567890);
+        assert!(filename.starts_with("Snipp Recording "));
+        assert!(filename.contains(" at "));
+        assert!(filename.ends_with(".mp4"));
+    }
+
+    #[test]
+    fn test_ffmpeg_args_full_screen() {
+        let options = RecordingOptions::new(PathBuf::from("/tmp/out.mp4"), None, 2.0);
+        let args = build_ffmpeg_args(&options);
+
+        assert!(args.contains(&"avfoundation".to_string()));
+        assert!(!args.contains(&"-vf".to_string()));
+        assert_eq!(args.last().map(String::as_str), Some("/tmp/out.mp4"));
+    }
+
+    #[test]
+    fn test_ffmpeg_args_crop_region_in_pixels() {
+        let region = CaptureRegion {
+            x: 100,
+            y: 50,
+            width: 640,
+            height: 480,
+        };
+        let options = RecordingOptions::new(PathBuf::from("/tmp/out.mp4"), Some(region), 2.0);
+        let args = build_ffmpeg_args(&options);
+
+        let vf_index = args.iter().position(|a| a == "-vf").expect("missing -vf");
+        assert_eq!(args[vf_index + 1], "crop=1280:960:200:100");
+    }
+
+    #[test]
+    fn test_ffmpeg_args_quality_settings() {
+        let region = CaptureRegion {
+            x: 0,
+            y: 0,
+            width: 800,
+            height: 600,
+        };
+        let mut options = RecordingOptions::new(PathBuf::from("/tmp/out.mp4"), Some(region), 1.0);
+        options.quality = RecordingQuality {
+            frame_rate: 60,
+            scale: 0.5,
+            crf: 28,
+            bitrate_kbps: 0,
+        };
+        let args = build_ffmpeg_args(&options);
+
+        let vf_index = args.iter().position(|a| a == "-vf").unwrap();
+        assert_eq!(
+            args[vf_index + 1],
+            "crop=800:600:0:0,scale=trunc(iw*0.5/2)*2:trunc(ih*0.5/2)*2"
+        );
+        let fps_index = args.iter().position(|a| a == "-framerate").unwrap();
+        assert_eq!(args[fps_index + 1], "60");
+        let crf_index = args.iter().position(|a| a == "-crf").unwrap();
+        assert_eq!(args[crf_index + 1], "28");
+
+        options.quality.bitrate_kbps = 4000;
+        let args = build_ffmpeg_args(&options);
+        assert!(!args.contains(&"-crf".to_string()));
+        assert!(args.contains(&"4000k".to_string()));
+        assert!(args.contains(&"8000k".to_string()));
+    }
+
+    #[test]
+    fn test_animation_args_gif_uses_generated_palette() {
+        let options = AnimationOptions {
+            max_colors: 64,
+            dither: false,
+            ..AnimationOptions::default()
+        };
+        let args = build_animation_args(
+            Path::new("/tmp/in.mp4"),
+            Path::new("/tmp/in.gif"),
+            AnimationFormat::Gif,
+            &options,
+        );
+
+        let fc_index = args.iter().position(|a| a == "-filter_complex").unwrap();
+        let graph = &args[fc_index + 1];
+        assert!(graph.starts_with("fps=12,scale='min(800,iw)':-2:flags=lanczos,split"));
+        assert!(graph.contains("palettegen=max_colors=64"));
+        assert!(graph.ends_with("paletteuse=dither=none"));
+        assert_eq!(args.last().map(String::as_str), Some("/tmp/in.gif"));
+    }
+
+    #[test]
+    fn test_animation_args_webp() {
+        let options = AnimationOptions {
+            fps: 15,
+            max_width: 0,
+            quality: 60,
+            ..AnimationOptions::default()
+        };
+        let args = build_animation_args(
+            Path::new("/tmp/in.mp4"),
+            Path::new("/tmp/in.webp"),
+            AnimationFormat::Webp,
+            &options,
+        );
+
+        let vf_index = args.iter().position(|a| a == "-vf").unwrap();
+        assert_eq!(args[vf_index + 1], "fps=15");
+        assert!(args.contains(&"libwebp".to_string()));
+        let q_index = args.iter().position(|a| a == "-q:v").unwrap();
+        assert_eq!(args[q_index + 1], "60");
+    }
+
+    #[test]
+    fn test_animation_options_validation() {
+        assert!(AnimationOptions::default().validate().is_ok());
+        let too_few_colors = AnimationOptions {
+            max_colors: 1,
+            ..AnimationOptions::default()
+        };
+        assert!(too_few_colors.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_trim() {
+        assert!(validate_trim(1000, 5000, 10_000).is_ok());
+        assert!(validate_trim(1000, 5000, 0).is_ok());
+        assert!(validate_trim(5000, 5000, 10_000).is_err());
+        assert!(validate_trim(12_000, 15_000, 10_000).is_err());
+    }
+
+    #[test]
+    fn test_trim_args_copy_then_reencode() {
+        let input = PathBuf::from("/tmp/in.mp4");
+        let output = trimmed_output_path(&input);
+        assert_eq!(output, PathBuf::from("/tmp/in (trimmed).mp4"));
+
+        let args = build_trim_args(&input, &output, 1500, 4000, false);
+        assert_eq!(&args[..7], ["-y", "-ss", "1.500", "-i", "/tmp/in.mp4", "-t", "2.500"]);
+        assert!(args.contains(&"copy".to_string()));
+
+        let args = build_trim_args(&input, &output, 1500, 4000, true);
+        assert!(!args.contains(&"copy".to_string()));
+        assert!(args.contains(&"libx264".to_string()));
+        assert_eq!(args.last().map(String::as_str), Some("/tmp/in (trimmed).mp4"));
+    }
+
+    #[test]
+    fn test_recording_limits() {
+        let limits = RecordingLimits {
+            max_duration_secs: 90,
+            max_file_size_mb: 10,
+        };
+        assert_eq!(limits.exceeded(Duration::from_secs(89), 0), None);
+        assert_eq!(
+            limits.exceeded(Duration::from_secs(90), 0).as_deref(),
+            Some("Recording reached the 1m 30s limit")
+        );
+        let whole_minutes = RecordingLimits {
+            max_duration_secs: 120,
+            ..limits.clone()
+        };
+        assert_eq!(
+            whole_minutes.exceeded(Duration::from_secs(120), 0).as_deref(),
+            Some("Recording reached the 2 minute limit")
+        );
+        let seconds = RecordingLimits {
+            max_duration_secs: 45,
+            ..limits.clone()
+        };
+        assert_eq!(
+            seconds.exceeded(Duration::from_secs(45), 0).as_deref(),
+            Some("Recording reached the 45 second limit")
+        );
+        assert_eq!(
+            limits.exceeded(Duration::ZERO, 10 * 1024 * 1024).as_deref(),
+            Some("Recording reached the 10 MB size limit")
+        );
+
+        let unlimited = RecordingLimits {
+            max_duration_secs: 0,
+            max_file_size_mb: 0,
+        };
+        assert_eq!(unlimited.exceeded(Duration::from_secs(86_400), u64::MAX), None);
+    }
+
+    #[test]
+    fn test_recording_quality_validation() {
+        assert!(RecordingQuality::default().validate().is_ok());
+        let invalid = [
+            RecordingQuality {
+                frame_rate: 0,
+                ..RecordingQuality::default()
+            },
+            RecordingQuality {
+                scale: 1.5,
+                ..RecordingQuality::default()
+            },
+            RecordingQuality {
+                crf: 60,
+                ..RecordingQuality::default()
+            },
+        ];
+        for quality in invalid {
+            assert!(quality.validate().is_err());
+        }
+    }
+
+    #[test]
+    fn test_clicks_in_video() {
+        let region = CaptureRegion {
+            x: 100,
+            y: 50,
+            width: 200,
+            height: 100,
+        };
+        let mut options = RecordingOptions::new(PathBuf::from("/tmp/out.mp4"), Some(region), 2.0);
+        options.quality.scale = 0.5;
+        let clicks = vec![
+            Click { at_ms: 10, x: 150.0, y: 60.0 },
+            Click { at_ms: 20, x: 350.0, y: 60.0 },
+        ];
+        assert_eq!(options.clicks_in_video(clicks.clone()), vec![Click { at_ms: 10, x: 50.0, y: 10.0 }]);
+
+        options.region = None;
+        assert_eq!(options.clicks_in_video(clicks).len(), 2);
+    }
+
+    #[test]
+    fn test_highlight_args_loop_sprites_and_copy_audio() {
+        let sprites = [PathBuf::from("/tmp/a.ripple0.png"), PathBuf::from("/tmp/a.ripple1.png")];
+        let args = build_highlight_args(
+            Path::new("/tmp/a.mp4"),
+            &sprites,
+            "[0:v]null[vout]",
+            Path::new("/tmp/a.clicks.mp4"),
+            &RecordingQuality::default(),
+        );
+
+        assert_eq!(args[..3], ["-y", "-i", "/tmp/a.mp4"]);
+        assert_eq!(args[3..7], ["-loop", "1", "-i", "/tmp/a.ripple0.png"]);
+        assert!(args.windows(2).any(|pair| pair == ["-map", "0:a?"]));
+        assert!(args.windows(2).any(|pair| pair == ["-c:a", "copy"]));
+        assert_eq!(args.last().unwrap(), "/tmp/a.clicks.mp4");
+        assert!(!build_ffmpeg_args(&RecordingOptions::new(PathBuf::from("/tmp/out.mp4"), None, 1.0))
+            .contains(&"-capture_mouse_clicks".to_string()));
+    }
+
+    #[test]
+    fn test_ffmpeg_args_with_microphone() {
+        let mut options = RecordingOptions::new(PathBuf::from("/tmp/out.mp4"), None, 1.0);
+        options.audio_device = Some("MacBook Pro Microphone".to_string());
+        let args = build_ffmpeg_args(&options);
+
+        assert!(args.contains(&"Capture screen 0:MacBook Pro Microphone".to_string()));
+        assert!(args.contains(&"-af".to_string()));
+        assert!(args.contains(&"aac".to_string()));
+    }
+
+    #[test]
+    fn test_mix_args_add_or_mix_system_audio() {
+        let video = PathBuf::from("/tmp/seg.mp4");
+        let audio = system_audio_path(&video);
+        assert_eq!(audio, PathBuf::from("/tmp/seg.wav"));
+        let output = PathBuf::from("/tmp/seg.mixed.mp4");
+
+        let args = build_mix_args(&video, &audio, &output, false);
+        assert!(!args.contains(&"-filter_complex".to_string()));
+        assert!(args.contains(&"1:a".to_string()));
+        let copy_index = args.iter().position(|a| a == "-c:v").unwrap();
+        assert_eq!(args[copy_index + 1], "copy");
+
+        let args = build_mix_args(&video, &audio, &output, true);
+        let fc_index = args.iter().position(|a| a == "-filter_complex").expect("missing mix");
+        assert!(args[fc_index + 1].starts_with("[0:a][1:a]amix=inputs=2"));
+        assert!(args.contains(&"[aout]".to_string()));
+        assert_eq!(args.last().map(String::as_str), Some("/tmp/seg.mixed.mp4"));
+    }
+
+    #[test]
+    fn test_parse_audio_level() {
+        assert_eq!(
+            parse_audio_level("[Parsed_ametadata_1 @ 0x1] lavfi.astats.Overall.RMS_level=-23.5"),
+            Some(-23.5)
+        );
+        assert_eq!(parse_audio_level("lavfi.astats.Overall.RMS_level=-inf"), Some(-100.0));
+        assert_eq!(parse_audio_level("frame=  10 fps=30"), None);
+    }
+
+    #[test]
+    fn test_parse_audio_devices() {
+        let listing = "\
+[AVFoundation indev @ 0x7f] AVFoundation video devices:
+[AVFoundation indev @ 0x7f] [0] FaceTime HD Camera
+[AVFoundation indev @ 0x7f] [1] Capture screen 0
+[AVFoundation indev @ 0x7f] AVFoundation audio devices:
+[AVFoundation indev @ 0x7f] [0] MacBook Pro Microphone
+[AVFoundation indev @ 0x7f] [1] External [USB] Mic
+: Input/output error";
+
+        assert_eq!(
+            parse_audio_devices(listing),
+            vec!["MacBook Pro Microphone".to_string(), "External [USB] Mic".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_parse_camera_devices_skips_screens() {
+        let listing = "\
+[AVFoundation indev @ 0x7f] AVFoundation video devices:
+[AVFoundation indev @ 0x7f] [0] FaceTime HD Camera
+[AVFoundation indev @ 0x7f] [1] Capture screen 0
+[AVFoundation indev @ 0x7f] AVFoundation audio devices:
+[AVFoundation indev @ 0x7f] [0] MacBook Pro Microphone";
+
+        assert_eq!(parse_camera_devices(listing), vec!["FaceTime HD Camera".to_string()]);
+    }
+
+    #[test]
+    fn test_ffmpeg_args_with_webcam_overlay() {
+        let region = CaptureRegion {
+            x: 0,
+            y: 0,
+            width: 800,
+            height: 600,
+        };
+        let mut options = RecordingOptions::new(PathBuf::from("/tmp/out.mp4"), Some(region), 2.0);
+        options.webcam = Some(WebcamOverlay {
+            enabled: true,
+            ..WebcamOverlay::default()
+        });
+        let args = build_ffmpeg_args(&options);
+
+        assert!(args.contains(&"default:none".to_string()));
+        assert!(!args.contains(&"-vf".to_string()));
+        let fc_index = args.iter().position(|a| a == "-filter_complex").expect("missing overlay");
+        let graph = &args[fc_index + 1];
+        assert!(graph.starts_with("[0:v]crop=1600:1200:0:0[screen];[1:v]scale=400:400"));
+        assert!(graph.contains("geq="));
+        assert!(graph.ends_with("[screen][cam]overlay=main_w-overlay_w-48:main_h-overlay_h-48[vout]"));
+        assert!(args.contains(&"[vout]".to_string()));
+    }
+
+    #[test]
+    fn test_ffmpeg_args_webcam_with_microphone() {
+        let mut options = RecordingOptions::new(PathBuf::from("/tmp/out.mp4"), None, 1.0);
+        options.audio_device = Some("default".to_string());
+        options.system_audio = true;
+        options.webcam = Some(WebcamOverlay {
+            enabled: true,
+            device: "FaceTime HD Camera".to_string(),
+            position: WebcamPosition::TopLeft,
+            shape: WebcamShape::Square,
+            size: 100,
+        });
+        let args = build_ffmpeg_args(&options);
+
+        let fc_index = args.iter().position(|a| a == "-filter_complex").unwrap();
+        let graph = &args[fc_index + 1];
+        assert!(graph.starts_with("[0:v]null[screen];[1:v]scale=100:100"));
+        assert!(!graph.contains("geq="));
+        assert!(graph.contains("overlay=24:24[vout]"));
+        assert!(!graph.contains("amix"));
+        assert_eq!(args.iter().filter(|a| *a == "-filter_complex").count(), 1);
+        assert!(args.windows(2).any(|pair| pair == ["-map", "0:a"]));
+    }
+
+    #[test]
+    fn test_find_ffmpeg_rejects_missing_configured_path() {
+        assert!(find_ffmpeg("/definitely/not/here/ffmpeg").is_none());
+    }
+
+    #[test]
+    fn test_manager_status_when_idle() {
+        let manager = RecordingManager::new();
+        let status = manager.status();
+        assert!(!status.active);
+        assert!(!status.paused);
+        assert!(status.id.is_none());
+        assert!(!manager.is_recording());
+    }
+
+    #[test]
+    fn test_settle_pause_waits_for_the_segment() {
+        let mut recording = ActiveRecording {
+            id: 1,
+            output_path: PathBuf::from("/tmp/out.mp4"),
+            ffmpeg: PathBuf::from("ffmpeg"),
+            template: RecordingOptions::new(PathBuf::from("/tmp/out.mp4"), None, 1.0),
+            started_at: Instant::now(),
+            segments: Vec::new(),
+            recorded: Duration::from_secs(2),
+            current: None,
+            pausing: None,
+        };
+        let (done, pausing) = watch::channel(None);
+        recording.pausing = Some(pausing);
+
+        assert!(!recording.settle_pause());
+        done.send(Some(Duration::from_secs(3))).unwrap();
+        assert!(recording.settle_pause());
+        assert_eq!(recording.recorded(), Duration::from_secs(5));
+        assert!(recording.pausing.is_none());
+    }
+
+    #[test]
+    fn test_concat_list_escapes_quotes() {
+        let segments = vec![
+            PathBuf::from("/tmp/a.mp4"),
+            PathBuf::from("/tmp/it's.mp4"),
+        ];
+        assert_eq!(
+            build_concat_list(&segments),
+            "file '/tmp/a.mp4'\nfile '/tmp/it'\\''s.mp4'\n"
+        );
+    }
+
+    #[test]
+    fn test_segment_paths_are_distinct_per_index() {
+        assert_ne!(segment_path(1, 0), segment_path(1, 1));
+        assert!(segment_path(1, 0).to_string_lossy().ends_with("snipp_recording_1_0.mp4"));
+    }
+}
@@ -0,0 +1,163 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use snipp_core::naming;
+use snipp_core::output::OutputFormat;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Extensions Snipp writes for recordings and animated captures; still
+/// captures use any `OutputFormat`.
+const MEDIA_EXTENSIONS: [&str; 2] = ["mp4", "gif"];
+
+/// A saved file that is older than the retention period.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExpiredFile {
+    pub path: String,
+    pub size_bytes: u64,
+    pub modified: DateTime<Utc>,
+}
+
+/// Only files named the way Snipp names them, either by the filename
+/// `template` or the built-in "Snipp ..." names, are ever considered, so
+/// other files sharing the save folder are left alone.
+pub fn is_snipp_file(path: &Path, template: &str) -> bool {
+    let name_matches = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with("Snipp ") || naming::matches_template(template, name));
+    let ext_matches = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            OutputFormat::from_extension(ext).is_some()
+                || MEDIA_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str())
+        });
+    name_matches && ext_matches
+}
+
+/// Lists Snipp files directly inside `dir` last modified more than `days`
+/// days before `now`, oldest first. Subfolders are not scanned.
+pub fn find_expired(dir: &Path, template: &str, days: u32, now: SystemTime) -> Result<Vec<ExpiredFile>, String> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let cutoff = now
+        .checked_sub(Duration::from_secs(days as u64 * 24 * 60 * 60))
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+
+    let entries = std::fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+
+    let mut expired: Vec<ExpiredFile> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| is_snipp_file(&entry.path(), template))
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            let modified = metadata.modified().ok()?;
+            (metadata.is_file() && modified < cutoff).then(|| ExpiredFile {
+                path: entry.path().to_string_lossy().to_string(),
+                size_bytes: metadata.len(),
+                modified: modified.into(),
+            })
+        })
+        .collect();
+
+    expired.sort_by_key(|file| file.modified);
+    Ok(expired)
+}
+
+/// `find_expired` across several save folders, oldest first.
+pub fn find_expired_in(
+    dirs: &[String],
+    template: &str,
+    days: u32,
+    now: SystemTime,
+) -> Result<Vec<ExpiredFile>, String> {
+    let mut expired = Vec::new();
+    for dir in dirs {
+        expired.extend(find_expired(Path::new(dir), template, days, now)?);
+    }
+    expired.sort_by_key(|file| file.modified);
+    Ok(expired)
+}
+
+/// Moves the files to the Trash so an over-eager policy can be undone.
+/// Returns the paths that were removed.
+pub fn move_to_trash(files: &[ExpiredFile]) -> Result<Vec<String>, String> {
+    let paths: Vec<PathBuf> = files
+        .iter()
+        .map(|file| PathBuf::from(&file.path))
+        .filter(|path| path.exists())
+        .collect();
+    if paths.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // Metadata and annotation sidecars go with their image.
+    let sidecars: Vec<PathBuf> = paths
+        .iter()
+        .flat_map(|path| crate::sidecar::existing_sidecars(path))
+        .collect();
+    trash::delete_all(paths.iter().chain(&sidecars))
+        .map_err(|e| format!("Failed to move files to Trash: {}", e))?;
+    Ok(paths
+        .iter()
+        .map(|path| path.to_string_lossy().to_string())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_snipp_file_matches_snipp_names_only() {
+        let template = naming::DEFAULT_FILENAME_TEMPLATE;
+        assert!(is_snipp_file(Path::new("/s/Snipp 24-01-01 at 10.00.00.png"), template));
+        assert!(is_snipp_file(Path::new("/s/Snipp 24-01-01 at 10.00.00.heic"), template));
+        assert!(is_snipp_file(Path::new("/s/Snipp Recording 24-01-01 at 10.00.00.mp4"), template));
+        assert!(!is_snipp_file(Path::new("/s/Screenshot 2024-01-01.png"), template));
+        assert!(!is_snipp_file(Path::new("/s/Snipp notes.txt"), template));
+    }
+
+    #[test]
+    fn test_find_expired_uses_custom_template_and_formats() {
+        let dir = tempfile::tempdir().unwrap();
+        let template = "shot_%Y%m%d_{seq}";
+        std::fs::write(dir.path().join("shot_20240501_003.jpg"), b"x").unwrap();
+        std::fs::write(dir.path().join("shot_20240501_004-1.avif"), b"x").unwrap();
+        std::fs::write(dir.path().join("shot_list.jpg"), b"x").unwrap();
+        std::fs::write(dir.path().join("holiday.jpg"), b"x").unwrap();
+
+        let later = SystemTime::now() + Duration::from_secs(3 * 24 * 60 * 60);
+        let mut names: Vec<String> = find_expired(dir.path(), template, 2, later)
+            .unwrap()
+            .into_iter()
+            .map(|file| Path::new(&file.path).file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        assert_eq!(names, ["shot_20240501_003.jpg", "shot_20240501_004-1.avif"]);
+    }
+
+    #[test]
+    fn test_find_expired_respects_cutoff() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Snipp old.png"), b"x").unwrap();
+        std::fs::write(dir.path().join("keep me.png"), b"x").unwrap();
+
+        let now = SystemTime::now();
+        let later = now + Duration::from_secs(3 * 24 * 60 * 60);
+
+        assert!(find_expired(dir.path(), naming::DEFAULT_FILENAME_TEMPLATE, 7, later).unwrap().is_empty());
+        let expired = find_expired(dir.path(), naming::DEFAULT_FILENAME_TEMPLATE, 2, later).unwrap();
+        assert_eq!(expired.len(), 1);
+        assert!(expired[0].path.ends_with("Snipp old.png"));
+        assert_eq!(expired[0].size_bytes, 1);
+    }
+
+    #[test]
+    fn test_find_expired_missing_dir_is_empty() {
+        let missing = Path::new("/definitely/not/a/snipp/folder");
+        assert!(find_expired(missing, naming::DEFAULT_FILENAME_TEMPLATE, 1, SystemTime::now()).unwrap().is_empty());
+    }
+}
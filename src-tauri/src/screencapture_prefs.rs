@@ -0,0 +1,79 @@
+use serde::Serialize;
+use std::path::PathBuf;
+use tauri::AppHandle;
+use tauri_plugin_shell::ShellExt;
+
+/// Reads `key` from the macOS screenshot preferences
+/// (`com.apple.screencapture`); `None` when it isn't set.
+pub async fn read(app_handle: &AppHandle, key: &str) -> Option<String> {
+    let output = app_handle
+        .shell()
+        .command("defaults")
+        .args(["read", "com.apple.screencapture", key])
+        .output()
+        .await
+        .ok()?;
+    let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !value.is_empty()).then_some(value)
+}
+
+/// Where macOS saves Cmd+Shift+3 screenshots: the chosen location, or the
+/// Desktop.
+pub async fn screenshot_folder(app_handle: &AppHandle) -> Option<PathBuf> {
+    match read(app_handle, "location").await {
+        Some(location) => Some(expand_home(&location)),
+        None => dirs::desktop_dir(),
+    }
+}
+
+/// The macOS screenshot settings the user changed from their defaults,
+/// offered for adoption during onboarding.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ScreencapturePrefs {
+    pub location: Option<String>,
+    /// File type, e.g. "jpg".
+    pub format: Option<String>,
+    pub window_shadow: Option<bool>,
+}
+
+pub async fn load(app_handle: &AppHandle) -> ScreencapturePrefs {
+    ScreencapturePrefs {
+        location: read(app_handle, "location")
+            .await
+            .map(|location| expand_home(&location).to_string_lossy().to_string()),
+        format: read(app_handle, "type").await.map(|format| format.to_ascii_lowercase()),
+        window_shadow: read(app_handle, "disable-shadow").await.map(|disabled| !parse_bool(&disabled)),
+    }
+}
+
+/// `defaults` prints booleans as 1/0, but strings written by hand also count.
+fn parse_bool(value: &str) -> bool {
+    matches!(value.to_ascii_lowercase().as_str(), "1" | "true" | "yes")
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_home() {
+        let home = dirs::home_dir().unwrap();
+        assert_eq!(expand_home("~/Pictures/Screenshots"), home.join("Pictures/Screenshots"));
+        assert_eq!(expand_home("/Volumes/Shots"), PathBuf::from("/Volumes/Shots"));
+    }
+
+    #[test]
+    fn test_parse_bool() {
+        assert!(parse_bool("1"));
+        assert!(parse_bool("YES"));
+        assert!(!parse_bool("0"));
+        assert!(!parse_bool("false"));
+    }
+}
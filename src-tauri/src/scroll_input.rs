@@ -0,0 +1,61 @@
+#[cfg(target_os = "macos")]
+mod ffi {
+    use std::ffi::c_void;
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    pub struct CGPoint {
+        pub x: f64,
+        pub y: f64,
+    }
+
+    pub const SCROLL_UNIT_PIXEL: u32 = 0;
+    pub const HID_EVENT_TAP: u32 = 0;
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        pub fn CGWarpMouseCursorPosition(point: CGPoint) -> i32;
+        pub fn CGEventCreateScrollWheelEvent(
+            source: *const c_void,
+            units: u32,
+            wheel_count: u32,
+            wheel1: i32,
+            ...
+        ) -> *const c_void;
+        pub fn CGEventPost(tap: u32, event: *const c_void);
+    }
+
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        pub fn CFRelease(object: *const c_void);
+    }
+}
+
+/// Moves the pointer to `(x, y)` in global screen points and scrolls
+/// whatever is under it down by `points`. Needs the Accessibility
+/// permission; without it macOS drops the event.
+#[cfg(target_os = "macos")]
+pub fn scroll_down(x: f64, y: f64, points: i32) -> Result<(), String> {
+    if !crate::permissions::is_granted(crate::permissions::PermissionKind::Accessibility) {
+        return Err("Scrolling capture needs the Accessibility permission".to_string());
+    }
+    // SAFETY: the event is created, posted and released here; a null event
+    // means creation failed and is never posted.
+    unsafe {
+        if ffi::CGWarpMouseCursorPosition(ffi::CGPoint { x, y }) != 0 {
+            return Err("Failed to move the pointer".to_string());
+        }
+        let event = ffi::CGEventCreateScrollWheelEvent(std::ptr::null(), ffi::SCROLL_UNIT_PIXEL, 1, -points);
+        if event.is_null() {
+            return Err("Failed to create a scroll event".to_string());
+        }
+        ffi::CGEventPost(ffi::HID_EVENT_TAP, event);
+        ffi::CFRelease(event);
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn scroll_down(_x: f64, _y: f64, _points: i32) -> Result<(), String> {
+    Err("Scrolling capture is only available on macOS".to_string())
+}
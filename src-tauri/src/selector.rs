@@ -0,0 +1,180 @@
+use base64::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Listener, Manager, WebviewUrl, WebviewWindowBuilder, WindowEvent};
+
+use crate::region::CaptureRegion;
+use crate::window_list::WindowInfo;
+
+/// The frozen screen image handed to the selector window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectorFrame {
+    pub base64_image: String,
+    /// The frame is in physical pixels while selections come back in points.
+    pub scale_factor: f64,
+    /// On-screen windows, front to back, offered as snap and click targets.
+    pub windows: Vec<WindowInfo>,
+}
+
+/// Payload of `region-selected`; `None` means the user cancelled.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegionSelection {
+    pub region: Option<CaptureRegion>,
+}
+
+/// Shows the frozen frame in a borderless overlay and waits for the user to
+/// drag out a region. Resolves to `None` on Escape or if the window closes.
+pub async fn select_region(
+    app_handle: &AppHandle,
+    frame_png: &[u8],
+    scale_factor: f64,
+    windows: Vec<WindowInfo>,
+) -> Result<Option<CaptureRegion>, String> {
+    if let Some(existing) = app_handle.get_webview_window("selector") {
+        let _ = existing.close();
+    }
+
+    let (width, height) = app_handle
+        .primary_monitor()
+        .ok()
+        .flatten()
+        .map(|monitor| {
+            let size = monitor.size();
+            (
+                size.width as f64 / monitor.scale_factor(),
+                size.height as f64 / monitor.scale_factor(),
+            )
+        })
+        .unwrap_or((1440.0, 900.0));
+
+    let window = WebviewWindowBuilder::new(
+        app_handle,
+        "selector",
+        WebviewUrl::App("selector.html".into()),
+    )
+    .title("Select Region")
+    .position(0.0, 0.0)
+    .inner_size(width, height)
+    .decorations(false)
+    .always_on_top(true)
+    .skip_taskbar(true)
+    .resizable(false)
+    .minimizable(false)
+    .maximizable(false)
+    .visible(false)
+    .build()
+    .map_err(|e| format!("Failed to create selector window: {}", e))?;
+
+    crate::wait_for_window_ready(&window, "selector-ready").await;
+
+    let (selected_tx, selected_rx) = tokio::sync::oneshot::channel::<Option<CaptureRegion>>();
+    let selected_tx = Arc::new(Mutex::new(Some(selected_tx)));
+
+    let event_tx = selected_tx.clone();
+    window.once("region-selected", move |event| {
+        let region = serde_json::from_str::<RegionSelection>(event.payload())
+            .ok()
+            .and_then(|selection| selection.region);
+        if let Some(tx) = event_tx.lock().unwrap().take() {
+            let _ = tx.send(region);
+        }
+    });
+    let closed_tx = selected_tx.clone();
+    window.on_window_event(move |event| {
+        if let WindowEvent::Destroyed = event {
+            if let Some(tx) = closed_tx.lock().unwrap().take() {
+                let _ = tx.send(None);
+            }
+        }
+    });
+
+    let frame = SelectorFrame {
+        base64_image: BASE64_STANDARD.encode(frame_png),
+        scale_factor,
+        windows,
+    };
+    window
+        .emit("selector-frame", &frame)
+        .map_err(|e| format!("Failed to send frame to selector: {}", e))?;
+    window
+        .show()
+        .map_err(|e| format!("Failed to show selector: {}", e))?;
+    let _ = window.set_focus();
+
+    let region = selected_rx.await.unwrap_or(None);
+    let _ = window.close();
+    Ok(region)
+}
+
+/// Crops the frozen frame to a selection given in points.
+pub fn crop_frame(
+    frame_png: &[u8],
+    region: CaptureRegion,
+    scale_factor: f64,
+) -> Result<Vec<u8>, String> {
+    let frame = image::load_from_memory(frame_png)
+        .map_err(|e| format!("Failed to decode frozen frame: {}", e))?;
+    let (x, y, width, height) = region
+        .to_pixels(scale_factor, frame.width(), frame.height())
+        .ok_or("Selection is outside the captured screen")?;
+
+    let mut png_bytes = Vec::new();
+    frame
+        .crop_imm(x, y, width, height)
+        .write_to(
+            &mut std::io::Cursor::new(&mut png_bytes),
+            image::ImageOutputFormat::Png,
+        )
+        .map_err(|e| format!("Failed to encode selection: {}", e))?;
+
+    Ok(png_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_frame(width: u32, height: u32) -> Vec<u8> {
+        let image = image::RgbaImage::from_fn(width, height, |x, _| {
+            image::Rgba([(x * 10) as u8, 0, 0, 255])
+        });
+        let mut png_bytes = Vec::new();
+        image::DynamicImage::ImageRgba8(image)
+            .write_to(
+                &mut std::io::Cursor::new(&mut png_bytes),
+                image::ImageOutputFormat::Png,
+            )
+            .unwrap();
+        png_bytes
+    }
+
+    #[test]
+    fn test_crop_frame_scales_points_to_pixels() {
+        let frame = sample_frame(20, 20);
+        let region = CaptureRegion {
+            x: 2,
+            y: 1,
+            width: 3,
+            height: 4,
+        };
+
+        let cropped = crop_frame(&frame, region, 2.0).unwrap();
+        let cropped = image::load_from_memory(&cropped).unwrap().to_rgba8();
+
+        assert_eq!(cropped.dimensions(), (6, 8));
+        assert_eq!(cropped.get_pixel(0, 0)[0], 40);
+    }
+
+    #[test]
+    fn test_crop_frame_rejects_offscreen_selection() {
+        let frame = sample_frame(10, 10);
+        let region = CaptureRegion {
+            x: 50,
+            y: 50,
+            width: 10,
+            height: 10,
+        };
+
+        assert!(crop_frame(&frame, region, 1.0).is_err());
+    }
+}
@@ -0,0 +1,229 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::annotate::Shape;
+use crate::cloud_sync;
+use crate::region::CaptureRegion;
+
+/// Bumped when the annotation sidecar changes in a way older builds can't read.
+const ANNOTATIONS_VERSION: u32 = 1;
+
+/// Metadata written as `name.png.json` next to a saved capture so other
+/// tools can index Snipp output without decoding images.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Sidecar {
+    pub timestamp: DateTime<Local>,
+    pub width: u32,
+    pub height: u32,
+    /// Frontmost app when the capture was taken, when known.
+    pub source_app: Option<String>,
+    pub note: String,
+    /// Recognized text; filled in once text recognition finishes.
+    pub text: String,
+    /// What was done to the pixels, e.g. "edited", "stamp".
+    pub operations: Vec<String>,
+    #[serde(default, flatten)]
+    pub context: CaptureContext,
+}
+
+/// Where a capture was taken, as far as Snipp could tell; what's known
+/// depends on the capture mode and the permissions granted.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CaptureContext {
+    /// Selected area in global screen points; absent for full-screen and
+    /// window captures.
+    pub region: Option<CaptureRegion>,
+    /// `screencapture -D` number of the display.
+    pub display_id: Option<u32>,
+    pub display_name: Option<String>,
+    /// Of the frontmost app, e.g. `com.apple.Safari`.
+    pub bundle_id: Option<String>,
+    pub window_title: Option<String>,
+}
+
+/// Editor shapes written as `name.snipp.json` next to a saved capture, in
+/// its pixels, so reopening it in the editor brings them back editable.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnnotationSidecar {
+    pub version: u32,
+    pub shapes: Vec<Shape>,
+}
+
+pub fn sidecar_path(image: &Path) -> PathBuf {
+    let mut name = image.as_os_str().to_os_string();
+    name.push(".json");
+    PathBuf::from(name)
+}
+
+pub fn annotations_path(image: &Path) -> PathBuf {
+    image.with_extension("snipp.json")
+}
+
+/// The sidecars of `image` that exist, which move and delete with it.
+pub fn existing_sidecars(image: &Path) -> Vec<PathBuf> {
+    [sidecar_path(image), annotations_path(image)]
+        .into_iter()
+        .filter(|path| path.exists())
+        .collect()
+}
+
+/// Width and height from the PNG header, without decoding the pixels.
+pub fn png_dimensions(png: &[u8]) -> Result<(u32, u32), String> {
+    image::io::Reader::with_format(std::io::Cursor::new(png), image::ImageFormat::Png)
+        .into_dimensions()
+        .map_err(|e| format!("Failed to read image size: {}", e))
+}
+
+pub fn write_sidecar(image: &Path, sidecar: &Sidecar) -> Result<(), String> {
+    let json = serde_json::to_vec_pretty(sidecar).map_err(|e| format!("Failed to serialize sidecar: {}", e))?;
+    cloud_sync::write_file(&sidecar_path(image), &json)
+        .map(|_| ())
+        .map_err(|e| format!("Failed to write sidecar: {}", e))
+}
+
+/// Saves the editable shapes of `image`; no shapes removes the file.
+pub fn write_annotations(image: &Path, shapes: &[Shape]) -> Result<(), String> {
+    let path = annotations_path(image);
+    if shapes.is_empty() {
+        return match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(format!("Failed to remove annotations: {}", e))
+            }
+            _ => Ok(()),
+        };
+    }
+    let sidecar = AnnotationSidecar {
+        version: ANNOTATIONS_VERSION,
+        shapes: shapes.to_vec(),
+    };
+    let json = serde_json::to_vec_pretty(&sidecar).map_err(|e| format!("Failed to serialize annotations: {}", e))?;
+    cloud_sync::write_file(&path, &json)
+        .map(|_| ())
+        .map_err(|e| format!("Failed to write annotations: {}", e))
+}
+
+/// The editable shapes saved for `image`, or none if it has no sidecar.
+pub fn read_annotations(image: &Path) -> Result<Vec<Shape>, String> {
+    let Ok(contents) = std::fs::read(annotations_path(image)) else {
+        return Ok(Vec::new());
+    };
+    let sidecar: AnnotationSidecar =
+        serde_json::from_slice(&contents).map_err(|e| format!("Failed to read annotations: {}", e))?;
+    if sidecar.version > ANNOTATIONS_VERSION {
+        return Err("These annotations were saved by a newer version of Snipp".to_string());
+    }
+    Ok(sidecar.shapes)
+}
+
+/// The metadata sidecar of `image`, or `None` if it was saved without one.
+pub fn read_sidecar(image: &Path) -> Result<Option<Sidecar>, String> {
+    let Ok(contents) = std::fs::read(sidecar_path(image)) else {
+        return Ok(None);
+    };
+    serde_json::from_slice(&contents)
+        .map(Some)
+        .map_err(|e| format!("Failed to read sidecar: {}", e))
+}
+
+/// Fills in the recognized text of an existing sidecar; captures saved
+/// without one are left alone.
+pub fn update_text(image: &Path, text: &str) -> Result<(), String> {
+    let path = sidecar_path(image);
+    let Ok(contents) = std::fs::read(&path) else {
+        return Ok(());
+    };
+    let mut sidecar: Sidecar =
+        serde_json::from_slice(&contents).map_err(|e| format!("Failed to read sidecar: {}", e))?;
+    sidecar.text = text.trim().to_string();
+    write_sidecar(image, &sidecar)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_sidecar_sits_next_to_image_and_takes_text_later() {
+        let dir = tempfile::tempdir().unwrap();
+        let image = dir.path().join("Snipp 24-05-01 at 10.00.00.png");
+        assert_eq!(
+            sidecar_path(&image),
+            dir.path().join("Snipp 24-05-01 at 10.00.00.png.json")
+        );
+
+        let sidecar = Sidecar {
+            timestamp: Local.with_ymd_and_hms(2024, 5, 1, 10, 0, 0).unwrap(),
+            width: 800,
+            height: 600,
+            source_app: Some("Safari".to_string()),
+            note: String::new(),
+            text: String::new(),
+            operations: vec!["stamp".to_string()],
+            context: CaptureContext {
+                region: Some(CaptureRegion { x: 10, y: 20, width: 300, height: 200 }),
+                bundle_id: Some("com.apple.Safari".to_string()),
+                ..CaptureContext::default()
+            },
+        };
+        write_sidecar(&image, &sidecar).unwrap();
+        update_text(&image, " Total: $42 ").unwrap();
+
+        let saved = read_sidecar(&image).unwrap().unwrap();
+        assert_eq!(saved.text, "Total: $42");
+        assert_eq!(saved.source_app.as_deref(), Some("Safari"));
+        assert_eq!(saved.context, sidecar.context);
+        assert!(read_sidecar(&dir.path().join("other.png")).unwrap().is_none());
+
+        update_text(&dir.path().join("other.png"), "x").unwrap();
+        assert!(!sidecar_path(&dir.path().join("other.png")).exists());
+    }
+
+    #[test]
+    fn test_sidecar_without_context_still_reads() {
+        let json = r#"{"timestamp":"2024-05-01T10:00:00+00:00","width":1,"height":1,
+            "source_app":null,"note":"","text":"","operations":[]}"#;
+
+        let sidecar: Sidecar = serde_json::from_str(json).unwrap();
+
+        assert_eq!(sidecar.context, CaptureContext::default());
+    }
+
+    #[test]
+    fn test_annotations_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let image = dir.path().join("Snipp 24-05-01 at 10.00.00.png");
+        std::fs::write(&image, b"png").unwrap();
+        assert_eq!(
+            annotations_path(&image),
+            dir.path().join("Snipp 24-05-01 at 10.00.00.snipp.json")
+        );
+        assert!(read_annotations(&image).unwrap().is_empty());
+
+        let shapes = vec![Shape::Line {
+            from: [1.0, 2.0],
+            to: [30.0, 40.0],
+            color: "#ff0000".to_string(),
+            stroke_width: 3.0,
+            shadow: true,
+        }];
+        write_annotations(&image, &shapes).unwrap();
+
+        assert_eq!(read_annotations(&image).unwrap(), shapes);
+        assert_eq!(existing_sidecars(&image), vec![annotations_path(&image)]);
+        write_annotations(&image, &[]).unwrap();
+        assert!(!annotations_path(&image).exists());
+    }
+
+    #[test]
+    fn test_png_dimensions() {
+        let mut png = Vec::new();
+        image::DynamicImage::ImageRgba8(image::RgbaImage::new(7, 3))
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageOutputFormat::Png)
+            .unwrap();
+
+        assert_eq!(png_dimensions(&png).unwrap(), (7, 3));
+    }
+}
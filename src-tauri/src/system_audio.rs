@@ -0,0 +1,192 @@
+use snipp_core::audio::WavWriter;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tauri::AppHandle;
+
+/// ScreenCaptureKit's native format; asking for it avoids resampling.
+#[cfg(target_os = "macos")]
+const SAMPLE_RATE: u32 = 48_000;
+#[cfg(target_os = "macos")]
+const CHANNELS: u16 = 2;
+
+type SharedWriter = Arc<Mutex<Option<WavWriter<BufWriter<File>>>>>;
+
+/// Records what the Mac is playing, without a loopback driver, into a WAV
+/// that is mixed into the segment once ffmpeg has finished it.
+pub struct SystemAudioCapture {
+    path: PathBuf,
+    writer: SharedWriter,
+    #[cfg(target_os = "macos")]
+    stream: screencapturekit::sc_stream::SCStream,
+}
+
+#[cfg(target_os = "macos")]
+fn open_writer(path: &std::path::Path) -> Result<SharedWriter, String> {
+    let file = File::create(path).map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+    let writer = WavWriter::new(BufWriter::new(file), SAMPLE_RATE, CHANNELS)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(Arc::new(Mutex::new(Some(writer))))
+}
+
+#[cfg(target_os = "macos")]
+mod sck {
+    use super::SharedWriter;
+    use crate::recording::AudioLevel;
+    use screencapturekit::cm_sample_buffer::CMSampleBuffer;
+    use screencapturekit::sc_error_handler::StreamErrorHandler;
+    use screencapturekit::sc_output_handler::{SCStreamOutputType, StreamOutput};
+    use std::sync::Mutex;
+    use std::time::Instant;
+    use tauri::{AppHandle, Emitter};
+
+    pub struct AudioOutput {
+        pub writer: SharedWriter,
+        /// Set when the microphone isn't recorded, so the level meter
+        /// follows system audio instead of staying silent.
+        pub level: Option<(AppHandle, u64)>,
+        pub last_level_at: Mutex<Option<Instant>>,
+    }
+
+    impl StreamOutput for AudioOutput {
+        fn did_output_sample_buffer(&self, sample: CMSampleBuffer, of_type: SCStreamOutputType) {
+            if !matches!(of_type, SCStreamOutputType::Audio) {
+                return;
+            }
+            let planes: Vec<Vec<f32>> = sample
+                .sys_ref
+                .get_av_audio_buffer_list()
+                .into_iter()
+                .map(|buffer| {
+                    buffer
+                        .data
+                        .chunks_exact(4)
+                        .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+                        .collect()
+                })
+                .collect();
+            if planes.is_empty() {
+                return;
+            }
+
+            if let Some(writer) = self.writer.lock().unwrap().as_mut() {
+                if let Err(e) = writer.write_planes(&planes) {
+                    tracing::error!("Failed to write system audio: {}", e);
+                }
+            }
+
+            if let Some((app_handle, recording_id)) = &self.level {
+                let mut last_level_at = self.last_level_at.lock().unwrap();
+                let due = last_level_at
+                    .map(|at| at.elapsed() >= crate::recording::AUDIO_LEVEL_INTERVAL)
+                    .unwrap_or(true);
+                if due {
+                    *last_level_at = Some(Instant::now());
+                    let level_db = snipp_core::audio::rms_db(&planes[0]);
+                    let _ = app_handle.emit(
+                        "recording-audio-level",
+                        AudioLevel {
+                            recording_id: *recording_id,
+                            level_db,
+                        },
+                    );
+                }
+            }
+        }
+    }
+
+    pub struct ErrorHandler;
+
+    impl StreamErrorHandler for ErrorHandler {
+        fn on_error(&self) {
+            tracing::error!("ScreenCaptureKit stopped delivering system audio");
+        }
+    }
+}
+
+impl SystemAudioCapture {
+    /// Starts capturing into `path`. `level_for` names the recording whose
+    /// level meter this capture should drive, if any.
+    #[cfg(target_os = "macos")]
+    pub fn start(path: PathBuf, app_handle: &AppHandle, level_for: Option<u64>) -> Result<Self, String> {
+        use screencapturekit::sc_content_filter::{InitParams, SCContentFilter};
+        use screencapturekit::sc_output_handler::SCStreamOutputType;
+        use screencapturekit::sc_shareable_content::SCShareableContent;
+        use screencapturekit::sc_stream::SCStream;
+        use screencapturekit::sc_stream_configuration::SCStreamConfiguration;
+        use screencapturekit::sc_types::base::CMTime;
+
+        let display = SCShareableContent::try_current()
+            .map_err(|e| format!("System audio needs the Screen Recording permission: {}", e))?
+            .displays
+            .into_iter()
+            .next()
+            .ok_or("No display to capture system audio from")?;
+
+        let writer = open_writer(&path)?;
+        // The stream has to capture a display, but only its audio is used,
+        // so the video is kept as small and infrequent as allowed.
+        let config = SCStreamConfiguration {
+            width: 2,
+            height: 2,
+            minimum_frame_interval: CMTime {
+                value: 1,
+                timescale: 1,
+                flags: 1,
+                epoch: 0,
+            },
+            captures_audio: true,
+            sample_rate: SAMPLE_RATE,
+            channel_count: CHANNELS as u32,
+            excludes_current_process_audio: true,
+            ..Default::default()
+        };
+        let mut stream = SCStream::new(
+            SCContentFilter::new(InitParams::Display(display)),
+            config,
+            sck::ErrorHandler,
+        );
+        stream.add_output(
+            sck::AudioOutput {
+                writer: writer.clone(),
+                level: level_for.map(|id| (app_handle.clone(), id)),
+                last_level_at: Mutex::new(None),
+            },
+            SCStreamOutputType::Audio,
+        );
+        if let Err(e) = stream.start_capture() {
+            let _ = std::fs::remove_file(&path);
+            return Err(format!("Failed to capture system audio: {}", e));
+        }
+
+        Ok(Self { path, writer, stream })
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    pub fn start(_path: PathBuf, _app_handle: &AppHandle, _level_for: Option<u64>) -> Result<Self, String> {
+        Err("System audio capture is only available on macOS".to_string())
+    }
+
+    /// Stops capturing and closes the WAV. Returns its path, or `None` when
+    /// nothing was heard and there is nothing to mix in.
+    pub fn stop(self) -> Option<PathBuf> {
+        #[cfg(target_os = "macos")]
+        if let Err(e) = self.stream.stop_capture() {
+            tracing::warn!("Failed to stop system audio capture: {}", e);
+        }
+
+        let writer = self.writer.lock().unwrap().take()?;
+        let has_audio = writer.data_len() > 0;
+        if let Err(e) = writer.finish() {
+            tracing::error!("Failed to finish {}: {}", self.path.display(), e);
+            let _ = std::fs::remove_file(&self.path);
+            return None;
+        }
+        if !has_audio {
+            let _ = std::fs::remove_file(&self.path);
+            return None;
+        }
+        Some(self.path)
+    }
+}
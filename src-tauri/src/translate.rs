@@ -0,0 +1,146 @@
+use serde::Deserialize;
+use serde_json::json;
+use tauri::AppHandle;
+use tauri_plugin_shell::ShellExt;
+
+use crate::config::{TranslationProvider, TranslationSettings};
+
+/// Accepts codes like "en", "de", "pt-BR" or "zh-Hans".
+pub fn validate_language(code: &str) -> Result<(), String> {
+    let valid = !code.is_empty()
+        && code.len() <= 12
+        && code.split('-').all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric()));
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("'{}' is not a language code", code))
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiResponse {
+    translated_text: Option<String>,
+    error: Option<String>,
+}
+
+/// Reads a LibreTranslate-style `{"translatedText": ...}` body.
+pub fn parse_api_response(body: &str) -> Result<String, String> {
+    let response: ApiResponse = serde_json::from_str(body)
+        .map_err(|e| format!("Unexpected response from translation service: {}", e))?;
+    match (response.translated_text, response.error) {
+        (Some(text), _) => Ok(text),
+        (None, Some(error)) => Err(format!("Translation service error: {}", error)),
+        (None, None) => Err("Translation service returned no text".to_string()),
+    }
+}
+
+async fn translate_with_api(settings: &TranslationSettings, text: &str, target: &str) -> Result<String, String> {
+    if settings.api_url.is_empty() {
+        return Err("Set a translation API URL in settings first".to_string());
+    }
+
+    let mut body = json!({ "q": text, "source": "auto", "target": target, "format": "text" });
+    if !settings.api_key.is_empty() {
+        body["api_key"] = json!(settings.api_key);
+    }
+
+    let response = reqwest::Client::new()
+        .post(&settings.api_url)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach translation service: {}", e))?;
+    let body = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read translation response: {}", e))?;
+    parse_api_response(&body)
+}
+
+/// The shortcut receives `{"text": ..., "target_language": ...}` as its
+/// input file and should output the translated text.
+async fn translate_with_shortcut(
+    app_handle: &AppHandle,
+    settings: &TranslationSettings,
+    text: &str,
+    target: &str,
+) -> Result<String, String> {
+    let stem = std::env::temp_dir().join(format!(
+        "snipp_translate_{}_{}",
+        std::process::id(),
+        chrono::Utc::now().timestamp_millis()
+    ));
+    let input_path = stem.with_extension("json");
+    let output_path = stem.with_extension("txt");
+    let input = json!({ "text": text, "target_language": target }).to_string();
+    std::fs::write(&input_path, input).map_err(|e| format!("Failed to stage text for translation: {}", e))?;
+
+    let output = app_handle
+        .shell()
+        .command("shortcuts")
+        .args([
+            "run",
+            settings.shortcut_name.as_str(),
+            "--input-path",
+            input_path.to_string_lossy().as_ref(),
+            "--output-path",
+            output_path.to_string_lossy().as_ref(),
+        ])
+        .output()
+        .await;
+    let _ = std::fs::remove_file(&input_path);
+    let translated = std::fs::read_to_string(&output_path);
+    let _ = std::fs::remove_file(&output_path);
+
+    let output = output.map_err(|e| format!("Failed to run shortcuts: {}", e))?;
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        return Err(format!(
+            "The '{}' shortcut failed: {}",
+            settings.shortcut_name,
+            error.trim()
+        ));
+    }
+    translated
+        .map(|text| text.trim_end().to_string())
+        .map_err(|_| format!("The '{}' shortcut produced no text", settings.shortcut_name))
+}
+
+/// Translates `text` into `target` with the configured provider.
+pub async fn translate(
+    app_handle: &AppHandle,
+    settings: &TranslationSettings,
+    text: &str,
+    target: &str,
+) -> Result<String, String> {
+    validate_language(target)?;
+    match settings.provider {
+        TranslationProvider::Apple => translate_with_shortcut(app_handle, settings, text, target).await,
+        TranslationProvider::Api => translate_with_api(settings, text, target).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_language() {
+        assert!(validate_language("en").is_ok());
+        assert!(validate_language("pt-BR").is_ok());
+        assert!(validate_language("").is_err());
+        assert!(validate_language("en-").is_err());
+        assert!(validate_language("en; rm").is_err());
+    }
+
+    #[test]
+    fn test_parse_api_response() {
+        assert_eq!(parse_api_response(r#"{"translatedText": "Hallo"}"#).unwrap(), "Hallo");
+        assert_eq!(
+            parse_api_response(r#"{"error": "Invalid API key"}"#).unwrap_err(),
+            "Translation service error: Invalid API key"
+        );
+        assert!(parse_api_response("<html>").is_err());
+    }
+}
@@ -6,13 +6,34 @@ use tauri::{
 };
 use tauri_plugin_opener::OpenerExt;
 
-use crate::{AppConfig, ConfigState};
+use crate::recording::RecordingStatus;
+use crate::{AppConfig, ConfigState, LastRegionState, RecordingState};
 
 pub fn create_tray_menu(
     app: &AppHandle,
     config: &AppConfig,
 ) -> Result<Menu<tauri::Wry>, Box<dyn std::error::Error>> {
     let capture_area_hotkey = format_hotkey_for_menu(&config.capture_hotkey);
+    let import_clipboard_hotkey = if config.import_clipboard_hotkey.is_empty() {
+        None
+    } else {
+        Some(format_hotkey_for_menu(&config.import_clipboard_hotkey))
+    };
+    let repeat_region_hotkey = if config.repeat_region_hotkey.is_empty() {
+        None
+    } else {
+        Some(format_hotkey_for_menu(&config.repeat_region_hotkey))
+    };
+    let capture_text_hotkey = if config.capture_text_hotkey.is_empty() {
+        None
+    } else {
+        Some(format_hotkey_for_menu(&config.capture_text_hotkey))
+    };
+    let quick_search_hotkey = if config.quick_search_hotkey.is_empty() {
+        None
+    } else {
+        Some(format_hotkey_for_menu(&config.quick_search_hotkey))
+    };
 
     let open_snipp = MenuItem::with_id(
         app,
@@ -29,6 +50,13 @@ pub fn create_tray_menu(
         true,
         None::<&str>,
     )?;
+    let capture_delayed = MenuItem::with_id(
+        app,
+        "capture_delayed",
+        format!("Capture Full Screen in {}s", config.default_capture_delay),
+        true,
+        None::<&str>,
+    )?;
     let capture_area = MenuItem::with_id(
         app,
         "capture_area",
@@ -36,9 +64,62 @@ pub fn create_tray_menu(
         true,
         Some(capture_area_hotkey),
     )?;
+    let capture_last_region = MenuItem::with_id(
+        app,
+        "capture_last_region",
+        "Capture Last Region",
+        true,
+        repeat_region_hotkey,
+    )?;
+    let capture_browser_page = MenuItem::with_id(
+        app,
+        "capture_browser_page",
+        "Capture Full Web Page",
+        true,
+        None::<&str>,
+    )?;
+    let capture_text = MenuItem::with_id(
+        app,
+        "capture_text",
+        "Capture Text",
+        true,
+        capture_text_hotkey,
+    )?;
+    let import_clipboard = MenuItem::with_id(
+        app,
+        "import_clipboard",
+        "Import from Clipboard",
+        true,
+        import_clipboard_hotkey,
+    )?;
+    let quick_search = MenuItem::with_id(
+        app,
+        "quick_search",
+        "Search Screenshots",
+        true,
+        quick_search_hotkey,
+    )?;
     let separator2 = PredefinedMenuItem::separator(app)?;
+
+    let recording = app.state::<RecordingState>().lock().unwrap().status();
+    let mut recording_items: Vec<MenuItem<tauri::Wry>> = Vec::new();
+    if recording.active {
+        let (id, label) = if recording.paused {
+            ("resume_recording", "Resume Recording")
+        } else {
+            ("pause_recording", "Pause Recording")
+        };
+        recording_items.push(MenuItem::with_id(app, id, label, true, None::<&str>)?);
+        recording_items.push(MenuItem::with_id(app, "stop_recording", "Stop Recording", true, None::<&str>)?);
+    } else {
+        recording_items.push(MenuItem::with_id(app, "start_recording", "Start Recording", true, None::<&str>)?);
+    }
+    let separator_recording = PredefinedMenuItem::separator(app)?;
+
     let suggest_feature = MenuItem::with_id(app, "suggest_feature", "Suggest a Feature", true, None::<&str>)?;
     let report_bug = MenuItem::with_id(app, "report_bug", "Report a Bug", true, None::<&str>)?;
+    let export_diagnostics =
+        MenuItem::with_id(app, "export_diagnostics", "Export Diagnostics...", true, None::<&str>)?;
     let separator3 = PredefinedMenuItem::separator(app)?;
     let quit = PredefinedMenuItem::quit(app, Some("Quit Snipp"))?;
 
@@ -46,10 +127,23 @@ pub fn create_tray_menu(
         &open_snipp,
         &separator1,
         &capture_screen,
+        &capture_delayed,
         &capture_area,
+        &capture_last_region,
+        &capture_browser_page,
+        &capture_text,
+        &import_clipboard,
+        &quick_search,
         &separator2,
+    ])?;
+    for item in &recording_items {
+        menu.append(item)?;
+    }
+    menu.append_items(&[
+        &separator_recording,
         &suggest_feature,
         &report_bug,
+        &export_diagnostics,
         &separator3,
         &quit,
     ])?;
@@ -58,24 +152,22 @@ pub fn create_tray_menu(
 }
 
 pub fn setup_system_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
-    log::debug!("Setting up system tray...");
+    tracing::debug!("Setting up system tray...");
     let config = {
         let config_state = app.state::<ConfigState>();
         let config = config_state.lock().unwrap().get_config().clone();
         config
     };
     let menu = create_tray_menu(app, &config)?;
-    log::debug!("Tray menu created successfully");
+    tracing::debug!("Tray menu created successfully");
     
-    let icon_bytes = include_bytes!("../icons/AppIcon-32.png");
-    let image_data = image::load_from_memory(icon_bytes)?;
-    let rgba_data = image_data.to_rgba8();
-    let (width, height) = (rgba_data.width(), rgba_data.height());
-    let icon = Image::new_owned(rgba_data.into_raw(), width, height);
+    let recording = app.state::<RecordingState>().lock().unwrap().status();
+    let icon = tray_icon(&recording)?;
     
     let _tray = TrayIconBuilder::with_id("main")
         .tooltip("Snipp - Screenshot Tool")
         .icon(icon)
+        .icon_as_template(cfg!(target_os = "macos"))
         .menu(&menu)
         .show_menu_on_left_click(true)
         .on_tray_icon_event(|tray_handle, event| {
@@ -85,35 +177,59 @@ pub fn setup_system_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Erro
             match event.id().as_ref() {
                 "open_snipp" => {
                     if let Err(e) = show_main_window(app) {
-                        log::error!("Failed to show main window: {}", e);
+                        tracing::error!("Failed to show main window: {}", e);
                     }
                 }
                 "capture_screen" => {
                     if let Err(e) = trigger_screen_capture(app) {
-                        log::error!("Failed to trigger screen capture: {}", e);
+                        tracing::error!("Failed to trigger screen capture: {}", e);
                     }
                 }
+                "capture_delayed" => {
+                    trigger_delayed_capture(app);
+                }
                 "capture_area" => {
                     if let Err(e) = trigger_area_capture(app) {
-                        log::error!("Failed to trigger area capture: {}", e);
+                        tracing::error!("Failed to trigger area capture: {}", e);
                     }
                 }
+                "capture_last_region" => {
+                    trigger_last_region_capture(app);
+                }
+                "capture_browser_page" => {
+                    trigger_browser_page_capture(app);
+                }
+                "capture_text" => {
+                    trigger_text_capture(app);
+                }
+                "import_clipboard" => {
+                    trigger_clipboard_import(app);
+                }
+                "quick_search" => {
+                    trigger_quick_search(app);
+                }
+                "start_recording" | "pause_recording" | "resume_recording" | "stop_recording" => {
+                    trigger_recording_action(app, event.id().as_ref());
+                }
                 "suggest_feature" => {
                     if let Err(e) = open_url_with_app(app, "https://github.com/codehakase/snipp/issues/new?template=feature_request.md") {
-                        log::error!("Failed to open feature request URL: {}", e);
+                        tracing::error!("Failed to open feature request URL: {}", e);
                     }
                 }
                 "report_bug" => {
                     if let Err(e) = open_url_with_app(app, "https://github.com/codehakase/snipp/issues/new?template=bug_report.md") {
-                        log::error!("Failed to open bug report URL: {}", e);
+                        tracing::error!("Failed to open bug report URL: {}", e);
                     }
                 }
+                "export_diagnostics" => {
+                    trigger_diagnostics_export(app);
+                }
                 _ => {}
             }
         })
         .build(app)?;
 
-    log::debug!("System tray built successfully!");
+    tracing::debug!("System tray built successfully!");
     Ok(())
 }
 
@@ -124,10 +240,38 @@ pub fn update_tray_menu(
     if let Some(tray) = app.tray_by_id("main") {
         let menu = create_tray_menu(app, config)?;
         tray.set_menu(Some(menu))?;
+
+        let recording = app.state::<RecordingState>().lock().unwrap().status();
+        let tooltip = match (recording.active, recording.paused) {
+            (true, true) => "Snipp - Recording paused",
+            (true, false) => "Snipp - Recording",
+            _ => "Snipp - Screenshot Tool",
+        };
+        tray.set_tooltip(Some(tooltip))?;
+        tray.set_icon(Some(tray_icon(&recording)?))?;
+        tray.set_icon_as_template(cfg!(target_os = "macos"))?;
     }
     Ok(())
 }
 
+/// Menu bar icon for the recording state. On macOS these are template
+/// images, so the menu bar tints them for light and dark appearance.
+fn tray_icon(recording: &RecordingStatus) -> Result<Image<'static>, Box<dyn std::error::Error>> {
+    let icon_bytes: &[u8] = if !cfg!(target_os = "macos") {
+        // Black template glyphs vanish on dark Windows and Linux panels.
+        include_bytes!("../icons/AppIcon-32.png")
+    } else if recording.paused {
+        include_bytes!("../icons/TrayPausedTemplate.png")
+    } else if recording.active {
+        include_bytes!("../icons/TrayRecordingTemplate.png")
+    } else {
+        include_bytes!("../icons/TrayTemplate.png")
+    };
+    let rgba_data = image::load_from_memory(icon_bytes)?.to_rgba8();
+    let (width, height) = (rgba_data.width(), rgba_data.height());
+    Ok(Image::new_owned(rgba_data.into_raw(), width, height))
+}
+
 fn format_hotkey_for_menu(hotkey: &str) -> String {
     let parts: Vec<&str> = hotkey
         .split('+')
@@ -166,33 +310,156 @@ fn trigger_screen_capture(app: &AppHandle) -> Result<(), Box<dyn std::error::Err
     let app_handle = app.clone();
     tauri::async_runtime::spawn(async move {
         match crate::capture_full_screen(app_handle.clone(), app_handle.state::<ConfigState>()).await {
-            Ok(_) => log::debug!("Full screen capture completed successfully"),
-            Err(e) => log::error!("Failed to capture full screen: {}", e),
+            Ok(_) => tracing::debug!("Full screen capture completed successfully"),
+            Err(e) => tracing::error!("Failed to capture full screen: {}", e),
         }
     });
     Ok(())
 }
 
+fn trigger_delayed_capture(app: &AppHandle) {
+    let app_handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        match crate::capture_with_delay(app_handle.clone(), app_handle.state::<ConfigState>(), None).await {
+            Ok(_) => tracing::debug!("Timed capture completed successfully"),
+            Err(e) => tracing::error!("Failed to take timed capture: {}", e),
+        }
+    });
+}
+
 fn trigger_area_capture(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
     let app_handle = app.clone();
     tauri::async_runtime::spawn(async move {
         match crate::capture_screenshot(app_handle.clone(), app_handle.state::<ConfigState>()).await {
-            Ok(_) => log::debug!("Area capture completed successfully"),
-            Err(e) => log::error!("Failed to capture area: {}", e),
+            Ok(_) => tracing::debug!("Area capture completed successfully"),
+            Err(e) => tracing::error!("Failed to capture area: {}", e),
         }
     });
     Ok(())
 }
 
+fn trigger_last_region_capture(app: &AppHandle) {
+    let app_handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        match crate::capture_last_region(
+            app_handle.clone(),
+            app_handle.state::<ConfigState>(),
+            app_handle.state::<LastRegionState>(),
+        )
+        .await
+        {
+            Ok(_) => tracing::debug!("Last region capture completed successfully"),
+            Err(e) => tracing::error!("Failed to capture last region: {}", e),
+        }
+    });
+}
+
+fn trigger_browser_page_capture(app: &AppHandle) {
+    let app_handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        match crate::capture_browser_page(app_handle.clone(), app_handle.state::<ConfigState>()).await {
+            Ok(_) => tracing::debug!("Browser page capture completed successfully"),
+            Err(e) => tracing::error!("Failed to capture browser page: {}", e),
+        }
+    });
+}
+
+fn trigger_text_capture(app: &AppHandle) {
+    let app_handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        match crate::capture_text(app_handle).await {
+            Ok(_) => tracing::debug!("Text capture completed successfully"),
+            Err(e) => tracing::error!("Failed to capture text: {}", e),
+        }
+    });
+}
+
+fn trigger_quick_search(app: &AppHandle) {
+    let app_handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = crate::toggle_quick_search(app_handle).await {
+            tracing::error!("Failed to toggle quick search: {}", e);
+        }
+    });
+}
+
+fn trigger_clipboard_import(app: &AppHandle) {
+    let app_handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        match crate::import_from_clipboard(app_handle).await {
+            Ok(_) => tracing::debug!("Clipboard import completed successfully"),
+            Err(e) => tracing::error!("Failed to import from clipboard: {}", e),
+        }
+    });
+}
+
+fn trigger_recording_action(app: &AppHandle, action: &str) {
+    let app_handle = app.clone();
+    let action = action.to_string();
+    tauri::async_runtime::spawn(async move {
+        let result = match action.as_str() {
+            "start_recording" => crate::start_recording(
+                app_handle.clone(),
+                app_handle.state::<ConfigState>(),
+                app_handle.state::<RecordingState>(),
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .map(|_| ()),
+            "pause_recording" => crate::pause_recording(
+                app_handle.clone(),
+                app_handle.state::<RecordingState>(),
+            )
+            .await
+            .map(|_| ()),
+            "resume_recording" => crate::resume_recording(
+                app_handle.clone(),
+                app_handle.state::<RecordingState>(),
+            )
+            .await
+            .map(|_| ()),
+            _ => crate::stop_recording(
+                app_handle.clone(),
+                app_handle.state::<RecordingState>(),
+                app_handle.state::<crate::HistoryState>(),
+            )
+            .await
+            .map(|_| ()),
+        };
+        if let Err(e) = result {
+            tracing::error!("Recording action '{}' failed: {}", action, e);
+        }
+    });
+}
+
+/// Saves a diagnostics bundle and shows it in Finder, ready to attach.
+fn trigger_diagnostics_export(app: &AppHandle) {
+    let app_handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        match crate::export_diagnostics(app_handle.clone(), app_handle.state::<crate::HistoryState>()).await {
+            Ok(Some(path)) => {
+                if let Err(e) = crate::open_in_finder(app_handle, path).await {
+                    tracing::error!("Failed to reveal diagnostics bundle: {}", e);
+                }
+            }
+            Ok(None) => tracing::debug!("Diagnostics export cancelled"),
+            Err(e) => tracing::error!("Failed to export diagnostics: {}", e),
+        }
+    });
+}
+
 fn open_url_with_app(app: &AppHandle, url: &str) -> Result<(), Box<dyn std::error::Error>> {
-    log::debug!("Attempting to open URL: {}", url);
+    tracing::debug!("Attempting to open URL: {}", url);
 
     match app.opener().open_url(url, None::<&str>) {
         Ok(_) => {
             Ok(())
         }
         Err(e) => {
-            log::error!("Failed to open URL {}: {}", url, e);
+            tracing::error!("Failed to open URL {}: {}", url, e);
             Err(Box::new(e))
         }
     }
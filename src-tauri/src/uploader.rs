@@ -0,0 +1,384 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::time::Instant;
+
+use crate::config::{CustomUploader, ShortenerSettings, UploadHeader, UploadProvider, UploadSettings};
+
+/// Result of one file in a bulk upload.
+#[derive(Debug, Clone, Serialize)]
+pub struct UploadOutcome {
+    pub file_path: String,
+    pub url: Option<String>,
+    pub error: Option<String>,
+}
+
+fn field(name: &str, value: &str) -> UploadHeader {
+    UploadHeader {
+        name: name.to_string(),
+        value: value.to_string(),
+    }
+}
+
+/// The request for the configured host. Presets only need the server URL
+/// and a token; the rest of the request is filled in here.
+pub fn resolve_target(settings: &UploadSettings) -> Result<CustomUploader, String> {
+    if settings.provider == UploadProvider::Custom {
+        if settings.custom.endpoint.trim().is_empty() {
+            return Err("Set an upload endpoint in settings first".to_string());
+        }
+        return Ok(settings.custom.clone());
+    }
+
+    let server = settings.server_url.trim().trim_end_matches('/');
+    if server.is_empty() {
+        return Err("Set your upload server URL in settings first".to_string());
+    }
+    let token = settings.token.trim();
+    if token.is_empty() && settings.provider != UploadProvider::Lutim {
+        return Err("Set your upload API token in settings first".to_string());
+    }
+
+    Ok(match settings.provider {
+        UploadProvider::Zipline => CustomUploader {
+            endpoint: format!("{}/api/upload", server),
+            file_field: "file".to_string(),
+            headers: vec![field("Authorization", token)],
+            form: Vec::new(),
+            url_template: "{files.0.url}".to_string(),
+        },
+        UploadProvider::Chibisafe => CustomUploader {
+            endpoint: format!("{}/api/upload", server),
+            file_field: "file[]".to_string(),
+            headers: vec![field("x-api-key", token)],
+            form: Vec::new(),
+            url_template: "{url}".to_string(),
+        },
+        // Lutim is anonymous and answers with the short id, not a link.
+        UploadProvider::Lutim => CustomUploader {
+            endpoint: format!("{}/", server),
+            file_field: "file".to_string(),
+            headers: Vec::new(),
+            form: vec![field("format", "json")],
+            url_template: format!("{}/{{msg.short}}.{{msg.ext}}", server),
+        },
+        UploadProvider::Custom => unreachable!(),
+    })
+}
+
+/// Follows a dotted path such as "files.0.url" through objects and arrays.
+fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(value, |current, key| match current {
+        Value::Array(items) => key.parse::<usize>().ok().and_then(|index| items.get(index)),
+        _ => current.get(key),
+    })
+}
+
+/// Fills each `{path}` in `template` from the JSON upload response.
+pub fn share_url(template: &str, body: &str) -> Result<String, String> {
+    let response: Value = serde_json::from_str(body)
+        .map_err(|_| format!("Unexpected upload response: {}", body.trim()))?;
+
+    let mut url = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let end = rest[start..]
+            .find('}')
+            .map(|offset| start + offset)
+            .ok_or_else(|| format!("Unclosed '{{' in URL template '{}'", template))?;
+        url.push_str(&rest[..start]);
+        let path = &rest[start + 1..end];
+        match lookup(&response, path) {
+            Some(Value::String(text)) => url.push_str(text),
+            Some(Value::Number(number)) => url.push_str(&number.to_string()),
+            _ => {
+                // Hosts commonly report failures as {"error": ...} or {"message": ...}.
+                let reason = ["error", "message"]
+                    .iter()
+                    .find_map(|key| response.get(*key).and_then(Value::as_str))
+                    .map(str::to_string)
+                    .unwrap_or_else(|| format!("no '{}' in the response", path));
+                return Err(format!("Upload failed: {}", reason));
+            }
+        }
+        rest = &rest[end + 1..];
+    }
+    url.push_str(rest);
+    Ok(url)
+}
+
+/// MIME type for an upload, from its file extension.
+fn mime_type(filename: &str) -> &'static str {
+    match filename.rsplit('.').next().map(str::to_ascii_lowercase).as_deref() {
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("webp") => "image/webp",
+        Some("gif") => "image/gif",
+        _ => "image/png",
+    }
+}
+
+/// Uploads an image through `queue` and returns its share link.
+pub async fn upload(
+    queue: &UploadQueue,
+    settings: &UploadSettings,
+    filename: &str,
+    image: Vec<u8>,
+) -> Result<String, String> {
+    let target = resolve_target(settings)?;
+    let _slot = queue
+        .acquire(
+            &destination_host(&target.endpoint),
+            settings.max_concurrent,
+            settings.uploads_per_minute,
+        )
+        .await;
+
+    let part = reqwest::multipart::Part::bytes(image)
+        .file_name(filename.to_string())
+        .mime_str(mime_type(filename))
+        .map_err(|e| format!("Failed to build upload: {}", e))?;
+    let mut form = reqwest::multipart::Form::new();
+    for text in &target.form {
+        form = form.text(text.name.clone(), text.value.clone());
+    }
+    form = form.part(target.file_field.clone(), part);
+
+    let mut request = reqwest::Client::new().post(&target.endpoint).multipart(form);
+    for header in &target.headers {
+        request = request.header(header.name.as_str(), header.value.as_str());
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach upload server: {}", e))?;
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read upload response: {}", e))?;
+    if !status.is_success() {
+        return Err(format!("Upload server returned {}: {}", status, body.trim()));
+    }
+    share_url(&target.url_template, &body)
+}
+
+/// The host part of an endpoint URL, used to rate-limit per destination.
+pub fn destination_host(endpoint: &str) -> String {
+    let rest = endpoint.split_once("://").map_or(endpoint, |(_, rest)| rest);
+    rest.split(['/', '?', '#']).next().unwrap_or(rest).to_ascii_lowercase()
+}
+
+/// Books the next start time for `host` so starts are spaced evenly at no
+/// more than `per_minute` a minute; 0 means no limit.
+pub fn reserve_start(
+    next_start: &mut HashMap<String, Instant>,
+    host: &str,
+    now: Instant,
+    per_minute: u32,
+) -> Instant {
+    if per_minute == 0 {
+        return now;
+    }
+    let spacing = Duration::from_secs(60) / per_minute;
+    let start = next_start.get(host).copied().map_or(now, |next| next.max(now));
+    next_start.insert(host.to_string(), start + spacing);
+    start
+}
+
+#[derive(Default)]
+struct QueueState {
+    active: usize,
+    next_start: HashMap<String, Instant>,
+}
+
+/// Gates every upload so bulk uploads don't flood the destination or the
+/// user's uplink. Limits are passed per call, so config edits apply to the
+/// next upload.
+#[derive(Default)]
+pub struct UploadQueue {
+    state: Mutex<QueueState>,
+    released: Notify,
+}
+
+/// Holds one of the concurrent upload slots until dropped.
+pub struct UploadSlot<'a> {
+    queue: &'a UploadQueue,
+}
+
+impl Drop for UploadSlot<'_> {
+    fn drop(&mut self) {
+        self.queue.state.lock().unwrap().active -= 1;
+        self.queue.released.notify_waiters();
+    }
+}
+
+impl UploadQueue {
+    /// Waits for this destination's rate limit, then for a free slot.
+    pub async fn acquire(&self, host: &str, max_concurrent: u32, per_minute: u32) -> UploadSlot<'_> {
+        let start = {
+            let mut state = self.state.lock().unwrap();
+            reserve_start(&mut state.next_start, host, Instant::now(), per_minute)
+        };
+        tokio::time::sleep_until(start).await;
+
+        let max_concurrent = max_concurrent.max(1) as usize;
+        loop {
+            // Registered before checking so a release in between isn't missed.
+            let released = self.released.notified();
+            {
+                let mut state = self.state.lock().unwrap();
+                if state.active < max_concurrent {
+                    state.active += 1;
+                    return UploadSlot { queue: self };
+                }
+            }
+            released.await;
+        }
+    }
+}
+
+/// Percent-encodes everything but RFC 3986 unreserved characters.
+fn encode_component(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// The shortener request for `long_url`.
+pub fn shortener_request(settings: &ShortenerSettings, long_url: &str) -> Result<String, String> {
+    if !settings.request_url.contains("{url}") {
+        return Err("The shortener request URL needs a {url} placeholder".to_string());
+    }
+    Ok(settings.request_url.replace("{url}", &encode_component(long_url)))
+}
+
+/// Asks the configured shortener for a short form of `long_url`.
+pub async fn shorten(settings: &ShortenerSettings, long_url: &str) -> Result<String, String> {
+    let mut request = reqwest::Client::new().get(shortener_request(settings, long_url)?);
+    for header in &settings.headers {
+        request = request.header(header.name.as_str(), header.value.as_str());
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach URL shortener: {}", e))?;
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read shortener response: {}", e))?;
+    if !status.is_success() {
+        return Err(format!("URL shortener returned {}: {}", status, body.trim()));
+    }
+
+    let short = if settings.url_template.is_empty() {
+        body.trim().to_string()
+    } else {
+        share_url(&settings.url_template, &body)?
+    };
+    if !short.starts_with("http") {
+        return Err(format!("URL shortener returned no link: {}", short));
+    }
+    Ok(short)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mime_type() {
+        assert_eq!(mime_type("Snipp 24-05-01.png"), "image/png");
+        assert_eq!(mime_type("shot.JPG"), "image/jpeg");
+        assert_eq!(mime_type("shot.webp"), "image/webp");
+    }
+
+    fn settings(provider: UploadProvider) -> UploadSettings {
+        UploadSettings {
+            enabled: true,
+            provider,
+            server_url: "https://img.example.com/".to_string(),
+            token: "secret".to_string(),
+            ..UploadSettings::default()
+        }
+    }
+
+    #[test]
+    fn test_presets_need_only_url_and_token() {
+        let zipline = resolve_target(&settings(UploadProvider::Zipline)).unwrap();
+        assert_eq!(zipline.endpoint, "https://img.example.com/api/upload");
+        assert_eq!(zipline.headers, vec![field("Authorization", "secret")]);
+
+        let mut lutim = settings(UploadProvider::Lutim);
+        lutim.token.clear();
+        let lutim = resolve_target(&lutim).unwrap();
+        assert_eq!(lutim.url_template, "https://img.example.com/{msg.short}.{msg.ext}");
+
+        let mut chibisafe = settings(UploadProvider::Chibisafe);
+        chibisafe.token.clear();
+        assert!(resolve_target(&chibisafe).is_err());
+        assert!(resolve_target(&settings(UploadProvider::Custom)).is_err());
+    }
+
+    #[test]
+    fn test_share_url_from_response() {
+        let zipline = r#"{"files":[{"id":"1","url":"https://img.example.com/u/abc.png"}]}"#;
+        assert_eq!(share_url("{files.0.url}", zipline).unwrap(), "https://img.example.com/u/abc.png");
+
+        let lutim = r#"{"success":true,"msg":{"short":"Xy12","ext":"png"}}"#;
+        assert_eq!(share_url("https://l.example/{msg.short}.{msg.ext}", lutim).unwrap(), "https://l.example/Xy12.png");
+
+        let error = share_url("{url}", r#"{"error":"Invalid token"}"#).unwrap_err();
+        assert_eq!(error, "Upload failed: Invalid token");
+        assert!(share_url("{url}", "<html>").is_err());
+    }
+
+    #[test]
+    fn test_rate_limit_spaces_starts_per_host() {
+        assert_eq!(destination_host("https://IMG.example.com:8443/api/upload"), "img.example.com:8443");
+
+        let now = Instant::now();
+        let mut next = HashMap::new();
+        assert_eq!(reserve_start(&mut next, "a", now, 30), now);
+        assert_eq!(reserve_start(&mut next, "a", now, 30), now + Duration::from_secs(2));
+        assert_eq!(reserve_start(&mut next, "b", now, 30), now);
+        assert_eq!(reserve_start(&mut next, "a", now + Duration::from_secs(10), 30), now + Duration::from_secs(10));
+        assert_eq!(reserve_start(&mut next, "c", now, 0), now);
+    }
+
+    #[tokio::test]
+    async fn test_queue_caps_concurrent_uploads() {
+        let queue = UploadQueue::default();
+        let first = queue.acquire("a", 1, 0).await;
+
+        let second = tokio::time::timeout(Duration::from_millis(20), queue.acquire("b", 1, 0)).await;
+        assert!(second.is_err());
+
+        drop(first);
+        let second = tokio::time::timeout(Duration::from_millis(20), queue.acquire("b", 1, 0)).await;
+        assert!(second.is_ok());
+    }
+
+    #[test]
+    fn test_shortener_request_encodes_link() {
+        let settings = ShortenerSettings::default();
+        assert_eq!(
+            shortener_request(&settings, "https://img.example.com/u/a b.png?x=1&y").unwrap(),
+            "https://is.gd/create.php?format=simple&url=https%3A%2F%2Fimg.example.com%2Fu%2Fa%20b.png%3Fx%3D1%26y"
+        );
+
+        let missing = ShortenerSettings {
+            request_url: "https://short.example/api".to_string(),
+            ..ShortenerSettings::default()
+        };
+        assert!(shortener_request(&missing, "https://a").is_err());
+    }
+}
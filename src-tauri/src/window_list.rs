@@ -0,0 +1,184 @@
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use tauri_plugin_shell::ShellExt;
+
+use crate::region::CaptureRegion;
+
+/// JXA bridge to `CGWindowListCopyWindowInfo`; prints the on-screen window
+/// list (front to back) as JSON.
+const WINDOW_LIST_SCRIPT: &str = "ObjC.import('CoreGraphics'); \
+JSON.stringify(ObjC.deepUnwrap(ObjC.castRefToObject($.CGWindowListCopyWindowInfo(\
+$.kCGWindowListOptionOnScreenOnly | $.kCGWindowListExcludeDesktopElements, $.kCGNullWindowID))))";
+
+/// Windows smaller than this (status items, tooltips) aren't worth snapping to.
+const MIN_WINDOW_SIZE: u32 = 40;
+
+/// Longest edge of the previews returned by `list_capture_windows`.
+pub const THUMBNAIL_SIZE: u32 = 240;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WindowInfo {
+    pub id: u32,
+    pub app_name: String,
+    pub title: String,
+    /// Frame in global screen points, origin at the top-left of the main display.
+    pub bounds: CaptureRegion,
+}
+
+/// A window plus a JPEG data URL preview for the window picker.
+#[derive(Debug, Clone, Serialize)]
+pub struct WindowThumbnail {
+    #[serde(flatten)]
+    pub window: WindowInfo,
+    pub thumbnail: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawWindow {
+    #[serde(rename = "kCGWindowNumber")]
+    number: u32,
+    #[serde(rename = "kCGWindowOwnerName", default)]
+    owner_name: String,
+    #[serde(rename = "kCGWindowName", default)]
+    name: String,
+    #[serde(rename = "kCGWindowOwnerPID", default)]
+    owner_pid: u32,
+    #[serde(rename = "kCGWindowLayer", default)]
+    layer: i32,
+    #[serde(rename = "kCGWindowAlpha", default = "opaque")]
+    alpha: f64,
+    #[serde(rename = "kCGWindowBounds")]
+    bounds: RawBounds,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct RawBounds {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+fn opaque() -> f64 {
+    1.0
+}
+
+/// Keeps normal, visible application windows (layer 0) in front-to-back
+/// order, skipping Snipp's own windows.
+pub fn parse_window_list(json: &str, own_pid: u32) -> Result<Vec<WindowInfo>, String> {
+    let raw: Vec<RawWindow> =
+        serde_json::from_str(json).map_err(|e| format!("Failed to parse window list: {}", e))?;
+
+    Ok(raw
+        .into_iter()
+        .filter(|window| window.layer == 0 && window.alpha > 0.0 && window.owner_pid != own_pid)
+        .map(|window| WindowInfo {
+            id: window.number,
+            app_name: window.owner_name,
+            title: window.name,
+            bounds: CaptureRegion {
+                x: window.bounds.x.round() as i32,
+                y: window.bounds.y.round() as i32,
+                width: window.bounds.width.max(0.0).round() as u32,
+                height: window.bounds.height.max(0.0).round() as u32,
+            },
+        })
+        .filter(|window| {
+            window.bounds.width >= MIN_WINDOW_SIZE && window.bounds.height >= MIN_WINDOW_SIZE
+        })
+        .collect())
+}
+
+/// Shrinks a window capture to fit `max_size` and encodes it as a JPEG data URL.
+pub fn thumbnail_data_url(png: &[u8], max_size: u32) -> Result<String, String> {
+    let image = image::load_from_memory(png).map_err(|e| format!("Failed to decode image: {}", e))?;
+    let thumbnail = image::DynamicImage::ImageRgb8(image.thumbnail(max_size, max_size).to_rgb8());
+
+    let mut jpeg = Vec::new();
+    thumbnail
+        .write_to(&mut std::io::Cursor::new(&mut jpeg), image::ImageOutputFormat::Jpeg(80))
+        .map_err(|e| format!("Failed to encode thumbnail: {}", e))?;
+    Ok(format!(
+        "data:image/jpeg;base64,{}",
+        base64::prelude::BASE64_STANDARD.encode(&jpeg)
+    ))
+}
+
+pub async fn list_windows(app_handle: &AppHandle) -> Result<Vec<WindowInfo>, String> {
+    let output = app_handle
+        .shell()
+        .command("osascript")
+        .args(["-l", "JavaScript", "-e", WINDOW_LIST_SCRIPT])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run osascript: {}", e))?;
+
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to list windows: {}", error));
+    }
+
+    parse_window_list(&String::from_utf8_lossy(&output.stdout), std::process::id())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"[
+        {"kCGWindowNumber": 812, "kCGWindowOwnerName": "Safari", "kCGWindowName": "Docs",
+         "kCGWindowOwnerPID": 501, "kCGWindowLayer": 0, "kCGWindowAlpha": 1,
+         "kCGWindowBounds": {"X": 100, "Y": 38.5, "Width": 1200, "Height": 800}},
+        {"kCGWindowNumber": 3, "kCGWindowOwnerName": "Window Server", "kCGWindowName": "Menubar",
+         "kCGWindowOwnerPID": 90, "kCGWindowLayer": 24, "kCGWindowAlpha": 1,
+         "kCGWindowBounds": {"X": 0, "Y": 0, "Width": 1440, "Height": 24}},
+        {"kCGWindowNumber": 40, "kCGWindowOwnerName": "Snipp",
+         "kCGWindowOwnerPID": 777, "kCGWindowLayer": 0,
+         "kCGWindowBounds": {"X": 0, "Y": 0, "Width": 580, "Height": 720}},
+        {"kCGWindowNumber": 41, "kCGWindowOwnerName": "Finder",
+         "kCGWindowOwnerPID": 300, "kCGWindowLayer": 0, "kCGWindowAlpha": 1,
+         "kCGWindowBounds": {"X": 10, "Y": 10, "Width": 20, "Height": 20}}
+    ]"#;
+
+    #[test]
+    fn test_parse_window_list_keeps_normal_windows() {
+        let windows = parse_window_list(SAMPLE, 777).unwrap();
+
+        assert_eq!(windows.len(), 1);
+        assert_eq!(windows[0].id, 812);
+        assert_eq!(windows[0].app_name, "Safari");
+        assert_eq!(windows[0].title, "Docs");
+        assert_eq!(
+            windows[0].bounds,
+            CaptureRegion {
+                x: 100,
+                y: 39,
+                width: 1200,
+                height: 800
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_window_list_rejects_garbage() {
+        assert!(parse_window_list("not json", 1).is_err());
+    }
+
+    #[test]
+    fn test_thumbnail_data_url_fits_max_size() {
+        let mut png = Vec::new();
+        image::DynamicImage::ImageRgba8(image::RgbaImage::new(1200, 600))
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageOutputFormat::Png)
+            .unwrap();
+
+        let url = thumbnail_data_url(&png, 240).unwrap();
+        let jpeg = base64::prelude::BASE64_STANDARD
+            .decode(url.strip_prefix("data:image/jpeg;base64,").unwrap())
+            .unwrap();
+        let thumbnail = image::load_from_memory(&jpeg).unwrap();
+
+        assert_eq!((thumbnail.width(), thumbnail.height()), (240, 120));
+    }
+}
@@ -0,0 +1,10 @@
+import React from 'react'
+import ReactDOM from 'react-dom/client'
+import { CompareView } from '@/components/compare/CompareView'
+import '@/styles.css'
+
+ReactDOM.createRoot(document.getElementById('root')!).render(
+  <React.StrictMode>
+    <CompareView />
+  </React.StrictMode>,
+)
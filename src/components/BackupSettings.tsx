@@ -0,0 +1,85 @@
+import { useState } from 'react';
+import { Download, Upload } from 'lucide-react';
+import { Button } from '@/components/ui/button';
+import { invoke } from '@/lib/tauri';
+import type { BackupSummary } from '@/types';
+
+interface BackupSettingsProps {
+  /** Called after a restore so the dashboard can reload the config. */
+  onRestored: () => void;
+}
+
+const describe = (summary: BackupSummary) =>
+  `${summary.config_files} data files, ${summary.thumbnails} thumbnails, ${summary.screenshots} screenshots`;
+
+export function BackupSettings({ onRestored }: BackupSettingsProps) {
+  const [includeScreenshots, setIncludeScreenshots] = useState(false);
+  const [isBusy, setIsBusy] = useState(false);
+  const [message, setMessage] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const handleExport = async () => {
+    setIsBusy(true);
+    setError(null);
+    setMessage(null);
+    try {
+      const summary = await invoke('export_backup', { includeScreenshots });
+      if (summary) setMessage(`Exported ${describe(summary)}`);
+    } catch (err) {
+      setError(String(err));
+      console.error('Failed to export backup:', err);
+    } finally {
+      setIsBusy(false);
+    }
+  };
+
+  const handleRestore = async () => {
+    setIsBusy(true);
+    setError(null);
+    setMessage(null);
+    try {
+      const summary = await invoke('restore_backup');
+      if (summary) {
+        setMessage(`Restored ${describe(summary)}`);
+        onRestored();
+      }
+    } catch (err) {
+      setError(String(err));
+      console.error('Failed to restore backup:', err);
+    } finally {
+      setIsBusy(false);
+    }
+  };
+
+  return (
+    <div className="mt-8 p-4 border border-border rounded-md space-y-3">
+      <div>
+        <div className="text-sm font-medium">Backup</div>
+        <div className="text-xs text-muted-foreground mt-1">
+          Settings, history and thumbnails in one archive; restoring replaces the current data
+        </div>
+      </div>
+      <label className="flex items-center gap-3 text-sm cursor-pointer">
+        <input
+          type="checkbox"
+          checked={includeScreenshots}
+          onChange={(e) => setIncludeScreenshots(e.target.checked)}
+          className="w-4 h-4 rounded border-border"
+        />
+        Include saved screenshots
+      </label>
+      <div className="flex gap-2">
+        <Button onClick={handleExport} disabled={isBusy} className="flex-1 rounded-md" variant="outline">
+          <Download className="w-4 h-4 mr-2" />
+          Export
+        </Button>
+        <Button onClick={handleRestore} disabled={isBusy} className="flex-1 rounded-md" variant="outline">
+          <Upload className="w-4 h-4 mr-2" />
+          Restore
+        </Button>
+      </div>
+      {message && <div className="text-xs text-accent">{message}</div>}
+      {error && <div className="text-xs text-destructive">{error}</div>}
+    </div>
+  );
+}
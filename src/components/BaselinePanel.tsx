@@ -0,0 +1,97 @@
+import { useCallback, useEffect, useState } from 'react';
+import { CheckCircle2, Crop, ImagePlus, Trash2, XCircle } from 'lucide-react';
+import { Button } from '@/components/ui/button';
+import { invoke, listen } from '@/lib/tauri';
+import type { RegressionResult } from '@/types';
+
+interface BaselinePanelProps {
+  preset: string;
+}
+
+export function BaselinePanel({ preset }: BaselinePanelProps) {
+  const [hasBaseline, setHasBaseline] = useState(false);
+  const [result, setResult] = useState<RegressionResult | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const refresh = useCallback(() => {
+    invoke('list_region_baselines')
+      .then((names) => setHasBaseline(names.includes(preset)))
+      .catch((err) => console.error('Failed to list baselines:', err));
+  }, [preset]);
+
+  useEffect(() => {
+    refresh();
+    setResult(null);
+  }, [refresh]);
+
+  useEffect(() => {
+    const unlisten = listen('regression-checked', (checked) => {
+      if (checked.preset === preset) setResult(checked);
+    });
+    return () => {
+      unlisten.then((fn) => fn()).catch(() => {});
+    };
+  }, [preset]);
+
+  const run = async (action: () => Promise<unknown>) => {
+    setError(null);
+    try {
+      await action();
+    } catch (err) {
+      setError(String(err));
+      console.error('Baseline action failed:', err);
+    }
+    refresh();
+  };
+
+  return (
+    <div className="p-4 border border-border rounded-md space-y-3">
+      <div>
+        <div className="text-sm font-medium">Baseline for {preset}</div>
+        <div className="text-xs text-muted-foreground mt-1">
+          {hasBaseline
+            ? 'Captures of this preset are compared against the baseline'
+            : 'Set a baseline to check future captures of this preset'}
+        </div>
+      </div>
+
+      <div className="flex gap-2">
+        <Button
+          onClick={() => run(() => invoke('capture_region', { preset }))}
+          className="flex-1 rounded-md"
+          variant="outline"
+        >
+          <Crop className="w-4 h-4 mr-2" />
+          Capture
+        </Button>
+        <Button
+          onClick={() => run(() => invoke('set_region_baseline', { preset }))}
+          className="flex-1 rounded-md"
+          variant="outline"
+        >
+          <ImagePlus className="w-4 h-4 mr-2" />
+          {hasBaseline ? 'Replace baseline' : 'Set baseline'}
+        </Button>
+        {hasBaseline && (
+          <Button
+            onClick={() => run(() => invoke('clear_region_baseline', { preset }))}
+            className="rounded-md"
+            variant="outline"
+          >
+            <Trash2 className="w-4 h-4" />
+          </Button>
+        )}
+      </div>
+
+      {result && (
+        <div className={`flex items-center gap-2 text-xs ${result.passed ? 'text-accent' : 'text-destructive'}`}>
+          {result.passed ? <CheckCircle2 className="w-4 h-4" /> : <XCircle className="w-4 h-4" />}
+          {result.passed ? 'Passed' : 'Failed'}: {result.changed_percent.toFixed(2)}% changed
+          (threshold {result.threshold_percent.toFixed(2)}%)
+        </div>
+      )}
+
+      {error && <div className="text-xs text-destructive">{error}</div>}
+    </div>
+  );
+}
@@ -0,0 +1,220 @@
+import type { BeautifyBackground, BeautifyOptions, CollageLayout, CornerOptions } from '@/types';
+
+interface BeautifySettingsProps {
+  options: BeautifyOptions;
+  corners: CornerOptions;
+  collage: CollageLayout;
+  onChange: (updates: Partial<BeautifyOptions>) => void;
+  onCornersChange: (updates: Partial<CornerOptions>) => void;
+  onCollageChange: (updates: Partial<CollageLayout>) => void;
+}
+
+const BACKGROUNDS: { label: string; background: BeautifyBackground }[] = [
+  { label: 'Purple Blue', background: { kind: 'gradient', from: '#667eea', to: '#764ba2' } },
+  { label: 'Pink Red', background: { kind: 'gradient', from: '#f093fb', to: '#f5576c' } },
+  { label: 'Blue Cyan', background: { kind: 'gradient', from: '#4facfe', to: '#00f2fe' } },
+  { label: 'Green Teal', background: { kind: 'gradient', from: '#43e97b', to: '#38f9d7' } },
+  { label: 'Dark Teal', background: { kind: 'gradient', from: '#2c3e50', to: '#4ca1af' } },
+  { label: 'White', background: { kind: 'solid', color: '#ffffff' } },
+  { label: 'Dark', background: { kind: 'solid', color: '#1a1a1a' } },
+];
+
+const BORDER_COLORS = [
+  { label: 'Light', color: '#d4d4d4' },
+  { label: 'Dark', color: '#404040' },
+];
+
+const backgroundKey = (background: BeautifyBackground) =>
+  background.kind === 'solid' ? background.color : `${background.from}-${background.to}`;
+
+export function BeautifySettings({
+  options,
+  corners,
+  collage,
+  onChange,
+  onCornersChange,
+  onCollageChange,
+}: BeautifySettingsProps) {
+  const selected = backgroundKey(options.background);
+  const isPreset = BACKGROUNDS.some(({ background }) => backgroundKey(background) === selected);
+  const collageSelected = backgroundKey(collage.background);
+  const isCollagePreset = BACKGROUNDS.some(({ background }) => backgroundKey(background) === collageSelected);
+  const collageArrangement =
+    collage.arrangement.kind === 'strip' ? 'strip' : String(collage.arrangement.columns);
+
+  return (
+    <div className="mt-8 p-4 border border-border rounded-md space-y-3">
+      <div>
+        <div className="text-sm font-medium">Beautify</div>
+        <div className="text-xs text-muted-foreground mt-1">
+          How the popup's Beautify action frames a capture for sharing
+        </div>
+      </div>
+      <div className="grid grid-cols-2 gap-3 text-sm">
+        <label className="flex items-center justify-between gap-2">
+          Background
+          <select
+            value={selected}
+            onChange={(e) => {
+              const preset = BACKGROUNDS.find(({ background }) => backgroundKey(background) === e.target.value);
+              if (preset) onChange({ background: preset.background });
+            }}
+            className="h-9 px-2 bg-muted border border-border rounded-md text-sm"
+          >
+            {!isPreset && <option value={selected}>Custom</option>}
+            {BACKGROUNDS.map(({ label, background }) => (
+              <option key={label} value={backgroundKey(background)}>
+                {label}
+              </option>
+            ))}
+          </select>
+        </label>
+        <label className="flex items-center justify-between gap-2">
+          Padding
+          <select
+            value={options.padding}
+            onChange={(e) => onChange({ padding: Number(e.target.value) })}
+            className="h-9 px-2 bg-muted border border-border rounded-md text-sm"
+          >
+            {[16, 32, 64, 96, 128].map((padding) => (
+              <option key={padding} value={padding}>{padding}px</option>
+            ))}
+          </select>
+        </label>
+        <label className="flex items-center justify-between gap-2">
+          Corner radius
+          <select
+            value={options.corner_radius}
+            onChange={(e) => onChange({ corner_radius: Number(e.target.value) })}
+            className="h-9 px-2 bg-muted border border-border rounded-md text-sm"
+          >
+            {[0, 8, 12, 16, 24, 32].map((radius) => (
+              <option key={radius} value={radius}>{radius === 0 ? 'Square' : `${radius}px`}</option>
+            ))}
+          </select>
+        </label>
+        <label className="flex items-center justify-between gap-2">
+          Shadow
+          <select
+            value={options.shadow}
+            onChange={(e) => onChange({ shadow: Number(e.target.value) })}
+            className="h-9 px-2 bg-muted border border-border rounded-md text-sm"
+          >
+            <option value={0}>None</option>
+            <option value={16}>Subtle</option>
+            <option value={32}>Soft</option>
+            <option value={64}>Large</option>
+          </select>
+        </label>
+      </div>
+      <div className="pt-3 border-t border-border">
+        <div className="text-sm font-medium">Round corners</div>
+        <div className="text-xs text-muted-foreground mt-1">
+          The popup's Round action, for window captures without a frame
+        </div>
+      </div>
+      <div className="grid grid-cols-2 gap-3 text-sm">
+        <label className="flex items-center justify-between gap-2">
+          Radius
+          <select
+            value={corners.radius}
+            onChange={(e) => onCornersChange({ radius: Number(e.target.value) })}
+            className="h-9 px-2 bg-muted border border-border rounded-md text-sm"
+          >
+            {[8, 12, 16, 24, 32].map((radius) => (
+              <option key={radius} value={radius}>{radius}px</option>
+            ))}
+          </select>
+        </label>
+        <label className="flex items-center justify-between gap-2">
+          Border
+          <select
+            value={corners.border ? `${corners.border.width}:${corners.border.color}` : ''}
+            onChange={(e) => {
+              if (!e.target.value) {
+                onCornersChange({ border: null });
+                return;
+              }
+              const [width, color] = e.target.value.split(':');
+              onCornersChange({ border: { width: Number(width), color } });
+            }}
+            className="h-9 px-2 bg-muted border border-border rounded-md text-sm"
+          >
+            <option value="">None</option>
+            {corners.border && !BORDER_COLORS.some(({ color }) => color === corners.border?.color) && (
+              <option value={`${corners.border.width}:${corners.border.color}`}>Custom</option>
+            )}
+            {BORDER_COLORS.flatMap(({ label, color }) =>
+              [1, 2, 3, 4].map((width) => (
+                <option key={`${width}:${color}`} value={`${width}:${color}`}>
+                  {label} {width}px
+                </option>
+              ))
+            )}
+          </select>
+        </label>
+      </div>
+      <div className="pt-3 border-t border-border">
+        <div className="text-sm font-medium">Collage</div>
+        <div className="text-xs text-muted-foreground mt-1">
+          How Collage combines captures from the compare picker or the popup
+        </div>
+      </div>
+      <div className="grid grid-cols-2 gap-3 text-sm">
+        <label className="flex items-center justify-between gap-2">
+          Layout
+          <select
+            value={collageArrangement}
+            onChange={(e) =>
+              onCollageChange({
+                arrangement:
+                  e.target.value === 'strip'
+                    ? { kind: 'strip' }
+                    : { kind: 'grid', columns: Number(e.target.value) },
+              })
+            }
+            className="h-9 px-2 bg-muted border border-border rounded-md text-sm"
+          >
+            <option value="strip">Strip</option>
+            {[2, 3, 4].map((columns) => (
+              <option key={columns} value={columns}>{columns} columns</option>
+            ))}
+            {!['strip', '2', '3', '4'].includes(collageArrangement) && (
+              <option value={collageArrangement}>Custom</option>
+            )}
+          </select>
+        </label>
+        <label className="flex items-center justify-between gap-2">
+          Gutter
+          <select
+            value={collage.gutter}
+            onChange={(e) => onCollageChange({ gutter: Number(e.target.value) })}
+            className="h-9 px-2 bg-muted border border-border rounded-md text-sm"
+          >
+            {[0, 16, 32, 64].map((gutter) => (
+              <option key={gutter} value={gutter}>{gutter === 0 ? 'None' : `${gutter}px`}</option>
+            ))}
+          </select>
+        </label>
+        <label className="flex items-center justify-between gap-2">
+          Background
+          <select
+            value={collageSelected}
+            onChange={(e) => {
+              const preset = BACKGROUNDS.find(({ background }) => backgroundKey(background) === e.target.value);
+              if (preset) onCollageChange({ background: preset.background });
+            }}
+            className="h-9 px-2 bg-muted border border-border rounded-md text-sm"
+          >
+            {!isCollagePreset && <option value={collageSelected}>Custom</option>}
+            {BACKGROUNDS.map(({ label, background }) => (
+              <option key={label} value={backgroundKey(background)}>
+                {label}
+              </option>
+            ))}
+          </select>
+        </label>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,259 @@
+import { useEffect, useState } from 'react';
+import { Cloud, CloudOff, Columns2, LayoutGrid, RotateCcw, Search, X } from 'lucide-react';
+import { Button } from '@/components/ui/button';
+import { invoke, listen } from '@/lib/tauri';
+import type { RecentScreenshot, ScreenshotMetadata } from '@/types';
+
+/** Matches `collage::MAX_COLLAGE_IMAGES` in the backend. */
+const MAX_COLLAGE_IMAGES = 16;
+
+interface ComparePickerProps {
+  onClose: () => void;
+}
+
+export function ComparePicker({ onClose }: ComparePickerProps) {
+  const [screenshots, setScreenshots] = useState<RecentScreenshot[]>([]);
+  const [selected, setSelected] = useState<string[]>([]);
+  const [error, setError] = useState<string | null>(null);
+  const [imported, setImported] = useState(0);
+  const [query, setQuery] = useState('');
+  const [note, setNote] = useState('');
+  const [metadata, setMetadata] = useState<ScreenshotMetadata | null>(null);
+
+  useEffect(() => {
+    const load = query.trim()
+      ? invoke('search_screenshots', { query })
+      : invoke('get_recent_screenshots');
+    load
+      .then((recent) => setScreenshots(recent.filter((s) => s.thumbnail || s.sync_status === 'placeholder')))
+      .catch((err) => setError(String(err)));
+  }, [query, imported]);
+
+  // Screenshots picked up from the watched folder show up without reopening.
+  useEffect(() => {
+    const unlisten = listen('screenshot-imported', () => setImported((count) => count + 1));
+    return () => {
+      unlisten.then((fn) => fn()).catch(() => {});
+    };
+  }, []);
+
+  // With exactly one capture picked, its note can be edited below the grid.
+  const noted = selected.length === 1 ? screenshots.find((s) => s.file_path === selected[0]) : undefined;
+
+  useEffect(() => {
+    setNote(noted?.note ?? '');
+  }, [noted?.file_path, noted?.note]);
+
+  useEffect(() => {
+    setMetadata(null);
+    if (!noted) return;
+    invoke('get_screenshot_metadata', { filePath: noted.file_path })
+      .then(setMetadata)
+      .catch((err) => console.error('Failed to load screenshot metadata:', err));
+  }, [noted?.file_path]);
+
+  // Captures the same area again, e.g. after changing the UI it shows.
+  const handleRetake = async () => {
+    if (!metadata?.region) return;
+    setError(null);
+    try {
+      await invoke('capture_region', { region: metadata.region });
+    } catch (err) {
+      setError(String(err));
+      console.error('Failed to retake region:', err);
+    }
+  };
+
+  const handleSaveNote = async () => {
+    if (!noted) return;
+    setError(null);
+    try {
+      await invoke('set_screenshot_note', { filePath: noted.file_path, note });
+      setScreenshots((prev) =>
+        prev.map((s) => (s.file_path === noted.file_path ? { ...s, note: note.trim() } : s))
+      );
+    } catch (err) {
+      setError(String(err));
+      console.error('Failed to save note:', err);
+    }
+  };
+
+  // Keep the most recent picks, in the order they were picked. Compare
+  // takes exactly two; Collage takes up to the backend's limit.
+  const toggle = (path: string) => {
+    setSelected((prev) =>
+      prev.includes(path) ? prev.filter((p) => p !== path) : [...prev, path].slice(-MAX_COLLAGE_IMAGES)
+    );
+  };
+
+  // Space previews the focused capture in Quick Look, like Finder.
+  const handlePreviewKey = (e: React.KeyboardEvent, path: string) => {
+    if (e.key !== ' ') return;
+    e.preventDefault();
+    invoke('quick_look', { filePath: path }).catch((err) => setError(String(err)));
+  };
+
+  const handleCompare = async () => {
+    setError(null);
+    try {
+      await invoke('compare_screenshots', { a: selected[0], b: selected[1] });
+    } catch (err) {
+      setError(String(err));
+      console.error('Failed to compare screenshots:', err);
+    }
+  };
+
+  const handleCollage = async () => {
+    setError(null);
+    try {
+      const config = await invoke('get_config');
+      await invoke('compose_collage', { timestamps: [], filePaths: selected, layout: config.collage });
+    } catch (err) {
+      setError(String(err));
+      console.error('Failed to compose collage:', err);
+    }
+  };
+
+  return (
+    <div className="p-4 border border-border rounded-md space-y-3">
+      <div className="flex items-start justify-between gap-3">
+        <div>
+          <div className="text-sm font-medium">Compare screenshots</div>
+          <div className="text-xs text-muted-foreground mt-1">
+            Pick a before and an after, or several for a collage; Space previews
+          </div>
+        </div>
+        <button onClick={onClose} className="text-muted-foreground hover:text-foreground">
+          <X className="w-4 h-4" />
+        </button>
+      </div>
+
+      <div className="relative">
+        <Search className="absolute left-2 top-1/2 -translate-y-1/2 w-3.5 h-3.5 text-muted-foreground" />
+        <input
+          type="search"
+          value={query}
+          onChange={(e) => setQuery(e.target.value)}
+          placeholder="Search names and notes"
+          className="w-full pl-7 pr-2 py-1.5 text-xs rounded-md border border-border bg-background"
+        />
+      </div>
+
+      {screenshots.length === 0 ? (
+        <div className="text-xs text-muted-foreground">
+          {query.trim() ? 'No matching screenshots' : 'No saved screenshots yet'}
+        </div>
+      ) : (
+        <div className="grid grid-cols-5 gap-2">
+          {screenshots.map((shot) => {
+            const order = selected.indexOf(shot.file_path);
+            return (
+              <button
+                key={shot.file_path}
+                onClick={() => toggle(shot.file_path)}
+                onKeyDown={(e) => handlePreviewKey(e, shot.file_path)}
+                title={
+                  (shot.sync_status === 'placeholder'
+                    ? `${shot.filename} (in the cloud, downloads when compared)`
+                    : shot.sync_status === 'conflict'
+                      ? `${shot.filename} (has a sync conflict copy)`
+                      : shot.filename) +
+                  (shot.mirror === 'queued' ? '\nWaiting for the network share' : '') +
+                  (shot.note ? `\n${shot.note}` : '')
+                }
+                className={`relative aspect-square rounded-md overflow-hidden border-2 ${
+                  order >= 0 ? 'border-accent' : 'border-border hover:border-muted-foreground'
+                }`}
+              >
+                {shot.thumbnail ? (
+                  <img src={shot.thumbnail} alt="" className="w-full h-full object-cover" />
+                ) : (
+                  <div className="w-full h-full flex items-center justify-center bg-muted">
+                    <Cloud className="w-5 h-5 text-muted-foreground" />
+                  </div>
+                )}
+                {shot.sync_status === 'conflict' && (
+                  <span className="absolute bottom-1 right-1 px-1 rounded bg-destructive text-[10px] text-white">!</span>
+                )}
+                {shot.mirror === 'queued' && (
+                  <span className="absolute bottom-1 left-1 rounded bg-background/80 p-0.5">
+                    <CloudOff className="w-3 h-3 text-muted-foreground" />
+                  </span>
+                )}
+                {order >= 0 && (
+                  <span className="absolute top-1 left-1 px-1 rounded bg-accent text-[10px] text-accent-foreground">
+                    {selected.length > 2 ? order + 1 : order === 0 ? 'Before' : 'After'}
+                  </span>
+                )}
+              </button>
+            );
+          })}
+        </div>
+      )}
+
+      {noted && (
+        <div className="flex gap-2">
+          <input
+            value={note}
+            onChange={(e) => setNote(e.target.value)}
+            onKeyDown={(e) => e.key === 'Enter' && handleSaveNote()}
+            placeholder="Note, e.g. repro of crash #123"
+            className="flex-1 px-2 py-1.5 text-xs rounded-md border border-border bg-background"
+          />
+          <Button
+            onClick={handleSaveNote}
+            disabled={note.trim() === noted.note}
+            size="sm"
+            variant="outline"
+            className="rounded-md"
+          >
+            Save note
+          </Button>
+        </div>
+      )}
+
+      {metadata && (metadata.source_app || metadata.display_name || metadata.region) && (
+        <div className="flex items-center justify-between gap-2">
+          <div className="text-xs text-muted-foreground truncate">
+            {[
+              metadata.window_title || metadata.source_app,
+              metadata.display_name,
+              metadata.region && `${metadata.region.width} x ${metadata.region.height} at ${metadata.region.x}, ${metadata.region.y}`,
+            ]
+              .filter(Boolean)
+              .join(' - ')}
+          </div>
+          {metadata.region && (
+            <Button onClick={handleRetake} size="sm" variant="outline" className="rounded-md shrink-0">
+              <RotateCcw className="w-3.5 h-3.5 mr-1.5" />
+              Retake
+            </Button>
+          )}
+        </div>
+      )}
+
+      {error && <div className="text-xs text-destructive">{error}</div>}
+
+      <div className="flex gap-2">
+        <Button
+          onClick={handleCompare}
+          disabled={selected.length !== 2}
+          className="flex-1 rounded-md"
+          variant="outline"
+        >
+          <Columns2 className="w-4 h-4 mr-2" />
+          Compare
+        </Button>
+        <Button
+          onClick={handleCollage}
+          disabled={selected.length < 2}
+          className="flex-1 rounded-md"
+          variant="outline"
+        >
+          <LayoutGrid className="w-4 h-4 mr-2" />
+          Collage
+        </Button>
+      </div>
+    </div>
+  );
+}
@@ -1,8 +1,29 @@
 import { useEffect, useState, useCallback } from 'react';
-import { Camera, EyeOff, Folder, Keyboard, Check, AlertCircle, X, Maximize, Edit3 } from 'lucide-react';
+import { AppWindow, Camera, Columns2, ListOrdered, Search, Crosshair, Repeat, Ruler, ScanText, Globe, EyeOff, Folder, Keyboard, Check, AlertCircle, X, Maximize, Edit3, ClipboardPaste, Video, Square, Pause, Play, Timer, Film, ScrollText, ImageUp } from 'lucide-react';
 import { Button } from '@/components/ui/button';
-import { invoke } from '@/lib/tauri';
-import type { AppConfig } from '@/types';
+import { invoke, isCancelled, listen } from '@/lib/tauri';
+import { RecordingTrimmer } from '@/components/RecordingTrimmer';
+import { ComparePicker } from '@/components/ComparePicker';
+import { WindowPicker } from '@/components/WindowPicker';
+import { GuidePanel } from '@/components/GuidePanel';
+import { BaselinePanel } from '@/components/BaselinePanel';
+import { RetentionSettings } from '@/components/RetentionSettings';
+import { BackupSettings } from '@/components/BackupSettings';
+import { DiagnosticsPanel } from '@/components/DiagnosticsPanel';
+import { ThumbnailCacheSettings } from '@/components/ThumbnailCacheSettings';
+import { TranslationSettings } from '@/components/TranslationSettings';
+import { StampSettings } from '@/components/StampSettings';
+import { BeautifySettings } from '@/components/BeautifySettings';
+import { RedactionSettings } from '@/components/RedactionSettings';
+import { UploadSettings } from '@/components/UploadSettings';
+import { Onboarding } from '@/components/Onboarding';
+import type { AnimationOptions, AppConfig, AutoRedactSettings, BeautifyOptions, BurstOptions, ClickHighlight, CollageLayout, CornerOptions, ColorProfileMode, DisplayInfo, GifCaptureOptions, MetadataMode, OutputFormat, RecordingInfo, RecordingLimits, RecordingQuality, RecordingStatus, RetentionPolicy, StampSettings as StampConfig, TextDefaults, TranslationSettings as TranslationConfig, UploadSettings as UploadConfig, WebcamOverlay } from '@/types';
+
+const CLICK_HIGHLIGHTS: { value: ClickHighlight; label: string }[] = [
+  { value: 'off', label: 'Off' },
+  { value: 'ripple', label: 'Ripple' },
+  { value: 'spotlight', label: 'Spotlight' },
+];
 
 const formatHotkeyForDisplay = (hotkey: string): string => {
   return hotkey
@@ -40,6 +61,13 @@ const codeToKey = (code: string): string | null => {
 
 const MODIFIER_KEYS = new Set(['Meta', 'Control', 'Shift', 'Alt']);
 
+// The quality setting each lossy save format reads.
+const QUALITY_SETTINGS: Partial<Record<OutputFormat, 'jpeg_quality' | 'avif_quality' | 'heic_quality'>> = {
+  jpeg: 'jpeg_quality',
+  avif: 'avif_quality',
+  heic: 'heic_quality',
+};
+
 export function Dashboard() {
   const [config, setConfig] = useState<AppConfig | null>(null);
   const [originalConfig, setOriginalConfig] = useState<AppConfig | null>(null);
@@ -50,11 +78,76 @@ export function Dashboard() {
   const [editingShortcut, setEditingShortcut] = useState<string | null>(null);
   const [tempHotkey, setTempHotkey] = useState<string>('');
   const [isLoading, setIsLoading] = useState(false);
+  const [recordingStatus, setRecordingStatus] = useState<RecordingStatus | null>(null);
+  const [lastRecording, setLastRecording] = useState<RecordingInfo | null>(null);
+  const [selectedPreset, setSelectedPreset] = useState<string>('');
+  const [audioDevices, setAudioDevices] = useState<string[]>([]);
+  const [cameraDevices, setCameraDevices] = useState<string[]>([]);
+  const [fonts, setFonts] = useState<string[]>([]);
+  const [isComparing, setIsComparing] = useState(false);
+  const [isPickingWindow, setIsPickingWindow] = useState(false);
+  const [isPickingScrollWindow, setIsPickingScrollWindow] = useState(false);
+  const [isBuildingGuide, setIsBuildingGuide] = useState(false);
+  const [hookCheck, setHookCheck] = useState<string | null>(null);
+  const [countdown, setCountdown] = useState<number | null>(null);
+  const [isCapturingGif, setIsCapturingGif] = useState(false);
+  const [displays, setDisplays] = useState<DisplayInfo[]>([]);
+  const [selectedDisplay, setSelectedDisplay] = useState<number | 'all' | null>(null);
 
   useEffect(() => {
     loadConfig();
+    invoke('get_recording_status')
+      .then(setRecordingStatus)
+      .catch((err) => console.error('Failed to load recording status:', err));
+  }, []);
+
+  useEffect(() => {
+    const unlisteners: Array<Promise<() => void>> = [
+      listen('recording-progress', setRecordingStatus),
+      listen('recording-paused', setRecordingStatus),
+      listen('recording-resumed', setRecordingStatus),
+      listen('recording-stopped', (info) => {
+        setRecordingStatus(null);
+        setLastRecording(info);
+      }),
+      listen('recording-limit-reached', setError),
+      listen('capture-countdown', (remaining) => setCountdown(remaining > 0 ? remaining : null)),
+    ];
+    return () => {
+      unlisteners.forEach((unlisten) => unlisten.then((fn) => fn()).catch(() => {}));
+    };
+  }, []);
+
+  const wantsAudioDevices = Boolean(config?.record_microphone);
+  useEffect(() => {
+    if (!wantsAudioDevices) return;
+    invoke('list_audio_devices')
+      .then(setAudioDevices)
+      .catch((err) => console.error('Failed to list audio devices:', err));
+  }, [wantsAudioDevices]);
+
+  const wantsCameraDevices = Boolean(config?.webcam_overlay.enabled);
+  useEffect(() => {
+    if (!wantsCameraDevices) return;
+    invoke('list_camera_devices')
+      .then(setCameraDevices)
+      .catch((err) => console.error('Failed to list cameras:', err));
+  }, [wantsCameraDevices]);
+
+  useEffect(() => {
+    invoke('list_displays')
+      .then(setDisplays)
+      .catch((err) => console.error('Failed to list displays:', err));
+  }, []);
+
+  useEffect(() => {
+    invoke('list_fonts')
+      .then(setFonts)
+      .catch((err) => console.error('Failed to list fonts:', err));
   }, []);
 
+  const isRecording = recordingStatus?.active ?? false;
+
   const loadConfig = async () => {
     try {
       const loadedConfig = await invoke('get_config');
@@ -67,6 +160,81 @@ export function Dashboard() {
     }
   };
 
+  const handleQualityChange = (updates: Partial<RecordingQuality>) => {
+    if (!config) return;
+    handleConfigChange({ recording_quality: { ...config.recording_quality, ...updates } });
+  };
+
+  const handleLimitsChange = (updates: Partial<RecordingLimits>) => {
+    if (!config) return;
+    handleConfigChange({ recording_limits: { ...config.recording_limits, ...updates } });
+  };
+
+  const handleAnimationChange = (updates: Partial<AnimationOptions>) => {
+    if (!config) return;
+    handleConfigChange({ animation_export: { ...config.animation_export, ...updates } });
+  };
+
+  const handleBurstChange = (updates: Partial<BurstOptions>) => {
+    if (!config) return;
+    handleConfigChange({ burst_animation: { ...config.burst_animation, ...updates } });
+  };
+
+  const handleGifCaptureChange = (updates: Partial<GifCaptureOptions>) => {
+    if (!config) return;
+    handleConfigChange({ gif_capture: { ...config.gif_capture, ...updates } });
+  };
+
+  const handleTextDefaultsChange = (updates: Partial<TextDefaults>) => {
+    if (!config) return;
+    handleConfigChange({ text_annotation: { ...config.text_annotation, ...updates } });
+  };
+
+  const handleRetentionChange = (updates: Partial<RetentionPolicy>) => {
+    if (!config) return;
+    handleConfigChange({ retention: { ...config.retention, ...updates } });
+  };
+
+  const handleStampChange = (updates: Partial<StampConfig>) => {
+    if (!config) return;
+    handleConfigChange({ stamp: { ...config.stamp, ...updates } });
+  };
+
+  const handleBeautifyChange = (updates: Partial<BeautifyOptions>) => {
+    if (!config) return;
+    handleConfigChange({ beautify: { ...config.beautify, ...updates } });
+  };
+
+  const handleCornersChange = (updates: Partial<CornerOptions>) => {
+    if (!config) return;
+    handleConfigChange({ corners: { ...config.corners, ...updates } });
+  };
+
+  const handleCollageChange = (updates: Partial<CollageLayout>) => {
+    if (!config) return;
+    handleConfigChange({ collage: { ...config.collage, ...updates } });
+  };
+
+  const handleAutoRedactChange = (updates: Partial<AutoRedactSettings>) => {
+    if (!config) return;
+    handleConfigChange({ auto_redact: { ...config.auto_redact, ...updates } });
+  };
+
+  const handleTranslationChange = (updates: Partial<TranslationConfig>) => {
+    if (!config) return;
+    handleConfigChange({ translation: { ...config.translation, ...updates } });
+  };
+
+  const handleUploadChange = (updates: Partial<UploadConfig>) => {
+    if (!config) return;
+    handleConfigChange({ upload: { ...config.upload, ...updates } });
+  };
+
+  const handleWebcamChange = (updates: Partial<WebcamOverlay>) => {
+    if (!config) return;
+    handleConfigChange({ webcam_overlay: { ...config.webcam_overlay, ...updates } });
+  };
+
   const handleConfigChange = (updates: Partial<AppConfig>) => {
     if (!config || !originalConfig) return;
     
@@ -76,6 +244,15 @@ export function Dashboard() {
     setError(null);
   };
 
+  const handlePresetHighlightChange = (name: string, highlight: ClickHighlight | '') => {
+    if (!config) return;
+    handleConfigChange({
+      region_presets: config.region_presets.map((preset) =>
+        preset.name === name ? { ...preset, click_highlight: highlight || null } : preset
+      ),
+    });
+  };
+
   const handleSave = async () => {
     if (!config) return;
     
@@ -107,6 +284,58 @@ export function Dashboard() {
     }
   };
 
+  const handleAddLocation = async () => {
+    if (!config) return;
+    try {
+      const folder = await invoke('choose_save_location');
+      if (folder && folder !== config.default_save_location && !config.additional_save_locations.includes(folder)) {
+        handleConfigChange({ additional_save_locations: [...config.additional_save_locations, folder] });
+      }
+    } catch (err) {
+      console.error('Failed to choose save location:', err);
+    }
+  };
+
+  const handleChooseMirror = async () => {
+    try {
+      const folder = await invoke('choose_save_location');
+      if (folder) {
+        handleConfigChange({ mirror_location: folder });
+      }
+    } catch (err) {
+      console.error('Failed to choose network share:', err);
+    }
+  };
+
+  const handleChooseWatchFolder = async () => {
+    if (!config) return;
+    try {
+      const folder = await invoke('choose_save_location');
+      if (folder) {
+        handleConfigChange({ watch_folder: { ...config.watch_folder, folder } });
+      }
+    } catch (err) {
+      console.error('Failed to choose watch folder:', err);
+    }
+  };
+
+  const handleCheckHookScript = async () => {
+    if (!config?.hook_script) return;
+    try {
+      await invoke('check_hook_script', { path: config.hook_script });
+      setHookCheck('Script looks good');
+    } catch (err) {
+      setHookCheck(String(err));
+    }
+  };
+
+  const handleRemoveLocation = (folder: string) => {
+    if (!config) return;
+    handleConfigChange({
+      additional_save_locations: config.additional_save_locations.filter((f) => f !== folder),
+    });
+  };
+
   const startEditingShortcut = (shortcutId: string) => {
     if (!config) return;
     setEditingShortcut(shortcutId);
@@ -152,6 +381,14 @@ export function Dashboard() {
     const tauriHotkey = parseHotkeyFromDisplay([...modifiers, key].join(' '));
     if (editingShortcut === 'capture') {
       handleConfigChange({ capture_hotkey: tauriHotkey });
+    } else if (editingShortcut === 'import_clipboard') {
+      handleConfigChange({ import_clipboard_hotkey: tauriHotkey });
+    } else if (editingShortcut === 'repeat_region') {
+      handleConfigChange({ repeat_region_hotkey: tauriHotkey });
+    } else if (editingShortcut === 'capture_text') {
+      handleConfigChange({ capture_text_hotkey: tauriHotkey });
+    } else if (editingShortcut === 'quick_search') {
+      handleConfigChange({ quick_search_hotkey: tauriHotkey });
     }
     setEditingShortcut(null);
     setTempHotkey('');
@@ -169,16 +406,101 @@ export function Dashboard() {
     try {
       await invoke('capture_screenshot');
     } catch (err) {
+      if (!isCancelled(err)) setError(String(err));
       console.error('Failed to capture screenshot:', err);
     } finally {
       setIsLoading(false);
     }
   };
 
+  const handleCapturePrecise = async () => {
+    setIsLoading(true);
+    try {
+      await invoke('capture_with_selector');
+    } catch (err) {
+      if (!isCancelled(err)) setError(String(err));
+      console.error('Failed to capture with selector:', err);
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const handleCaptureLastRegion = async () => {
+    setIsLoading(true);
+    try {
+      await invoke('capture_last_region');
+    } catch (err) {
+      if (!isCancelled(err)) setError(String(err));
+      console.error('Failed to capture last region:', err);
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const handleCaptureBrowserPage = async () => {
+    setIsLoading(true);
+    try {
+      await invoke('capture_browser_page');
+    } catch (err) {
+      setError(String(err));
+      console.error('Failed to capture browser page:', err);
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const handleCaptureText = async () => {
+    setIsLoading(true);
+    try {
+      await invoke('capture_text');
+    } catch (err) {
+      if (!isCancelled(err)) setError(String(err));
+      console.error('Failed to capture text:', err);
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const handleQuickSearch = async () => {
+    try {
+      await invoke('toggle_quick_search');
+    } catch (err) {
+      setError(String(err));
+      console.error('Failed to open quick search:', err);
+    }
+  };
+
+  const handleMeasureOnScreen = async () => {
+    setIsLoading(true);
+    try {
+      const measurement = await invoke('measure_on_screen');
+      if (measurement) {
+        const round = (value: number) => Math.round(value * 10) / 10;
+        setSuccessMessage(
+          `${round(measurement.width)} x ${round(measurement.height)} px, ` +
+          `${round(measurement.distance)} px diagonal ` +
+          `(${round(measurement.width_points)} x ${round(measurement.height_points)} pt)`
+        );
+        setTimeout(() => setSuccessMessage(null), 10000);
+      }
+    } catch (err) {
+      setError(String(err));
+      console.error('Failed to measure on screen:', err);
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
   const handleCaptureFull = async () => {
     setIsLoading(true);
     try {
-      await invoke('capture_full_screen');
+      if (selectedDisplay === 'all') {
+        await invoke('capture_all_displays');
+      } else if (selectedDisplay !== null) {
+        await invoke('capture_display', { displayId: selectedDisplay });
+      } else {
+        await invoke('capture_full_screen');
+      }
     } catch (err) {
       console.error('Failed to capture full screen:', err);
     } finally {
@@ -186,6 +508,89 @@ export function Dashboard() {
     }
   };
 
+  const handleCaptureDelayed = async () => {
+    try {
+      await invoke('capture_with_delay', {});
+    } catch (err) {
+      console.error('Failed to take timed capture:', err);
+    } finally {
+      setCountdown(null);
+    }
+  };
+
+  const handleCaptureGif = async () => {
+    if (isCapturingGif) {
+      try {
+        await invoke('stop_gif_capture');
+      } catch (err) {
+        console.error('Failed to stop GIF capture:', err);
+      }
+      return;
+    }
+    setIsCapturingGif(true);
+    try {
+      await invoke('capture_gif', {});
+    } catch (err) {
+      console.error('Failed to capture GIF:', err);
+    } finally {
+      setIsCapturingGif(false);
+    }
+  };
+
+  const handleImportClipboard = async () => {
+    setIsLoading(true);
+    try {
+      await invoke('import_from_clipboard');
+    } catch (err) {
+      setError('No image found on the clipboard');
+      console.error('Failed to import from clipboard:', err);
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const handleImportFile = async () => {
+    setIsLoading(true);
+    try {
+      await invoke('import_image_file', {});
+    } catch (err) {
+      setError(String(err));
+      console.error('Failed to import image file:', err);
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const handleToggleRecording = async () => {
+    setIsLoading(true);
+    try {
+      if (isRecording) {
+        setLastRecording(await invoke('stop_recording'));
+        setRecordingStatus(null);
+      } else {
+        await invoke('start_recording', selectedPreset ? { preset: selectedPreset } : {});
+        setRecordingStatus(await invoke('get_recording_status'));
+      }
+    } catch (err) {
+      setError(String(err));
+      console.error('Failed to toggle recording:', err);
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const handleTogglePause = async () => {
+    if (!recordingStatus) return;
+    try {
+      const status = recordingStatus.paused
+        ? await invoke('resume_recording')
+        : await invoke('pause_recording');
+      setRecordingStatus(status);
+    } catch (err) {
+      console.error('Failed to pause/resume recording:', err);
+    }
+  };
+
   const handleHideWindow = async () => {
     try {
       await invoke('hide_window');
@@ -202,6 +607,12 @@ export function Dashboard() {
     );
   }
 
+  if (!config.onboarding_completed) {
+    return <Onboarding onDone={loadConfig} formatHotkey={formatHotkeyForDisplay} />;
+  }
+
+  const qualitySetting = QUALITY_SETTINGS[config.output_format];
+
   return (
     <div className="min-h-screen bg-background text-foreground">
       <div className="max-w-2xl mx-auto px-6 py-8">
@@ -259,80 +670,757 @@ export function Dashboard() {
             </Button>
 
             <Button 
-              onClick={handleCaptureFull}
+              onClick={handleCapturePrecise}
               disabled={isLoading}
               className="w-full justify-between px-4 py-6 h-auto rounded-md border border-border hover:bg-muted bg-transparent"
               variant="outline"
               size="lg"
             >
               <div className="flex items-center gap-3 text-left">
-                <Maximize className="w-5 h-5 flex-shrink-0" />
+                <Crosshair className="w-5 h-5 flex-shrink-0" />
                 <div>
-                  <div className="font-medium text-sm">Capture Full Screen</div>
+                  <div className="font-medium text-sm">Precise Selection</div>
                   <div className="text-xs text-muted-foreground mt-0.5">
-                    Entire screen
+                    Freeze the screen and select with a pixel magnifier
                   </div>
                 </div>
               </div>
             </Button>
 
             <Button 
-              onClick={handleHideWindow}
+              onClick={handleCaptureLastRegion}
+              disabled={isLoading}
               className="w-full justify-between px-4 py-6 h-auto rounded-md border border-border hover:bg-muted bg-transparent"
               variant="outline"
               size="lg"
             >
               <div className="flex items-center gap-3 text-left">
-                <EyeOff className="w-5 h-5 flex-shrink-0" />
+                <Repeat className="w-5 h-5 flex-shrink-0" />
                 <div>
-                  <div className="font-medium text-sm">Hide to Menubar</div>
+                  <div className="font-medium text-sm">Capture Last Region</div>
                   <div className="text-xs text-muted-foreground mt-0.5">
-                    Keep running in background
+                    Re-shoot the previously selected area
                   </div>
                 </div>
               </div>
+              {config.repeat_region_hotkey && (
+                <kbd className="text-xs font-mono opacity-75">
+                  {formatHotkeyForDisplay(config.repeat_region_hotkey)}
+                </kbd>
+              )}
             </Button>
-          </div>
-        </section>
 
-        {/* Configuration Section */}
-        <section className="mb-12">
-          <h2 className="text-sm font-semibold uppercase tracking-widest text-muted-foreground mb-4">Settings</h2>
+            <Button 
+              onClick={handleCaptureBrowserPage}
+              disabled={isLoading}
+              className="w-full justify-between px-4 py-6 h-auto rounded-md border border-border hover:bg-muted bg-transparent"
+              variant="outline"
+              size="lg"
+            >
+              <div className="flex items-center gap-3 text-left">
+                <Globe className="w-5 h-5 flex-shrink-0" />
+                <div>
+                  <div className="font-medium text-sm">Capture Full Web Page</div>
+                  <div className="text-xs text-muted-foreground mt-0.5">
+                    Scroll and capture the whole front Safari or Chrome tab
+                  </div>
+                </div>
+              </div>
+            </Button>
 
-          {/* Save Location */}
-          <div className="mb-8">
-            <label className="text-sm font-medium block mb-3">Save Location</label>
-            <div className="flex gap-2">
-              <input
-                type="text"
-                value={config.default_save_location}
-                readOnly
-                className="flex-1 h-10 px-3 bg-muted border border-border rounded-md text-sm"
-              />
-              <Button 
-                variant="outline" 
-                size="sm"
-                onClick={handleBrowseLocation}
-                className="rounded-md bg-transparent"
-              >
-                <Folder className="w-4 h-4 mr-2" />
-                Browse
-              </Button>
-            </div>
-          </div>
+            <Button 
+              onClick={handleCaptureText}
+              disabled={isLoading}
+              className="w-full justify-between px-4 py-6 h-auto rounded-md border border-border hover:bg-muted bg-transparent"
+              variant="outline"
+              size="lg"
+            >
+              <div className="flex items-center gap-3 text-left">
+                <ScanText className="w-5 h-5 flex-shrink-0" />
+                <div>
+                  <div className="font-medium text-sm">Capture Text</div>
+                  <div className="text-xs text-muted-foreground mt-0.5">
+                    Select an area and copy the text in it
+                  </div>
+                </div>
+              </div>
+              {config.capture_text_hotkey && (
+                <kbd className="text-xs font-mono opacity-75">
+                  {formatHotkeyForDisplay(config.capture_text_hotkey)}
+                </kbd>
+              )}
+            </Button>
 
-          {/* Hotkeys */}
-          <div className="mb-8">
-            <label className="text-sm font-medium block mb-3 flex items-center gap-2">
-              <Keyboard className="w-4 h-4" />
-              Global Hotkeys
+            <Button 
+              onClick={handleMeasureOnScreen}
+              disabled={isLoading}
+              className="w-full justify-between px-4 py-6 h-auto rounded-md border border-border hover:bg-muted bg-transparent"
+              variant="outline"
+              size="lg"
+            >
+              <div className="flex items-center gap-3 text-left">
+                <Ruler className="w-5 h-5 flex-shrink-0" />
+                <div>
+                  <div className="font-medium text-sm">Measure on Screen</div>
+                  <div className="text-xs text-muted-foreground mt-0.5">
+                    Drag out a box to read its size in pixels
+                  </div>
+                </div>
+              </div>
+            </Button>
+
+            <Button 
+              onClick={() => setIsPickingWindow((open) => !open)}
+              className="w-full justify-between px-4 py-6 h-auto rounded-md border border-border hover:bg-muted bg-transparent"
+              variant="outline"
+              size="lg"
+            >
+              <div className="flex items-center gap-3 text-left">
+                <AppWindow className="w-5 h-5 flex-shrink-0" />
+                <div>
+                  <div className="font-medium text-sm">Capture Window</div>
+                  <div className="text-xs text-muted-foreground mt-0.5">
+                    Pick an open window from a list
+                  </div>
+                </div>
+              </div>
+            </Button>
+
+            {isPickingWindow && <WindowPicker onClose={() => setIsPickingWindow(false)} />}
+
+            <Button 
+              onClick={() => setIsPickingScrollWindow((open) => !open)}
+              className="w-full justify-between px-4 py-6 h-auto rounded-md border border-border hover:bg-muted bg-transparent"
+              variant="outline"
+              size="lg"
+            >
+              <div className="flex items-center gap-3 text-left">
+                <ScrollText className="w-5 h-5 flex-shrink-0" />
+                <div>
+                  <div className="font-medium text-sm">Scrolling Capture</div>
+                  <div className="text-xs text-muted-foreground mt-0.5">
+                    Capture a whole page, scrolling as it goes
+                  </div>
+                </div>
+              </div>
+            </Button>
+
+            {isPickingScrollWindow && (
+              <WindowPicker scrolling onClose={() => setIsPickingScrollWindow(false)} />
+            )}
+
+            <Button 
+              onClick={() => setIsComparing((open) => !open)}
+              className="w-full justify-between px-4 py-6 h-auto rounded-md border border-border hover:bg-muted bg-transparent"
+              variant="outline"
+              size="lg"
+            >
+              <div className="flex items-center gap-3 text-left">
+                <Columns2 className="w-5 h-5 flex-shrink-0" />
+                <div>
+                  <div className="font-medium text-sm">Compare Screenshots</div>
+                  <div className="text-xs text-muted-foreground mt-0.5">
+                    Review two captures side by side
+                  </div>
+                </div>
+              </div>
+            </Button>
+
+            {isComparing && <ComparePicker onClose={() => setIsComparing(false)} />}
+
+            <Button 
+              onClick={() => setIsBuildingGuide((open) => !open)}
+              className="w-full justify-between px-4 py-6 h-auto rounded-md border border-border hover:bg-muted bg-transparent"
+              variant="outline"
+              size="lg"
+            >
+              <div className="flex items-center gap-3 text-left">
+                <ListOrdered className="w-5 h-5 flex-shrink-0" />
+                <div>
+                  <div className="font-medium text-sm">Build a Guide</div>
+                  <div className="text-xs text-muted-foreground mt-0.5">
+                    Turn a series of captures into step-by-step docs
+                  </div>
+                </div>
+              </div>
+            </Button>
+
+            {isBuildingGuide && <GuidePanel onClose={() => setIsBuildingGuide(false)} />}
+
+            <Button 
+              onClick={handleQuickSearch}
+              className="w-full justify-between px-4 py-6 h-auto rounded-md border border-border hover:bg-muted bg-transparent"
+              variant="outline"
+              size="lg"
+            >
+              <div className="flex items-center gap-3 text-left">
+                <Search className="w-5 h-5 flex-shrink-0" />
+                <div>
+                  <div className="font-medium text-sm">Search Screenshots</div>
+                  <div className="text-xs text-muted-foreground mt-0.5">
+                    Find a past capture by name, note or text
+                  </div>
+                </div>
+              </div>
+              {config.quick_search_hotkey && (
+                <kbd className="text-xs font-mono opacity-75">
+                  {formatHotkeyForDisplay(config.quick_search_hotkey)}
+                </kbd>
+              )}
+            </Button>
+
+            <div className="flex gap-2">
+              <Button 
+                onClick={handleCaptureFull}
+                disabled={isLoading}
+                className="flex-1 justify-between px-4 py-6 h-auto rounded-md border border-border hover:bg-muted bg-transparent"
+                variant="outline"
+                size="lg"
+              >
+                <div className="flex items-center gap-3 text-left">
+                  <Maximize className="w-5 h-5 flex-shrink-0" />
+                  <div>
+                    <div className="font-medium text-sm">Capture Full Screen</div>
+                    <div className="text-xs text-muted-foreground mt-0.5">
+                      {selectedDisplay === 'all'
+                        ? 'All displays in one image'
+                        : displays.find((display) => display.id === selectedDisplay)?.name ?? 'Entire screen'}
+                    </div>
+                  </div>
+                </div>
+              </Button>
+              {displays.length > 1 && (
+                <select
+                  value={selectedDisplay ?? ''}
+                  onChange={(e) => {
+                    const value = e.target.value;
+                    setSelectedDisplay(value === 'all' ? 'all' : value ? Number(value) : null);
+                  }}
+                  className="h-auto px-3 bg-muted border border-border rounded-md text-sm"
+                >
+                  <option value="">Main display</option>
+                  <option value="all">All displays</option>
+                  {displays.map((display) => (
+                    <option key={display.id} value={display.id}>
+                      {display.name} ({display.width}x{display.height})
+                    </option>
+                  ))}
+                </select>
+              )}
+            </div>
+
+            <Button 
+              onClick={handleCaptureDelayed}
+              disabled={isLoading || countdown !== null}
+              className="w-full justify-between px-4 py-6 h-auto rounded-md border border-border hover:bg-muted bg-transparent"
+              variant="outline"
+              size="lg"
+            >
+              <div className="flex items-center gap-3 text-left">
+                <Timer className="w-5 h-5 flex-shrink-0" />
+                <div>
+                  <div className="font-medium text-sm">Timed Capture</div>
+                  <div className="text-xs text-muted-foreground mt-0.5">
+                    {countdown !== null
+                      ? `Capturing in ${countdown}...`
+                      : `Full screen after ${config.default_capture_delay} seconds`}
+                  </div>
+                </div>
+              </div>
+            </Button>
+
+            <Button 
+              onClick={handleCaptureGif}
+              disabled={isLoading}
+              className="w-full justify-between px-4 py-6 h-auto rounded-md border border-border hover:bg-muted bg-transparent"
+              variant="outline"
+              size="lg"
+            >
+              <div className="flex items-center gap-3 text-left">
+                <Film className="w-5 h-5 flex-shrink-0" />
+                <div>
+                  <div className="font-medium text-sm">{isCapturingGif ? 'Stop GIF Capture' : 'Capture GIF'}</div>
+                  <div className="text-xs text-muted-foreground mt-0.5">
+                    {isCapturingGif
+                      ? 'Click to finish the GIF'
+                      : `Animate an area for up to ${config.gif_capture.max_seconds} seconds`}
+                  </div>
+                </div>
+              </div>
+            </Button>
+
+            <Button 
+              onClick={handleImportClipboard}
+              disabled={isLoading}
+              className="w-full justify-between px-4 py-6 h-auto rounded-md border border-border hover:bg-muted bg-transparent"
+              variant="outline"
+              size="lg"
+            >
+              <div className="flex items-center gap-3 text-left">
+                <ClipboardPaste className="w-5 h-5 flex-shrink-0" />
+                <div>
+                  <div className="font-medium text-sm">Import from Clipboard</div>
+                  <div className="text-xs text-muted-foreground mt-0.5">
+                    Preview, annotate or save a copied image
+                  </div>
+                </div>
+              </div>
+              {config.import_clipboard_hotkey && (
+                <kbd className="text-xs font-mono opacity-75">
+                  {formatHotkeyForDisplay(config.import_clipboard_hotkey)}
+                </kbd>
+              )}
+            </Button>
+
+            <Button 
+              onClick={handleImportFile}
+              disabled={isLoading}
+              className="w-full justify-between px-4 py-6 h-auto rounded-md border border-border hover:bg-muted bg-transparent"
+              variant="outline"
+              size="lg"
+            >
+              <div className="flex items-center gap-3 text-left">
+                <ImageUp className="w-5 h-5 flex-shrink-0" />
+                <div>
+                  <div className="font-medium text-sm">Open Image</div>
+                  <div className="text-xs text-muted-foreground mt-0.5">
+                    Annotate and share an existing PNG or JPEG
+                  </div>
+                </div>
+              </div>
+            </Button>
+
+            <div className="flex gap-2">
+              <Button 
+                onClick={handleToggleRecording}
+                disabled={isLoading}
+                className="flex-1 justify-between px-4 py-6 h-auto rounded-md border border-border hover:bg-muted bg-transparent"
+                variant="outline"
+                size="lg"
+              >
+                <div className="flex items-center gap-3 text-left">
+                  {isRecording ? (
+                    <Square className="w-5 h-5 flex-shrink-0" />
+                  ) : (
+                    <Video className="w-5 h-5 flex-shrink-0" />
+                  )}
+                  <div>
+                    <div className="font-medium text-sm">
+                      {isRecording ? 'Stop Recording' : 'Record Screen'}
+                    </div>
+                    <div className="text-xs text-muted-foreground mt-0.5">
+                      {recordingStatus?.active
+                        ? `${Math.floor(recordingStatus.recorded_ms / 1000)}s recorded${recordingStatus.paused ? ' (paused)' : ''}`
+                        : selectedPreset || 'Entire screen'}
+                    </div>
+                  </div>
+                </div>
+              </Button>
+              {isRecording && (
+                <Button
+                  onClick={handleTogglePause}
+                  className="h-auto px-4 rounded-md border border-border hover:bg-muted bg-transparent"
+                  variant="outline"
+                >
+                  {recordingStatus?.paused ? <Play className="w-5 h-5" /> : <Pause className="w-5 h-5" />}
+                </Button>
+              )}
+              {config.region_presets.length > 0 && (
+                <select
+                  value={selectedPreset}
+                  onChange={(e) => setSelectedPreset(e.target.value)}
+                  disabled={isRecording}
+                  className="h-auto px-3 bg-muted border border-border rounded-md text-sm"
+                >
+                  <option value="">Entire screen</option>
+                  {config.region_presets.map((preset) => (
+                    <option key={preset.name} value={preset.name}>{preset.name}</option>
+                  ))}
+                </select>
+              )}
+            </div>
+
+            {selectedPreset && !isRecording && <BaselinePanel preset={selectedPreset} />}
+
+            {lastRecording && !isRecording && lastRecording.duration_ms > 0 && (
+              <RecordingTrimmer
+                recording={lastRecording}
+                onTrimmed={setLastRecording}
+                onClose={() => setLastRecording(null)}
+              />
+            )}
+
+            <Button 
+              onClick={handleHideWindow}
+              className="w-full justify-between px-4 py-6 h-auto rounded-md border border-border hover:bg-muted bg-transparent"
+              variant="outline"
+              size="lg"
+            >
+              <div className="flex items-center gap-3 text-left">
+                <EyeOff className="w-5 h-5 flex-shrink-0" />
+                <div>
+                  <div className="font-medium text-sm">Hide to Menubar</div>
+                  <div className="text-xs text-muted-foreground mt-0.5">
+                    Keep running in background
+                  </div>
+                </div>
+              </div>
+            </Button>
+          </div>
+        </section>
+
+        {/* Configuration Section */}
+        <section className="mb-12">
+          <h2 className="text-sm font-semibold uppercase tracking-widest text-muted-foreground mb-4">Settings</h2>
+
+          {/* Save Location */}
+          <div className="mb-8">
+            <label className="text-sm font-medium block mb-3">Save Location</label>
+            <div className="flex gap-2">
+              <input
+                type="text"
+                value={config.default_save_location}
+                readOnly
+                className="flex-1 h-10 px-3 bg-muted border border-border rounded-md text-sm"
+              />
+              <Button 
+                variant="outline" 
+                size="sm"
+                onClick={handleBrowseLocation}
+                className="rounded-md bg-transparent"
+              >
+                <Folder className="w-4 h-4 mr-2" />
+                Browse
+              </Button>
+            </div>
+            {config.additional_save_locations.map((folder) => (
+              <div key={folder} className="flex gap-2 mt-2">
+                <input
+                  type="text"
+                  value={folder}
+                  readOnly
+                  className="flex-1 h-10 px-3 bg-muted border border-border rounded-md text-sm"
+                />
+                <Button
+                  variant="outline"
+                  size="sm"
+                  onClick={() => handleRemoveLocation(folder)}
+                  className="rounded-md bg-transparent"
+                >
+                  <X className="w-4 h-4" />
+                </Button>
+              </div>
+            ))}
+            <button
+              onClick={handleAddLocation}
+              className="mt-2 text-xs text-muted-foreground hover:text-foreground"
+            >
+              + Also save to another folder
+            </button>
+          </div>
+
+          {/* Network Mirror */}
+          <div className="mb-8">
+            <label className="text-sm font-medium block mb-3">Network Share Mirror</label>
+            <div className="flex gap-2">
+              <input
+                type="text"
+                value={config.mirror_location}
+                readOnly
+                placeholder="Off"
+                className="flex-1 h-10 px-3 bg-muted border border-border rounded-md text-sm"
+              />
+              <Button
+                variant="outline"
+                size="sm"
+                onClick={handleChooseMirror}
+                className="rounded-md bg-transparent"
+              >
+                <Folder className="w-4 h-4 mr-2" />
+                Browse
+              </Button>
+              {config.mirror_location && (
+                <Button
+                  variant="outline"
+                  size="sm"
+                  onClick={() => handleConfigChange({ mirror_location: '' })}
+                  className="rounded-md bg-transparent"
+                >
+                  <X className="w-4 h-4" />
+                </Button>
+              )}
+            </div>
+            <div className="text-xs text-muted-foreground mt-2">
+              A folder on a mounted SMB or NFS share. While it is unmounted, copies wait and are sent when it comes back.
+            </div>
+          </div>
+
+          {/* Watch Folder */}
+          <div className="mb-8">
+            <label className="text-sm font-medium block mb-3">Watch Folder</label>
+            <label className="flex items-center gap-3 p-4 border border-border rounded-md cursor-pointer hover:bg-muted/50 transition-colors mb-2">
+              <input
+                type="checkbox"
+                checked={config.watch_folder.enabled}
+                onChange={(e) =>
+                  handleConfigChange({ watch_folder: { ...config.watch_folder, enabled: e.target.checked } })
+                }
+                className="w-4 h-4 accent-accent"
+              />
+              <div className="flex-1">
+                <div className="text-sm font-medium">Import screenshots taken outside Snipp</div>
+                <div className="text-xs text-muted-foreground mt-1">
+                  New images in this folder, like Cmd+Shift+3 captures, are added to history and search
+                </div>
+              </div>
+            </label>
+            <div className="flex gap-2">
+              <input
+                type="text"
+                value={config.watch_folder.folder}
+                readOnly
+                placeholder="macOS screenshot location"
+                className="flex-1 h-10 px-3 bg-muted border border-border rounded-md text-sm"
+              />
+              <Button
+                variant="outline"
+                size="sm"
+                onClick={handleChooseWatchFolder}
+                className="rounded-md bg-transparent"
+              >
+                <Folder className="w-4 h-4 mr-2" />
+                Browse
+              </Button>
+              {config.watch_folder.folder && (
+                <Button
+                  variant="outline"
+                  size="sm"
+                  onClick={() => handleConfigChange({ watch_folder: { ...config.watch_folder, folder: '' } })}
+                  className="rounded-md bg-transparent"
+                >
+                  <X className="w-4 h-4" />
+                </Button>
+              )}
+            </div>
+          </div>
+
+          {/* Hook Script */}
+          <div className="mb-8">
+            <label className="text-sm font-medium block mb-3">Hook Script</label>
+            <div className="flex gap-2">
+              <input
+                type="text"
+                value={config.hook_script}
+                onChange={(e) => {
+                  setHookCheck(null);
+                  handleConfigChange({ hook_script: e.target.value });
+                }}
+                placeholder="/path/to/hooks.rhai"
+                className="flex-1 h-10 px-3 bg-muted border border-border rounded-md text-sm"
+              />
+              <Button
+                variant="outline"
+                size="sm"
+                onClick={handleCheckHookScript}
+                disabled={!config.hook_script}
+                className="rounded-md bg-transparent"
+              >
+                Check
+              </Button>
+            </div>
+            <div className="text-xs text-muted-foreground mt-2">
+              {hookCheck ??
+                'A Rhai script defining on_capture, on_save or on_upload(capture). Hooks can rename, set_destination and add_tag.'}
+            </div>
+          </div>
+
+          {/* File Names */}
+          <div className="mb-8">
+            <label className="text-sm font-medium block mb-3">File Names</label>
+            <input
+              type="text"
+              value={config.filename_template}
+              onChange={(e) => handleConfigChange({ filename_template: e.target.value })}
+              placeholder="Snipp %y-%m-%d at %H.%M.%S"
+              className="w-full h-10 px-3 bg-muted border border-border rounded-md text-sm font-mono"
+            />
+            <div className="text-xs text-muted-foreground mt-2">
+              Date codes like <code>%Y-%m-%d</code>, plus <code>{'{seq}'}</code> for a counter that restarts each day,
+              e.g. <code>{'snipp-%Y-%m-%d-{seq}'}</code>. Auto-cleanup only removes names starting with "Snipp ".
+            </div>
+          </div>
+
+          {/* File Format */}
+          <div className="mb-8">
+            <label className="text-sm font-medium block mb-3">File Format</label>
+            <div className="flex gap-2">
+              <select
+                value={config.output_format}
+                onChange={(e) => handleConfigChange({ output_format: e.target.value as OutputFormat })}
+                className="flex-1 h-10 px-3 bg-muted border border-border rounded-md text-sm"
+              >
+                <option value="png">PNG</option>
+                <option value="jpeg">JPEG</option>
+                <option value="webp">WebP (lossless)</option>
+                <option value="tiff">TIFF</option>
+                <option value="avif">AVIF</option>
+                <option value="heic">HEIC</option>
+              </select>
+              {qualitySetting && (
+                <select
+                  value={config[qualitySetting]}
+                  onChange={(e) => handleConfigChange({ [qualitySetting]: Number(e.target.value) } as Partial<AppConfig>)}
+                  className="h-10 px-3 bg-muted border border-border rounded-md text-sm"
+                >
+                  {[50, 60, 70, 75, 80, 85, 90, 100].map((quality) => (
+                    <option key={quality} value={quality}>Quality {quality}</option>
+                  ))}
+                </select>
+              )}
+            </div>
+            <div className="text-xs text-muted-foreground mt-2">
+              Captures are converted when saved or dragged out. Only PNG keeps the display color profile.
+              AVIF and HEIC are far smaller; AVIF takes a few seconds to encode large captures.
+            </div>
+          </div>
+
+          {/* Hotkeys */}
+          <div className="mb-8">
+            <label className="text-sm font-medium block mb-3 flex items-center gap-2">
+              <Keyboard className="w-4 h-4" />
+              Global Hotkeys
             </label>
             
             <div className="space-y-2">
               {/* Capture Hotkey */}
               <div className="flex items-center justify-between p-4 border border-border rounded-md hover:bg-muted/50 transition-colors">
-                <span className="text-sm">Capture Screenshot</span>
-                {editingShortcut === 'capture' ? (
+                <span className="text-sm">Capture Screenshot</span>
+                {editingShortcut === 'capture' ? (
+                  <div className="flex items-center gap-2">
+                    <span className="text-sm text-accent animate-pulse font-mono">
+                      {tempHotkey || 'Press keys...'}
+                    </span>
+                    <Button 
+                      variant="ghost" 
+                      size="sm"
+                      onClick={cancelEditingShortcut}
+                      className="h-6 w-6 p-0"
+                    >
+                      <X className="w-4 h-4" />
+                    </Button>
+                  </div>
+                ) : (
+                  <Button 
+                    variant="ghost" 
+                    size="sm"
+                    onClick={() => startEditingShortcut('capture')}
+                    className="font-mono text-sm h-8 px-2"
+                  >
+                    {formatHotkeyForDisplay(config.capture_hotkey)}
+                    <Edit3 className="w-3 h-3 ml-2" />
+                  </Button>
+                )}
+              </div>
+
+              {/* Clipboard Import Hotkey */}
+              <div className="flex items-center justify-between p-4 border border-border rounded-md hover:bg-muted/50 transition-colors">
+                <span className="text-sm">Import from Clipboard</span>
+                {editingShortcut === 'import_clipboard' ? (
+                  <div className="flex items-center gap-2">
+                    <span className="text-sm text-accent animate-pulse font-mono">
+                      {tempHotkey || 'Press keys...'}
+                    </span>
+                    <Button 
+                      variant="ghost" 
+                      size="sm"
+                      onClick={cancelEditingShortcut}
+                      className="h-6 w-6 p-0"
+                    >
+                      <X className="w-4 h-4" />
+                    </Button>
+                  </div>
+                ) : (
+                  <Button 
+                    variant="ghost" 
+                    size="sm"
+                    onClick={() => startEditingShortcut('import_clipboard')}
+                    className="font-mono text-sm h-8 px-2"
+                  >
+                    {config.import_clipboard_hotkey
+                      ? formatHotkeyForDisplay(config.import_clipboard_hotkey)
+                      : 'Not set'}
+                    <Edit3 className="w-3 h-3 ml-2" />
+                  </Button>
+                )}
+              </div>
+
+              {/* Repeat Last Region Hotkey */}
+              <div className="flex items-center justify-between p-4 border border-border rounded-md hover:bg-muted/50 transition-colors">
+                <span className="text-sm">Capture Last Region</span>
+                {editingShortcut === 'repeat_region' ? (
+                  <div className="flex items-center gap-2">
+                    <span className="text-sm text-accent animate-pulse font-mono">
+                      {tempHotkey || 'Press keys...'}
+                    </span>
+                    <Button 
+                      variant="ghost" 
+                      size="sm"
+                      onClick={cancelEditingShortcut}
+                      className="h-6 w-6 p-0"
+                    >
+                      <X className="w-4 h-4" />
+                    </Button>
+                  </div>
+                ) : (
+                  <Button 
+                    variant="ghost" 
+                    size="sm"
+                    onClick={() => startEditingShortcut('repeat_region')}
+                    className="font-mono text-sm h-8 px-2"
+                  >
+                    {config.repeat_region_hotkey
+                      ? formatHotkeyForDisplay(config.repeat_region_hotkey)
+                      : 'Not set'}
+                    <Edit3 className="w-3 h-3 ml-2" />
+                  </Button>
+                )}
+              </div>
+
+              {/* Capture Text Hotkey */}
+              <div className="flex items-center justify-between p-4 border border-border rounded-md hover:bg-muted/50 transition-colors">
+                <span className="text-sm">Capture Text</span>
+                {editingShortcut === 'capture_text' ? (
+                  <div className="flex items-center gap-2">
+                    <span className="text-sm text-accent animate-pulse font-mono">
+                      {tempHotkey || 'Press keys...'}
+                    </span>
+                    <Button 
+                      variant="ghost" 
+                      size="sm"
+                      onClick={cancelEditingShortcut}
+                      className="h-6 w-6 p-0"
+                    >
+                      <X className="w-4 h-4" />
+                    </Button>
+                  </div>
+                ) : (
+                  <Button 
+                    variant="ghost" 
+                    size="sm"
+                    onClick={() => startEditingShortcut('capture_text')}
+                    className="font-mono text-sm h-8 px-2"
+                  >
+                    {config.capture_text_hotkey
+                      ? formatHotkeyForDisplay(config.capture_text_hotkey)
+                      : 'Not set'}
+                    <Edit3 className="w-3 h-3 ml-2" />
+                  </Button>
+                )}
+              </div>
+
+              <div className="flex items-center justify-between p-4 border border-border rounded-md hover:bg-muted/50 transition-colors">
+                <span className="text-sm">Search Screenshots</span>
+                {editingShortcut === 'quick_search' ? (
                   <div className="flex items-center gap-2">
                     <span className="text-sm text-accent animate-pulse font-mono">
                       {tempHotkey || 'Press keys...'}
@@ -350,10 +1438,12 @@ export function Dashboard() {
                   <Button 
                     variant="ghost" 
                     size="sm"
-                    onClick={() => startEditingShortcut('capture')}
+                    onClick={() => startEditingShortcut('quick_search')}
                     className="font-mono text-sm h-8 px-2"
                   >
-                    {formatHotkeyForDisplay(config.capture_hotkey)}
+                    {config.quick_search_hotkey
+                      ? formatHotkeyForDisplay(config.quick_search_hotkey)
+                      : 'Not set'}
                     <Edit3 className="w-3 h-3 ml-2" />
                   </Button>
                 )}
@@ -378,6 +1468,137 @@ export function Dashboard() {
               </div>
             </label>
 
+            <label className="flex items-center gap-3 p-4 border border-border rounded-md cursor-pointer hover:bg-muted/50 transition-colors">
+              <input
+                type="checkbox"
+                checked={config.show_capture_popup}
+                onChange={(e) => handleConfigChange({ show_capture_popup: e.target.checked })}
+                className="w-4 h-4 accent-accent"
+              />
+              <div className="flex-1">
+                <div className="text-sm font-medium">Show preview after capture</div>
+                <div className="text-xs text-muted-foreground mt-1">
+                  When off, screenshots are saved straight away with no popup
+                </div>
+              </div>
+            </label>
+
+            <label className="flex items-center gap-3 p-4 border border-border rounded-md cursor-pointer hover:bg-muted/50 transition-colors">
+              <input
+                type="checkbox"
+                checked={config.selection_overlay}
+                onChange={(e) => handleConfigChange({ selection_overlay: e.target.checked })}
+                className="w-4 h-4 accent-accent"
+              />
+              <div className="flex-1">
+                <div className="text-sm font-medium">Snipp selection overlay</div>
+                <div className="text-xs text-muted-foreground mt-1">
+                  Freeze the screen for area captures, with size readout, aspect lock and reselection
+                </div>
+              </div>
+            </label>
+
+            <label className="flex items-center gap-3 p-4 border border-border rounded-md cursor-pointer hover:bg-muted/50 transition-colors">
+              <input
+                type="checkbox"
+                checked={config.window_shadow}
+                onChange={(e) => handleConfigChange({ window_shadow: e.target.checked })}
+                className="w-4 h-4 accent-accent"
+              />
+              <div className="flex-1">
+                <div className="text-sm font-medium">Window shadows</div>
+                <div className="text-xs text-muted-foreground mt-1">
+                  Include the shadow when picking a window with Space
+                </div>
+              </div>
+            </label>
+
+            <label className="flex items-center gap-3 p-4 border border-border rounded-md cursor-pointer hover:bg-muted/50 transition-colors">
+              <input
+                type="checkbox"
+                checked={config.downscale_retina}
+                onChange={(e) => handleConfigChange({ downscale_retina: e.target.checked })}
+                className="w-4 h-4 accent-accent"
+              />
+              <div className="flex-1">
+                <div className="text-sm font-medium">Save Retina captures at 1x</div>
+                <div className="text-xs text-muted-foreground mt-1">
+                  Shrink captures from high-density displays to their size on screen
+                </div>
+              </div>
+            </label>
+
+            <div className="flex items-center gap-3 p-4 border border-border rounded-md">
+              <div className="flex-1">
+                <div className="text-sm font-medium">Timed capture delay</div>
+                <div className="text-xs text-muted-foreground mt-1">
+                  Countdown before Timed Capture takes the full screen
+                </div>
+              </div>
+              <input
+                type="number"
+                min={1}
+                max={60}
+                value={config.default_capture_delay}
+                onChange={(e) =>
+                  handleConfigChange({
+                    default_capture_delay: Math.min(60, Math.max(1, Math.round(Number(e.target.value)))),
+                  })
+                }
+                className="w-20 h-9 px-2 bg-muted border border-border rounded-md text-sm"
+              />
+              <span className="text-sm text-muted-foreground">s</span>
+            </div>
+
+            {config.show_capture_popup && (
+              <label className="flex items-center gap-3 p-4 border border-border rounded-md cursor-pointer hover:bg-muted/50 transition-colors">
+                <input
+                  type="checkbox"
+                  checked={config.tray_popover}
+                  onChange={(e) => handleConfigChange({ tray_popover: e.target.checked })}
+                  className="w-4 h-4 accent-accent"
+                />
+                <div className="flex-1">
+                  <div className="text-sm font-medium">Preview in the menu bar</div>
+                  <div className="text-xs text-muted-foreground mt-1">
+                    Show the capture under the menu bar icon with copy, save and edit, instead of the corner popup
+                  </div>
+                </div>
+              </label>
+            )}
+
+            {config.show_capture_popup && (
+              <label className="flex items-center gap-3 p-4 border border-border rounded-md cursor-pointer hover:bg-muted/50 transition-colors">
+                <input
+                  type="checkbox"
+                  checked={config.restore_focus_after_capture}
+                  onChange={(e) => handleConfigChange({ restore_focus_after_capture: e.target.checked })}
+                  className="w-4 h-4 accent-accent"
+                />
+                <div className="flex-1">
+                  <div className="text-sm font-medium">Keep typing after capture</div>
+                  <div className="text-xs text-muted-foreground mt-1">
+                    Return focus to the app you were using once the preview appears
+                  </div>
+                </div>
+              </label>
+            )}
+
+            <label className="flex items-center gap-3 p-4 border border-border rounded-md cursor-pointer hover:bg-muted/50 transition-colors">
+              <input
+                type="checkbox"
+                checked={config.announce_actions}
+                onChange={(e) => handleConfigChange({ announce_actions: e.target.checked })}
+                className="w-4 h-4 accent-accent"
+              />
+              <div className="flex-1">
+                <div className="text-sm font-medium">Announce results</div>
+                <div className="text-xs text-muted-foreground mt-1">
+                  VoiceOver speaks when a screenshot is captured, copied or saved
+                </div>
+              </div>
+            </label>
+
             <label className="flex items-center gap-3 p-4 border border-border rounded-md cursor-pointer hover:bg-muted/50 transition-colors">
               <input
                 type="checkbox"
@@ -392,6 +1613,556 @@ export function Dashboard() {
                 </div>
               </div>
             </label>
+
+            <label className="flex items-center gap-3 p-4 border border-border rounded-md cursor-pointer hover:bg-muted/50 transition-colors">
+              <input
+                type="checkbox"
+                checked={config.index_capture_text}
+                onChange={(e) => handleConfigChange({ index_capture_text: e.target.checked })}
+                className="w-4 h-4 accent-accent"
+              />
+              <div className="flex-1">
+                <div className="text-sm font-medium">Make text searchable</div>
+                <div className="text-xs text-muted-foreground mt-1">
+                  Recognize text in saved screenshots for search
+                </div>
+              </div>
+            </label>
+
+            <label className="flex items-center gap-3 p-4 border border-border rounded-md cursor-pointer hover:bg-muted/50 transition-colors">
+              <input
+                type="checkbox"
+                checked={config.write_sidecar}
+                onChange={(e) => handleConfigChange({ write_sidecar: e.target.checked })}
+                className="w-4 h-4 accent-accent"
+              />
+              <div className="flex-1">
+                <div className="text-sm font-medium">Write metadata files</div>
+                <div className="text-xs text-muted-foreground mt-1">
+                  Save name.png.json with time, size, area, display, source app, window title and text for other tools
+                </div>
+              </div>
+            </label>
+
+            <label className="flex items-center gap-3 p-4 border border-border rounded-md cursor-pointer hover:bg-muted/50 transition-colors">
+              <input
+                type="checkbox"
+                checked={config.secure_delete}
+                onChange={(e) => handleConfigChange({ secure_delete: e.target.checked })}
+                className="w-4 h-4 accent-accent"
+              />
+              <div className="flex-1">
+                <div className="text-sm font-medium">Secure delete</div>
+                <div className="text-xs text-muted-foreground mt-1">
+                  Overwrite deleted screenshots, their thumbnails and captures cached on disk before removing them
+                </div>
+              </div>
+            </label>
+
+            <div className="flex items-center gap-3 p-4 border border-border rounded-md">
+              <div className="flex-1">
+                <div className="text-sm font-medium">Memory for unsaved captures</div>
+                <div className="text-xs text-muted-foreground mt-1">
+                  Past this, the least recently used captures move to disk until they're saved or closed
+                </div>
+              </div>
+              <input
+                type="number"
+                min={32}
+                max={8192}
+                step={32}
+                value={config.cache_limit_mb}
+                onChange={(e) => handleConfigChange({ cache_limit_mb: Math.max(32, Number(e.target.value)) })}
+                className="w-24 h-9 px-2 bg-muted border border-border rounded-md text-sm"
+              />
+              <span className="text-sm text-muted-foreground">MB</span>
+            </div>
+
+            <div className="flex items-center gap-3 p-4 border border-border rounded-md">
+              <div className="flex-1">
+                <div className="text-sm font-medium">Colors</div>
+                <div className="text-xs text-muted-foreground mt-1">
+                  Keep the display profile, or convert saved screenshots to sRGB for apps that ignore profiles
+                </div>
+              </div>
+              <select
+                value={config.color_profile}
+                onChange={(e) => handleConfigChange({ color_profile: e.target.value as ColorProfileMode })}
+                className="h-10 px-3 bg-muted border border-border rounded-md text-sm"
+              >
+                <option value="preserve">Keep display profile</option>
+                <option value="convert_to_srgb">Convert to sRGB</option>
+              </select>
+            </div>
+
+            <div className="flex items-center gap-3 p-4 border border-border rounded-md">
+              <div className="flex-1">
+                <div className="text-sm font-medium">Metadata</div>
+                <div className="text-xs text-muted-foreground mt-1">
+                  Strip text and EXIF from saved screenshots, or record when, where and in which app they were taken
+                </div>
+              </div>
+              <select
+                value={config.metadata}
+                onChange={(e) => handleConfigChange({ metadata: e.target.value as MetadataMode })}
+                className="h-10 px-3 bg-muted border border-border rounded-md text-sm"
+              >
+                <option value="keep">Keep as captured</option>
+                <option value="strip">Strip</option>
+                <option value="embed">Embed provenance</option>
+              </select>
+            </div>
+          </div>
+
+          {/* Text Annotation Defaults */}
+          <div className="mt-8 p-4 border border-border rounded-md space-y-3">
+            <div>
+              <div className="text-sm font-medium">Text annotations</div>
+              <div className="text-xs text-muted-foreground mt-1">
+                Starting font, size and color for text in the editor
+              </div>
+            </div>
+            <select
+              value={config.text_annotation.font_family}
+              onChange={(e) => handleTextDefaultsChange({ font_family: e.target.value })}
+              className="w-full h-10 px-3 bg-muted border border-border rounded-md text-sm"
+            >
+              <option value="">Default (Inter)</option>
+              {fonts.map((family) => (
+                <option key={family} value={family}>{family}</option>
+              ))}
+            </select>
+            <div className="flex items-center gap-3 text-sm">
+              <span className="text-muted-foreground">Size</span>
+              <input
+                type="number"
+                min={8}
+                max={200}
+                value={config.text_annotation.font_size}
+                onChange={(e) => handleTextDefaultsChange({ font_size: Math.max(8, Number(e.target.value)) })}
+                className="w-20 h-9 px-2 bg-muted border border-border rounded-md text-sm"
+              />
+              <span className="text-muted-foreground ml-auto">Color</span>
+              <input
+                type="color"
+                value={config.text_annotation.color}
+                onChange={(e) => handleTextDefaultsChange({ color: e.target.value })}
+                className="w-9 h-9 bg-transparent border border-border rounded-md cursor-pointer"
+              />
+            </div>
+          </div>
+
+          {/* Baseline Checks */}
+          <div className="mt-8 p-4 border border-border rounded-md space-y-3">
+            <div>
+              <div className="text-sm font-medium">Baseline checks</div>
+              <div className="text-xs text-muted-foreground mt-1">
+                A preset capture fails when more pixels than this changed from its baseline
+              </div>
+            </div>
+            <div className="flex items-center gap-3 text-sm">
+              <span className="text-muted-foreground">Threshold</span>
+              <input
+                type="number"
+                min={0}
+                max={100}
+                step={0.1}
+                value={config.regression_threshold_percent}
+                onChange={(e) =>
+                  handleConfigChange({
+                    regression_threshold_percent: Math.min(100, Math.max(0, Number(e.target.value))),
+                  })
+                }
+                className="w-20 h-9 px-2 bg-muted border border-border rounded-md text-sm"
+              />
+              <span className="text-muted-foreground">%</span>
+            </div>
+          </div>
+
+          {/* Stamp */}
+          <StampSettings
+            settings={config.stamp}
+            onChange={handleStampChange}
+          />
+
+          {/* Beautify */}
+          <BeautifySettings
+            options={config.beautify}
+            corners={config.corners}
+            collage={config.collage}
+            onChange={handleBeautifyChange}
+            onCornersChange={handleCornersChange}
+            onCollageChange={handleCollageChange}
+          />
+
+          {/* Auto-redact */}
+          <RedactionSettings
+            settings={config.auto_redact}
+            onChange={handleAutoRedactChange}
+          />
+
+          {/* Retention */}
+          <RetentionSettings
+            policy={config.retention}
+            hasUnsavedChanges={hasChanges}
+            onChange={handleRetentionChange}
+          />
+
+          {/* Thumbnail Cache */}
+          <ThumbnailCacheSettings />
+
+          {/* Translation */}
+          <TranslationSettings
+            settings={config.translation}
+            onChange={handleTranslationChange}
+          />
+
+          {/* Upload */}
+          <UploadSettings
+            settings={config.upload}
+            onChange={handleUploadChange}
+          />
+
+          {/* Backup */}
+          <BackupSettings onRestored={loadConfig} />
+
+          {/* Diagnostics */}
+          <DiagnosticsPanel
+            logLevel={config.log_level}
+            onLogLevelChange={(log_level) => handleConfigChange({ log_level })}
+          />
+
+          {/* Recording Options */}
+          <div className="mt-8 space-y-2">
+            <label className="flex items-center gap-3 p-4 border border-border rounded-md cursor-pointer hover:bg-muted/50 transition-colors">
+              <input
+                type="checkbox"
+                checked={config.record_microphone}
+                onChange={(e) => handleConfigChange({ record_microphone: e.target.checked })}
+                className="w-4 h-4 accent-accent"
+              />
+              <div className="flex-1">
+                <div className="text-sm font-medium">Record microphone</div>
+                <div className="text-xs text-muted-foreground mt-1">
+                  Include narration in screen recordings
+                </div>
+              </div>
+            </label>
+
+            {config.record_microphone && (
+              <select
+                value={config.microphone_device}
+                onChange={(e) => handleConfigChange({ microphone_device: e.target.value })}
+                className="w-full h-10 px-3 bg-muted border border-border rounded-md text-sm"
+              >
+                <option value="">System default</option>
+                {audioDevices.map((device) => (
+                  <option key={device} value={device}>{device}</option>
+                ))}
+              </select>
+            )}
+
+            <label className="flex items-center gap-3 p-4 border border-border rounded-md cursor-pointer hover:bg-muted/50 transition-colors">
+              <input
+                type="checkbox"
+                checked={config.record_system_audio}
+                onChange={(e) => handleConfigChange({ record_system_audio: e.target.checked })}
+                className="w-4 h-4 accent-accent"
+              />
+              <div className="flex-1">
+                <div className="text-sm font-medium">Record system audio</div>
+                <div className="text-xs text-muted-foreground mt-1">
+                  App sounds, captured with ScreenCaptureKit (macOS 13 or later)
+                </div>
+              </div>
+            </label>
+
+            <div className="p-4 border border-border rounded-md space-y-3">
+              <div>
+                <div className="text-sm font-medium">Recording quality</div>
+                <div className="text-xs text-muted-foreground mt-1">
+                  Lower settings produce smaller files
+                </div>
+              </div>
+              <div className="grid grid-cols-2 gap-2">
+                <select
+                  value={config.recording_quality.frame_rate}
+                  onChange={(e) => handleQualityChange({ frame_rate: Number(e.target.value) })}
+                  className="h-10 px-3 bg-muted border border-border rounded-md text-sm"
+                >
+                  {[15, 24, 30, 60].map((fps) => (
+                    <option key={fps} value={fps}>{fps} fps</option>
+                  ))}
+                </select>
+                <select
+                  value={config.recording_quality.scale}
+                  onChange={(e) => handleQualityChange({ scale: Number(e.target.value) })}
+                  className="h-10 px-3 bg-muted border border-border rounded-md text-sm"
+                >
+                  <option value={1}>Full resolution</option>
+                  <option value={0.75}>75% resolution</option>
+                  <option value={0.5}>50% resolution</option>
+                </select>
+                <label className="flex items-center gap-2 text-sm">
+                  <span className="text-muted-foreground">CRF</span>
+                  <input
+                    type="number"
+                    min={0}
+                    max={51}
+                    value={config.recording_quality.crf}
+                    disabled={config.recording_quality.bitrate_kbps > 0}
+                    onChange={(e) => handleQualityChange({ crf: Number(e.target.value) })}
+                    className="w-full h-10 px-3 bg-muted border border-border rounded-md text-sm disabled:opacity-50"
+                  />
+                </label>
+                <label className="flex items-center gap-2 text-sm">
+                  <span className="text-muted-foreground whitespace-nowrap">Bitrate (kbps)</span>
+                  <input
+                    type="number"
+                    min={0}
+                    step={500}
+                    value={config.recording_quality.bitrate_kbps}
+                    onChange={(e) => handleQualityChange({ bitrate_kbps: Number(e.target.value) })}
+                    placeholder="0 = use CRF"
+                    className="w-full h-10 px-3 bg-muted border border-border rounded-md text-sm"
+                  />
+                </label>
+              </div>
+            </div>
+
+            <div className="p-4 border border-border rounded-md space-y-3">
+              <div>
+                <div className="text-sm font-medium">Recording limits</div>
+                <div className="text-xs text-muted-foreground mt-1">
+                  Stop automatically so a forgotten recording can't fill the disk. 0 means no limit.
+                </div>
+              </div>
+              <div className="grid grid-cols-2 gap-2">
+                <label className="flex items-center gap-2 text-sm">
+                  <span className="text-muted-foreground whitespace-nowrap">Max minutes</span>
+                  <input
+                    type="number"
+                    min={0}
+                    value={Math.round(config.recording_limits.max_duration_secs / 60)}
+                    onChange={(e) => handleLimitsChange({ max_duration_secs: Number(e.target.value) * 60 })}
+                    className="w-full h-10 px-3 bg-muted border border-border rounded-md text-sm"
+                  />
+                </label>
+                <label className="flex items-center gap-2 text-sm">
+                  <span className="text-muted-foreground whitespace-nowrap">Max MB</span>
+                  <input
+                    type="number"
+                    min={0}
+                    step={256}
+                    value={config.recording_limits.max_file_size_mb}
+                    onChange={(e) => handleLimitsChange({ max_file_size_mb: Number(e.target.value) })}
+                    className="w-full h-10 px-3 bg-muted border border-border rounded-md text-sm"
+                  />
+                </label>
+              </div>
+            </div>
+
+            <div className="p-4 border border-border rounded-md space-y-3">
+              <div>
+                <div className="text-sm font-medium">GIF / WebP export</div>
+                <div className="text-xs text-muted-foreground mt-1">
+                  Used when converting a recording to an animated image
+                </div>
+              </div>
+              <div className="grid grid-cols-3 gap-2">
+                <select
+                  value={config.animation_export.fps}
+                  onChange={(e) => handleAnimationChange({ fps: Number(e.target.value) })}
+                  className="h-10 px-3 bg-muted border border-border rounded-md text-sm"
+                >
+                  {[8, 10, 12, 15, 20, 25].map((fps) => (
+                    <option key={fps} value={fps}>{fps} fps</option>
+                  ))}
+                </select>
+                <select
+                  value={config.animation_export.max_width}
+                  onChange={(e) => handleAnimationChange({ max_width: Number(e.target.value) })}
+                  className="h-10 px-3 bg-muted border border-border rounded-md text-sm"
+                >
+                  <option value={480}>480px wide</option>
+                  <option value={800}>800px wide</option>
+                  <option value={1280}>1280px wide</option>
+                  <option value={0}>Original size</option>
+                </select>
+                <select
+                  value={config.animation_export.max_colors}
+                  onChange={(e) => handleAnimationChange({ max_colors: Number(e.target.value) })}
+                  className="h-10 px-3 bg-muted border border-border rounded-md text-sm"
+                >
+                  {[32, 64, 128, 256].map((colors) => (
+                    <option key={colors} value={colors}>{colors} colors</option>
+                  ))}
+                </select>
+              </div>
+            </div>
+
+            <div className="p-4 border border-border rounded-md space-y-3">
+              <div>
+                <div className="text-sm font-medium">Animated captures</div>
+                <div className="text-xs text-muted-foreground mt-1">
+                  Used when animating search results from quick search
+                </div>
+              </div>
+              <div className="grid grid-cols-2 gap-2">
+                <select
+                  value={config.burst_animation.delay_ms}
+                  onChange={(e) => handleBurstChange({ delay_ms: Number(e.target.value) })}
+                  className="h-10 px-3 bg-muted border border-border rounded-md text-sm"
+                >
+                  {[100, 250, 500, 1000, 2000].map((delay) => (
+                    <option key={delay} value={delay}>{delay / 1000} s per frame</option>
+                  ))}
+                </select>
+                <select
+                  value={config.burst_animation.max_width}
+                  onChange={(e) => handleBurstChange({ max_width: Number(e.target.value) })}
+                  className="h-10 px-3 bg-muted border border-border rounded-md text-sm"
+                >
+                  <option value={480}>480px wide</option>
+                  <option value={800}>800px wide</option>
+                  <option value={1280}>1280px wide</option>
+                  <option value={0}>Original size</option>
+                </select>
+              </div>
+            </div>
+
+            <div className="p-4 border border-border rounded-md space-y-3">
+              <div>
+                <div className="text-sm font-medium">GIF captures</div>
+                <div className="text-xs text-muted-foreground mt-1">
+                  Frame rate, length limit and size for Capture GIF
+                </div>
+              </div>
+              <div className="grid grid-cols-3 gap-2">
+                <select
+                  value={config.gif_capture.fps}
+                  onChange={(e) => handleGifCaptureChange({ fps: Number(e.target.value) })}
+                  className="h-10 px-3 bg-muted border border-border rounded-md text-sm"
+                >
+                  {[5, 10, 15, 20, 30].map((fps) => (
+                    <option key={fps} value={fps}>{fps} fps</option>
+                  ))}
+                </select>
+                <select
+                  value={config.gif_capture.max_seconds}
+                  onChange={(e) => handleGifCaptureChange({ max_seconds: Number(e.target.value) })}
+                  className="h-10 px-3 bg-muted border border-border rounded-md text-sm"
+                >
+                  {[5, 10, 20, 30, 60].map((seconds) => (
+                    <option key={seconds} value={seconds}>Up to {seconds} s</option>
+                  ))}
+                </select>
+                <select
+                  value={config.gif_capture.max_width}
+                  onChange={(e) => handleGifCaptureChange({ max_width: Number(e.target.value) })}
+                  className="h-10 px-3 bg-muted border border-border rounded-md text-sm"
+                >
+                  <option value={480}>480px wide</option>
+                  <option value={800}>800px wide</option>
+                  <option value={1280}>1280px wide</option>
+                  <option value={0}>Original size</option>
+                </select>
+              </div>
+            </div>
+
+            <div className="p-4 border border-border rounded-md space-y-3">
+              <div>
+                <div className="text-sm font-medium">Highlight clicks</div>
+                <div className="text-xs text-muted-foreground mt-1">
+                  Draw each click into recordings and GIF captures; presets can pick their own
+                </div>
+              </div>
+              <select
+                value={config.click_highlight}
+                onChange={(e) => handleConfigChange({ click_highlight: e.target.value as ClickHighlight })}
+                className="w-full h-10 px-3 bg-muted border border-border rounded-md text-sm"
+              >
+                {CLICK_HIGHLIGHTS.map(({ value, label }) => (
+                  <option key={value} value={value}>{label}</option>
+                ))}
+              </select>
+              {config.region_presets.map((preset) => (
+                <div key={preset.name} className="flex items-center justify-between gap-3">
+                  <span className="text-sm truncate">{preset.name}</span>
+                  <select
+                    value={preset.click_highlight ?? ''}
+                    onChange={(e) => handlePresetHighlightChange(preset.name, e.target.value as ClickHighlight | '')}
+                    className="h-9 px-3 bg-muted border border-border rounded-md text-sm"
+                  >
+                    <option value="">Default</option>
+                    {CLICK_HIGHLIGHTS.map(({ value, label }) => (
+                      <option key={value} value={value}>{label}</option>
+                    ))}
+                  </select>
+                </div>
+              ))}
+            </div>
+
+            <label className="flex items-center gap-3 p-4 border border-border rounded-md cursor-pointer hover:bg-muted/50 transition-colors">
+              <input
+                type="checkbox"
+                checked={config.webcam_overlay.enabled}
+                onChange={(e) => handleWebcamChange({ enabled: e.target.checked })}
+                className="w-4 h-4 accent-accent"
+              />
+              <div className="flex-1">
+                <div className="text-sm font-medium">Webcam overlay</div>
+                <div className="text-xs text-muted-foreground mt-1">
+                  Show your camera in a corner of screen recordings
+                </div>
+              </div>
+            </label>
+
+            {config.webcam_overlay.enabled && (
+              <div className="grid grid-cols-2 gap-2">
+                <select
+                  value={config.webcam_overlay.device}
+                  onChange={(e) => handleWebcamChange({ device: e.target.value })}
+                  className="col-span-2 h-10 px-3 bg-muted border border-border rounded-md text-sm"
+                >
+                  <option value="">Default camera</option>
+                  {cameraDevices.map((device) => (
+                    <option key={device} value={device}>{device}</option>
+                  ))}
+                </select>
+                <select
+                  value={config.webcam_overlay.position}
+                  onChange={(e) => handleWebcamChange({ position: e.target.value as WebcamOverlay['position'] })}
+                  className="h-10 px-3 bg-muted border border-border rounded-md text-sm"
+                >
+                  <option value="top_left">Top left</option>
+                  <option value="top_right">Top right</option>
+                  <option value="bottom_left">Bottom left</option>
+                  <option value="bottom_right">Bottom right</option>
+                </select>
+                <select
+                  value={config.webcam_overlay.shape}
+                  onChange={(e) => handleWebcamChange({ shape: e.target.value as WebcamOverlay['shape'] })}
+                  className="h-10 px-3 bg-muted border border-border rounded-md text-sm"
+                >
+                  <option value="circle">Circle</option>
+                  <option value="square">Square</option>
+                </select>
+                <label className="col-span-2 flex items-center gap-3 text-sm">
+                  <span className="text-muted-foreground">Size</span>
+                  <input
+                    type="range"
+                    min={96}
+                    max={480}
+                    step={8}
+                    value={config.webcam_overlay.size}
+                    onChange={(e) => handleWebcamChange({ size: Number(e.target.value) })}
+                    className="flex-1 accent-accent"
+                  />
+                  <span className="w-12 text-right tabular-nums">{config.webcam_overlay.size}pt</span>
+                </label>
+              </div>
+            )}
           </div>
 
           {/* Save Button */}
@@ -0,0 +1,154 @@
+import { useState } from 'react';
+import { AlertCircle, Check, ClipboardCopy, FileArchive, ScrollText, Stethoscope, X } from 'lucide-react';
+import { Button } from '@/components/ui/button';
+import { invoke } from '@/lib/tauri';
+import type { CheckStatus, DiagnosticsReport, LogLevel } from '@/types';
+
+const STATUS_ICON: Record<CheckStatus, { icon: typeof Check; className: string }> = {
+  pass: { icon: Check, className: 'text-accent' },
+  warn: { icon: AlertCircle, className: 'text-yellow-500' },
+  fail: { icon: X, className: 'text-destructive' },
+};
+
+const LOG_LEVELS: LogLevel[] = ['error', 'warn', 'info', 'debug', 'trace'];
+const RECENT_LOG_LINES = 200;
+
+interface DiagnosticsPanelProps {
+  logLevel: LogLevel;
+  onLogLevelChange: (level: LogLevel) => void;
+}
+
+export function DiagnosticsPanel({ logLevel, onLogLevelChange }: DiagnosticsPanelProps) {
+  const [report, setReport] = useState<DiagnosticsReport | null>(null);
+  const [logs, setLogs] = useState<string[] | null>(null);
+  const [running, setRunning] = useState(false);
+  const [message, setMessage] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const handleRun = async () => {
+    setError(null);
+    setMessage(null);
+    setRunning(true);
+    try {
+      setReport(await invoke('run_diagnostics'));
+    } catch (err) {
+      setError(String(err));
+      console.error('Failed to run diagnostics:', err);
+    } finally {
+      setRunning(false);
+    }
+  };
+
+  const handleShowLogs = async () => {
+    setError(null);
+    try {
+      setLogs(await invoke('get_recent_logs', { lines: RECENT_LOG_LINES }));
+    } catch (err) {
+      setError(String(err));
+      console.error('Failed to read logs:', err);
+    }
+  };
+
+  const handleExport = async () => {
+    setError(null);
+    setMessage(null);
+    try {
+      const path = await invoke('export_diagnostics');
+      if (path) setMessage(`Saved to ${path}; attach it to your bug report`);
+    } catch (err) {
+      setError(String(err));
+      console.error('Failed to export diagnostics:', err);
+    }
+  };
+
+  // Plain JSON pastes cleanly into a GitHub issue.
+  const handleCopy = async () => {
+    if (!report) return;
+    try {
+      await navigator.clipboard.writeText(JSON.stringify(report, null, 2));
+      setMessage('Report copied; paste it into your bug report');
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  return (
+    <div className="mt-8 p-4 border border-border rounded-md space-y-3">
+      <div className="flex items-center gap-3">
+        <div className="flex-1">
+          <div className="text-sm font-medium">Diagnostics</div>
+          <div className="text-xs text-muted-foreground mt-1">
+            Checks permissions, shortcuts, settings, save folders, caches and disk space
+          </div>
+        </div>
+        <Button onClick={handleExport} className="rounded-md" variant="outline" size="sm">
+          <FileArchive className="w-4 h-4 mr-2" />
+          Export
+        </Button>
+        <Button onClick={handleRun} disabled={running} className="rounded-md" variant="outline" size="sm">
+          <Stethoscope className="w-4 h-4 mr-2" />
+          {running ? 'Checking...' : 'Run'}
+        </Button>
+      </div>
+
+      {report && (
+        <>
+          <ul className="space-y-1 text-xs">
+            {report.checks.map((check, index) => {
+              const { icon: Icon, className } = STATUS_ICON[check.status];
+              return (
+                <li key={index} className="flex items-start gap-2">
+                  <Icon className={`w-4 h-4 flex-shrink-0 ${className}`} />
+                  <span className="font-medium">{check.name}</span>
+                  <span className="text-muted-foreground break-all">{check.detail}</span>
+                </li>
+              );
+            })}
+          </ul>
+          <div className="flex items-center gap-3">
+            <span className="flex-1 text-xs text-muted-foreground">
+              Snipp {report.version} on {report.os}
+            </span>
+            <Button onClick={handleCopy} className="rounded-md" variant="outline" size="sm">
+              <ClipboardCopy className="w-4 h-4 mr-2" />
+              Copy report
+            </Button>
+          </div>
+        </>
+      )}
+
+      <div className="flex items-center gap-3">
+        <div className="flex-1">
+          <div className="text-sm font-medium">Log level</div>
+          <div className="text-xs text-muted-foreground mt-1">
+            Debug and trace help with bug reports but make the log grow faster
+          </div>
+        </div>
+        <select
+          value={logLevel}
+          onChange={(e) => onLogLevelChange(e.target.value as LogLevel)}
+          className="h-9 px-2 bg-muted border border-border rounded-md text-sm"
+        >
+          {LOG_LEVELS.map((level) => (
+            <option key={level} value={level}>
+              {level}
+            </option>
+          ))}
+        </select>
+        <Button onClick={handleShowLogs} className="rounded-md" variant="outline" size="sm">
+          <ScrollText className="w-4 h-4 mr-2" />
+          {logs ? 'Refresh logs' : 'Show logs'}
+        </Button>
+      </div>
+
+      {logs && (
+        <pre className="max-h-64 overflow-auto p-2 bg-muted rounded-md text-[11px] leading-snug whitespace-pre-wrap break-all">
+          {logs.length > 0 ? logs.join('\n') : 'Nothing logged yet'}
+        </pre>
+      )}
+
+      {message && <div className="text-xs text-accent">{message}</div>}
+      {error && <div className="text-xs text-destructive">{error}</div>}
+    </div>
+  );
+}
@@ -0,0 +1,133 @@
+import { useEffect, useState } from 'react';
+import { FileDown, FileText, Trash2, X } from 'lucide-react';
+import { Button } from '@/components/ui/button';
+import { invoke, listen } from '@/lib/tauri';
+import type { GuideFormat, GuideSession } from '@/types';
+
+interface GuidePanelProps {
+  onClose: () => void;
+}
+
+export function GuidePanel({ onClose }: GuidePanelProps) {
+  const [guide, setGuide] = useState<GuideSession | null>(null);
+  const [title, setTitle] = useState('');
+  const [exported, setExported] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke('get_guide')
+      .then(setGuide)
+      .catch((err) => console.error('Failed to load guide:', err));
+    const unlisten = listen('guide-updated', setGuide);
+    return () => {
+      unlisten.then((fn) => fn()).catch(() => {});
+    };
+  }, []);
+
+  const run = async (action: () => Promise<unknown>) => {
+    setError(null);
+    try {
+      await action();
+    } catch (err) {
+      setError(String(err));
+      console.error('Guide action failed:', err);
+    }
+  };
+
+  const handleExport = (format: GuideFormat) =>
+    run(async () => {
+      const path = await invoke('export_guide', { format });
+      if (path) setExported(path);
+    });
+
+  return (
+    <div className="p-4 border border-border rounded-md space-y-3">
+      <div className="flex items-start justify-between gap-3">
+        <div>
+          <div className="text-sm font-medium">{guide ? guide.title || 'Untitled guide' : 'Build a guide'}</div>
+          <div className="text-xs text-muted-foreground mt-1">
+            {guide
+              ? 'Every capture you save is added as the next step'
+              : 'Collect captures as numbered steps, then export one document'}
+          </div>
+        </div>
+        <button onClick={onClose} className="text-muted-foreground hover:text-foreground">
+          <X className="w-4 h-4" />
+        </button>
+      </div>
+
+      {!guide && (
+        <div className="flex gap-2">
+          <input
+            type="text"
+            value={title}
+            onChange={(e) => setTitle(e.target.value)}
+            placeholder="e.g. How to reset a password"
+            className="flex-1 h-9 px-3 bg-muted border border-border rounded-md text-sm"
+          />
+          <Button onClick={() => run(() => invoke('start_guide', { title }))} className="rounded-md">
+            Start
+          </Button>
+        </div>
+      )}
+
+      {guide && guide.steps.length === 0 && (
+        <div className="text-xs text-muted-foreground">No steps yet. Take a capture to add the first one.</div>
+      )}
+
+      {guide?.steps.map((step, index) => (
+        <div key={`${index}-${step.file_path}`} className="flex items-center gap-2">
+          <span className="w-6 h-6 flex-shrink-0 rounded-full bg-accent text-accent-foreground text-xs flex items-center justify-center">
+            {index + 1}
+          </span>
+          <input
+            type="text"
+            defaultValue={step.caption}
+            onBlur={(e) => {
+              if (e.target.value !== step.caption) {
+                run(() => invoke('set_guide_caption', { index, caption: e.target.value }));
+              }
+            }}
+            placeholder={step.file_path.split('/').pop()}
+            className="flex-1 h-9 px-3 bg-muted border border-border rounded-md text-sm"
+          />
+          <button
+            onClick={() => run(() => invoke('remove_guide_step', { index }))}
+            className="text-muted-foreground hover:text-foreground"
+          >
+            <Trash2 className="w-4 h-4" />
+          </button>
+        </div>
+      ))}
+
+      {guide && (
+        <div className="flex gap-2">
+          <Button
+            onClick={() => handleExport('markdown')}
+            disabled={guide.steps.length === 0}
+            className="flex-1 rounded-md"
+            variant="outline"
+          >
+            <FileText className="w-4 h-4 mr-2" />
+            Markdown
+          </Button>
+          <Button
+            onClick={() => handleExport('pdf')}
+            disabled={guide.steps.length === 0}
+            className="flex-1 rounded-md"
+            variant="outline"
+          >
+            <FileDown className="w-4 h-4 mr-2" />
+            PDF
+          </Button>
+          <Button onClick={() => run(() => invoke('end_guide'))} className="rounded-md" variant="outline">
+            End
+          </Button>
+        </div>
+      )}
+
+      {exported && <div className="text-xs text-muted-foreground truncate">Exported to {exported}</div>}
+      {error && <div className="text-xs text-destructive">{error}</div>}
+    </div>
+  );
+}
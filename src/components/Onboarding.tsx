@@ -0,0 +1,227 @@
+import { useCallback, useEffect, useState } from 'react';
+import { Camera, Check, Folder, Keyboard, Monitor, MousePointer2 } from 'lucide-react';
+import { Button } from '@/components/ui/button';
+import { invoke } from '@/lib/tauri';
+import type { OnboardingStatus, OutputFormat, PermissionKind, ScreencapturePrefs } from '@/types';
+
+interface OnboardingProps {
+  /** Called once the wizard has saved its choices. */
+  onDone: () => void;
+  formatHotkey: (hotkey: string) => string;
+}
+
+const PERMISSIONS: Array<{ kind: PermissionKind; label: string; detail: string; icon: typeof Monitor }> = [
+  {
+    kind: 'screen_recording',
+    label: 'Screen Recording',
+    detail: 'Without it, captures come out black or show only the wallpaper',
+    icon: Monitor,
+  },
+  {
+    kind: 'accessibility',
+    label: 'Accessibility',
+    detail: 'Lets global shortcuts work while other apps are focused',
+    icon: MousePointer2,
+  },
+];
+
+/** The Snipp save format matching a macOS screenshot `type`, if Snipp can write it. */
+function outputFormatFor(type: string): OutputFormat | null {
+  switch (type) {
+    case 'png':
+      return 'png';
+    case 'jpg':
+    case 'jpeg':
+      return 'jpeg';
+    case 'tif':
+    case 'tiff':
+      return 'tiff';
+    case 'heic':
+      return 'heic';
+    default:
+      return null;
+  }
+}
+
+export function Onboarding({ onDone, formatHotkey }: OnboardingProps) {
+  const [status, setStatus] = useState<OnboardingStatus | null>(null);
+  const [saveLocation, setSaveLocation] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [macosPrefs, setMacosPrefs] = useState<ScreencapturePrefs | null>(null);
+  const [adoptMacosPrefs, setAdoptMacosPrefs] = useState(true);
+
+  const refresh = useCallback(() => {
+    invoke('get_onboarding_status')
+      .then(setStatus)
+      .catch((err) => setError(String(err)));
+  }, []);
+
+  useEffect(() => {
+    invoke('get_macos_screenshot_prefs')
+      .then(setMacosPrefs)
+      .catch((err) => console.error('Failed to read macOS screenshot settings:', err));
+  }, []);
+
+  useEffect(() => {
+    refresh();
+    // Permissions are granted in System Settings; re-check when we come back.
+    window.addEventListener('focus', refresh);
+    return () => window.removeEventListener('focus', refresh);
+  }, [refresh]);
+
+  const handleGrant = async (kind: PermissionKind) => {
+    setError(null);
+    try {
+      await invoke('request_permission', { kind });
+    } catch (err) {
+      setError(String(err));
+    }
+    refresh();
+  };
+
+  const handleBrowse = async () => {
+    try {
+      const folder = await invoke('choose_save_location');
+      if (folder) setSaveLocation(folder);
+    } catch (err) {
+      console.error('Failed to choose save location:', err);
+    }
+  };
+
+  const handleFinish = async () => {
+    setError(null);
+    try {
+      const adopted = adoptMacosPrefs && macosPrefs ? macosPrefs : null;
+      const location = saveLocation ?? adopted?.location ?? null;
+      const adoptedFormat = adopted?.format ? outputFormatFor(adopted.format) : null;
+      await invoke('complete_onboarding', {
+        ...(location ? { saveLocation: location } : {}),
+        ...(adopted?.window_shadow != null ? { windowShadow: adopted.window_shadow } : {}),
+        ...(adoptedFormat ? { outputFormat: adoptedFormat } : {}),
+      });
+      onDone();
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  if (!status) {
+    return (
+      <div className="min-h-screen bg-background flex items-center justify-center">
+        <div className="text-muted-foreground">Loading...</div>
+      </div>
+    );
+  }
+
+  const granted: Record<PermissionKind, boolean> = {
+    screen_recording: status.screen_recording,
+    accessibility: status.accessibility,
+  };
+
+  return (
+    <div className="min-h-screen bg-background text-foreground">
+      <div className="max-w-2xl mx-auto px-6 py-8 space-y-8">
+        <header>
+          <h1 className="text-2xl font-semibold">Welcome to Snipp</h1>
+          <p className="text-sm text-muted-foreground mt-1">A few things to set up before your first capture</p>
+        </header>
+
+        <section className="space-y-2">
+          <h2 className="text-sm font-semibold uppercase tracking-widest text-muted-foreground mb-3">Permissions</h2>
+          {PERMISSIONS.map(({ kind, label, detail, icon: Icon }) => (
+            <div key={kind} className="flex items-center gap-3 p-4 border border-border rounded-md">
+              <Icon className="w-5 h-5 flex-shrink-0" />
+              <div className="flex-1">
+                <div className="text-sm font-medium">{label}</div>
+                <div className="text-xs text-muted-foreground mt-0.5">{detail}</div>
+              </div>
+              {granted[kind] ? (
+                <span className="flex items-center gap-1 text-xs text-accent">
+                  <Check className="w-4 h-4" />
+                  Granted
+                </span>
+              ) : (
+                <Button onClick={() => handleGrant(kind)} variant="outline" size="sm" className="rounded-md">
+                  Grant
+                </Button>
+              )}
+            </div>
+          ))}
+          {!status.screen_recording && (
+            <p className="text-xs text-muted-foreground">
+              macOS may ask you to quit and reopen Snipp after granting Screen Recording.
+            </p>
+          )}
+        </section>
+
+        {macosPrefs && (macosPrefs.location || macosPrefs.format || macosPrefs.window_shadow != null) && (
+          <section>
+            <h2 className="text-sm font-semibold uppercase tracking-widest text-muted-foreground mb-3">
+              macOS Screenshot Settings
+            </h2>
+            <label className="flex items-start gap-3 p-4 border border-border rounded-md cursor-pointer hover:bg-muted/50 transition-colors">
+              <input
+                type="checkbox"
+                checked={adoptMacosPrefs}
+                onChange={(e) => setAdoptMacosPrefs(e.target.checked)}
+                className="w-4 h-4 mt-0.5 accent-accent"
+              />
+              <Camera className="w-5 h-5 flex-shrink-0" />
+              <div className="flex-1">
+                <div className="text-sm font-medium">Use the settings from the built-in screenshot tool</div>
+                <ul className="text-xs text-muted-foreground mt-1 space-y-0.5">
+                  {macosPrefs.location && <li>Save to {macosPrefs.location}</li>}
+                  {macosPrefs.window_shadow != null && (
+                    <li>{macosPrefs.window_shadow ? 'Keep' : 'Remove'} window shadows</li>
+                  )}
+                  {macosPrefs.format && macosPrefs.format !== 'png' && (
+                    <li>
+                      {outputFormatFor(macosPrefs.format)
+                        ? `Save as ${macosPrefs.format.toUpperCase()}`
+                        : `Saves as ${macosPrefs.format.toUpperCase()}; Snipp will save PNG`}
+                    </li>
+                  )}
+                </ul>
+              </div>
+            </label>
+          </section>
+        )}
+
+        <section>
+          <h2 className="text-sm font-semibold uppercase tracking-widest text-muted-foreground mb-3">Save Location</h2>
+          <div className="flex gap-2">
+            <input
+              type="text"
+              value={saveLocation ?? (adoptMacosPrefs ? macosPrefs?.location : null) ?? status.save_location}
+              readOnly
+              className="flex-1 h-10 px-3 bg-muted border border-border rounded-md text-sm"
+            />
+            <Button variant="outline" size="sm" onClick={handleBrowse} className="rounded-md bg-transparent">
+              <Folder className="w-4 h-4 mr-2" />
+              Browse
+            </Button>
+          </div>
+          {!saveLocation && !status.save_location_writable && (
+            <p className="text-xs text-destructive mt-2">This folder can't be written to; choose another one.</p>
+          )}
+        </section>
+
+        <section>
+          <h2 className="text-sm font-semibold uppercase tracking-widest text-muted-foreground mb-3">Hotkey</h2>
+          <div className="flex items-center gap-3 p-4 border border-border rounded-md">
+            <Keyboard className="w-5 h-5 flex-shrink-0" />
+            <span className="flex-1 text-sm">Capture Screenshot</span>
+            <kbd className="px-2 py-1 bg-muted rounded text-xs font-mono">{formatHotkey(status.capture_hotkey)}</kbd>
+          </div>
+          <p className="text-xs text-muted-foreground mt-2">You can change shortcuts later in Settings.</p>
+        </section>
+
+        {error && <div className="text-xs text-destructive">{error}</div>}
+
+        <Button onClick={handleFinish} className="w-full rounded-md" size="lg">
+          Start using Snipp
+        </Button>
+      </div>
+    </div>
+  );
+}
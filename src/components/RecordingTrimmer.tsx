@@ -0,0 +1,137 @@
+import { useEffect, useState } from 'react';
+import { Film, Scissors, X } from 'lucide-react';
+import { Button } from '@/components/ui/button';
+import { invoke } from '@/lib/tauri';
+import type { AnimationFormat, RecordingInfo } from '@/types';
+
+interface RecordingTrimmerProps {
+  recording: RecordingInfo;
+  onTrimmed: (trimmed: RecordingInfo) => void;
+  onClose: () => void;
+}
+
+const formatSeconds = (ms: number) => `${(ms / 1000).toFixed(1)}s`;
+
+export function RecordingTrimmer({ recording, onTrimmed, onClose }: RecordingTrimmerProps) {
+  const duration = recording.duration_ms;
+  const [startMs, setStartMs] = useState(0);
+  const [endMs, setEndMs] = useState(duration);
+  const [isTrimming, setIsTrimming] = useState(false);
+  const [converting, setConverting] = useState<AnimationFormat | null>(null);
+  const [exportedPath, setExportedPath] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    setStartMs(0);
+    setEndMs(duration);
+    setError(null);
+    setExportedPath(null);
+  }, [recording.id, duration]);
+
+  const handleTrim = async () => {
+    setIsTrimming(true);
+    setError(null);
+    try {
+      const trimmed = await invoke('trim_recording', {
+        id: recording.id,
+        filePath: recording.file_path,
+        startMs,
+        endMs,
+      });
+      onTrimmed(trimmed);
+    } catch (err) {
+      setError(String(err));
+      console.error('Failed to trim recording:', err);
+    } finally {
+      setIsTrimming(false);
+    }
+  };
+
+  const handleConvert = async (format: AnimationFormat) => {
+    setConverting(format);
+    setError(null);
+    try {
+      setExportedPath(
+        await invoke('convert_recording', { id: recording.id, filePath: recording.file_path, format })
+      );
+    } catch (err) {
+      setError(String(err));
+      console.error('Failed to convert recording:', err);
+    } finally {
+      setConverting(null);
+    }
+  };
+
+  const fileName = recording.file_path.split('/').pop();
+
+  return (
+    <div className="p-4 border border-border rounded-md space-y-3">
+      <div className="flex items-start justify-between gap-3">
+        <div className="min-w-0">
+          <div className="text-sm font-medium">Last recording</div>
+          <div className="text-xs text-muted-foreground mt-1 truncate">{fileName}</div>
+        </div>
+        <button onClick={onClose} className="text-muted-foreground hover:text-foreground">
+          <X className="w-4 h-4" />
+        </button>
+      </div>
+
+      <label className="flex items-center gap-3 text-sm">
+        <span className="w-10 text-muted-foreground">Start</span>
+        <input
+          type="range"
+          min={0}
+          max={duration}
+          step={100}
+          value={startMs}
+          onChange={(e) => setStartMs(Math.min(Number(e.target.value), endMs - 100))}
+          className="flex-1 accent-accent"
+        />
+        <span className="w-14 text-right tabular-nums">{formatSeconds(startMs)}</span>
+      </label>
+      <label className="flex items-center gap-3 text-sm">
+        <span className="w-10 text-muted-foreground">End</span>
+        <input
+          type="range"
+          min={0}
+          max={duration}
+          step={100}
+          value={endMs}
+          onChange={(e) => setEndMs(Math.max(Number(e.target.value), startMs + 100))}
+          className="flex-1 accent-accent"
+        />
+        <span className="w-14 text-right tabular-nums">{formatSeconds(endMs)}</span>
+      </label>
+
+      {error && <div className="text-xs text-destructive">{error}</div>}
+      {exportedPath && (
+        <div className="text-xs text-muted-foreground truncate">Saved {exportedPath.split('/').pop()}</div>
+      )}
+
+      <Button
+        onClick={handleTrim}
+        disabled={isTrimming || (startMs === 0 && endMs === duration)}
+        className="w-full rounded-md"
+        variant="outline"
+      >
+        <Scissors className="w-4 h-4 mr-2" />
+        {isTrimming ? 'Trimming...' : `Save ${formatSeconds(endMs - startMs)} clip`}
+      </Button>
+
+      <div className="grid grid-cols-2 gap-2">
+        {(['gif', 'webp'] as const).map((format) => (
+          <Button
+            key={format}
+            onClick={() => handleConvert(format)}
+            disabled={converting !== null}
+            className="rounded-md"
+            variant="outline"
+          >
+            <Film className="w-4 h-4 mr-2" />
+            {converting === format ? 'Converting...' : `Export ${format === 'gif' ? 'GIF' : 'WebP'}`}
+          </Button>
+        ))}
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,46 @@
+import type { AutoRedactSettings } from '@/types';
+
+interface RedactionSettingsProps {
+  settings: AutoRedactSettings;
+  onChange: (updates: Partial<AutoRedactSettings>) => void;
+}
+
+const DETECTORS: { key: 'emails' | 'api_keys' | 'credit_cards'; label: string }[] = [
+  { key: 'emails', label: 'Email addresses' },
+  { key: 'api_keys', label: 'API keys and tokens' },
+  { key: 'credit_cards', label: 'Card numbers' },
+];
+
+export function RedactionSettings({ settings, onChange }: RedactionSettingsProps) {
+  return (
+    <div className="mt-8 p-4 border border-border rounded-md space-y-3">
+      <div>
+        <div className="text-sm font-medium">Auto-redact</div>
+        <div className="text-xs text-muted-foreground mt-1">
+          What the popup's Redact action finds in a capture's text and hides, in the editor's redaction style
+        </div>
+      </div>
+      <div className="flex flex-wrap gap-4 text-sm">
+        {DETECTORS.map(({ key, label }) => (
+          <label key={key} className="flex items-center gap-2 cursor-pointer">
+            <input
+              type="checkbox"
+              checked={settings[key]}
+              onChange={(e) => onChange({ [key]: e.target.checked })}
+              className="w-4 h-4 rounded border-border"
+            />
+            {label}
+          </label>
+        ))}
+      </div>
+      <textarea
+        value={settings.patterns.join('\n')}
+        onChange={(e) => onChange({ patterns: e.target.value.split('\n') })}
+        placeholder={'More patterns, one regular expression per line, e.g.\nACME-\\d{6}'}
+        aria-label="Extra patterns"
+        rows={3}
+        className="w-full px-3 py-2 bg-muted border border-border rounded-md text-sm font-mono"
+      />
+    </div>
+  );
+}
@@ -0,0 +1,115 @@
+import { useState } from 'react';
+import { Search, Trash2 } from 'lucide-react';
+import { Button } from '@/components/ui/button';
+import { invoke } from '@/lib/tauri';
+import type { ExpiredFile, RetentionPolicy } from '@/types';
+
+interface RetentionSettingsProps {
+  policy: RetentionPolicy;
+  /** Cleanup runs against the saved config, so it waits for unsaved edits. */
+  hasUnsavedChanges: boolean;
+  onChange: (updates: Partial<RetentionPolicy>) => void;
+}
+
+const fileName = (path: string) => path.split('/').pop() ?? path;
+
+export function RetentionSettings({ policy, hasUnsavedChanges, onChange }: RetentionSettingsProps) {
+  const [preview, setPreview] = useState<ExpiredFile[] | null>(null);
+  const [message, setMessage] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const handlePreview = async () => {
+    setError(null);
+    setMessage(null);
+    try {
+      setPreview(await invoke('preview_retention', { days: policy.days }));
+    } catch (err) {
+      setError(String(err));
+      console.error('Failed to preview retention:', err);
+    }
+  };
+
+  const handleApply = async () => {
+    setError(null);
+    try {
+      const removed = await invoke('apply_retention');
+      setPreview(null);
+      setMessage(`Moved ${removed.length} file${removed.length === 1 ? '' : 's'} to the Trash`);
+    } catch (err) {
+      setError(String(err));
+      console.error('Failed to apply retention:', err);
+    }
+  };
+
+  const totalMb = (preview ?? []).reduce((sum, file) => sum + file.size_bytes, 0) / (1024 * 1024);
+
+  return (
+    <div className="mt-8 p-4 border border-border rounded-md space-y-3">
+      <label className="flex items-center gap-3 cursor-pointer">
+        <input
+          type="checkbox"
+          checked={policy.enabled}
+          onChange={(e) => onChange({ enabled: e.target.checked })}
+          className="w-4 h-4 rounded border-border"
+        />
+        <div className="flex-1">
+          <div className="text-sm font-medium">Auto-delete old captures</div>
+          <div className="text-xs text-muted-foreground mt-1">
+            Moves Snipp files in the save folders to the Trash after this many days
+          </div>
+        </div>
+      </label>
+      <div className="flex items-center gap-3 text-sm">
+        <input
+          type="number"
+          min={1}
+          max={3650}
+          value={policy.days}
+          onChange={(e) => {
+            onChange({ days: Math.max(1, Number(e.target.value)) });
+            setPreview(null);
+          }}
+          className="w-20 h-9 px-2 bg-muted border border-border rounded-md text-sm"
+        />
+        <span className="text-muted-foreground">days</span>
+        <Button onClick={handlePreview} className="ml-auto rounded-md" variant="outline" size="sm">
+          <Search className="w-4 h-4 mr-2" />
+          Preview
+        </Button>
+      </div>
+
+      {preview && (
+        <div className="space-y-2">
+          <div className="text-xs text-muted-foreground">
+            {preview.length === 0
+              ? 'Nothing is old enough to remove'
+              : `${preview.length} file${preview.length === 1 ? '' : 's'} (${totalMb.toFixed(1)} MB) would be removed`}
+          </div>
+          {preview.length > 0 && (
+            <>
+              <ul className="max-h-32 overflow-y-auto text-xs font-mono space-y-0.5">
+                {preview.map((file) => (
+                  <li key={file.path} title={file.path} className="truncate">
+                    {fileName(file.path)}
+                  </li>
+                ))}
+              </ul>
+              <Button
+                onClick={handleApply}
+                disabled={hasUnsavedChanges}
+                className="w-full rounded-md"
+                variant="outline"
+              >
+                <Trash2 className="w-4 h-4 mr-2" />
+                {hasUnsavedChanges ? 'Save changes to clean up' : 'Move to Trash now'}
+              </Button>
+            </>
+          )}
+        </div>
+      )}
+
+      {message && <div className="text-xs text-accent">{message}</div>}
+      {error && <div className="text-xs text-destructive">{error}</div>}
+    </div>
+  );
+}
@@ -1,8 +1,9 @@
 import { useState, useCallback, useRef } from 'react';
-import { Trash2, Pencil } from 'lucide-react';
+import { Trash2, Pencil, Languages, StickyNote } from 'lucide-react';
 import { cn, debugLog } from '@/lib/utils';
 import { useScreenshot } from '@/hooks/useScreenshot';
 import { startDrag } from '@crabnebula/tauri-plugin-drag';
+import type { Plugin } from '@/types';
 
 interface ScreenshotPreviewProps {
   imageUrl: string;
@@ -11,6 +12,17 @@ interface ScreenshotPreviewProps {
   onCopy?: () => void;
   onDelete?: () => void;
   onEdit?: () => void;
+  onTranslate?: () => void;
+  onNote?: () => void;
+  onAnalyze?: () => void;
+  onDetectCodes?: () => void;
+  onBeautify?: () => void;
+  onRoundCorners?: () => void;
+  onRedact?: () => void;
+  onDiff?: () => void;
+  onCollage?: () => void;
+  plugins?: Plugin[];
+  onPlugin?: (plugin: Plugin) => void;
   className?: string;
 }
 
@@ -21,6 +33,17 @@ export function ScreenshotPreview({
   onCopy,
   onDelete,
   onEdit,
+  onTranslate,
+  onNote,
+  onAnalyze,
+  onDetectCodes,
+  onBeautify,
+  onRoundCorners,
+  onRedact,
+  onDiff,
+  onCollage,
+  plugins = [],
+  onPlugin,
   className
 }: ScreenshotPreviewProps) {
   const [showActions, setShowActions] = useState(false);
@@ -69,7 +92,7 @@ export function ScreenshotPreview({
   return (
     <div className={className}>
       <div
-        className="relative w-[176px] h-[160px] rounded-2xl overflow-hidden shadow-2xl group animate-in zoom-in-95 fade-in duration-200"
+        className="relative w-[176px] h-[160px] rounded-2xl overflow-hidden shadow-2xl group animate-in zoom-in-95 fade-in duration-200 motion-reduce:animate-none"
         style={{ WebkitUserDrag: 'none' } as React.CSSProperties}
         onPointerDown={handlePointerDown}
         onPointerMove={handlePointerMove}
@@ -82,7 +105,7 @@ export function ScreenshotPreview({
         <button
           onClick={onDelete}
           disabled={isDragging}
-          className="absolute top-2 right-2 z-20 w-7 h-7 flex items-center justify-center rounded-full bg-white border border-black hover:bg-red-50 hover:border-red-500 hover:scale-110 transition-all duration-200 disabled:opacity-50"
+          className="absolute top-2 right-2 z-20 w-7 h-7 flex items-center justify-center rounded-full bg-white border border-black hover:bg-red-50 hover:border-red-500 hover:scale-110 transition-all duration-200 motion-reduce:transition-none disabled:opacity-50"
           aria-label="Delete screenshot"
           title="Delete"
         >
@@ -93,13 +116,39 @@ export function ScreenshotPreview({
         <button
           onClick={onEdit}
           disabled={isLoading || isDragging}
-          className="absolute top-2 left-2 z-20 w-7 h-7 flex items-center justify-center rounded-full bg-white border border-black hover:bg-blue-50 hover:border-blue-500 hover:scale-110 transition-all duration-200 disabled:opacity-50"
+          className="absolute top-2 left-2 z-20 w-7 h-7 flex items-center justify-center rounded-full bg-white border border-black hover:bg-blue-50 hover:border-blue-500 hover:scale-110 transition-all duration-200 motion-reduce:transition-none disabled:opacity-50"
           aria-label="Edit screenshot"
           title="Edit"
         >
           <Pencil size={14} className="text-black" />
         </button>
 
+        {/* Translate button - bottom left */}
+        {onTranslate && (
+          <button
+            onClick={onTranslate}
+            disabled={isLoading || isDragging}
+            className="absolute bottom-2 left-2 z-20 w-7 h-7 flex items-center justify-center rounded-full bg-white border border-black hover:bg-blue-50 hover:border-blue-500 hover:scale-110 transition-all duration-200 motion-reduce:transition-none disabled:opacity-50"
+            aria-label="Translate text in screenshot"
+            title="Translate"
+          >
+            <Languages size={14} className="text-black" />
+          </button>
+        )}
+
+        {/* Note button - bottom right */}
+        {onNote && (
+          <button
+            onClick={onNote}
+            disabled={isLoading || isDragging}
+            className="absolute bottom-2 right-2 z-20 w-7 h-7 flex items-center justify-center rounded-full bg-white border border-black hover:bg-blue-50 hover:border-blue-500 hover:scale-110 transition-all duration-200 motion-reduce:transition-none disabled:opacity-50"
+            aria-label="Add a note and save"
+            title="Note"
+          >
+            <StickyNote size={14} className="text-black" />
+          </button>
+        )}
+
         {/* Image container with drag support */}
         <div className={cn(
           "relative w-full h-full select-none",
@@ -121,14 +170,14 @@ export function ScreenshotPreview({
           {/* Hover overlay with Copy and Save actions */}
           <div 
             className={cn(
-              "absolute inset-0 flex flex-col items-center justify-center gap-2 bg-black/70 backdrop-blur-sm transition-opacity duration-200",
+              "absolute inset-0 flex flex-col items-center justify-center gap-2 bg-black/70 backdrop-blur-sm transition-opacity duration-200 motion-reduce:transition-none",
               showActions ? "opacity-100" : "opacity-0"
             )}
           >
             <button
               onClick={onCopy}
               disabled={isLoading || isDragging}
-              className="flex items-center justify-center px-5 py-1.5 bg-white text-black rounded-full hover:bg-white/90 hover:scale-105 active:scale-95 transition-all duration-200 disabled:opacity-50"
+              className="flex items-center justify-center px-5 py-1.5 bg-white text-black rounded-full hover:bg-white/90 hover:scale-105 active:scale-95 transition-all duration-200 motion-reduce:transition-none disabled:opacity-50"
               aria-label="Copy to clipboard"
               title="Copy"
             >
@@ -138,12 +187,108 @@ export function ScreenshotPreview({
             <button
               onClick={onSave}
               disabled={isLoading || isDragging}
-              className="flex items-center justify-center px-5 py-1.5 bg-white text-black rounded-full hover:bg-white/90 hover:scale-105 active:scale-95 transition-all duration-200 disabled:opacity-50"
+              className="flex items-center justify-center px-5 py-1.5 bg-white text-black rounded-full hover:bg-white/90 hover:scale-105 active:scale-95 transition-all duration-200 motion-reduce:transition-none disabled:opacity-50"
               aria-label="Save screenshot"
               title="Save"
             >
               <span className="text-xs font-medium">Save</span>
             </button>
+
+            {onAnalyze && (
+              <button
+                onClick={onAnalyze}
+                disabled={isLoading || isDragging}
+                className="text-[11px] text-white/80 hover:text-white underline-offset-2 hover:underline disabled:opacity-50"
+                aria-label="Compare file sizes by format"
+                title="File sizes"
+              >
+                Sizes
+              </button>
+            )}
+
+            {onDetectCodes && (
+              <button
+                onClick={onDetectCodes}
+                disabled={isLoading || isDragging}
+                className="text-[11px] text-white/80 hover:text-white underline-offset-2 hover:underline disabled:opacity-50"
+                aria-label="Read QR codes and barcodes"
+                title="QR codes and barcodes"
+              >
+                Codes
+              </button>
+            )}
+
+            {onBeautify && (
+              <button
+                onClick={onBeautify}
+                disabled={isLoading || isDragging}
+                className="text-[11px] text-white/80 hover:text-white underline-offset-2 hover:underline disabled:opacity-50"
+                aria-label="Add padding, background and shadow"
+                title="Beautify"
+              >
+                Beautify
+              </button>
+            )}
+
+            {onRoundCorners && (
+              <button
+                onClick={onRoundCorners}
+                disabled={isLoading || isDragging}
+                className="text-[11px] text-white/80 hover:text-white underline-offset-2 hover:underline disabled:opacity-50"
+                aria-label="Round the corners"
+                title="Round corners"
+              >
+                Round
+              </button>
+            )}
+
+            {onRedact && (
+              <button
+                onClick={onRedact}
+                disabled={isLoading || isDragging}
+                className="text-[11px] text-white/80 hover:text-white underline-offset-2 hover:underline disabled:opacity-50"
+                aria-label="Hide emails, keys and card numbers"
+                title="Hide emails, keys and card numbers"
+              >
+                Redact
+              </button>
+            )}
+
+            {onDiff && (
+              <button
+                onClick={onDiff}
+                disabled={isLoading || isDragging}
+                className="text-[11px] text-white/80 hover:text-white underline-offset-2 hover:underline disabled:opacity-50"
+                aria-label="Show what changed since the previous capture"
+                title="Show what changed since the previous capture"
+              >
+                Diff
+              </button>
+            )}
+
+            {onCollage && (
+              <button
+                onClick={onCollage}
+                disabled={isLoading || isDragging}
+                className="text-[11px] text-white/80 hover:text-white underline-offset-2 hover:underline disabled:opacity-50"
+                aria-label="Combine with the previous capture"
+                title="Combine with the previous capture"
+              >
+                Collage
+              </button>
+            )}
+
+            {onPlugin && plugins.map((plugin) => (
+              <button
+                key={plugin.id}
+                onClick={() => onPlugin(plugin)}
+                disabled={isLoading || isDragging}
+                className="max-w-[150px] truncate text-[11px] text-white/80 hover:text-white underline-offset-2 hover:underline disabled:opacity-50"
+                title={plugin.description || plugin.name}
+              >
+                {plugin.name}
+              </button>
+            ))}
           </div>
         </div>
 
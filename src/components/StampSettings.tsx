@@ -0,0 +1,62 @@
+import type { Corner, StampSettings as Settings } from '@/types';
+
+interface StampSettingsProps {
+  settings: Settings;
+  onChange: (updates: Partial<Settings>) => void;
+}
+
+const CORNERS: { value: Corner; label: string }[] = [
+  { value: 'top_left', label: 'Top left' },
+  { value: 'top_right', label: 'Top right' },
+  { value: 'bottom_left', label: 'Bottom left' },
+  { value: 'bottom_right', label: 'Bottom right' },
+];
+
+export function StampSettings({ settings, onChange }: StampSettingsProps) {
+  return (
+    <div className="mt-8 p-4 border border-border rounded-md space-y-3">
+      <label className="flex items-center gap-3 cursor-pointer">
+        <input
+          type="checkbox"
+          checked={settings.enabled}
+          onChange={(e) => onChange({ enabled: e.target.checked })}
+          className="w-4 h-4 rounded border-border"
+        />
+        <div className="flex-1">
+          <div className="text-sm font-medium">Stamp date and time</div>
+          <div className="text-xs text-muted-foreground mt-1">
+            Burns the capture time, and an optional label, into every saved image
+          </div>
+        </div>
+      </label>
+      <div className="flex items-center gap-3 text-sm">
+        <input
+          type="text"
+          value={settings.label}
+          onChange={(e) => onChange({ label: e.target.value })}
+          placeholder="Label, e.g. ticket number"
+          className="flex-1 h-9 px-3 bg-muted border border-border rounded-md text-sm"
+        />
+        <select
+          value={settings.corner}
+          onChange={(e) => onChange({ corner: e.target.value as Corner })}
+          className="h-9 px-2 bg-muted border border-border rounded-md text-sm"
+        >
+          {CORNERS.map((corner) => (
+            <option key={corner.value} value={corner.value}>
+              {corner.label}
+            </option>
+          ))}
+        </select>
+      </div>
+      <input
+        type="text"
+        value={settings.format}
+        onChange={(e) => onChange({ format: e.target.value })}
+        placeholder="%Y-%m-%d %H:%M:%S"
+        aria-label="Date format"
+        className="w-full h-9 px-3 bg-muted border border-border rounded-md text-sm font-mono"
+      />
+    </div>
+  );
+}
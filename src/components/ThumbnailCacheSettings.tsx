@@ -0,0 +1,52 @@
+import { useEffect, useState } from 'react';
+import { Trash2 } from 'lucide-react';
+import { Button } from '@/components/ui/button';
+import { invoke } from '@/lib/tauri';
+import type { CacheUsage } from '@/types';
+
+const formatMb = (bytes: number) => `${(bytes / (1024 * 1024)).toFixed(1)} MB`;
+
+export function ThumbnailCacheSettings() {
+  const [usage, setUsage] = useState<CacheUsage | null>(null);
+  const [message, setMessage] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke('get_thumbnail_cache_usage')
+      .then(setUsage)
+      .catch((err) => setError(String(err)));
+  }, []);
+
+  const handlePurge = async () => {
+    setError(null);
+    try {
+      const freed = await invoke('purge_thumbnail_cache');
+      setUsage({ files: 0, bytes: 0 });
+      setMessage(`Freed ${formatMb(freed.bytes)}`);
+    } catch (err) {
+      setError(String(err));
+      console.error('Failed to clear thumbnail cache:', err);
+    }
+  };
+
+  return (
+    <div className="mt-8 p-4 border border-border rounded-md space-y-3">
+      <div className="flex items-center gap-3">
+        <div className="flex-1">
+          <div className="text-sm font-medium">Thumbnail cache</div>
+          <div className="text-xs text-muted-foreground mt-1">
+            {usage
+              ? `${usage.files} thumbnail${usage.files === 1 ? '' : 's'}, ${formatMb(usage.bytes)}. They are rebuilt when needed.`
+              : 'Checking...'}
+          </div>
+        </div>
+        <Button onClick={handlePurge} disabled={!usage?.files} className="rounded-md" variant="outline" size="sm">
+          <Trash2 className="w-4 h-4 mr-2" />
+          Clear
+        </Button>
+      </div>
+      {message && <div className="text-xs text-accent">{message}</div>}
+      {error && <div className="text-xs text-destructive">{error}</div>}
+    </div>
+  );
+}
@@ -0,0 +1,70 @@
+import type { TranslationProvider, TranslationSettings as Settings } from '@/types';
+
+interface TranslationSettingsProps {
+  settings: Settings;
+  onChange: (updates: Partial<Settings>) => void;
+}
+
+const inputClass = 'w-full h-9 px-3 bg-muted border border-border rounded-md text-sm';
+
+export function TranslationSettings({ settings, onChange }: TranslationSettingsProps) {
+  return (
+    <div className="mt-8 p-4 border border-border rounded-md space-y-3">
+      <div>
+        <div className="text-sm font-medium">Translation</div>
+        <div className="text-xs text-muted-foreground mt-1">
+          Used by the Translate button on the capture popup
+        </div>
+      </div>
+      <div className="flex items-center gap-3 text-sm">
+        <select
+          value={settings.provider}
+          onChange={(e) => onChange({ provider: e.target.value as TranslationProvider })}
+          className="flex-1 h-9 px-2 bg-muted border border-border rounded-md text-sm"
+        >
+          <option value="apple">Apple Translate (via Shortcuts)</option>
+          <option value="api">Translation API</option>
+        </select>
+        <input
+          type="text"
+          value={settings.target_language}
+          onChange={(e) => onChange({ target_language: e.target.value.trim() })}
+          placeholder="en"
+          aria-label="Target language"
+          className="w-20 h-9 px-2 bg-muted border border-border rounded-md text-sm font-mono"
+        />
+      </div>
+      {settings.provider === 'apple' ? (
+        <div className="space-y-1">
+          <input
+            type="text"
+            value={settings.shortcut_name}
+            onChange={(e) => onChange({ shortcut_name: e.target.value })}
+            placeholder="Shortcut name"
+            className={inputClass}
+          />
+          <div className="text-xs text-muted-foreground">
+            The shortcut gets a dictionary with <code>text</code> and <code>target_language</code> and should output the translation
+          </div>
+        </div>
+      ) : (
+        <>
+          <input
+            type="url"
+            value={settings.api_url}
+            onChange={(e) => onChange({ api_url: e.target.value })}
+            placeholder="https://libretranslate.com/translate"
+            className={inputClass}
+          />
+          <input
+            type="password"
+            value={settings.api_key}
+            onChange={(e) => onChange({ api_key: e.target.value })}
+            placeholder="API key (optional)"
+            className={inputClass}
+          />
+        </>
+      )}
+    </div>
+  );
+}
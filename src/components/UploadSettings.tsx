@@ -0,0 +1,183 @@
+import type { UploadHeader, UploadProvider, UploadSettings as Settings } from '@/types';
+
+interface UploadSettingsProps {
+  settings: Settings;
+  onChange: (updates: Partial<Settings>) => void;
+}
+
+const inputClass = 'w-full h-9 px-3 bg-muted border border-border rounded-md text-sm';
+
+// "Name: value" per line, the way headers are usually copied from docs.
+const formatPairs = (pairs: UploadHeader[]) => pairs.map((p) => `${p.name}: ${p.value}`).join('\n');
+const parsePairs = (text: string): UploadHeader[] =>
+  text
+    .split('\n')
+    .map((line) => line.split(/:(.*)/s))
+    .filter(([name]) => name.trim())
+    .map(([name, value = '']) => ({ name: name.trim(), value: value.trim() }));
+
+export function UploadSettings({ settings, onChange }: UploadSettingsProps) {
+  const updateCustom = (updates: Partial<Settings['custom']>) =>
+    onChange({ custom: { ...settings.custom, ...updates } });
+  const updateShortener = (updates: Partial<Settings['shortener']>) =>
+    onChange({ shortener: { ...settings.shortener, ...updates } });
+
+  return (
+    <div className="mt-8 p-4 border border-border rounded-md space-y-3">
+      <label className="flex items-center gap-3 cursor-pointer">
+        <input
+          type="checkbox"
+          checked={settings.enabled}
+          onChange={(e) => onChange({ enabled: e.target.checked })}
+          className="w-4 h-4 accent-accent"
+        />
+        <div>
+          <div className="text-sm font-medium">Upload to your image host</div>
+          <div className="text-xs text-muted-foreground mt-1">
+            Share links are copied to the clipboard; Alt+Enter in search uploads
+          </div>
+        </div>
+      </label>
+      {settings.enabled && (
+        <>
+          <select
+            value={settings.provider}
+            onChange={(e) => onChange({ provider: e.target.value as UploadProvider })}
+            className="w-full h-9 px-2 bg-muted border border-border rounded-md text-sm"
+          >
+            <option value="zipline">Zipline</option>
+            <option value="chibisafe">Chibisafe</option>
+            <option value="lutim">Lutim</option>
+            <option value="custom">Custom uploader</option>
+          </select>
+          {settings.provider === 'custom' ? (
+            <>
+              <input
+                type="url"
+                value={settings.custom.endpoint}
+                onChange={(e) => updateCustom({ endpoint: e.target.value })}
+                placeholder="https://example.com/upload"
+                className={inputClass}
+              />
+              <input
+                type="text"
+                value={settings.custom.file_field}
+                onChange={(e) => updateCustom({ file_field: e.target.value })}
+                placeholder="File field (file)"
+                className={inputClass}
+              />
+              <textarea
+                value={formatPairs(settings.custom.headers)}
+                onChange={(e) => updateCustom({ headers: parsePairs(e.target.value) })}
+                placeholder="Authorization: Bearer ..."
+                rows={2}
+                className="w-full px-3 py-2 bg-muted border border-border rounded-md text-sm font-mono"
+              />
+              <input
+                type="text"
+                value={settings.custom.url_template}
+                onChange={(e) => updateCustom({ url_template: e.target.value })}
+                placeholder="{data.link}"
+                className={`${inputClass} font-mono`}
+              />
+              <div className="text-xs text-muted-foreground">
+                <code>{'{path}'}</code> in the link is read from the JSON response, e.g. <code>{'{files.0.url}'}</code>
+              </div>
+            </>
+          ) : (
+            <>
+              <input
+                type="url"
+                value={settings.server_url}
+                onChange={(e) => onChange({ server_url: e.target.value })}
+                placeholder="https://img.example.com"
+                className={inputClass}
+              />
+              {settings.provider !== 'lutim' && (
+                <input
+                  type="password"
+                  value={settings.token}
+                  onChange={(e) => onChange({ token: e.target.value })}
+                  placeholder="API token"
+                  className={inputClass}
+                />
+              )}
+            </>
+          )}
+          <label className="flex items-center gap-2 text-sm cursor-pointer">
+            <input
+              type="checkbox"
+              checked={settings.upload_after_save}
+              onChange={(e) => onChange({ upload_after_save: e.target.checked })}
+              className="w-4 h-4 accent-accent"
+            />
+            Upload every screenshot when it is saved
+          </label>
+          <label className="flex items-center gap-2 text-sm cursor-pointer">
+            <input
+              type="checkbox"
+              checked={settings.auto_format}
+              onChange={(e) => onChange({ auto_format: e.target.checked })}
+              className="w-4 h-4 accent-accent"
+            />
+            Upload in the smallest format (PNG, WebP, or JPEG for photos)
+          </label>
+          <div className="flex items-center gap-3 text-sm">
+            <span className="text-muted-foreground">At once</span>
+            <input
+              type="number"
+              min={1}
+              max={10}
+              value={settings.max_concurrent}
+              onChange={(e) => onChange({ max_concurrent: Math.max(1, Number(e.target.value)) })}
+              className="w-16 h-9 px-2 bg-muted border border-border rounded-md text-sm"
+            />
+            <span className="text-muted-foreground ml-auto">Per minute</span>
+            <input
+              type="number"
+              min={0}
+              value={settings.uploads_per_minute}
+              onChange={(e) => onChange({ uploads_per_minute: Math.max(0, Number(e.target.value)) })}
+              title="0 means no limit"
+              className="w-16 h-9 px-2 bg-muted border border-border rounded-md text-sm"
+            />
+          </div>
+          <label className="flex items-center gap-2 text-sm cursor-pointer">
+            <input
+              type="checkbox"
+              checked={settings.shortener.enabled}
+              onChange={(e) => updateShortener({ enabled: e.target.checked })}
+              className="w-4 h-4 accent-accent"
+            />
+            Shorten links before copying
+          </label>
+          {settings.shortener.enabled && (
+            <>
+              <input
+                type="url"
+                value={settings.shortener.request_url}
+                onChange={(e) => updateShortener({ request_url: e.target.value })}
+                placeholder="https://is.gd/create.php?format=simple&url={url}"
+                className={`${inputClass} font-mono`}
+              />
+              <textarea
+                value={formatPairs(settings.shortener.headers)}
+                onChange={(e) => updateShortener({ headers: parsePairs(e.target.value) })}
+                placeholder="X-Api-Key: ... (optional)"
+                rows={1}
+                className="w-full px-3 py-2 bg-muted border border-border rounded-md text-sm font-mono"
+              />
+              <input
+                type="text"
+                value={settings.shortener.url_template}
+                onChange={(e) => updateShortener({ url_template: e.target.value })}
+                placeholder="Link from JSON, e.g. {shortUrl} (empty for plain text)"
+                className={`${inputClass} font-mono`}
+              />
+            </>
+          )}
+        </>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,101 @@
+import { useEffect, useState } from 'react';
+import { AppWindow, RefreshCw, X } from 'lucide-react';
+import { invoke } from '@/lib/tauri';
+import type { WindowThumbnail } from '@/types';
+
+interface WindowPickerProps {
+  onClose: () => void;
+  /** Scroll the picked window and stitch it into one tall capture. */
+  scrolling?: boolean;
+}
+
+export function WindowPicker({ onClose, scrolling = false }: WindowPickerProps) {
+  const [windows, setWindows] = useState<WindowThumbnail[]>([]);
+  const [isLoading, setIsLoading] = useState(true);
+  const [error, setError] = useState<string | null>(null);
+  const [isCapturing, setIsCapturing] = useState(false);
+
+  const load = () => {
+    setIsLoading(true);
+    setError(null);
+    invoke('list_capture_windows', {})
+      .then(setWindows)
+      .catch((err) => setError(String(err)))
+      .finally(() => setIsLoading(false));
+  };
+
+  useEffect(load, []);
+
+  const handleCapture = async (target: WindowThumbnail) => {
+    setError(null);
+    setIsCapturing(true);
+    try {
+      if (scrolling) {
+        await invoke('capture_scrolling', { windowId: target.id });
+      } else {
+        await invoke('capture_window', { windowId: target.id });
+      }
+      onClose();
+    } catch (err) {
+      setError(String(err));
+      console.error('Failed to capture window:', err);
+    } finally {
+      setIsCapturing(false);
+    }
+  };
+
+  return (
+    <div className="p-4 border border-border rounded-md space-y-3">
+      <div className="flex items-start justify-between gap-3">
+        <div>
+          <div className="text-sm font-medium">{scrolling ? 'Scrolling capture' : 'Capture a window'}</div>
+          <div className="text-xs text-muted-foreground mt-1">
+            {scrolling
+              ? isCapturing
+                ? 'Scrolling and capturing...'
+                : 'Click a window to scroll through it and capture everything'
+              : 'Click a window to capture it'}
+          </div>
+        </div>
+        <div className="flex items-center gap-2">
+          <button onClick={load} disabled={isLoading} className="text-muted-foreground hover:text-foreground">
+            <RefreshCw className={`w-4 h-4 ${isLoading ? 'animate-spin' : ''}`} />
+          </button>
+          <button onClick={onClose} className="text-muted-foreground hover:text-foreground">
+            <X className="w-4 h-4" />
+          </button>
+        </div>
+      </div>
+
+      {!isLoading && windows.length === 0 && !error && (
+        <div className="text-xs text-muted-foreground">No windows to capture</div>
+      )}
+
+      <div className="grid grid-cols-3 gap-2">
+        {windows.map((target) => (
+          <button
+            key={target.id}
+            onClick={() => handleCapture(target)}
+            disabled={isCapturing}
+            title={target.title ? `${target.app_name} - ${target.title}` : target.app_name}
+            className="flex flex-col gap-1 p-1 rounded-md border border-border hover:border-accent text-left"
+          >
+            <div className="aspect-video w-full rounded overflow-hidden bg-muted flex items-center justify-center">
+              {target.thumbnail ? (
+                <img src={target.thumbnail} alt="" className="w-full h-full object-contain" />
+              ) : (
+                <AppWindow className="w-5 h-5 text-muted-foreground" />
+              )}
+            </div>
+            <div className="text-[11px] font-medium truncate">{target.app_name}</div>
+            <div className="text-[10px] text-muted-foreground truncate">
+              {target.title || `${target.bounds.width} x ${target.bounds.height}`}
+            </div>
+          </button>
+        ))}
+      </div>
+
+      {error && <div className="text-xs text-destructive">{error}</div>}
+    </div>
+  );
+}
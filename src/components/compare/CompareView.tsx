@@ -0,0 +1,126 @@
+import { useEffect, useState } from 'react';
+import { X } from 'lucide-react';
+import { emit, invoke, listen } from '@/lib/tauri';
+import { cn } from '@/lib/utils';
+import type { ComparisonData } from '@/types';
+
+type CompareMode = 'side-by-side' | 'slider' | 'blend';
+
+const modes: { mode: CompareMode; label: string }[] = [
+  { mode: 'side-by-side', label: 'Side by side' },
+  { mode: 'slider', label: 'Slider' },
+  { mode: 'blend', label: 'Blend' },
+];
+
+const toDataUrl = (base64: string) => `data:image/png;base64,${base64}`;
+const fileName = (path: string) => path.split('/').pop() ?? path;
+
+export function CompareView() {
+  const [data, setData] = useState<ComparisonData | null>(null);
+  const [mode, setMode] = useState<CompareMode>('slider');
+  const [split, setSplit] = useState(50);
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    listen('compare-data', setData)
+      .then((fn) => {
+        unlisten = fn;
+        return emit('compare-ready', {});
+      })
+      .catch((err) => console.error('Failed to set up compare window:', err));
+    return () => unlisten?.();
+  }, []);
+
+  useEffect(() => {
+    const handleKeyDown = (e: KeyboardEvent) => {
+      if (e.key === 'Escape') {
+        invoke('close_compare_window').catch(() => {});
+      }
+    };
+    window.addEventListener('keydown', handleKeyDown);
+    return () => window.removeEventListener('keydown', handleKeyDown);
+  }, []);
+
+  if (!data) {
+    return (
+      <div className="w-screen h-screen flex items-center justify-center bg-neutral-900">
+        <div className="text-neutral-400">Loading comparison...</div>
+      </div>
+    );
+  }
+
+  const before = toDataUrl(data.before_image);
+  const after = toDataUrl(data.after_image);
+  const aspectRatio = `${data.width} / ${data.height}`;
+
+  return (
+    <div className="w-screen h-screen flex flex-col bg-neutral-900 overflow-hidden">
+      <div className="h-12 bg-neutral-800 border-b border-neutral-700 flex items-center justify-between px-4">
+        <div className="flex gap-1">
+          {modes.map(({ mode: value, label }) => (
+            <button
+              key={value}
+              onClick={() => setMode(value)}
+              className={cn(
+                'px-3 py-1.5 text-xs rounded transition-colors',
+                mode === value
+                  ? 'bg-blue-600 text-white'
+                  : 'bg-neutral-700 text-neutral-300 hover:bg-neutral-600'
+              )}
+            >
+              {label}
+            </button>
+          ))}
+        </div>
+        <div className="flex items-center gap-3 text-xs text-neutral-400 min-w-0">
+          <span className="truncate">{fileName(data.before_path)}</span>
+          <span>vs</span>
+          <span className="truncate">{fileName(data.after_path)}</span>
+          <button
+            onClick={() => invoke('close_compare_window')}
+            className="p-1.5 rounded-md hover:bg-neutral-700 text-neutral-400 hover:text-white transition-colors"
+          >
+            <X size={18} />
+          </button>
+        </div>
+      </div>
+
+      <div className="flex-1 overflow-auto flex items-center justify-center p-6">
+        {mode === 'side-by-side' && (
+          <div className="grid grid-cols-2 gap-4 w-full">
+            {[before, after].map((src, i) => (
+              <img key={i} src={src} alt="" className="w-full h-auto shadow-2xl rounded" />
+            ))}
+          </div>
+        )}
+
+        {mode === 'slider' && (
+          <div className="relative max-w-full max-h-full shadow-2xl" style={{ aspectRatio, width: data.width }}>
+            <img src={after} alt="" className="absolute inset-0 w-full h-full" draggable={false} />
+            <img
+              src={before}
+              alt=""
+              className="absolute inset-0 w-full h-full"
+              style={{ clipPath: `inset(0 ${100 - split}% 0 0)` }}
+              draggable={false}
+            />
+            <div className="absolute inset-y-0 w-0.5 bg-white pointer-events-none" style={{ left: `${split}%` }} />
+            <input
+              type="range"
+              min={0}
+              max={100}
+              step={0.5}
+              value={split}
+              onChange={(e) => setSplit(Number(e.target.value))}
+              className="absolute inset-0 w-full h-full opacity-0 cursor-ew-resize"
+            />
+          </div>
+        )}
+
+        {mode === 'blend' && (
+          <img src={toDataUrl(data.blend_image)} alt="" className="max-w-full max-h-full shadow-2xl" />
+        )}
+      </div>
+    </div>
+  );
+}
@@ -1,6 +1,8 @@
 import { forwardRef, useEffect, useImperativeHandle, useRef, useState, useCallback } from 'react';
 import * as fabric from 'fabric';
 import type { EditorState } from './EditorApp';
+import type { AnnotationShape, EditorGrid } from '@/types';
+import { guidesFromBounds, snapBounds, snapPoint, type Guides } from './snapping';
 
 // Custom metadata type for blur regions
 interface BlurRegionData {
@@ -10,14 +12,29 @@ interface BlurRegionData {
 }
 
 interface AnnotationCanvasProps {
-  imageData: string;
+  imageUrl: string;
   editorState: EditorState;
+  grid: EditorGrid;
   containerSize: { width: number; height: number };
   onZoomCalculated?: (zoom: number) => void;
+  /** Called with the two ends of a measure drag, in image pixels. */
+  onMeasure?: (start: { x: number; y: number }, end: { x: number; y: number }) => void;
+  /** Called with the clicked pixel when the eyedropper is active, in image pixels. */
+  onPickColor?: (point: { x: number; y: number }) => void;
+  /** Called with the dragged crop rectangle, in image pixels. */
+  onCrop?: (rect: { x: number; y: number; width: number; height: number }) => void;
+  /** Called with a dragged redaction rectangle, in image pixels. */
+  onRedact?: (rect: { x: number; y: number; width: number; height: number }) => void;
+  /** Editable shapes saved with the capture, added once the image loads. */
+  initialShapes?: AnnotationShape[] | null;
 }
 
 export interface CanvasRef {
   exportToDataURL: () => string | null;
+  /** The annotations in image pixels, or null if some can't be drawn by the backend. */
+  getAnnotationShapes: () => AnnotationShape[] | null;
+  /** Swaps in edited pixels of the same size, keeping the annotations. */
+  replaceImage: (imageUrl: string) => Promise<void>;
   undo: () => void;
   redo: () => void;
   setZoom: (zoom: number) => void;
@@ -62,6 +79,133 @@ function parseGradient(cssGradient: string, width: number, height: number): fabr
   });
 }
 
+// Soft drop shadow used when the active style has shadows on
+function createShadow(): fabric.Shadow {
+  return new fabric.Shadow({
+    color: 'rgba(0, 0, 0, 0.35)',
+    blur: 8,
+    offsetX: 2,
+    offsetY: 2,
+  });
+}
+
+// Arrow made of a line and a triangle head centered on the end point
+function createArrow(
+  x1: number,
+  y1: number,
+  x2: number,
+  y2: number,
+  color: string,
+  strokeWidth: number,
+  shadow: boolean
+): fabric.Group {
+  // Calculate arrow head angle and size
+  const angle = Math.atan2(y2 - y1, x2 - x1);
+  const headLength = 15;
+
+  // Create line with coordinates relative to group center
+  const centerX = (x1 + x2) / 2;
+  const centerY = (y1 + y2) / 2;
+
+  const arrowLine = new fabric.Line(
+    [x1 - centerX, y1 - centerY, x2 - centerX, y2 - centerY],
+    {
+      stroke: color,
+      strokeWidth,
+      originX: 'center',
+      originY: 'center',
+    }
+  );
+
+  // Create arrow head positioned at the end point, relative to group center
+  const arrowHead = new fabric.Triangle({
+    left: x2 - centerX,
+    top: y2 - centerY,
+    width: headLength,
+    height: headLength,
+    fill: color,
+    angle: (angle * 180 / Math.PI) + 90,
+    originX: 'center',
+    originY: 'center',
+  });
+
+  // Group the line and head together
+  const arrowGroup = new fabric.Group([arrowLine, arrowHead], {
+    left: centerX,
+    top: centerY,
+    originX: 'center',
+    originY: 'center',
+    shadow: shadow ? createShadow() : null,
+  });
+
+  // Mark this group as an arrow for serialization
+  (arrowGroup as fabric.Group & { arrowData?: object }).arrowData = {
+    color,
+    strokeWidth,
+  };
+
+  return arrowGroup;
+}
+
+// Rebuild an editable object from a saved shape; `offset` is where the
+// image sits on the canvas
+function shapeToObject(shape: AnnotationShape, offset: { x: number; y: number }): fabric.Object | null {
+  const shadow = shape.shadow ? createShadow() : undefined;
+  switch (shape.kind) {
+    case 'rect':
+      return new fabric.Rect({
+        left: shape.x + offset.x - shape.stroke_width / 2,
+        top: shape.y + offset.y - shape.stroke_width / 2,
+        width: shape.width,
+        height: shape.height,
+        fill: shape.filled ? shape.color : 'transparent',
+        stroke: shape.color,
+        strokeWidth: shape.filled ? 0 : shape.stroke_width,
+        shadow,
+      });
+    case 'ellipse':
+      return new fabric.Ellipse({
+        left: shape.cx - shape.rx + offset.x - shape.stroke_width / 2,
+        top: shape.cy - shape.ry + offset.y - shape.stroke_width / 2,
+        rx: shape.rx,
+        ry: shape.ry,
+        fill: 'transparent',
+        stroke: shape.color,
+        strokeWidth: shape.stroke_width,
+        shadow,
+      });
+    case 'line':
+      return new fabric.Line(
+        [shape.from[0] + offset.x, shape.from[1] + offset.y, shape.to[0] + offset.x, shape.to[1] + offset.y],
+        { stroke: shape.color, strokeWidth: shape.stroke_width, shadow }
+      );
+    case 'arrow':
+      return createArrow(
+        shape.from[0] + offset.x,
+        shape.from[1] + offset.y,
+        shape.to[0] + offset.x,
+        shape.to[1] + offset.y,
+        shape.color,
+        shape.stroke_width,
+        Boolean(shape.shadow)
+      );
+    case 'text':
+      return new fabric.IText(shape.text, {
+        left: shape.x + offset.x,
+        top: shape.y + offset.y,
+        fontSize: shape.font_size,
+        fill: shape.color,
+        fontFamily: shape.font_family
+          ? `"${shape.font_family}", Inter, system-ui, sans-serif`
+          : 'Inter, system-ui, sans-serif',
+        shadow,
+      });
+    default:
+      // The editor has no freehand tool to edit paths with
+      return null;
+  }
+}
+
 // Create a pixelated blur region from the underlying image
 async function createBlurRegion(
   sourceImage: fabric.FabricImage,
@@ -134,7 +278,7 @@ async function createBlurRegion(
 }
 
 export const AnnotationCanvas = forwardRef<CanvasRef, AnnotationCanvasProps>(
-  ({ imageData, editorState, containerSize, onZoomCalculated }, ref) => {
+  ({ imageUrl, editorState, grid, containerSize, onZoomCalculated, onMeasure, onPickColor, onCrop, onRedact, initialShapes }, ref) => {
     const canvasRef = useRef<HTMLCanvasElement>(null);
     const fabricRef = useRef<fabric.Canvas | null>(null);
     const imageRef = useRef<fabric.FabricImage | null>(null);
@@ -175,7 +319,8 @@ export const AnnotationCanvas = forwardRef<CanvasRef, AnnotationCanvasProps>(
       fabricRef.current = canvas;
 
       // Load the screenshot image
-      fabric.FabricImage.fromURL(`data:image/png;base64,${imageData}`).then((img) => {
+      // The protocol allows any origin, so loading it as CORS keeps the canvas exportable
+      fabric.FabricImage.fromURL(imageUrl, { crossOrigin: 'anonymous' }).then((img) => {
         // Skip if component was unmounted during async load
         if (!isMountedRef.current || !fabricRef.current) {
           console.log('[AnnotationCanvas] Skipping - component unmounted during image load');
@@ -278,7 +423,7 @@ export const AnnotationCanvas = forwardRef<CanvasRef, AnnotationCanvasProps>(
         setOriginalImageSize({ width: 0, height: 0 });
         setCanvasSize({ width: 0, height: 0 });
       };
-    }, [imageData]);
+    }, [imageUrl]);
 
     // Calculate initial zoom when both containerSize and canvasSize become available
     useEffect(() => {
@@ -443,6 +588,7 @@ export const AnnotationCanvas = forwardRef<CanvasRef, AnnotationCanvasProps>(
             scaleY: objData.scaleY as number ?? 1,
             originX: (objData.originX as fabric.TOriginX) ?? 'center',
             originY: (objData.originY as fabric.TOriginY) ?? 'center',
+            shadow: (objData.shadow as fabric.Shadow | null) ?? null,
           });
 
           return group;
@@ -619,6 +765,56 @@ export const AnnotationCanvas = forwardRef<CanvasRef, AnnotationCanvasProps>(
       };
     }, [saveToHistory]);
 
+    // Snap targets: the image and every annotation not in `exclude`
+    const collectGuides = useCallback((exclude: fabric.Object[] = []): Guides => {
+      const canvas = fabricRef.current;
+      if (!canvas) return { x: [], y: [] };
+      const boxes = canvas.getObjects()
+        .filter(obj => obj !== bgRectRef.current && !exclude.includes(obj))
+        .map(obj => obj.getBoundingRect());
+      return guidesFromBounds(boxes);
+    }, []);
+
+    // Snap dragged annotations to the grid or alignment guides
+    useEffect(() => {
+      const canvas = fabricRef.current;
+      if (!canvas) return;
+
+      const origin = { x: editorState.padding.left, y: editorState.padding.top };
+
+      const handleObjectMoving = ({ target }: { target: fabric.Object }) => {
+        const moving = target instanceof fabric.ActiveSelection
+          ? [target, ...target.getObjects()]
+          : [target];
+        const { dx, dy } = snapBounds(target.getBoundingRect(), grid, origin, collectGuides(moving));
+        if (dx === 0 && dy === 0) return;
+        target.set({ left: (target.left || 0) + dx, top: (target.top || 0) + dy });
+        target.setCoords();
+      };
+
+      canvas.on('object:moving', handleObjectMoving);
+
+      return () => {
+        canvas.off('object:moving', handleObjectMoving);
+      };
+    }, [grid, editorState.padding, collectGuides]);
+
+    // Restore shapes saved with the capture once the image is in place
+    const restoredShapesRef = useRef(false);
+    useEffect(() => {
+      const canvas = fabricRef.current;
+      if (!canvas || !initialShapes || restoredShapesRef.current || canvasSize.width === 0) return;
+      restoredShapesRef.current = true;
+
+      const offset = { x: editorState.padding.left, y: editorState.padding.top };
+      for (const shape of initialShapes) {
+        const obj = shapeToObject(shape, offset);
+        if (obj) canvas.add(obj);
+      }
+      canvas.renderAll();
+      saveToHistory();
+    }, [initialShapes, canvasSize, editorState.padding, saveToHistory]);
+
     // Handle mouse events for drawing
     useEffect(() => {
       const canvas = fabricRef.current;
@@ -627,7 +823,18 @@ export const AnnotationCanvas = forwardRef<CanvasRef, AnnotationCanvasProps>(
       const handleMouseDown = (opt: fabric.TPointerEventInfo) => {
         if (editorState.tool === 'select') return;
 
-        const pointer = canvas.getScenePoint(opt.e);
+        // Sample the pixel under the cursor as-is; snapping would move it
+        if (editorState.tool === 'eyedropper') {
+          const point = canvas.getScenePoint(opt.e);
+          onPickColor?.({
+            x: Math.floor(point.x - editorState.padding.left),
+            y: Math.floor(point.y - editorState.padding.top),
+          });
+          return;
+        }
+
+        const origin = { x: editorState.padding.left, y: editorState.padding.top };
+        const pointer = snapPoint(canvas.getScenePoint(opt.e), grid, origin, collectGuides());
         startPointRef.current = { x: pointer.x, y: pointer.y };
         setIsDrawing(true);
 
@@ -673,14 +880,39 @@ export const AnnotationCanvas = forwardRef<CanvasRef, AnnotationCanvasProps>(
               left: pointer.x,
               top: pointer.y,
               fontSize: editorState.fontSize,
-              fill: editorState.color,
-              fontFamily: 'Inter, system-ui, sans-serif',
+              fill: editorState.textColor,
+              fontFamily: editorState.fontFamily
+                ? `"${editorState.fontFamily}", Inter, system-ui, sans-serif`
+                : 'Inter, system-ui, sans-serif',
+              shadow: editorState.shadow ? createShadow() : null,
             });
             canvas.add(text);
             canvas.setActiveObject(text);
             text.enterEditing();
             saveToHistory();
             return;
+          case 'measure':
+            shape = new fabric.Line([pointer.x, pointer.y, pointer.x, pointer.y], {
+              stroke: '#22d3ee',
+              strokeWidth: 1,
+              strokeDashArray: [4, 4],
+              excludeFromExport: true,
+            });
+            break;
+          case 'crop':
+            shape = new fabric.Rect({
+              left: pointer.x,
+              top: pointer.y,
+              width: 0,
+              height: 0,
+              fill: 'rgba(34, 211, 238, 0.1)',
+              stroke: '#22d3ee',
+              strokeWidth: 1,
+              strokeDashArray: [4, 4],
+              strokeUniform: true,
+              excludeFromExport: true,
+            });
+            break;
           case 'blur':
             shape = new fabric.Rect({
               left: pointer.x,
@@ -697,6 +929,9 @@ export const AnnotationCanvas = forwardRef<CanvasRef, AnnotationCanvasProps>(
         }
 
         if (shape) {
+          if (editorState.shadow && !['blur', 'measure', 'crop'].includes(editorState.tool)) {
+            shape.set({ shadow: createShadow() });
+          }
           canvas.add(shape);
           activeShapeRef.current = shape;
         }
@@ -705,7 +940,13 @@ export const AnnotationCanvas = forwardRef<CanvasRef, AnnotationCanvasProps>(
       const handleMouseMove = (opt: fabric.TPointerEventInfo) => {
         if (!isDrawing || !startPointRef.current || !activeShapeRef.current) return;
 
-        const pointer = canvas.getScenePoint(opt.e);
+        const origin = { x: editorState.padding.left, y: editorState.padding.top };
+        const pointer = snapPoint(
+          canvas.getScenePoint(opt.e),
+          grid,
+          origin,
+          collectGuides([activeShapeRef.current])
+        );
         const startX = startPointRef.current.x;
         const startY = startPointRef.current.y;
 
@@ -740,6 +981,37 @@ export const AnnotationCanvas = forwardRef<CanvasRef, AnnotationCanvasProps>(
         setIsDrawing(false);
         startPointRef.current = null;
 
+        // Measure lines are only a guide; report the ends and drop the line
+        if (editorState.tool === 'measure' && activeShapeRef.current instanceof fabric.Line) {
+          const line = activeShapeRef.current;
+          canvas.remove(line);
+          activeShapeRef.current = null;
+          const { left, top } = editorState.padding;
+          onMeasure?.(
+            { x: (line.x1 || 0) - left, y: (line.y1 || 0) - top },
+            { x: (line.x2 || 0) - left, y: (line.y2 || 0) - top }
+          );
+          return;
+        }
+
+        // Crop outlines are only a guide too; report the area and drop it
+        if (editorState.tool === 'crop' && activeShapeRef.current instanceof fabric.Rect) {
+          const rect = activeShapeRef.current;
+          canvas.remove(rect);
+          activeShapeRef.current = null;
+          // Anything dragged over the padding is trimmed to the image
+          const left = Math.round((rect.left || 0) - editorState.padding.left);
+          const top = Math.round((rect.top || 0) - editorState.padding.top);
+          const x = Math.max(0, left);
+          const y = Math.max(0, top);
+          const width = left + Math.round(rect.width || 0) - x;
+          const height = top + Math.round(rect.height || 0) - y;
+          if (width > 0 && height > 0) {
+            onCrop?.({ x, y, width, height });
+          }
+          return;
+        }
+
         // Convert arrow line to grouped arrow (line + head)
         if (editorState.tool === 'arrow' && activeShapeRef.current instanceof fabric.Line) {
           const line = activeShapeRef.current;
@@ -751,50 +1023,7 @@ export const AnnotationCanvas = forwardRef<CanvasRef, AnnotationCanvasProps>(
           // Remove the temporary line
           canvas.remove(line);
 
-          // Calculate arrow head angle and size
-          const angle = Math.atan2(y2 - y1, x2 - x1);
-          const headLength = 15;
-
-          // Create line with coordinates relative to group center
-          const centerX = (x1 + x2) / 2;
-          const centerY = (y1 + y2) / 2;
-
-          const arrowLine = new fabric.Line(
-            [x1 - centerX, y1 - centerY, x2 - centerX, y2 - centerY],
-            {
-              stroke: editorState.color,
-              strokeWidth: editorState.strokeWidth,
-              originX: 'center',
-              originY: 'center',
-            }
-          );
-
-          // Create arrow head positioned at the end point, relative to group center
-          const arrowHead = new fabric.Triangle({
-            left: x2 - centerX,
-            top: y2 - centerY,
-            width: headLength,
-            height: headLength,
-            fill: editorState.color,
-            angle: (angle * 180 / Math.PI) + 90,
-            originX: 'center',
-            originY: 'center',
-          });
-
-          // Group the line and head together
-          const arrowGroup = new fabric.Group([arrowLine, arrowHead], {
-            left: centerX,
-            top: centerY,
-            originX: 'center',
-            originY: 'center',
-          });
-
-          // Mark this group as an arrow for serialization
-          (arrowGroup as fabric.Group & { arrowData?: object }).arrowData = {
-            color: editorState.color,
-            strokeWidth: editorState.strokeWidth,
-          };
-
+          const arrowGroup = createArrow(x1, y1, x2, y2, editorState.color, editorState.strokeWidth, editorState.shadow);
           canvas.add(arrowGroup);
           activeShapeRef.current = null;
 
@@ -823,6 +1052,19 @@ export const AnnotationCanvas = forwardRef<CanvasRef, AnnotationCanvasProps>(
           // Remove the temporary rectangle
           canvas.remove(rect);
 
+          // Redact the capture itself when the backend can, so the pixels
+          // are gone rather than covered
+          if (onRedact) {
+            onRedact({
+              x: Math.max(0, Math.round(rectLeft - editorState.padding.left)),
+              y: Math.max(0, Math.round(rectTop - editorState.padding.top)),
+              width: Math.round(rectWidth + Math.min(0, rectLeft - editorState.padding.left)),
+              height: Math.round(rectHeight + Math.min(0, rectTop - editorState.padding.top)),
+            });
+            activeShapeRef.current = null;
+            return;
+          }
+
           // Create pixelated blur region from the image
           const img = imageRef.current;
           if (img) {
@@ -863,7 +1105,7 @@ export const AnnotationCanvas = forwardRef<CanvasRef, AnnotationCanvasProps>(
         canvas.off('mouse:move', handleMouseMove);
         canvas.off('mouse:up', handleMouseUp);
       };
-    }, [editorState, isDrawing, saveToHistory]);
+    }, [editorState, grid, isDrawing, saveToHistory, onMeasure, onPickColor, onCrop, onRedact, collectGuides]);
 
     // Delete key handler
     useEffect(() => {
@@ -948,6 +1190,103 @@ export const AnnotationCanvas = forwardRef<CanvasRef, AnnotationCanvasProps>(
 
         return dataURL;
       },
+      getAnnotationShapes: () => {
+        const canvas = fabricRef.current;
+        if (!canvas) return null;
+
+        const { left: offsetX, top: offsetY } = editorState.padding;
+        const toImage = (point: fabric.Point): [number, number] => [point.x - offsetX, point.y - offsetY];
+        const lineEnds = (line: fabric.Line) => {
+          const { x1, y1, x2, y2 } = line.calcLinePoints();
+          const matrix = line.calcTransformMatrix();
+          return {
+            from: toImage(new fabric.Point(x1, y1).transform(matrix)),
+            to: toImage(new fabric.Point(x2, y2).transform(matrix)),
+          };
+        };
+
+        const shapes: AnnotationShape[] = [];
+        for (const obj of canvas.getObjects()) {
+          if (obj === bgRectRef.current || obj === imageRef.current) continue;
+          // Rotation and redactions only exist in the canvas renderer
+          if (obj.angle) return null;
+
+          const scaleX = obj.scaleX || 1;
+          const scaleY = obj.scaleY || 1;
+          const center = toImage(obj.getCenterPoint());
+          const stroke = typeof obj.stroke === 'string' ? obj.stroke : null;
+          const strokeWidth = (obj.strokeWidth || 0) * scaleX;
+          const shadow = Boolean(obj.shadow);
+
+          if (obj instanceof fabric.IText) {
+            if (typeof obj.fill !== 'string') return null;
+            const family = obj.fontFamily?.match(/^"([^"]+)"/)?.[1] ?? '';
+            shapes.push({
+              kind: 'text',
+              x: center[0] - (obj.width * scaleX) / 2,
+              y: center[1] - (obj.height * scaleY) / 2,
+              text: obj.text,
+              color: obj.fill,
+              font_size: (obj.fontSize || 0) * scaleY,
+              font_family: family,
+              shadow,
+            });
+          } else if (obj instanceof fabric.Rect) {
+            const filled = typeof obj.fill === 'string' && obj.fill !== 'transparent';
+            const color = filled ? obj.fill as string : stroke;
+            if (!color) return null;
+            const width = obj.width * scaleX;
+            const height = obj.height * scaleY;
+            shapes.push({
+              kind: 'rect',
+              x: center[0] - width / 2,
+              y: center[1] - height / 2,
+              width,
+              height,
+              color,
+              stroke_width: strokeWidth,
+              filled,
+              shadow,
+            });
+          } else if (obj instanceof fabric.Ellipse) {
+            if (!stroke) return null;
+            shapes.push({
+              kind: 'ellipse',
+              cx: center[0],
+              cy: center[1],
+              rx: obj.rx * scaleX,
+              ry: obj.ry * scaleY,
+              color: stroke,
+              stroke_width: strokeWidth,
+              shadow,
+            });
+          } else if (obj instanceof fabric.Line) {
+            if (!stroke) return null;
+            shapes.push({ kind: 'line', ...lineEnds(obj), color: stroke, stroke_width: strokeWidth, shadow });
+          } else if (obj instanceof fabric.Group) {
+            // Arrows are a line grouped with a triangle head
+            const line = obj.getObjects().find((child): child is fabric.Line => child instanceof fabric.Line);
+            const hasHead = obj.getObjects().some(child => child instanceof fabric.Triangle);
+            if (!line || !hasHead || typeof line.stroke !== 'string') return null;
+            shapes.push({
+              kind: 'arrow',
+              ...lineEnds(line),
+              color: line.stroke,
+              stroke_width: (line.strokeWidth || 0) * scaleX,
+              shadow,
+            });
+          } else {
+            return null;
+          }
+        }
+        return shapes;
+      },
+      replaceImage: async (url: string) => {
+        const img = imageRef.current;
+        if (!img || !fabricRef.current) return;
+        await img.setSrc(url, { crossOrigin: 'anonymous' });
+        fabricRef.current.renderAll();
+      },
       undo: () => {
         if (historyIndexRef.current > 0) {
           restoreFromHistory(historyIndexRef.current - 1);
@@ -984,9 +1323,22 @@ export const AnnotationCanvas = forwardRef<CanvasRef, AnnotationCanvasProps>(
       },
     }), [canvasSize, restoreFromHistory, editorState.borderRadius]);
 
+    // The grid is an HTML overlay so it never ends up in exported images
+    const zoom = editorState.zoom || 1;
+    const gridStyle = {
+      backgroundImage:
+        'linear-gradient(to right, rgba(255, 255, 255, 0.18) 1px, transparent 1px), ' +
+        'linear-gradient(to bottom, rgba(255, 255, 255, 0.18) 1px, transparent 1px)',
+      backgroundSize: `${grid.size * zoom}px ${grid.size * zoom}px`,
+      backgroundPosition: `${editorState.padding.left * zoom}px ${editorState.padding.top * zoom}px`,
+    };
+
     return (
-      <div className="shadow-2xl rounded-lg overflow-hidden">
+      <div className="relative shadow-2xl rounded-lg overflow-hidden">
         <canvas ref={canvasRef} />
+        {grid.show_grid && grid.size > 0 && (
+          <div className="absolute inset-0 pointer-events-none" style={gridStyle} />
+        )}
       </div>
     );
   }
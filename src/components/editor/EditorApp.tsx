@@ -3,29 +3,46 @@ import { AnnotationCanvas } from './AnnotationCanvas';
 import { Toolbar } from './Toolbar';
 import { ToolSettings } from './ToolSettings';
 import { PaddingControls } from './PaddingControls';
+import { GridControls } from './GridControls';
+import { StylePresets } from './StylePresets';
 import { useEditor } from '@/hooks/useEditor';
 import { invoke } from '@/lib/tauri';
-import { Save, Copy, X, Settings2, Undo2, Redo2 } from 'lucide-react';
+import { refreshed } from '@/lib/utils';
+import type { AnnotationShape, AnnotationStyle, AppConfig, ColorSample, EditorGrid, FlipAxis, MeasurePoint, Measurement, PaletteColor, PixelRect, RedactStyle } from '@/types';
+import { Save, Copy, X, Settings2, Undo2, Redo2, FileText, Layers, Stamp } from 'lucide-react';
 
-export type ToolType = 'select' | 'rect' | 'ellipse' | 'arrow' | 'line' | 'text' | 'blur';
+export type ToolType = 'select' | 'rect' | 'ellipse' | 'arrow' | 'line' | 'text' | 'blur' | 'measure' | 'eyedropper' | 'crop';
 
 export interface EditorState {
   tool: ToolType;
   color: string;
   strokeWidth: number;
   fontSize: number;
+  /** Empty means the built-in font stack. */
+  fontFamily: string;
+  textColor: string;
+  shadow: boolean;
   padding: { top: number; right: number; bottom: number; left: number };
   backgroundColor: string;
   borderRadius: number;
   zoom: number; // 0.1 to 2, where 1 = 100%
 }
 
+const DEFAULT_GRID: EditorGrid = {
+  show_grid: false,
+  size: 16,
+  snap_to_grid: false,
+  snap_to_guides: false,
+};
+
 export function EditorApp() {
   const {
-    imageData,
+    imageUrl,
     timestamp,
+    filePath,
     canvasRef,
     exportCanvas,
+    reloadImage,
   } = useEditor();
 
   const [editorState, setEditorState] = useState<EditorState>({
@@ -33,6 +50,9 @@ export function EditorApp() {
     color: '#ff0000',
     strokeWidth: 3,
     fontSize: 24,
+    fontFamily: '',
+    textColor: '#ff0000',
+    shadow: false,
     padding: { top: 32, right: 32, bottom: 32, left: 32 },
     backgroundColor: 'linear-gradient(135deg, #667eea 0%, #764ba2 100%)',
     borderRadius: 12,
@@ -42,6 +62,37 @@ export function EditorApp() {
   const canvasContainerRef = useRef<HTMLDivElement>(null);
   const [containerSize, setContainerSize] = useState({ width: 0, height: 0 });
   const [isSidebarOpen, setIsSidebarOpen] = useState(false);
+  const [measurement, setMeasurement] = useState<Measurement | null>(null);
+  const [pickedColor, setPickedColor] = useState<ColorSample | null>(null);
+  const [palette, setPalette] = useState<PaletteColor[]>([]);
+  const [savedShapes, setSavedShapes] = useState<AnnotationShape[] | null>(null);
+  const [grid, setGrid] = useState<EditorGrid>(DEFAULT_GRID);
+  const [fonts, setFonts] = useState<string[]>([]);
+  const [styles, setStyles] = useState<AnnotationStyle[]>([]);
+  const [redaction, setRedaction] = useState<RedactStyle>({ kind: 'pixelate', block_size: 12 });
+
+  useEffect(() => {
+    invoke('get_config')
+      .then((config) => {
+        setGrid(config.editor_grid);
+        setStyles(config.annotation_styles);
+        setRedaction(config.redaction);
+        const { font_family, font_size, color } = config.text_annotation;
+        setEditorState(prev => ({ ...prev, fontFamily: font_family, fontSize: font_size, textColor: color }));
+      })
+      .catch((err) => console.error('Failed to load editor settings:', err));
+    invoke('list_fonts')
+      .then(setFonts)
+      .catch((err) => console.error('Failed to list fonts:', err));
+  }, []);
+  // Shapes saved next to the capture come back editable
+  useEffect(() => {
+    if (!filePath) return;
+    invoke('load_annotations', { filePath })
+      .then(setSavedShapes)
+      .catch((err) => console.error('Failed to load annotations:', err));
+  }, [filePath]);
+
   const sidebarTimeoutRef = useRef<NodeJS.Timeout | null>(null);
 
   // Track container size
@@ -77,6 +128,81 @@ export function EditorApp() {
     setEditorState(prev => ({ ...prev, tool }));
   }, []);
 
+  const handleMeasure = useCallback(async (start: MeasurePoint, end: MeasurePoint) => {
+    try {
+      // Captures are taken at the display's backing scale, so the editor's
+      // pixel ratio is the best guess for converting back to points.
+      setMeasurement(await invoke('measure_distance', {
+        start,
+        end,
+        scaleFactor: window.devicePixelRatio,
+      }));
+    } catch (err) {
+      console.error('Failed to measure:', err);
+    }
+  }, []);
+
+  // Picked colors become the annotation color, so they can be drawn with
+  const handlePickColor = useCallback(async (point: MeasurePoint) => {
+    if (!timestamp) return;
+    try {
+      const sample = await invoke('pick_color', { timestamp, x: point.x, y: point.y });
+      setPickedColor(sample);
+      setEditorState(prev => ({ ...prev, color: sample.hex }));
+    } catch (err) {
+      console.error('Failed to pick color:', err);
+    }
+  }, [timestamp]);
+
+  // The palette only depends on the capture, so fetch it once
+  useEffect(() => {
+    if (editorState.tool !== 'eyedropper' || !timestamp || palette.length > 0) return;
+    invoke('extract_palette', { timestamp, n: 6 })
+      .then(setPalette)
+      .catch((err) => console.error('Failed to extract palette:', err));
+  }, [editorState.tool, timestamp, palette.length]);
+
+  const saveConfig = useCallback(async (updates: Partial<AppConfig>) => {
+    try {
+      // Re-read the config so settings changed elsewhere aren't overwritten.
+      const config = await invoke('get_config');
+      await invoke('update_config', { newConfig: { ...config, ...updates } });
+    } catch (err) {
+      console.error('Failed to save editor settings:', err);
+    }
+  }, []);
+
+  const handleGridChange = useCallback((next: EditorGrid) => {
+    setGrid(next);
+    saveConfig({ editor_grid: next });
+  }, [saveConfig]);
+
+  const handleStylesChange = useCallback((next: AnnotationStyle[]) => {
+    setStyles(next);
+    saveConfig({ annotation_styles: next });
+  }, [saveConfig]);
+
+  const handleRedactionChange = useCallback((next: RedactStyle) => {
+    setRedaction(next);
+    saveConfig({ redaction: next });
+  }, [saveConfig]);
+
+  const handleApplyStyle = useCallback((style: AnnotationStyle) => {
+    setEditorState(prev => ({
+      ...prev,
+      color: style.color,
+      strokeWidth: style.stroke_width,
+      fontFamily: style.font_family,
+      fontSize: style.font_size,
+      textColor: style.text_color,
+      shadow: style.shadow,
+    }));
+  }, []);
+
+  const handleShadowChange = useCallback((shadow: boolean) => {
+    setEditorState(prev => ({ ...prev, shadow }));
+  }, []);
+
   const handleColorChange = useCallback((color: string) => {
     setEditorState(prev => ({ ...prev, color }));
   }, []);
@@ -89,6 +215,14 @@ export function EditorApp() {
     setEditorState(prev => ({ ...prev, fontSize }));
   }, []);
 
+  const handleFontFamilyChange = useCallback((fontFamily: string) => {
+    setEditorState(prev => ({ ...prev, fontFamily }));
+  }, []);
+
+  const handleTextColorChange = useCallback((textColor: string) => {
+    setEditorState(prev => ({ ...prev, textColor }));
+  }, []);
+
   const handlePaddingChange = useCallback((padding: EditorState['padding']) => {
     setEditorState(prev => ({ ...prev, padding }));
   }, []);
@@ -126,13 +260,23 @@ export function EditorApp() {
 
   const handleSave = useCallback(async () => {
     const base64 = await exportCanvas();
+    // Keep the shapes editable next to the original capture; saving the
+    // edited copy closes the editor, so this goes first.
+    const shapes = canvasRef.current?.getAnnotationShapes();
+    if (filePath && shapes) {
+      try {
+        await invoke('save_annotations', { filePath, shapes });
+      } catch (err) {
+        console.error('Failed to save annotations:', err);
+      }
+    }
     if (base64 && timestamp) {
       await invoke('save_edited_screenshot', {
         base64Image: base64,
         timestamp
       });
     }
-  }, [exportCanvas, timestamp]);
+  }, [exportCanvas, canvasRef, filePath, timestamp]);
 
   const handleCopy = useCallback(async () => {
     const base64 = await exportCanvas();
@@ -144,6 +288,94 @@ export function EditorApp() {
     }
   }, [exportCanvas, timestamp]);
 
+  const handleExportPdf = useCallback(async () => {
+    const base64 = await exportCanvas();
+    if (base64 && timestamp) {
+      try {
+        await invoke('export_searchable_pdf', {
+          base64Image: base64,
+          timestamp
+        });
+      } catch (err) {
+        console.error('Failed to export PDF:', err);
+      }
+    }
+  }, [exportCanvas, timestamp]);
+
+  const handleExportAssetPair = useCallback(async () => {
+    const base64 = await exportCanvas();
+    if (base64 && timestamp) {
+      try {
+        await invoke('export_asset_pair', {
+          base64Image: base64,
+          timestamp
+        });
+      } catch (err) {
+        console.error('Failed to export @1x/@2x pair:', err);
+      }
+    }
+  }, [exportCanvas, timestamp]);
+
+  // Redactions change the capture's pixels in place; they can't be undone
+  const handleRedact = useCallback(async (rect: PixelRect) => {
+    if (!timestamp) return;
+    try {
+      await invoke('redact_region', { timestamp, rect, style: redaction });
+      if (imageUrl) await canvasRef.current?.replaceImage(refreshed(imageUrl));
+    } catch (err) {
+      console.error('Failed to redact:', err);
+    }
+  }, [canvasRef, imageUrl, timestamp, redaction]);
+
+  // Crops, rotations and flips edit the cached capture; the canvas reloads
+  // with the result, which clears the annotations.
+  const handleCrop = useCallback(async (rect: PixelRect) => {
+    if (!timestamp) return;
+    try {
+      await invoke('crop_screenshot', { timestamp, rect });
+      reloadImage();
+    } catch (err) {
+      console.error('Failed to crop:', err);
+    }
+  }, [timestamp, reloadImage]);
+
+  const handleRotate = useCallback(async (degrees: number) => {
+    if (!timestamp) return;
+    try {
+      await invoke('rotate_screenshot', { timestamp, degrees });
+      reloadImage();
+    } catch (err) {
+      console.error('Failed to rotate:', err);
+    }
+  }, [timestamp, reloadImage]);
+
+  const handleFlip = useCallback(async (axis: FlipAxis) => {
+    if (!timestamp) return;
+    try {
+      await invoke('flip_screenshot', { timestamp, axis });
+      reloadImage();
+    } catch (err) {
+      console.error('Failed to flip:', err);
+    }
+  }, [timestamp, reloadImage]);
+
+  // Burns the annotations into the capture at full resolution; the canvas
+  // reloads with the result, so they can no longer be edited.
+  const handleFlatten = useCallback(async () => {
+    const shapes = canvasRef.current?.getAnnotationShapes();
+    if (!timestamp || !shapes) {
+      console.error('Only unrotated shapes and text without redactions can be flattened');
+      return;
+    }
+    if (shapes.length === 0) return;
+    try {
+      await invoke('render_annotations', { timestamp, shapes });
+      reloadImage();
+    } catch (err) {
+      console.error('Failed to flatten annotations:', err);
+    }
+  }, [canvasRef, timestamp, reloadImage]);
+
   const handleClose = useCallback(async () => {
     await invoke('close_editor_window');
   }, []);
@@ -183,6 +415,9 @@ export function EditorApp() {
           case 'l': handleToolChange('line'); break;
           case 't': handleToolChange('text'); break;
           case 'b': handleToolChange('blur'); break;
+          case 'm': handleToolChange('measure'); break;
+          case 'i': handleToolChange('eyedropper'); break;
+          case 'c': handleToolChange('crop'); break;
         }
       }
     };
@@ -191,7 +426,7 @@ export function EditorApp() {
     return () => window.removeEventListener('keydown', handleKeyDown);
   }, [handleClose, handleSave, handleCopy, handleToolChange, handleUndo, handleRedo]);
 
-  if (!imageData) {
+  if (!imageUrl) {
     return (
       <div className="w-screen h-screen flex items-center justify-center bg-neutral-900">
         <div className="text-neutral-400">Loading editor...</div>
@@ -222,15 +457,33 @@ export function EditorApp() {
           </div>
           <ToolSettings
             tool={editorState.tool}
-            color={editorState.color}
+            color={editorState.tool === 'text' ? editorState.textColor : editorState.color}
             strokeWidth={editorState.strokeWidth}
             fontSize={editorState.fontSize}
-            onColorChange={handleColorChange}
+            fontFamily={editorState.fontFamily}
+            fonts={fonts}
+            measurement={measurement}
+            pickedColor={pickedColor}
+            palette={palette}
+            onColorChange={editorState.tool === 'text' ? handleTextColorChange : handleColorChange}
             onStrokeWidthChange={handleStrokeWidthChange}
             onFontSizeChange={handleFontSizeChange}
+            onFontFamilyChange={handleFontFamilyChange}
+            redaction={redaction}
+            onRedactionChange={handleRedactionChange}
+            onRotate={handleRotate}
+            onFlip={handleFlip}
           />
         </div>
         <div className="flex items-center gap-2">
+          <button
+            onClick={handleFlatten}
+            className="flex items-center gap-2 px-3 py-1.5 rounded-md bg-neutral-700 hover:bg-neutral-600 text-white text-sm transition-colors"
+            title="Draw the annotations into the capture at full resolution"
+          >
+            <Stamp size={16} />
+            Flatten
+          </button>
           <button
             onClick={handleCopy}
             className="flex items-center gap-2 px-3 py-1.5 rounded-md bg-neutral-700 hover:bg-neutral-600 text-white text-sm transition-colors"
@@ -238,6 +491,22 @@ export function EditorApp() {
             <Copy size={16} />
             Copy
           </button>
+          <button
+            onClick={handleExportPdf}
+            className="flex items-center gap-2 px-3 py-1.5 rounded-md bg-neutral-700 hover:bg-neutral-600 text-white text-sm transition-colors"
+            title="Export as searchable PDF"
+          >
+            <FileText size={16} />
+            PDF
+          </button>
+          <button
+            onClick={handleExportAssetPair}
+            className="flex items-center gap-2 px-3 py-1.5 rounded-md bg-neutral-700 hover:bg-neutral-600 text-white text-sm transition-colors"
+            title="Export name.png and name@2x.png"
+          >
+            <Layers size={16} />
+            @1x/@2x
+          </button>
           <button
             onClick={handleSave}
             className="flex items-center gap-2 px-3 py-1.5 rounded-md bg-blue-600 hover:bg-blue-500 text-white text-sm transition-colors"
@@ -269,10 +538,16 @@ export function EditorApp() {
         >
           <AnnotationCanvas
             ref={canvasRef}
-            imageData={imageData}
+            imageUrl={imageUrl}
             editorState={editorState}
+            grid={grid}
             containerSize={containerSize}
             onZoomCalculated={handleZoomCalculated}
+            onMeasure={handleMeasure}
+            onPickColor={handlePickColor}
+            onCrop={handleCrop}
+            onRedact={handleRedact}
+            initialShapes={savedShapes}
           />
         </div>
 
@@ -310,6 +585,25 @@ export function EditorApp() {
                 onBorderRadiusChange={handleBorderRadiusChange}
                 onZoomChange={handleZoomChange}
               />
+              <div className="mt-6">
+                <GridControls grid={grid} onGridChange={handleGridChange} />
+              </div>
+              <div className="mt-6">
+                <StylePresets
+                  styles={styles}
+                  current={{
+                    color: editorState.color,
+                    stroke_width: editorState.strokeWidth,
+                    font_family: editorState.fontFamily,
+                    font_size: editorState.fontSize,
+                    text_color: editorState.textColor,
+                    shadow: editorState.shadow,
+                  }}
+                  onApply={handleApplyStyle}
+                  onStylesChange={handleStylesChange}
+                  onShadowChange={handleShadowChange}
+                />
+              </div>
             </div>
           </div>
         </div>
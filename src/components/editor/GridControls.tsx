@@ -0,0 +1,52 @@
+import type { EditorGrid } from '@/types';
+
+interface GridControlsProps {
+  grid: EditorGrid;
+  onGridChange: (grid: EditorGrid) => void;
+}
+
+const gridSizePresets = [8, 16, 24, 32];
+
+export function GridControls({ grid, onGridChange }: GridControlsProps) {
+  const toggles: { key: 'show_grid' | 'snap_to_grid' | 'snap_to_guides'; label: string }[] = [
+    { key: 'show_grid', label: 'Show grid' },
+    { key: 'snap_to_grid', label: 'Snap to grid' },
+    { key: 'snap_to_guides', label: 'Snap to edges' },
+  ];
+
+  return (
+    <div>
+      <h3 className="text-sm font-medium text-white mb-3">Grid</h3>
+
+      <div className="space-y-2 mb-4">
+        {toggles.map(({ key, label }) => (
+          <label key={key} className="flex items-center justify-between cursor-pointer">
+            <span className="text-xs text-neutral-400">{label}</span>
+            <input
+              type="checkbox"
+              checked={grid[key]}
+              onChange={(e) => onGridChange({ ...grid, [key]: e.target.checked })}
+              className="w-4 h-4 accent-blue-600"
+            />
+          </label>
+        ))}
+      </div>
+
+      <div className="flex gap-1">
+        {gridSizePresets.map((size) => (
+          <button
+            key={size}
+            onClick={() => onGridChange({ ...grid, size })}
+            className={`flex-1 py-1.5 text-xs rounded transition-colors ${
+              grid.size === size
+                ? 'bg-blue-600 text-white'
+                : 'bg-neutral-700 text-neutral-300 hover:bg-neutral-600'
+            }`}
+          >
+            {size}px
+          </button>
+        ))}
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,86 @@
+import { useState } from 'react';
+import { X } from 'lucide-react';
+import type { AnnotationStyle } from '@/types';
+
+interface StylePresetsProps {
+  styles: AnnotationStyle[];
+  /** The editor's current style, saved when adding a preset. */
+  current: Omit<AnnotationStyle, 'name'>;
+  onApply: (style: AnnotationStyle) => void;
+  onStylesChange: (styles: AnnotationStyle[]) => void;
+  onShadowChange: (shadow: boolean) => void;
+}
+
+export function StylePresets({ styles, current, onApply, onStylesChange, onShadowChange }: StylePresetsProps) {
+  const [name, setName] = useState('');
+
+  const handleSave = () => {
+    const trimmed = name.trim();
+    if (!trimmed) return;
+    // Saving under an existing name updates that preset in place.
+    const style = { ...current, name: trimmed };
+    const exists = styles.some((s) => s.name === trimmed);
+    onStylesChange(exists ? styles.map((s) => (s.name === trimmed ? style : s)) : [...styles, style]);
+    setName('');
+  };
+
+  return (
+    <div>
+      <h3 className="text-sm font-medium text-white mb-3">Styles</h3>
+
+      <label className="flex items-center justify-between cursor-pointer mb-3">
+        <span className="text-xs text-neutral-400">Drop shadow</span>
+        <input
+          type="checkbox"
+          checked={current.shadow}
+          onChange={(e) => onShadowChange(e.target.checked)}
+          className="w-4 h-4 accent-blue-600"
+        />
+      </label>
+
+      {styles.length > 0 && (
+        <div className="space-y-1 mb-3">
+          {styles.map((style) => (
+            <div key={style.name} className="flex items-center gap-2">
+              <button
+                onClick={() => onApply(style)}
+                className="flex-1 flex items-center gap-2 px-2 py-1.5 text-xs rounded bg-neutral-700 text-neutral-200 hover:bg-neutral-600 transition-colors"
+              >
+                <span className="w-3 h-3 rounded-sm border border-neutral-500" style={{ background: style.color }} />
+                <span className="truncate">{style.name}</span>
+              </button>
+              <button
+                onClick={() => onStylesChange(styles.filter((s) => s.name !== style.name))}
+                className="text-neutral-500 hover:text-white"
+                title="Delete style"
+              >
+                <X size={14} />
+              </button>
+            </div>
+          ))}
+        </div>
+      )}
+
+      <div className="flex gap-1">
+        <input
+          type="text"
+          value={name}
+          placeholder="Style name"
+          onChange={(e) => setName(e.target.value)}
+          onKeyDown={(e) => {
+            e.stopPropagation();
+            if (e.key === 'Enter') handleSave();
+          }}
+          className="flex-1 min-w-0 px-2 py-1 text-xs bg-neutral-700 border border-neutral-600 rounded text-white"
+        />
+        <button
+          onClick={handleSave}
+          disabled={!name.trim()}
+          className="px-2 py-1 text-xs rounded bg-blue-600 text-white disabled:opacity-50"
+        >
+          Save
+        </button>
+      </div>
+    </div>
+  );
+}
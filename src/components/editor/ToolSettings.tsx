@@ -1,14 +1,34 @@
+import { FlipHorizontal2, FlipVertical2, RotateCcw, RotateCw } from 'lucide-react';
 import { ColorPicker } from './ColorPicker';
 import type { ToolType } from './EditorApp';
+import type { ColorSample, FlipAxis, Measurement, PaletteColor, RedactStyle } from '@/types';
+
+const REDACTION_PRESETS = [
+  { label: 'Fine mosaic', value: 'pixelate:8' },
+  { label: 'Mosaic', value: 'pixelate:12' },
+  { label: 'Coarse mosaic', value: 'pixelate:24' },
+  { label: 'Blur', value: 'blur:8' },
+  { label: 'Heavy blur', value: 'blur:16' },
+];
 
 interface ToolSettingsProps {
   tool: ToolType;
   color: string;
   strokeWidth: number;
   fontSize: number;
+  fontFamily: string;
+  fonts: string[];
+  measurement: Measurement | null;
+  pickedColor: ColorSample | null;
+  palette: PaletteColor[];
   onColorChange: (color: string) => void;
   onStrokeWidthChange: (width: number) => void;
   onFontSizeChange: (size: number) => void;
+  onFontFamilyChange: (family: string) => void;
+  redaction: RedactStyle;
+  onRedactionChange: (style: RedactStyle) => void;
+  onRotate: (degrees: number) => void;
+  onFlip: (axis: FlipAxis) => void;
 }
 
 export function ToolSettings({
@@ -16,11 +36,22 @@ export function ToolSettings({
   color,
   strokeWidth,
   fontSize,
+  fontFamily,
+  fonts,
+  measurement,
+  pickedColor,
+  palette,
   onColorChange,
   onStrokeWidthChange,
   onFontSizeChange,
+  onFontFamilyChange,
+  redaction,
+  onRedactionChange,
+  onRotate,
+  onFlip,
 }: ToolSettingsProps) {
-  const showColorPicker = tool !== 'select';
+  const showColorPicker = !['select', 'measure', 'eyedropper', 'crop'].includes(tool);
+  const redactionValue = redaction.kind === 'pixelate' ? `pixelate:${redaction.block_size}` : `blur:${redaction.sigma}`;
   const showStrokeWidth = ['rect', 'ellipse', 'arrow', 'line'].includes(tool);
   const showFontSize = tool === 'text';
 
@@ -34,6 +65,90 @@ export function ToolSettings({
     );
   }
 
+  if (tool === 'measure') {
+    if (!measurement) {
+      return (
+        <div className="flex items-center gap-4">
+          <span className="text-sm text-neutral-400">
+            Drag between two points to measure them
+          </span>
+        </div>
+      );
+    }
+    const round = (value: number) => Math.round(value * 10) / 10;
+    return (
+      <div className="flex items-center gap-4 text-sm font-mono text-neutral-300">
+        <span>{round(measurement.width)} x {round(measurement.height)} px</span>
+        <span>{round(measurement.distance)} px</span>
+        <span>{round(measurement.angle)}&deg;</span>
+        {measurement.scale_factor !== 1 && (
+          <span className="text-neutral-400">
+            {round(measurement.width_points)} x {round(measurement.height_points)} pt @{measurement.scale_factor}x
+          </span>
+        )}
+      </div>
+    );
+  }
+
+  if (tool === 'crop') {
+    const transforms = [
+      { icon: RotateCcw, label: 'Rotate left', onClick: () => onRotate(-90) },
+      { icon: RotateCw, label: 'Rotate right', onClick: () => onRotate(90) },
+      { icon: FlipHorizontal2, label: 'Flip horizontally', onClick: () => onFlip('horizontal') },
+      { icon: FlipVertical2, label: 'Flip vertically', onClick: () => onFlip('vertical') },
+    ];
+    return (
+      <div className="flex items-center gap-4">
+        <span className="text-sm text-neutral-400">
+          Drag over the image to crop it. Annotations are cleared.
+        </span>
+        <div className="flex items-center gap-1">
+          {transforms.map(({ icon: Icon, label, onClick }) => (
+            <button
+              key={label}
+              onClick={onClick}
+              className="p-1.5 rounded-md hover:bg-neutral-700 text-neutral-400 hover:text-white transition-colors"
+              title={label}
+            >
+              <Icon size={16} />
+            </button>
+          ))}
+        </div>
+      </div>
+    );
+  }
+
+  if (tool === 'eyedropper') {
+    return (
+      <div className="flex items-center gap-4 text-sm">
+        {pickedColor ? (
+          <div className="flex items-center gap-2 font-mono text-neutral-300">
+            <span className="w-4 h-4 rounded border border-neutral-600" style={{ backgroundColor: pickedColor.hex }} />
+            <span>{pickedColor.hex}</span>
+            <span>rgb({pickedColor.rgb.join(', ')})</span>
+            <span>hsl({pickedColor.hsl[0]}, {pickedColor.hsl[1]}%, {pickedColor.hsl[2]}%)</span>
+          </div>
+        ) : (
+          <span className="text-neutral-400">Click the image to pick a color</span>
+        )}
+        {palette.length > 0 && (
+          <div className="flex items-center gap-1">
+            <span className="text-xs text-neutral-400 mr-1">Palette</span>
+            {palette.map((swatch) => (
+              <button
+                key={swatch.hex}
+                onClick={() => onColorChange(swatch.hex)}
+                className="w-5 h-5 rounded border border-neutral-600 hover:scale-110 transition-transform"
+                style={{ backgroundColor: swatch.hex }}
+                title={`${swatch.hex} (${Math.round(swatch.share * 100)}%)`}
+              />
+            ))}
+          </div>
+        )}
+      </div>
+    );
+  }
+
   return (
     <div className="flex items-center gap-4">
       {showColorPicker && (
@@ -58,6 +173,22 @@ export function ToolSettings({
         </div>
       )}
 
+      {showFontSize && (
+        <div className="flex items-center gap-2">
+          <span className="text-xs text-neutral-400">Font</span>
+          <select
+            value={fontFamily}
+            onChange={(e) => onFontFamilyChange(e.target.value)}
+            className="h-7 max-w-40 px-2 text-xs bg-neutral-700 border border-neutral-600 rounded text-white"
+          >
+            <option value="">Default</option>
+            {fonts.map((family) => (
+              <option key={family} value={family} style={{ fontFamily: family }}>{family}</option>
+            ))}
+          </select>
+        </div>
+      )}
+
       {showFontSize && (
         <div className="flex items-center gap-2">
           <span className="text-xs text-neutral-400">Size</span>
@@ -74,9 +205,29 @@ export function ToolSettings({
       )}
 
       {tool === 'blur' && (
-        <span className="text-sm text-neutral-400">
-          Draw rectangles to pixelate sensitive content
-        </span>
+        <div className="flex items-center gap-2">
+          <span className="text-xs text-neutral-400">Style</span>
+          <select
+            value={redactionValue}
+            onChange={(e) => {
+              const [kind, amount] = e.target.value.split(':');
+              onRedactionChange(kind === 'pixelate'
+                ? { kind: 'pixelate', block_size: Number(amount) }
+                : { kind: 'blur', sigma: Number(amount) });
+            }}
+            className="h-7 px-2 text-xs bg-neutral-700 border border-neutral-600 rounded text-white"
+          >
+            {!REDACTION_PRESETS.some(({ value }) => value === redactionValue) && (
+              <option value={redactionValue}>Custom</option>
+            )}
+            {REDACTION_PRESETS.map(({ label, value }) => (
+              <option key={value} value={value}>{label}</option>
+            ))}
+          </select>
+          <span className="text-sm text-neutral-400">
+            Draw rectangles to hide sensitive content; this can't be undone
+          </span>
+        </div>
       )}
     </div>
   );
@@ -5,7 +5,10 @@ import {
   ArrowUpRight,
   Minus,
   Type,
-  EyeOff
+  EyeOff,
+  Ruler,
+  Pipette,
+  Crop
 } from 'lucide-react';
 import { cn } from '@/lib/utils';
 import type { ToolType } from './EditorApp';
@@ -23,6 +26,9 @@ const tools: { type: ToolType; icon: typeof MousePointer2; label: string; shortc
   { type: 'line', icon: Minus, label: 'Line', shortcut: 'L' },
   { type: 'text', icon: Type, label: 'Text', shortcut: 'T' },
   { type: 'blur', icon: EyeOff, label: 'Redact', shortcut: 'B' },
+  { type: 'measure', icon: Ruler, label: 'Measure', shortcut: 'M' },
+  { type: 'eyedropper', icon: Pipette, label: 'Eyedropper', shortcut: 'I' },
+  { type: 'crop', icon: Crop, label: 'Crop', shortcut: 'C' },
 ];
 
 export function Toolbar({ currentTool, onToolChange }: ToolbarProps) {
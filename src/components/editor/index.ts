@@ -4,5 +4,6 @@ export { Toolbar } from './Toolbar';
 export { ToolSettings } from './ToolSettings';
 export { ColorPicker } from './ColorPicker';
 export { PaddingControls } from './PaddingControls';
+export { GridControls } from './GridControls';
 export type { ToolType, EditorState } from './EditorApp';
 export type { CanvasRef } from './AnnotationCanvas';
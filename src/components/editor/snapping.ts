@@ -0,0 +1,90 @@
+import type { EditorGrid } from '@/types';
+
+/** How close, in canvas units, a value must be to a guide to snap onto it. */
+export const SNAP_THRESHOLD = 6;
+
+export interface Guides {
+  x: number[];
+  y: number[];
+}
+
+export interface Bounds {
+  left: number;
+  top: number;
+  width: number;
+  height: number;
+}
+
+/** Rounds `value` to the nearest grid line, with lines counted from `origin`. */
+export const snapToGrid = (value: number, origin: number, size: number) =>
+  origin + Math.round((value - origin) / size) * size;
+
+/**
+ * Finds the smallest shift that puts one of `values` on a guide, or 0 if
+ * nothing is within the threshold.
+ */
+export const guideOffset = (values: number[], guides: number[]) => {
+  let best = 0;
+  let closest = SNAP_THRESHOLD + 1;
+  for (const value of values) {
+    for (const guide of guides) {
+      const distance = Math.abs(guide - value);
+      if (distance <= SNAP_THRESHOLD && distance < closest) {
+        best = guide - value;
+        closest = distance;
+      }
+    }
+  }
+  return best;
+};
+
+/** Edges and centre lines of each box, used as snap targets. */
+export const guidesFromBounds = (boxes: Bounds[]): Guides => ({
+  x: boxes.flatMap((b) => [b.left, b.left + b.width / 2, b.left + b.width]),
+  y: boxes.flatMap((b) => [b.top, b.top + b.height / 2, b.top + b.height]),
+});
+
+/** Snaps a single point, preferring the grid over guides when both are on. */
+export const snapPoint = (
+  point: { x: number; y: number },
+  grid: EditorGrid,
+  origin: { x: number; y: number },
+  guides: Guides
+) => {
+  if (grid.snap_to_grid && grid.size > 0) {
+    return {
+      x: snapToGrid(point.x, origin.x, grid.size),
+      y: snapToGrid(point.y, origin.y, grid.size),
+    };
+  }
+  if (grid.snap_to_guides) {
+    return {
+      x: point.x + guideOffset([point.x], guides.x),
+      y: point.y + guideOffset([point.y], guides.y),
+    };
+  }
+  return point;
+};
+
+/** Shift to apply to a moving box so its edges land on the grid or a guide. */
+export const snapBounds = (
+  bounds: Bounds,
+  grid: EditorGrid,
+  origin: { x: number; y: number },
+  guides: Guides
+) => {
+  if (grid.snap_to_grid && grid.size > 0) {
+    return {
+      dx: snapToGrid(bounds.left, origin.x, grid.size) - bounds.left,
+      dy: snapToGrid(bounds.top, origin.y, grid.size) - bounds.top,
+    };
+  }
+  if (grid.snap_to_guides) {
+    const { x, y } = guidesFromBounds([bounds]);
+    return {
+      dx: guideOffset(x, guides.x),
+      dy: guideOffset(y, guides.y),
+    };
+  }
+  return { dx: 0, dy: 0 };
+};
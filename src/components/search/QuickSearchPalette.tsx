@@ -0,0 +1,252 @@
+import { useEffect, useRef, useState } from 'react';
+import { Cloud, Search } from 'lucide-react';
+import { emit, invoke, listen } from '@/lib/tauri';
+import { cn } from '@/lib/utils';
+import type { Plugin, RecentScreenshot } from '@/types';
+
+const formatDate = (timestamp: string) => new Date(timestamp).toLocaleString();
+
+export function QuickSearchPalette() {
+  const [query, setQuery] = useState('');
+  const [results, setResults] = useState<RecentScreenshot[]>([]);
+  const [active, setActive] = useState(0);
+  const [error, setError] = useState<string | null>(null);
+  const [plugins, setPlugins] = useState<Plugin[]>([]);
+  const [notice, setNotice] = useState<string | null>(null);
+  const inputRef = useRef<HTMLInputElement>(null);
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    listen('quick-search-opened', () => {
+      setQuery('');
+      setError(null);
+      inputRef.current?.focus();
+    })
+      .then((fn) => {
+        unlisten = fn;
+        return emit('quick-search-ready', {});
+      })
+      .catch((err) => console.error('Failed to set up quick search:', err));
+    return () => unlisten?.();
+  }, []);
+
+  // Bulk uploads take a while; show which file is in flight.
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    listen('upload-progress', (progress) => {
+      const name = progress.file_path.split('/').pop();
+      if (progress.stage === 'uploading') {
+        setNotice(`Uploading ${name}...`);
+      } else if (progress.stage === 'failed') {
+        setNotice(`${name}: ${progress.error}`);
+      }
+    })
+      .then((fn) => {
+        unlisten = fn;
+      })
+      .catch((err) => console.error('Failed to listen for upload progress:', err));
+    return () => unlisten?.();
+  }, []);
+
+  useEffect(() => {
+    invoke('list_plugins', { context: 'history' })
+      .then(setPlugins)
+      .catch((err) => console.error('Failed to load plugins:', err));
+  }, []);
+
+  // Debounced so each keystroke doesn't regenerate thumbnails.
+  useEffect(() => {
+    const timer = setTimeout(() => {
+      invoke('quick_search', { query })
+        .then((found) => {
+          setResults(found);
+          setActive(0);
+        })
+        .catch((err) => setError(String(err)));
+    }, 120);
+    return () => clearTimeout(timer);
+  }, [query]);
+
+  const close = () => invoke('close_quick_search').catch(() => {});
+
+  const run = async (shot: RecentScreenshot, action: 'copy' | 'copy-file' | 'copy-path' | 'open' | 'upload') => {
+    setError(null);
+    try {
+      if (action === 'open') {
+        await invoke('open_screenshot', { filePath: shot.file_path });
+      } else if (action === 'upload') {
+        await invoke('upload_screenshot', { filePath: shot.file_path });
+      } else if (action === 'copy-file') {
+        await invoke('copy_file_to_clipboard', { filePath: shot.file_path });
+      } else if (action === 'copy-path') {
+        await invoke('copy_path_to_clipboard', { filePath: shot.file_path });
+      } else {
+        await invoke('copy_screenshot_from_path', { filePath: shot.file_path });
+      }
+      await close();
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  const runPlugin = async (shot: RecentScreenshot, plugin: Plugin) => {
+    setError(null);
+    setNotice(null);
+    try {
+      const output = await invoke('run_plugin', { pluginId: plugin.id, filePath: shot.file_path });
+      setNotice([output.message, output.url].filter(Boolean).join(' ') || `${plugin.name} finished`);
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  const uploadAll = async () => {
+    setError(null);
+    try {
+      const outcomes = await invoke('upload_screenshots', { filePaths: results.map((s) => s.file_path) });
+      const failed = outcomes.filter((o) => o.error);
+      if (failed.length > 0) {
+        setError(`${failed.length} upload(s) failed: ${failed[0].error}`);
+      } else {
+        await close();
+      }
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  const exportMarkdown = async () => {
+    setError(null);
+    try {
+      const path = await invoke('export_markdown', { filePaths: results.map((s) => s.file_path) });
+      if (path) await close();
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  // Results are newest first; animations play in the order they were taken.
+  const animate = async () => {
+    setError(null);
+    try {
+      const filePaths = results.map((s) => s.file_path).reverse();
+      await invoke('animate_captures', { filePaths, format: 'gif' });
+      await close();
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  const handleKeyDown = (e: React.KeyboardEvent) => {
+    if (e.key === 'Escape') {
+      e.preventDefault();
+      close();
+    } else if (e.key === 'ArrowDown') {
+      e.preventDefault();
+      setActive((i) => Math.min(i + 1, results.length - 1));
+    } else if (e.key === 'ArrowUp') {
+      e.preventDefault();
+      setActive((i) => Math.max(i - 1, 0));
+    } else if (e.key === 'Enter' && results[active]) {
+      e.preventDefault();
+      run(results[active], e.metaKey ? 'open' : e.altKey ? 'upload' : e.shiftKey ? 'copy-file' : 'copy');
+    }
+  };
+
+  return (
+    <div className="w-screen h-screen p-2 bg-transparent" onKeyDown={handleKeyDown}>
+      <div className="h-full flex flex-col rounded-xl border border-border bg-background shadow-2xl overflow-hidden">
+        <div className="flex items-center gap-2 px-4 border-b border-border">
+          <Search className="w-4 h-4 text-muted-foreground" />
+          <input
+            ref={inputRef}
+            autoFocus
+            value={query}
+            onChange={(e) => setQuery(e.target.value)}
+            placeholder="Search screenshots by name, note or text"
+            className="flex-1 py-3 bg-transparent text-sm outline-none"
+          />
+        </div>
+
+        <div className="flex-1 overflow-y-auto p-1">
+          {results.length === 0 ? (
+            <div className="p-4 text-xs text-muted-foreground">
+              {query.trim() ? 'No matching screenshots' : 'No saved screenshots yet'}
+            </div>
+          ) : (
+            results.map((shot, index) => (
+              <button
+                key={shot.file_path}
+                onMouseEnter={() => setActive(index)}
+                onClick={(e) => run(shot, e.metaKey ? 'open' : e.altKey ? 'upload' : 'copy')}
+                className={cn(
+                  'w-full flex items-center gap-3 p-2 rounded-md text-left',
+                  index === active ? 'bg-muted' : ''
+                )}
+              >
+                {shot.thumbnail ? (
+                  <img src={shot.thumbnail} alt="" className="w-10 h-10 rounded object-cover flex-shrink-0" />
+                ) : (
+                  <div className="w-10 h-10 rounded bg-muted flex items-center justify-center flex-shrink-0">
+                    <Cloud className="w-4 h-4 text-muted-foreground" />
+                  </div>
+                )}
+                <div className="min-w-0">
+                  <div className="text-sm truncate">{shot.note || shot.filename}</div>
+                  <div className="text-xs text-muted-foreground truncate">
+                    {shot.note ? shot.filename : formatDate(shot.timestamp)}
+                    {shot.tags.length > 0 && ` - ${shot.tags.map((tag) => `#${tag}`).join(' ')}`}
+                  </div>
+                </div>
+              </button>
+            ))
+          )}
+        </div>
+
+        {plugins.length > 0 && results[active] && (
+          <div className="px-4 py-2 border-t border-border text-[11px] text-muted-foreground flex gap-3 overflow-x-auto">
+            {plugins.map((plugin) => (
+              <button
+                key={plugin.id}
+                onClick={() => runPlugin(results[active], plugin)}
+                title={plugin.description || plugin.name}
+                className="hover:text-foreground whitespace-nowrap"
+              >
+                {plugin.name}
+              </button>
+            ))}
+          </div>
+        )}
+
+        <div className="px-4 py-2 border-t border-border text-[11px] text-muted-foreground flex justify-between">
+          <span className="truncate select-text">{error ?? notice ?? 'Enter copies, Shift+Enter copies the file, Cmd+Enter opens, Alt+Enter uploads'}</span>
+          {results.length > 0 ? (
+            <span className="flex gap-3">
+              <button onClick={() => run(results[active], 'copy-path')} className="hover:text-foreground">
+                Copy path
+              </button>
+              <button onClick={() => run(results[active], 'copy-file')} className="hover:text-foreground">
+                Copy file
+              </button>
+              {results.length > 1 && (
+                <>
+                  <button onClick={animate} className="hover:text-foreground">
+                    Make GIF
+                  </button>
+                  <button onClick={exportMarkdown} className="hover:text-foreground">
+                    Export as Markdown
+                  </button>
+                  <button onClick={uploadAll} className="hover:text-foreground">
+                    Upload all {results.length}
+                  </button>
+                </>
+              )}
+            </span>
+          ) : (
+            <span>Esc to close</span>
+          )}
+        </div>
+      </div>
+    </div>
+  );
+}
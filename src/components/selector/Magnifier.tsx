@@ -0,0 +1,72 @@
+import { useEffect, useMemo, useRef } from 'react';
+
+interface MagnifierProps {
+  /** Canvas holding the frozen frame at full (physical) resolution. */
+  source: HTMLCanvasElement;
+  /** Cursor position in window points. */
+  cursor: { x: number; y: number };
+  scaleFactor: number;
+}
+
+const SAMPLE_SIZE = 15;
+const ZOOM = 8;
+const LOUPE_SIZE = SAMPLE_SIZE * ZOOM;
+const CURSOR_OFFSET = 20;
+const READOUT_HEIGHT = 40;
+
+const toHex = (r: number, g: number, b: number) =>
+  `#${[r, g, b].map((v) => v.toString(16).padStart(2, '0')).join('')}`.toUpperCase();
+
+export function Magnifier({ source, cursor, scaleFactor }: MagnifierProps) {
+  const canvasRef = useRef<HTMLCanvasElement>(null);
+
+  const px = Math.min(source.width - 1, Math.max(0, Math.floor(cursor.x * scaleFactor)));
+  const py = Math.min(source.height - 1, Math.max(0, Math.floor(cursor.y * scaleFactor)));
+
+  const color = useMemo(() => {
+    const context = source.getContext('2d', { willReadFrequently: true });
+    if (!context) return '#000000';
+    const [r, g, b] = context.getImageData(px, py, 1, 1).data;
+    return toHex(r, g, b);
+  }, [source, px, py]);
+
+  useEffect(() => {
+    const context = canvasRef.current?.getContext('2d');
+    if (!context) return;
+
+    const half = Math.floor(SAMPLE_SIZE / 2);
+    context.imageSmoothingEnabled = false;
+    context.fillStyle = '#000';
+    context.fillRect(0, 0, LOUPE_SIZE, LOUPE_SIZE);
+    context.drawImage(source, px - half, py - half, SAMPLE_SIZE, SAMPLE_SIZE, 0, 0, LOUPE_SIZE, LOUPE_SIZE);
+
+    // Outline the pixel under the cursor.
+    context.strokeStyle = 'rgba(255, 255, 255, 0.9)';
+    context.lineWidth = 1;
+    context.strokeRect(half * ZOOM + 0.5, half * ZOOM + 0.5, ZOOM - 1, ZOOM - 1);
+  }, [source, px, py]);
+
+  // Keep the loupe on screen by flipping it to the other side of the cursor.
+  const left = cursor.x + CURSOR_OFFSET + LOUPE_SIZE > window.innerWidth
+    ? cursor.x - CURSOR_OFFSET - LOUPE_SIZE
+    : cursor.x + CURSOR_OFFSET;
+  const top = cursor.y + CURSOR_OFFSET + LOUPE_SIZE + READOUT_HEIGHT > window.innerHeight
+    ? cursor.y - CURSOR_OFFSET - LOUPE_SIZE - READOUT_HEIGHT
+    : cursor.y + CURSOR_OFFSET;
+
+  return (
+    <div
+      className="absolute pointer-events-none rounded-md overflow-hidden border border-white/70 bg-black/80 shadow-lg"
+      style={{ left, top, width: LOUPE_SIZE }}
+    >
+      <canvas ref={canvasRef} width={LOUPE_SIZE} height={LOUPE_SIZE} className="block" />
+      <div className="px-2 py-1 text-[11px] leading-4 font-mono text-white">
+        <div>{px}, {py} px</div>
+        <div className="flex items-center gap-1.5">
+          <span className="inline-block w-2.5 h-2.5 rounded-sm border border-white/50" style={{ background: color }} />
+          {color}
+        </div>
+      </div>
+    </div>
+  );
+}
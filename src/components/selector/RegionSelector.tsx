@@ -0,0 +1,248 @@
+import { useCallback, useEffect, useMemo, useRef, useState } from 'react';
+import { emit, listen } from '@/lib/tauri';
+import type { CaptureRegion, SelectorFrame, WindowInfo } from '@/types';
+import { Magnifier } from './Magnifier';
+
+interface Point {
+  x: number;
+  y: number;
+}
+
+const MIN_SELECTION_SIZE = 3;
+const SNAP_DISTANCE = 8;
+
+/** Ratios cycled with the A key; null drags freely. */
+const ASPECT_RATIOS: Array<{ label: string; ratio: number | null }> = [
+  { label: 'Free', ratio: null },
+  { label: '1:1', ratio: 1 },
+  { label: '4:3', ratio: 4 / 3 },
+  { label: '16:9', ratio: 16 / 9 },
+];
+
+const toRegion = (a: Point, b: Point): CaptureRegion => ({
+  x: Math.round(Math.min(a.x, b.x)),
+  y: Math.round(Math.min(a.y, b.y)),
+  width: Math.round(Math.abs(a.x - b.x)),
+  height: Math.round(Math.abs(a.y - b.y)),
+});
+
+const containsPoint = (bounds: CaptureRegion, point: Point) =>
+  point.x >= bounds.x &&
+  point.x <= bounds.x + bounds.width &&
+  point.y >= bounds.y &&
+  point.y <= bounds.y + bounds.height;
+
+/** Moves `point` so the drag from `anchor` keeps `ratio` (width / height). */
+const lockAspect = (anchor: Point, point: Point, ratio: number | null): Point => {
+  if (ratio === null) return point;
+  const width = Math.abs(point.x - anchor.x);
+  const height = Math.max(width / ratio, Math.abs(point.y - anchor.y));
+  return {
+    x: anchor.x + Math.sign(point.x - anchor.x || 1) * height * ratio,
+    y: anchor.y + Math.sign(point.y - anchor.y || 1) * height,
+  };
+};
+
+/** Moves `value` onto the closest edge within SNAP_DISTANCE, if any. */
+const snapToEdges = (value: number, edges: number[]) => {
+  let snapped = value;
+  let closest = SNAP_DISTANCE + 1;
+  for (const edge of edges) {
+    const distance = Math.abs(edge - value);
+    if (distance <= SNAP_DISTANCE && distance < closest) {
+      snapped = edge;
+      closest = distance;
+    }
+  }
+  return snapped;
+};
+
+/** Clips window bounds to the visible screen; windows can hang off an edge. */
+const clipToScreen = (bounds: CaptureRegion): CaptureRegion => {
+  const x = Math.max(0, bounds.x);
+  const y = Math.max(0, bounds.y);
+  return {
+    x,
+    y,
+    width: Math.min(window.innerWidth, bounds.x + bounds.width) - x,
+    height: Math.min(window.innerHeight, bounds.y + bounds.height) - y,
+  };
+};
+
+export function RegionSelector() {
+  const [frame, setFrame] = useState<SelectorFrame | null>(null);
+  const [source, setSource] = useState<HTMLCanvasElement | null>(null);
+  const [cursor, setCursor] = useState<Point | null>(null);
+  const [anchor, setAnchor] = useState<Point | null>(null);
+  // A finished drag waits here so it can be redrawn before capturing.
+  const [pending, setPending] = useState<CaptureRegion | null>(null);
+  const [aspectIndex, setAspectIndex] = useState(0);
+  const finished = useRef(false);
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    listen('selector-frame', setFrame)
+      .then((fn) => {
+        unlisten = fn;
+        return emit('selector-ready', {});
+      })
+      .catch((err) => console.error('Failed to set up selector:', err));
+    return () => unlisten?.();
+  }, []);
+
+  // Keep the frozen frame in a canvas so the magnifier can sample pixels.
+  useEffect(() => {
+    if (!frame) return;
+    const image = new Image();
+    image.onload = () => {
+      const canvas = document.createElement('canvas');
+      canvas.width = image.naturalWidth;
+      canvas.height = image.naturalHeight;
+      canvas.getContext('2d', { willReadFrequently: true })?.drawImage(image, 0, 0);
+      setSource(canvas);
+    };
+    image.src = `data:image/png;base64,${frame.base64_image}`;
+  }, [frame]);
+
+  const finish = useCallback((region: CaptureRegion | null) => {
+    if (finished.current) return;
+    finished.current = true;
+    emit('region-selected', { region }).catch((err) => console.error('Failed to send selection:', err));
+  }, []);
+
+  useEffect(() => {
+    const handleKeyDown = (e: KeyboardEvent) => {
+      if (e.key === 'Escape') {
+        finish(null);
+      } else if (e.key === 'Enter' && pending) {
+        finish(pending);
+      } else if (e.key === 'a' || e.key === 'A') {
+        setAspectIndex((index) => (index + 1) % ASPECT_RATIOS.length);
+      }
+    };
+    document.addEventListener('keydown', handleKeyDown);
+    return () => document.removeEventListener('keydown', handleKeyDown);
+  }, [finish, pending]);
+
+  const windows: WindowInfo[] = frame?.windows ?? [];
+  const edges = useMemo(() => {
+    const list = frame?.windows ?? [];
+    return {
+      x: list.flatMap((w) => [w.bounds.x, w.bounds.x + w.bounds.width]),
+      y: list.flatMap((w) => [w.bounds.y, w.bounds.y + w.bounds.height]),
+    };
+  }, [frame]);
+  const snap = (point: Point): Point => ({
+    x: snapToEdges(point.x, edges.x),
+    y: snapToEdges(point.y, edges.y),
+  });
+
+  const aspect = ASPECT_RATIOS[aspectIndex];
+  // Snapping would break a locked ratio, so only free drags snap.
+  const dragTo = (from: Point, point: Point) =>
+    aspect.ratio === null ? toRegion(from, snap(point)) : toRegion(from, lockAspect(from, point, aspect.ratio));
+
+  // Windows come front to back, so the first hit is the one that's visible.
+  const hoveredWindow =
+    !anchor && !pending && cursor ? windows.find((w) => containsPoint(w.bounds, cursor)) : undefined;
+
+  const frameUrl = useMemo(
+    () => (frame ? `data:image/png;base64,${frame.base64_image}` : null),
+    [frame]
+  );
+
+  const handleMouseDown = (e: React.MouseEvent) => {
+    const point = { x: e.clientX, y: e.clientY };
+    // Double-clicking inside a pending selection captures it.
+    if (pending && e.detail === 2 && containsPoint(pending, point)) {
+      finish(pending);
+      return;
+    }
+    setAnchor(aspect.ratio === null ? snap(point) : point);
+  };
+
+  const handleMouseUp = (e: React.MouseEvent) => {
+    if (!anchor) return;
+    const point = { x: e.clientX, y: e.clientY };
+    const region = dragTo(anchor, point);
+    setAnchor(null);
+    if (region.width >= MIN_SELECTION_SIZE && region.height >= MIN_SELECTION_SIZE) {
+      setPending(region);
+      return;
+    }
+    // Stray clicks keep the pending selection.
+    if (pending) return;
+    // A click without a drag picks the window under the cursor.
+    const clicked = windows.find((w) => containsPoint(w.bounds, point));
+    if (clicked) {
+      finish(clipToScreen(clicked.bounds));
+    }
+  };
+
+  const selection = anchor && cursor ? dragTo(anchor, cursor) : pending;
+  const scale = frame?.scale_factor ?? 1;
+
+  return (
+    <div
+      className="fixed inset-0"
+      onMouseDown={handleMouseDown}
+      onMouseMove={(e) => setCursor({ x: e.clientX, y: e.clientY })}
+      onMouseUp={handleMouseUp}
+    >
+      {frameUrl && (
+        <img src={frameUrl} alt="" draggable={false} className="absolute inset-0 w-full h-full pointer-events-none" />
+      )}
+      {selection ? (
+        <div
+          className="absolute border border-white pointer-events-none"
+          style={{
+            left: selection.x,
+            top: selection.y,
+            width: selection.width,
+            height: selection.height,
+            boxShadow: '0 0 0 9999px rgba(0, 0, 0, 0.4)',
+          }}
+        >
+          <span
+            className={`absolute px-2 py-0.5 rounded bg-black/70 text-white text-xs font-mono whitespace-nowrap ${
+              selection.y > 28 ? '-top-7 left-0' : 'top-1 left-1'
+            }`}
+          >
+            {Math.round(selection.width * scale)} x {Math.round(selection.height * scale)}
+            {aspect.ratio !== null && ` (${aspect.label})`}
+          </span>
+          {pending && (
+            <span className="absolute left-1/2 -translate-x-1/2 bottom-2 px-2 py-0.5 rounded bg-black/70 text-white text-xs whitespace-nowrap">
+              Enter or double-click to capture, drag to reselect
+            </span>
+          )}
+        </div>
+      ) : hoveredWindow ? (
+        <div
+          className="absolute border-2 border-accent bg-accent/10 pointer-events-none"
+          style={{
+            left: hoveredWindow.bounds.x,
+            top: hoveredWindow.bounds.y,
+            width: hoveredWindow.bounds.width,
+            height: hoveredWindow.bounds.height,
+            boxShadow: '0 0 0 9999px rgba(0, 0, 0, 0.3)',
+          }}
+        >
+          <span className="absolute left-2 top-2 px-2 py-0.5 rounded bg-black/70 text-white text-xs">
+            {hoveredWindow.app_name}{hoveredWindow.title ? ` - ${hoveredWindow.title}` : ''}
+          </span>
+        </div>
+      ) : (
+        <div className="absolute inset-0 bg-black/20 pointer-events-none" />
+      )}
+      {aspect.ratio !== null && !selection && (
+        <span className="absolute left-1/2 -translate-x-1/2 top-4 px-2 py-0.5 rounded bg-black/70 text-white text-xs pointer-events-none">
+          Aspect locked to {aspect.label} (A to change)
+        </span>
+      )}
+      {cursor && source && frame && (
+        <Magnifier source={source} cursor={cursor} scaleFactor={frame.scale_factor} />
+      )}
+    </div>
+  );
+}
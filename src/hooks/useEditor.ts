@@ -1,12 +1,13 @@
 import { useState, useEffect, useRef, useCallback } from 'react';
 import { listen, emit } from '@/lib/tauri';
-import { debugLog } from '@/lib/utils';
+import { debugLog, refreshed } from '@/lib/utils';
 import type { EditorData } from '@/types';
 import type { CanvasRef } from '@/components/editor/AnnotationCanvas';
 
 export const useEditor = () => {
-  const [imageData, setImageData] = useState<string | null>(null);
+  const [imageUrl, setImageUrl] = useState<string | null>(null);
   const [timestamp, setTimestamp] = useState<number | null>(null);
+  const [filePath, setFilePath] = useState<string | null>(null);
   const canvasRef = useRef<CanvasRef>(null);
 
   useEffect(() => {
@@ -15,8 +16,9 @@ export const useEditor = () => {
         debugLog('Setting up editor-data listener...');
         const unlisten = await listen('editor-data', (data: EditorData) => {
           debugLog('Received editor-data event');
-          setImageData(data.base64_image);
+          setImageUrl(data.image_url);
           setTimestamp(data.timestamp);
+          setFilePath(data.file_path ?? null);
         });
         debugLog('Editor listener setup successfully');
         await emit('editor-ready', {});
@@ -49,10 +51,18 @@ export const useEditor = () => {
     return dataUrl.replace(/^data:image\/png;base64,/, '');
   }, []);
 
+  // Crops, rotations and flips change the image size, so the canvas is
+  // rebuilt from the edited capture.
+  const reloadImage = useCallback(() => {
+    setImageUrl((url) => (url ? refreshed(url) : url));
+  }, []);
+
   return {
-    imageData,
+    imageUrl,
     timestamp,
+    filePath,
     canvasRef,
     exportCanvas,
+    reloadImage,
   };
 };
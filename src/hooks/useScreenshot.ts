@@ -1,11 +1,14 @@
-import { useState, useEffect } from 'react';
+import { useState, useEffect, useRef } from 'react';
 import { listen, invoke, emit } from '@/lib/tauri';
-import { debugLog } from '@/lib/utils';
-import type { ScreenshotData } from '@/types';
+import { debugLog, refreshed } from '@/lib/utils';
+import type { DetectedCode, ScreenshotData, ScreenshotDiff, SizeReport } from '@/types';
 
 export const useScreenshot = () => {
   const [currentScreenshot, setCurrentScreenshot] = useState<ScreenshotData | null>(null);
   const [isLoading, setIsLoading] = useState(false);
+  // The capture shown before this one, while it's still in the cache
+  const [previousTimestamp, setPreviousTimestamp] = useState<number | null>(null);
+  const latestTimestamp = useRef<number | null>(null);
 
   useEffect(() => {
     const setupListener = async () => {
@@ -13,6 +16,10 @@ export const useScreenshot = () => {
         debugLog('Setting up screenshot-data listener...');
         const unlisten = await listen('screenshot-data', (data) => {
           debugLog('Received screenshot-data event:', data);
+          if (latestTimestamp.current !== data.timestamp) {
+            setPreviousTimestamp(latestTimestamp.current);
+            latestTimestamp.current = data.timestamp;
+          }
           setCurrentScreenshot(data);
         });
         debugLog('Screenshot listener setup successfully');
@@ -36,11 +43,11 @@ export const useScreenshot = () => {
     };
   }, []);
 
-  const saveScreenshot = async () => {
+  const saveScreenshot = async (note?: string) => {
     if (!currentScreenshot) return;
     setIsLoading(true);
     try {
-      await invoke('save_to_disk', { timestamp: currentScreenshot.timestamp });
+      await invoke('save_to_disk', { timestamp: currentScreenshot.timestamp, note: note || undefined });
     } catch (error) {
       console.error('Failed to save screenshot:', error);
     } finally {
@@ -65,6 +72,8 @@ export const useScreenshot = () => {
     setIsLoading(true);
     try {
       await invoke('delete_from_memory', { timestamp: currentScreenshot.timestamp });
+      latestTimestamp.current = null;
+      setPreviousTimestamp(null);
       setCurrentScreenshot(null);
     } catch (error) {
       console.error('Failed to delete screenshot:', error);
@@ -75,6 +84,9 @@ export const useScreenshot = () => {
 
   const closePopup = async () => {
     try {
+      // Closing the popup clears the cache, so there is nothing left to diff against
+      latestTimestamp.current = null;
+      setPreviousTimestamp(null);
       await invoke('close_popup_window');
     } catch (error) {
       console.error('Failed to close popup:', error);
@@ -85,7 +97,10 @@ export const useScreenshot = () => {
     if (!currentScreenshot) return;
     setIsLoading(true);
     try {
-      await invoke('open_editor_window', { timestamp: currentScreenshot.timestamp });
+      await invoke('open_editor_window', {
+        timestamp: currentScreenshot.timestamp,
+        filePath: currentScreenshot.file_path,
+      });
       await invoke('close_popup_window');
     } catch (error) {
       console.error('Failed to open editor:', error);
@@ -94,6 +109,114 @@ export const useScreenshot = () => {
     }
   };
 
+  const analyzeScreenshot = async (): Promise<SizeReport | null> => {
+    if (!currentScreenshot) return null;
+    setIsLoading(true);
+    try {
+      return await invoke('analyze_capture', { timestamp: currentScreenshot.timestamp });
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const detectCodes = async (): Promise<DetectedCode[]> => {
+    if (!currentScreenshot) return [];
+    setIsLoading(true);
+    try {
+      return await invoke('detect_codes', { timestamp: currentScreenshot.timestamp });
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const diffWithPrevious = async (): Promise<ScreenshotDiff | null> => {
+    if (!currentScreenshot || previousTimestamp === null) return null;
+    setIsLoading(true);
+    try {
+      return await invoke('diff_screenshots', {
+        timestampA: previousTimestamp,
+        timestampB: currentScreenshot.timestamp,
+      });
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  /** Combines the previous capture and this one; the collage opens as a new capture. */
+  const collageWithPrevious = async () => {
+    if (!currentScreenshot || previousTimestamp === null) return;
+    setIsLoading(true);
+    try {
+      const config = await invoke('get_config');
+      await invoke('compose_collage', {
+        timestamps: [previousTimestamp, currentScreenshot.timestamp],
+        layout: config.collage,
+      });
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const beautifyScreenshot = async () => {
+    if (!currentScreenshot) return;
+    setIsLoading(true);
+    try {
+      const config = await invoke('get_config');
+      await invoke('beautify_screenshot', {
+        timestamp: currentScreenshot.timestamp,
+        options: config.beautify,
+      });
+      setCurrentScreenshot({ ...currentScreenshot, image_url: refreshed(currentScreenshot.image_url) });
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  /** Hides sensitive text in the capture; returns how many areas were hidden. */
+  const autoRedactScreenshot = async (): Promise<number> => {
+    if (!currentScreenshot) return 0;
+    setIsLoading(true);
+    try {
+      const { count } = await invoke('auto_redact', { timestamp: currentScreenshot.timestamp });
+      if (count > 0) {
+        setCurrentScreenshot({ ...currentScreenshot, image_url: refreshed(currentScreenshot.image_url) });
+      }
+      return count;
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const roundScreenshotCorners = async () => {
+    if (!currentScreenshot) return;
+    setIsLoading(true);
+    try {
+      const config = await invoke('get_config');
+      await invoke('apply_corner_radius', {
+        timestamp: currentScreenshot.timestamp,
+        radius: config.corners.radius,
+        border: config.corners.border,
+      });
+      setCurrentScreenshot({ ...currentScreenshot, image_url: refreshed(currentScreenshot.image_url) });
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const translateScreenshot = async (): Promise<string> => {
+    if (!currentScreenshot) return '';
+    setIsLoading(true);
+    try {
+      const config = await invoke('get_config');
+      return await invoke('translate_text', {
+        timestamp: currentScreenshot.timestamp,
+        targetLang: config.translation.target_language,
+      });
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
   const testScreenshot = async () => {
     setIsLoading(true);
     try {
@@ -107,12 +230,21 @@ export const useScreenshot = () => {
 
   return {
     currentScreenshot,
+    previousTimestamp,
     isLoading,
     saveScreenshot,
     copyScreenshot,
     deleteScreenshot,
     closePopup,
     openEditor,
+    translateScreenshot,
+    analyzeScreenshot,
+    detectCodes,
+    diffWithPrevious,
+    collageWithPrevious,
+    beautifyScreenshot,
+    roundScreenshotCorners,
+    autoRedactScreenshot,
     testScreenshot,
   };
 };
@@ -1,4 +1,28 @@
-import type { TauriCommand, TauriEvent } from '@/types';
+import type { SnippErrorCode, TauriCommand, TauriEvent } from '@/types';
+
+/** A rejected command. `String(err)` is just the message, as before codes. */
+export class SnippError extends Error {
+  constructor(public readonly code: SnippErrorCode, message: string) {
+    super(message);
+    this.name = 'SnippError';
+  }
+
+  toString(): string {
+    return this.message;
+  }
+}
+
+/** True when `err` is a capture or picker the user dismissed. */
+export const isCancelled = (err: unknown): boolean =>
+  err instanceof SnippError && err.code === 'cancelled';
+
+const toSnippError = (err: unknown): unknown => {
+  if (err && typeof err === 'object' && 'code' in err && 'message' in err) {
+    const { code, message } = err as { code: SnippErrorCode; message: string };
+    return new SnippError(code, message);
+  }
+  return err;
+};
 
 export const invoke = async <T extends keyof TauriCommand>(
   command: T,
@@ -7,7 +31,11 @@ export const invoke = async <T extends keyof TauriCommand>(
   if (!window.__TAURI__?.core) {
     throw new Error('Tauri API not available');
   }
-  return window.__TAURI__.core.invoke(command, args);
+  try {
+    return await window.__TAURI__.core.invoke(command, args);
+  } catch (err) {
+    throw toSnippError(err);
+  }
 };
 
 export const listen = async <T extends keyof TauriEvent>(
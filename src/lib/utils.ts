@@ -17,4 +17,7 @@ export function debugError(...args: unknown[]): void {
   if (DEBUG_ENABLED) {
     console.error(...args);
   }
-}
\ No newline at end of file
+}
+
+// Edits keep a capture's URL, so a new query makes the webview fetch it again.
+export const refreshed = (imageUrl: string) => `${imageUrl.split('?')[0]}?v=${Date.now()}`;
@@ -0,0 +1,92 @@
+import React, { useEffect } from 'react'
+import ReactDOM from 'react-dom/client'
+import { Copy, Download, Pencil, X } from 'lucide-react'
+import { useScreenshot } from '@/hooks/useScreenshot'
+import '@/styles.css'
+
+// Compact preview under the menu bar icon: the latest capture with the
+// three actions people reach for most.
+function TrayPopoverApp() {
+  const { currentScreenshot, isLoading, saveScreenshot, copyScreenshot, openEditor, closePopup } = useScreenshot();
+
+  useEffect(() => {
+    if (!currentScreenshot) return;
+    const autoDismissTimer = setTimeout(closePopup, 5000);
+    const cancelTimer = () => clearTimeout(autoDismissTimer);
+    document.addEventListener('pointerdown', cancelTimer, { once: true });
+    return () => {
+      clearTimeout(autoDismissTimer);
+      document.removeEventListener('pointerdown', cancelTimer);
+    };
+  }, [currentScreenshot, closePopup]);
+
+  useEffect(() => {
+    const handleKeyDown = (e: KeyboardEvent) => {
+      if (e.key === 'Escape') closePopup();
+    };
+    document.addEventListener('keydown', handleKeyDown);
+    return () => document.removeEventListener('keydown', handleKeyDown);
+  }, [closePopup]);
+
+  if (!currentScreenshot) {
+    return (
+      <div className="w-screen h-screen flex items-center justify-center bg-transparent">
+        <div className="text-muted-foreground">Loading screenshot...</div>
+      </div>
+    );
+  }
+
+  const handleSave = async () => {
+    await saveScreenshot();
+    setTimeout(closePopup, 200);
+  };
+
+  const handleCopy = async () => {
+    await copyScreenshot();
+    setTimeout(closePopup, 200);
+  };
+
+  const actions = [
+    { label: 'Copy', icon: Copy, onClick: handleCopy },
+    { label: 'Save', icon: Download, onClick: handleSave },
+    { label: 'Edit', icon: Pencil, onClick: openEditor },
+  ];
+
+  return (
+    <div className="w-screen h-screen p-2 bg-transparent">
+      <div className="h-full flex flex-col gap-2 p-2 rounded-xl bg-black/85 text-white shadow-2xl animate-in fade-in duration-150 motion-reduce:animate-none">
+        <div className="flex items-center gap-2 text-xs">
+          <span className="flex-1 truncate text-white/70">{currentScreenshot.filename}</span>
+          <button onClick={closePopup} className="text-white/60 hover:text-white" aria-label="Close" title="Close">
+            <X size={14} />
+          </button>
+        </div>
+        <img
+          src={currentScreenshot.image_url}
+          alt="Latest capture"
+          className="flex-1 min-h-0 w-full object-contain rounded-md bg-white/5"
+          draggable={false}
+        />
+        <div className="flex gap-2">
+          {actions.map(({ label, icon: Icon, onClick }) => (
+            <button
+              key={label}
+              onClick={onClick}
+              disabled={isLoading}
+              className="flex-1 flex items-center justify-center gap-1 py-1 rounded-full bg-white text-black text-xs font-medium hover:bg-white/90 disabled:opacity-50"
+            >
+              <Icon size={12} />
+              {label}
+            </button>
+          ))}
+        </div>
+      </div>
+    </div>
+  );
+}
+
+ReactDOM.createRoot(document.getElementById('root')!).render(
+  <React.StrictMode>
+    <TrayPopoverApp />
+  </React.StrictMode>,
+)
@@ -3,22 +3,54 @@ import ReactDOM from 'react-dom/client'
 import { ScreenshotPreview } from '@/components/ScreenshotPreview'
 import { useScreenshot } from '@/hooks/useScreenshot'
 import { invoke } from '@/lib/tauri'
+import type { DetectedCode, Plugin, PluginOutput, ScreenshotDiff, SizeReport } from '@/types'
 import '@/styles.css'
 
+const formatBytes = (bytes: number) =>
+  bytes >= 1024 * 1024 ? `${(bytes / 1024 / 1024).toFixed(1)} MB` : `${Math.max(1, Math.round(bytes / 1024))} KB`
+
 function PopupApp() {
   const {
     currentScreenshot,
+    previousTimestamp,
     saveScreenshot,
     copyScreenshot,
     deleteScreenshot,
     openEditor,
+    translateScreenshot,
+    analyzeScreenshot,
+    detectCodes,
+    diffWithPrevious,
+    collageWithPrevious,
+    beautifyScreenshot,
+    roundScreenshotCorners,
+    autoRedactScreenshot,
     closePopup
   } = useScreenshot();
 
   const [dragFilePath, setDragFilePath] = useState<string | null>(null);
+  const [translation, setTranslation] = useState<string | null>(null);
+  const [note, setNote] = useState<string | null>(null);
+  const [sizes, setSizes] = useState<SizeReport | string | null>(null);
+  const [codes, setCodes] = useState<DetectedCode[] | string | null>(null);
+  const [diff, setDiff] = useState<ScreenshotDiff | string | null>(null);
+  const [plugins, setPlugins] = useState<Plugin[]>([]);
+  const [pluginResult, setPluginResult] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke('list_plugins', { context: 'popup' })
+      .then(setPlugins)
+      .catch((err) => console.error('Failed to load plugins:', err));
+  }, []);
 
   useEffect(() => {
     console.log('PopupApp mounted, currentScreenshot:', currentScreenshot);
+    setTranslation(null);
+    setNote(null);
+    setSizes(null);
+    setCodes(null);
+    setDiff(null);
+    setPluginResult(null);
   }, [currentScreenshot]);
 
   useEffect(() => {
@@ -48,7 +80,7 @@ function PopupApp() {
   }, [currentScreenshot]);
 
   useEffect(() => {
-    if (!currentScreenshot) return;
+    if (!currentScreenshot || translation !== null || note !== null || sizes !== null || codes !== null || diff !== null || pluginResult !== null) return;
     const autoDismissTimer = setTimeout(() => {
       closePopup();
     }, 5000);
@@ -58,21 +90,25 @@ function PopupApp() {
       clearTimeout(autoDismissTimer);
       document.removeEventListener('pointerdown', cancelTimer);
     };
-  }, [currentScreenshot, closePopup]);
+  }, [currentScreenshot, translation, note, sizes, codes, diff, pluginResult, closePopup]);
 
   useEffect(() => {
     const handleKeyDown = (e: KeyboardEvent) => {
       if (e.key === 'Escape') {
+        if (note !== null) {
+          setNote(null);
+          return;
+        }
         closePopup();
       }
     };
 
     document.addEventListener('keydown', handleKeyDown);
     return () => document.removeEventListener('keydown', handleKeyDown);
-  }, [closePopup]);
+  }, [note, closePopup]);
 
-  const handleSave = async () => {
-    await saveScreenshot();
+  const handleSave = async (withNote?: string) => {
+    await saveScreenshot(withNote);
     setTimeout(closePopup, 200);
   };
 
@@ -81,6 +117,106 @@ function PopupApp() {
     setTimeout(closePopup, 200);
   };
 
+  const handleTranslate = async () => {
+    try {
+      setTranslation(await translateScreenshot());
+    } catch (err) {
+      setTranslation(String(err));
+      console.error('Failed to translate screenshot:', err);
+    }
+  };
+
+  const handleAnalyze = async () => {
+    try {
+      setSizes(await analyzeScreenshot());
+    } catch (err) {
+      setSizes(String(err));
+      console.error('Failed to analyze screenshot:', err);
+    }
+  };
+
+  const handleDetectCodes = async () => {
+    try {
+      const detected = await detectCodes();
+      setCodes(detected.length > 0 ? detected : 'No QR codes or barcodes found');
+    } catch (err) {
+      setCodes(String(err));
+      console.error('Failed to detect codes:', err);
+    }
+  };
+
+  const handleDiff = async () => {
+    try {
+      setDiff(await diffWithPrevious());
+    } catch (err) {
+      setDiff(String(err));
+      console.error('Failed to diff screenshots:', err);
+    }
+  };
+
+  // The collage arrives as a new capture, which replaces the preview
+  const handleCollage = async () => {
+    try {
+      await collageWithPrevious();
+    } catch (err) {
+      setPluginResult(String(err));
+      console.error('Failed to compose collage:', err);
+    }
+  };
+
+  const handleCopyCode = async (payload: string) => {
+    try {
+      await navigator.clipboard.writeText(payload);
+      setTimeout(closePopup, 200);
+    } catch (err) {
+      console.error('Failed to copy code:', err);
+    }
+  };
+
+  const handleBeautify = async () => {
+    try {
+      await beautifyScreenshot();
+    } catch (err) {
+      setPluginResult(String(err));
+      console.error('Failed to beautify screenshot:', err);
+    }
+  };
+
+  const handleRoundCorners = async () => {
+    try {
+      await roundScreenshotCorners();
+    } catch (err) {
+      setPluginResult(String(err));
+      console.error('Failed to round corners:', err);
+    }
+  };
+
+  // A redacted capture replaces the preview, which is feedback enough
+  const handleAutoRedact = async () => {
+    try {
+      if (await autoRedactScreenshot() === 0) {
+        setPluginResult('Nothing sensitive found');
+      }
+    } catch (err) {
+      setPluginResult(String(err));
+      console.error('Failed to redact screenshot:', err);
+    }
+  };
+
+  const handlePlugin = async (plugin: Plugin) => {
+    if (!currentScreenshot) return;
+    try {
+      const output: PluginOutput = await invoke('run_plugin', {
+        pluginId: plugin.id,
+        timestamp: currentScreenshot.timestamp,
+      });
+      setPluginResult([output.message, output.url].filter(Boolean).join('\n') || `${plugin.name} finished`);
+    } catch (err) {
+      setPluginResult(String(err));
+      console.error('Plugin failed:', err);
+    }
+  };
+
   const handleDelete = async () => {
     await deleteScreenshot();
     setTimeout(closePopup, 200);
@@ -97,13 +233,150 @@ function PopupApp() {
   return (
     <div className="fixed bottom-4 left-4 bg-transparent">
       <ScreenshotPreview
-        imageUrl={`data:image/png;base64,${currentScreenshot.base64_image}`}
+        imageUrl={currentScreenshot.image_url}
         dragFilePath={dragFilePath ?? undefined}
-        onSave={handleSave}
+        onSave={() => handleSave()}
         onCopy={handleCopy}
         onDelete={handleDelete}
         onEdit={openEditor}
+        onTranslate={handleTranslate}
+        onNote={() => setNote('')}
+        onAnalyze={handleAnalyze}
+        onDetectCodes={handleDetectCodes}
+        onBeautify={handleBeautify}
+        onRoundCorners={handleRoundCorners}
+        onRedact={handleAutoRedact}
+        onDiff={previousTimestamp !== null ? handleDiff : undefined}
+        onCollage={previousTimestamp !== null ? handleCollage : undefined}
+        plugins={plugins}
+        onPlugin={handlePlugin}
       />
+      {pluginResult !== null && (
+        <div
+          className="absolute inset-0 z-40 overflow-y-auto p-3 rounded-2xl bg-black/85 text-white text-xs whitespace-pre-wrap break-all select-text cursor-pointer"
+          onClick={() => setPluginResult(null)}
+          title="Click to dismiss"
+        >
+          {pluginResult}
+        </div>
+      )}
+      {translation !== null && (
+        <div
+          className="absolute inset-0 z-40 overflow-y-auto p-3 rounded-2xl bg-black/85 text-white text-xs whitespace-pre-wrap select-text cursor-pointer"
+          onClick={() => setTranslation(null)}
+          title="Click to dismiss"
+        >
+          {translation}
+        </div>
+      )}
+      {sizes !== null && (
+        <div
+          className="absolute inset-0 z-40 flex flex-col justify-center gap-1 p-3 rounded-2xl bg-black/85 text-white text-xs cursor-pointer"
+          onClick={() => setSizes(null)}
+          title="Click to dismiss"
+        >
+          {typeof sizes === 'string' ? (
+            sizes
+          ) : (
+            <>
+              <div className="text-white/60 mb-1">
+                {sizes.width} x {sizes.height}, now {formatBytes(sizes.original_bytes)}
+              </div>
+              {sizes.sizes.map((size) => (
+                <div key={size.format} className="flex justify-between">
+                  <span>
+                    {size.format.toUpperCase()}
+                    {size.lossless ? '' : ' q85'}
+                    {size.format === sizes.recommended ? ' *' : ''}
+                  </span>
+                  <span>{formatBytes(size.bytes)}</span>
+                </div>
+              ))}
+              <div className="text-white/60 mt-1">* smallest without visible loss</div>
+            </>
+          )}
+        </div>
+      )}
+      {codes !== null && (
+        <div
+          className="absolute inset-0 z-40 flex flex-col gap-2 overflow-y-auto p-3 rounded-2xl bg-black/85 text-white text-xs cursor-pointer"
+          onClick={() => setCodes(null)}
+          title="Click to dismiss"
+        >
+          {typeof codes === 'string' ? (
+            codes
+          ) : (
+            codes.map((code) => (
+              <div key={code.payload} className="flex flex-col gap-1">
+                <span className="text-white/60">{code.symbology}</span>
+                <span className="break-all select-text">{code.payload}</span>
+                <button
+                  onClick={(e) => {
+                    e.stopPropagation();
+                    handleCopyCode(code.payload);
+                  }}
+                  className="self-start px-3 py-0.5 bg-white text-black rounded-full text-[11px] font-medium hover:bg-white/90"
+                >
+                  {code.is_url ? 'Copy link' : 'Copy'}
+                </button>
+              </div>
+            ))
+          )}
+        </div>
+      )}
+      {diff !== null && (
+        <div
+          className="absolute inset-0 z-40 flex flex-col gap-2 p-3 rounded-2xl bg-black/85 text-white text-xs cursor-pointer"
+          onClick={() => setDiff(null)}
+          title="Click to dismiss"
+        >
+          {typeof diff === 'string' ? (
+            diff
+          ) : (
+            <>
+              <img
+                src={`data:image/png;base64,${diff.diff_image}`}
+                alt=""
+                className="flex-1 min-h-0 object-contain rounded"
+              />
+              <div className="text-white/60">
+                {diff.changed_pixels === 0
+                  ? 'No changes since the previous capture'
+                  : `${diff.changed_percent.toFixed(2)}% changed since the previous capture`}
+              </div>
+            </>
+          )}
+        </div>
+      )}
+      {note !== null && (
+        <form
+          className="absolute inset-0 z-40 flex flex-col gap-2 p-3 rounded-2xl bg-black/85"
+          onSubmit={(e) => {
+            e.preventDefault();
+            handleSave(note);
+          }}
+        >
+          <textarea
+            autoFocus
+            value={note}
+            onChange={(e) => setNote(e.target.value)}
+            onKeyDown={(e) => {
+              if (e.key === 'Enter' && !e.shiftKey) {
+                e.preventDefault();
+                handleSave(note);
+              }
+            }}
+            placeholder="Note, e.g. repro of crash #123"
+            className="flex-1 resize-none rounded-md bg-white/10 p-2 text-xs text-white placeholder:text-white/50 outline-none"
+          />
+          <button
+            type="submit"
+            className="px-3 py-1 bg-white text-black rounded-full text-xs font-medium hover:bg-white/90"
+          >
+            Save with note
+          </button>
+        </form>
+      )}
     </div>
   );
 }
@@ -0,0 +1,10 @@
+import React from 'react'
+import ReactDOM from 'react-dom/client'
+import { QuickSearchPalette } from '@/components/search/QuickSearchPalette'
+import '@/styles.css'
+
+ReactDOM.createRoot(document.getElementById('root')!).render(
+  <React.StrictMode>
+    <QuickSearchPalette />
+  </React.StrictMode>,
+)
@@ -0,0 +1,10 @@
+import React from 'react'
+import ReactDOM from 'react-dom/client'
+import { RegionSelector } from '@/components/selector/RegionSelector'
+import '@/styles.css'
+
+ReactDOM.createRoot(document.getElementById('root')!).render(
+  <React.StrictMode>
+    <RegionSelector />
+  </React.StrictMode>,
+)
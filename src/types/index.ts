@@ -1,35 +1,547 @@
 export interface ScreenshotData {
-  base64_image: string;
+  /** snipp://screenshot/<timestamp>, served from the capture cache. */
+  image_url: string;
   filename: string;
   timestamp: number;
   file_path: string | null;
 }
 
 export interface EditorData {
-  base64_image: string;
+  /** The cached capture over the `snipp://` protocol. */
+  image_url: string;
   filename?: string;
   timestamp: number;
   file_path?: string | null;
 }
 
+export interface CaptureRegion {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
+export interface MeasurePoint {
+  x: number;
+  y: number;
+}
+
+export interface Measurement {
+  width: number;
+  height: number;
+  distance: number;
+  angle: number;
+  scale_factor: number;
+  width_points: number;
+  height_points: number;
+  distance_points: number;
+}
+
+export type ClickHighlight = 'off' | 'ripple' | 'spotlight';
+
+export interface RegionPreset {
+  name: string;
+  region: CaptureRegion;
+  /** Replaces `click_highlight` for recordings and GIF captures of this preset. */
+  click_highlight?: ClickHighlight | null;
+}
+
+export interface RecordingInfo {
+  id: number;
+  file_path: string;
+  region: CaptureRegion | null;
+  duration_ms: number;
+}
+
+export interface RecordingStatus {
+  active: boolean;
+  paused: boolean;
+  id: number | null;
+  elapsed_ms: number;
+  recorded_ms: number;
+}
+
+/** What happens to the display's color profile when a capture is re-encoded. */
+export type ColorProfileMode = 'preserve' | 'convert_to_srgb';
+
+/** `embed` writes capture time, display, app and Snipp version into PNG text/EXIF. */
+export type MetadataMode = 'keep' | 'strip' | 'embed';
+
+export type LogLevel = 'error' | 'warn' | 'info' | 'debug' | 'trace';
+
+export type OutputFormat = 'png' | 'jpeg' | 'webp' | 'tiff' | 'avif' | 'heic';
+
 export interface AppConfig {
   default_save_location: string;
+  additional_save_locations: string[];
+  /** Network share folder mirroring every save; empty when off. */
+  mirror_location: string;
+  /** Rhai script with on_capture/on_save/on_upload hooks; empty disables them. */
+  hook_script: string;
+  watch_folder: WatchFolder;
+  write_sidecar: boolean;
+  color_profile: ColorProfileMode;
+  metadata: MetadataMode;
+  secure_delete: boolean;
+  /** Memory unsaved captures may use before the least recently used move to disk. */
+  cache_limit_mb: number;
+  /** Least severe messages written to the log file. */
+  log_level: LogLevel;
+  /** strftime pattern; `{seq}` is a counter that restarts daily. */
+  filename_template: string;
+  /** Anything but PNG is saved as sRGB. */
+  output_format: OutputFormat;
+  /** 1-100, used for JPEG. */
+  jpeg_quality: number;
+  /** 1-100, used for AVIF. */
+  avif_quality: number;
+  /** 1-100, used for HEIC. */
+  heic_quality: number;
+  window_shadow: boolean;
+  /** Resize Retina captures to their size in points. */
+  downscale_retina: boolean;
+  /** Seconds counted down before a timed capture. */
+  default_capture_delay: number;
+  /** Select areas in Snipp's frozen-frame overlay instead of the system picker. */
+  selection_overlay: boolean;
   capture_hotkey: string;
+  import_clipboard_hotkey: string;
+  repeat_region_hotkey: string;
+  /** Kept by the backend; updates from settings don't change it. */
+  last_region: CaptureRegion | null;
+  capture_text_hotkey: string;
+  quick_search_hotkey: string;
+  index_capture_text: boolean;
   auto_copy_after_capture: boolean;
+  show_capture_popup: boolean;
+  /** Preview captures under the menu bar icon instead of the corner popup. */
+  tray_popover: boolean;
+  restore_focus_after_capture: boolean;
+  announce_actions: boolean;
   auto_copy_after_edit: boolean;
+  region_presets: RegionPreset[];
+  ffmpeg_path: string;
+  record_microphone: boolean;
+  microphone_device: string;
+  record_system_audio: boolean;
+  webcam_overlay: WebcamOverlay;
+  click_highlight: ClickHighlight;
+  recording_quality: RecordingQuality;
+  recording_limits: RecordingLimits;
+  animation_export: AnimationOptions;
+  burst_animation: BurstOptions;
+  gif_capture: GifCaptureOptions;
+  editor_grid: EditorGrid;
+  text_annotation: TextDefaults;
+  annotation_styles: AnnotationStyle[];
+  regression_threshold_percent: number;
+  retention: RetentionPolicy;
+  translation: TranslationSettings;
+  stamp: StampSettings;
+  /** Framing applied by the popup's Beautify action. */
+  beautify: BeautifyOptions;
+  /** Radius and border used by the popup's Round Corners action. */
+  corners: CornerOptions;
+  /** How the editor's Redact tool hides an area. */
+  redaction: RedactStyle;
+  /** What the popup's Redact action looks for. */
+  auto_redact: AutoRedactSettings;
+  /** How the Collage action lays out captures. */
+  collage: CollageLayout;
+  upload: UploadSettings;
+  onboarding_completed: boolean;
+}
+
+export type PermissionKind = 'screen_recording' | 'accessibility';
+
+export interface OnboardingStatus {
+  completed: boolean;
+  screen_recording: boolean;
+  accessibility: boolean;
+  save_location: string;
+  save_location_writable: boolean;
+  capture_hotkey: string;
+}
+
+export type CheckStatus = 'pass' | 'warn' | 'fail';
+
+export interface DiagnosticCheck {
+  name: string;
+  status: CheckStatus;
+  detail: string;
+}
+
+export interface DiagnosticsReport {
+  version: string;
+  os: string;
+  /** The worst status among the checks. */
+  status: CheckStatus;
+  checks: DiagnosticCheck[];
+}
+
+/** Screenshot settings changed in macOS; unset ones are null. */
+export interface ScreencapturePrefs {
+  location: string | null;
+  format: string | null;
+  window_shadow: boolean | null;
+}
+
+/** Imports screenshots taken outside Snipp; an empty folder means the macOS screenshot location. */
+export interface WatchFolder {
+  enabled: boolean;
+  folder: string;
+}
+
+/** Files and bytes in the thumbnail cache. */
+export interface CacheUsage {
+  files: number;
+  bytes: number;
+}
+
+export interface RetentionPolicy {
+  enabled: boolean;
+  days: number;
+}
+
+export type Corner = 'top_left' | 'top_right' | 'bottom_left' | 'bottom_right';
+
+export interface StampSettings {
+  enabled: boolean;
+  corner: Corner;
+  format: string;
+  label: string;
+}
+
+/** Colors are `#rrggbb`; gradients run from the top-left corner. */
+export type BeautifyBackground =
+  | { kind: 'solid'; color: string }
+  | { kind: 'gradient'; from: string; to: string };
+
+/** Sizes are in image pixels; a `shadow` of 0 leaves it out. */
+export interface BeautifyOptions {
+  padding: number;
+  background: BeautifyBackground;
+  corner_radius: number;
+  shadow: number;
+}
+
+/** Drawn just inside the rounded edge; `width` is 1-4 pixels. */
+export interface Border {
+  width: number;
+  color: string;
+}
+
+export interface CornerOptions {
+  radius: number;
+  border: Border | null;
+}
+
+export type CollageArrangement = { kind: 'grid'; columns: number } | { kind: 'strip' };
+
+/** `gutter` is in image pixels and also surrounds the edge. */
+export interface CollageLayout {
+  arrangement: CollageArrangement;
+  gutter: number;
+  background: BeautifyBackground;
+}
+
+export type TranslationProvider = 'apple' | 'api';
+
+export interface TranslationSettings {
+  provider: TranslationProvider;
+  target_language: string;
+  shortcut_name: string;
+  api_url: string;
+  api_key: string;
+}
+
+export type UploadProvider = 'zipline' | 'chibisafe' | 'lutim' | 'custom';
+
+export interface UploadHeader {
+  name: string;
+  value: string;
+}
+
+export interface CustomUploader {
+  endpoint: string;
+  file_field: string;
+  headers: UploadHeader[];
+  form: UploadHeader[];
+  /** Share link built from the JSON response, e.g. "{data.link}". */
+  url_template: string;
+}
+
+export interface ShortenerSettings {
+  enabled: boolean;
+  /** GET request with `{url}` standing in for the encoded link. */
+  request_url: string;
+  headers: UploadHeader[];
+  /** Short link built from a JSON response; empty takes the body as is. */
+  url_template: string;
+}
+
+export interface UploadSettings {
+  enabled: boolean;
+  provider: UploadProvider;
+  server_url: string;
+  token: string;
+  upload_after_save: boolean;
+  max_concurrent: number;
+  /** Uploads started per minute against one host; 0 means no limit. */
+  uploads_per_minute: number;
+  /** Upload in whichever format is smallest without visible loss. */
+  auto_format: boolean;
+  custom: CustomUploader;
+  shortener: ShortenerSettings;
+}
+
+export interface UploadOutcome {
+  file_path: string;
+  url: string | null;
+  error: string | null;
+}
+
+export interface BackupSummary {
+  path: string;
+  config_files: number;
+  thumbnails: number;
+  screenshots: number;
+}
+
+export interface ExpiredFile {
+  path: string;
+  size_bytes: number;
+  modified: string;
+}
+
+export interface RegressionResult {
+  preset: string;
+  baseline_path: string;
+  changed_pixels: number;
+  total_pixels: number;
+  changed_percent: number;
+  threshold_percent: number;
+  passed: boolean;
+}
+
+export interface AnnotationStyle {
+  name: string;
+  color: string;
+  stroke_width: number;
+  font_family: string;
+  font_size: number;
+  text_color: string;
+  shadow: boolean;
+}
+
+export interface TextDefaults {
+  font_family: string;
+  font_size: number;
+  color: string;
+}
+
+export interface EditorGrid {
+  show_grid: boolean;
+  size: number;
+  snap_to_grid: boolean;
+  snap_to_guides: boolean;
+}
+
+export type AnimationFormat = 'gif' | 'webp';
+
+export interface AnimationOptions {
+  fps: number;
+  max_width: number;
+  max_colors: number;
+  dither: boolean;
+  quality: number;
+}
+
+export type BurstFormat = 'gif' | 'apng';
+
+/** Settings for stitching still captures into an animation. */
+export interface BurstOptions {
+  delay_ms: number;
+  /** 0 keeps the first capture's width. */
+  max_width: number;
+}
+
+/** Settings for capturing a screen region straight to a GIF. */
+export interface GifCaptureOptions {
+  fps: number;
+  /** Capture stops on its own after this long. */
+  max_seconds: number;
+  /** 0 keeps the region's captured width. */
+  max_width: number;
+}
+
+export type ShareFormat = 'png' | 'jpeg' | 'webp';
+
+export interface FormatSize {
+  format: ShareFormat;
+  bytes: number;
+  lossless: boolean;
+}
+
+/** What a capture would weigh in each format, smallest first. */
+export interface SizeReport {
+  original_bytes: number;
+  width: number;
+  height: number;
+  has_transparency: boolean;
+  sizes: FormatSize[];
+  recommended: ShareFormat;
+}
+
+/** A decoded QR code or barcode; the box is in fractions of the image. */
+export interface DetectedCode {
+  payload: string;
+  symbology: string;
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+  is_url: boolean;
+}
+
+/** A rectangle of image pixels. */
+export interface PixelRect {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
+export type FlipAxis = 'horizontal' | 'vertical';
+
+export interface AutoRedactSettings {
+  emails: boolean;
+  api_keys: boolean;
+  credit_cards: boolean;
+  /** Extra regular expressions. */
+  patterns: string[];
+}
+
+export interface AutoRedaction {
+  /** How many areas were hidden; 0 leaves the capture as it was. */
+  count: number;
+}
+
+/** A mosaic of `block_size` pixels (4-64) or a Gaussian blur of `sigma` (4-50). */
+export type RedactStyle =
+  | { kind: 'pixelate'; block_size: number }
+  | { kind: 'blur'; sigma: number };
+
+/**
+ * An editor annotation in image pixels, drawn by `render_annotations`.
+ * Strokes are centered on the outline; `shadow` adds the editor's drop shadow.
+ */
+export type AnnotationShape = (
+  | { kind: 'rect'; x: number; y: number; width: number; height: number; color: string; stroke_width: number; filled?: boolean }
+  | { kind: 'ellipse'; cx: number; cy: number; rx: number; ry: number; color: string; stroke_width: number }
+  | { kind: 'line' | 'arrow'; from: [number, number]; to: [number, number]; color: string; stroke_width: number }
+  | { kind: 'path'; points: [number, number][]; color: string; stroke_width: number }
+  | { kind: 'text'; x: number; y: number; text: string; color: string; font_size: number; font_family?: string }
+) & { shadow?: boolean };
+
+/** A color as hex, RGB and HSL (degrees, percent, percent). */
+export interface ColorSample {
+  hex: string;
+  rgb: [number, number, number];
+  hsl: [number, number, number];
+}
+
+/** One of a capture's dominant colors. */
+export interface PaletteColor extends ColorSample {
+  /** Fraction of the capture's opaque pixels nearest this color. */
+  share: number;
+}
+
+export type PluginContext = 'popup' | 'history';
+
+/** An action from the plugins folder. */
+export interface Plugin {
+  id: string;
+  name: string;
+  description: string;
+  contexts: PluginContext[];
+}
+
+export interface PluginOutput {
+  message: string | null;
+  url: string | null;
+}
+
+export interface RecordingLimits {
+  max_duration_secs: number;
+  max_file_size_mb: number;
+}
+
+export interface RecordingQuality {
+  frame_rate: number;
+  scale: number;
+  crf: number;
+  bitrate_kbps: number;
+}
+
+export type WebcamPosition = 'top_left' | 'top_right' | 'bottom_left' | 'bottom_right';
+
+export interface WebcamOverlay {
+  enabled: boolean;
+  device: string;
+  position: WebcamPosition;
+  shape: 'circle' | 'square';
+  size: number;
+}
+
+export interface AudioLevel {
+  recording_id: number;
+  level_db: number;
 }
 
 export interface TauriCommand {
-  save_to_disk: (args: { timestamp: number }) => Promise<string>;
+  save_to_disk: (args: { timestamp: number; note?: string }) => Promise<string>;
   copy_to_clipboard: (args: { timestamp: number }) => Promise<void>;
   delete_from_memory: (args: { timestamp: number }) => Promise<void>;
   close_popup_window: () => Promise<void>;
   close_editor_window: () => Promise<void>;
-  open_editor_window: (args: { timestamp: number }) => Promise<void>;
+  open_editor_window: (args: { timestamp: number; filePath?: string | null }) => Promise<void>;
   hide_window: () => Promise<void>;
   show_window: () => Promise<void>;
   capture_screenshot: () => Promise<ScreenshotData>;
   capture_full_screen: () => Promise<ScreenshotData>;
+  capture_with_delay: (args: { seconds?: number }) => Promise<ScreenshotData>;
+  capture_with_selector: () => Promise<ScreenshotData>;
+  capture_last_region: () => Promise<ScreenshotData>;
+  capture_browser_page: () => Promise<ScreenshotData>;
+  capture_region: (args: { region?: CaptureRegion; preset?: string }) => Promise<ScreenshotData>;
+  list_windows: (args: { thumbnails?: boolean }) => Promise<WindowThumbnail[]>;
+  list_capture_windows: (args: { thumbnails?: boolean }) => Promise<WindowThumbnail[]>;
+  capture_window: (args: { windowId: number }) => Promise<ScreenshotData>;
+  capture_scrolling: (args: { windowId: number }) => Promise<ScreenshotData>;
+  list_displays: () => Promise<DisplayInfo[]>;
+  capture_display: (args: { displayId: number }) => Promise<ScreenshotData>;
+  capture_all_displays: () => Promise<ScreenshotData>;
+  capture_gif: (args: { region?: CaptureRegion; preset?: string }) => Promise<ScreenshotData>;
+  stop_gif_capture: () => Promise<void>;
+  set_region_baseline: (args: { preset: string }) => Promise<string>;
+  clear_region_baseline: (args: { preset: string }) => Promise<void>;
+  list_region_baselines: () => Promise<string[]>;
+  measure_distance: (args: { start: MeasurePoint; end: MeasurePoint; scaleFactor?: number }) => Promise<Measurement>;
+  measure_on_screen: () => Promise<Measurement | null>;
+  capture_text: () => Promise<string>;
+  translate_text: (args: { timestamp: number; targetLang: string }) => Promise<string>;
+  export_searchable_pdf: (args: { base64Image: string; timestamp: number }) => Promise<string | null>;
+  export_asset_pair: (args: { base64Image: string; timestamp: number }) => Promise<string[] | null>;
+  start_guide: (args: { title: string }) => Promise<GuideSession>;
+  get_guide: () => Promise<GuideSession | null>;
+  set_guide_caption: (args: { index: number; caption: string }) => Promise<void>;
+  remove_guide_step: (args: { index: number }) => Promise<void>;
+  end_guide: () => Promise<void>;
+  export_guide: (args: { format: GuideFormat }) => Promise<string | null>;
+  export_markdown: (args: { filePaths: string[] }) => Promise<string | null>;
+  import_from_clipboard: () => Promise<ScreenshotData>;
+  import_image_file: (args: { path?: string }) => Promise<ScreenshotData | null>;
   get_config: () => Promise<AppConfig>;
   update_config: (args: { newConfig: AppConfig }) => Promise<void>;
   choose_save_location: () => Promise<string | null>;
@@ -37,21 +549,289 @@ export interface TauriCommand {
   copy_edited_screenshot: (args: { base64Image: string; timestamp: number }) => Promise<void>;
   prepare_drag_file: (args: { timestamp: number }) => Promise<string>;
   cleanup_drag_file: (args: { timestamp: number }) => Promise<void>;
-  get_recent_screenshots: () => Promise<Array<Record<string, unknown>>>;
+  get_recent_screenshots: () => Promise<RecentScreenshot[]>;
+  get_thumbnail_cache_usage: () => Promise<CacheUsage>;
+  purge_thumbnail_cache: () => Promise<CacheUsage>;
+  search_screenshots: (args: { query: string }) => Promise<RecentScreenshot[]>;
+  set_screenshot_note: (args: { filePath: string; note: string }) => Promise<void>;
+  toggle_quick_search: () => Promise<void>;
+  close_quick_search: () => Promise<void>;
+  quick_search: (args: { query: string }) => Promise<RecentScreenshot[]>;
+  open_screenshot: (args: { filePath: string }) => Promise<void>;
+  upload_screenshot: (args: { filePath: string }) => Promise<string>;
+  upload_screenshots: (args: { filePaths: string[] }) => Promise<UploadOutcome[]>;
   copy_screenshot_from_path: (args: { filePath: string }) => Promise<void>;
+  copy_path_to_clipboard: (args: { filePath: string }) => Promise<void>;
+  copy_file_to_clipboard: (args: { filePath: string }) => Promise<void>;
   open_in_finder: (args: { filePath: string }) => Promise<void>;
+  quick_look: (args: { filePath: string }) => Promise<void>;
   delete_screenshot: (args: { filePath: string }) => Promise<void>;
   close_recent_window: () => Promise<void>;
+  start_recording: (args: {
+    region?: CaptureRegion;
+    preset?: string;
+    systemAudio?: boolean;
+    quality?: RecordingQuality;
+  }) => Promise<RecordingInfo>;
+  pause_recording: () => Promise<RecordingStatus>;
+  resume_recording: () => Promise<RecordingStatus>;
+  stop_recording: () => Promise<RecordingInfo>;
+  get_recording_status: () => Promise<RecordingStatus>;
+  /** `filePath` finds recordings from earlier sessions through history. */
+  trim_recording: (args: { id: number; filePath?: string; startMs: number; endMs: number }) => Promise<RecordingInfo>;
+  convert_recording: (args: {
+    id: number;
+    filePath?: string;
+    format: AnimationFormat;
+    options?: AnimationOptions;
+  }) => Promise<string>;
+  analyze_capture: (args: { timestamp: number }) => Promise<SizeReport>;
+  pick_color: (args: { timestamp: number; x: number; y: number }) => Promise<ColorSample>;
+  extract_palette: (args: { timestamp: number; n: number }) => Promise<PaletteColor[]>;
+  detect_codes: (args: { timestamp: number }) => Promise<DetectedCode[]>;
+  beautify_screenshot: (args: { timestamp: number; options: BeautifyOptions }) => Promise<void>;
+  apply_corner_radius: (args: { timestamp: number; radius: number; border?: Border | null }) => Promise<void>;
+  crop_screenshot: (args: { timestamp: number; rect: PixelRect }) => Promise<void>;
+  rotate_screenshot: (args: { timestamp: number; degrees: number }) => Promise<void>;
+  flip_screenshot: (args: { timestamp: number; axis: FlipAxis }) => Promise<void>;
+  redact_region: (args: { timestamp: number; rect: PixelRect; style?: RedactStyle | null }) => Promise<void>;
+  auto_redact: (args: { timestamp: number }) => Promise<AutoRedaction>;
+  render_annotations: (args: { timestamp: number; shapes: AnnotationShape[] }) => Promise<void>;
+  save_annotations: (args: { filePath: string; shapes: AnnotationShape[] }) => Promise<void>;
+  load_annotations: (args: { filePath: string }) => Promise<AnnotationShape[]>;
+  check_hook_script: (args: { path: string }) => Promise<void>;
+  list_plugins: (args: { context?: PluginContext }) => Promise<Plugin[]>;
+  run_plugin: (args: { pluginId: string; filePath?: string; timestamp?: number }) => Promise<PluginOutput>;
+  animate_captures: (args: {
+    filePaths: string[];
+    format: BurstFormat;
+    options?: BurstOptions;
+  }) => Promise<string>;
+  list_audio_devices: () => Promise<string[]>;
+  list_camera_devices: () => Promise<string[]>;
+  list_fonts: () => Promise<string[]>;
+  preview_retention: (args: { days?: number }) => Promise<ExpiredFile[]>;
+  apply_retention: () => Promise<string[]>;
+  export_backup: (args: { includeScreenshots: boolean }) => Promise<BackupSummary | null>;
+  restore_backup: () => Promise<BackupSummary | null>;
+  get_onboarding_status: () => Promise<OnboardingStatus>;
+  request_permission: (args: { kind: PermissionKind }) => Promise<boolean>;
+  get_macos_screenshot_prefs: () => Promise<ScreencapturePrefs>;
+  run_diagnostics: () => Promise<DiagnosticsReport>;
+  get_recent_logs: (args: { lines: number }) => Promise<string[]>;
+  /** Resolves to the saved zip, or null if the save dialog was cancelled. */
+  export_diagnostics: () => Promise<string | null>;
+  complete_onboarding: (args: {
+    saveLocation?: string;
+    captureHotkey?: string;
+    windowShadow?: boolean;
+    outputFormat?: OutputFormat;
+  }) => Promise<void>;
+  compare_screenshots: (args: { a: string; b: string }) => Promise<void>;
+  close_compare_window: () => Promise<void>;
+  diff_screenshots: (args: { timestampA: number; timestampB: number }) => Promise<ScreenshotDiff>;
+  get_screenshot_metadata: (args: { filePath: string }) => Promise<ScreenshotMetadata | null>;
+  compose_collage: (args: { timestamps: number[]; filePaths?: string[]; layout: CollageLayout }) => Promise<ScreenshotData>;
+}
+
+export interface GuideStep {
+  file_path: string;
+  caption: string;
+}
+
+/** A step-by-step guide; while open, every saved capture becomes a step. */
+export interface GuideSession {
+  title: string;
+  steps: GuideStep[];
+}
+
+export type GuideFormat = 'markdown' | 'pdf';
+
+export interface RecentScreenshot {
+  file_path: string;
+  timestamp: string;
+  filename: string;
+  /** JPEG data URL; empty when the file is missing or only in the cloud. */
+  thumbnail: string;
+  regression: RegressionResult | null;
+  /** Per-folder results; empty when only one save folder was configured. */
+  destinations: DestinationResult[];
+  sync_status: SyncStatus;
+  note: string;
+  share_url: string | null;
+  /** Copy on the network share; null when mirroring was off. */
+  mirror: MirrorStatus | null;
+  /** Labels added by hook scripts. */
+  tags: string[];
+}
+
+export type MirrorStatus = 'queued' | 'mirrored';
+
+export type SyncStatus = 'local' | 'synced' | 'placeholder' | 'conflict' | 'missing';
+
+export interface DestinationResult {
+  folder: string;
+  file_path: string | null;
+  error: string | null;
+  sync_status: SyncStatus | null;
+}
+
+export interface ComparisonData {
+  before_path: string;
+  after_path: string;
+  before_image: string;
+  after_image: string;
+  blend_image: string;
+  width: number;
+  height: number;
+}
+
+/** The `name.png.json` sidecar of a saved capture, written when sidecars are on. */
+export interface ScreenshotMetadata {
+  timestamp: string;
+  width: number;
+  height: number;
+  source_app: string | null;
+  note: string;
+  text: string;
+  operations: string[];
+  /** Selected area in screen points; null for full-screen and window captures. */
+  region: CaptureRegion | null;
+  display_id: number | null;
+  display_name: string | null;
+  bundle_id: string | null;
+  window_title: string | null;
+}
+
+/** Changed pixels are red over a faded copy of the later capture. */
+export interface ScreenshotDiff {
+  diff_image: string;
+  width: number;
+  height: number;
+  changed_pixels: number;
+  total_pixels: number;
+  changed_percent: number;
+}
+
+export interface WindowInfo {
+  id: number;
+  app_name: string;
+  title: string;
+  bounds: CaptureRegion;
+}
+
+export interface WindowThumbnail extends WindowInfo {
+  /** JPEG data URL; null when the window couldn't be grabbed. */
+  thumbnail: string | null;
+}
+
+/** A connected screen; `id` is its `screencapture -D` number. */
+export interface DisplayInfo {
+  id: number;
+  name: string;
+  /** Resolution in pixels. */
+  width: number;
+  height: number;
+  scale_factor: number;
+  bounds: CaptureRegion;
+  primary: boolean;
+}
+
+export interface SelectorFrame {
+  base64_image: string;
+  scale_factor: number;
+  windows: WindowInfo[];
 }
 
+/** `code` of a rejected command, from `SnippError` on the Rust side. */
+export type SnippErrorCode =
+  | 'cancelled'
+  | 'permission_denied'
+  | 'not_found'
+  | 'disk_full'
+  | 'io'
+  | 'invalid_data'
+  | 'other';
+
+/** Lifecycle events carry `version`, bumped when a payload changes incompatibly. */
+interface VersionedEvent {
+  version: number;
+}
+
+export type CaptureKind = 'area' | 'full_screen' | 'region' | 'window' | 'display';
+
+export interface CaptureStartedEvent extends VersionedEvent {
+  timestamp: number;
+  kind: CaptureKind;
+}
+
+export interface CaptureCompletedEvent extends VersionedEvent {
+  timestamp: number;
+  kind: CaptureKind;
+  filename: string;
+  bytes: number;
+}
+
+export interface CaptureCancelledEvent extends VersionedEvent {
+  timestamp: number;
+  kind: CaptureKind;
+  reason: string;
+}
+
+export interface SaveCompletedEvent extends VersionedEvent {
+  timestamp: number | null;
+  file_path: string;
+  saved_paths: string[];
+}
+
+export interface CopyCompletedEvent extends VersionedEvent {
+  timestamp: number | null;
+  file_path: string | null;
+  content: 'image' | 'path' | 'file';
+}
+
+export type UploadProgressEvent = VersionedEvent & { file_path: string } & (
+    | { stage: 'started' | 'uploading' | 'shortening' }
+    | { stage: 'completed'; url: string }
+    | { stage: 'failed'; error: string }
+  );
+
 export interface TauriEvent {
   'screenshot-data': ScreenshotData;
   'editor-data': EditorData;
   'screenshot-saved': { path: string };
+  'screenshot-imported': { path: string };
   'screenshot-copied': {};
+  /** Seconds left before a timed capture; 0 as it fires. */
+  'capture-countdown': number;
+  'gif-capture-started': CaptureRegion;
+  /** Number of frames captured. */
+  'gif-capture-stopped': number;
   'screenshot-deleted': {};
   'popup-ready': {};
   'editor-ready': {};
+  'selector-ready': {};
+  'selector-frame': SelectorFrame;
+  'region-selected': { region: CaptureRegion | null };
+  'compare-ready': {};
+  'compare-data': ComparisonData;
+  'quick-search-ready': {};
+  'quick-search-opened': null;
+  'regression-checked': RegressionResult;
+  'guide-updated': GuideSession | null;
+  'recording-started': RecordingInfo;
+  'recording-stopped': RecordingInfo;
+  'recording-limit-reached': string;
+  'recording-paused': RecordingStatus;
+  'recording-resumed': RecordingStatus;
+  'recording-progress': RecordingStatus;
+  'recording-audio-level': AudioLevel;
+  'capture-started': CaptureStartedEvent;
+  'capture-completed': CaptureCompletedEvent;
+  'capture-cancelled': CaptureCancelledEvent;
+  'save-completed': SaveCompletedEvent;
+  'copy-completed': CopyCompletedEvent;
+  'upload-progress': UploadProgressEvent;
 }
 
 declare global {
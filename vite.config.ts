@@ -18,7 +18,11 @@ export default defineConfig({
       input: {
         index: resolve(__dirname, 'src/index.html'),
         popup: resolve(__dirname, 'src/popup.html'),
+        popover: resolve(__dirname, 'src/popover.html'),
         editor: resolve(__dirname, 'src/editor.html'),
+        selector: resolve(__dirname, 'src/selector.html'),
+        compare: resolve(__dirname, 'src/compare.html'),
+        search: resolve(__dirname, 'src/search.html'),
       },
     },
   },